@@ -9,8 +9,129 @@ export function loadProgram(newCode) {
     editor.gotoLine(Number.MAX_SAFE_INTEGER);
 }
 
+// Function to move the cursor to a 1-based line and column of the code and show it
+export function gotoPosition(line, col) {
+    editor.gotoLine(line, col - 1, true);
+    editor.focus();
+}
+
+// Function to insert text at a 1-based line and column of the code and move the cursor after it
+export function insertText(line, col, text) {
+    editor.session.insert({ row: line - 1, column: col - 1 }, text);
+    editor.gotoLine(line, col - 1 + text.length, true);
+    editor.focus();
+}
+
 // Uses the clipboard api to set the device's clipboard
 // From https://www.freecodecamp.org/news/copy-text-to-clipboard-javascript/
 export function setClipboard(newText) {
     navigator.clipboard.writeText(newText);
 }
+
+// Dark theme for the editor, which is made here because the bundled ace only has the light textmate theme
+const darkTheme = {
+    isDark: true,
+    cssClass: "ace-nexus-dark",
+    cssText: `
+.ace-nexus-dark .ace_gutter { background: #25282c; color: #8f908a; }
+.ace-nexus-dark { background-color: #1e1f22; color: #e6e6e6; }
+.ace-nexus-dark .ace_cursor { color: #f8f8f0; }
+.ace-nexus-dark .ace_marker-layer .ace_selection { background: #44475a; }
+.ace-nexus-dark .ace_marker-layer .ace_active-line { background: #2a2c30; }
+.ace-nexus-dark .ace_gutter-active-line { background-color: #2a2c30; }
+.ace-nexus-dark .ace_marker-layer .ace_bracket { border: 1px solid #8f908a; }
+.ace-nexus-dark .ace_print-margin { background: #3b3d41; }
+`
+};
+
+// Function to switch the editor between the light and dark themes
+export function setEditorTheme(isDark) {
+    editor.setTheme(isDark ? darkTheme : "ace/theme/textmate");
+}
+
+// Function to set the font size of the editor in pixels
+export function setEditorFontSize(fontSize) {
+    editor.setFontSize(fontSize);
+}
+
+// Function to turn line wrapping in the editor on or off
+export function setEditorWrap(wrap) {
+    editor.session.setUseWrapMode(wrap);
+}
+
+// Function to get the first selected line (starting at 1)
+export function getSelectionStartLine() {
+    return editor.getSelectionRange().start.row + 1;
+}
+
+// Function to get the last selected line (starting at 1)
+// A selection that ends at the start of a line does not include that line
+export function getSelectionEndLine() {
+    const range = editor.getSelectionRange();
+    if (range.end.column == 0 && range.end.row > range.start.row) {
+        return range.end.row;
+    }
+    return range.end.row + 1;
+}
+
+// Function to replace whole lines (starting at 1) in the editor and keep them selected
+export function replaceLines(startLine, endLine, text) {
+    const Range = ace.require("ace/range").Range;
+    const endColumn = editor.session.getLine(endLine - 1).length;
+    editor.session.replace(new Range(startLine - 1, 0, endLine - 1, endColumn), text);
+    editor.selection.setSelectionRange(new Range(startLine - 1, 0, endLine - 1, editor.session.getLine(endLine - 1).length));
+    editor.focus();
+}
+
+// Function to run the callback for Ctrl-/ instead of the default comment command of ace
+export function bindToggleComment(callback) {
+    editor.commands.addCommand({
+        name: "togglecomment",
+        bindKey: { win: "Ctrl-/", mac: "Command-/" },
+        exec: () => callback()
+    });
+}
+
+// Function to run the callback for Ctrl-Shift-P, which opens the command palette while typing in the editor
+export function bindCommandPalette(callback) {
+    editor.commands.addCommand({
+        name: "commandpalette",
+        bindKey: { win: "Ctrl-Shift-P", mac: "Command-Shift-P" },
+        exec: () => callback()
+    });
+}
+
+// Function to save the text as a file on the user's device
+export function downloadFile(fileName, text, mimeType) {
+    const url = URL.createObjectURL(new Blob([text], { type: mimeType }));
+    const link = document.createElement("a");
+    link.href = url;
+    link.download = fileName;
+    link.click();
+    URL.revokeObjectURL(url);
+}
+
+// Function to call the callback with the text of each file that is picked with the file input
+export function bindFileImport(inputId, callback) {
+    const fileInput = document.getElementById(inputId);
+    fileInput.addEventListener("change", async () => {
+        if (fileInput.files.length > 0) {
+            callback(await fileInput.files[0].text());
+        }
+        // Let the same file be picked again
+        fileInput.value = "";
+    });
+}
+
+// The ids of the markers for the identifiers, which are removed before the next ones are added
+let semanticMarkers = [];
+
+// Function to color each identifier by how it is used, where the tokens are a JSON array from getSemanticTokens
+export function highlightSemanticTokens(tokensJson) {
+    const Range = ace.require("ace/range").Range;
+    semanticMarkers.forEach((markerId) => editor.session.removeMarker(markerId));
+    semanticMarkers = JSON.parse(tokensJson).map((token) => {
+        const range = new Range(token.line - 1, token.column - 1, token.line - 1, token.column - 1 + token.length);
+        return editor.session.addMarker(range, `semantic-${token.kind}`, "text", false);
+    });
+}
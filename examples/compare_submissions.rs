@@ -0,0 +1,22 @@
+use nexus_compiler::{compare_sources, compare_submissions, BatchSource, SimilarityReport};
+
+// Compares submissions by the structure of their ASTs and prints the most similar pairs first
+// Run with: cargo run --example compare_submissions
+fn main() {
+    let submissions: Vec<BatchSource> = vec![
+        BatchSource::new("alice", "{ int a a = 3 while (a != 9) { print(a) a = 1 + a } }$"),
+        // The same program with the variable renamed and reformatted
+        BatchSource::new("bob", "{\n\tint c\n\tc = 3\n\twhile (c != 9) {\n\t\tprint(c)\n\t\tc = 1 + c\n\t}\n}$"),
+        BatchSource::new("carol", "{ string s s = \"hello\" if (s == \"hello\") { print(s) } }$"),
+        BatchSource::new("dave", "{ print( }$")
+    ];
+
+    let similarity: f64 = compare_sources(&submissions[0].source, &submissions[1].source).expect("Both sources should parse");
+    assert_eq!(similarity, 1.0);
+
+    let report: SimilarityReport = compare_submissions(&submissions);
+    for pair in report.pairs.iter() {
+        println!("{} and {}: {:.0}%", pair.first, pair.second, pair.similarity * 100.0);
+    }
+    println!("Could not be parsed: {}", report.unparsed.join(", "));
+}
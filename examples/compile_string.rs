@@ -0,0 +1,17 @@
+use nexus_compiler::{compile_source, BatchResult, BatchSource, Target, TargetConfig};
+
+// Compiles a string for both targets and prints the generated code
+// Run with: cargo run --example compile_string
+fn main() {
+    let batch_source: BatchSource = BatchSource::new("hello", "{ int a a = 3 print(a) print(\"hi\") }$");
+
+    for target in [Target::Target6502, Target::TargetRiscV] {
+        let batch_result: BatchResult = compile_source(&batch_source, &TargetConfig::new(target));
+        assert!(batch_result.success, "The program should compile for {}", target);
+
+        println!("{} code for {}:", target, batch_result.name);
+        for program in batch_result.programs.iter() {
+            println!("{}", program.code.as_ref().expect("Every program should have code"));
+        }
+    }
+}
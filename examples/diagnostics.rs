@@ -0,0 +1,33 @@
+use nexus_compiler::{compile_source, BatchResult, BatchSource, LogRecord, LogTypes, MessageCode, Position, TargetConfig, Target};
+
+// Compiles a program with a mistake and prints the errors and warnings that were found
+// Run with: cargo run --example diagnostics
+fn main() {
+    let batch_source: BatchSource = BatchSource::new("mistake", "{\n\tint a\n\tprint(b)\n}$");
+    let batch_result: BatchResult = compile_source(&batch_source, &TargetConfig::new(Target::Target6502));
+    assert!(!batch_result.success);
+    assert_eq!(batch_result.num_warnings, 1);
+
+    // Errors and warnings at a position have a code that stays the same when the messages are translated
+    assert!(batch_result.diagnostics.iter().any(|diagnostic| diagnostic.code == Some(MessageCode::UndeclaredId) && diagnostic.position == Some(Position::new(3, 8))));
+
+    for diagnostic in batch_result.diagnostics.iter() {
+        print_diagnostic(diagnostic);
+    }
+
+    // The stage that failed is given for each program
+    println!("Failed at {}", batch_result.programs[0].failed_stage.as_ref().expect("The program should fail"));
+}
+
+fn print_diagnostic(diagnostic: &LogRecord) {
+    let log_type: &str = match diagnostic.log_type {
+        LogTypes::Error => "error",
+        LogTypes::Warning => "warning",
+        _ => "info"
+    };
+    let code: String = diagnostic.code.map(|code| format!("[{}] ", code)).unwrap_or_default();
+    match diagnostic.position {
+        Some(position) => println!("{} {}{} at {}:{} - {}", diagnostic.source, code, log_type, position.line, position.col, diagnostic.message),
+        None => println!("{} {}{} - {}", diagnostic.source, code, log_type, diagnostic.message)
+    }
+}
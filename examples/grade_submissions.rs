@@ -0,0 +1,29 @@
+use nexus_compiler::{grade_submissions, BatchSource, GradeReport, Rubric};
+
+// Grades two submissions with a rubric and prints each score with the feedback of the failed checks
+// Run with: cargo run --example grade_submissions
+fn main() {
+    let rubric: Rubric = Rubric::from_json(r#"{
+        "version": 1,
+        "target": "6502",
+        "checks": [
+            { "name": "Counts to 3", "points": 2, "programNumber": 1, "output": "0\n1\n2\n3", "errors": [] },
+            { "name": "Finds the type mismatch", "points": 1, "programNumber": 2, "errors": [{ "code": "S003", "line": 2 }] }
+        ]
+    }"#).expect("The rubric should be valid");
+
+    let submissions: Vec<BatchSource> = vec![
+        BatchSource::new("alice", "{ int a while (a != 4) { print(a) a = 1 + a } }$\n{ int b b = \"hi\" }$"),
+        BatchSource::new("bob", "{ int a while (a != 3) { print(a) a = 1 + a } }$")
+    ];
+
+    let grade_reports: Vec<GradeReport> = grade_submissions(&rubric, &submissions);
+    assert_eq!(grade_reports[0].score, 3);
+
+    for grade_report in grade_reports.iter() {
+        println!("{}: {} of {} points", grade_report.name, grade_report.score, grade_report.max_score);
+        for check in grade_report.checks.iter().filter(|check| !check.passed) {
+            println!("  {}: {}", check.name, check.feedback.as_ref().unwrap());
+        }
+    }
+}
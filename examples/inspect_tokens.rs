@@ -0,0 +1,18 @@
+use nexus_compiler::{lex_source, Keywords, Token, TokenType};
+
+// Lexes a source with two programs and prints every token with its position
+// Run with: cargo run --example inspect_tokens
+fn main() {
+    let programs: Vec<Vec<Token>> = lex_source("{ print(\"a b\") }$\n{ while true { } }$").expect("The source should lex");
+    assert_eq!(programs.len(), 2);
+
+    for (i, token_stream) in programs.iter().enumerate() {
+        println!("Program {}", i + 1);
+        for token in token_stream.iter() {
+            println!("{} [ {} ] at {:?}", token.token_type.get_kind_name(), token.text, token.position);
+        }
+    }
+
+    // Tokens can be matched on their type
+    assert!(matches!(programs[1][1].token_type, TokenType::Keyword(Keywords::While)));
+}
@@ -0,0 +1,38 @@
+use std::{env, fs, process};
+
+use nexus_compiler::differential::{self, DifferentialReport};
+
+// Checks the code generators against the interpreter without the browser
+// Usage: differential [--generated <count>] [source files...]
+fn main() {
+    let mut num_generated: u64 = 0;
+    let mut sources: Vec<(String, String)> = Vec::new();
+
+    let mut args = env::args().skip(1);
+    while let Some(arg) = args.next() {
+        if arg.eq("--generated") {
+            num_generated = match args.next().and_then(|count| count.parse::<u64>().ok()) {
+                Some(count) => count,
+                None => {
+                    eprintln!("--generated needs the number of programs to generate");
+                    process::exit(2);
+                }
+            };
+        } else {
+            let source: String = match fs::read_to_string(&arg) {
+                Ok(source) => source,
+                Err(err) => {
+                    eprintln!("Could not read {}: {}", arg, err);
+                    process::exit(2);
+                }
+            };
+            sources.push((arg, source));
+        }
+    }
+
+    let report: DifferentialReport = differential::check_corpus(&sources, num_generated);
+    println!("{}", report);
+    if !report.is_successful() {
+        process::exit(1);
+    }
+}
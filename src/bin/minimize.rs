@@ -0,0 +1,42 @@
+use std::{env, fs, process};
+
+use nexus_compiler::{differential::Backend, program_minimizer::{self, Failure}, Target};
+
+// Shrinks a failing program to a minimal reproducer without the browser
+// Usage: minimize <program file> (--crash | --output <6502|6502-size|riscv> | --codegen <6502|riscv>)
+fn main() {
+    let args: Vec<String> = env::args().skip(1).collect();
+    if args.len() < 2 {
+        exit_with_usage();
+    }
+
+    let failure: Failure = match (args[1].as_str(), args.get(2).map(|arg| arg.as_str())) {
+        ("--crash", None) => Failure::Crash,
+        ("--output", Some("6502")) => Failure::WrongOutput(Backend::Image6502),
+        ("--output", Some("6502-size")) => Failure::WrongOutput(Backend::SizeImage6502),
+        ("--output", Some("riscv")) => Failure::WrongOutput(Backend::RiscV),
+        ("--codegen", Some("6502")) => Failure::CodeGenError(Target::Target6502),
+        ("--codegen", Some("riscv")) => Failure::CodeGenError(Target::TargetRiscV),
+        _ => exit_with_usage()
+    };
+
+    let program: String = match fs::read_to_string(&args[0]) {
+        Ok(program) => program,
+        Err(err) => {
+            eprintln!("Could not read {}: {}", args[0], err);
+            process::exit(2);
+        }
+    };
+
+    // There is nothing to minimize if the program does not fail to begin with
+    if !failure.is_failing(&program) {
+        eprintln!("The program does not have the failure {:?}", failure);
+        process::exit(1);
+    }
+    println!("{}", program_minimizer::minimize_failure(&program, failure));
+}
+
+fn exit_with_usage() -> ! {
+    eprintln!("Usage: minimize <program file> (--crash | --output <6502|6502-size|riscv> | --codegen <6502|riscv>)");
+    process::exit(2);
+}
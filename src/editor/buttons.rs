@@ -1,8 +1,11 @@
+use std::rc::Rc;
+
 use wasm_bindgen::{prelude::Closure, JsCast};
-use web_sys::{Window, Document, HtmlElement, Event, Element, DomTokenList, HtmlInputElement};
+use web_sys::{Window, Document, HtmlElement, Event, Element, DomTokenList, HtmlInputElement, HtmlSelectElement};
 
-use crate::{nexus::{compiler, syntax_tree::SyntaxTree, code_generator_6502::CodeGenerator6502}, util::nexus_log};
+use crate::{nexus::{compiler, syntax_tree::SyntaxTree, code_generator_6502::CodeGenerator6502, statistics, artifacts}, util::nexus_log};
 use crate::util::target::Target;
+use crate::editor::{command_palette, output};
 
 use wasm_bindgen::prelude::*;
 
@@ -11,7 +14,10 @@ use wasm_bindgen::prelude::*;
 extern "C" {
     // Import the getCodeInput function from js so we can call it from the Rust code
     #[wasm_bindgen(js_name = "getCodeInput")]
-    fn get_code_input() -> String;
+    pub fn get_code_input() -> String;
+
+    #[wasm_bindgen(js_name = "getSelectionStartLine")]
+    fn get_selection_start_line() -> usize;
 }
 
 // Function used to set up all interactive elements in the webpage
@@ -29,6 +35,18 @@ pub fn set_up_buttons(document: &Document) {
     compile_btn.add_event_listener_with_callback("click", compile_btn_fn.as_ref().unchecked_ref()).expect("Should be able to add the event listener");
     compile_btn_fn.forget();
 
+    // Button to run the programs with the interpreter without generating code
+    let run_btn: Element = document
+        .get_element_by_id("run-btn")
+        .expect("There should be an element called run-btn");
+
+    let run_btn_fn: Closure<dyn FnMut()> = Closure::wrap(Box::new(move || {
+        compiler::run(&get_code_input());
+    }) as Box<dyn FnMut()>);
+
+    run_btn.add_event_listener_with_callback("click", run_btn_fn.as_ref().unchecked_ref()).expect("Should be able to add the event listener");
+    run_btn_fn.forget();
+
     // Button to clear the logs
     let clear_btn: Element = document
         .get_element_by_id("clear-btn")
@@ -39,6 +57,7 @@ pub fn set_up_buttons(document: &Document) {
         nexus_log::clear_logs();
         SyntaxTree::clear_display();
         CodeGenerator6502::clear_display();
+        statistics::clear_display();
     }) as Box<dyn FnMut()>);
 
     clear_btn.add_event_listener_with_callback("click", clear_btn_fn.as_ref().unchecked_ref()).expect("Should be able to add the event listener");
@@ -59,52 +78,80 @@ pub fn set_up_buttons(document: &Document) {
     reset_btn.add_event_listener_with_callback("click", reset_btn_fn.as_ref().unchecked_ref()).expect("Should be able to add the event listener");
     reset_btn_fn.forget();
 
-    // Get each of the log mode buttons
-    let nexus_log_mode: Element = document
-        .get_element_by_id("nexus-log-mode")
-        .expect("There should be an element called nexus-log-mode");
-
-    let lexer_log_mode: Element = document
-        .get_element_by_id("lexer-log-mode")
-        .expect("There should be an element called lexer-log-mode");
-
-    let parser_log_mode: Element = document
-        .get_element_by_id("parser-log-mode")
-        .expect("There should be an element called parser-log-mode");
-
-    let semantic_log_mode: Element = document
-        .get_element_by_id("semantic-log-mode")
-        .expect("There should be an element called semantic-log-mode");
-
-    let codegen_log_mode: Element = document
-        .get_element_by_id("codegen-log-mode")
-        .expect("There should be an element called codegen-log-mode");
-
-    // Universal function for toggling log mode buttons
-    let toggle_log_mode_fn: Closure<dyn FnMut(_)> = Closure::wrap(Box::new(|e: Event| {
-        // Get the element that was clicked
-        let target: HtmlElement = e.target().expect("Should be able to get the target").dyn_into::<HtmlElement>().expect("Should be able to cast to an HtmlElement object");
-
-        let target_classes: DomTokenList = target.class_list();
-        if target_classes.contains("verbose") {
-            target_classes.remove_1("verbose").expect("Should be able to remove the class");
-            target_classes.add_1("simple").expect("Should be able to add the class");
-            target.set_inner_text("Simple");
-        } else if target_classes.contains("simple") {
-            target_classes.remove_1("simple").expect("Should be able to remove the class");
-            target_classes.add_1("verbose").expect("Should be able to add the class");
-            target.set_inner_text("Verbose");
-        }
-    }) as Box<dyn FnMut(_)>);
+    // Create a label and a toggle for each log source
+    let log_source_labels: Element = document
+        .get_element_by_id("log-source-labels")
+        .expect("There should be an element called log-source-labels");
+
+    let log_source_modes: Element = document
+        .get_element_by_id("log-source-modes")
+        .expect("There should be an element called log-source-modes");
+
+    for log_source in nexus_log::get_registered_sources() {
+        let source_label: Element = document.create_element("p").expect("Should be able to create the element");
+        source_label.set_class_name("col");
+        source_label.set_inner_html(log_source.get_label());
+        log_source_labels.append_child(&source_label).expect("Should be able to add the child");
+
+        let source_mode: Element = document.create_element("p").expect("Should be able to create the element");
+        source_mode.set_class_name("verbose col");
+        source_mode.set_id(&log_source.get_mode_id());
+        source_mode.set_inner_html("Verbose");
+        log_source_modes.append_child(&source_mode).expect("Should be able to add the child");
+
+        // Function for toggling the log mode of the source
+        let toggle_log_mode_fn: Closure<dyn FnMut(_)> = Closure::wrap(Box::new(move |e: Event| {
+            // Get the element that was clicked
+            let target: HtmlElement = e.target().expect("Should be able to get the target").dyn_into::<HtmlElement>().expect("Should be able to cast to an HtmlElement object");
+
+            let target_classes: DomTokenList = target.class_list();
+            if target_classes.contains("verbose") {
+                target_classes.remove_1("verbose").expect("Should be able to remove the class");
+                target_classes.add_1("simple").expect("Should be able to add the class");
+                target.set_inner_text("Simple");
+                nexus_log::set_verbose_mode(&log_source, false);
+            } else if target_classes.contains("simple") {
+                target_classes.remove_1("simple").expect("Should be able to remove the class");
+                target_classes.add_1("verbose").expect("Should be able to add the class");
+                target.set_inner_text("Verbose");
+                nexus_log::set_verbose_mode(&log_source, true);
+            }
+        }) as Box<dyn FnMut(_)>);
 
-    // Add the event listener
-    nexus_log_mode.add_event_listener_with_callback("click", toggle_log_mode_fn.as_ref().unchecked_ref()).expect("Should be able to add the event listener");
-    lexer_log_mode.add_event_listener_with_callback("click", toggle_log_mode_fn.as_ref().unchecked_ref()).expect("Should be able to add the event listener");
-    parser_log_mode.add_event_listener_with_callback("click", toggle_log_mode_fn.as_ref().unchecked_ref()).expect("Should be able to add the event listener");
-    semantic_log_mode.add_event_listener_with_callback("click", toggle_log_mode_fn.as_ref().unchecked_ref()).expect("Should be able to add the event listener");
-    codegen_log_mode.add_event_listener_with_callback("click", toggle_log_mode_fn.as_ref().unchecked_ref()).expect("Should be able to add the event listener");
+        source_mode.add_event_listener_with_callback("click", toggle_log_mode_fn.as_ref().unchecked_ref()).expect("Should be able to add the event listener");
+        toggle_log_mode_fn.forget();
+    }
+
+    // The buttons and compiler options can also be used from the command palette
+    command_palette::register_action("Compile", Rc::new(|| compiler::compile(&get_code_input())));
+    command_palette::register_action("Compile current program", Rc::new(|| compiler::compile_program_at_line(&get_code_input(), get_selection_start_line())));
+    command_palette::register_action("Run with the interpreter", Rc::new(|| compiler::run(&get_code_input())));
+    command_palette::register_click_action("Clear output", "clear-btn");
+    command_palette::register_click_action("Reset", "reset-btn");
+    command_palette::register_click_action("Switch target to 6502", "target-6502");
+    command_palette::register_click_action("Switch target to RISC-V", "target-riscv");
+    command_palette::register_click_action("Toggle optimize for size (6502)", "optimize-size");
+    command_palette::register_click_action("Toggle scope comments", "scope-comments");
+    command_palette::register_action("Download image", Rc::new(download_generated_code));
+}
+
+// Function to download the image or assembly of every program from the most recent compilation
+fn download_generated_code() {
+    let mut num_downloaded: usize = 0;
+    for program_number in 1..=artifacts::get_program_count() {
+        if let Some(generated_code) = artifacts::get_program_artifacts(program_number).and_then(|program_artifacts| program_artifacts.generated_code) {
+            output::download_text(&generated_code.get_file_name(program_number), &generated_code.to_text(), "text/plain");
+            num_downloaded += 1;
+        }
+    }
 
-    toggle_log_mode_fn.forget();
+    if num_downloaded == 0 {
+        nexus_log::log(
+            nexus_log::LogTypes::Warning,
+            nexus_log::LogSource::NEXUS,
+            String::from("There is no generated code to download, so compile the programs first")
+        );
+    }
 }
 
 // Function to get the current target platform
@@ -125,3 +172,342 @@ pub fn get_current_target() -> Target {
         return Target::TargetRiscV;
     }
 }
+
+// Function to check if the 6502 code should be optimized for size
+pub fn is_optimize_size_checked() -> bool {
+    let window: Window = web_sys::window().expect("Should be able to get the window");
+    let document: Document = window.document().expect("Should be able to get the document");
+
+    let optimize_size: HtmlInputElement = document
+        .get_element_by_id("optimize-size")
+        .expect("Should be able to get the element")
+        .dyn_into::<HtmlInputElement>()
+        .expect("The element should be recognized as an input element");
+
+    return optimize_size.checked();
+}
+
+// Function to check if the generated code should show where each scope starts and ends
+pub fn is_scope_comments_checked() -> bool {
+    let window: Window = web_sys::window().expect("Should be able to get the window");
+    let document: Document = window.document().expect("Should be able to get the document");
+
+    let scope_comments: HtmlInputElement = document
+        .get_element_by_id("scope-comments")
+        .expect("Should be able to get the element")
+        .dyn_into::<HtmlInputElement>()
+        .expect("The element should be recognized as an input element");
+
+    return scope_comments.checked();
+}
+
+// Function to get the (reserved cells, heap start) inputs for the 6502 memory layout
+pub fn get_memory_layout_input() -> (String, String) {
+    let window: Window = web_sys::window().expect("Should be able to get the window");
+    let document: Document = window.document().expect("Should be able to get the document");
+
+    let reserved_cells: HtmlInputElement = document
+        .get_element_by_id("reserved-cells")
+        .expect("Should be able to get the element")
+        .dyn_into::<HtmlInputElement>()
+        .expect("The element should be recognized as an input element");
+
+    let heap_split: HtmlInputElement = document
+        .get_element_by_id("heap-split")
+        .expect("Should be able to get the element")
+        .dyn_into::<HtmlInputElement>()
+        .expect("The element should be recognized as an input element");
+
+    return (reserved_cells.value(), heap_split.value());
+}
+
+// Function to get the (integer output, string output) inputs for the 6502 output device
+pub fn get_mmio_input() -> (String, String) {
+    let window: Window = web_sys::window().expect("Should be able to get the window");
+    let document: Document = window.document().expect("Should be able to get the document");
+
+    let int_out: HtmlInputElement = document
+        .get_element_by_id("mmio-int-out")
+        .expect("Should be able to get the element")
+        .dyn_into::<HtmlInputElement>()
+        .expect("The element should be recognized as an input element");
+
+    let string_out: HtmlInputElement = document
+        .get_element_by_id("mmio-string-out")
+        .expect("Should be able to get the element")
+        .dyn_into::<HtmlInputElement>()
+        .expect("The element should be recognized as an input element");
+
+    return (int_out.value(), string_out.value());
+}
+
+// Function to get the (convention, write, exit) inputs for the RISC-V system calls
+pub fn get_syscall_input() -> (String, String, String) {
+    let window: Window = web_sys::window().expect("Should be able to get the window");
+    let document: Document = window.document().expect("Should be able to get the document");
+
+    let convention: HtmlSelectElement = document
+        .get_element_by_id("syscall-convention")
+        .expect("Should be able to get the element")
+        .dyn_into::<HtmlSelectElement>()
+        .expect("The element should be recognized as a select element");
+
+    let mut numbers: Vec<String> = Vec::new();
+    for input_id in ["syscall-write", "syscall-exit"] {
+        numbers.push(document
+            .get_element_by_id(input_id)
+            .expect("Should be able to get the element")
+            .dyn_into::<HtmlInputElement>()
+            .expect("The element should be recognized as an input element")
+            .value());
+    }
+
+    return (convention.value(), numbers.remove(0), numbers.remove(0));
+}
+
+// Function to get the input for how deep blocks can be nested before there is a warning
+pub fn get_max_nesting_depth_input() -> String {
+    let window: Window = web_sys::window().expect("Should be able to get the window");
+    let document: Document = window.document().expect("Should be able to get the document");
+
+    return document
+        .get_element_by_id("max-nesting-depth")
+        .expect("Should be able to get the element")
+        .dyn_into::<HtmlInputElement>()
+        .expect("The element should be recognized as an input element")
+        .value();
+}
+
+// Function to get the input for the longest string literal, which is blank to allow anything the target can store
+pub fn get_max_string_length_input() -> String {
+    let window: Window = web_sys::window().expect("Should be able to get the window");
+    let document: Document = window.document().expect("Should be able to get the document");
+
+    return document
+        .get_element_by_id("max-string-length")
+        .expect("Should be able to get the element")
+        .dyn_into::<HtmlInputElement>()
+        .expect("The element should be recognized as an input element")
+        .value();
+}
+
+// Function to get the input for the grammar the class is using, which is course or extended
+pub fn get_language_profile_input() -> String {
+    let window: Window = web_sys::window().expect("Should be able to get the window");
+    let document: Document = window.document().expect("Should be able to get the document");
+
+    return document
+        .get_element_by_id("language-profile")
+        .expect("Should be able to get the element")
+        .dyn_into::<HtmlSelectElement>()
+        .expect("The element should be recognized as a select element")
+        .value();
+}
+
+// Function to get the input for the characters identifiers can have after their first letter
+pub fn get_identifier_charset_input() -> String {
+    let window: Window = web_sys::window().expect("Should be able to get the window");
+    let document: Document = window.document().expect("Should be able to get the document");
+
+    return document
+        .get_element_by_id("identifier-charset")
+        .expect("Should be able to get the element")
+        .dyn_into::<HtmlInputElement>()
+        .expect("The element should be recognized as an input element")
+        .value();
+}
+
+// Function to get the input for the last phase that the class has reached
+pub fn get_course_stage_input() -> String {
+    let window: Window = web_sys::window().expect("Should be able to get the window");
+    let document: Document = window.document().expect("Should be able to get the document");
+
+    return document
+        .get_element_by_id("course-stage")
+        .expect("Should be able to get the element")
+        .dyn_into::<HtmlSelectElement>()
+        .expect("The element should be recognized as a select element")
+        .value();
+}
+
+// Function to check if the lint is turned on, which is the default if it does not have a checkbox
+pub fn is_lint_enabled(lint_name: &str) -> bool {
+    let window: Window = web_sys::window().expect("Should be able to get the window");
+    let document: Document = window.document().expect("Should be able to get the document");
+
+    return match document.get_element_by_id(format!("lint-{}", lint_name).as_str()) {
+        Some(lint_checkbox) => {
+            lint_checkbox
+                .dyn_into::<HtmlInputElement>()
+                .expect("The element should be recognized as an input element")
+                .checked()
+        },
+        None => true
+    };
+}
+
+// Function to select the target platform, which is used when a workspace is imported
+pub fn set_current_target(target: Target) {
+    let window: Window = web_sys::window().expect("Should be able to get the window");
+    let document: Document = window.document().expect("Should be able to get the document");
+
+    let target_id: &str = match target {
+        Target::Target6502 => "target-6502",
+        Target::TargetRiscV => "target-riscv"
+    };
+    document
+        .get_element_by_id(target_id)
+        .expect("Should be able to get the element")
+        .dyn_into::<HtmlInputElement>()
+        .expect("The element should be recognized as an input element")
+        .set_checked(true);
+}
+
+pub fn set_optimize_size_checked(optimize_size: bool) {
+    let window: Window = web_sys::window().expect("Should be able to get the window");
+    let document: Document = window.document().expect("Should be able to get the document");
+
+    document
+        .get_element_by_id("optimize-size")
+        .expect("Should be able to get the element")
+        .dyn_into::<HtmlInputElement>()
+        .expect("The element should be recognized as an input element")
+        .set_checked(optimize_size);
+}
+
+pub fn set_scope_comments_checked(scope_comments: bool) {
+    let window: Window = web_sys::window().expect("Should be able to get the window");
+    let document: Document = window.document().expect("Should be able to get the document");
+
+    document
+        .get_element_by_id("scope-comments")
+        .expect("Should be able to get the element")
+        .dyn_into::<HtmlInputElement>()
+        .expect("The element should be recognized as an input element")
+        .set_checked(scope_comments);
+}
+
+pub fn set_memory_layout_input(reserved_cells: &str, heap_split: &str) {
+    let window: Window = web_sys::window().expect("Should be able to get the window");
+    let document: Document = window.document().expect("Should be able to get the document");
+
+    for (input_id, value) in [("reserved-cells", reserved_cells), ("heap-split", heap_split)] {
+        document
+            .get_element_by_id(input_id)
+            .expect("Should be able to get the element")
+            .dyn_into::<HtmlInputElement>()
+            .expect("The element should be recognized as an input element")
+            .set_value(value);
+    }
+}
+
+pub fn set_mmio_input(int_out: &str, string_out: &str) {
+    let window: Window = web_sys::window().expect("Should be able to get the window");
+    let document: Document = window.document().expect("Should be able to get the document");
+
+    for (input_id, value) in [("mmio-int-out", int_out), ("mmio-string-out", string_out)] {
+        document
+            .get_element_by_id(input_id)
+            .expect("Should be able to get the element")
+            .dyn_into::<HtmlInputElement>()
+            .expect("The element should be recognized as an input element")
+            .set_value(value);
+    }
+}
+
+pub fn set_syscall_input(convention: &str, write: &str, exit: &str) {
+    let window: Window = web_sys::window().expect("Should be able to get the window");
+    let document: Document = window.document().expect("Should be able to get the document");
+
+    document
+        .get_element_by_id("syscall-convention")
+        .expect("Should be able to get the element")
+        .dyn_into::<HtmlSelectElement>()
+        .expect("The element should be recognized as a select element")
+        .set_value(convention);
+
+    for (input_id, value) in [("syscall-write", write), ("syscall-exit", exit)] {
+        document
+            .get_element_by_id(input_id)
+            .expect("Should be able to get the element")
+            .dyn_into::<HtmlInputElement>()
+            .expect("The element should be recognized as an input element")
+            .set_value(value);
+    }
+}
+
+pub fn set_max_nesting_depth_input(max_nesting_depth: &str) {
+    let window: Window = web_sys::window().expect("Should be able to get the window");
+    let document: Document = window.document().expect("Should be able to get the document");
+
+    document
+        .get_element_by_id("max-nesting-depth")
+        .expect("Should be able to get the element")
+        .dyn_into::<HtmlInputElement>()
+        .expect("The element should be recognized as an input element")
+        .set_value(max_nesting_depth);
+}
+
+pub fn set_max_string_length_input(max_string_length: &str) {
+    let window: Window = web_sys::window().expect("Should be able to get the window");
+    let document: Document = window.document().expect("Should be able to get the document");
+
+    document
+        .get_element_by_id("max-string-length")
+        .expect("Should be able to get the element")
+        .dyn_into::<HtmlInputElement>()
+        .expect("The element should be recognized as an input element")
+        .set_value(max_string_length);
+}
+
+pub fn set_language_profile_input(language_profile: &str) {
+    let window: Window = web_sys::window().expect("Should be able to get the window");
+    let document: Document = window.document().expect("Should be able to get the document");
+
+    document
+        .get_element_by_id("language-profile")
+        .expect("Should be able to get the element")
+        .dyn_into::<HtmlSelectElement>()
+        .expect("The element should be recognized as a select element")
+        .set_value(language_profile);
+}
+
+pub fn set_identifier_charset_input(identifier_charset: &str) {
+    let window: Window = web_sys::window().expect("Should be able to get the window");
+    let document: Document = window.document().expect("Should be able to get the document");
+
+    document
+        .get_element_by_id("identifier-charset")
+        .expect("Should be able to get the element")
+        .dyn_into::<HtmlInputElement>()
+        .expect("The element should be recognized as an input element")
+        .set_value(identifier_charset);
+}
+
+// Function to set the course stage, which does nothing if the stage was locked by the link of the page
+pub fn set_course_stage_input(course_stage: &str) {
+    let window: Window = web_sys::window().expect("Should be able to get the window");
+    let document: Document = window.document().expect("Should be able to get the document");
+
+    let course_stage_select: HtmlSelectElement = document
+        .get_element_by_id("course-stage")
+        .expect("Should be able to get the element")
+        .dyn_into::<HtmlSelectElement>()
+        .expect("The element should be recognized as a select element");
+    if !course_stage_select.disabled() {
+        course_stage_select.set_value(course_stage);
+    }
+}
+
+// Function to turn the lint on or off, which does nothing if it does not have a checkbox
+pub fn set_lint_enabled(lint_name: &str, is_enabled: bool) {
+    let window: Window = web_sys::window().expect("Should be able to get the window");
+    let document: Document = window.document().expect("Should be able to get the document");
+
+    if let Some(lint_checkbox) = document.get_element_by_id(format!("lint-{}", lint_name).as_str()) {
+        lint_checkbox
+            .dyn_into::<HtmlInputElement>()
+            .expect("The element should be recognized as an input element")
+            .set_checked(is_enabled);
+    }
+}
@@ -0,0 +1,280 @@
+use std::{cell::RefCell, rc::Rc};
+
+use wasm_bindgen::{JsCast, prelude::*};
+use web_sys::{Window, Document, Element, HtmlElement, HtmlInputElement, Event, KeyboardEvent};
+
+use crate::util::html::escape_html;
+
+// Have to import the editor js module
+#[wasm_bindgen(module = "/editor.js")]
+extern "C" {
+    #[wasm_bindgen(js_name = "bindCommandPalette")]
+    fn bind_command_palette(callback: &js_sys::Function);
+}
+
+// An action that can be searched for and run from the command palette
+struct PaletteAction {
+    name: String,
+    run_fn: Rc<dyn Fn()>
+}
+
+// What the palette is showing while it is open
+struct PaletteState {
+    // The indices of the actions that match the query, with the best matches first
+    matches: Vec<usize>,
+
+    // The index into the matches of the action that Enter runs
+    selected: usize
+}
+
+// Every subsystem adds its actions here when it is set up, so the palette does not need to know about them
+thread_local! {
+    static ACTIONS: RefCell<Vec<PaletteAction>> = RefCell::new(Vec::new());
+
+    static PALETTE_STATE: RefCell<PaletteState> = RefCell::new(PaletteState {
+        matches: Vec::new(),
+        selected: 0
+    });
+}
+
+// Function to add an action to the command palette
+pub fn register_action(name: &str, run_fn: Rc<dyn Fn()>) {
+    ACTIONS.with(|actions| {
+        actions.borrow_mut().push(PaletteAction {
+            name: String::from(name),
+            run_fn: run_fn
+        });
+    });
+}
+
+// Function to add an action that does the same thing as clicking an element on the page
+// Checkboxes and radio buttons are changed by the click, so their listeners run like they were clicked by the user
+pub fn register_click_action(name: &str, element_id: &'static str) {
+    register_action(name, Rc::new(move || {
+        let window: Window = web_sys::window().expect("Should be able to get the window");
+        let document: Document = window.document().expect("Should be able to get the document");
+        document
+            .get_element_by_id(element_id)
+            .expect("Should be able to get the element")
+            .dyn_into::<HtmlElement>()
+            .expect("Should be able to cast to an HtmlElement object")
+            .click();
+    }));
+}
+
+// Function to set up the command palette, which opens with the button or Ctrl-Shift-P
+pub fn create_command_palette(document: &Document) {
+    let palette_btn: Element = document
+        .get_element_by_id("command-palette-btn")
+        .expect("There should be an element called command-palette-btn");
+
+    let open_palette_fn: Closure<dyn FnMut()> = Closure::wrap(Box::new(|| {
+        open_palette();
+    }) as Box<dyn FnMut()>);
+    palette_btn.add_event_listener_with_callback("click", open_palette_fn.as_ref().unchecked_ref()).expect("Should be able to add the event listener");
+
+    // The editor handles its own keys, so the shortcut is bound in the editor as well as the rest of the page
+    bind_command_palette(open_palette_fn.as_ref().unchecked_ref());
+    open_palette_fn.forget();
+
+    let page_key_fn: Closure<dyn FnMut(KeyboardEvent)> = Closure::wrap(Box::new(|event: KeyboardEvent| {
+        if (event.ctrl_key() || event.meta_key()) && event.shift_key() && event.key().to_lowercase().eq("p") {
+            event.prevent_default();
+            open_palette();
+        }
+    }) as Box<dyn FnMut(KeyboardEvent)>);
+    document.add_event_listener_with_callback("keydown", page_key_fn.as_ref().unchecked_ref()).expect("Should be able to add the event listener");
+    page_key_fn.forget();
+
+    // The list is searched again with every change to the query
+    let palette_input: Element = document
+        .get_element_by_id("command-palette-input")
+        .expect("There should be an element called command-palette-input");
+
+    let input_fn: Closure<dyn FnMut()> = Closure::wrap(Box::new(|| {
+        update_matches(&get_palette_input().value());
+    }) as Box<dyn FnMut()>);
+    palette_input.add_event_listener_with_callback("input", input_fn.as_ref().unchecked_ref()).expect("Should be able to add the event listener");
+    input_fn.forget();
+
+    // The arrow keys move the selection, Enter runs the selected action, and Escape closes the palette
+    let palette_key_fn: Closure<dyn FnMut(KeyboardEvent)> = Closure::wrap(Box::new(|event: KeyboardEvent| {
+        match event.key().as_str() {
+            "ArrowDown" => {
+                event.prevent_default();
+                move_selection(true);
+            },
+            "ArrowUp" => {
+                event.prevent_default();
+                move_selection(false);
+            },
+            "Enter" => {
+                let selected_action: Option<usize> = PALETTE_STATE.with(|state| {
+                    let state_ref = state.borrow();
+                    state_ref.matches.get(state_ref.selected).copied()
+                });
+                if let Some(action_index) = selected_action {
+                    run_action(action_index);
+                }
+            },
+            "Escape" => close_palette(),
+            _ => {}
+        }
+    }) as Box<dyn FnMut(KeyboardEvent)>);
+    palette_input.add_event_listener_with_callback("keydown", palette_key_fn.as_ref().unchecked_ref()).expect("Should be able to add the event listener");
+    palette_key_fn.forget();
+
+    // Clicking outside of the list closes the palette
+    let palette_area: Element = document
+        .get_element_by_id("command-palette")
+        .expect("There should be an element called command-palette");
+
+    let area_click_fn: Closure<dyn FnMut(Event)> = Closure::wrap(Box::new(|event: Event| {
+        let target: Element = event.target().expect("Should be able to get the target").dyn_into::<Element>().expect("Should be able to cast to an Element object");
+        if target.id().eq("command-palette") {
+            close_palette();
+        }
+    }) as Box<dyn FnMut(Event)>);
+    palette_area.add_event_listener_with_callback("click", area_click_fn.as_ref().unchecked_ref()).expect("Should be able to add the event listener");
+    area_click_fn.forget();
+}
+
+// Function to get the indices of the actions whose names match the query
+// Names that contain the query come first, then names that have its characters in order, and ties keep the order the actions were registered
+fn search_actions(names: &Vec<String>, query: &str) -> Vec<usize> {
+    let query_lower: String = query.trim().to_lowercase();
+
+    let mut contains_matches: Vec<usize> = Vec::new();
+    let mut in_order_matches: Vec<usize> = Vec::new();
+    for (action_index, name) in names.iter().enumerate() {
+        let name_lower: String = name.to_lowercase();
+        if name_lower.contains(&query_lower) {
+            contains_matches.push(action_index);
+        } else if has_chars_in_order(&name_lower, &query_lower) {
+            in_order_matches.push(action_index);
+        }
+    }
+
+    contains_matches.append(&mut in_order_matches);
+    return contains_matches;
+}
+
+// Function to check if every non-space character of the query is in the name in the same order
+fn has_chars_in_order(name: &str, query: &str) -> bool {
+    let mut name_chars = name.chars();
+    return query.chars()
+        .filter(|c| !c.is_whitespace())
+        .all(|query_char| name_chars.any(|name_char| name_char == query_char));
+}
+
+fn open_palette() {
+    let window: Window = web_sys::window().expect("Should be able to get the window");
+    let document: Document = window.document().expect("Should be able to get the document");
+
+    document
+        .get_element_by_id("command-palette")
+        .expect("Should be able to get the element")
+        .dyn_into::<HtmlElement>()
+        .expect("Should be able to cast to an HtmlElement object")
+        .set_hidden(false);
+
+    let palette_input: HtmlInputElement = get_palette_input();
+    palette_input.set_value("");
+    palette_input.focus().expect("Should be able to focus the input");
+    update_matches("");
+}
+
+fn close_palette() {
+    let window: Window = web_sys::window().expect("Should be able to get the window");
+    let document: Document = window.document().expect("Should be able to get the document");
+
+    document
+        .get_element_by_id("command-palette")
+        .expect("Should be able to get the element")
+        .dyn_into::<HtmlElement>()
+        .expect("Should be able to cast to an HtmlElement object")
+        .set_hidden(true);
+}
+
+// Function to close the palette and run the action
+fn run_action(action_index: usize) {
+    close_palette();
+
+    // The action is taken out of the registry first so it can register more actions while it runs
+    let run_fn: Rc<dyn Fn()> = ACTIONS.with(|actions| Rc::clone(&actions.borrow()[action_index].run_fn));
+    run_fn();
+}
+
+// Function to search for the query and show the matching actions with the first one selected
+fn update_matches(query: &str) {
+    let names: Vec<String> = ACTIONS.with(|actions| actions.borrow().iter().map(|action| action.name.to_owned()).collect());
+    PALETTE_STATE.with(|state| {
+        let mut state_ref = state.borrow_mut();
+        state_ref.matches = search_actions(&names, query);
+        state_ref.selected = 0;
+    });
+    display_matches();
+}
+
+// Function to select the next or previous match, which wraps around at the ends of the list
+fn move_selection(is_next: bool) {
+    PALETTE_STATE.with(|state| {
+        let mut state_ref = state.borrow_mut();
+        let num_matches: usize = state_ref.matches.len();
+        if num_matches > 0 {
+            state_ref.selected = match is_next {
+                true => (state_ref.selected + 1) % num_matches,
+                false => (state_ref.selected + num_matches - 1) % num_matches
+            };
+        }
+    });
+    display_matches();
+}
+
+fn display_matches() {
+    let window: Window = web_sys::window().expect("Should be able to get the window");
+    let document: Document = window.document().expect("Should be able to get the document");
+
+    let palette_list: Element = document.get_element_by_id("command-palette-list").expect("Should be able to get the element");
+    palette_list.set_inner_html("");
+
+    let (matches, selected): (Vec<usize>, usize) = PALETTE_STATE.with(|state| {
+        let state_ref = state.borrow();
+        (state_ref.matches.to_owned(), state_ref.selected)
+    });
+
+    if matches.len() == 0 {
+        let empty_message: Element = document.create_element("p").expect("Should be able to create the element");
+        empty_message.set_inner_html("No matching commands");
+        palette_list.append_child(&empty_message).expect("Should be able to add the child node");
+        return;
+    }
+
+    for (match_index, action_index) in matches.into_iter().enumerate() {
+        let action_elem: Element = document.create_element("p").expect("Should be able to create the element");
+        action_elem.set_class_name(if match_index == selected { "palette-action selected" } else { "palette-action" });
+        action_elem.set_inner_html(&escape_html(&ACTIONS.with(|actions| actions.borrow()[action_index].name.to_owned())));
+
+        let action_fn: Closure<dyn FnMut()> = Closure::wrap(Box::new(move || {
+            run_action(action_index);
+        }) as Box<dyn FnMut()>);
+        action_elem.add_event_listener_with_callback("click", action_fn.as_ref().unchecked_ref()).expect("Should be able to add the event listener");
+        action_fn.forget();
+
+        palette_list.append_child(&action_elem).expect("Should be able to add the child node");
+        if match_index == selected {
+            action_elem.scroll_into_view_with_bool(false);
+        }
+    }
+}
+
+fn get_palette_input() -> HtmlInputElement {
+    let window: Window = web_sys::window().expect("Should be able to get the window");
+    let document: Document = window.document().expect("Should be able to get the document");
+
+    return document
+        .get_element_by_id("command-palette-input")
+        .expect("Should be able to get the element")
+        .dyn_into::<HtmlInputElement>()
+        .expect("The element should be recognized as an input element");
+}
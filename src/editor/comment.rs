@@ -0,0 +1,183 @@
+use wasm_bindgen::{prelude::*, JsCast};
+use web_sys::{Document, Element};
+
+use crate::editor::{buttons, command_palette};
+
+// Have to import the editor js module
+#[wasm_bindgen(module = "/editor.js")]
+extern "C" {
+    #[wasm_bindgen(js_name = "getSelectionStartLine")]
+    fn get_selection_start_line() -> usize;
+
+    #[wasm_bindgen(js_name = "getSelectionEndLine")]
+    fn get_selection_end_line() -> usize;
+
+    #[wasm_bindgen(js_name = "replaceLines")]
+    fn replace_lines(start_line: usize, end_line: usize, text: &str);
+
+    #[wasm_bindgen(js_name = "bindToggleComment")]
+    fn bind_toggle_comment(callback: &js_sys::Function);
+}
+
+// A piece of a line that is either code or part of a comment (including the /* and */)
+#[derive (Debug)]
+enum LineSegment {
+    Code(String),
+    Comment(String)
+}
+
+// Function to set up the toggle comment button and the Ctrl-/ shortcut in the editor
+pub fn create_comment_command(document: &Document) {
+    let toggle_comment_btn: Element = document
+        .get_element_by_id("toggle-comment-btn")
+        .expect("There should be an element called toggle-comment-btn");
+
+    let toggle_comment_fn: Closure<dyn FnMut()> = Closure::wrap(Box::new(|| {
+        let start_line: usize = get_selection_start_line();
+        let end_line: usize = get_selection_end_line();
+
+        // Toggling never adds or removes lines, so only the selected lines have to be replaced
+        let new_code: String = toggle_comment(&buttons::get_code_input(), start_line, end_line);
+        let new_lines: Vec<&str> = new_code.split('\n').collect();
+        if start_line >= 1 && end_line <= new_lines.len() && start_line <= end_line {
+            replace_lines(start_line, end_line, &new_lines[start_line - 1..end_line].join("\n"));
+        }
+    }) as Box<dyn FnMut()>);
+
+    toggle_comment_btn.add_event_listener_with_callback("click", toggle_comment_fn.as_ref().unchecked_ref()).expect("Should be able to add the event listener");
+    bind_toggle_comment(toggle_comment_fn.as_ref().unchecked_ref());
+    toggle_comment_fn.forget();
+
+    command_palette::register_click_action("Toggle comment", "toggle-comment-btn");
+}
+
+// Function to comment out or uncomment the lines from start_line to end_line (starting at 1)
+// Every line gets its own /* */ so the result is still correct when the lines are different statements
+// If every selected line is already a comment, the comments are removed instead
+pub fn toggle_comment(code: &str, start_line: usize, end_line: usize) -> String {
+    let lines: Vec<&str> = code.split('\n').collect();
+    if start_line < 1 || start_line > end_line || end_line > lines.len() {
+        return String::from(code);
+    }
+
+    // Comments can span lines, so everything before the selection has to be read to know where comments are
+    let mut in_comment: bool = false;
+    let mut line_segments: Vec<Vec<LineSegment>> = Vec::new();
+    for line in lines.iter() {
+        let (segments, still_in_comment) = split_line(line, in_comment);
+        line_segments.push(segments);
+        in_comment = still_in_comment;
+    }
+
+    let selected_range: std::ops::Range<usize> = start_line - 1..end_line;
+    let should_uncomment: bool = selected_range.clone()
+        .filter(|line_index| lines[*line_index].trim().len() > 0)
+        .all(|line_index| is_comment_only(&line_segments[line_index]));
+
+    let new_lines: Vec<String> = lines.iter().enumerate()
+        .map(|(line_index, line)| {
+            if !selected_range.contains(&line_index) || line.trim().len() == 0 {
+                return String::from(*line);
+            } else if should_uncomment {
+                return uncomment_line(line);
+            } else {
+                return comment_line(&line_segments[line_index]);
+            }
+        })
+        .collect();
+
+    return new_lines.join("\n");
+}
+
+// Function to split a line into code and comments, which also returns if a comment is still open at the end of the line
+fn split_line(line: &str, starts_in_comment: bool) -> (Vec<LineSegment>, bool) {
+    let mut segments: Vec<LineSegment> = Vec::new();
+    let mut in_comment: bool = starts_in_comment;
+    let mut remaining: &str = line;
+
+    while remaining.len() > 0 {
+        if in_comment {
+            // The rest of the comment goes up to and includes the */
+            match remaining.find("*/") {
+                Some(end_index) => {
+                    segments.push(LineSegment::Comment(String::from(&remaining[..end_index + 2])));
+                    remaining = &remaining[end_index + 2..];
+                    in_comment = false;
+                },
+                None => {
+                    segments.push(LineSegment::Comment(String::from(remaining)));
+                    remaining = "";
+                }
+            }
+        } else {
+            match remaining.find("/*") {
+                Some(start_index) => {
+                    if start_index > 0 {
+                        segments.push(LineSegment::Code(String::from(&remaining[..start_index])));
+                    }
+                    // The */ is searched for after the /* so /*/ does not close itself
+                    let comment: &str = &remaining[start_index..];
+                    match comment[2..].find("*/") {
+                        Some(end_index) => {
+                            segments.push(LineSegment::Comment(String::from(&comment[..end_index + 4])));
+                            remaining = &comment[end_index + 4..];
+                        },
+                        None => {
+                            segments.push(LineSegment::Comment(String::from(comment)));
+                            remaining = "";
+                            in_comment = true;
+                        }
+                    }
+                },
+                None => {
+                    segments.push(LineSegment::Code(String::from(remaining)));
+                    remaining = "";
+                }
+            }
+        }
+    }
+
+    return (segments, in_comment);
+}
+
+// A line is already commented out if it does not have any code outside of its comments
+fn is_comment_only(segments: &Vec<LineSegment>) -> bool {
+    return segments.iter().all(|segment| match segment {
+        LineSegment::Code(code) => code.trim().len() == 0,
+        LineSegment::Comment(_) => true
+    });
+}
+
+// Function to wrap every piece of code on the line in a comment while keeping the comments that are already there
+fn comment_line(segments: &Vec<LineSegment>) -> String {
+    let mut new_line: String = String::new();
+    for segment in segments.iter() {
+        match segment {
+            LineSegment::Code(code) if code.trim().len() > 0 => {
+                // Keep the indentation and spacing around the code outside of the comment
+                let leading: &str = &code[..code.len() - code.trim_start().len()];
+                let trailing: &str = &code[code.trim_end().len()..];
+                new_line.push_str(format!("{}/* {} */{}", leading, code.trim(), trailing).as_str());
+            },
+            LineSegment::Code(code) | LineSegment::Comment(code) => new_line.push_str(code)
+        }
+    }
+    return new_line;
+}
+
+// Function to remove the comment around a line, which is only done when the line is exactly one comment
+// Lines with more than one comment are left alone because the original comments cannot be told apart from the added ones
+fn uncomment_line(line: &str) -> String {
+    let trimmed: &str = line.trim();
+    if !trimmed.starts_with("/*") || !trimmed.ends_with("*/") || trimmed.len() < 4 || trimmed[2..trimmed.len() - 2].contains("*/") {
+        return String::from(line);
+    }
+
+    let inner: &str = &trimmed[2..trimmed.len() - 2];
+    let inner: &str = inner.strip_prefix(' ').unwrap_or(inner);
+    let inner: &str = inner.strip_suffix(' ').unwrap_or(inner);
+
+    let leading: &str = &line[..line.len() - line.trim_start().len()];
+    let trailing: &str = &line[line.trim_end().len()..];
+    return format!("{}{}{}", leading, inner, trailing);
+}
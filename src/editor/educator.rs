@@ -0,0 +1,77 @@
+use wasm_bindgen::{prelude::Closure, JsCast};
+use web_sys::{Window, Document, Element, HtmlElement, HtmlSelectElement};
+
+use crate::editor::buttons;
+use crate::util::{nexus_log, course_stage::CourseStage};
+
+// The parts of the page that show a phase, which are hidden until the class reaches that phase
+const STAGE_ELEMENT_IDS: [(CourseStage, &[&str]); 3] = [
+    (CourseStage::Parse, &["cst-heading", "cst-area"]),
+    (CourseStage::SemanticAnalysis, &["ast-heading", "ast-area", "query-heading", "query-area", "evaluator-heading", "evaluator-area"]),
+    (CourseStage::CodeGeneration, &["code-gen-heading", "code-gen-area", "run-btn", "repl-heading", "repl-area"])
+];
+
+// Function to set up the course stage, which can be locked by the link of the page
+// An instructor can share a link like index.html?stage=parse so the same build works for every part of the course
+pub fn create_educator_mode(document: &Document) {
+    let course_stage_select: HtmlSelectElement = document
+        .get_element_by_id("course-stage")
+        .expect("There should be an element called course-stage")
+        .dyn_into::<HtmlSelectElement>()
+        .expect("The element should be recognized as a select element");
+
+    if let Some(linked_stage) = get_linked_stage() {
+        match CourseStage::from_name(&linked_stage) {
+            Ok(course_stage) => {
+                course_stage_select.set_value(&course_stage.to_string());
+                course_stage_select.set_disabled(true);
+                course_stage_select.set_title("The course stage is set by the link of the page");
+            },
+            Err(message) => {
+                nexus_log::log(
+                    nexus_log::LogTypes::Warning,
+                    nexus_log::LogSource::NEXUS,
+                    format!("Ignoring the course stage in the link of the page: {}", message)
+                );
+            }
+        }
+    }
+
+    let course_stage_fn: Closure<dyn FnMut()> = Closure::wrap(Box::new(|| {
+        show_course_stage();
+    }) as Box<dyn FnMut()>);
+    course_stage_select.add_event_listener_with_callback("change", course_stage_fn.as_ref().unchecked_ref()).expect("Should be able to add the event listener");
+    course_stage_fn.forget();
+
+    show_course_stage();
+}
+
+// Function to show the parts of the page for the phases of the selected course stage and hide the rest
+pub fn show_course_stage() {
+    let window: Window = web_sys::window().expect("Should be able to get the window");
+    let document: Document = window.document().expect("Should be able to get the document");
+
+    // The compiler refuses an invalid stage, so everything is shown until it is fixed
+    let course_stage: CourseStage = CourseStage::from_name(&buttons::get_course_stage_input()).unwrap_or_default();
+    for (stage, element_ids) in STAGE_ELEMENT_IDS.iter() {
+        for element_id in element_ids.iter() {
+            let stage_elem: Element = document.get_element_by_id(element_id).expect("Should be able to get the element");
+            stage_elem
+                .dyn_into::<HtmlElement>()
+                .expect("Should be able to cast to an HtmlElement object")
+                .set_hidden(!course_stage.includes(*stage));
+        }
+    }
+}
+
+// Function to get the value of stage in the query of the page's link, such as parse for ?stage=parse
+fn get_linked_stage() -> Option<String> {
+    let window: Window = web_sys::window().expect("Should be able to get the window");
+    let query: String = window.location().search().unwrap_or_default();
+
+    return query.trim_start_matches('?')
+        .split('&')
+        .filter_map(|parameter| parameter.split_once('='))
+        .find(|(name, _)| name.eq(&"stage"))
+        .map(|(_, value)| String::from(value));
+}
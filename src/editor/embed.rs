@@ -0,0 +1,147 @@
+use serde::Deserialize;
+use wasm_bindgen::{prelude::*, JsCast};
+use web_sys::{Window, Document, Element, HtmlTextAreaElement};
+
+use crate::nexus::batch::{self, BatchSource, BatchResult};
+use crate::util::{nexus_log::LogTypes, target::{Target, TargetConfig}};
+
+// The options for an embedded compiler, which are all optional
+#[derive (Debug, Deserialize)]
+#[serde (rename_all = "camelCase", default, deny_unknown_fields)]
+pub struct EmbedOptions {
+    // "6502" or "RISC-V"
+    pub target: String,
+
+    // The code that is in the editor at the start
+    pub source: String,
+
+    // The height of the editor in lines
+    pub rows: u32
+}
+
+impl Default for EmbedOptions {
+    fn default() -> Self {
+        return EmbedOptions {
+            target: String::from("6502"),
+            source: String::from("{\n\tprint(\"hello world\")\n}$"),
+            rows: 10
+        };
+    }
+}
+
+impl EmbedOptions {
+    // Reads the options from JSON, where nothing given uses all of the defaults
+    pub fn from_json(options_json: Option<String>) -> Result<Self, String> {
+        let options: EmbedOptions = match options_json {
+            Some(options_json) => serde_json::from_str(&options_json).map_err(|err| format!("Invalid embed options: {}", err))?,
+            None => EmbedOptions::default()
+        };
+
+        if !options.target.eq("6502") && !options.target.eq("RISC-V") {
+            return Err(format!("Invalid target [ {} ], which must be 6502 or RISC-V", options.target));
+        }
+        if options.rows < 1 || options.rows > 100 {
+            return Err(format!("Invalid number of rows [ {} ], which must be between 1 and 100", options.rows));
+        }
+        return Ok(options);
+    }
+
+    fn get_target_config(&self) -> TargetConfig {
+        return match self.target.as_str() {
+            "RISC-V" => TargetConfig::new(Target::TargetRiscV),
+            _ => TargetConfig::new(Target::Target6502)
+        };
+    }
+}
+
+// Function to build a small editor and output area inside of any element on a page
+// It does not need any of the elements of the full Nexus page, and each call makes its own independent widget
+#[wasm_bindgen(js_name = "nexus_embed")]
+pub fn nexus_embed(container_id: &str, options_json: Option<String>) -> Result<(), JsValue> {
+    let options: EmbedOptions = EmbedOptions::from_json(options_json).map_err(|err| JsValue::from_str(&err))?;
+
+    let window: Window = web_sys::window().expect("Should be able to get the window");
+    let document: Document = window.document().expect("Should be able to get the document");
+    let container: Element = document
+        .get_element_by_id(container_id)
+        .ok_or_else(|| JsValue::from_str(format!("There is no element called {} to embed Nexus in", container_id).as_str()))?;
+
+    // The page may not have the Nexus styles, so the few that are needed are set on the elements
+    let embed_area: Element = create_element(&document, "div", "nexus-embed", "font-family: monospace;");
+
+    let code_input: HtmlTextAreaElement = create_element(&document, "textarea", "nexus-embed-code", "width: 100%; font-family: monospace; tab-size: 4;")
+        .dyn_into::<HtmlTextAreaElement>()
+        .expect("The element should be recognized as a text area element");
+    code_input.set_rows(options.rows);
+    code_input.set_spellcheck(false);
+    code_input.set_value(&options.source);
+    embed_area.append_child(&code_input).expect("Should be able to add the child node");
+
+    let compile_btn: Element = create_element(&document, "button", "nexus-embed-compile", "");
+    compile_btn.set_text_content(Some(format!("Compile ({})", options.target).as_str()));
+    embed_area.append_child(&compile_btn).expect("Should be able to add the child node");
+
+    let summary: Element = create_element(&document, "span", "nexus-embed-summary", "margin-left: 1em;");
+    embed_area.append_child(&summary).expect("Should be able to add the child node");
+
+    let diagnostics_area: Element = create_element(&document, "div", "nexus-embed-diagnostics", "");
+    embed_area.append_child(&diagnostics_area).expect("Should be able to add the child node");
+
+    let output_area: Element = create_element(&document, "pre", "nexus-embed-output", "white-space: pre-wrap; word-break: break-all;");
+    embed_area.append_child(&output_area).expect("Should be able to add the child node");
+
+    container.append_child(&embed_area).expect("Should be able to add the child node");
+
+    // The widget only uses its own elements, so many of them can be on the same page
+    let target_config: TargetConfig = options.get_target_config();
+    let compile_fn: Closure<dyn FnMut()> = Closure::wrap(Box::new(move || {
+        let batch_source: BatchSource = BatchSource {
+            name: String::from("embed"),
+            source: code_input.value()
+        };
+        let batch_result: BatchResult = batch::compile_source(&batch_source, &target_config);
+        display_result(&batch_result, &summary, &diagnostics_area, &output_area);
+    }) as Box<dyn FnMut()>);
+
+    compile_btn.add_event_listener_with_callback("click", compile_fn.as_ref().unchecked_ref()).expect("Should be able to add the event listener");
+    compile_fn.forget();
+
+    return Ok(());
+}
+
+fn create_element(document: &Document, tag_name: &str, class_name: &str, style: &str) -> Element {
+    let element: Element = document.create_element(tag_name).expect("Should be able to create the element");
+    element.set_class_name(class_name);
+    if style.len() > 0 {
+        element.set_attribute("style", style).expect("Should be able to set the style");
+    }
+    return element;
+}
+
+// Function to show the result of a compilation in the widget
+// Everything is added as text, so nothing in the source code can be read as HTML
+fn display_result(batch_result: &BatchResult, summary: &Element, diagnostics_area: &Element, output_area: &Element) {
+    summary.set_text_content(Some(format!("{} with {} error(s) and {} warning(s)",
+        if batch_result.success { "Compiled" } else { "Failed" }, batch_result.num_errors, batch_result.num_warnings).as_str()));
+
+    let document: Document = web_sys::window().expect("Should be able to get the window").document().expect("Should be able to get the document");
+    diagnostics_area.set_text_content(None);
+    for diagnostic in batch_result.diagnostics.iter() {
+        let color: &str = match diagnostic.log_type {
+            LogTypes::Error => "color: #c00; margin: 0;",
+            _ => "color: #b60; margin: 0;"
+        };
+        let diagnostic_elem: Element = create_element(&document, "p", "nexus-embed-diagnostic", color);
+        diagnostic_elem.set_text_content(Some(format!("[{} - {}]: {}", diagnostic.log_type, diagnostic.source, diagnostic.message).as_str()));
+        diagnostics_area.append_child(&diagnostic_elem).expect("Should be able to add the child node");
+    }
+
+    let program_outputs: Vec<String> = batch_result.programs.iter()
+        .map(|program| match (&program.code, &program.failed_stage) {
+            (Some(code), _) => format!("Program {}:\n{}", program.program_number, code),
+            (None, Some(failed_stage)) => format!("Program {}: failed during {}", program.program_number, failed_stage),
+            (None, None) => format!("Program {}: no code", program.program_number)
+        })
+        .collect();
+    output_area.set_text_content(Some(program_outputs.join("\n\n").as_str()));
+}
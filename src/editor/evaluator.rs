@@ -0,0 +1,80 @@
+use wasm_bindgen::{JsCast, prelude::Closure};
+use web_sys::{Document, Window, Element, HtmlInputElement, KeyboardEvent};
+
+use crate::nexus::{preprocessor::Preprocessor, interpreter::Value, symbol_table::Type};
+use crate::util::{nexus_log, html::escape_html};
+use crate::editor::{buttons, repl, output};
+
+// Function to set up the input and button of the evaluator pane
+pub fn create_evaluator(document: &Document) {
+    let evaluator_btn: Element = document.get_element_by_id("evaluator-btn").expect("There should be an element called evaluator-btn");
+    let evaluator_fn: Closure<dyn FnMut()> = Closure::wrap(Box::new(move || {
+        submit_evaluator_input();
+    }) as Box<dyn FnMut()>);
+    evaluator_btn.add_event_listener_with_callback("click", evaluator_fn.as_ref().unchecked_ref()).expect("Should be able to add the event listener");
+    evaluator_fn.forget();
+
+    // Enter also evaluates the expression
+    let evaluator_input: Element = document.get_element_by_id("evaluator-input").expect("There should be an element called evaluator-input");
+    let evaluator_key_fn: Closure<dyn FnMut(KeyboardEvent)> = Closure::wrap(Box::new(move |event: KeyboardEvent| {
+        if event.key().eq("Enter") {
+            submit_evaluator_input();
+        }
+    }) as Box<dyn FnMut(KeyboardEvent)>);
+    evaluator_input.add_event_listener_with_callback("keydown", evaluator_key_fn.as_ref().unchecked_ref()).expect("Should be able to add the event listener");
+    evaluator_key_fn.forget();
+}
+
+// Function to evaluate the expression in the input and show the result
+fn submit_evaluator_input() {
+    let window: Window = web_sys::window().expect("Should be able to get the window");
+    let document: Document = window.document().expect("Should be able to get the document");
+
+    let evaluator_input: HtmlInputElement = document
+        .get_element_by_id("evaluator-input")
+        .expect("Should be able to get the element")
+        .dyn_into::<HtmlInputElement>()
+        .expect("The element should be recognized as an input element");
+    let evaluator_result: Element = document.get_element_by_id("evaluator-result").expect("Should be able to get the element");
+
+    let expression: String = evaluator_input.value().trim().to_string();
+    if expression.len() == 0 {
+        evaluator_result.set_inner_html("");
+        return;
+    }
+
+    match evaluate(&buttons::get_code_input(), &expression) {
+        Ok(result_text) => {
+            evaluator_result.set_class_name("evaluator-value");
+            evaluator_result.set_inner_html(&escape_html(&result_text));
+        },
+        Err(errors) => {
+            evaluator_result.set_class_name("evaluator-error");
+            let error_lines: Vec<String> = errors.iter().map(|error| escape_html(error)).collect();
+            evaluator_result.set_inner_html(&output::join_html_lines(&error_lines));
+        }
+    }
+}
+
+// Function to get the text showing the value and type of the expression
+// Constants come from the define directives in the editor and variables come from the REPL session
+fn evaluate(source_code: &str, expression: &str) -> Result<String, Vec<String>> {
+    // Only the constants are needed, so the logs of the editor's code are not shown
+    let mut preprocessor: Preprocessor = Preprocessor::new();
+    nexus_log::start_capture();
+    let preprocess_res: Result<String, ()> = preprocessor.expand_defines(source_code);
+    nexus_log::stop_capture();
+    if preprocess_res.is_err() {
+        return Err(vec![format!("The constants in the editor could not be expanded because preprocessing failed with {} error(s)", preprocessor.num_errors)]);
+    }
+
+    let expanded_expression: String = preprocessor.expand_constants(expression);
+    let (value, expr_type): (Value, Type) = repl::evaluate_expression(&expanded_expression)?;
+
+    // Strings are quoted so empty strings and spaces can be seen
+    let value_text: String = match value {
+        Value::String(string) => format!("\"{}\"", string),
+        other => other.to_output()
+    };
+    return Ok(format!("{} : {:?}", value_text, expr_type));
+}
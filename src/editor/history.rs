@@ -0,0 +1,413 @@
+use std::cell::RefCell;
+
+use serde::{Serialize, Deserialize};
+use wasm_bindgen::{prelude::*, JsCast};
+use web_sys::{Window, Document, Element};
+
+use crate::editor::{command_palette, output, settings, workspace::CompilerOptionsJson};
+use crate::nexus::{artifacts::{self, ProgramArtifacts, GeneratedCode}, statistics::ProgramStatistics};
+use crate::util::{diagnostic::Issue, diff::{self, DiffItem}, html::escape_html, nexus_log::{self, LogTypes}, position::Position};
+
+// Have to import the editor js module
+#[wasm_bindgen(module = "/editor.js")]
+extern "C" {
+    #[wasm_bindgen(js_name = "loadProgram")]
+    fn load_program(new_code: &str);
+}
+
+// The key the history is saved under in local storage
+const HISTORY_KEY: &str = "nexus-compile-history";
+
+// The version of the saved history, which works the same way as the workspace version
+pub const HISTORY_VERSION: u32 = 1;
+
+// Only the most recent compilations are kept so the history fits in local storage
+pub const MAX_HISTORY_ENTRIES: usize = 10;
+
+// An error or warning of a program in the history, which keeps what the issues pane shows
+#[derive (Debug, Clone, Serialize, Deserialize)]
+#[serde (rename_all = "camelCase")]
+pub struct HistoryIssueJson {
+    pub is_error: bool,
+    pub source: String,
+    pub code: Option<String>,
+    pub position: Position,
+    pub message: String
+}
+
+impl HistoryIssueJson {
+    fn new(issue: &Issue) -> Self {
+        return HistoryIssueJson {
+            is_error: matches!(issue.log_type, LogTypes::Error),
+            source: issue.source.to_string(),
+            code: issue.code.map(|code| code.to_string()),
+            position: issue.position,
+            message: issue.message.to_owned()
+        };
+    }
+}
+
+// What a compilation produced for a single program
+#[derive (Debug, Clone, Serialize, Deserialize)]
+#[serde (rename_all = "camelCase")]
+pub struct HistoryProgramJson {
+    pub program_number: u32,
+    #[serde (default)]
+    pub issues: Vec<HistoryIssueJson>,
+
+    // The hex of the image or the assembly if code gen was successful
+    pub generated_code: Option<String>,
+    #[serde (default)]
+    pub statistics: ProgramStatistics
+}
+
+impl HistoryProgramJson {
+    fn new(program_number: u32, program_artifacts: ProgramArtifacts) -> Self {
+        return HistoryProgramJson {
+            program_number: program_number,
+            issues: program_artifacts.issues.iter().map(|issue| HistoryIssueJson::new(issue)).collect(),
+            generated_code: program_artifacts.generated_code.map(|generated_code| generated_code.to_text()),
+            statistics: program_artifacts.statistics
+        };
+    }
+}
+
+// A single compilation with the source it was given
+#[derive (Debug, Clone, Serialize, Deserialize)]
+#[serde (rename_all = "camelCase")]
+pub struct HistoryEntryJson {
+    // Milliseconds since the epoch when the compilation finished
+    pub compiled_at: f64,
+    pub source: String,
+    #[serde (default)]
+    pub compiler_options: CompilerOptionsJson,
+
+    // True if the programs were run with the interpreter instead of generating code
+    #[serde (default)]
+    pub is_interpreted: bool,
+    #[serde (default)]
+    pub programs: Vec<HistoryProgramJson>
+}
+
+impl HistoryEntryJson {
+    // Function to get the number of errors and warnings of every program
+    fn get_issue_counts(&self) -> (usize, usize) {
+        let num_errors: usize = self.programs.iter().flat_map(|program| program.issues.iter()).filter(|issue| issue.is_error).count();
+        let num_warnings: usize = self.programs.iter().flat_map(|program| program.issues.iter()).filter(|issue| !issue.is_error).count();
+        return (num_errors, num_warnings);
+    }
+}
+
+// The history as it is saved in local storage, with the oldest compilation first
+#[derive (Debug, Serialize, Deserialize)]
+#[serde (rename_all = "camelCase")]
+pub struct HistoryJson {
+    pub version: u32,
+    #[serde (default)]
+    pub entries: Vec<HistoryEntryJson>
+}
+
+impl HistoryJson {
+    // Reads the saved history, which returns why it cannot be used if it is not valid
+    pub fn from_json(history_json: &str) -> Result<Self, String> {
+        let history_value: serde_json::Value = serde_json::from_str(history_json).map_err(|err| format!("Invalid history JSON: {}", err))?;
+        match history_value.get("version").and_then(|version| version.as_u64()) {
+            Some(version) if version == HISTORY_VERSION as u64 => {},
+            Some(version) => return Err(format!("History version {} is not supported, which must be {}", version, HISTORY_VERSION)),
+            None => return Err(String::from("Invalid history, which must have a version number"))
+        }
+        return serde_json::from_value(history_value).map_err(|err| format!("Invalid history: {}", err));
+    }
+
+    pub fn to_json(&self) -> String {
+        return serde_json::to_string(self).expect("Should be able to serialize the history");
+    }
+
+    // Function to add a compilation, which forgets the oldest ones once there are too many
+    pub fn add_entry(&mut self, entry: HistoryEntryJson) {
+        self.entries.push(entry);
+        if self.entries.len() > MAX_HISTORY_ENTRIES {
+            let num_removed: usize = self.entries.len() - MAX_HISTORY_ENTRIES;
+            self.entries.drain(0..num_removed);
+        }
+    }
+}
+
+// The history lives for the whole page like the artifacts do, and is loaded from local storage when the pane is set up
+thread_local! {
+    static HISTORY: RefCell<HistoryJson> = RefCell::new(HistoryJson {
+        version: HISTORY_VERSION,
+        entries: Vec::new()
+    });
+}
+
+// Function to load the saved history and set up the history pane
+pub fn create_history_pane(document: &Document) {
+    let saved_json: Option<String> = settings::get_local_storage().and_then(|storage| storage.get_item(HISTORY_KEY).ok().flatten());
+    if let Some(history_json) = saved_json {
+        match HistoryJson::from_json(&history_json) {
+            Ok(history) => HISTORY.with(|saved_history| *saved_history.borrow_mut() = history),
+            Err(message) => {
+                nexus_log::log(
+                    nexus_log::LogTypes::Warning,
+                    nexus_log::LogSource::NEXUS,
+                    format!("Starting a new compile history because the saved one could not be used: {}", message)
+                );
+            }
+        }
+    }
+
+    let clear_history_btn: Element = document
+        .get_element_by_id("clear-history-btn")
+        .expect("There should be an element called clear-history-btn");
+
+    let clear_history_fn: Closure<dyn FnMut()> = Closure::wrap(Box::new(|| {
+        HISTORY.with(|history| history.borrow_mut().entries.clear());
+        save_history();
+        display_history();
+    }) as Box<dyn FnMut()>);
+    clear_history_btn.add_event_listener_with_callback("click", clear_history_fn.as_ref().unchecked_ref()).expect("Should be able to add the event listener");
+    clear_history_fn.forget();
+
+    command_palette::register_click_action("Clear compile history", "clear-history-btn");
+
+    display_history();
+}
+
+// Function to add the most recent compilation to the history with the artifacts of each of its programs
+pub fn record_compilation(source_code: &str, is_interpreted: bool) {
+    let entry: HistoryEntryJson = HistoryEntryJson {
+        compiled_at: js_sys::Date::now(),
+        source: String::from(source_code),
+        compiler_options: CompilerOptionsJson::from_page(),
+        is_interpreted: is_interpreted,
+        programs: (1..=artifacts::get_program_count())
+            .map(|program_number| HistoryProgramJson::new(program_number, artifacts::get_program_artifacts(program_number).unwrap()))
+            .collect()
+    };
+
+    HISTORY.with(|history| history.borrow_mut().add_entry(entry));
+    save_history();
+    display_history();
+}
+
+fn save_history() {
+    // The history still works for the visit when local storage is not available or is full
+    if let Some(storage) = settings::get_local_storage() {
+        let _ = storage.set_item(HISTORY_KEY, &HISTORY.with(|history| history.borrow().to_json()));
+    }
+}
+
+// Function to put the source of a compilation back into the editor, which does not compile it
+fn restore_entry(entry_index: usize) {
+    let source: Option<String> = HISTORY.with(|history| history.borrow().entries.get(entry_index).map(|entry| entry.source.to_owned()));
+    if let Some(source_code) = source {
+        load_program(&source_code);
+        nexus_log::log(
+            nexus_log::LogTypes::Info,
+            nexus_log::LogSource::NEXUS,
+            format!("Restored the source of compilation {} from the history", entry_index + 1)
+        );
+    }
+}
+
+// Function to show every compilation in the history with the most recent one first
+fn display_history() {
+    let document: Document = get_document();
+    let history_list: Element = document.get_element_by_id("history-list").expect("Should be able to find the element");
+    history_list.set_inner_html("");
+
+    let entries: Vec<HistoryEntryJson> = HISTORY.with(|history| history.borrow().entries.to_owned());
+    document.get_element_by_id("history-count").expect("Should be able to find the element")
+        .set_inner_html(format!("{} of the last {} compilations", entries.len(), MAX_HISTORY_ENTRIES).as_str());
+
+    if entries.len() == 0 {
+        let empty_message: Element = document.create_element("p").expect("Should be able to create the element");
+        empty_message.set_inner_html("No compilations yet");
+        history_list.append_child(&empty_message).expect("Should be able to add the child node");
+        document.get_element_by_id("history-details").expect("Should be able to find the element").set_inner_html("");
+        return;
+    }
+
+    for (entry_index, entry) in entries.iter().enumerate().rev() {
+        let (num_errors, num_warnings): (usize, usize) = entry.get_issue_counts();
+        let entry_elem: Element = document.create_element("p").expect("Should be able to create the element");
+        entry_elem.set_class_name("history-entry");
+        entry_elem.set_inner_html(format!(
+            "{}. {} - {} {}, {} program{}, {} error{}, {} warning{} ",
+            entry_index + 1,
+            get_time_text(entry.compiled_at),
+            if entry.is_interpreted { "Run" } else { "Compile" },
+            escape_html(&entry.compiler_options.target),
+            entry.programs.len(), if entry.programs.len() == 1 { "" } else { "s" },
+            num_errors, if num_errors == 1 { "" } else { "s" },
+            num_warnings, if num_warnings == 1 { "" } else { "s" }
+        ).as_str());
+
+        let view_btn: Element = document.create_element("button").expect("Should be able to create the element");
+        view_btn.set_inner_html("View");
+        view_btn.set_class_name("copy-btn");
+        entry_elem.append_child(&view_btn).expect("Should be able to add the child node");
+
+        let compare_btn: Element = document.create_element("button").expect("Should be able to create the element");
+        compare_btn.set_inner_html("Compare");
+        compare_btn.set_class_name("copy-btn");
+        entry_elem.append_child(&compare_btn).expect("Should be able to add the child node");
+
+        let restore_btn: Element = document.create_element("button").expect("Should be able to create the element");
+        restore_btn.set_inner_html("Restore");
+        restore_btn.set_class_name("copy-btn");
+        entry_elem.append_child(&restore_btn).expect("Should be able to add the child node");
+
+        let view_fn: Closure<dyn FnMut()> = Closure::wrap(Box::new(move || {
+            display_entry(entry_index);
+        }) as Box<dyn FnMut()>);
+        view_btn.add_event_listener_with_callback("click", view_fn.as_ref().unchecked_ref()).expect("Should be able to add the event listener");
+        view_fn.forget();
+
+        let compare_fn: Closure<dyn FnMut()> = Closure::wrap(Box::new(move || {
+            display_comparison(entry_index);
+        }) as Box<dyn FnMut()>);
+        compare_btn.add_event_listener_with_callback("click", compare_fn.as_ref().unchecked_ref()).expect("Should be able to add the event listener");
+        compare_fn.forget();
+
+        let restore_fn: Closure<dyn FnMut()> = Closure::wrap(Box::new(move || {
+            restore_entry(entry_index);
+        }) as Box<dyn FnMut()>);
+        restore_btn.add_event_listener_with_callback("click", restore_fn.as_ref().unchecked_ref()).expect("Should be able to add the event listener");
+        restore_fn.forget();
+
+        history_list.append_child(&entry_elem).expect("Should be able to add the child node");
+    }
+}
+
+// Function to show the source of a compilation and the issues and generated code of each of its programs
+fn display_entry(entry_index: usize) {
+    let document: Document = get_document();
+    let history_details: Element = document.get_element_by_id("history-details").expect("Should be able to find the element");
+    history_details.set_inner_html("");
+
+    let entry_res: Option<HistoryEntryJson> = HISTORY.with(|history| history.borrow().entries.get(entry_index).cloned());
+    if entry_res.is_none() {
+        return;
+    }
+    let entry: HistoryEntryJson = entry_res.unwrap();
+
+    let source_header: Element = document.create_element("h6").expect("Should be able to create the element");
+    source_header.set_inner_html(format!("Compilation {} source", entry_index + 1).as_str());
+    history_details.append_child(&source_header).expect("Should be able to add the child node");
+
+    let source_elem: Element = document.create_element("pre").expect("Should be able to create the element");
+    source_elem.set_inner_html(&escape_html(&entry.source));
+    history_details.append_child(&source_elem).expect("Should be able to add the child node");
+
+    for program in entry.programs.iter() {
+        let program_header: Element = document.create_element("h6").expect("Should be able to create the element");
+        program_header.set_inner_html(format!("Program {}", program.program_number).as_str());
+        history_details.append_child(&program_header).expect("Should be able to add the child node");
+
+        for issue in program.issues.iter() {
+            let issue_elem: Element = document.create_element("p").expect("Should be able to create the element");
+            let mut label: String = String::from(if issue.is_error { "Error" } else { "Warning" });
+            if let Some(code) = &issue.code {
+                label.push_str(format!(" {}", code).as_str());
+            }
+            issue_elem.set_inner_html(format!("{}:{} {} - {}: {}", issue.position.line, issue.position.col, label, escape_html(&issue.source), escape_html(&issue.message)).as_str());
+            issue_elem.set_class_name(if issue.is_error { "error" } else { "warning" });
+            history_details.append_child(&issue_elem).expect("Should be able to add the child node");
+        }
+
+        let code_elem: Element = document.create_element("p").expect("Should be able to create the element");
+        code_elem.set_class_name("code-text");
+        match &program.generated_code {
+            Some(generated_code) => {
+                let code_lines: Vec<String> = generated_code.lines().map(|line| escape_html(line)).collect();
+                code_elem.set_inner_html(&output::join_html_lines(&code_lines));
+            },
+            None => code_elem.set_inner_html("No generated code")
+        }
+        history_details.append_child(&code_elem).expect("Should be able to add the child node");
+    }
+}
+
+// Function to show how the generated code of each program changed from a compilation in the history to the current compilation
+// The image is compared byte by byte and the assembly is compared line by line
+fn display_comparison(entry_index: usize) {
+    let document: Document = get_document();
+    let history_details: Element = document.get_element_by_id("history-details").expect("Should be able to find the element");
+    history_details.set_inner_html("");
+
+    let entry_res: Option<HistoryEntryJson> = HISTORY.with(|history| history.borrow().entries.get(entry_index).cloned());
+    if entry_res.is_none() {
+        return;
+    }
+    let entry: HistoryEntryJson = entry_res.unwrap();
+
+    let comparison_header: Element = document.create_element("h6").expect("Should be able to create the element");
+    comparison_header.set_inner_html(format!("Compilation {} compared to the current compilation", entry_index + 1).as_str());
+    history_details.append_child(&comparison_header).expect("Should be able to add the child node");
+
+    // Programs are matched by their number, so a program that only one compilation has is still listed
+    let num_programs: u32 = (entry.programs.len() as u32).max(artifacts::get_program_count());
+    for program_number in 1..=num_programs {
+        let program_header: Element = document.create_element("h6").expect("Should be able to create the element");
+        program_header.set_inner_html(format!("Program {}", program_number).as_str());
+        history_details.append_child(&program_header).expect("Should be able to add the child node");
+
+        let old_code: Option<String> = entry.programs.get(program_number as usize - 1).and_then(|program| program.generated_code.to_owned());
+        let new_code: Option<GeneratedCode> = artifacts::get_program_artifacts(program_number).and_then(|program_artifacts| program_artifacts.generated_code);
+
+        let summary_elem: Element = document.create_element("p").expect("Should be able to create the element");
+        history_details.append_child(&summary_elem).expect("Should be able to add the child node");
+        match (old_code, new_code) {
+            (Some(old_text), Some(generated_code)) => {
+                if !entry.compiler_options.target.eq(&generated_code.get_target().to_string()) {
+                    summary_elem.set_inner_html(format!("Cannot compare code for {} with code for {}", escape_html(&entry.compiler_options.target), generated_code.get_target()).as_str());
+                    continue;
+                }
+
+                let is_image: bool = matches!(generated_code, GeneratedCode::Image(_));
+                let new_text: String = generated_code.to_text();
+                let (old_items, new_items): (Vec<&str>, Vec<&str>) = match is_image {
+                    true => (old_text.split_whitespace().collect(), new_text.split_whitespace().collect()),
+                    false => (old_text.lines().collect(), new_text.lines().collect())
+                };
+                let code_diff: Vec<DiffItem> = diff::diff_items(&old_items, &new_items);
+                let (num_added, num_removed): (usize, usize) = diff::count_changes(&code_diff);
+                let unit: &str = if is_image { "byte" } else { "line" };
+                summary_elem.set_inner_html(format!(
+                    "{} {}{} added, {} {}{} removed",
+                    num_added, unit, if num_added == 1 { "" } else { "s" },
+                    num_removed, unit, if num_removed == 1 { "" } else { "s" }
+                ).as_str());
+
+                let diff_html: Vec<String> = code_diff.iter()
+                    .map(|item| match item {
+                        DiffItem::Same(text) => escape_html(text),
+                        DiffItem::Removed(text) => format!("<span class=\"diff-removed\">{}</span>", escape_html(text)),
+                        DiffItem::Added(text) => format!("<span class=\"diff-added\">{}</span>", escape_html(text))
+                    })
+                    .collect();
+                let code_elem: Element = document.create_element("p").expect("Should be able to create the element");
+                code_elem.set_class_name("code-text");
+                code_elem.set_inner_html(&if is_image { diff_html.join(" ") } else { output::join_html_lines(&diff_html) });
+                history_details.append_child(&code_elem).expect("Should be able to add the child node");
+            },
+            (Some(_), None) => summary_elem.set_inner_html("Only the earlier compilation has generated code"),
+            (None, Some(_)) => summary_elem.set_inner_html("Only the current compilation has generated code"),
+            (None, None) => summary_elem.set_inner_html("Neither compilation has generated code")
+        }
+    }
+}
+
+// Function to get the local time of day of a timestamp in milliseconds
+fn get_time_text(timestamp: f64) -> String {
+    let time_string: String = String::from(js_sys::Date::new(&JsValue::from_f64(timestamp)).to_time_string());
+    // The time string ends with the time zone, which is left out
+    return time_string.split(' ').next().unwrap_or_default().to_string();
+}
+
+fn get_document() -> Document {
+    let window: Window = web_sys::window().expect("Should be able to get the window");
+    return window.document().expect("Should be able to get the document");
+}
@@ -0,0 +1,192 @@
+use wasm_bindgen::{prelude::*, JsCast};
+use std::rc::Rc;
+
+use web_sys::{Window, Document, Element, HtmlInputElement, Event};
+
+use crate::nexus::artifacts;
+use crate::nexus::compiler;
+use crate::editor::{buttons, command_palette};
+use crate::util::{diagnostic::{Issue, QuickFix}, html::escape_html, nexus_log::{self, LogSink, LogRecord, LogTypes}, position::Position};
+
+// Have to import the editor js module
+#[wasm_bindgen(module = "/editor.js")]
+extern "C" {
+    #[wasm_bindgen(js_name = "gotoPosition")]
+    fn goto_position(line: usize, col: usize);
+
+    #[wasm_bindgen(js_name = "insertText")]
+    fn insert_text(line: usize, col: usize, text: &str);
+}
+
+// Keeps the errors and warnings that have a position with the artifacts of the program being compiled
+pub struct IssuesSink;
+
+impl LogSink for IssuesSink {
+    fn get_name(&self) -> &'static str {
+        return "issues";
+    }
+
+    fn write(&mut self, record: &LogRecord) {
+        if !matches!(record.log_type, LogTypes::Error | LogTypes::Warning) || record.position.is_none() {
+            return;
+        }
+
+        let issue: Issue = Issue {
+            log_type: record.log_type,
+            source: record.source,
+            code: record.code,
+            position: record.position.unwrap(),
+            message: record.message.to_owned(),
+            fix: record.fix.to_owned()
+        };
+        artifacts::update_current_program(|program_artifacts| program_artifacts.issues.push(issue));
+    }
+}
+
+// Function to start collecting the issues and set up the filters of the issues pane
+pub fn create_issues_pane(document: &Document) {
+    nexus_log::add_sink(Box::new(IssuesSink));
+
+    // The list is drawn again from the artifacts whenever a filter changes
+    let filter_fn: Closure<dyn FnMut()> = Closure::wrap(Box::new(|| {
+        display_issues();
+    }) as Box<dyn FnMut()>);
+    for filter_id in ["issues-show-errors", "issues-show-warnings"] {
+        let filter_input: Element = document.get_element_by_id(filter_id).expect("Should be able to find the element");
+        filter_input.add_event_listener_with_callback("change", filter_fn.as_ref().unchecked_ref()).expect("Should be able to add the event listener");
+    }
+    filter_fn.forget();
+
+    command_palette::register_click_action("Toggle errors in the issues pane", "issues-show-errors");
+    command_palette::register_click_action("Toggle warnings in the issues pane", "issues-show-warnings");
+    command_palette::register_action("Apply the first quick fix", Rc::new(|| {
+        let first_fix: Option<QuickFix> = (1..=artifacts::get_program_count())
+            .flat_map(|program_number| artifacts::get_program_artifacts(program_number).unwrap().issues)
+            .find_map(|issue| issue.fix);
+        match first_fix {
+            Some(fix) => apply_fix(&fix),
+            None => nexus_log::log(
+                nexus_log::LogTypes::Info,
+                nexus_log::LogSource::NEXUS,
+                String::from("There are no quick fixes in the last compilation")
+            )
+        }
+    }));
+}
+
+// Function to show the issues of every program from the most recent compilation
+pub fn display_issues() {
+    let document: Document = get_document();
+    let issues_list: Element = document.get_element_by_id("issues-list").expect("Should be able to find the element");
+    issues_list.set_inner_html("");
+
+    let show_errors: bool = is_filter_checked(&document, "issues-show-errors");
+    let show_warnings: bool = is_filter_checked(&document, "issues-show-warnings");
+
+    // The counts are of every issue so the filters do not hide how many there are
+    let mut num_errors: usize = 0;
+    let mut num_warnings: usize = 0;
+    let mut is_list_empty: bool = true;
+
+    for program_number in 1..=artifacts::get_program_count() {
+        let issues: Vec<Issue> = artifacts::get_program_artifacts(program_number).unwrap().issues;
+        num_errors += issues.iter().filter(|issue| matches!(issue.log_type, LogTypes::Error)).count();
+        num_warnings += issues.iter().filter(|issue| matches!(issue.log_type, LogTypes::Warning)).count();
+
+        let shown_issues: Vec<&Issue> = issues.iter()
+            .filter(|issue| match issue.log_type {
+                LogTypes::Error => show_errors,
+                _ => show_warnings
+            })
+            .collect();
+        if shown_issues.len() == 0 {
+            continue;
+        }
+        is_list_empty = false;
+
+        let program_header: Element = document.create_element("h6").expect("Should be able to create the element");
+        program_header.set_inner_html(format!("Program {}", program_number).as_str());
+        issues_list.append_child(&program_header).expect("Should be able to add the child node");
+
+        for issue in shown_issues {
+            issues_list.append_child(&create_issue_element(&document, issue)).expect("Should be able to add the child node");
+        }
+    }
+
+    if is_list_empty {
+        let empty_message: Element = document.create_element("p").expect("Should be able to create the element");
+        empty_message.set_inner_html("No issues");
+        issues_list.append_child(&empty_message).expect("Should be able to add the child node");
+    }
+
+    document.get_element_by_id("issues-count").expect("Should be able to find the element")
+        .set_inner_html(format!("{} error{}, {} warning{}", num_errors, if num_errors == 1 { "" } else { "s" }, num_warnings, if num_warnings == 1 { "" } else { "s" }).as_str());
+}
+
+// Function to create the line of an issue, which moves the cursor of the editor to the issue when clicked
+fn create_issue_element(document: &Document, issue: &Issue) -> Element {
+    let issue_elem: Element = document.create_element("p").expect("Should be able to create the element");
+    let mut label: String = String::from(match issue.log_type {
+        LogTypes::Error => "Error",
+        _ => "Warning"
+    });
+    if let Some(code) = issue.code {
+        label.push_str(format!(" {}", code).as_str());
+    }
+    issue_elem.set_inner_html(format!("{}:{} {} - {}: {}", issue.position.line, issue.position.col, label, issue.source, escape_html(&issue.message)).as_str());
+    issue_elem.set_class_name(match issue.log_type {
+        LogTypes::Error => "error issue",
+        _ => "warning issue"
+    });
+
+    let position: Position = issue.position;
+    let issue_fn: Closure<dyn FnMut()> = Closure::wrap(Box::new(move || {
+        goto_position(position.line, position.col);
+    }) as Box<dyn FnMut()>);
+    issue_elem.add_event_listener_with_callback("click", issue_fn.as_ref().unchecked_ref()).expect("Should be able to add the event listener");
+    issue_fn.forget();
+
+    // Issues with a quick fix get a button that makes the change in the editor
+    if let Some(fix) = &issue.fix {
+        let fix_btn: Element = document.create_element("button").expect("Should be able to create the element");
+        fix_btn.set_class_name("btn btn-sm btn-outline-secondary issue-fix");
+        fix_btn.set_inner_html(&escape_html(&fix.title));
+
+        let fix: QuickFix = fix.to_owned();
+        let fix_fn: Closure<dyn FnMut(Event)> = Closure::wrap(Box::new(move |event: Event| {
+            // The issue itself only moves the cursor
+            event.stop_propagation();
+            apply_fix(&fix);
+        }) as Box<dyn FnMut(Event)>);
+        fix_btn.add_event_listener_with_callback("click", fix_fn.as_ref().unchecked_ref()).expect("Should be able to add the event listener");
+        fix_fn.forget();
+        issue_elem.append_child(&fix_btn).expect("Should be able to add the child node");
+    }
+
+    return issue_elem;
+}
+
+// Function to make the change of a quick fix in the editor
+// The positions of the other issues are out of date once the code changes, so the code is compiled again
+fn apply_fix(fix: &QuickFix) {
+    insert_text(fix.position.line, fix.position.col, &fix.insert);
+    compiler::compile(&buttons::get_code_input());
+}
+
+fn is_filter_checked(document: &Document, filter_id: &str) -> bool {
+    return document.get_element_by_id(filter_id).expect("Should be able to find the element")
+        .dyn_into::<HtmlInputElement>()
+        .expect("Should be able to cast to an HtmlInputElement object")
+        .checked();
+}
+
+pub fn clear_display() {
+    let document: Document = get_document();
+    document.get_element_by_id("issues-list").expect("Should be able to find the element").set_inner_html("");
+    document.get_element_by_id("issues-count").expect("Should be able to find the element").set_inner_html("");
+}
+
+fn get_document() -> Document {
+    let window: Window = web_sys::window().expect("Should be able to get the window");
+    return window.document().expect("Should be able to get the document");
+}
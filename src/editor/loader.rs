@@ -0,0 +1,173 @@
+use wasm_bindgen::{prelude::*, JsCast};
+use web_sys::{Window, Response};
+use js_sys::Promise;
+
+use crate::editor::share;
+use crate::nexus::compiler;
+use crate::util::nexus_log;
+
+// Have to import the editor js module
+#[wasm_bindgen(module = "/editor.js")]
+extern "C" {
+    #[wasm_bindgen(js_name = "loadProgram")]
+    fn load_program(new_code: &str);
+}
+
+// The most source code a link can load, which is far more than any program for the course
+pub const MAX_LINKED_SOURCE_BYTES: usize = 64 * 1024;
+
+// Gists are only downloaded from here, so a link cannot make the page request anything else
+const RAW_GIST_HOST: &str = "https://gist.githubusercontent.com/";
+
+// The source code a link to the page starts with
+#[derive (Debug, PartialEq)]
+pub enum LinkedSource {
+    // The code itself, such as index.html?src=%7B%7D%24 for {}$
+    Source(String),
+    // The raw link of a gist to download, such as index.html?gist=https://gist.githubusercontent.com/user/id/raw/lab.txt
+    Gist(String)
+}
+
+impl LinkedSource {
+    // Function to read the source from the query of the page's link, which is None if the link does not have one
+    pub fn from_query(query: &str) -> Result<Option<Self>, String> {
+        let src: Option<String> = get_query_parameter(query, "src")?;
+        let gist: Option<String> = get_query_parameter(query, "gist")?;
+
+        return match (src, gist) {
+            (Some(_), Some(_)) => Err(String::from("A link can have a src or a gist, but not both")),
+            (Some(source), None) => {
+                check_source_size(&source)?;
+                Ok(Some(LinkedSource::Source(source)))
+            },
+            (None, Some(gist_url)) => {
+                if !gist_url.starts_with(RAW_GIST_HOST) || gist_url.contains(char::is_whitespace) {
+                    return Err(format!("Invalid gist [ {} ], which must be a raw gist link starting with {}", gist_url, RAW_GIST_HOST));
+                }
+                Ok(Some(LinkedSource::Gist(gist_url)))
+            },
+            (None, None) => Ok(None)
+        };
+    }
+}
+
+// Function to get the decoded value of the parameter in the query, such as hello world for ?src=hello+world
+fn get_query_parameter(query: &str, name: &str) -> Result<Option<String>, String> {
+    return match query.trim_start_matches('?')
+        .split('&')
+        .filter_map(|parameter| parameter.split_once('='))
+        .find(|(parameter_name, _)| parameter_name.eq(&name)) {
+        Some((_, value)) => decode_query_value(value).map(Some),
+        None => Ok(None)
+    };
+}
+
+// Function to undo the percent encoding of a value in the query, where + is a space
+pub fn decode_query_value(value: &str) -> Result<String, String> {
+    let mut bytes: Vec<u8> = Vec::with_capacity(value.len());
+    let value_bytes: &[u8] = value.as_bytes();
+    let mut index: usize = 0;
+    while index < value_bytes.len() {
+        match value_bytes[index] {
+            b'+' => bytes.push(b' '),
+            b'%' => {
+                let hex_digits: &str = value.get(index + 1..value.len().min(index + 3)).unwrap_or("");
+                match u8::from_str_radix(hex_digits, 16) {
+                    Ok(byte) if hex_digits.len() == 2 => bytes.push(byte),
+                    _ => return Err(format!("Invalid escape [ %{} ] in the link", hex_digits))
+                }
+                index += 2;
+            },
+            byte => bytes.push(byte)
+        }
+        index += 1;
+    }
+    return String::from_utf8(bytes).map_err(|_| String::from("The source in the link is not valid UTF-8"));
+}
+
+// Function to make sure the source is small enough to be loaded from a link
+pub fn check_source_size(source: &str) -> Result<(), String> {
+    if source.len() > MAX_LINKED_SOURCE_BYTES {
+        return Err(format!("The linked source has {} bytes, but a link can load at most {} bytes", source.len(), MAX_LINKED_SOURCE_BYTES));
+    }
+    return Ok(());
+}
+
+// Function to load the source in the link of the page into the editor
+// An instructor can share a link with the code of a lab so it is already there when the page opens
+pub fn load_linked_source() {
+    // A shared program has the options too, so it is used instead of the query
+    if share::load_shared_program() {
+        return;
+    }
+
+    let window: Window = web_sys::window().expect("Should be able to get the window");
+    let query: String = window.location().search().unwrap_or_default();
+
+    match LinkedSource::from_query(&query) {
+        Ok(Some(LinkedSource::Source(source))) => show_linked_source(&source, "the link"),
+        Ok(Some(LinkedSource::Gist(gist_url))) => fetch_gist(&window, &gist_url),
+        Ok(None) => {},
+        Err(message) => log_load_error(message)
+    }
+}
+
+// Function to download the gist and load it once it is there, which does not hold up the rest of the page
+fn fetch_gist(window: &Window, gist_url: &str) {
+    nexus_log::log(
+        nexus_log::LogTypes::Info,
+        nexus_log::LogSource::NEXUS,
+        format!("Downloading the source from the gist [ {} ]", gist_url)
+    );
+
+    let on_response: Closure<dyn FnMut(JsValue)> = Closure::wrap(Box::new(|response_value: JsValue| {
+        let response: Response = response_value.dyn_into::<Response>().expect("The fetch should give a response");
+        if !response.ok() {
+            log_load_error(format!("The gist could not be downloaded (HTTP {})", response.status()));
+            return;
+        }
+
+        let on_text: Closure<dyn FnMut(JsValue)> = Closure::wrap(Box::new(|text_value: JsValue| {
+            show_linked_source(&text_value.as_string().unwrap_or_default(), "the gist");
+        }) as Box<dyn FnMut(JsValue)>);
+        catch_load_error(&response.text().expect("Should be able to read the response").then(&on_text));
+        on_text.forget();
+    }) as Box<dyn FnMut(JsValue)>);
+
+    // A network error or a gist that does not allow the page to read it rejects the fetch
+    catch_load_error(&window.fetch_with_str(gist_url).then(&on_response));
+    on_response.forget();
+}
+
+// Function to log why the promise was rejected
+fn catch_load_error(promise: &Promise) {
+    let on_error: Closure<dyn FnMut(JsValue)> = Closure::wrap(Box::new(|err: JsValue| {
+        log_load_error(format!("The gist could not be downloaded ({})", err.as_string().unwrap_or_else(|| String::from("network error"))));
+    }) as Box<dyn FnMut(JsValue)>);
+    let _ = promise.catch(&on_error);
+    on_error.forget();
+}
+
+// Function to put the linked source in the editor and compile it
+pub fn show_linked_source(source: &str, origin: &str) {
+    if let Err(message) = check_source_size(source) {
+        log_load_error(message);
+        return;
+    }
+
+    load_program(source);
+    nexus_log::log(
+        nexus_log::LogTypes::Info,
+        nexus_log::LogSource::NEXUS,
+        format!("Loaded the source from {}", origin)
+    );
+    compiler::compile(source);
+}
+
+fn log_load_error(message: String) {
+    nexus_log::log(
+        nexus_log::LogTypes::Error,
+        nexus_log::LogSource::NEXUS,
+        format!("Could not load the source in the link of the page: {}", message)
+    );
+}
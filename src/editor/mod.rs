@@ -1,2 +1,16 @@
 pub mod buttons;
-pub mod tests;
\ No newline at end of file
+pub mod tests;
+pub mod test_runner;
+pub mod repl;
+pub mod evaluator;
+pub mod settings;
+pub mod output;
+pub mod issues;
+pub mod comment;
+pub mod workspace;
+pub mod embed;
+pub mod command_palette;
+pub mod history;
+pub mod educator;
+pub mod loader;
+pub mod share;
\ No newline at end of file
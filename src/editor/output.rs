@@ -0,0 +1,83 @@
+use serde::{Serialize, Deserialize};
+use wasm_bindgen::{prelude::*, JsCast};
+use web_sys::{Document, Element};
+
+use crate::editor::settings::EditorSettings;
+
+// Have to import the editor js module
+#[wasm_bindgen(module = "/editor.js")]
+extern "C" {
+    #[wasm_bindgen(js_name = "setClipboard")]
+    fn set_clipboard(new_text: &str);
+
+    #[wasm_bindgen(js_name = "downloadFile")]
+    fn download_file(file_name: &str, text: &str, mime_type: &str);
+}
+
+// The line endings that copied and downloaded text can have
+#[derive (Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde (rename_all = "camelCase")]
+pub enum LineEnding {
+    Lf,
+    Crlf
+}
+
+impl LineEnding {
+    pub fn get_text(&self) -> &'static str {
+        return match self {
+            LineEnding::Lf => "\n",
+            LineEnding::Crlf => "\r\n"
+        };
+    }
+}
+
+// Function to show lines that are already HTML on their own lines in the page
+pub fn join_html_lines(html_lines: &Vec<String>) -> String {
+    return html_lines.join("<br>");
+}
+
+// Function to get the text with every line ending replaced, which works on text that has either kind
+pub fn to_plain_text(text: &str, line_ending: LineEnding) -> String {
+    let mut plain_text: String = text.lines().collect::<Vec<&str>>().join(line_ending.get_text());
+    if text.ends_with('\n') {
+        plain_text.push_str(line_ending.get_text());
+    }
+    return plain_text;
+}
+
+// Function to copy the text to the clipboard with the line ending from the settings
+pub fn copy_text(text: &str) {
+    set_clipboard(&to_plain_text(text, EditorSettings::load().line_ending));
+}
+
+// Function to save the text as a file on the user's device with the line ending from the settings
+pub fn download_text(file_name: &str, text: &str, mime_type: &str) {
+    download_file(file_name, &to_plain_text(text, EditorSettings::load().line_ending), mime_type);
+}
+
+// Function to add the buttons to copy the text and save it as a file to the element
+pub fn add_output_buttons(document: &Document, parent: &Element, text: String, file_name: String) {
+    let copy_btn: Element = document.create_element("button").expect("Should be able to create the element");
+    copy_btn.set_inner_html("Copy to Clipboard");
+    copy_btn.set_class_name("copy-btn");
+    parent.append_child(&copy_btn).expect("Should be able to add the child node");
+
+    let download_btn: Element = document.create_element("button").expect("Should be able to create the element");
+    download_btn.set_inner_html("Download");
+    download_btn.set_class_name("copy-btn");
+    parent.append_child(&download_btn).expect("Should be able to add the child node");
+
+    // The line ending is read when the button is clicked so it always matches the settings
+    let copy_text_clone: String = text.to_owned();
+    let copy_btn_fn: Closure<dyn FnMut()> = Closure::wrap(Box::new(move || {
+        copy_text(&copy_text_clone);
+    }) as Box<dyn FnMut()>);
+    copy_btn.add_event_listener_with_callback("click", copy_btn_fn.as_ref().unchecked_ref()).expect("Should be able to add the event listener");
+    copy_btn_fn.forget();
+
+    let download_btn_fn: Closure<dyn FnMut()> = Closure::wrap(Box::new(move || {
+        download_text(&file_name, &text, "text/plain");
+    }) as Box<dyn FnMut()>);
+    download_btn.add_event_listener_with_callback("click", download_btn_fn.as_ref().unchecked_ref()).expect("Should be able to add the event listener");
+    download_btn_fn.forget();
+}
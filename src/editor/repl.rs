@@ -0,0 +1,252 @@
+use std::cell::RefCell;
+
+use petgraph::graph::NodeIndex;
+use wasm_bindgen::{JsCast, prelude::Closure};
+use web_sys::{Document, Window, Element, HtmlInputElement, KeyboardEvent};
+
+use crate::nexus::{lexer::Lexer, token::Token, parser::Parser, syntax_tree::SyntaxTree, interpreter::{Interpreter, Value}, symbol_table::Type};
+use crate::nexus::semantic_analyzer::{SemanticAnalyzer, SemanticAnalysisResult};
+use crate::util::{nexus_log::{self, LogRecord, LogTypes}, target::{Target, TargetConfig}, html::escape_html};
+use crate::editor::{buttons, command_palette};
+
+// The statements that have been run so far in the REPL
+struct ReplSession {
+    // Every statement that was accepted, which get compiled again with each new statement
+    // so it is checked against the same symbol table
+    statements: Vec<String>,
+
+    // The number of statements in the AST of the accepted statements, which can be more than
+    // the number of statements typed because initializers become their own assignment
+    num_ast_statements: usize,
+
+    interpreter: Interpreter
+}
+
+// What happened when a statement was entered
+struct ReplResponse {
+    // The line of the statement in the program the REPL builds, which is used in the positions of the messages
+    line_number: usize,
+
+    // True if the statement was added to the session
+    is_accepted: bool,
+
+    output: Option<String>,
+    errors: Vec<String>,
+    warnings: Vec<String>
+}
+
+// The REPL lives for the whole page like the artifacts do
+thread_local! {
+    static REPL_SESSION: RefCell<ReplSession> = RefCell::new(ReplSession {
+        statements: Vec::new(),
+        num_ast_statements: 0,
+        interpreter: Interpreter::new(&TargetConfig::new(Target::Target6502))
+    });
+}
+
+// Function to set up the input and buttons of the REPL pane
+pub fn create_repl(document: &Document) {
+    let repl_run_btn: Element = document.get_element_by_id("repl-run-btn").expect("There should be an element called repl-run-btn");
+    let repl_run_fn: Closure<dyn FnMut()> = Closure::wrap(Box::new(move || {
+        submit_repl_input();
+    }) as Box<dyn FnMut()>);
+    repl_run_btn.add_event_listener_with_callback("click", repl_run_fn.as_ref().unchecked_ref()).expect("Should be able to add the event listener");
+    repl_run_fn.forget();
+
+    // Enter also runs the statement
+    let repl_input: Element = document.get_element_by_id("repl-input").expect("There should be an element called repl-input");
+    let repl_key_fn: Closure<dyn FnMut(KeyboardEvent)> = Closure::wrap(Box::new(move |event: KeyboardEvent| {
+        if event.key().eq("Enter") {
+            submit_repl_input();
+        }
+    }) as Box<dyn FnMut(KeyboardEvent)>);
+    repl_input.add_event_listener_with_callback("keydown", repl_key_fn.as_ref().unchecked_ref()).expect("Should be able to add the event listener");
+    repl_key_fn.forget();
+
+    let repl_reset_btn: Element = document.get_element_by_id("repl-reset-btn").expect("There should be an element called repl-reset-btn");
+    let repl_reset_fn: Closure<dyn FnMut()> = Closure::wrap(Box::new(move || {
+        REPL_SESSION.with(|session| {
+            let mut session_ref = session.borrow_mut();
+            session_ref.statements.clear();
+            session_ref.num_ast_statements = 0;
+            session_ref.interpreter.reset();
+        });
+
+        let window: Window = web_sys::window().expect("Should be able to get the window");
+        let document: Document = window.document().expect("Should be able to get the document");
+        document.get_element_by_id("repl-history").expect("Should be able to get the element").set_inner_html("");
+    }) as Box<dyn FnMut()>);
+    repl_reset_btn.add_event_listener_with_callback("click", repl_reset_fn.as_ref().unchecked_ref()).expect("Should be able to add the event listener");
+    repl_reset_fn.forget();
+
+    command_palette::register_click_action("Reset the REPL", "repl-reset-btn");
+}
+
+// Function to run the statement in the input and show the result
+fn submit_repl_input() {
+    let window: Window = web_sys::window().expect("Should be able to get the window");
+    let document: Document = window.document().expect("Should be able to get the document");
+
+    let repl_input: HtmlInputElement = document
+        .get_element_by_id("repl-input")
+        .expect("Should be able to get the element")
+        .dyn_into::<HtmlInputElement>()
+        .expect("The element should be recognized as an input element");
+
+    let statement: String = repl_input.value().trim().to_string();
+    if statement.len() == 0 {
+        return;
+    }
+
+    let response: ReplResponse = REPL_SESSION.with(|session| run_statement(&mut session.borrow_mut(), &statement));
+
+    // Keep the statement in the input so it can be fixed if it was not accepted
+    if response.is_accepted {
+        repl_input.set_value("");
+    }
+
+    let repl_history: Element = document.get_element_by_id("repl-history").expect("Should be able to get the element");
+    let mut lines: Vec<(&str, String)> = vec![("repl-statement", format!("{}: {}", response.line_number, escape_html(&statement)))];
+    if response.output.is_some() {
+        lines.push(("repl-output", escape_html(&response.output.unwrap())));
+    }
+    lines.extend(response.errors.iter().map(|error| ("repl-error", escape_html(error))));
+    lines.extend(response.warnings.iter().map(|warning| ("repl-warning", escape_html(warning))));
+
+    for (class_name, text) in lines {
+        let line_element: Element = document.create_element("p").expect("Should be able to create the element");
+        line_element.set_class_name(class_name);
+        line_element.set_inner_html(&text);
+        repl_history.append_child(&line_element).expect("Should be able to add the child");
+    }
+    repl_history.set_scroll_top(repl_history.scroll_height());
+}
+
+// Function to check the statement with everything before it and run it if it is valid
+fn run_statement(session: &mut ReplSession, statement: &str) -> ReplResponse {
+    // Line 1 is the open brace of the program
+    let mut response: ReplResponse = ReplResponse {
+        line_number: session.statements.len() + 2,
+        is_accepted: false,
+        output: None,
+        errors: Vec::new(),
+        warnings: Vec::new()
+    };
+
+    // The statements are put in a single program, so another $ would split it
+    if statement.contains('$') {
+        response.errors.push(String::from("Statements cannot contain the EOP symbol [ $ ]"));
+        return response;
+    }
+
+    let mut program_lines: Vec<&str> = session.statements.iter().map(|line| line.as_str()).collect();
+    program_lines.push(statement);
+    let program_code: String = format!("{{\n{}\n}}$", program_lines.join("\n"));
+
+    nexus_log::start_capture();
+    let analysis_res: Option<SemanticAnalysisResult> = analyze_program(&program_code);
+    let diagnostics: Vec<LogRecord> = nexus_log::stop_capture();
+
+    // Earlier statements were already accepted, so only the messages for the new line matter
+    let position_prefix: String = format!("({}, ", response.line_number);
+    for diagnostic in diagnostics.into_iter() {
+        if matches!(diagnostic.log_type, LogTypes::Error) {
+            response.errors.push(diagnostic.message);
+        } else if diagnostic.message.contains(&position_prefix) {
+            response.warnings.push(diagnostic.message);
+        }
+    }
+
+    if analysis_res.is_none() {
+        return response;
+    }
+
+    // Statements are stored in reverse order
+    let semantic_analysis_res: SemanticAnalysisResult = analysis_res.unwrap();
+    let ast: &SyntaxTree = &semantic_analysis_res.ast;
+    let program_statements: Vec<NodeIndex> = (*ast).graph.neighbors(NodeIndex::new((*ast).root.unwrap())).collect::<Vec<NodeIndex>>().into_iter().rev().collect();
+
+    let mut output: String = String::new();
+    for statement_index in program_statements[session.num_ast_statements..].iter() {
+        match session.interpreter.run_statement(ast, *statement_index) {
+            Ok(statement_output) => output.push_str(&statement_output),
+            Err(message) => {
+                response.errors.push(message);
+                break;
+            }
+        }
+    }
+    if output.len() > 0 {
+        response.output = Some(output);
+    }
+
+    // The statement may have changed variables even if it stopped early, so it stays in the session
+    session.statements.push(String::from(statement));
+    session.num_ast_statements = program_statements.len();
+    response.is_accepted = true;
+    return response;
+}
+
+// Function to get the value and type of an expression with the variables of the REPL session
+// The expression is checked as a print after the accepted statements, but nothing is added to the session
+pub fn evaluate_expression(expression: &str) -> Result<(Value, Type), Vec<String>> {
+    if expression.contains('$') {
+        return Err(vec![String::from("Expressions cannot contain the EOP symbol [ $ ]")]);
+    }
+
+    return REPL_SESSION.with(|session| {
+        let session_ref = session.borrow();
+
+        let mut program_lines: Vec<&str> = session_ref.statements.iter().map(|line| line.as_str()).collect();
+        let print_statement: String = format!("print({})", expression);
+        program_lines.push(&print_statement);
+        let program_code: String = format!("{{\n{}\n}}$", program_lines.join("\n"));
+
+        nexus_log::start_capture();
+        let analysis_res: Option<SemanticAnalysisResult> = analyze_program(&program_code);
+        let diagnostics: Vec<LogRecord> = nexus_log::stop_capture();
+
+        if analysis_res.is_none() {
+            let errors: Vec<String> = diagnostics.into_iter()
+                .filter(|diagnostic| matches!(diagnostic.log_type, LogTypes::Error))
+                .map(|diagnostic| diagnostic.message)
+                .collect();
+            return Err(errors);
+        }
+
+        // The print is the last statement, which is the first child because they are stored in reverse order
+        let semantic_analysis_res: SemanticAnalysisResult = analysis_res.unwrap();
+        let ast: &SyntaxTree = &semantic_analysis_res.ast;
+        let print_index: NodeIndex = (*ast).graph.neighbors(NodeIndex::new((*ast).root.unwrap())).next().unwrap();
+        let expr_index: NodeIndex = (*ast).graph.neighbors(print_index).next().unwrap();
+
+        let expr_type: Type = semantic_analysis_res.node_types.get(&expr_index).expect("Should have the type of the expression").to_owned();
+        return match session_ref.interpreter.evaluate(ast, expr_index) {
+            Ok(value) => Ok((value, expr_type)),
+            Err(message) => Err(vec![message])
+        };
+    });
+}
+
+// Function to run the program through semantic analysis with the logs captured
+fn analyze_program(program_code: &str) -> Option<SemanticAnalysisResult> {
+    let mut lexer: Lexer = Lexer::new(program_code);
+    let lex_res: Result<Vec<Token>, ()> = lexer.lex_program();
+    if lex_res.is_err() {
+        return None;
+    }
+
+    let token_stream: Vec<Token> = lex_res.unwrap();
+    let mut parser: Parser = Parser::new();
+    if parser.parse_program(&token_stream).is_err() {
+        return None;
+    }
+
+    let mut semantic_analyzer: SemanticAnalyzer = SemanticAnalyzer::new(TargetConfig::new(buttons::get_current_target()));
+    let ast: SyntaxTree = semantic_analyzer.generate_ast(&token_stream);
+    let semantic_analysis_res: SemanticAnalysisResult = semantic_analyzer.analyze_program(ast);
+    if !semantic_analysis_res.is_successful() {
+        return None;
+    }
+    return Some(semantic_analysis_res);
+}
@@ -0,0 +1,176 @@
+use serde::{Serialize, Deserialize};
+use wasm_bindgen::{prelude::*, JsCast};
+use web_sys::{Window, Document, Element, HtmlInputElement, HtmlSelectElement, Storage};
+
+use crate::editor::output::LineEnding;
+
+// Have to import the editor js module
+#[wasm_bindgen(module = "/editor.js")]
+extern "C" {
+    #[wasm_bindgen(js_name = "setEditorTheme")]
+    fn set_editor_theme(is_dark: bool);
+
+    #[wasm_bindgen(js_name = "setEditorFontSize")]
+    fn set_editor_font_size(font_size: u32);
+
+    #[wasm_bindgen(js_name = "setEditorWrap")]
+    fn set_editor_wrap(wrap: bool);
+}
+
+// The key the settings are saved under in local storage
+const SETTINGS_KEY: &str = "nexus-editor-settings";
+
+// The range of font sizes that are allowed, where the upper end is big enough for projectors
+pub const MIN_FONT_SIZE: u32 = 8;
+pub const MAX_FONT_SIZE: u32 = 32;
+
+#[derive (Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde (rename_all = "camelCase")]
+pub enum EditorTheme {
+    Light,
+    Dark
+}
+
+// The settings of the code editor that are kept between visits
+#[derive (Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde (rename_all = "camelCase", default)]
+pub struct EditorSettings {
+    pub theme: EditorTheme,
+    pub font_size: u32,
+    pub wrap: bool,
+
+    // The line ending of text that is copied or downloaded
+    pub line_ending: LineEnding
+}
+
+impl Default for EditorSettings {
+    fn default() -> Self {
+        // Matches the look of the editor before there were settings
+        return EditorSettings {
+            theme: EditorTheme::Light,
+            font_size: 12,
+            wrap: false,
+            line_ending: LineEnding::Lf
+        };
+    }
+}
+
+impl EditorSettings {
+    // Reads the settings from their JSON, where anything missing or invalid uses the default
+    pub fn from_json(settings_json: &str) -> Self {
+        let mut settings: EditorSettings = serde_json::from_str(settings_json).unwrap_or_default();
+        settings.font_size = settings.font_size.clamp(MIN_FONT_SIZE, MAX_FONT_SIZE);
+        return settings;
+    }
+
+    pub fn to_json(&self) -> String {
+        return serde_json::to_string(self).expect("Should be able to serialize the editor settings");
+    }
+
+    // Loads the saved settings, or the default ones if nothing has been saved yet
+    pub fn load() -> Self {
+        return match get_local_storage().and_then(|storage| storage.get_item(SETTINGS_KEY).ok().flatten()) {
+            Some(settings_json) => EditorSettings::from_json(&settings_json),
+            None => EditorSettings::default()
+        };
+    }
+
+    pub fn save(&self) {
+        // The settings still work for the visit when local storage is not available, such as in private browsing
+        if let Some(storage) = get_local_storage() {
+            let _ = storage.set_item(SETTINGS_KEY, &self.to_json());
+        }
+    }
+
+    // Updates the editor to use the settings
+    pub fn apply(&self) {
+        set_editor_theme(matches!(self.theme, EditorTheme::Dark));
+        set_editor_font_size(self.font_size);
+        set_editor_wrap(self.wrap);
+    }
+}
+
+pub fn get_local_storage() -> Option<Storage> {
+    let window: Window = web_sys::window().expect("Should be able to get the window");
+    return window.local_storage().ok().flatten();
+}
+
+// Function to set up the editor settings panel with the saved settings
+pub fn create_settings_panel(document: &Document) {
+    let settings: EditorSettings = EditorSettings::load();
+    settings.apply();
+    show_settings(document, &settings);
+
+    let (theme_select, font_size_input, wrap_input, line_ending_select) = get_settings_inputs(document);
+
+    // Every input shares the same function that reads all of the settings, applies them, and saves them
+    let settings_fn: Closure<dyn FnMut()> = Closure::wrap(Box::new(|| {
+        let window: Window = web_sys::window().expect("Should be able to get the window");
+        let document: Document = window.document().expect("Should be able to get the document");
+
+        let (theme_select, font_size_input, wrap_input, line_ending_select) = get_settings_inputs(&document);
+        let font_size: u32 = match font_size_input.value().trim().parse::<u32>() {
+            Ok(font_size) => font_size.clamp(MIN_FONT_SIZE, MAX_FONT_SIZE),
+            // Keep the last good size while the input is being typed in
+            Err(_) => EditorSettings::load().font_size
+        };
+
+        let new_settings: EditorSettings = EditorSettings {
+            theme: if theme_select.value().eq("dark") { EditorTheme::Dark } else { EditorTheme::Light },
+            font_size: font_size,
+            wrap: wrap_input.checked(),
+            line_ending: if line_ending_select.value().eq("crlf") { LineEnding::Crlf } else { LineEnding::Lf }
+        };
+        new_settings.apply();
+        new_settings.save();
+    }) as Box<dyn FnMut()>);
+
+    let settings_inputs: [Element; 4] = [theme_select.into(), font_size_input.into(), wrap_input.into(), line_ending_select.into()];
+    for settings_input in settings_inputs.iter() {
+        settings_input.add_event_listener_with_callback("change", settings_fn.as_ref().unchecked_ref()).expect("Should be able to add the event listener");
+    }
+    settings_fn.forget();
+}
+
+// Function to update the inputs of the settings panel to match the settings
+pub fn show_settings(document: &Document, settings: &EditorSettings) {
+    let (theme_select, font_size_input, wrap_input, line_ending_select) = get_settings_inputs(document);
+    theme_select.set_value(match settings.theme {
+        EditorTheme::Light => "light",
+        EditorTheme::Dark => "dark"
+    });
+    font_size_input.set_value(settings.font_size.to_string().as_str());
+    wrap_input.set_checked(settings.wrap);
+    line_ending_select.set_value(match settings.line_ending {
+        LineEnding::Lf => "lf",
+        LineEnding::Crlf => "crlf"
+    });
+}
+
+fn get_settings_inputs(document: &Document) -> (HtmlSelectElement, HtmlInputElement, HtmlInputElement, HtmlSelectElement) {
+    let theme_select: HtmlSelectElement = document
+        .get_element_by_id("editor-theme")
+        .expect("Should be able to get the element")
+        .dyn_into::<HtmlSelectElement>()
+        .expect("The element should be recognized as a select element");
+
+    let font_size_input: HtmlInputElement = document
+        .get_element_by_id("editor-font-size")
+        .expect("Should be able to get the element")
+        .dyn_into::<HtmlInputElement>()
+        .expect("The element should be recognized as an input element");
+
+    let wrap_input: HtmlInputElement = document
+        .get_element_by_id("editor-wrap")
+        .expect("Should be able to get the element")
+        .dyn_into::<HtmlInputElement>()
+        .expect("The element should be recognized as an input element");
+
+    let line_ending_select: HtmlSelectElement = document
+        .get_element_by_id("editor-line-ending")
+        .expect("Should be able to get the element")
+        .dyn_into::<HtmlSelectElement>()
+        .expect("The element should be recognized as a select element");
+
+    return (theme_select, font_size_input, wrap_input, line_ending_select);
+}
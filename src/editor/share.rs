@@ -0,0 +1,176 @@
+use serde::{Serialize, Deserialize};
+use wasm_bindgen::{prelude::Closure, JsCast};
+use web_sys::{Window, Document, Element, Location};
+
+use crate::editor::{buttons, command_palette, output, loader, workspace::CompilerOptionsJson};
+use crate::util::nexus_log;
+
+// The version of the shared program, which works the same way as the workspace version
+pub const SHARE_VERSION: u32 = 1;
+
+// What comes before the shared program in the fragment of the link, such as index.html#share=...
+const SHARE_PREFIX: &str = "#share=";
+
+// The characters of URL safe base64, which can be in a link without being escaped
+const BASE64_URL_CHARS: &[u8; 64] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789-_";
+
+// The most JSON a shared link can expand to, which leaves room for the options and the escapes of the source
+const MAX_SHARED_JSON_BYTES: usize = 2 * loader::MAX_LINKED_SOURCE_BYTES;
+
+// The code in the editor and the compiler options, which are compressed into the link
+#[derive (Debug, Serialize, Deserialize)]
+#[serde (rename_all = "camelCase")]
+pub struct SharedProgramJson {
+    pub version: u32,
+    pub source: String,
+    #[serde (default)]
+    pub compiler_options: CompilerOptionsJson
+}
+
+impl SharedProgramJson {
+    // Function to get the fragment of the link, which is the JSON compressed with deflate and written in base64
+    pub fn to_fragment(&self) -> String {
+        let shared_json: String = serde_json::to_string(self).expect("Should be able to serialize the shared program");
+        let compressed: Vec<u8> = miniz_oxide::deflate::compress_to_vec(shared_json.as_bytes(), 9);
+        return format!("{}{}", SHARE_PREFIX, encode_base64_url(&compressed));
+    }
+
+    // Reads and checks the shared program in the fragment of the link, which is None if the link does not have one
+    pub fn from_fragment(fragment: &str) -> Result<Option<Self>, String> {
+        let encoded: &str = match fragment.strip_prefix(SHARE_PREFIX) {
+            Some(encoded) => encoded,
+            None => return Ok(None)
+        };
+
+        let compressed: Vec<u8> = decode_base64_url(encoded)?;
+        let shared_bytes: Vec<u8> = miniz_oxide::inflate::decompress_to_vec_with_limit(&compressed, MAX_SHARED_JSON_BYTES)
+            .map_err(|_| String::from("The shared link is not a compressed program or is too large"))?;
+        let shared_json: String = String::from_utf8(shared_bytes).map_err(|_| String::from("The shared program is not valid UTF-8"))?;
+
+        // The version is checked first so a link from a newer version gets a clear error instead of a missing field
+        let shared_value: serde_json::Value = serde_json::from_str(&shared_json).map_err(|err| format!("Invalid shared program JSON: {}", err))?;
+        match shared_value.get("version").and_then(|version| version.as_u64()) {
+            Some(version) if version == SHARE_VERSION as u64 => {},
+            Some(version) => return Err(format!("Shared program version {} is not supported, which must be {}", version, SHARE_VERSION)),
+            None => return Err(String::from("Invalid shared program, which must have a version number"))
+        }
+
+        let shared_program: SharedProgramJson = serde_json::from_value(shared_value).map_err(|err| format!("Invalid shared program: {}", err))?;
+        loader::check_source_size(&shared_program.source)?;
+        shared_program.compiler_options.validate()?;
+        return Ok(Some(shared_program));
+    }
+}
+
+// Function to write the bytes as base64 with the URL safe characters and without the padding
+pub fn encode_base64_url(bytes: &[u8]) -> String {
+    let mut encoded: String = String::with_capacity(bytes.len().div_ceil(3) * 4);
+    for chunk in bytes.chunks(3) {
+        let group: u32 = chunk.iter().enumerate().fold(0, |group, (i, byte)| group | (*byte as u32) << (16 - 8 * i));
+        // Every character has 6 bits, so a chunk of n bytes needs n + 1 characters
+        for i in 0..chunk.len() + 1 {
+            encoded.push(BASE64_URL_CHARS[(group >> (18 - 6 * i) & 0x3F) as usize] as char);
+        }
+    }
+    return encoded;
+}
+
+// Function to read base64 with the URL safe characters and without the padding back into bytes
+pub fn decode_base64_url(encoded: &str) -> Result<Vec<u8>, String> {
+    let mut bytes: Vec<u8> = Vec::with_capacity(encoded.len() / 4 * 3 + 2);
+    for chunk in encoded.as_bytes().chunks(4) {
+        // A single character is not a whole byte, so the link was cut off
+        if chunk.len() == 1 {
+            return Err(String::from("The shared link is incomplete"));
+        }
+
+        let mut group: u32 = 0;
+        for (i, character) in chunk.iter().enumerate() {
+            let value: usize = BASE64_URL_CHARS.iter().position(|base64_char| base64_char == character)
+                .ok_or_else(|| format!("Invalid character [ {} ] in the shared link", *character as char))?;
+            group |= (value as u32) << (18 - 6 * i);
+        }
+        for i in 0..chunk.len() - 1 {
+            bytes.push((group >> (16 - 8 * i)) as u8);
+        }
+    }
+    return Ok(bytes);
+}
+
+// Function to set up the share button, which copies a link with the code and options in it
+pub fn create_share_button(document: &Document) {
+    let share_btn: Element = document
+        .get_element_by_id("share-btn")
+        .expect("There should be an element called share-btn");
+
+    let share_fn: Closure<dyn FnMut()> = Closure::wrap(Box::new(|| {
+        share_program();
+    }) as Box<dyn FnMut()>);
+    share_btn.add_event_listener_with_callback("click", share_fn.as_ref().unchecked_ref()).expect("Should be able to add the event listener");
+    share_fn.forget();
+
+    command_palette::register_click_action("Share", "share-btn");
+}
+
+// Function to put the code and options in the link of the page and copy it
+fn share_program() {
+    let shared_program: SharedProgramJson = SharedProgramJson {
+        version: SHARE_VERSION,
+        source: buttons::get_code_input(),
+        compiler_options: CompilerOptionsJson::from_page()
+    };
+    if let Err(message) = loader::check_source_size(&shared_program.source).and_then(|_| shared_program.compiler_options.validate()) {
+        nexus_log::log(
+            nexus_log::LogTypes::Error,
+            nexus_log::LogSource::NEXUS,
+            format!("Could not share the program: {}", message)
+        );
+        return;
+    }
+
+    // The rest of the query is kept so a link with a locked stage stays locked, but the code is only in the fragment
+    let window: Window = web_sys::window().expect("Should be able to get the window");
+    let location: Location = window.location();
+    let query_parameters: Vec<String> = location.search().unwrap_or_default()
+        .trim_start_matches('?')
+        .split('&')
+        .filter(|parameter| parameter.len() > 0 && !parameter.starts_with("src=") && !parameter.starts_with("gist="))
+        .map(|parameter| String::from(parameter))
+        .collect();
+    let query: String = if query_parameters.len() > 0 { format!("?{}", query_parameters.join("&")) } else { String::new() };
+
+    let fragment: String = shared_program.to_fragment();
+    location.set_hash(&fragment).expect("Should be able to set the hash");
+    let share_link: String = format!("{}{}{}{}", location.origin().unwrap_or_default(), location.pathname().unwrap_or_default(), query, fragment);
+    output::copy_text(&share_link);
+
+    nexus_log::log(
+        nexus_log::LogTypes::Info,
+        nexus_log::LogSource::NEXUS,
+        format!("Copied a link to the program to the clipboard ({} characters)", share_link.len())
+    );
+}
+
+// Function to load the shared program in the link of the page, which returns false if the link does not have one
+// The options are shown but not saved, so opening a shared link does not change the options of the next visit
+pub fn load_shared_program() -> bool {
+    let window: Window = web_sys::window().expect("Should be able to get the window");
+    let fragment: String = window.location().hash().unwrap_or_default();
+
+    return match SharedProgramJson::from_fragment(&fragment) {
+        Ok(Some(shared_program)) => {
+            shared_program.compiler_options.show();
+            loader::show_linked_source(&shared_program.source, "the shared link");
+            true
+        },
+        Ok(None) => false,
+        Err(message) => {
+            nexus_log::log(
+                nexus_log::LogTypes::Error,
+                nexus_log::LogSource::NEXUS,
+                format!("Could not load the shared program in the link of the page: {}", message)
+            );
+            true
+        }
+    };
+}
@@ -0,0 +1,2080 @@
+use wasm_bindgen::{JsCast, prelude::Closure};
+use web_sys::{Document, Window, Element, DomTokenList};
+
+use crate::nexus::{preprocessor::Preprocessor, lexer::Lexer, token::Token, parser::Parser, program_cache, syntax_tree::SyntaxTree};
+use crate::nexus::semantic_analyzer::{SemanticAnalyzer, SemanticAnalysisResult};
+use crate::nexus::{code_generator_6502::CodeGenerator6502, code_generator_riscv::{self, CodeGeneratorRiscV}};
+use crate::nexus::{coverage::CoverageJson, source_map::SourceMapJson};
+use crate::nexus::{balance, batch::{self, BatchSource, BatchResult}, pipeline::CompileOptions, fingerprint, grading::{self, Rubric, GradeReport}, riscv_validator, semantic_tokens, emulator_6502::Emulator6502, emulator_riscv::EmulatorRiscV, disassembler_6502, assembler_6502, differential};
+use crate::util::{nexus_log::{self, LogRecord, RecordedLog}, messages, position::Position, language_profile::LanguageProfile, diff::{self, DiffItem}, test::*, diagnostic::{ErrorClass, QuickFix}, target::{Target, TargetConfig, MmioConfig, SyscallConvention}, html, program_generator::{ProgramGenerator, ProgramGeneratorConfig}, program_minimizer::{self, Failure}, program_mutator::{self, ProgramMutator, DefectClass}};
+use crate::editor::{comment, command_palette, workspace::{WorkspaceJson, CompilerOptionsJson}, embed::EmbedOptions, loader::LinkedSource, share::{self, SharedProgramJson}};
+
+// The result of running a single test case
+struct TestOutcome {
+    passed: bool,
+
+    // Line diff between the expected and actual results
+    diff: String
+}
+
+// Function to set up the button that runs the test suite
+pub fn create_test_runner(document: &Document) {
+    let run_tests_btn: Element = document
+        .get_element_by_id("run-tests-btn")
+        .expect("There should be an element called run-tests-btn");
+
+    let run_tests_fn: Closure<dyn FnMut()> = Closure::wrap(Box::new(move || {
+        run_test_suite();
+    }) as Box<dyn FnMut()>);
+
+    run_tests_btn.add_event_listener_with_callback("click", run_tests_fn.as_ref().unchecked_ref()).expect("Should be able to add the event listener");
+    run_tests_fn.forget();
+
+    command_palette::register_click_action("Run test suite", "run-tests-btn");
+}
+
+// Function to run every test case and show the results
+fn run_test_suite() {
+    let window: Window = web_sys::window().expect("Should be able to get the window");
+    let document: Document = window.document().expect("Should be able to get the document");
+
+    let results_area: Element = document.get_element_by_id("test-results").expect("Should be able to find the element");
+    results_area.set_inner_html("");
+
+    // The phases write to the log, so start with a clean one
+    nexus_log::clear_logs();
+
+    let test_cases: Vec<TestCase> = get_test_cases();
+    let mut num_passed: usize = 0;
+
+    for test_case in test_cases.iter() {
+        nexus_log::log(
+            nexus_log::LogTypes::Info,
+            nexus_log::LogSource::NEXUS,
+            format!("Running test [{}] - {}", test_case.test_type, test_case.test_name)
+        );
+
+        let outcome: TestOutcome = run_test_case(test_case);
+        if outcome.passed {
+            num_passed += 1;
+        }
+        nexus_log::insert_empty_line();
+
+        // Each test gets its own row with the diff shown on failure
+        let result_row: Element = document.create_element("div").expect("Should be able to create the element");
+        let result_classes: DomTokenList = result_row.class_list();
+        result_classes.add_1("test-result").expect("Should be able to add the class");
+
+        let result_text: Element = document.create_element("p").expect("Should be able to create the element");
+        if outcome.passed {
+            result_classes.add_1("test-passed").expect("Should be able to add the class");
+            result_text.set_inner_html(format!("PASS [{}] - {}", test_case.test_type, test_case.test_name).as_str());
+        } else {
+            result_classes.add_1("test-failed").expect("Should be able to add the class");
+            result_text.set_inner_html(format!("FAIL [{}] - {}", test_case.test_type, test_case.test_name).as_str());
+        }
+        result_row.append_child(&result_text).expect("Should be able to add the child node");
+
+        if !outcome.passed {
+            let diff_elem: Element = document.create_element("pre").expect("Should be able to create the element");
+            diff_elem.class_list().add_1("test-diff").expect("Should be able to add the class");
+            diff_elem.set_text_content(Some(&outcome.diff));
+            result_row.append_child(&diff_elem).expect("Should be able to add the child node");
+        }
+
+        results_area.append_child(&result_row).expect("Should be able to add the child node");
+    }
+
+    let summary: Element = document.get_element_by_id("test-summary").expect("Should be able to find the element");
+    summary.set_inner_html(format!("{} of {} tests passed", num_passed, test_cases.len()).as_str());
+}
+
+// Function to run a single test case through as many phases as its expectation needs
+fn run_test_case(test_case: &TestCase) -> TestOutcome {
+    // String literals are checked without compiling a program
+    match &test_case.expectation {
+        TestExpectation::AssemblyString(_) => {
+            let escaped: String = code_generator_riscv::escape_assembly_string(&test_case.test_code);
+            let actual: String = match riscv_validator::decode_ascii_string(&escaped) {
+                Ok(bytes) if bytes.eq(test_case.test_code.as_bytes()) => escaped,
+                Ok(_) => format!("{} does not read back as the same string", escaped),
+                Err(message) => format!("{}: {}", escaped, message)
+            };
+            return compare(&expected_text(&test_case.expectation), &actual);
+        },
+        TestExpectation::HtmlText(_) => {
+            let escaped: String = html::escape_html(&test_case.test_code);
+            let actual: String = match html::unescape_html(&escaped) {
+                unescaped if unescaped.eq(&test_case.test_code) => escaped,
+                _ => format!("{} does not read back as the same text", escaped)
+            };
+            return compare(&expected_text(&test_case.expectation), &actual);
+        },
+        TestExpectation::ToggleComment(start_line, end_line, _) => {
+            return compare(&expected_text(&test_case.expectation), &comment::toggle_comment(&test_case.test_code, *start_line, *end_line));
+        },
+        TestExpectation::Workspace(_) => {
+            let actual: String = match WorkspaceJson::from_json(&test_case.test_code) {
+                Ok(workspace) => match WorkspaceJson::from_json(&workspace.to_json()) {
+                    Ok(_) => String::from("Valid workspace"),
+                    Err(message) => format!("Exported workspace is not valid: {}", message)
+                },
+                Err(message) => message
+            };
+            return compare(&expected_text(&test_case.expectation), &actual);
+        },
+        TestExpectation::SavedOptions(_) => {
+            let actual: String = match CompilerOptionsJson::from_saved_json(&test_case.test_code) {
+                Ok(compiler_options) => match CompilerOptionsJson::from_saved_json(&compiler_options.to_saved_json()) {
+                    Ok(_) => String::from("Valid saved options"),
+                    Err(message) => format!("Saved options are not valid once saved again: {}", message)
+                },
+                Err(message) => message
+            };
+            return compare(&expected_text(&test_case.expectation), &actual);
+        },
+        TestExpectation::LinkedSource(_) => {
+            let actual: String = match LinkedSource::from_query(&test_case.test_code) {
+                Ok(Some(LinkedSource::Source(source))) => source,
+                Ok(Some(LinkedSource::Gist(gist_url))) => gist_url,
+                Ok(None) => String::new(),
+                Err(message) => message
+            };
+            return compare(&expected_text(&test_case.expectation), &actual);
+        },
+        TestExpectation::SharedLink(options_json, Ok(())) => {
+            let shared_program: SharedProgramJson = SharedProgramJson {
+                version: share::SHARE_VERSION,
+                source: test_case.test_code.to_owned(),
+                compiler_options: serde_json::from_str(options_json).expect("The test options should be valid JSON")
+            };
+            let actual: String = match SharedProgramJson::from_fragment(&shared_program.to_fragment()) {
+                Ok(Some(read_program)) if !read_program.source.eq(&shared_program.source) => format!("The shared source changed to {:?}", read_program.source),
+                Ok(Some(read_program)) if !read_program.compiler_options.to_saved_json().eq(&shared_program.compiler_options.to_saved_json()) => {
+                    format!("The shared options changed to {}", read_program.compiler_options.to_saved_json())
+                },
+                Ok(Some(_)) => String::from("Same after sharing"),
+                Ok(None) => String::from("The link does not have a shared program"),
+                Err(message) => message
+            };
+            return compare(&expected_text(&test_case.expectation), &actual);
+        },
+        TestExpectation::SharedLink(_, Err(_)) => {
+            let actual: String = match SharedProgramJson::from_fragment(&test_case.test_code) {
+                Ok(_) => String::from("Read the shared program"),
+                Err(message) => message
+            };
+            return compare(&expected_text(&test_case.expectation), &actual);
+        },
+        TestExpectation::EmbedOptions(_) => {
+            let actual: String = match EmbedOptions::from_json(Some(test_case.test_code.to_owned())) {
+                Ok(_) => String::from("Valid options"),
+                Err(message) => message
+            };
+            return compare(&expected_text(&test_case.expectation), &actual);
+        },
+        TestExpectation::SortedPositions(_) => {
+            let mut positions: Vec<Position> = test_case.test_code.split_whitespace()
+                .filter_map(|position| position.split_once(':'))
+                .map(|(line, col)| Position::new(line.parse().unwrap_or(0), col.parse().unwrap_or(0)))
+                .collect();
+            positions.sort();
+            let actual: Vec<String> = positions.iter().map(|position| position.to_string()).collect();
+            return compare(&expected_text(&test_case.expectation), &actual.join("\n"));
+        },
+        TestExpectation::SemanticTokens(_) => {
+            let actual: Vec<String> = semantic_tokens::get_program_semantic_tokens(&test_case.test_code).iter()
+                .flat_map(|program| program.tokens.iter())
+                .map(|token| format!("{} ({}, {}) {:?}", token.id, token.line, token.column, token.kind))
+                .collect();
+            return compare(&expected_text(&test_case.expectation), &actual.join("\n"));
+        },
+        TestExpectation::Mutation(defect_class) => {
+            let actual: String = match ProgramMutator::new(1).mutate_with(&test_case.test_code, *defect_class) {
+                Some(mutated_program) => match program_mutator::check_mutated_program(&mutated_program) {
+                    Ok(_) => format!("Reported the {} defect", defect_class),
+                    Err(message) => format!("{} ({})", message, mutated_program.description)
+                },
+                None => format!("The code cannot have a {} defect", defect_class)
+            };
+            return compare(&expected_text(&test_case.expectation), &actual);
+        },
+        TestExpectation::MessageCodes(_) => {
+            let actual: Vec<String> = get_catalog_messages(&test_case.test_code).iter()
+                .map(|record| record.code.unwrap().to_string())
+                .collect();
+            return compare(&expected_text(&test_case.expectation), &actual.join("\n"));
+        },
+        TestExpectation::StringLengthCodes(target, max_string_length_input, _) => {
+            let mut target_config: TargetConfig = TargetConfig::new(*target);
+            let actual: String = match target_config.set_max_string_length(max_string_length_input) {
+                Ok(_) => batch::compile_source(&BatchSource::new(&test_case.test_name, &test_case.test_code), &target_config).diagnostics.iter()
+                    .filter_map(|record| record.code.map(|code| code.to_string()))
+                    .collect::<Vec<String>>()
+                    .join("\n"),
+                Err(message) => message
+            };
+            return compare(&expected_text(&test_case.expectation), &actual);
+        },
+        TestExpectation::TranslatedMessages(catalog_json, _) => {
+            if let Err(message) = messages::set_translations(catalog_json) {
+                return compare(&expected_text(&test_case.expectation), &message);
+            }
+            let actual: Vec<String> = get_catalog_messages(&test_case.test_code).iter()
+                .map(|record| record.message.to_owned())
+                .collect();
+            // Go back to English so the other tests and the page are not affected
+            messages::set_translations("{}").expect("Should be able to clear the translations");
+            return compare(&expected_text(&test_case.expectation), &actual.join("\n"));
+        },
+        TestExpectation::Similarity(other_source, _) => {
+            let actual: String = match fingerprint::compare_sources(&test_case.test_code, other_source) {
+                Ok(similarity) => format!("{}% similar", (similarity * 100.0).round()),
+                Err(_) => String::from("A source could not be parsed")
+            };
+            return compare(&expected_text(&test_case.expectation), &actual);
+        },
+        TestExpectation::Grade(rubric_json, _) => {
+            let actual: String = match Rubric::from_json(rubric_json) {
+                Ok(rubric) => {
+                    let grade_report: GradeReport = grading::grade_source(&rubric, &BatchSource::new(&test_case.test_name, &test_case.test_code));
+                    let mut lines: Vec<String> = vec![format!("{} of {} points", grade_report.score, grade_report.max_score)];
+                    for check in grade_report.checks.iter().filter(|check| !check.passed) {
+                        lines.push(format!("{}: {}", check.name, check.feedback.as_ref().unwrap()));
+                    }
+                    lines.join("\n")
+                },
+                Err(message) => message
+            };
+            return compare(&expected_text(&test_case.expectation), &actual);
+        },
+        TestExpectation::QuickFix(_) => {
+            let first_fix: Option<QuickFix> = batch::compile_source(&BatchSource::new(&test_case.test_name, &test_case.test_code), &TargetConfig::new(Target::Target6502))
+                .diagnostics.into_iter()
+                .find_map(|record| record.fix);
+            let actual: String = match first_fix {
+                Some(fix) => fix.apply(&test_case.test_code).unwrap_or(format!("Quick fix at {:?} is not in the code", fix.position)),
+                None => String::from("No quick fix")
+            };
+            return compare(&expected_text(&test_case.expectation), &actual);
+        },
+        TestExpectation::ProfileOutput(preset_input, charset_input, _) => {
+            let mut options: CompileOptions = CompileOptions::new(TargetConfig::new(Target::Target6502));
+            options.is_interpreted = true;
+            let actual: String = match LanguageProfile::from_inputs(preset_input, charset_input) {
+                Ok(language_profile) => {
+                    options.language_profile = language_profile;
+                    let batch_result: BatchResult = batch::compile_source_with_options(&BatchSource::new(&test_case.test_name, &test_case.test_code), &options);
+                    match batch_result.success || batch_result.programs.len() > 0 {
+                        true => batch_result.programs.iter()
+                            .map(|program| match (&program.output, &program.failed_stage) {
+                                (Some(output), None) => output.to_owned(),
+                                (_, Some(stage)) => format!(
+                                    "Program {} failed at {} with [ {} ]\n",
+                                    program.program_number,
+                                    stage,
+                                    program.diagnostics.iter()
+                                        .filter(|record| matches!(record.log_type, nexus_log::LogTypes::Error))
+                                        .filter_map(|record| record.code.map(|code| code.to_string()))
+                                        .collect::<Vec<String>>()
+                                        .join(", ")
+                                ),
+                                (None, None) => format!("Program {} was not run\n", program.program_number)
+                            })
+                            .collect(),
+                        // Nothing is compiled if the constants could not be expanded
+                        false => batch_result.diagnostics.iter()
+                            .filter(|record| matches!(record.log_type, nexus_log::LogTypes::Error))
+                            .map(|record| record.message.to_owned())
+                            .collect::<Vec<String>>()
+                            .join("\n")
+                    }
+                },
+                Err(message) => message
+            };
+            return compare(&expected_text(&test_case.expectation), &actual);
+        },
+        _ => {}
+    }
+
+    let mut preprocessor: Preprocessor = Preprocessor::new();
+    let preprocess_res: Result<String, ()> = preprocessor.expand_defines(&test_case.test_code);
+
+    match &test_case.expectation {
+        TestExpectation::PreprocessErrors(_) => {
+            let actual: String = match &preprocess_res {
+                Ok(_) => String::from("Preprocessing succeeded"),
+                Err(_) => format!("Preprocessing failed with {} errors", preprocessor.num_errors)
+            };
+            return compare(&expected_text(&test_case.expectation), &actual);
+        },
+        _ => {
+            // Everything else needs the constants to be expanded
+            if preprocess_res.is_err() {
+                return compare(&expected_text(&test_case.expectation), &format!("Preprocessing failed with {} errors", preprocessor.num_errors));
+            }
+        }
+    }
+
+    let mut lexer: Lexer = Lexer::new(&preprocess_res.unwrap());
+    lexer.set_column_map(preprocessor.get_column_map());
+
+    // Counting the programs has to lex all of them instead of just the first one
+    match &test_case.expectation {
+        TestExpectation::ProgramCount(_) => {
+            let mut program_count: u32 = 0;
+            while lexer.has_program_to_lex() {
+                program_count += 1;
+                let _ = lexer.lex_program();
+            }
+            return compare(&expected_text(&test_case.expectation), &format!("{} program(s)", program_count));
+        },
+        TestExpectation::CachedPrograms(edited_code, _) => {
+            return compare(&expected_text(&test_case.expectation), &get_cached_programs(&test_case.test_code, edited_code).join("\n"));
+        },
+        _ => {}
+    }
+
+    let lex_res: Result<Vec<Token>, ()> = lexer.lex_program();
+
+    match &test_case.expectation {
+        TestExpectation::Tokens(expected_tokens) => {
+            let expected: String = expected_tokens.join("\n");
+            let actual: String = match &lex_res {
+                Ok(token_stream) => token_stream.iter().map(|token| token.text.to_owned()).collect::<Vec<String>>().join("\n"),
+                Err(_) => format!("Lex failed with {} errors", lexer.num_errors)
+            };
+            return compare(&expected, &actual);
+        },
+        TestExpectation::TokenPositions(_) => {
+            let actual: String = match &lex_res {
+                Ok(token_stream) => token_stream.iter().map(|token| format!("{} {:?}", token.text, token.position)).collect::<Vec<String>>().join("\n"),
+                Err(_) => format!("Lex failed with {} errors", lexer.num_errors)
+            };
+            return compare(&expected_text(&test_case.expectation), &actual);
+        },
+        TestExpectation::LexErrors(expected_errors) => {
+            let actual: String = match &lex_res {
+                Ok(_) => String::from("Lex succeeded"),
+                Err(_) => format!("Lex failed with {} errors", lexer.num_errors)
+            };
+            return compare(&format!("Lex failed with {} errors", expected_errors), &actual);
+        },
+        _ => {
+            // Everything else needs the lexer to succeed
+            if lex_res.is_err() {
+                return compare(&expected_text(&test_case.expectation), &format!("Lex failed with {} errors", lexer.num_errors));
+            }
+        }
+    }
+
+    let token_stream: Vec<Token> = lex_res.unwrap();
+    if let TestExpectation::BalanceHints(_) = &test_case.expectation {
+        let actual: Vec<String> = balance::find_unbalanced(&token_stream).iter()
+            .map(|(position, hint)| format!("{} {:?}", hint.code, position))
+            .collect();
+        return compare(&expected_text(&test_case.expectation), &actual.join("\n"));
+    }
+
+    let mut parser: Parser = Parser::new();
+    let parse_res: Result<SyntaxTree, ()> = parser.parse_program(&token_stream);
+
+    match &test_case.expectation {
+        TestExpectation::ParseSucceeds | TestExpectation::ParseFails => {
+            let actual: &str = match &parse_res {
+                Ok(_) => "Parse succeeded",
+                Err(_) => "Parse failed"
+            };
+            return compare(&expected_text(&test_case.expectation), actual);
+        },
+        TestExpectation::ParseErrors(_) => {
+            let actual: String = match &parse_res {
+                Ok(_) => String::from("Parse succeeded"),
+                Err(_) => format!("Parse failed with {} errors", parser.num_errors)
+            };
+            return compare(&expected_text(&test_case.expectation), &actual);
+        },
+        _ => {
+            if parse_res.is_err() {
+                return compare(&expected_text(&test_case.expectation), "Parse failed");
+            }
+        }
+    }
+
+    let target: Target = match &test_case.expectation {
+        TestExpectation::RiscVProgramOutput(_) => Target::TargetRiscV,
+        _ => Target::Target6502
+    };
+    let mut semantic_analyzer: SemanticAnalyzer = SemanticAnalyzer::new(TargetConfig::new(target));
+    let ast: SyntaxTree = semantic_analyzer.generate_ast(&token_stream);
+    let mut semantic_analysis_res: SemanticAnalysisResult = semantic_analyzer.analyze_program(ast);
+
+    let actual_errors: String = semantic_analysis_res.errors.iter()
+        .map(|error| error.class.to_string())
+        .collect::<Vec<String>>()
+        .join("\n");
+
+    match &test_case.expectation {
+        TestExpectation::SemanticErrors(_) => return compare(&expected_text(&test_case.expectation), &actual_errors),
+        _ => {
+            if !semantic_analysis_res.is_successful() {
+                return compare(&expected_text(&test_case.expectation), &actual_errors);
+            }
+        }
+    }
+
+    match &test_case.expectation {
+        TestExpectation::ProgramOutput(_) => return compare(&expected_text(&test_case.expectation), &get_program_output(&mut semantic_analysis_res, None)),
+        TestExpectation::MmioProgramOutput(_) => {
+            let mmio: MmioConfig = MmioConfig { int_out: 0xFD, string_out: 0xFE };
+            return compare(&expected_text(&test_case.expectation), &get_program_output(&mut semantic_analysis_res, Some(mmio)));
+        },
+        TestExpectation::RiscVProgramOutput(_) => return compare(&expected_text(&test_case.expectation), &get_riscv_program_output(&mut semantic_analysis_res)),
+        TestExpectation::CodeSize(_, _) => return compare(&expected_text(&test_case.expectation), &get_code_size(&mut semantic_analysis_res)),
+        TestExpectation::OptimizedCode(_) => return compare(&expected_text(&test_case.expectation), &get_optimized_code(&mut semantic_analysis_res)),
+        TestExpectation::DisassemblyRoundTrip(num_generated) => return compare(&expected_text(&test_case.expectation), &get_round_trip_result(&mut semantic_analysis_res, *num_generated)),
+        TestExpectation::InterpreterMatches(num_generated) => return compare(&expected_text(&test_case.expectation), &get_interpreter_result(&mut semantic_analysis_res, *num_generated)),
+        TestExpectation::ScopeComments(_) => return compare(&expected_text(&test_case.expectation), &get_scope_comments(&mut semantic_analysis_res)),
+        TestExpectation::MinimizedProgram(failure, _) => return compare(&expected_text(&test_case.expectation), &get_minimized_program(&test_case.test_code, *failure)),
+        _ => {}
+    }
+
+    let mut code_generator: CodeGenerator6502 = CodeGenerator6502::new();
+    let actual: &str = match code_generator.generate_image(&mut semantic_analysis_res) {
+        true => "Code generation succeeded",
+        false => "Code generation failed"
+    };
+    return compare(&expected_text(&test_case.expectation), actual);
+}
+
+// Function to compile every program in the code and get the errors and warnings that came from the message catalog
+fn get_catalog_messages(code: &str) -> Vec<LogRecord> {
+    let batch_source: BatchSource = BatchSource {
+        name: String::from("test"),
+        source: String::from(code)
+    };
+    return batch::compile_source(&batch_source, &TargetConfig::new(Target::Target6502)).diagnostics.into_iter()
+        .filter(|record| record.code.is_some())
+        .collect();
+}
+
+// Function to lex and parse the code and then the edited code the way the compiler does, getting whether each edited program was reused
+// This starts the cache over, so the next compilation in the editor lexes every program
+fn get_cached_programs(code: &str, edited_code: &str) -> Vec<String> {
+    let mut parser: Parser = Parser::new();
+    program_cache::clear_cache();
+    program_cache::start_compilation(&LanguageProfile::new());
+    let mut lexer: Lexer = Lexer::new(code);
+    while lexer.has_program_to_lex() {
+        if let Ok(token_stream) = program_cache::lex_program(&mut lexer) {
+            let _ = program_cache::parse_program(&mut parser, &token_stream);
+        }
+    }
+
+    let mut programs: Vec<String> = Vec::new();
+    program_cache::start_compilation(&LanguageProfile::new());
+    lexer = Lexer::new(edited_code);
+    while lexer.has_program_to_lex() {
+        // The same program is lexed again from the same place to check the cached one
+        let mut fresh_lexer: Lexer = Lexer::new(edited_code);
+        fresh_lexer.set_cursor(lexer.get_cursor());
+
+        let cached: String = get_front_end_summary(&mut || {
+            let lex_res: Result<Vec<Token>, ()> = program_cache::lex_program(&mut lexer);
+            let parse_res: Option<Result<SyntaxTree, ()>> = lex_res.as_ref().ok().map(|token_stream| program_cache::parse_program(&mut parser, token_stream));
+            (lex_res, parse_res)
+        });
+        let is_reused: bool = program_cache::is_reused();
+        let fresh: String = get_front_end_summary(&mut || {
+            let lex_res: Result<Vec<Token>, ()> = fresh_lexer.lex_program();
+            let parse_res: Option<Result<SyntaxTree, ()>> = lex_res.as_ref().ok().map(|token_stream| Parser::new().parse_program(token_stream));
+            (lex_res, parse_res)
+        });
+
+        let mut program: String = format!("Program {} {}", programs.len() + 1, if is_reused { "reused" } else { "lexed" });
+        if !cached.eq(&fresh) || lexer.get_cursor() != fresh_lexer.get_cursor() {
+            program.push_str(", but it is different from lexing it again");
+        }
+        programs.push(program);
+    }
+    return programs;
+}
+
+// Function to get the tokens, CST, and positioned logs of lexing and parsing a program as text that can be compared
+fn get_front_end_summary(front_end_fn: &mut dyn FnMut() -> (Result<Vec<Token>, ()>, Option<Result<SyntaxTree, ()>>)) -> String {
+    nexus_log::start_recording();
+    let (lex_res, parse_res): (Result<Vec<Token>, ()>, Option<Result<SyntaxTree, ()>>) = front_end_fn();
+    let records: Vec<LogRecord> = nexus_log::stop_recording().into_iter()
+        .filter_map(|log| match log {
+            RecordedLog::Record(record) if record.position.is_some() => Some(record),
+            _ => None
+        })
+        .collect();
+
+    return format!("{:?}\n{:?}\n{}",
+        lex_res,
+        parse_res.map(|res| res.map(|cst| cst.to_sexpr())),
+        serde_json::to_string(&records).expect("Should be able to serialize the logs")
+    );
+}
+
+// Function to run the 6502 image and get what it prints, which should be the same with and without size optimization
+// With an output device, the image also should not have any system calls
+fn get_program_output(semantic_analysis_res: &mut SemanticAnalysisResult, mmio: Option<MmioConfig>) -> String {
+    let mut target_config: TargetConfig = TargetConfig::new(Target::Target6502);
+    if mmio.is_some() {
+        target_config.reserved_cells = 3;
+        target_config.mmio = mmio;
+    }
+
+    let mut outputs: Vec<String> = vec![
+        get_image_output(semantic_analysis_res, &target_config, false),
+        get_image_output(semantic_analysis_res, &target_config, true)
+    ];
+    if outputs[0].eq(&outputs[1]) {
+        return outputs.remove(0);
+    }
+    return format!("{}\n{} when optimizing for size", outputs[0], outputs[1]);
+}
+
+// Function to get the size of the 6502 code without and with size optimization
+fn get_code_size(semantic_analysis_res: &mut SemanticAnalysisResult) -> String {
+    let mut code_sizes: Vec<String> = Vec::new();
+    for optimize_size in [false, true] {
+        let mut code_generator: CodeGenerator6502 = CodeGenerator6502::new();
+        code_generator.set_optimize_size(optimize_size);
+        if !code_generator.generate_image(semantic_analysis_res) {
+            return String::from("Code generation failed");
+        }
+        code_sizes.push(format!("{} bytes", code_generator.get_memory_usage().0));
+    }
+    return format!("{}\n{} when optimizing for size", code_sizes[0], code_sizes[1]);
+}
+
+// Function to get the instructions of the 6502 code with size optimization
+fn get_optimized_code(semantic_analysis_res: &mut SemanticAnalysisResult) -> String {
+    let mut code_generator: CodeGenerator6502 = CodeGenerator6502::new();
+    code_generator.set_optimize_size(true);
+    if !code_generator.generate_image(semantic_analysis_res) {
+        return String::from("Code generation failed");
+    }
+
+    let code_size: usize = code_generator.get_memory_usage().0;
+    return match disassembler_6502::disassemble(&code_generator.get_image_bytes()[..code_size], code_size) {
+        Ok(lines) => lines.join("\n"),
+        Err(message) => format!("Disassembly failed: {}", message)
+    };
+}
+
+// Function to generate the 6502 image with or without size optimization and get what it prints
+fn get_image_output(semantic_analysis_res: &mut SemanticAnalysisResult, target_config: &TargetConfig, optimize_size: bool) -> String {
+    let mut code_generator: CodeGenerator6502 = CodeGenerator6502::new();
+    code_generator.set_optimize_size(optimize_size);
+    code_generator.set_memory_layout(target_config);
+    return match code_generator.generate_image(semantic_analysis_res) {
+        true => match run_image(&code_generator, target_config.mmio) {
+            Ok(output) => output,
+            Err(message) => format!("Program failed: {}", message)
+        },
+        false => String::from("Code generation failed")
+    };
+}
+
+// Function to run the 6502 image with the output device mapped
+fn run_image(code_generator: &CodeGenerator6502, mmio: Option<MmioConfig>) -> Result<String, String> {
+    let image: Vec<u8> = code_generator.get_image_bytes();
+    if mmio.is_some() {
+        let lines: Vec<String> = disassembler_6502::disassemble(&image, code_generator.get_memory_usage().0)?;
+        if lines.iter().any(|line| line.starts_with("SYS")) {
+            return Err(String::from("Image has a system call"));
+        }
+    }
+
+    let mut emulator: Emulator6502 = Emulator6502::from_bytes(&image)?;
+    emulator.set_mmio(mmio);
+    return emulator.run();
+}
+
+// Function to run the RISC-V assembly and get what it prints, which should be the same with every system call convention
+fn get_riscv_program_output(semantic_analysis_res: &mut SemanticAnalysisResult) -> String {
+    let syscall_conventions: [SyscallConvention; 5] = [
+        SyscallConvention::Linux,
+        SyscallConvention::Sbi,
+        SyscallConvention::Rars,
+        SyscallConvention::Venus,
+        SyscallConvention::Custom { write: 4, exit: 10 }
+    ];
+
+    let mut outputs: Vec<String> = Vec::new();
+    for syscall_convention in syscall_conventions {
+        let mut code_generator: CodeGeneratorRiscV = CodeGeneratorRiscV::new();
+        code_generator.set_syscall_convention(syscall_convention);
+        if !code_generator.generate_assembly(semantic_analysis_res) {
+            return String::from("Code generation failed");
+        }
+        let output: String = match EmulatorRiscV::from_assembly(&code_generator.get_assembly()).and_then(|mut emulator| {
+            emulator.set_syscall_convention(syscall_convention);
+            emulator.run()
+        }) {
+            Ok(output) => output,
+            Err(message) => format!("Program failed: {}", message)
+        };
+        outputs.push(output);
+    }
+
+    // Every output is compared with Linux, which is the default
+    for (syscall_convention, output) in syscall_conventions.iter().zip(outputs.iter()).skip(1) {
+        if !output.eq(&outputs[0]) {
+            return format!("{}\n{} with the {} system calls", outputs[0], output, syscall_convention);
+        }
+    }
+    return outputs.remove(0);
+}
+
+// Function to check the disassembler and assembler with the program and the generated programs
+fn get_round_trip_result(semantic_analysis_res: &mut SemanticAnalysisResult, num_generated: u64) -> String {
+    let mut failures: Vec<String> = Vec::new();
+    if let Err(message) = check_round_trip(semantic_analysis_res) {
+        failures.push(format!("Round trip failed: {}", message));
+    }
+
+    // The seeds are fixed so a failure can be reproduced with the generate button's program
+    for seed in 1..=num_generated {
+        let mut program_generator: ProgramGenerator = ProgramGenerator::new(ProgramGeneratorConfig::new(seed));
+        let check_res: Result<(), String> = analyze_generated_program(&program_generator.generate_program())
+            .and_then(|mut generated_res| check_round_trip(&mut generated_res));
+        if let Err(message) = check_res {
+            failures.push(format!("Round trip failed for the generated program with seed {}: {}", seed, message));
+        }
+    }
+
+    if failures.len() == 0 {
+        return String::from("Round trip matched");
+    }
+    return failures.join("\n");
+}
+
+// Function to disassemble the 6502 image and assemble it again, which should give back the same bytes
+// Both the plain and the size optimized images are checked
+fn check_round_trip(semantic_analysis_res: &mut SemanticAnalysisResult) -> Result<(), String> {
+    for optimize_size in [false, true] {
+        let mut code_generator: CodeGenerator6502 = CodeGenerator6502::new();
+        code_generator.set_optimize_size(optimize_size);
+
+        // Programs that do not fit in memory do not have an image to check
+        if !code_generator.generate_image(semantic_analysis_res) {
+            continue;
+        }
+
+        let image: Vec<u8> = code_generator.get_image_bytes();
+        let lines: Vec<String> = disassembler_6502::disassemble(&image, code_generator.get_memory_usage().0)?;
+        let assembled_image: Vec<u8> = assembler_6502::assemble(&lines)?;
+        if assembled_image.ne(&image) {
+            let mismatch: usize = image.iter().zip(assembled_image.iter())
+                .position(|(original, assembled)| original != assembled)
+                .unwrap_or(image.len().min(assembled_image.len()));
+            return Err(format!("The assembled image is different starting at ${:02X}", mismatch));
+        }
+    }
+    return Ok(());
+}
+
+// Function to check the code generators against the interpreter with the program and the generated programs
+fn get_interpreter_result(semantic_analysis_res: &mut SemanticAnalysisResult, num_generated: u64) -> String {
+    let mut failures: Vec<String> = Vec::new();
+    if let Err(message) = check_interpreter(semantic_analysis_res) {
+        failures.push(message);
+    }
+
+    // The seeds are fixed so a failure can be reproduced with the generate button's program
+    for seed in 1..=num_generated {
+        let mut program_generator: ProgramGenerator = ProgramGenerator::new(ProgramGeneratorConfig::new(seed));
+        let check_res: Result<(), String> = analyze_generated_program(&program_generator.generate_program())
+            .and_then(|mut generated_res| check_interpreter(&mut generated_res));
+        if let Err(message) = check_res {
+            failures.push(format!("For the generated program with seed {}: {}", seed, message));
+        }
+    }
+
+    if failures.len() == 0 {
+        return String::from("Outputs matched");
+    }
+    return failures.join("\n");
+}
+
+// Function to run the program with the interpreter and make sure both targets print the same
+fn check_interpreter(semantic_analysis_res: &mut SemanticAnalysisResult) -> Result<(), String> {
+    return match differential::compare_outputs(semantic_analysis_res) {
+        Ok(None) => Ok(()),
+        Ok(Some(divergence)) => Err(divergence.to_string()),
+        Err(message) => Err(format!("Interpreter failed: {}", message))
+    };
+}
+
+// Function to get the scope comments of the RISC-V assembly and the scopes in the 6502 source map
+fn get_scope_comments(semantic_analysis_res: &mut SemanticAnalysisResult) -> String {
+    let mut code_generator_riscv: CodeGeneratorRiscV = CodeGeneratorRiscV::new();
+    code_generator_riscv.set_scope_comments(true);
+    if !code_generator_riscv.generate_assembly(semantic_analysis_res) {
+        return String::from("Code generation failed");
+    }
+    let assembly: String = code_generator_riscv.get_assembly();
+    let mut lines: Vec<String> = assembly.lines()
+        .filter(|line| line.starts_with('#'))
+        .map(|line| String::from(line))
+        .collect();
+    if let Err(message) = EmulatorRiscV::from_assembly(&assembly).and_then(|mut emulator| emulator.run()) {
+        lines.push(format!("Program failed: {}", message));
+    }
+
+    let mut code_generator_6502: CodeGenerator6502 = CodeGenerator6502::new();
+    code_generator_6502.set_scope_comments(true);
+    if !code_generator_6502.generate_image(semantic_analysis_res) {
+        return String::from("Code generation failed");
+    }
+    let coverage: CoverageJson = CoverageJson::new("6502", "byte", &semantic_analysis_res.ast, code_generator_6502.get_coverage_ranges());
+    let source_map: SourceMapJson = SourceMapJson::new("6502", "address", &semantic_analysis_res.ast, &coverage, &code_generator_6502.get_scope_ranges(), 0);
+    lines.extend(source_map.scopes.iter().map(|scope_extent| format!("scope {} at line {}", scope_extent.scope, scope_extent.line)));
+    return lines.join("\n");
+}
+
+// Function to minimize the program for the failure, which it has to have to begin with
+fn get_minimized_program(program: &str, failure: Failure) -> String {
+    if !failure.is_failing(program) {
+        return format!("The program does not have the failure {:?}", failure);
+    }
+    return program_minimizer::minimize_failure(program, failure);
+}
+
+// Function to run a generated program through semantic analysis, which it should always pass
+fn analyze_generated_program(program: &str) -> Result<SemanticAnalysisResult, String> {
+    let mut lexer: Lexer = Lexer::new(program);
+    let token_stream: Vec<Token> = lexer.lex_program().map_err(|_| String::from("Lex failed"))?;
+    let mut parser: Parser = Parser::new();
+    parser.parse_program(&token_stream).map_err(|_| String::from("Parse failed"))?;
+
+    let mut semantic_analyzer: SemanticAnalyzer = SemanticAnalyzer::new(TargetConfig::new(Target::Target6502));
+    let ast: SyntaxTree = semantic_analyzer.generate_ast(&token_stream);
+    let semantic_analysis_res: SemanticAnalysisResult = semantic_analyzer.analyze_program(ast);
+    if !semantic_analysis_res.is_successful() {
+        return Err(String::from("Semantic analysis failed"));
+    }
+    return Ok(semantic_analysis_res);
+}
+
+// Text representation of the expectations that do not have their own output
+fn expected_text(expectation: &TestExpectation) -> String {
+    return match expectation {
+        TestExpectation::PreprocessErrors(expected_errors) => format!("Preprocessing failed with {} errors", expected_errors),
+        TestExpectation::Tokens(expected_tokens) | TestExpectation::TokenPositions(expected_tokens) => expected_tokens.join("\n"),
+        TestExpectation::LexErrors(expected_errors) => format!("Lex failed with {} errors", expected_errors),
+        TestExpectation::ProgramCount(expected_count) => format!("{} program(s)", expected_count),
+        TestExpectation::CachedPrograms(_, expected_programs) => expected_programs.join("\n"),
+        TestExpectation::ParseSucceeds => String::from("Parse succeeded"),
+        TestExpectation::ParseFails => String::from("Parse failed"),
+        TestExpectation::ParseErrors(expected_errors) => format!("Parse failed with {} errors", expected_errors),
+        TestExpectation::BalanceHints(expected_hints) => expected_hints.join("\n"),
+        TestExpectation::SemanticErrors(expected_errors) => expected_errors.iter().map(|class| class.to_string()).collect::<Vec<String>>().join("\n"),
+        TestExpectation::CodeGenSucceeds => String::from("Code generation succeeded"),
+        TestExpectation::CodeGenFails => String::from("Code generation failed"),
+        TestExpectation::OptimizedCode(expected_code) => expected_code.join("\n"),
+        TestExpectation::CodeSize(code_size, optimized_code_size) => format!("{} bytes\n{} bytes when optimizing for size", code_size, optimized_code_size),
+        TestExpectation::ProgramOutput(expected_output) | TestExpectation::MmioProgramOutput(expected_output) | TestExpectation::RiscVProgramOutput(expected_output) => String::from(*expected_output),
+        TestExpectation::DisassemblyRoundTrip(_) => String::from("Round trip matched"),
+        TestExpectation::InterpreterMatches(_) => String::from("Outputs matched"),
+        TestExpectation::ScopeComments(expected_lines) => expected_lines.join("\n"),
+        TestExpectation::MinimizedProgram(_, expected_program) => String::from(*expected_program),
+        TestExpectation::AssemblyString(expected_string) => String::from(*expected_string),
+        TestExpectation::HtmlText(expected_text) => String::from(*expected_text),
+        TestExpectation::ToggleComment(_, _, expected_code) => String::from(*expected_code),
+        TestExpectation::Workspace(Ok(())) => String::from("Valid workspace"),
+        TestExpectation::Workspace(Err(expected_error)) => String::from(*expected_error),
+        TestExpectation::SavedOptions(Ok(())) => String::from("Valid saved options"),
+        TestExpectation::SavedOptions(Err(expected_error)) => String::from(*expected_error),
+        TestExpectation::EmbedOptions(Ok(())) => String::from("Valid options"),
+        TestExpectation::EmbedOptions(Err(expected_error)) => String::from(*expected_error),
+        TestExpectation::LinkedSource(Ok(expected_source)) | TestExpectation::LinkedSource(Err(expected_source)) => String::from(*expected_source),
+        TestExpectation::SharedLink(_, Ok(())) => String::from("Same after sharing"),
+        TestExpectation::SharedLink(_, Err(expected_error)) => String::from(*expected_error),
+        TestExpectation::SemanticTokens(expected_tokens) => expected_tokens.join("\n"),
+        TestExpectation::Mutation(defect_class) => format!("Reported the {} defect", defect_class),
+        TestExpectation::SortedPositions(expected_positions) => expected_positions.join("\n"),
+        TestExpectation::MessageCodes(expected_codes) | TestExpectation::StringLengthCodes(_, _, expected_codes) => expected_codes.join("\n"),
+        TestExpectation::TranslatedMessages(_, expected_messages) => expected_messages.join("\n"),
+        TestExpectation::Similarity(_, expected_percent) => format!("{}% similar", expected_percent),
+        TestExpectation::Grade(_, expected_report) => String::from(*expected_report),
+        TestExpectation::QuickFix(expected_code) => String::from(*expected_code),
+        TestExpectation::ProfileOutput(_, _, expected_output) => String::from(*expected_output)
+    };
+}
+
+fn compare(expected: &str, actual: &str) -> TestOutcome {
+    return TestOutcome {
+        passed: expected.eq(actual),
+        diff: diff_lines(expected, actual)
+    };
+}
+
+// Creates a line diff where removed lines are expected and added lines are actual
+fn diff_lines(expected: &str, actual: &str) -> String {
+    let expected_lines: Vec<&str> = expected.lines().collect();
+    let actual_lines: Vec<&str> = actual.lines().collect();
+
+    return diff::diff_items(&expected_lines, &actual_lines).iter()
+        .map(|item| match item {
+            DiffItem::Same(line) => format!("  {}", line),
+            DiffItem::Removed(line) => format!("- {}", line),
+            DiffItem::Added(line) => format!("+ {}", line)
+        })
+        .collect::<Vec<String>>()
+        .join("\n");
+}
+
+// Function that returns all of the test cases with their expected results
+fn get_test_cases() -> Vec<TestCase> {
+    let test_cases: Vec<TestCase> = vec![
+        TestCase {
+            test_type: TestType::Lex,
+            test_name: String::from("Empty program"),
+            test_code: String::from("{}$"),
+            expectation: TestExpectation::Tokens(vec!["{", "}", "$"])
+        },
+        TestCase {
+            test_type: TestType::Lex,
+            test_name: String::from("Keywords and symbols"),
+            test_code: String::from("{\n\tint a\n\ta = 1 + 2\n\tif (a != 3) {}\n}$"),
+            expectation: TestExpectation::Tokens(vec!["{", "int", "a", "a", "=", "1", "+", "2", "if", "(", "a", "!=", "3", ")", "{", "}", "}", "$"])
+        },
+        TestCase {
+            test_type: TestType::Lex,
+            test_name: String::from("Invalid token"),
+            test_code: String::from("{\n\tint @\n}$"),
+            expectation: TestExpectation::LexErrors(1)
+        },
+        TestCase {
+            test_type: TestType::Lex,
+            test_name: String::from("Capitalized keyword"),
+            test_code: String::from("{\n\tPrint(1)\n}$"),
+            expectation: TestExpectation::LexErrors(1)
+        },
+        TestCase {
+            test_type: TestType::Lex,
+            test_name: String::from("EOP in a comment"),
+            test_code: String::from("{\n\t/* costs $5 */\n\tprint(1)\n}$"),
+            expectation: TestExpectation::Tokens(vec!["{", "print", "(", "1", ")", "}", "$"])
+        },
+        TestCase {
+            test_type: TestType::Lex,
+            test_name: String::from("Char literals"),
+            test_code: String::from("{char c c='a' print(' ')}$"),
+            expectation: TestExpectation::Tokens(vec!["{", "char", "c", "c", "=", "'a'", "print", "(", "' '", ")", "}", "$"])
+        },
+        TestCase {
+            test_type: TestType::Lex,
+            test_name: String::from("Index brackets"),
+            test_code: String::from("{print(\"ab\"[1])}$"),
+            expectation: TestExpectation::Tokens(vec!["{", "print", "(", "\"", "a", "b", "\"", "[", "1", "]", ")", "}", "$"])
+        },
+        TestCase {
+            test_type: TestType::Lex,
+            test_name: String::from("Len keyword"),
+            test_code: String::from("{print(len(\"ab\"))}$"),
+            expectation: TestExpectation::Tokens(vec!["{", "print", "(", "len", "(", "\"", "a", "b", "\"", ")", ")", "}", "$"])
+        },
+        TestCase {
+            test_type: TestType::Lex,
+            test_name: String::from("Token positions on one line"),
+            test_code: String::from("{int a a=1 print(a)}$"),
+            expectation: TestExpectation::TokenPositions(vec!["{ (1, 1)", "int (1, 2)", "a (1, 6)", "a (1, 8)", "= (1, 9)", "1 (1, 10)", "print (1, 12)", "( (1, 17)", "a (1, 18)", ") (1, 19)", "} (1, 20)", "$ (1, 21)"])
+        },
+        TestCase {
+            test_type: TestType::Lex,
+            test_name: String::from("Token positions after comments and strings"),
+            test_code: String::from("{/* c */print(\"a b\")/* */a=1}$"),
+            expectation: TestExpectation::TokenPositions(vec!["{ (1, 1)", "print (1, 9)", "( (1, 14)", "\" (1, 15)", "a (1, 16)", "  (1, 17)", "b (1, 18)", "\" (1, 19)", ") (1, 20)", "a (1, 26)", "= (1, 27)", "1 (1, 28)", "} (1, 29)", "$ (1, 30)"])
+        },
+        TestCase {
+            test_type: TestType::Lex,
+            test_name: String::from("Token positions after a multi-line comment"),
+            test_code: String::from("{/* a\nb */print(1)}$"),
+            expectation: TestExpectation::TokenPositions(vec!["{ (1, 1)", "print (2, 5)", "( (2, 10)", "1 (2, 11)", ") (2, 12)", "} (2, 13)", "$ (2, 14)"])
+        },
+        TestCase {
+            test_type: TestType::Lex,
+            test_name: String::from("EOP in a string"),
+            test_code: String::from("{\n\tprint(\"a$b\")\n}$\n{}$"),
+            expectation: TestExpectation::LexErrors(1)
+        },
+        TestCase {
+            test_type: TestType::Lex,
+            test_name: String::from("Programs split at EOP only"),
+            test_code: String::from("{\n\tprint(\"a$b\")\n}$\n/* $ */\n{\n\t/* $ */\n}$\n/* trailing comment with $ */"),
+            expectation: TestExpectation::ProgramCount(2)
+        },
+        TestCase {
+            test_type: TestType::Lex,
+            test_name: String::from("Unchanged programs are reused"),
+            test_code: String::from("{print(1)}$\n{print(2)}$\n{Print(3)}$\n{print(4)}"),
+            expectation: TestExpectation::CachedPrograms("{print(1)}$\n{print(2 + 2)}$\n{Print(3)}$\n{print(4)}$", vec!["Program 1 reused", "Program 2 lexed", "Program 3 reused", "Program 4 lexed"])
+        },
+        TestCase {
+            test_type: TestType::Lex,
+            test_name: String::from("Programs after a new line are lexed again"),
+            test_code: String::from("{print(1)}$\n{print(2)}$\n{print(3)}$"),
+            expectation: TestExpectation::CachedPrograms("{print(1)}$\n{\nprint(2)}$\n{print(3)}$", vec!["Program 1 reused", "Program 2 lexed", "Program 3 lexed"])
+        },
+        TestCase {
+            test_type: TestType::Lex,
+            test_name: String::from("Constant substitution"),
+            test_code: String::from("define LIMIT 5\ndefine GREETING \"hi\"\n{\n\tprint(LIMIT)\n\tprint(GREETING)\n\t/* LIMIT */\n}$"),
+            expectation: TestExpectation::Tokens(vec!["{", "print", "(", "5", ")", "print", "(", "\"", "h", "i", "\"", ")", "}", "$"])
+        },
+        TestCase {
+            test_type: TestType::Lex,
+            test_name: String::from("Constants keep the columns of the source code"),
+            test_code: String::from("define GREETING \"hello\"\ndefine ON true\n{ print(GREETING) print(ON) }$"),
+            expectation: TestExpectation::TokenPositions(vec!["{ (3, 1)", "print (3, 3)", "( (3, 8)", "\" (3, 9)", "h (3, 10)", "e (3, 11)", "l (3, 12)", "l (3, 13)", "o (3, 14)", "\" (3, 15)", ") (3, 17)", "print (3, 19)", "( (3, 24)", "true (3, 25)", ") (3, 27)", "} (3, 29)", "$ (3, 30)"])
+        },
+        TestCase {
+            test_type: TestType::SemanticAnalysis,
+            test_name: String::from("Errors after a constant on the same line"),
+            test_code: String::from("define GREETING \"hello there\"\n{\n\tprint(GREETING) print(b)\n}$"),
+            expectation: TestExpectation::TranslatedMessages("{}", vec!["Error at (3, 24); Id [ b ] has not been declared"])
+        },
+        TestCase {
+            test_type: TestType::Lex,
+            test_name: String::from("Invalid constant value"),
+            test_code: String::from("define LIMIT 10\n{\n\tprint(LIMIT)\n}$"),
+            expectation: TestExpectation::PreprocessErrors(1)
+        },
+        TestCase {
+            test_type: TestType::Parse,
+            test_name: String::from("Valid statements"),
+            test_code: String::from("{\n\tint a\n\ta = 1\n\twhile (a != 5) {\n\t\ta = 1 + a\n\t}\n\tprint(a)\n}$"),
+            expectation: TestExpectation::ParseSucceeds
+        },
+        TestCase {
+            test_type: TestType::Parse,
+            test_name: String::from("Unclosed print"),
+            test_code: String::from("{\n\tprint(1\n}$"),
+            expectation: TestExpectation::ParseFails
+        },
+        TestCase {
+            test_type: TestType::Parse,
+            test_name: String::from("Hint for an unclosed print"),
+            test_code: String::from("{\n\tprint(1\n\tif (a == b) {\n\t\tprint(a)\n\t}\n}$"),
+            expectation: TestExpectation::BalanceHints(vec!["P021 (2, 7)"])
+        },
+        TestCase {
+            test_type: TestType::Parse,
+            test_name: String::from("Hint for a missing brace"),
+            test_code: String::from("{\n\twhile (a != b) {\n\t\ta = 1 + a\n}$"),
+            expectation: TestExpectation::BalanceHints(vec!["P021 (1, 1)"])
+        },
+        TestCase {
+            test_type: TestType::Parse,
+            test_name: String::from("Hint for a brace that closes nothing"),
+            test_code: String::from("{\n\tprint(a)\n}}$"),
+            expectation: TestExpectation::BalanceHints(vec!["P022 (3, 2)"])
+        },
+        TestCase {
+            test_type: TestType::Parse,
+            // The parenthesis is read as part of the string, so the lexer reports the string instead of a balance hint
+            test_name: String::from("Unclosed quote is an unclosed string"),
+            test_code: String::from("{\n\tprint(\"ab)\n}$"),
+            expectation: TestExpectation::MessageCodes(vec!["L004", "L005"])
+        },
+        TestCase {
+            test_type: TestType::Parse,
+            test_name: String::from("Balanced program has no hints"),
+            test_code: String::from("{\n\ts = \"ab\"[(1 + 2)]\n\tif (a == b) { print(s) }\n}$"),
+            expectation: TestExpectation::BalanceHints(vec![])
+        },
+        TestCase {
+            test_type: TestType::Parse,
+            test_name: String::from("Quick fix for a block missing its right brace"),
+            test_code: String::from("{\n\tif (a == b) {\n\t\tprint(a)\n}$"),
+            expectation: TestExpectation::QuickFix("{\n\tif (a == b) {\n\t\tprint(a)\n}}$")
+        },
+        TestCase {
+            test_type: TestType::Parse,
+            test_name: String::from("Quick fix for a block at the end of the code"),
+            test_code: String::from("{\n\tprint(a)\n"),
+            expectation: TestExpectation::QuickFix("{\n\tprint(a)}\n")
+        },
+        TestCase {
+            test_type: TestType::Parse,
+            test_name: String::from("Quick fix for a print missing its right paren"),
+            test_code: String::from("{\n\tprint(1 + a\n\ta = 2\n}$"),
+            expectation: TestExpectation::QuickFix("{\n\tprint(1 + a)\n\ta = 2\n}$")
+        },
+        TestCase {
+            test_type: TestType::Parse,
+            test_name: String::from("Quick fix for = in a condition"),
+            test_code: String::from("{\n\tif (a = 1) {\n\t\tprint(a)\n\t}\n}$"),
+            expectation: TestExpectation::QuickFix("{\n\tif (a == 1) {\n\t\tprint(a)\n\t}\n}$")
+        },
+        TestCase {
+            test_type: TestType::Parse,
+            test_name: String::from("No quick fix for an unknown statement"),
+            test_code: String::from("{\n\t+ 1\n}$"),
+            expectation: TestExpectation::QuickFix("No quick fix")
+        },
+        TestCase {
+            test_type: TestType::Parse,
+            test_name: String::from("Empty print"),
+            test_code: String::from("{\n\tprint()\n\tprint(1)\n}$"),
+            expectation: TestExpectation::ParseErrors(1)
+        },
+        TestCase {
+            test_type: TestType::Parse,
+            test_name: String::from("Print with two expressions"),
+            test_code: String::from("{\n\tprint((a == b) c)\n}$"),
+            expectation: TestExpectation::ParseErrors(1)
+        },
+        TestCase {
+            test_type: TestType::Parse,
+            test_name: String::from("Statements after bad prints are still parsed"),
+            test_code: String::from("{\n\tprint()\n\tprint(1 \"a\")\n\tint a\n\ta = \n}$"),
+            expectation: TestExpectation::ParseErrors(3)
+        },
+        TestCase {
+            test_type: TestType::Parse,
+            test_name: String::from("Grouped integer expressions"),
+            test_code: String::from("{\n\ta = (1 + 2) + 3\n\tprint(((1 + 2)))\n\tb = 1 + (2 + a) + (3 + 4)\n\tif ((1 + a) + 2 == b) {}\n\tif ((1 + 2) != (3 + 4)) {}\n}$"),
+            expectation: TestExpectation::ParseSucceeds
+        },
+        TestCase {
+            test_type: TestType::Parse,
+            test_name: String::from("Grouped expressions must start with a digit"),
+            test_code: String::from("{\n\ta = (a + 1) + 2\n}$"),
+            expectation: TestExpectation::ParseFails
+        },
+        TestCase {
+            test_type: TestType::Parse,
+            test_name: String::from("Grouped expressions must be integer expressions"),
+            test_code: String::from("{\n\ta = ((1 == 2) + 3)\n}$"),
+            expectation: TestExpectation::ParseFails
+        },
+        TestCase {
+            test_type: TestType::Parse,
+            test_name: String::from("Missing EOP"),
+            test_code: String::from("{\n\tprint(1)\n}"),
+            expectation: TestExpectation::ParseSucceeds
+        },
+        TestCase {
+            test_type: TestType::Parse,
+            test_name: String::from("Missing brace"),
+            test_code: String::from("{\n\tif true {\n\t\tprint(1)\n}$"),
+            expectation: TestExpectation::ParseFails
+        },
+        TestCase {
+            test_type: TestType::SemanticAnalysis,
+            test_name: String::from("No errors"),
+            test_code: String::from("{\n\tint a\n\ta = 1\n\tprint(a)\n}$"),
+            expectation: TestExpectation::SemanticErrors(vec![])
+        },
+        TestCase {
+            test_type: TestType::SemanticAnalysis,
+            test_name: String::from("Undeclared identifier"),
+            test_code: String::from("{\n\tprint(b)\n}$"),
+            expectation: TestExpectation::SemanticErrors(vec![ErrorClass::UndeclaredId])
+        },
+        TestCase {
+            test_type: TestType::SemanticAnalysis,
+            test_name: String::from("Redeclared identifier"),
+            test_code: String::from("{\n\tint a\n\tint a\n}$"),
+            expectation: TestExpectation::SemanticErrors(vec![ErrorClass::Redeclaration])
+        },
+        TestCase {
+            test_type: TestType::SemanticAnalysis,
+            test_name: String::from("Grouped additions are type checked"),
+            test_code: String::from("{\n\tint a\n\ta = (1 + \"a\") + 2\n}$"),
+            expectation: TestExpectation::SemanticErrors(vec![ErrorClass::TypeMismatch])
+        },
+        TestCase {
+            test_type: TestType::SemanticAnalysis,
+            test_name: String::from("Type mismatches"),
+            test_code: String::from("{\n\tint a\n\ta = \"hi\"\n\tprint((1 == true))\n}$"),
+            expectation: TestExpectation::SemanticErrors(vec![ErrorClass::TypeMismatch, ErrorClass::TypeMismatch])
+        },
+        TestCase {
+            test_type: TestType::SemanticAnalysis,
+            test_name: String::from("Chars are not strings or ints"),
+            test_code: String::from("{\n\tchar c\n\tc = \"a\"\n\tprint((c == 1))\n\tc = 'a'\n}$"),
+            expectation: TestExpectation::SemanticErrors(vec![ErrorClass::TypeMismatch, ErrorClass::TypeMismatch])
+        },
+        TestCase {
+            test_type: TestType::SemanticAnalysis,
+            test_name: String::from("Index type mismatches and constant indices out of bounds"),
+            test_code: String::from("{\n\tstring s\n\tint i\n\tprint(s[s])\n\tprint(i[0])\n\tprint(\"ab\"[2])\n\tprint(\"ab\"[1 + 1])\n}$"),
+            expectation: TestExpectation::SemanticErrors(vec![ErrorClass::TypeMismatch, ErrorClass::TypeMismatch, ErrorClass::IndexOutOfBounds, ErrorClass::IndexOutOfBounds])
+        },
+        TestCase {
+            test_type: TestType::SemanticAnalysis,
+            test_name: String::from("String literals longer than the limit"),
+            test_code: String::from("{\n\tstring s\n\ts = \"hello\"\n\tprint(\"hi\")\n\tprint((s == \"world\"))\n}$"),
+            expectation: TestExpectation::StringLengthCodes(Target::Target6502, "4", vec!["S018", "S018", "S010"])
+        },
+        TestCase {
+            test_type: TestType::SemanticAnalysis,
+            test_name: String::from("String literals longer than the 6502 heap"),
+            test_code: format!("{{\n\tprint(\"{}\")\n}}$", "a".repeat(254)),
+            expectation: TestExpectation::StringLengthCodes(Target::Target6502, "", vec!["S018"])
+        },
+        TestCase {
+            test_type: TestType::SemanticAnalysis,
+            test_name: String::from("String literals longer than the 6502 heap on RISC-V"),
+            test_code: format!("{{\n\tprint(\"{}\")\n}}$", "a".repeat(254)),
+            expectation: TestExpectation::StringLengthCodes(Target::TargetRiscV, "", vec![])
+        },
+        TestCase {
+            test_type: TestType::SemanticAnalysis,
+            test_name: String::from("String length limit past the 6502 heap"),
+            test_code: String::from("{}$"),
+            expectation: TestExpectation::StringLengthCodes(Target::Target6502, "254", vec!["Invalid string length limit [ 254 ], which must be between 1 and 253 on the 6502"])
+        },
+        TestCase {
+            test_type: TestType::SemanticAnalysis,
+            test_name: String::from("Only strings have a length"),
+            test_code: String::from("{\n\tint i\n\tprint(len(i))\n\tprint(len('a'))\n\ti = 1 + len(\"ab\")\n}$"),
+            expectation: TestExpectation::SemanticErrors(vec![ErrorClass::TypeMismatch, ErrorClass::TypeMismatch])
+        },
+        TestCase {
+            test_type: TestType::CodeGen,
+            test_name: String::from("Simple program"),
+            test_code: String::from("{\n\tint a\n\ta = 3\n\tprint(a)\n}$"),
+            expectation: TestExpectation::CodeGenSucceeds
+        },
+        TestCase {
+            test_type: TestType::CodeGen,
+            test_name: String::from("Print every type of operand"),
+            test_code: String::from("{\n\tint a\n\ta = 1\n\tstring s\n\ts = \"x\"\n\tboolean b\n\tb = (a == 1)\n\tprint(a)\n\tprint(s)\n\tprint(b)\n\tprint(2)\n\tprint(\"y\")\n\tprint(true)\n\tprint(1 + a)\n\tprint((s != \"y\"))\n}$"),
+            expectation: TestExpectation::CodeGenSucceeds
+        },
+        TestCase {
+            test_type: TestType::CodeGen,
+            test_name: String::from("Output of every type of print"),
+            test_code: String::from("{\n\tint a\n\ta = 3\n\tprint(a)\n\tstring s\n\ts = \"hi\"\n\tprint(s)\n\tboolean b\n\tb = true\n\tprint(b)\n\tprint(5)\n\tprint(true)\n\tprint(false)\n}$"),
+            expectation: TestExpectation::ProgramOutput("3\nhi\ntrue\n5\ntrue\nfalse\n")
+        },
+        TestCase {
+            test_type: TestType::CodeGen,
+            test_name: String::from("Output of a while loop"),
+            test_code: String::from("{\n\tint a\n\twhile (a != 3) {\n\t\ta = 1 + a\n\t\tprint(a)\n\t}\n}$"),
+            expectation: TestExpectation::ProgramOutput("1\n2\n3\n")
+        },
+        TestCase {
+            test_type: TestType::CodeGen,
+            test_name: String::from("Boolean assigned from equal strings"),
+            test_code: String::from("{\n\tboolean b\n\tb = (\"a\" == \"a\")\n\tprint(b)\n}$"),
+            expectation: TestExpectation::ProgramOutput("true\n")
+        },
+        TestCase {
+            test_type: TestType::CodeGen,
+            test_name: String::from("Boolean assigned from strings that are not equal"),
+            test_code: String::from("{\n\tboolean b\n\tb = (\"a\" != \"a\")\n\tprint(b)\n\tb = (\"a\" == \"b\")\n\tprint(b)\n}$"),
+            expectation: TestExpectation::ProgramOutput("false\nfalse\n")
+        },
+        TestCase {
+            test_type: TestType::CodeGen,
+            test_name: String::from("Boolean assigned from a string variable"),
+            test_code: String::from("{\n\tstring s\n\ts = \"a\"\n\tboolean b\n\tb = (s == \"a\")\n\tprint(b)\n\tb = (s != \"a\")\n\tprint(b)\n}$"),
+            expectation: TestExpectation::ProgramOutput("true\nfalse\n")
+        },
+        TestCase {
+            test_type: TestType::CodeGen,
+            test_name: String::from("Boolean assigned from another boolean variable"),
+            test_code: String::from("{\n\tboolean b\n\tboolean c\n\tc = true\n\tb = c\n\tprint(b)\n\tb = false\n\tc = b\n\tprint(c)\n\tb = (c == false)\n\tprint(b)\n\tc = (true == b)\n\tprint(c)\n\tboolean d = c\n\tprint(d)\n}$"),
+            expectation: TestExpectation::ProgramOutput("true\nfalse\ntrue\ntrue\ntrue\n")
+        },
+        TestCase {
+            test_type: TestType::CodeGen,
+            test_name: String::from("Boolean assigned from another boolean variable to the output device"),
+            test_code: String::from("{\n\tboolean b\n\tboolean c\n\tc = true\n\tb = c\n\tprint(b)\n\tb = false\n\tc = b\n\tprint(c)\n\tb = (c == false)\n\tprint(b)\n\tc = (true == b)\n\tprint(c)\n\tboolean d = c\n\tprint(d)\n}$"),
+            expectation: TestExpectation::MmioProgramOutput("true\nfalse\ntrue\ntrue\ntrue\n")
+        },
+        TestCase {
+            test_type: TestType::CodeGen,
+            test_name: String::from("Boolean comparisons of variables and keywords"),
+            test_code: String::from("{\n\tboolean b\n\tboolean c\n\tc = true\n\tb = (c != true)\n\tprint(b)\n\tc = (b == c)\n\tprint(c)\n\tb = ((b != c) == true)\n\tprint(b)\n\tif (b == false) {\n\t\tprint(\"t\")\n\t}\n\tif (true != c) {\n\t\tprint(\"u\")\n\t}\n}$"),
+            expectation: TestExpectation::ProgramOutput("false\nfalse\nfalse\nt\nu\n")
+        },
+        TestCase {
+            test_type: TestType::CodeGen,
+            test_name: String::from("Boolean comparisons of variables and keywords (RISC-V)"),
+            test_code: String::from("{\n\tboolean b\n\tboolean c\n\tc = true\n\tb = (c != true)\n\tprint(b)\n\tc = (b == c)\n\tprint(c)\n\tb = ((b != c) == true)\n\tprint(b)\n\tif (b == false) {\n\t\tprint(\"t\")\n\t}\n\tif (true != c) {\n\t\tprint(\"u\")\n\t}\n}$"),
+            expectation: TestExpectation::RiscVProgramOutput("false\nfalse\nfalse\nt\nu\n")
+        },
+        TestCase {
+            test_type: TestType::CodeGen,
+            test_name: String::from("Comparisons of a variable with itself"),
+            test_code: String::from("{\n\tint a\n\ta = 3\n\tprint((a == a))\n\tprint((a != a))\n\tif (a == a) {\n\t\tprint(a)\n\t}\n\tboolean b\n\tb = ((a != a) == false)\n\tprint(b)\n}$"),
+            expectation: TestExpectation::ProgramOutput("true\nfalse\n3\ntrue\n")
+        },
+        TestCase {
+            test_type: TestType::CodeGen,
+            test_name: String::from("Removed if and while statements with empty bodies"),
+            test_code: String::from("{\n\tint a\n\ta = 1\n\tif (a == 1) {}\n\twhile false {}\n\twhile (a != a) {}\n\tprint(a)\n}$"),
+            expectation: TestExpectation::ProgramOutput("1\n")
+        },
+        TestCase {
+            test_type: TestType::CodeGen,
+            test_name: String::from("Printed string comparisons"),
+            test_code: String::from("{\n\tprint((\"a\" == \"a\"))\n\tprint((\"a\" != \"b\"))\n\tprint(((1 == 1) == (\"a\" == \"a\")))\n}$"),
+            expectation: TestExpectation::ProgramOutput("true\ntrue\ntrue\n")
+        },
+        TestCase {
+            test_type: TestType::CodeGen,
+            test_name: String::from("Printed comparisons with false"),
+            test_code: String::from("{\n\tprint((false != false))\n\tprint(((1 == 1) != false))\n\tprint((false == false))\n}$"),
+            expectation: TestExpectation::ProgramOutput("false\ntrue\ntrue\n")
+        },
+        TestCase {
+            test_type: TestType::CodeGen,
+            test_name: String::from("Print every form of boolean"),
+            test_code: String::from("{\n\tprint(true)\n\tprint(false)\n\tboolean b\n\tb = true\n\tprint(b)\n\tb = false\n\tprint(b)\n\tprint((1 == 1))\n\tprint((1 != 1))\n}$"),
+            expectation: TestExpectation::ProgramOutput("true\nfalse\ntrue\nfalse\ntrue\nfalse\n")
+        },
+        TestCase {
+            test_type: TestType::CodeGen,
+            test_name: String::from("Compare int ids and literals in either order"),
+            test_code: String::from("{\n\tint a\n\tint b\n\ta = 3\n\tb = 4\n\tprint((a == 3))\n\tprint((3 == a))\n\tprint((a == 4))\n\tprint((4 == a))\n\tprint((a != b))\n\tprint((b != a))\n\tprint((3 != 4))\n}$"),
+            expectation: TestExpectation::ProgramOutput("true\ntrue\nfalse\nfalse\ntrue\ntrue\ntrue\n")
+        },
+        TestCase {
+            test_type: TestType::CodeGen,
+            test_name: String::from("Compare int ids and literals in either order (RISC-V)"),
+            test_code: String::from("{\n\tint a\n\tint b\n\ta = 3\n\tb = 4\n\tprint((a == 3))\n\tprint((3 == a))\n\tprint((a == 4))\n\tprint((4 == a))\n\tprint((a != b))\n\tprint((b != a))\n\tprint((3 != 4))\n}$"),
+            expectation: TestExpectation::RiscVProgramOutput("true\ntrue\nfalse\nfalse\ntrue\ntrue\ntrue\n")
+        },
+        TestCase {
+            test_type: TestType::CodeGen,
+            test_name: String::from("Compare additions in either order"),
+            test_code: String::from("{\n\tint a\n\tint b\n\ta = 3\n\tb = 4\n\tprint((a == 1 + 2))\n\tprint((1 + 2 == a))\n\tprint((b != 1 + a))\n\tprint((1 + a == b))\n}$"),
+            expectation: TestExpectation::ProgramOutput("true\ntrue\nfalse\ntrue\n")
+        },
+        TestCase {
+            test_type: TestType::CodeGen,
+            test_name: String::from("Compare additions in either order (RISC-V)"),
+            test_code: String::from("{\n\tint a\n\tint b\n\ta = 3\n\tb = 4\n\tprint((a == 1 + 2))\n\tprint((1 + 2 == a))\n\tprint((b != 1 + a))\n\tprint((1 + a == b))\n}$"),
+            expectation: TestExpectation::RiscVProgramOutput("true\ntrue\nfalse\ntrue\n")
+        },
+        TestCase {
+            test_type: TestType::CodeGen,
+            test_name: String::from("Compare boolean ids and literals in either order"),
+            test_code: String::from("{\n\tboolean c\n\tc = true\n\tprint((c == true))\n\tprint((false == c))\n\tprint((c != false))\n\tprint((true != c))\n}$"),
+            expectation: TestExpectation::ProgramOutput("true\nfalse\ntrue\nfalse\n")
+        },
+        TestCase {
+            test_type: TestType::CodeGen,
+            test_name: String::from("Compare boolean ids and literals in either order (RISC-V)"),
+            test_code: String::from("{\n\tboolean c\n\tc = true\n\tprint((c == true))\n\tprint((false == c))\n\tprint((c != false))\n\tprint((true != c))\n}$"),
+            expectation: TestExpectation::RiscVProgramOutput("true\nfalse\ntrue\nfalse\n")
+        },
+        TestCase {
+            test_type: TestType::CodeGen,
+            test_name: String::from("Compare nested comparisons in either order"),
+            test_code: String::from("{\n\tint a\n\ta = 3\n\tboolean c\n\tc = true\n\tprint((c == (a == 3)))\n\tprint(((a != 3) == c))\n\tprint(((a == 3) == (3 == a)))\n}$"),
+            expectation: TestExpectation::ProgramOutput("true\nfalse\ntrue\n")
+        },
+        TestCase {
+            test_type: TestType::CodeGen,
+            test_name: String::from("Compare nested comparisons in either order (RISC-V)"),
+            test_code: String::from("{\n\tint a\n\ta = 3\n\tboolean c\n\tc = true\n\tprint((c == (a == 3)))\n\tprint(((a != 3) == c))\n\tprint(((a == 3) == (3 == a)))\n}$"),
+            expectation: TestExpectation::RiscVProgramOutput("true\nfalse\ntrue\n")
+        },
+        TestCase {
+            test_type: TestType::CodeGen,
+            test_name: String::from("Nested comparisons with additions on both sides"),
+            test_code: String::from("{\n\tint a\n\ta = 3\n\tprint(((1 + 2 == 3) == (4 != 2 + 2)))\n\tprint(((a == 1 + 2) != (1 + a == 2 + 2)))\n}$"),
+            expectation: TestExpectation::ProgramOutput("false\nfalse\n")
+        },
+        TestCase {
+            test_type: TestType::CodeGen,
+            test_name: String::from("Nested comparisons with additions on both sides (RISC-V)"),
+            test_code: String::from("{\n\tint a\n\ta = 3\n\tprint(((1 + 2 == 3) == (4 != 2 + 2)))\n\tprint(((a == 1 + 2) != (1 + a == 2 + 2)))\n\tprint((((a == 3) != false) == ((1 + 1 != a) == true)))\n\tif (((a == 3) == true) == (1 + 2 == a)) {\n\t\tprint(a)\n\t}\n}$"),
+            expectation: TestExpectation::RiscVProgramOutput("false\nfalse\ntrue\n3\n")
+        },
+        TestCase {
+            test_type: TestType::CodeGen,
+            test_name: String::from("Compare in either order for if and while"),
+            test_code: String::from("{\n\tint a\n\ta = 3\n\tif (a == 3) {\n\t\tprint(\"a\")\n\t}\n\tif (4 == a) {\n\t\tprint(\"c\")\n\t}\n\tif (a != 4) {\n\t\tprint(\"d\")\n\t}\n\tint i\n\twhile (2 + 3 != i) {\n\t\ti = 1 + i\n\t}\n\tprint(i)\n\twhile (i != 3 + 4) {\n\t\ti = 1 + i\n\t}\n\tprint(i)\n}$"),
+            expectation: TestExpectation::ProgramOutput("a\nd\n5\n7\n")
+        },
+        TestCase {
+            test_type: TestType::CodeGen,
+            test_name: String::from("Print to the output device"),
+            test_code: String::from("{\n\tint a\n\ta = 4\n\tboolean b\n\tb = true\n\tprint(a)\n\tprint(\"hi\")\n\tprint(b)\n\tprint(false)\n\tprint(1 + a)\n\tprint((a == 3))\n\tstring s\n\ts = \"yo\"\n\tprint(s)\n\tchar c\n\tc = 'z'\n\tprint(c)\n\tprint('q')\n}$"),
+            expectation: TestExpectation::MmioProgramOutput("4\nhi\ntrue\nfalse\n5\nfalse\nyo\nz\nq\n")
+        },
+        TestCase {
+            test_type: TestType::CodeGen,
+            test_name: String::from("Compare in either order for if and while (RISC-V)"),
+            test_code: String::from("{\n\tint a\n\ta = 3\n\tif (a == 3) {\n\t\tprint(\"a\")\n\t}\n\tif (3 == a) {\n\t\tprint(\"b\")\n\t}\n\tif (4 == a) {\n\t\tprint(\"c\")\n\t}\n\tif (a != 4) {\n\t\tprint(\"d\")\n\t}\n\tint i\n\twhile (2 + 3 != i) {\n\t\ti = 1 + i\n\t}\n\tprint(i)\n\twhile (i != 3 + 4) {\n\t\ti = 1 + i\n\t}\n\tprint(i)\n}$"),
+            expectation: TestExpectation::RiscVProgramOutput("a\nb\nd\n5\n7\n")
+        },
+        TestCase {
+            test_type: TestType::CodeGen,
+            test_name: String::from("Print zero"),
+            test_code: String::from("{\n\tint a\n\tprint(a)\n\tprint(0)\n\ta = 0 + a\n\tprint(a)\n}$"),
+            expectation: TestExpectation::ProgramOutput("0\n0\n0\n")
+        },
+        TestCase {
+            test_type: TestType::CodeGen,
+            test_name: String::from("Print zero (RISC-V)"),
+            test_code: String::from("{\n\tint a\n\tprint(a)\n\tprint(0)\n\ta = 0 + a\n\tprint(a)\n}$"),
+            expectation: TestExpectation::RiscVProgramOutput("0\n0\n0\n")
+        },
+        TestCase {
+            test_type: TestType::CodeGen,
+            test_name: String::from("Print ints without leading zeros"),
+            test_code: String::from("{\n\tprint(0)\n\tprint(7)\n\tprint(1 + 9)\n\tint a\n\ta = 9 + 9 + 9 + 9 + 9 + 9 + 9 + 9 + 9 + 9 + 9\n\tprint(a)\n\ta = 1 + a\n\tprint(a)\n}$"),
+            expectation: TestExpectation::ProgramOutput("0\n7\n10\n99\n100\n")
+        },
+        TestCase {
+            test_type: TestType::CodeGen,
+            test_name: String::from("Print ints without leading zeros (RISC-V)"),
+            test_code: String::from("{\n\tprint(0)\n\tprint(7)\n\tprint(1 + 9)\n\tint a\n\ta = 9 + 9 + 9 + 9 + 9 + 9 + 9 + 9 + 9 + 9 + 9\n\tprint(a)\n\ta = 1 + a\n\tprint(a)\n\ta = 9 + 9 + 9 + 9 + 9 + 9 + 9 + 9 + 9 + 9 + 9 + a\n\ta = 5 + 5 + 5 + 5 + 5 + 5 + 5 + 5 + 5 + 5 + 5 + a\n\tprint(1 + a)\n}$"),
+            expectation: TestExpectation::RiscVProgramOutput("0\n7\n10\n99\n100\n255\n")
+        },
+        TestCase {
+            test_type: TestType::CodeGen,
+            test_name: String::from("Grouped additions"),
+            test_code: String::from("{\n\tint a\n\ta = (1 + 2) + 3\n\tprint(a)\n\tint b\n\tb = (1 + a) + (2 + a)\n\tprint(b)\n\tprint(((1 + 2)) + b)\n\tprint(3 + (1 + a))\n\tprint(((1 + 2) + 3 == a))\n}$"),
+            expectation: TestExpectation::ProgramOutput("6\n15\n18\n10\ntrue\n")
+        },
+        TestCase {
+            test_type: TestType::CodeGen,
+            test_name: String::from("Grouped additions (RISC-V)"),
+            test_code: String::from("{\n\tint a\n\ta = (1 + 2) + 3\n\tprint(a)\n\tint b\n\tb = (1 + a) + (2 + a)\n\tprint(b)\n\tprint(((1 + 2)) + b)\n\tprint(3 + (1 + a))\n\tprint(((1 + 2) + 3 == a))\n}$"),
+            expectation: TestExpectation::RiscVProgramOutput("6\n15\n18\n10\ntrue\n")
+        },
+        TestCase {
+            test_type: TestType::CodeGen,
+            test_name: String::from("Char variables"),
+            test_code: String::from("{\n\tchar c\n\tprint(c)\n\tc = 'h'\n\tprint(c)\n\tprint('i')\n\tchar d\n\td = c\n\tprint((c == d))\n\tprint((d != 'x'))\n\tboolean b\n\tb = (d == ' ')\n\tprint(b)\n}$"),
+            expectation: TestExpectation::ProgramOutput(" \nh\ni\ntrue\ntrue\nfalse\n")
+        },
+        TestCase {
+            test_type: TestType::CodeGen,
+            test_name: String::from("Char variables (RISC-V)"),
+            test_code: String::from("{\n\tchar c\n\tprint(c)\n\tc = 'h'\n\tprint(c)\n\tprint('i')\n\tchar d\n\td = c\n\tprint((c == d))\n\tprint((d != 'x'))\n\tboolean b\n\tb = (d == ' ')\n\tprint(b)\n}$"),
+            expectation: TestExpectation::RiscVProgramOutput(" \nh\ni\ntrue\ntrue\nfalse\n")
+        },
+        TestCase {
+            test_type: TestType::CodeGen,
+            test_name: String::from("String indexing"),
+            test_code: String::from("{\n\tstring s\n\ts = \"hello\"\n\tint i\n\ti = 1\n\tprint(s[i])\n\tprint(\"abc\"[2])\n\tchar c\n\tc = s[1 + i]\n\tprint(c)\n\tprint(('l' == s[3]))\n\ti = 0\n\twhile (s[i] != 'o') {\n\t\ti = 1 + i\n\t}\n\tprint(i)\n}$"),
+            expectation: TestExpectation::ProgramOutput("e\nc\nl\ntrue\n4\n")
+        },
+        TestCase {
+            test_type: TestType::CodeGen,
+            test_name: String::from("String indexing (RISC-V)"),
+            test_code: String::from("{\n\tstring s\n\ts = \"hello\"\n\tint i\n\ti = 1\n\tprint(s[i])\n\tprint(\"abc\"[2])\n\tchar c\n\tc = s[1 + i]\n\tprint(c)\n\tprint((s[0] == 'h'))\n\tprint(('l' == s[3]))\n\ti = 0\n\twhile (s[i] != 'o') {\n\t\ti = 1 + i\n\t}\n\tprint(i)\n}$"),
+            expectation: TestExpectation::RiscVProgramOutput("e\nc\nl\ntrue\ntrue\n4\n")
+        },
+        TestCase {
+            test_type: TestType::CodeGen,
+            test_name: String::from("String length"),
+            test_code: String::from("{\n\tstring s\n\ts = \"hello\"\n\tprint(len(s))\n\tprint(len(\"ab\"))\n\tint n\n\tn = 1 + len(s)\n\tprint(n)\n\tprint((len(s) == 5))\n\tprint(s[len(\"ab\")])\n\ts = \"hi\"\n\tprint(len(s))\n}$"),
+            expectation: TestExpectation::ProgramOutput("5\n2\n6\ntrue\nl\n2\n")
+        },
+        TestCase {
+            test_type: TestType::CodeGen,
+            test_name: String::from("String length (RISC-V)"),
+            test_code: String::from("{\n\tstring s\n\ts = \"hello\"\n\tprint(len(s))\n\tprint(len(\"ab\"))\n\tint n\n\tn = 1 + len(s)\n\tprint(n)\n\tprint((len(s) == 5))\n\tprint(s[len(\"ab\")])\n\ts = \"hi\"\n\tprint(len(s))\n}$"),
+            expectation: TestExpectation::RiscVProgramOutput("5\n2\n6\ntrue\nl\n2\n")
+        },
+        TestCase {
+            test_type: TestType::CodeGen,
+            test_name: String::from("Disassembled images assemble to the same bytes"),
+            test_code: String::from("{\n\tint a\n\tstring s\n\ts = \"hi\"\n\twhile (a != 3) {\n\t\ta = 1 + a\n\t\tprint(a)\n\t}\n\tif (s == \"hi\") {\n\t\tprint(s)\n\t}\n\tprint((a == 3))\n}$"),
+            expectation: TestExpectation::DisassemblyRoundTrip(50)
+        },
+        TestCase {
+            test_type: TestType::CodeGen,
+            test_name: String::from("Code generators print the same as the interpreter"),
+            test_code: String::from("{\n\tint a\n\tstring s\n\ts = \"hi\"\n\twhile (a != 3) {\n\t\ta = 1 + a\n\t\tprint(a)\n\t}\n\tif (s == \"hi\") {\n\t\tprint(s)\n\t}\n\tboolean b\n\tb = (a == 3)\n\tprint(b)\n\tprint(9 + 9 + a)\n\tprint((s != \"hi\"))\n}$"),
+            expectation: TestExpectation::InterpreterMatches(50)
+        },
+        TestCase {
+            test_type: TestType::CodeGen,
+            test_name: String::from("Unassigned variables print the same as the interpreter"),
+            test_code: String::from("{\n\tstring s\n\tprint(s)\n\tint a\n\twhile (a != 2) {\n\t\tstring t\n\t\tprint(t)\n\t\tt = \"hi\"\n\t\tprint(t)\n\t\ta = 1 + a\n\t}\n\tchar c\n\tboolean b\n\tprint(c)\n\tprint(b)\n}$"),
+            expectation: TestExpectation::InterpreterMatches(0)
+        },
+        TestCase {
+            test_type: TestType::CodeGen,
+            test_name: String::from("Declared strings start empty"),
+            test_code: String::from("{\n\tstring s\n\tprint(s)\n\tprint(len(s))\n\ts = \"x\"\n\tprint(s)\n}$"),
+            expectation: TestExpectation::ProgramOutput("\n0\nx\n")
+        },
+        TestCase {
+            test_type: TestType::CodeGen,
+            test_name: String::from("Declared strings start empty (RISC-V)"),
+            test_code: String::from("{\n\tstring s\n\tprint(s)\n\tprint(len(s))\n\ts = \"x\"\n\tprint(s)\n}$"),
+            expectation: TestExpectation::RiscVProgramOutput("\n0\nx\n")
+        },
+        TestCase {
+            test_type: TestType::CodeGen,
+            test_name: String::from("Minimize a program that does not fit in memory"),
+            test_code: String::from("{\n\tint a\n\ta = 1\n\twhile (a != 3) {\n\t\tprint(a)\n\t\ta = 1 + a\n\t}\n\tstring s\n\ts = \"the quick brown fox jumps over the lazy dog and the quick brown fox jumps over the lazy dog again and again and again\"\n\tif (a == 3) {\n\t\tprint(s)\n\t\tprint(\"pack my box with five dozen liquor jugs and then pack my box with five dozen more liquor jugs for the party tonight at nine\")\n\t}\n\tprint((a == 3))\n}$"),
+            expectation: TestExpectation::MinimizedProgram(Failure::CodeGenError(Target::Target6502), "{\n\tint a\n\ta = 1\n\twhile (a != 3) {\n\t\tprint(a)\n\t\ta = 1 + a\n\t}\n\tstring s\n\t\tprint(s)\n\t\tprint(\"pack my box with five dozen liquor jugs and then pack my box with five dozen more liquor jugs for the party tonight at nine\")\n\tprint((a == 3))\n}$")
+        },
+        TestCase {
+            test_type: TestType::CodeGen,
+            test_name: String::from("Chained additions are left associative"),
+            test_code: String::from("{\n\tint a\n\ta = 1 + 2 + 3\n\tprint(a)\n\ta = 1 + 2 + (3 + a) + 4\n\tprint(a)\n\tint b\n\tb = 0 + 0 + a\n\tprint(1 + 2 + 3 + (4 + 5) + b)\n}$"),
+            expectation: TestExpectation::ProgramOutput("6\n16\n31\n")
+        },
+        TestCase {
+            test_type: TestType::CodeGen,
+            test_name: String::from("Chained additions are left associative (RISC-V)"),
+            test_code: String::from("{\n\tint a\n\ta = 1 + 2 + 3\n\tprint(a)\n\ta = 1 + 2 + (3 + a) + 4\n\tprint(a)\n\tint b\n\tb = 0 + 0 + a\n\tprint(1 + 2 + 3 + (4 + 5) + b)\n}$"),
+            expectation: TestExpectation::RiscVProgramOutput("6\n16\n31\n")
+        },
+        TestCase {
+            test_type: TestType::CodeGen,
+            test_name: String::from("Boolean print code size"),
+            test_code: String::from("{\n\tboolean b\n\tprint(b)\n}$"),
+            expectation: TestExpectation::CodeSize(25, 20)
+        },
+        TestCase {
+            test_type: TestType::CodeGen,
+            // The second print adds 14 bytes when optimizing for size, which is 5 to compare b and 9 for the inline sequence
+            // A call to a shared copy would take at least 10 bytes (5 to store the return selector and 5 to jump), so it can never be smaller
+            test_name: String::from("Every boolean print is inline"),
+            test_code: String::from("{\n\tboolean b\n\tprint(b)\n\tprint(b)\n}$"),
+            expectation: TestExpectation::CodeSize(44, 34)
+        },
+        TestCase {
+            test_type: TestType::CodeGen,
+            test_name: String::from("If at the end of a loop"),
+            test_code: String::from("{\n\tint a\n\twhile (a != 3) {\n\t\ta = 1 + a\n\t\tif (a == 2) {\n\t\t\tprint(a)\n\t\t}\n\t}\n\tprint(a)\n}$"),
+            expectation: TestExpectation::ProgramOutput("2\n3\n")
+        },
+        TestCase {
+            test_type: TestType::CodeGen,
+            // The if skips over its body straight to the start of the loop instead of to the unconditional jump back there
+            test_name: String::from("Branch threading"),
+            test_code: String::from("{\n\tint a\n\twhile (a != 3) {\n\t\ta = 1 + a\n\t\tif (a == 2) {\n\t\t\tprint(a)\n\t\t}\n\t}\n}$"),
+            expectation: TestExpectation::OptimizedCode(vec![
+                "LDA #$00",
+                "STA $0049",
+                "LDA $0049",
+                "STA $00FC",
+                "LDX #$03",
+                "CPX $00FC",
+                "LDX #$00",
+                "BNE $02",
+                "LDX #$01",
+                "CPX $00FF",
+                "BNE $2D",
+                "LDA $0049",
+                "STA $00FC",
+                "LDA #$01",
+                "ADC $00FC",
+                "STA $0049",
+                "LDA $0049",
+                "STA $00FC",
+                "LDX #$02",
+                "CPX $00FC",
+                "BNE $CF",
+                "LDY $0049",
+                "LDX #$01",
+                "SYS",
+                "LDX #$02",
+                "LDY #$FD",
+                "SYS",
+                "LDX #$01",
+                "CPX $00FF",
+                "BNE $BD",
+                "BRK"
+            ])
+        },
+        TestCase {
+            test_type: TestType::CodeGen,
+            test_name: String::from("Chained additions wrap around"),
+            test_code: String::from("{\n\tint a\n\ta = 9 + 9 + 9 + 9 + 9 + 9 + 9 + 9 + 9 + 9 + 9 + 9 + 9 + 9 + 9 + 9 + 9 + 9 + 9 + 9 + 9 + 9 + 9 + 9 + 9 + 9 + 9 + 9 + 9\n\tprint(a)\n}$"),
+            expectation: TestExpectation::ProgramOutput("5\n")
+        },
+        TestCase {
+            test_type: TestType::CodeGen,
+            test_name: String::from("Chained additions wrap around (RISC-V)"),
+            test_code: String::from("{\n\tint a\n\ta = 9 + 9 + 9 + 9 + 9 + 9 + 9 + 9 + 9 + 9 + 9 + 9 + 9 + 9 + 9 + 9 + 9 + 9 + 9 + 9 + 9 + 9 + 9 + 9 + 9 + 9 + 9 + 9 + 9\n\tprint(a)\n}$"),
+            expectation: TestExpectation::RiscVProgramOutput("5\n")
+        },
+        TestCase {
+            test_type: TestType::CodeGen,
+            test_name: String::from("Sums past 999 wrap around (RISC-V)"),
+            // 112 nines add up to 1008, which has 4 digits before it wraps around
+            test_code: format!("{{\n\tint a\n\ta = {}\n\tprint(a)\n}}$", vec!["9"; 112].join(" + ")),
+            expectation: TestExpectation::RiscVProgramOutput("240\n")
+        },
+        TestCase {
+            test_type: TestType::CodeGen,
+            test_name: String::from("Shadowed variables in sibling scopes"),
+            test_code: String::from("{\n\tint a\n\ta = 1\n\t{\n\t\tint a\n\t\ta = 2\n\t\tprint(a)\n\t}\n\t{\n\t\tstring a\n\t\ta = \"x\"\n\t\tprint(a)\n\t\ta = \"y\"\n\t}\n\tprint(a)\n}$"),
+            expectation: TestExpectation::ProgramOutput("2\nx\n1\n")
+        },
+        TestCase {
+            test_type: TestType::CodeGen,
+            test_name: String::from("Shadowed variables in sibling scopes (RISC-V)"),
+            test_code: String::from("{\n\tint a\n\ta = 1\n\t{\n\t\tint a\n\t\ta = 2\n\t\tprint(a)\n\t}\n\t{\n\t\tstring a\n\t\ta = \"x\"\n\t\tprint(a)\n\t\ta = \"y\"\n\t}\n\tprint(a)\n}$"),
+            expectation: TestExpectation::RiscVProgramOutput("2\nx\n1\n")
+        },
+        TestCase {
+            test_type: TestType::CodeGen,
+            test_name: String::from("Scope comments"),
+            test_code: String::from("{\n\tint a\n\ta = 1\n\t{\n\t\tint a\n\t\ta = 2\n\t\tprint(a)\n\t}\n\twhile (a != 3) {\n\t\ta = 1 + a\n\t}\n\tprint(a)\n}$"),
+            expectation: TestExpectation::ScopeComments(vec![
+                "# enter scope 0 (line 1)", "# enter scope 1 (line 4)", "# exit scope 1", "# enter scope 2 (line 9)", "# exit scope 2", "# exit scope 0",
+                "scope 0 at line 1", "scope 1 at line 4", "scope 2 at line 9"
+            ])
+        },
+        TestCase {
+            test_type: TestType::CodeGen,
+            test_name: String::from("Heap overflow"),
+            // Each string fits in the heap, so semantic analysis allows them, but both of them do not
+            test_code: format!("{{\n\tstring s\n\ts = \"{}\"\n\tprint(s)\n\ts = \"{}\"\n\tprint(s)\n}}$", "a".repeat(130), "b".repeat(130)),
+            expectation: TestExpectation::CodeGenFails
+        },
+        TestCase {
+            test_type: TestType::CodeGen,
+            test_name: String::from("Assembly string with quotes"),
+            test_code: String::from("say \"hi\""),
+            expectation: TestExpectation::AssemblyString(r#"say \"hi\""#)
+        },
+        TestCase {
+            test_type: TestType::CodeGen,
+            test_name: String::from("Assembly string with backslashes"),
+            test_code: String::from("a\\b\\"),
+            expectation: TestExpectation::AssemblyString(r#"a\\b\\"#)
+        },
+        TestCase {
+            test_type: TestType::CodeGen,
+            test_name: String::from("Assembly string with control characters"),
+            test_code: String::from("tab\there\nline\r\0"),
+            expectation: TestExpectation::AssemblyString(r#"tab\there\nline\015\000"#)
+        },
+        TestCase {
+            test_type: TestType::CodeGen,
+            test_name: String::from("Assembly string with multi-byte characters"),
+            test_code: String::from("caf\u{e9} \u{2713}"),
+            expectation: TestExpectation::AssemblyString(r#"caf\303\251 \342\234\223"#)
+        },
+        TestCase {
+            test_type: TestType::Lex,
+            test_name: String::from("Markup in string literal"),
+            test_code: String::from("{ print(\"<script>alert</script>\") }$"),
+            expectation: TestExpectation::LexErrors(5)
+        },
+        TestCase {
+            test_type: TestType::Lex,
+            test_name: String::from("Markup symbols in code"),
+            test_code: String::from("{ a = 1 < 2 & 3 }$"),
+            expectation: TestExpectation::LexErrors(2)
+        },
+        TestCase {
+            test_type: TestType::Lex,
+            test_name: String::from("Escape markup in source"),
+            test_code: String::from("{ print(\"<script>alert('hi')</script>\") }$"),
+            expectation: TestExpectation::HtmlText("{ print(&quot;&lt;script&gt;alert(&#39;hi&#39;)&lt;/script&gt;&quot;) }$")
+        },
+        TestCase {
+            test_type: TestType::Lex,
+            test_name: String::from("Escape ampersands and entities"),
+            test_code: String::from("a && b &lt; c"),
+            expectation: TestExpectation::HtmlText("a &amp;&amp; b &amp;lt; c")
+        },
+        TestCase {
+            test_type: TestType::Lex,
+            test_name: String::from("Escape lexer error message"),
+            test_code: String::from("Error at (1, 9); Unrecognized token '<'"),
+            expectation: TestExpectation::HtmlText("Error at (1, 9); Unrecognized token &#39;&lt;&#39;")
+        },
+        TestCase {
+            test_type: TestType::Editor,
+            test_name: String::from("Comment one line"),
+            test_code: String::from("{\n\tint a\n\ta = 1\n}$"),
+            expectation: TestExpectation::ToggleComment(2, 2, "{\n\t/* int a */\n\ta = 1\n}$")
+        },
+        TestCase {
+            test_type: TestType::Editor,
+            test_name: String::from("Comment several statements"),
+            test_code: String::from("{\n\tint a\n\ta = 1\n}$"),
+            expectation: TestExpectation::ToggleComment(2, 3, "{\n\t/* int a */\n\t/* a = 1 */\n}$")
+        },
+        TestCase {
+            test_type: TestType::Editor,
+            test_name: String::from("Uncomment lines"),
+            test_code: String::from("{\n\t/* int a */\n\t/*a = 1*/\n}$"),
+            expectation: TestExpectation::ToggleComment(2, 3, "{\n\tint a\n\ta = 1\n}$")
+        },
+        TestCase {
+            test_type: TestType::Editor,
+            test_name: String::from("Comment line with a comment"),
+            test_code: String::from("{\n\ta = 1 /* set a */\n}$"),
+            expectation: TestExpectation::ToggleComment(2, 2, "{\n\t/* a = 1 */ /* set a */\n}$")
+        },
+        TestCase {
+            test_type: TestType::Editor,
+            test_name: String::from("Comment mixed selection"),
+            test_code: String::from("{\n\t/* int a */\n\ta = 1\n}$"),
+            expectation: TestExpectation::ToggleComment(2, 3, "{\n\t/* int a */\n\t/* a = 1 */\n}$")
+        },
+        TestCase {
+            test_type: TestType::Editor,
+            test_name: String::from("Lines in a block comment are not changed"),
+            test_code: String::from("/*\nint a\n*/\n{}$"),
+            expectation: TestExpectation::ToggleComment(2, 2, "/*\nint a\n*/\n{}$")
+        },
+        TestCase {
+            test_type: TestType::Editor,
+            test_name: String::from("Comment skips blank lines"),
+            test_code: String::from("{\n\n\tprint(a)\n}$"),
+            expectation: TestExpectation::ToggleComment(1, 3, "/* { */\n\n\t/* print(a) */\n}$")
+        },
+        TestCase {
+            test_type: TestType::Editor,
+            test_name: String::from("Minimal workspace"),
+            test_code: String::from(r#"{"version": 1, "source": "{}$"}"#),
+            expectation: TestExpectation::Workspace(Ok(()))
+        },
+        TestCase {
+            test_type: TestType::Editor,
+            test_name: String::from("Full workspace"),
+            test_code: String::from(r#"{"version": 1, "source": "{}$", "editorSettings": {"theme": "dark", "fontSize": 20, "wrap": true}, "compilerOptions": {"target": "RISC-V", "optimizeSize": true, "reservedCells": "4", "heapSplit": "0x80", "lints": {"shadowing": false}}, "artifacts": [{"programNumber": 1, "cst": null, "statistics": {"tokenCount": 2}}]}"#),
+            expectation: TestExpectation::Workspace(Ok(()))
+        },
+        TestCase {
+            test_type: TestType::Editor,
+            test_name: String::from("Workspace from a newer version"),
+            test_code: String::from(r#"{"version": 2, "source": "{}$"}"#),
+            expectation: TestExpectation::Workspace(Err("Workspace version 2 is not supported, which must be 1"))
+        },
+        TestCase {
+            test_type: TestType::Editor,
+            test_name: String::from("Workspace without a version"),
+            test_code: String::from(r#"{"source": "{}$"}"#),
+            expectation: TestExpectation::Workspace(Err("Invalid workspace, which must have a version number"))
+        },
+        TestCase {
+            test_type: TestType::Editor,
+            test_name: String::from("Workspace without a source"),
+            test_code: String::from(r#"{"version": 1}"#),
+            expectation: TestExpectation::Workspace(Err("Invalid workspace: missing field `source`"))
+        },
+        TestCase {
+            test_type: TestType::Editor,
+            test_name: String::from("Workspace with an invalid target"),
+            test_code: String::from(r#"{"version": 1, "source": "{}$", "compilerOptions": {"target": "x86"}}"#),
+            expectation: TestExpectation::Workspace(Err("Invalid target [ x86 ], which must be 6502 or RISC-V"))
+        },
+        TestCase {
+            test_type: TestType::Editor,
+            test_name: String::from("Workspace with an output device outside of the reserved cells"),
+            test_code: String::from(r#"{"version": 1, "source": "{}$", "compilerOptions": {"reservedCells": "3", "mmioIntOut": "F0", "mmioStringOut": "FE"}}"#),
+            expectation: TestExpectation::Workspace(Err("Invalid output address [ F0 ], which must be a hex address between 0xFD and 0xFE"))
+        },
+        TestCase {
+            test_type: TestType::Editor,
+            test_name: String::from("Workspace with custom system calls that are the same"),
+            test_code: String::from(r#"{"version": 1, "source": "{}$", "compilerOptions": {"target": "RISC-V", "syscallConvention": "custom", "syscallWrite": "7", "syscallExit": "7"}}"#),
+            expectation: TestExpectation::Workspace(Err("The write and exit system calls cannot both be 7"))
+        },
+        TestCase {
+            test_type: TestType::Editor,
+            test_name: String::from("Saved options"),
+            test_code: String::from(r#"{"version": 1, "compilerOptions": {"target": "RISC-V", "syscallConvention": "rars", "lints": {"shadowing": false}}}"#),
+            expectation: TestExpectation::SavedOptions(Ok(()))
+        },
+        TestCase {
+            test_type: TestType::Editor,
+            test_name: String::from("Saved options from a newer version"),
+            test_code: String::from(r#"{"version": 2, "compilerOptions": {}}"#),
+            expectation: TestExpectation::SavedOptions(Err("Saved options version 2 is not supported, which must be 1"))
+        },
+        TestCase {
+            test_type: TestType::Editor,
+            test_name: String::from("Saved options with an invalid nesting limit"),
+            test_code: String::from(r#"{"version": 1, "compilerOptions": {"maxNestingDepth": "0"}}"#),
+            expectation: TestExpectation::SavedOptions(Err("Invalid nesting limit [ 0 ], which must be between 1 and 64"))
+        },
+        TestCase {
+            test_type: TestType::Editor,
+            test_name: String::from("Saved options with a string length limit past the heap"),
+            test_code: String::from(r#"{"version": 1, "compilerOptions": {"reservedCells": "3", "heapSplit": "80", "maxStringLength": "126"}}"#),
+            expectation: TestExpectation::SavedOptions(Err("Invalid string length limit [ 126 ], which must be between 1 and 123 on the 6502"))
+        },
+        TestCase {
+            test_type: TestType::Editor,
+            test_name: String::from("Saved options with a course stage"),
+            test_code: String::from(r#"{"version": 1, "compilerOptions": {"courseStage": "parse"}}"#),
+            expectation: TestExpectation::SavedOptions(Ok(()))
+        },
+        TestCase {
+            test_type: TestType::Editor,
+            test_name: String::from("Saved options with an invalid course stage"),
+            test_code: String::from(r#"{"version": 1, "compilerOptions": {"courseStage": "optimize"}}"#),
+            expectation: TestExpectation::SavedOptions(Err("Invalid course stage [ optimize ], which must be one of lex, parse, semantic, codegen"))
+        },
+        TestCase {
+            test_type: TestType::Editor,
+            test_name: String::from("Saved options with invalid identifier characters"),
+            test_code: String::from(r#"{"version": 1, "compilerOptions": {"identifierCharset": "a-z-"}}"#),
+            expectation: TestExpectation::SavedOptions(Err("Invalid identifier characters [ a-z- ], which can only be a-z, 0-9, and _ after the first letter"))
+        },
+        TestCase {
+            test_type: TestType::Editor,
+            test_name: String::from("Workspace with an unknown lint"),
+            test_code: String::from(r#"{"version": 1, "source": "{}$", "compilerOptions": {"lints": {"tabs": true}}}"#),
+            expectation: TestExpectation::Workspace(Err("Invalid lint [ tabs ], which must be one of empty-block, constant-condition, self-assignment, self-comparison, shadowing, string-heap"))
+        },
+        TestCase {
+            test_type: TestType::Editor,
+            test_name: String::from("Workspace with an invalid font size"),
+            test_code: String::from(r#"{"version": 1, "source": "{}$", "editorSettings": {"fontSize": 100}}"#),
+            expectation: TestExpectation::Workspace(Err("Invalid font size [ 100 ], which must be between 8 and 32"))
+        },
+        TestCase {
+            test_type: TestType::Editor,
+            test_name: String::from("Workspace with an invalid memory layout"),
+            test_code: String::from(r#"{"version": 1, "source": "{}$", "compilerOptions": {"reservedCells": "0"}}"#),
+            expectation: TestExpectation::Workspace(Err("Invalid number of reserved cells [ 0 ], which must be between 1 and 128"))
+        },
+        TestCase {
+            test_type: TestType::Editor,
+            test_name: String::from("Workspace with artifacts out of order"),
+            test_code: String::from(r#"{"version": 1, "source": "{}$", "artifacts": [{"programNumber": 2}]}"#),
+            expectation: TestExpectation::Workspace(Err("Invalid artifacts for program 2, which should be program 1"))
+        },
+        TestCase {
+            test_type: TestType::Editor,
+            test_name: String::from("Embed options"),
+            test_code: String::from(r#"{"target": "RISC-V", "source": "{}$", "rows": 5}"#),
+            expectation: TestExpectation::EmbedOptions(Ok(()))
+        },
+        TestCase {
+            test_type: TestType::Editor,
+            test_name: String::from("Embed options with an invalid target"),
+            test_code: String::from(r#"{"target": "arm"}"#),
+            expectation: TestExpectation::EmbedOptions(Err("Invalid target [ arm ], which must be 6502 or RISC-V"))
+        },
+        TestCase {
+            test_type: TestType::Editor,
+            test_name: String::from("Embed options with no rows"),
+            test_code: String::from(r#"{"rows": 0}"#),
+            expectation: TestExpectation::EmbedOptions(Err("Invalid number of rows [ 0 ], which must be between 1 and 100"))
+        },
+        TestCase {
+            test_type: TestType::Editor,
+            test_name: String::from("Embed options with an unknown option"),
+            test_code: String::from(r#"{"height": 5}"#),
+            expectation: TestExpectation::EmbedOptions(Err("Invalid embed options: unknown field `height`, expected one of `target`, `source`, `rows` at line 1 column 9"))
+        },
+        TestCase {
+            test_type: TestType::Editor,
+            test_name: String::from("Source in the link"),
+            test_code: String::from("?stage=parse&src=%7B%0A%09print(%22hi+there%22)%0A%7D%24"),
+            expectation: TestExpectation::LinkedSource(Ok("{\n\tprint(\"hi there\")\n}$"))
+        },
+        TestCase {
+            test_type: TestType::Editor,
+            test_name: String::from("Gist in the link"),
+            test_code: String::from("?gist=https%3A%2F%2Fgist.githubusercontent.com%2Fprof%2Fabc123%2Fraw%2Flab1.txt"),
+            expectation: TestExpectation::LinkedSource(Ok("https://gist.githubusercontent.com/prof/abc123/raw/lab1.txt"))
+        },
+        TestCase {
+            test_type: TestType::Editor,
+            test_name: String::from("Gist in the link from another site"),
+            test_code: String::from("?gist=https://example.com/lab1.txt"),
+            expectation: TestExpectation::LinkedSource(Err("Invalid gist [ https://example.com/lab1.txt ], which must be a raw gist link starting with https://gist.githubusercontent.com/"))
+        },
+        TestCase {
+            test_type: TestType::Editor,
+            test_name: String::from("Source in the link with a bad escape"),
+            test_code: String::from("?src=%7B%7D%2"),
+            expectation: TestExpectation::LinkedSource(Err("Invalid escape [ %2 ] in the link"))
+        },
+        TestCase {
+            test_type: TestType::Editor,
+            test_name: String::from("Shared link"),
+            test_code: String::from("/* caf\u{e9} */\n{\n\tstring s\n\ts = \"hi\"\n\tprint(s)\n}$\r\n{}$"),
+            expectation: TestExpectation::SharedLink(r#"{"target": "RISC-V", "scopeComments": true, "maxNestingDepth": "4", "lints": {"shadowing": false}}"#, Ok(()))
+        },
+        TestCase {
+            test_type: TestType::Editor,
+            test_name: String::from("Shared link of a long program"),
+            test_code: String::from("{\n\tint a\n\ta = 1\n\tprint(a)\n}$\n".repeat(50) + "{}$"),
+            expectation: TestExpectation::SharedLink("{}", Ok(()))
+        },
+        TestCase {
+            test_type: TestType::Editor,
+            test_name: String::from("Shared link with a character that is not base64"),
+            test_code: String::from("#share=abc!"),
+            expectation: TestExpectation::SharedLink("{}", Err("Invalid character [ ! ] in the shared link"))
+        },
+        TestCase {
+            test_type: TestType::Editor,
+            test_name: String::from("Shared link that is not compressed"),
+            test_code: String::from("#share=aGVsbG8"),
+            expectation: TestExpectation::SharedLink("{}", Err("The shared link is not a compressed program or is too large"))
+        },
+        TestCase {
+            test_type: TestType::SemanticAnalysis,
+            test_name: String::from("Semantic tokens of declarations and uses"),
+            test_code: String::from("{\n\tint a\n\tint b\n\tprint(a)\n\ta = 1\n\tprint(a)\n}$"),
+            expectation: TestExpectation::SemanticTokens(vec![
+                "a (2, 6) Declaration",
+                "b (3, 6) UnusedDeclaration",
+                "a (4, 8) UninitializedUse",
+                "a (5, 2) Assignment",
+                "a (6, 8) InitializedUse"
+            ])
+        },
+        TestCase {
+            test_type: TestType::SemanticAnalysis,
+            test_name: String::from("Semantic tokens link uses to the closest declaration"),
+            test_code: String::from("{\n\tint a\n\ta = 1\n\t{\n\t\tstring a\n\t\tprint(a)\n\t}\n\tprint(a)\n}$"),
+            expectation: TestExpectation::SemanticTokens(vec![
+                "a (2, 6) Declaration",
+                "a (3, 2) Assignment",
+                "a (5, 10) Declaration",
+                "a (6, 9) UninitializedUse",
+                "a (8, 8) InitializedUse"
+            ])
+        },
+        TestCase {
+            test_type: TestType::SemanticAnalysis,
+            test_name: String::from("Semantic tokens of undeclared identifiers"),
+            test_code: String::from("{\n\tc = 1\n\tprint(c)\n}$"),
+            expectation: TestExpectation::SemanticTokens(vec![
+                "c (2, 2) Undeclared",
+                "c (3, 8) Undeclared"
+            ])
+        },
+        TestCase {
+            test_type: TestType::SemanticAnalysis,
+            test_name: String::from("Mutation with a missing brace"),
+            test_code: String::from("{\n\tint a\n\ta = 1\n\twhile (a != 3) {\n\t\ta = 1 + a\n\t}\n\tprint(a)\n}$"),
+            expectation: TestExpectation::Mutation(DefectClass::MissingBrace)
+        },
+        TestCase {
+            test_type: TestType::SemanticAnalysis,
+            test_name: String::from("Mutation with a bad token"),
+            test_code: String::from("{\n\tint a\n\ta = 1\n\twhile (a != 3) {\n\t\ta = 1 + a\n\t}\n\tprint(a)\n}$"),
+            expectation: TestExpectation::Mutation(DefectClass::BadToken)
+        },
+        TestCase {
+            test_type: TestType::SemanticAnalysis,
+            test_name: String::from("Mutation with a type mismatch"),
+            test_code: String::from("{\n\tint a\n\ta = 1\n\twhile (a != 3) {\n\t\ta = 1 + a\n\t}\n\tprint(a)\n}$"),
+            expectation: TestExpectation::Mutation(DefectClass::TypeMismatch)
+        },
+        TestCase {
+            test_type: TestType::SemanticAnalysis,
+            test_name: String::from("Mutation with a undeclared id"),
+            test_code: String::from("{\n\tint a\n\ta = 1\n\twhile (a != 3) {\n\t\ta = 1 + a\n\t}\n\tprint(a)\n}$"),
+            expectation: TestExpectation::Mutation(DefectClass::UndeclaredId)
+        },
+        TestCase {
+            test_type: TestType::Lex,
+            test_name: String::from("Message codes of the lexer"),
+            test_code: String::from("{\n\tPrint(1)\n\ta = \"b\n}"),
+            expectation: TestExpectation::MessageCodes(vec!["L002", "L005", "L007"])
+        },
+        TestCase {
+            test_type: TestType::Parse,
+            test_name: String::from("Message codes of the parser"),
+            test_code: String::from("{\n\tprint()\n\tprint(1 2)\n}$"),
+            expectation: TestExpectation::MessageCodes(vec!["P013", "P014"])
+        },
+        TestCase {
+            test_type: TestType::Parse,
+            test_name: String::from("Assignment instead of equality in conditions"),
+            test_code: String::from("{\n\tint a\n\twhile (a = 1) {\n\t\ta = 1\n\t}\n}$\n{\n\tif (true = false) {}\n}$"),
+            expectation: TestExpectation::MessageCodes(vec!["P023", "P023"])
+        },
+        TestCase {
+            test_type: TestType::Parse,
+            test_name: String::from("Empty bodies of if and while statements"),
+            test_code: String::from("{\n\tif (1 == 2) {}\n\twhile false {\n\t}\n\t{}\n}$"),
+            expectation: TestExpectation::MessageCodes(vec!["P018", "P018", "P016", "W001", "W001", "W001", "W002", "W002"])
+        },
+        TestCase {
+            test_type: TestType::SemanticAnalysis,
+            test_name: String::from("Message codes of semantic analysis"),
+            test_code: String::from("{\n\tint a\n\tprint(b)\n\ta = \"hi\"\n}$"),
+            expectation: TestExpectation::MessageCodes(vec!["S002", "S003", "S012"])
+        },
+        TestCase {
+            test_type: TestType::SemanticAnalysis,
+            test_name: String::from("Blocks nested past the limit"),
+            test_code: String::from("{{{{{{{{print(1)}}}}}}}}$\n{{{{{{{{{{print(2)}}}}}}}}}}$"),
+            expectation: TestExpectation::MessageCodes(vec!["S014"])
+        },
+        TestCase {
+            test_type: TestType::SemanticAnalysis,
+            test_name: String::from("Message codes stay in the order of the programs"),
+            test_code: String::from("{print(a)}$\n{int b}$\n{Print(1)}$"),
+            expectation: TestExpectation::MessageCodes(vec!["S002", "S012", "L002"])
+        },
+        TestCase {
+            test_type: TestType::Editor,
+            test_name: String::from("Positions sort line first"),
+            test_code: String::from("3:1 1:10 2:7 1:2"),
+            expectation: TestExpectation::SortedPositions(vec!["line 1, col 2", "line 1, col 10", "line 2, col 7", "line 3, col 1"])
+        },
+        TestCase {
+            test_type: TestType::SemanticAnalysis,
+            test_name: String::from("Translated messages"),
+            test_code: String::from("{\n\tprint(b)\n}$"),
+            expectation: TestExpectation::TranslatedMessages(
+                "{ \"S002\": \"Erreur à {position} ; l'identifiant [ {id} ] n'a pas été déclaré\" }",
+                vec!["Erreur à (2, 8) ; l'identifiant [ b ] n'a pas été déclaré"]
+            )
+        },
+        TestCase {
+            test_type: TestType::SemanticAnalysis,
+            test_name: String::from("Translated warnings for a range of code"),
+            test_code: String::from("{\n\twhile (1 == 2) {}\n}$"),
+            expectation: TestExpectation::TranslatedMessages(
+                "{ \"P018\": \"Avertissement à {span} ; la condition de {statement} n'a aucun effet\" }",
+                vec![
+                    "Avertissement à (2, 8)-(2, 16) ; la condition de while n'a aucun effet",
+                    "Warning at (2, 9); Empty block has no effect [empty-block]",
+                    "Warning at (2, 9); The while condition is always false [constant-condition]"
+                ]
+            )
+        },
+        TestCase {
+            test_type: TestType::SemanticAnalysis,
+            test_name: String::from("Lint warnings have codes"),
+            test_code: String::from("{ int a = 1 a = a print(a) if true { print(a) } { int a = 2 print(a) } int b }$"),
+            expectation: TestExpectation::MessageCodes(vec!["S012", "W002", "W003", "W006"])
+        },
+        TestCase {
+            test_type: TestType::SemanticAnalysis,
+            test_name: String::from("Translated lint warnings"),
+            test_code: String::from("{\n\tint a\n\ta = 1\n\t{\n\t\tint a\n\t\ta = 2\n\t\tprint(a)\n\t}\n\tprint(a)\n}$"),
+            expectation: TestExpectation::TranslatedMessages(
+                "{ \"W006\": \"Avertissement à {position} ; l'identifiant [ {id} ] masque la déclaration à {declared_position} [shadowing]\" }",
+                vec!["Avertissement à (5, 7) ; l'identifiant [ a ] masque la déclaration à (2, 6) [shadowing]"]
+            )
+        },
+        TestCase {
+            test_type: TestType::SemanticAnalysis,
+            test_name: String::from("Define errors are in the catalog"),
+            test_code: String::from("define max 1\ndefine MAX a\ndefine MIN 1\n  define MIN 2\n{ print(MIN) }$"),
+            expectation: TestExpectation::TranslatedMessages("{}", vec![
+                "Error at (1, 1); Invalid constant name [ max ], which must be uppercase letters and underscores",
+                "Error at (2, 1); Invalid value [ a ] for constant [ MAX ], which must be a digit, boolean, or string",
+                "Error at (4, 3); Constant [ MIN ] has already been defined"
+            ])
+        },
+        TestCase {
+            test_type: TestType::SemanticAnalysis,
+            test_name: String::from("Similarity of a program with renamed variables"),
+            test_code: String::from("{ int a a = 3 while (a != 9) { print(a) a = 1 + a } }$"),
+            expectation: TestExpectation::Similarity("{\n\tint c\n\tc = 3\n\twhile (c != 9) {\n\t\tprint(c) /* count up */\n\t\tc = 1 + c\n\t}\n}$", 100)
+        },
+        TestCase {
+            test_type: TestType::SemanticAnalysis,
+            test_name: String::from("Similarity of a program with an added statement"),
+            test_code: String::from("{ int a a = 3 while (a != 9) { print(a) a = 1 + a } }$"),
+            expectation: TestExpectation::Similarity("{ int b b = 3 while (b != 9) { print(b) b = 1 + b } print(\"done\") }$", 85)
+        },
+        TestCase {
+            test_type: TestType::SemanticAnalysis,
+            test_name: String::from("Similarity of unrelated programs"),
+            test_code: String::from("{ int a a = 3 while (a != 9) { print(a) a = 1 + a } }$"),
+            expectation: TestExpectation::Similarity("{ string s s = \"hello\" if (s == \"hello\") { print(s) } }$", 0)
+        },
+        TestCase {
+            test_type: TestType::SemanticAnalysis,
+            test_name: String::from("Grading diagnostics and output with a rubric"),
+            test_code: String::from("{\n\tint a\n\ta = 2\n\tprint(1 + a)\n}$\n{\n\tint b\n\tb = \"hi\"\n}$"),
+            expectation: TestExpectation::Grade(
+                "{ \"version\": 1, \"checks\": [
+                    { \"name\": \"Prints 3\", \"points\": 2, \"programNumber\": 1, \"output\": \"3\", \"errors\": [] },
+                    { \"name\": \"Type mismatch\", \"points\": 3, \"programNumber\": 2, \"errors\": [{ \"code\": \"S003\", \"line\": 8 }] }
+                ] }",
+                "5 of 5 points"
+            )
+        },
+        TestCase {
+            test_type: TestType::SemanticAnalysis,
+            test_name: String::from("Feedback of failed rubric checks"),
+            test_code: String::from("{\n\tint a\n\ta = 2\n\tprint(a)\n}$"),
+            expectation: TestExpectation::Grade(
+                "{ \"version\": 1, \"checks\": [
+                    { \"name\": \"Prints 3\", \"programNumber\": 1, \"output\": \"3\\n\" },
+                    { \"name\": \"Type mismatch\", \"programNumber\": 1, \"errors\": [{ \"code\": \"S003\" }] },
+                    { \"name\": \"Second program\", \"programNumber\": 2, \"warnings\": [] }
+                ] }",
+                "0 of 3 points\nPrints 3: Expected output [ 3 ] but printed [ 2 ]\nType mismatch: Expected errors [ S003 ] but found none\nSecond program: There is no program 2"
+            )
+        },
+        TestCase {
+            test_type: TestType::SemanticAnalysis,
+            test_name: String::from("Rubric check without an expectation"),
+            test_code: String::from("{}$"),
+            expectation: TestExpectation::Grade(
+                "{ \"version\": 1, \"checks\": [{ \"name\": \"Compiles\", \"programNumber\": 1 }] }",
+                "Invalid check [ Compiles ], which must expect errors, warnings, or output"
+            )
+        },
+        TestCase {
+            test_type: TestType::Lex,
+            test_name: String::from("Identifiers with digits and underscores"),
+            test_code: String::from("{ int count_2 count_2 = 4 string name name = \"hi\" print(count_2) print(name) }$"),
+            expectation: TestExpectation::ProfileOutput("extended", "a-z0-9_", "4\nhi\n")
+        },
+        TestCase {
+            test_type: TestType::Lex,
+            test_name: String::from("Longer identifiers in the course grammar"),
+            test_code: String::from("{ int ab ab = 4 }$"),
+            expectation: TestExpectation::ProfileOutput("extended", "", "Program 1 failed at parse with [ P001 ]\n")
+        },
+        TestCase {
+            test_type: TestType::Lex,
+            test_name: String::from("Invalid identifier characters"),
+            test_code: String::from("{}$"),
+            expectation: TestExpectation::ProfileOutput("extended", "A-Z", "Invalid identifier characters [ A-Z ], which can only be a-z, 0-9, and _ after the first letter")
+        },
+        TestCase {
+            test_type: TestType::Lex,
+            test_name: String::from("Course grammar in the course profile"),
+            test_code: String::from("{ int a a = 1 + 2 string s s = \"hi\" if (a == 3) { print(s) } }$"),
+            expectation: TestExpectation::ProfileOutput("course", "", "hi\n")
+        },
+        TestCase {
+            test_type: TestType::Lex,
+            test_name: String::from("Extension tokens in the course profile"),
+            test_code: String::from("{ char c c = 'a' string s print(len(s)) print(s[0]) }$"),
+            expectation: TestExpectation::ProfileOutput("course", "", "Program 1 failed at lex with [ L008, L008, L008, L008, L008 ]\n")
+        },
+        TestCase {
+            test_type: TestType::Parse,
+            test_name: String::from("Grouped expression in the course profile"),
+            test_code: String::from("{ int a a = (1 + 2) + 3 }$"),
+            expectation: TestExpectation::ProfileOutput("course", "", "Program 1 failed at parse with [ P020 ]\n")
+        },
+        TestCase {
+            test_type: TestType::Lex,
+            test_name: String::from("Constant in the course profile"),
+            test_code: String::from("define MAX 9\n{ print(MAX) }$"),
+            expectation: TestExpectation::ProfileOutput("course", "", "Error at (1, 1); Constant [ MAX ] cannot be defined because the constant extension is not allowed in the course language profile\nPreprocessing failed with 1 error(s)")
+        },
+        TestCase {
+            test_type: TestType::Lex,
+            test_name: String::from("Identifier characters in the course profile"),
+            test_code: String::from("{}$"),
+            expectation: TestExpectation::ProfileOutput("course", "a-z", "Identifier characters cannot be changed in the course language profile, which only has single letter identifiers")
+        }
+    ];
+
+    return test_cases;
+}
@@ -1,7 +1,15 @@
+use std::rc::Rc;
+
 use wasm_bindgen::{JsCast, prelude::Closure};
 use web_sys::{Document, HtmlSelectElement, HtmlOptionElement, Window, Element};
 
 use crate::util::test::*;
+use crate::util::program_generator::{ProgramGenerator, ProgramGeneratorConfig};
+use crate::util::program_mutator::{ProgramMutator, MutatedProgram, check_mutated_program};
+use crate::util::nexus_log;
+use crate::nexus::syntax_tree::SyntaxTree;
+use crate::nexus::code_generator_6502::CodeGenerator6502;
+use crate::editor::command_palette;
 
 use wasm_bindgen::prelude::*;
 
@@ -27,8 +35,23 @@ pub fn create_test_environment(document: &Document) {
         .get_element_by_id("load-test-btn")
         .expect("There should be an element called load-test-btn");
 
+    // Grab the generate test program button
+    let generate_test_btn: Element = document
+        .get_element_by_id("generate-test-btn")
+        .expect("There should be an element called generate-test-btn");
+
+    // Grab the mutation test button
+    let mutation_test_btn: Element = document
+        .get_element_by_id("mutation-test-btn")
+        .expect("There should be an element called mutation-test-btn");
+
     load_tests(document, &test_options);
-    add_test_button_fn(&load_test_btn)
+    add_test_button_fn(&load_test_btn);
+    add_generate_button_fn(&generate_test_btn);
+    add_mutation_test_button_fn(&mutation_test_btn);
+
+    command_palette::register_click_action("Generate test program", "generate-test-btn");
+    command_palette::register_click_action("Run mutation tests", "mutation-test-btn");
 }
 
 // Function to load the tests into the select element
@@ -37,7 +60,7 @@ fn load_tests(document: &Document, test_selection: &HtmlSelectElement) {
     let tests: Vec<Test> = get_tests();
     
     // Loop through all of the tests
-    for test in tests.iter() {
+    for (test_index, test) in tests.iter().enumerate() {
         // Create the new option element with the given name and value
         let new_option = document
             .create_element("option")
@@ -49,6 +72,13 @@ fn load_tests(document: &Document, test_selection: &HtmlSelectElement) {
 
         // Add the option element to the dropdown menu
         test_selection.add_with_html_option_element(&new_option).expect("Should be able to add the option");
+
+        // Each test can also be opened from the command palette by its number in the dropdown
+        let test_code: String = test.test_code.to_owned();
+        command_palette::register_action(
+            &format!("Open example {}: [{}] - {}", test_index + 1, test.test_type, test.test_name),
+            Rc::new(move || load_program(&test_code))
+        );
     }
 }
 
@@ -66,6 +96,79 @@ fn add_test_button_fn(load_test_btn: &Element) {
     load_test_fn.forget();
 }
 
+// Function to set up the random program generator
+fn add_generate_button_fn(generate_test_btn: &Element) {
+    let generate_test_fn: Closure<dyn FnMut()> = Closure::wrap(Box::new(move || {
+        // Use the current time as the seed so each click gives a new program
+        let seed: u64 = js_sys::Date::now() as u64;
+        let mut program_generator: ProgramGenerator = ProgramGenerator::new(ProgramGeneratorConfig::new(seed));
+
+        load_program(&program_generator.generate_program());
+    }) as Box<dyn FnMut()>);
+
+    generate_test_btn.add_event_listener_with_callback("click", generate_test_fn.as_ref().unchecked_ref()).expect("Should be able to add the event listener");
+    generate_test_fn.forget();
+}
+
+// Function to set up the mutation-based negative tests
+fn add_mutation_test_button_fn(mutation_test_btn: &Element) {
+    let mutation_test_fn: Closure<dyn FnMut()> = Closure::wrap(Box::new(move || {
+        run_mutation_tests(20);
+    }) as Box<dyn FnMut()>);
+
+    mutation_test_btn.add_event_listener_with_callback("click", mutation_test_fn.as_ref().unchecked_ref()).expect("Should be able to add the event listener");
+    mutation_test_fn.forget();
+}
+
+// Function to generate valid programs, give each one defect, and make sure the compiler reports it correctly
+fn run_mutation_tests(num_programs: u64) {
+    SyntaxTree::clear_display();
+    CodeGenerator6502::clear_display();
+    nexus_log::clear_logs();
+
+    let seed: u64 = js_sys::Date::now() as u64;
+    let mut program_mutator: ProgramMutator = ProgramMutator::new(seed);
+    let mut num_passed: u64 = 0;
+
+    for i in 0..num_programs {
+        let mut program_generator: ProgramGenerator = ProgramGenerator::new(ProgramGeneratorConfig::new(seed + i));
+        let program: String = program_generator.generate_program();
+
+        let mutated_res: Option<MutatedProgram> = program_mutator.mutate(&program);
+        if mutated_res.is_none() {
+            // Nothing could be changed in the program, so it does not count
+            continue;
+        }
+        let mutated_program: MutatedProgram = mutated_res.unwrap();
+
+        nexus_log::insert_empty_line();
+        let check_res: Result<(), String> = check_mutated_program(&mutated_program);
+        nexus_log::insert_empty_line();
+
+        if check_res.is_ok() {
+            num_passed += 1;
+            nexus_log::log(
+                nexus_log::LogTypes::Info,
+                nexus_log::LogSource::NEXUS,
+                format!("Mutation test {} passed ({}: {})", i + 1, mutated_program.defect_class, mutated_program.description)
+            );
+        } else {
+            nexus_log::log(
+                nexus_log::LogTypes::Error,
+                nexus_log::LogSource::NEXUS,
+                format!("Mutation test {} failed ({}: {}); {}", i + 1, mutated_program.defect_class, mutated_program.description, check_res.unwrap_err())
+            );
+        }
+    }
+
+    nexus_log::insert_empty_line();
+    nexus_log::log(
+        nexus_log::LogTypes::Info,
+        nexus_log::LogSource::NEXUS,
+        format!("{} of {} mutation tests passed with seed {}", num_passed, num_programs, seed)
+    );
+}
+
 // Function that returns a vector of all of the tests
 fn get_tests() -> Vec<Test> {
     let tests: Vec<Test> = vec![
@@ -127,7 +230,7 @@ fn get_tests() -> Vec<Test> {
         Test {
             test_type: TestType::Parse,
             test_name: String::from("Missing $"),
-            test_code: String::from("{/* This should throw an error */}")
+            test_code: String::from("{/* The $ is assumed with a warning */}")
         },
         Test {
             test_type: TestType::Parse,
@@ -204,6 +307,26 @@ fn get_tests() -> Vec<Test> {
             test_name: String::from("Lots of warnings"),
             test_code: String::from("{\n\t/* Uninitialized and never used */\n\tint a\n\t/* Uninitialized and used */\n\tint b\n\t/* Initialized but never used */\n\tint c\n\tc = 2 + b\n\t/* Initialized after being used */\n\tint d\n\tc = d\n\td = 5\n}$")
         },
+        Test {
+            test_type: TestType::SemanticAnalysis,
+            test_name: String::from("Integer overflow warnings"),
+            test_code: String::from("{\n\t/* Constant expression evaluates to 279 */\n\tint a\n\ta = 9 + 9 + 9 + 9 + 9 + 9 + 9 + 9 + 9 + 9 + 9 + 9 + 9 + 9 + 9 + 9 + 9 + 9 + 9 + 9 + 9 + 9 + 9 + 9 + 9 + 9 + 9 + 9 + 9 + 9 + 9\n\t/* Constant operands sum to 270 regardless of a */\n\tprint(9 + 9 + 9 + 9 + 9 + 9 + 9 + 9 + 9 + 9 + 9 + 9 + 9 + 9 + 9 + 9 + 9 + 9 + 9 + 9 + 9 + 9 + 9 + 9 + 9 + 9 + 9 + 9 + 9 + 9 + a)\n\t/* No warning */\n\ta = 9 + 9 + 9\n}$")
+        },
+        Test {
+            test_type: TestType::SemanticAnalysis,
+            test_name: String::from("String comparison warning"),
+            test_code: String::from("{\n\tstring s = \"hi\"\n\t/* Compares the address of s with the address of the literal */\n\tif (s == \"hi\") {\n\t\tprint(s)\n\t}\n\t/* No warning because there are no variables */\n\tprint(\"a\" != \"b\")\n}$")
+        },
+        Test {
+            test_type: TestType::SemanticAnalysis,
+            test_name: String::from("Lint warnings"),
+            test_code: String::from("{\n\tint a\n\t/* self-assignment */\n\ta = a\n\ta = 0 + 0 + a\n\t{\n\t\t/* shadowing */\n\t\tint a\n\t\ta = 1\n\t}\n\t/* constant-condition and empty-block */\n\tif (1 == 1) {\n\t}\n\twhile false {\n\t\tprint(a)\n\t}\n}$")
+        },
+        Test {
+            test_type: TestType::CodeGen,
+            test_name: String::from("No-op assignments"),
+            test_code: String::from("{\n\t/* Should print 5 with no code for the middle assignments */\n\tint a\n\ta = 5\n\ta = a\n\ta = 0 + a\n\tprint(a)\n}$")
+        },
         Test {
             test_type: TestType::CodeGen,
             test_name: String::from("Alan's tests"),
@@ -243,6 +366,26 @@ fn get_tests() -> Vec<Test> {
             test_type: TestType::CodeGen,
             test_name: String::from("Nested if"),
             test_code: String::from("{\n\t/* Should print success */\n\tint x\n\tx = 2\n\tif (x != 3) {\n\t\tint y\n\t\ty = 1\n\t\tif (y == 1) {\n\t\t\tprint(\"success\")\n\t\t}\n\t}\n}$")
+        },
+        Test {
+            test_type: TestType::CodeGen,
+            test_name: String::from("Declaration initializers"),
+            test_code: String::from("{\n\t/* Should print 7hitrue */\n\tint a = 3 + 4\n\tstring s = \"hi\"\n\tboolean b = (a == 7)\n\tprint(a)\n\tprint(s)\n\tprint(b)\n}$")
+        },
+        Test {
+            test_type: TestType::CodeGen,
+            test_name: String::from("Named constants"),
+            test_code: String::from("define LIMIT 3\ndefine DONE \"done\"\n{\n\t/* Should print 012done */\n\tint i = 0\n\twhile (i != LIMIT) {\n\t\tprint(i)\n\t\ti = 1 + i\n\t}\n\tprint(DONE)\n}$")
+        },
+        Test {
+            test_type: TestType::CodeGen,
+            test_name: String::from("Loop-invariant condition"),
+            test_code: String::from("{\n\t/* Should print 01234 */\n\t/* 1 + 3 + n never changes in the loop, so it is computed once */\n\tint n = 0\n\tint i = 0\n\twhile (i != 1 + 3 + n) {\n\t\tprint(i)\n\t\ti = 1 + i\n\t}\n\tprint(i)\n}$")
+        },
+        Test {
+            test_type: TestType::CodeGen,
+            test_name: String::from("Sibling scopes"),
+            test_code: String::from("{\n\t/* Should print 12 */\n\t{\n\t\tint a = 1\n\t\tprint(a)\n\t}\n\t{\n\t\tint a = 2\n\t\tprint(a)\n\t}\n}$")
         }
     ];
 
@@ -0,0 +1,417 @@
+use std::collections::BTreeMap;
+
+use serde::{Serialize, Deserialize};
+use wasm_bindgen::{prelude::*, JsCast};
+use web_sys::{Window, Document, Element};
+
+use crate::editor::{buttons, command_palette, educator, output, settings::{self, EditorSettings}};
+use crate::nexus::{compiler, artifacts::{self, ProgramArtifacts}, statistics::ProgramStatistics, lint::Linter};
+use crate::util::{nexus_log, course_stage::CourseStage, language_profile::{LanguageProfile, LanguagePreset}, target::{Target, TargetConfig}};
+
+// Have to import the editor js module
+#[wasm_bindgen(module = "/editor.js")]
+extern "C" {
+    #[wasm_bindgen(js_name = "loadProgram")]
+    fn load_program(new_code: &str);
+
+    #[wasm_bindgen(js_name = "bindFileImport")]
+    fn bind_file_import(input_id: &str, callback: &js_sys::Function);
+}
+
+// The version of the workspace file, which has to go up whenever a field changes meaning or is removed
+pub const WORKSPACE_VERSION: u32 = 1;
+
+// The key the compiler options are saved under in local storage
+const OPTIONS_KEY: &str = "nexus-compiler-options";
+
+// The version of the saved compiler options, which works the same way as the workspace version
+pub const SAVED_OPTIONS_VERSION: u32 = 1;
+
+// The inputs above the editor that are saved whenever they change, along with the lint checkboxes
+const OPTION_INPUT_IDS: [&str; 16] = [
+    "target-6502", "target-riscv", "optimize-size", "scope-comments", "reserved-cells", "heap-split", "mmio-int-out",
+    "mmio-string-out", "syscall-convention", "syscall-write", "syscall-exit", "max-nesting-depth", "max-string-length",
+    "course-stage", "language-profile", "identifier-charset"
+];
+
+// Everything needed to bring back a session, which is saved as a single JSON file
+#[derive (Debug, Serialize, Deserialize)]
+#[serde (rename_all = "camelCase")]
+pub struct WorkspaceJson {
+    pub version: u32,
+    pub source: String,
+    #[serde (default)]
+    pub editor_settings: EditorSettings,
+    #[serde (default)]
+    pub compiler_options: CompilerOptionsJson,
+
+    // The artifacts are included for anyone reading the file, but importing compiles the source again to make them
+    #[serde (default)]
+    pub artifacts: Vec<ArtifactsJson>
+}
+
+// The options above the editor that change how the source is compiled
+#[derive (Debug, Clone, Serialize, Deserialize)]
+#[serde (rename_all = "camelCase", default)]
+pub struct CompilerOptionsJson {
+    pub target: String,
+    pub optimize_size: bool,
+    pub scope_comments: bool,
+    pub reserved_cells: String,
+    pub heap_split: String,
+    pub mmio_int_out: String,
+    pub mmio_string_out: String,
+    pub syscall_convention: String,
+    pub syscall_write: String,
+    pub syscall_exit: String,
+    pub max_nesting_depth: String,
+    pub max_string_length: String,
+    pub course_stage: String,
+    pub language_profile: String,
+    pub identifier_charset: String,
+    pub lints: BTreeMap<String, bool>
+}
+
+impl Default for CompilerOptionsJson {
+    fn default() -> Self {
+        // Matches the inputs when the page is first opened
+        return CompilerOptionsJson {
+            target: String::from("6502"),
+            optimize_size: false,
+            scope_comments: false,
+            reserved_cells: String::from("1"),
+            heap_split: String::new(),
+            mmio_int_out: String::new(),
+            mmio_string_out: String::new(),
+            syscall_convention: String::from("linux"),
+            syscall_write: String::from("64"),
+            syscall_exit: String::from("93"),
+            max_nesting_depth: String::from("8"),
+            max_string_length: String::new(),
+            course_stage: CourseStage::default().to_string(),
+            language_profile: LanguagePreset::default().to_string(),
+            identifier_charset: String::new(),
+            lints: BTreeMap::new()
+        };
+    }
+}
+
+impl CompilerOptionsJson {
+    // Function to read the options from the inputs above the editor
+    pub fn from_page() -> Self {
+        let lints: BTreeMap<String, bool> = Linter::new().get_lint_names().iter()
+            .map(|lint_name| (String::from(*lint_name), buttons::is_lint_enabled(lint_name)))
+            .collect();
+        let (reserved_cells, heap_split): (String, String) = buttons::get_memory_layout_input();
+        let (mmio_int_out, mmio_string_out): (String, String) = buttons::get_mmio_input();
+        let (syscall_convention, syscall_write, syscall_exit): (String, String, String) = buttons::get_syscall_input();
+
+        return CompilerOptionsJson {
+            target: buttons::get_current_target().to_string(),
+            optimize_size: buttons::is_optimize_size_checked(),
+            scope_comments: buttons::is_scope_comments_checked(),
+            reserved_cells: reserved_cells,
+            heap_split: heap_split,
+            mmio_int_out: mmio_int_out,
+            mmio_string_out: mmio_string_out,
+            syscall_convention: syscall_convention,
+            syscall_write: syscall_write,
+            syscall_exit: syscall_exit,
+            max_nesting_depth: buttons::get_max_nesting_depth_input(),
+            max_string_length: buttons::get_max_string_length_input(),
+            course_stage: buttons::get_course_stage_input(),
+            language_profile: buttons::get_language_profile_input(),
+            identifier_charset: buttons::get_identifier_charset_input(),
+            lints: lints
+        };
+    }
+
+    // Function to put the options into the inputs above the editor
+    pub fn show(&self) {
+        buttons::set_current_target(if self.target.eq("RISC-V") { Target::TargetRiscV } else { Target::Target6502 });
+        buttons::set_optimize_size_checked(self.optimize_size);
+        buttons::set_scope_comments_checked(self.scope_comments);
+        buttons::set_memory_layout_input(&self.reserved_cells, &self.heap_split);
+        buttons::set_mmio_input(&self.mmio_int_out, &self.mmio_string_out);
+        buttons::set_syscall_input(&self.syscall_convention, &self.syscall_write, &self.syscall_exit);
+        buttons::set_max_nesting_depth_input(&self.max_nesting_depth);
+        buttons::set_max_string_length_input(&self.max_string_length);
+        buttons::set_course_stage_input(&self.course_stage);
+        educator::show_course_stage();
+        buttons::set_language_profile_input(&self.language_profile);
+        buttons::set_identifier_charset_input(&self.identifier_charset);
+        // Lints that are not in the options were added after they were made, so they are left on
+        for lint_name in Linter::new().get_lint_names() {
+            buttons::set_lint_enabled(lint_name, *self.lints.get(lint_name).unwrap_or(&true));
+        }
+    }
+
+    // Function to make sure every option can be put back into the page
+    pub fn validate(&self) -> Result<(), String> {
+        let mut target_config: TargetConfig = match self.target.as_str() {
+            "6502" => TargetConfig::new(Target::Target6502),
+            "RISC-V" => TargetConfig::new(Target::TargetRiscV),
+            _ => return Err(format!("Invalid target [ {} ], which must be 6502 or RISC-V", self.target))
+        };
+        target_config.set_memory_layout(&self.reserved_cells, &self.heap_split)?;
+        target_config.set_mmio(&self.mmio_int_out, &self.mmio_string_out)?;
+        target_config.set_syscall_convention(&self.syscall_convention, &self.syscall_write, &self.syscall_exit)?;
+        target_config.set_max_nesting_depth(&self.max_nesting_depth)?;
+        target_config.set_max_string_length(&self.max_string_length)?;
+        CourseStage::from_name(&self.course_stage)?;
+        LanguageProfile::from_inputs(&self.language_profile, &self.identifier_charset)?;
+
+        let lint_names: Vec<&'static str> = Linter::new().get_lint_names();
+        for lint_name in self.lints.keys() {
+            if !lint_names.contains(&lint_name.as_str()) {
+                return Err(format!("Invalid lint [ {} ], which must be one of {}", lint_name, lint_names.join(", ")));
+            }
+        }
+        return Ok(());
+    }
+
+    // Reads and checks the saved options, which returns why they cannot be used if they are not valid
+    pub fn from_saved_json(options_json: &str) -> Result<Self, String> {
+        let options_value: serde_json::Value = serde_json::from_str(options_json).map_err(|err| format!("Invalid saved options JSON: {}", err))?;
+        match options_value.get("version").and_then(|version| version.as_u64()) {
+            Some(version) if version == SAVED_OPTIONS_VERSION as u64 => {},
+            Some(version) => return Err(format!("Saved options version {} is not supported, which must be {}", version, SAVED_OPTIONS_VERSION)),
+            None => return Err(String::from("Invalid saved options, which must have a version number"))
+        }
+
+        let saved_options: SavedOptionsJson = serde_json::from_value(options_value).map_err(|err| format!("Invalid saved options: {}", err))?;
+        saved_options.compiler_options.validate()?;
+        return Ok(saved_options.compiler_options);
+    }
+
+    pub fn to_saved_json(&self) -> String {
+        let saved_options: SavedOptionsJson = SavedOptionsJson {
+            version: SAVED_OPTIONS_VERSION,
+            compiler_options: self.to_owned()
+        };
+        return serde_json::to_string(&saved_options).expect("Should be able to serialize the saved options");
+    }
+
+    // Loads the saved options, or the default ones if nothing has been saved yet
+    pub fn load() -> Self {
+        let options_json: Option<String> = settings::get_local_storage().and_then(|storage| storage.get_item(OPTIONS_KEY).ok().flatten());
+        if options_json.is_none() {
+            return CompilerOptionsJson::default();
+        }
+
+        return match CompilerOptionsJson::from_saved_json(&options_json.unwrap()) {
+            Ok(compiler_options) => compiler_options,
+            Err(message) => {
+                nexus_log::log(
+                    nexus_log::LogTypes::Warning,
+                    nexus_log::LogSource::NEXUS,
+                    format!("Using the default compiler options because the saved ones could not be used: {}", message)
+                );
+                CompilerOptionsJson::default()
+            }
+        };
+    }
+
+    pub fn save(&self) {
+        // The options still work for the visit when local storage is not available, such as in private browsing
+        if let Some(storage) = settings::get_local_storage() {
+            let _ = storage.set_item(OPTIONS_KEY, &self.to_saved_json());
+        }
+    }
+}
+
+// The compiler options as they are saved in local storage
+#[derive (Debug, Serialize, Deserialize)]
+#[serde (rename_all = "camelCase")]
+pub struct SavedOptionsJson {
+    pub version: u32,
+    #[serde (default)]
+    pub compiler_options: CompilerOptionsJson
+}
+
+// The artifacts of a program, where the JSON ones are written as JSON instead of strings
+#[derive (Debug, Serialize, Deserialize)]
+#[serde (rename_all = "camelCase")]
+pub struct ArtifactsJson {
+    pub program_number: u32,
+    pub cst: Option<serde_json::Value>,
+    pub cst_sexpr: Option<String>,
+    pub ast: Option<serde_json::Value>,
+    pub ast_sexpr: Option<String>,
+    pub coverage: Option<serde_json::Value>,
+    pub source_map: Option<serde_json::Value>,
+    #[serde (default)]
+    pub statistics: ProgramStatistics
+}
+
+impl ArtifactsJson {
+    fn new(program_number: u32, program_artifacts: ProgramArtifacts) -> Self {
+        let parse_json = |json: Option<String>| json.map(|json| serde_json::from_str(&json).expect("Should be able to parse the artifact JSON"));
+        return ArtifactsJson {
+            program_number: program_number,
+            cst: parse_json(program_artifacts.cst_json),
+            cst_sexpr: program_artifacts.cst_sexpr,
+            ast: parse_json(program_artifacts.ast_json),
+            ast_sexpr: program_artifacts.ast_sexpr,
+            coverage: parse_json(program_artifacts.coverage_json),
+            source_map: parse_json(program_artifacts.source_map_json),
+            statistics: program_artifacts.statistics
+        };
+    }
+}
+
+impl WorkspaceJson {
+    // Reads and checks a workspace file, which returns why it cannot be used if it is not valid
+    pub fn from_json(workspace_json: &str) -> Result<Self, String> {
+        // The version is checked first so a file from a newer version gets a clear error instead of a missing field
+        let workspace_value: serde_json::Value = serde_json::from_str(workspace_json).map_err(|err| format!("Invalid workspace JSON: {}", err))?;
+        match workspace_value.get("version").and_then(|version| version.as_u64()) {
+            Some(version) if version == WORKSPACE_VERSION as u64 => {},
+            Some(version) => return Err(format!("Workspace version {} is not supported, which must be {}", version, WORKSPACE_VERSION)),
+            None => return Err(String::from("Invalid workspace, which must have a version number"))
+        }
+
+        let workspace: WorkspaceJson = serde_json::from_value(workspace_value).map_err(|err| format!("Invalid workspace: {}", err))?;
+        workspace.validate()?;
+        return Ok(workspace);
+    }
+
+    pub fn to_json(&self) -> String {
+        return serde_json::to_string_pretty(self).expect("Should be able to serialize the workspace");
+    }
+
+    // Function to make sure every option can be put back into the page
+    fn validate(&self) -> Result<(), String> {
+        self.compiler_options.validate()?;
+
+        let font_size: u32 = self.editor_settings.font_size;
+        if font_size < settings::MIN_FONT_SIZE || font_size > settings::MAX_FONT_SIZE {
+            return Err(format!("Invalid font size [ {} ], which must be between {} and {}", font_size, settings::MIN_FONT_SIZE, settings::MAX_FONT_SIZE));
+        }
+
+        for (i, program_artifacts) in self.artifacts.iter().enumerate() {
+            if program_artifacts.program_number != i as u32 + 1 {
+                return Err(format!("Invalid artifacts for program {}, which should be program {}", program_artifacts.program_number, i + 1));
+            }
+        }
+
+        return Ok(());
+    }
+}
+
+// Function to get the current session as a workspace
+#[wasm_bindgen(js_name = "exportWorkspace")]
+pub fn export_workspace() -> String {
+    let workspace: WorkspaceJson = WorkspaceJson {
+        version: WORKSPACE_VERSION,
+        source: buttons::get_code_input(),
+        editor_settings: EditorSettings::load(),
+        compiler_options: CompilerOptionsJson::from_page(),
+        artifacts: (1..=artifacts::get_program_count())
+            .map(|program_number| ArtifactsJson::new(program_number, artifacts::get_program_artifacts(program_number).unwrap()))
+            .collect()
+    };
+    return workspace.to_json();
+}
+
+// Function to bring back a session from a workspace, which compiles the source again
+// Nothing is changed if the workspace is not valid
+#[wasm_bindgen(js_name = "importWorkspace")]
+pub fn import_workspace(workspace_json: &str) -> Result<(), JsValue> {
+    let workspace: WorkspaceJson = WorkspaceJson::from_json(workspace_json).map_err(|err| JsValue::from_str(&err))?;
+
+    let window: Window = web_sys::window().expect("Should be able to get the window");
+    let document: Document = window.document().expect("Should be able to get the document");
+
+    workspace.editor_settings.apply();
+    workspace.editor_settings.save();
+    settings::show_settings(&document, &workspace.editor_settings);
+
+    workspace.compiler_options.show();
+    workspace.compiler_options.save();
+
+    load_program(&workspace.source);
+    compiler::compile(&workspace.source);
+    return Ok(());
+}
+
+// Function to set up the export and import workspace buttons
+pub fn create_workspace_buttons(document: &Document) {
+    let export_btn: Element = document
+        .get_element_by_id("export-workspace-btn")
+        .expect("There should be an element called export-workspace-btn");
+
+    let export_fn: Closure<dyn FnMut()> = Closure::wrap(Box::new(|| {
+        output::download_text("nexus-workspace.json", &export_workspace(), "application/json");
+    }) as Box<dyn FnMut()>);
+    export_btn.add_event_listener_with_callback("click", export_fn.as_ref().unchecked_ref()).expect("Should be able to add the event listener");
+    export_fn.forget();
+
+    // The file input is read in JS, which then gives the text of the file to this function
+    let import_fn: Closure<dyn FnMut(String)> = Closure::wrap(Box::new(|workspace_json: String| {
+        if let Err(err) = import_workspace(&workspace_json) {
+            nexus_log::log(
+                nexus_log::LogTypes::Error,
+                nexus_log::LogSource::NEXUS,
+                format!("Could not import the workspace: {}", err.as_string().unwrap_or_default())
+            );
+        }
+    }) as Box<dyn FnMut(String)>);
+    bind_file_import("import-workspace-input", import_fn.as_ref().unchecked_ref());
+    import_fn.forget();
+
+    command_palette::register_click_action("Export workspace", "export-workspace-btn");
+    command_palette::register_click_action("Import workspace", "import-workspace-input");
+}
+
+// Function to bring back the saved compiler options and save them again whenever they change
+// Reset Options puts both the compiler options and the editor settings back to their defaults
+pub fn create_saved_options(document: &Document) {
+    CompilerOptionsJson::load().show();
+
+    // Options that are not valid are not saved, so the last good options are used next time
+    let save_fn: Closure<dyn FnMut()> = Closure::wrap(Box::new(|| {
+        let compiler_options: CompilerOptionsJson = CompilerOptionsJson::from_page();
+        if compiler_options.validate().is_ok() {
+            compiler_options.save();
+        }
+    }) as Box<dyn FnMut()>);
+
+    let lint_ids: Vec<String> = Linter::new().get_lint_names().iter().map(|lint_name| format!("lint-{}", lint_name)).collect();
+    let input_ids: Vec<&str> = OPTION_INPUT_IDS.iter().copied().chain(lint_ids.iter().map(|lint_id| lint_id.as_str())).collect();
+    for input_id in input_ids {
+        // Lints without a checkbox are always on
+        if let Some(option_input) = document.get_element_by_id(input_id) {
+            option_input.add_event_listener_with_callback("change", save_fn.as_ref().unchecked_ref()).expect("Should be able to add the event listener");
+        }
+    }
+    save_fn.forget();
+
+    let reset_btn: Element = document
+        .get_element_by_id("reset-options-btn")
+        .expect("There should be an element called reset-options-btn");
+
+    let reset_fn: Closure<dyn FnMut()> = Closure::wrap(Box::new(|| {
+        let window: Window = web_sys::window().expect("Should be able to get the window");
+        let document: Document = window.document().expect("Should be able to get the document");
+
+        let compiler_options: CompilerOptionsJson = CompilerOptionsJson::default();
+        compiler_options.show();
+        compiler_options.save();
+
+        let editor_settings: EditorSettings = EditorSettings::default();
+        editor_settings.apply();
+        editor_settings.save();
+        settings::show_settings(&document, &editor_settings);
+
+        nexus_log::log(
+            nexus_log::LogTypes::Info,
+            nexus_log::LogSource::NEXUS,
+            String::from("Reset the compiler options and editor settings to their defaults")
+        );
+    }) as Box<dyn FnMut()>);
+    reset_btn.add_event_listener_with_callback("click", reset_fn.as_ref().unchecked_ref()).expect("Should be able to add the event listener");
+    reset_fn.forget();
+
+    command_palette::register_click_action("Reset options", "reset-options-btn");
+}
@@ -1,14 +1,31 @@
+#[cfg(feature = "ui")]
 use wasm_bindgen::prelude::*;
+#[cfg(feature = "ui")]
 use log::*;
+#[cfg(feature = "ui")]
 use web_sys::{Window, Document};
 
-mod nexus;
-mod util;
+pub mod nexus;
+pub mod util;
+#[cfg(feature = "ui")]
 mod editor;
 
+// The headless API for compiling from Rust without the browser, which is shown in the examples
+pub use nexus::batch::{BatchSource, BatchResult, BatchProgramResult, compile_source, lex_source};
+pub use nexus::fingerprint::{Fingerprint, SimilarityReport, SimilarityPair, fingerprint_source, compare_sources, compare_submissions};
+pub use nexus::grading::{Rubric, RubricCheck, ExpectedDiagnostic, GradeReport, CheckResult, grade_source, grade_submissions};
+pub use nexus::token::{Token, TokenType, Keywords, Symbols};
+pub use util::{nexus_log::{LogRecord, LogTypes, LogSource}, messages::MessageCode, position::{Position, Span}, target::{Target, TargetConfig}};
+
+// The differential checker and the minimizer also run natively with their binaries
+pub use nexus::differential;
+pub use util::program_minimizer;
+
+#[cfg(feature = "ui")]
 use editor::*;
 
 // Function to initialize Nexus
+#[cfg(feature = "ui")]
 #[wasm_bindgen]
 pub fn nexus_init() {
     // Set up console logs for debugging
@@ -19,9 +36,30 @@ pub fn nexus_init() {
     let window: Window = web_sys::window().expect("The window object should exist");
     let document: Document = window.document().expect("The document object should exist");
 
+    // Sources outside of the compiler stages need to be registered before their toggles are made
+    util::nexus_log::register_source(nexus::optimizer::LOG_SOURCE);
+    util::nexus_log::register_source(nexus::lint::LOG_SOURCE);
+    util::nexus_log::register_source(nexus::interpreter::LOG_SOURCE);
+
     // Set up the event listeners
+    command_palette::create_command_palette(&document);
     buttons::set_up_buttons(&document);
     tests::create_test_environment(&document);
+    test_runner::create_test_runner(&document);
+    nexus::ast_query::create_query_pane(&document);
+    repl::create_repl(&document);
+    evaluator::create_evaluator(&document);
+    settings::create_settings_panel(&document);
+    comment::create_comment_command(&document);
+    workspace::create_workspace_buttons(&document);
+    workspace::create_saved_options(&document);
+    educator::create_educator_mode(&document);
+    issues::create_issues_pane(&document);
+    history::create_history_pane(&document);
+    share::create_share_button(&document);
+
+    // The options are set up first so the source in the link is compiled with them
+    loader::load_linked_source();
 
     info!("Nexus initialized");
 }
@@ -0,0 +1,187 @@
+use std::cell::{RefCell, RefMut};
+
+use wasm_bindgen::prelude::*;
+
+use crate::nexus::statistics::ProgramStatistics;
+use crate::util::{diagnostic::Issue, target::Target};
+
+// The output of code generation for a program
+#[derive (Debug, Clone, PartialEq)]
+pub enum GeneratedCode {
+    // The 256 bytes of the 6502 executable image
+    Image(Vec<u8>),
+    // The RISC-V assembly text
+    Assembly(String)
+}
+
+impl GeneratedCode {
+    // Function to get the target the code was generated for
+    pub fn get_target(&self) -> Target {
+        return match self {
+            GeneratedCode::Image(_) => Target::Target6502,
+            GeneratedCode::Assembly(_) => Target::TargetRiscV
+        };
+    }
+
+    // Function to get the code as text, where the image is written as hex bytes like it is shown on the page
+    pub fn to_text(&self) -> String {
+        return match self {
+            GeneratedCode::Image(image) => image.iter().map(|code_byte| format!("{:02X}", code_byte)).collect::<Vec<String>>().join(" "),
+            GeneratedCode::Assembly(assembly) => assembly.to_owned()
+        };
+    }
+
+    // Function to get the name of the file the code is downloaded as
+    pub fn get_file_name(&self, program_number: u32) -> String {
+        return match self {
+            GeneratedCode::Image(_) => format!("program{}.hex", program_number),
+            GeneratedCode::Assembly(_) => format!("program{}.s", program_number)
+        };
+    }
+}
+
+// The artifacts produced for a single program in the most recent compilation
+#[derive (Debug, Default, Clone)]
+pub struct ProgramArtifacts {
+    // JSON export of the CST if parse was successful
+    pub cst_json: Option<String>,
+
+    // S-expression dump of the CST if parse was successful
+    pub cst_sexpr: Option<String>,
+
+    // JSON export of the AST with type annotations from semantic analysis
+    pub ast_json: Option<String>,
+
+    // S-expression dump of the AST with the derived types
+    pub ast_sexpr: Option<String>,
+
+    // JSON export of the code produced by each AST node if code gen was successful
+    pub coverage_json: Option<String>,
+
+    // JSON export of the source position of the generated code if code gen was successful
+    pub source_map_json: Option<String>,
+
+    // The image or assembly if code gen was successful
+    pub generated_code: Option<GeneratedCode>,
+
+    // Counts from every phase that the program got through
+    pub statistics: ProgramStatistics,
+
+    // The errors and warnings with a position in the source code, in the order they were found
+    pub issues: Vec<Issue>
+}
+
+// The compiler runs on the single browser thread, so the artifacts live in thread local storage
+thread_local! {
+    static ARTIFACTS: RefCell<Vec<ProgramArtifacts>> = RefCell::new(Vec::new());
+}
+
+// Function to remove the artifacts of the previous compilation
+pub fn clear_artifacts() {
+    ARTIFACTS.with(|artifacts| artifacts.borrow_mut().clear());
+}
+
+// Function to start collecting the artifacts for the next program
+pub fn new_program() {
+    ARTIFACTS.with(|artifacts| {
+        let mut artifacts_vec: RefMut<Vec<ProgramArtifacts>> = artifacts.borrow_mut();
+        let program_number: u32 = artifacts_vec.len() as u32 + 1;
+        artifacts_vec.push(ProgramArtifacts {
+            statistics: ProgramStatistics::new(program_number),
+            ..Default::default()
+        });
+    });
+}
+
+// Function to update the artifacts of the program currently being compiled
+pub fn update_current_program<F: FnOnce(&mut ProgramArtifacts)>(update_fn: F) {
+    ARTIFACTS.with(|artifacts| {
+        let mut artifacts_vec: RefMut<Vec<ProgramArtifacts>> = artifacts.borrow_mut();
+        if artifacts_vec.last().is_some() {
+            update_fn(artifacts_vec.last_mut().unwrap());
+        }
+    });
+}
+
+// Function to get a copy of the artifacts for a program, which are numbered starting at 1
+pub fn get_program_artifacts(program_number: u32) -> Option<ProgramArtifacts> {
+    return ARTIFACTS.with(|artifacts| {
+        if program_number == 0 {
+            return None;
+        }
+        return artifacts.borrow().get(program_number as usize - 1).cloned();
+    });
+}
+
+// Function to get the number of programs in the most recent compilation
+#[wasm_bindgen(js_name = "getProgramCount")]
+pub fn get_program_count() -> u32 {
+    return ARTIFACTS.with(|artifacts| artifacts.borrow().len() as u32);
+}
+
+// Function to get the CST of a program as JSON
+#[wasm_bindgen(js_name = "getCstJson")]
+pub fn get_cst_json(program_number: u32) -> Option<String> {
+    return get_program_artifacts(program_number).and_then(|program_artifacts| program_artifacts.cst_json);
+}
+
+// Function to get the CST of a program as an s-expression
+#[wasm_bindgen(js_name = "getCstSexpr")]
+pub fn get_cst_sexpr(program_number: u32) -> Option<String> {
+    return get_program_artifacts(program_number).and_then(|program_artifacts| program_artifacts.cst_sexpr);
+}
+
+// Function to get the code coverage of a program's AST as JSON
+#[wasm_bindgen(js_name = "getCoverageJson")]
+pub fn get_coverage_json(program_number: u32) -> Option<String> {
+    return get_program_artifacts(program_number).and_then(|program_artifacts| program_artifacts.coverage_json);
+}
+
+// Function to get the source map of a program's generated code as JSON
+#[wasm_bindgen(js_name = "getSourceMapJson")]
+pub fn get_source_map_json(program_number: u32) -> Option<String> {
+    return get_program_artifacts(program_number).and_then(|program_artifacts| program_artifacts.source_map_json);
+}
+
+// Function to get the AST of a program as JSON
+#[wasm_bindgen(js_name = "getAstJson")]
+pub fn get_ast_json(program_number: u32) -> Option<String> {
+    return get_program_artifacts(program_number).and_then(|program_artifacts| program_artifacts.ast_json);
+}
+
+// Function to get the AST of a program as an s-expression with the derived types
+#[wasm_bindgen(js_name = "getAstSexpr")]
+pub fn get_ast_sexpr(program_number: u32) -> Option<String> {
+    return get_program_artifacts(program_number).and_then(|program_artifacts| program_artifacts.ast_sexpr);
+}
+
+// Function to get the statistics of a program as JSON
+#[wasm_bindgen(js_name = "getStatisticsJson")]
+pub fn get_statistics_json(program_number: u32) -> Option<String> {
+    return get_program_artifacts(program_number).map(|program_artifacts| program_artifacts.statistics.to_json());
+}
+
+// Function to get the code generated for a program if it was compiled for the given target
+pub fn get_generated(program_number: u32, target: Target) -> Option<GeneratedCode> {
+    return get_program_artifacts(program_number)
+        .and_then(|program_artifacts| program_artifacts.generated_code)
+        .filter(|generated_code| generated_code.get_target() == target);
+}
+
+// Function to get the 6502 executable image of a program as bytes
+#[wasm_bindgen(js_name = "getGeneratedImage")]
+pub fn get_generated_image(program_number: u32) -> Option<Vec<u8>> {
+    return match get_generated(program_number, Target::Target6502) {
+        Some(GeneratedCode::Image(image)) => Some(image),
+        _ => None
+    };
+}
+
+// Function to get the RISC-V assembly of a program as text
+#[wasm_bindgen(js_name = "getGeneratedAssembly")]
+pub fn get_generated_assembly(program_number: u32) -> Option<String> {
+    return match get_generated(program_number, Target::TargetRiscV) {
+        Some(GeneratedCode::Assembly(assembly)) => Some(assembly),
+        _ => None
+    };
+}
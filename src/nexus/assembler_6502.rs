@@ -0,0 +1,66 @@
+use crate::util::instruction_set::{self, Instruction6502};
+
+// Function to turn 6502 assembly in the format of the disassembler into the bytes of an image
+// Only the instructions in INSTRUCTIONS_6502 and the DB directive for a single byte of data are supported
+pub fn assemble(lines: &[String]) -> Result<Vec<u8>, String> {
+    let mut image: Vec<u8> = Vec::new();
+
+    for (line_index, line) in lines.iter().enumerate() {
+        let (mnemonic, operand): (&str, &str) = match line.trim().split_once(' ') {
+            Some((mnemonic, operand)) => (mnemonic, operand.trim()),
+            None => (line.trim(), "")
+        };
+
+        if mnemonic.eq("DB") {
+            match parse_operand("$nn", operand) {
+                Some(data) => image.push(data as u8),
+                None => return Err(format!("Invalid data [ {} ] on line {}", operand, line_index + 1))
+            }
+            continue;
+        }
+
+        // Instructions like LDA have a different opcode for each operand format
+        let mut assembled: bool = false;
+        for instruction in instruction_set::INSTRUCTIONS_6502.iter().filter(|instruction| instruction.mnemonic.eq(mnemonic)) {
+            let operand_value: Option<u16> = parse_operand(instruction.operands, operand);
+            if operand_value.is_some() {
+                push_instruction(&mut image, instruction, operand_value.unwrap());
+                assembled = true;
+                break;
+            }
+        }
+
+        if !assembled {
+            return Err(format!("Invalid instruction [ {} ] on line {}", line.trim(), line_index + 1));
+        }
+    }
+
+    return Ok(image);
+}
+
+// Function to get the value of the operand if it has the operand format, such as #$nn or $nnnn
+fn parse_operand(operand_format: &str, operand: &str) -> Option<u16> {
+    // Instructions without an operand have a value of 0 that does not get written
+    if operand_format.is_empty() {
+        return if operand.is_empty() { Some(0) } else { None };
+    }
+
+    let prefix: &str = operand_format.trim_end_matches('n');
+    let num_digits: usize = operand_format.len() - prefix.len();
+    let digits: &str = operand.strip_prefix(prefix)?;
+    if digits.len() != num_digits || !digits.chars().all(|c| c.is_ascii_hexdigit()) {
+        return None;
+    }
+    return u16::from_str_radix(digits, 16).ok();
+}
+
+// Function to add the opcode and its operand bytes to the image
+fn push_instruction(image: &mut Vec<u8>, instruction: &Instruction6502, operand_value: u16) {
+    image.push(instruction.opcode as u8);
+    match instruction.length {
+        2 => image.push(operand_value as u8),
+        // Addresses are little endian, so the low order byte comes first
+        3 => image.extend_from_slice(&operand_value.to_le_bytes()),
+        _ => {}
+    }
+}
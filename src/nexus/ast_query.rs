@@ -0,0 +1,328 @@
+use std::collections::HashMap;
+
+use petgraph::graph::NodeIndex;
+use serde::Serialize;
+use wasm_bindgen::prelude::*;
+use web_sys::{Document, Element, HtmlInputElement, Window};
+use wasm_bindgen::{prelude::Closure, JsCast};
+
+use crate::nexus::{syntax_tree::SyntaxTree, syntax_tree_node::*, symbol_table::Type, cost_model};
+use crate::nexus::{preprocessor::Preprocessor, lexer::Lexer, token::{Token, TokenType, Keywords}, parser::Parser, semantic_analyzer::{SemanticAnalyzer, SemanticAnalysisResult}};
+use crate::util::{nexus_log, target::{Target, TargetConfig}, html::escape_html};
+use crate::editor::buttons;
+
+// The kinds of nodes that can be queried, which are the AST nonterminals and the token types of the terminals
+const NODE_KINDS: [&str; 17] = [
+    "Block", "VarDecl", "Assign", "Print", "While", "If", "Add", "IsEq", "NotEq", "Index", "Len",
+    "Keyword", "Identifier", "Symbol", "Digit", "Char", "CharLiteral"
+];
+
+// A single step of a query, which matches a node by its kind and type
+// None matches anything
+#[derive (Debug, Clone)]
+pub struct QueryStep {
+    pub kind: Option<String>,
+    pub node_type: Option<Type>
+}
+
+// A query over an analyzed AST such as "count While" or "Assign:Boolean > Add"
+// Every step after the first has to match a direct child of the node matched by the step before it,
+// and the nodes that match the first step are the results
+#[derive (Debug, Clone)]
+pub struct AstQuery {
+    pub steps: Vec<QueryStep>,
+
+    // Only the number of matches is reported
+    pub is_count: bool
+}
+
+// A node that matched a query
+#[derive (Debug, Serialize)]
+#[serde (rename_all = "camelCase")]
+pub struct QueryMatch {
+    // The index of the node in the AST, which is the same id as the JSON export
+    pub id: usize,
+
+    // Description of the node, such as "Line 3: Assign"
+    pub label: String
+}
+
+// The matches of a query in a single program
+#[derive (Debug, Serialize)]
+#[serde (rename_all = "camelCase")]
+pub struct QueryResult {
+    pub program_number: u32,
+    pub count: usize,
+
+    // Empty for count queries
+    pub matches: Vec<QueryMatch>,
+
+    // Set if the program could not be analyzed
+    #[serde (skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>
+}
+
+impl AstQuery {
+    // Function to parse the query text
+    // query := ["count"] step (">" step)*
+    // step := Kind | Kind:Type | :Type | *
+    pub fn parse(query_str: &str) -> Result<AstQuery, String> {
+        let mut query_text: &str = query_str.trim();
+        let mut is_count: bool = false;
+        if query_text.starts_with("count ") {
+            is_count = true;
+            query_text = query_text["count ".len()..].trim();
+        }
+
+        if query_text.len() == 0 {
+            return Err(String::from("The query is empty"));
+        }
+
+        let mut steps: Vec<QueryStep> = Vec::new();
+        for step_text in query_text.split('>').map(|step| step.trim()) {
+            steps.push(Self::parse_step(step_text)?);
+        }
+
+        return Ok(AstQuery {
+            steps: steps,
+            is_count: is_count
+        });
+    }
+
+    fn parse_step(step_text: &str) -> Result<QueryStep, String> {
+        let (kind_text, type_text): (&str, Option<&str>) = match step_text.split_once(':') {
+            Some((kind_text, type_text)) => (kind_text.trim(), Some(type_text.trim())),
+            None => (step_text, None)
+        };
+
+        let kind: Option<String> = match kind_text {
+            "*" | "" if type_text.is_some() || kind_text.eq("*") => None,
+            _ if NODE_KINDS.contains(&kind_text) => Some(String::from(kind_text)),
+            _ => return Err(format!("Unknown node kind [ {} ], which must be * or one of {}", kind_text, NODE_KINDS.join(", ")))
+        };
+
+        let node_type: Option<Type> = match type_text {
+            None => None,
+            Some("Int") => Some(Type::Int),
+            Some("String") => Some(Type::String),
+            Some("Boolean") => Some(Type::Boolean),
+            Some("Char") => Some(Type::Char),
+            Some(other) => return Err(format!("Unknown type [ {} ], which must be Int, String, Boolean, or Char", other))
+        };
+
+        return Ok(QueryStep {
+            kind: kind,
+            node_type: node_type
+        });
+    }
+
+    // Function to get every node that matches the query in source order
+    pub fn find_matches(&self, ast: &SyntaxTree, node_types: &HashMap<NodeIndex, Type>) -> Vec<NodeIndex> {
+        let mut matches: Vec<NodeIndex> = Vec::new();
+        if ast.root.is_some() {
+            self.find_matches_dfs(ast, node_types, NodeIndex::new(ast.root.unwrap()), &mut matches);
+        }
+        return matches;
+    }
+
+    fn find_matches_dfs(&self, ast: &SyntaxTree, node_types: &HashMap<NodeIndex, Type>, cur_index: NodeIndex, matches: &mut Vec<NodeIndex>) {
+        if self.matches_from(ast, node_types, cur_index, 0) {
+            matches.push(cur_index);
+        }
+
+        // Children are stored in reverse order
+        let children: Vec<NodeIndex> = (*ast).graph.neighbors(cur_index).collect();
+        for child_index in children.into_iter().rev() {
+            self.find_matches_dfs(ast, node_types, child_index, matches);
+        }
+    }
+
+    // Function to check if the node matches the step and a child matches the rest of the steps
+    fn matches_from(&self, ast: &SyntaxTree, node_types: &HashMap<NodeIndex, Type>, cur_index: NodeIndex, step_index: usize) -> bool {
+        let step: &QueryStep = &self.steps[step_index];
+
+        if step.kind.is_some() && !step.kind.as_ref().unwrap().eq(&get_node_kind(ast, cur_index)) {
+            return false;
+        }
+        if step.node_type.is_some() && get_node_type(ast, node_types, cur_index).ne(&step.node_type) {
+            return false;
+        }
+
+        if step_index + 1 == self.steps.len() {
+            return true;
+        } else {
+            return (*ast).graph.neighbors(cur_index).any(|child_index| self.matches_from(ast, node_types, child_index, step_index + 1));
+        }
+    }
+}
+
+// Function to get the kind of the node that a query step matches against
+pub fn get_node_kind(ast: &SyntaxTree, node_index: NodeIndex) -> String {
+    return match (*ast).graph.node_weight(node_index).unwrap() {
+        SyntaxTreeNode::Terminal(token) => {
+            let kind: &str = match &token.token_type {
+                TokenType::Keyword(_) => "Keyword",
+                TokenType::Identifier(_) => "Identifier",
+                TokenType::Symbol(_) => "Symbol",
+                TokenType::Digit(_) => "Digit",
+                TokenType::Char(_) => "Char",
+                TokenType::CharLiteral(_) => "CharLiteral",
+                TokenType::Unrecognized(_) => "Unrecognized"
+            };
+            String::from(kind)
+        },
+        non_terminal => format!("{:?}", non_terminal)
+    };
+}
+
+// Function to get the type of a node, where statements take the type of the id they declare or assign
+pub fn get_node_type(ast: &SyntaxTree, node_types: &HashMap<NodeIndex, Type>, node_index: NodeIndex) -> Option<Type> {
+    if node_types.contains_key(&node_index) {
+        return node_types.get(&node_index).cloned();
+    }
+
+    let children: Vec<NodeIndex> = (*ast).graph.neighbors(node_index).collect();
+    return match (*ast).graph.node_weight(node_index).unwrap() {
+        // The value has to be the same type as the id after semantic analysis
+        SyntaxTreeNode::NonTerminalAst(NonTerminalsAst::Assign) => node_types.get(&children[0]).cloned(),
+        SyntaxTreeNode::NonTerminalAst(NonTerminalsAst::Print) => node_types.get(&children[0]).cloned(),
+        SyntaxTreeNode::NonTerminalAst(NonTerminalsAst::VarDecl) => {
+            match (*ast).graph.node_weight(children[1]).unwrap() {
+                SyntaxTreeNode::Terminal(token) => {
+                    match &token.token_type {
+                        TokenType::Keyword(Keywords::Int) => Some(Type::Int),
+                        TokenType::Keyword(Keywords::String) => Some(Type::String),
+                        TokenType::Keyword(Keywords::Boolean) => Some(Type::Boolean),
+                        TokenType::Keyword(Keywords::Char) => Some(Type::Char),
+                        _ => None
+                    }
+                },
+                _ => None
+            }
+        },
+        _ => None
+    };
+}
+
+// Function to describe a match, which includes the text of terminals since they do not have any children
+fn get_match_label(ast: &SyntaxTree, node_index: NodeIndex) -> String {
+    return match (*ast).graph.node_weight(node_index).unwrap() {
+        SyntaxTreeNode::Terminal(token) => format!("Line {}: {} {}", token.position.line, get_node_kind(ast, node_index), token.text),
+        _ => cost_model::get_statement_label(ast, node_index)
+    };
+}
+
+// Function to run the query on every program in the source code without showing any logs
+#[wasm_bindgen(js_name = "queryAst")]
+pub fn query_ast(source_code: &str, query_str: &str) -> Result<String, JsValue> {
+    let ast_query: AstQuery = AstQuery::parse(query_str).map_err(|err| JsValue::from_str(&err))?;
+    return Ok(serde_json::to_string(&run_query(source_code, &ast_query)).expect("Should be able to serialize the query results"));
+}
+
+fn run_query(source_code: &str, ast_query: &AstQuery) -> Vec<QueryResult> {
+    nexus_log::start_capture();
+
+    let mut results: Vec<QueryResult> = Vec::new();
+    let mut preprocessor: Preprocessor = Preprocessor::new();
+    let preprocess_res: Result<String, ()> = preprocessor.expand_defines(source_code);
+
+    if preprocess_res.is_ok() {
+        let mut lexer: Lexer = Lexer::new(&preprocess_res.unwrap());
+        lexer.set_column_map(preprocessor.get_column_map());
+        let mut parser: Parser = Parser::new();
+        let mut semantic_analyzer: SemanticAnalyzer = SemanticAnalyzer::new(TargetConfig::new(Target::Target6502));
+
+        let mut program_number: u32 = 0;
+        while lexer.has_program_to_lex() {
+            program_number += 1;
+            results.push(query_program(&mut lexer, &mut parser, &mut semantic_analyzer, ast_query, program_number));
+        }
+    }
+
+    nexus_log::stop_capture();
+    return results;
+}
+
+fn query_program(lexer: &mut Lexer, parser: &mut Parser, semantic_analyzer: &mut SemanticAnalyzer, ast_query: &AstQuery, program_number: u32) -> QueryResult {
+    let mut result: QueryResult = QueryResult {
+        program_number: program_number,
+        count: 0,
+        matches: Vec::new(),
+        error: None
+    };
+
+    let lex_res: Result<Vec<Token>, ()> = lexer.lex_program();
+    if lex_res.is_err() {
+        result.error = Some(String::from("Lex failed"));
+        return result;
+    }
+
+    let token_stream: Vec<Token> = lex_res.unwrap();
+    if parser.parse_program(&token_stream).is_err() {
+        result.error = Some(String::from("Parse failed"));
+        return result;
+    }
+
+    // Types are only known if analysis succeeds, but the tree can still be queried
+    let ast: SyntaxTree = semantic_analyzer.generate_ast(&token_stream);
+    let semantic_analysis_res: SemanticAnalysisResult = semantic_analyzer.analyze_program(ast);
+    if !semantic_analysis_res.is_successful() {
+        result.error = Some(String::from("Semantic analysis failed, so some types may be missing"));
+    }
+
+    let matches: Vec<NodeIndex> = ast_query.find_matches(&semantic_analysis_res.ast, &semantic_analysis_res.node_types);
+    result.count = matches.len();
+    if !ast_query.is_count {
+        result.matches = matches.into_iter()
+            .map(|match_index| QueryMatch {
+                id: match_index.index(),
+                label: get_match_label(&semantic_analysis_res.ast, match_index)
+            })
+            .collect();
+    }
+
+    return result;
+}
+
+// Function to set up the query pane under the test runner
+pub fn create_query_pane(document: &Document) {
+    let query_btn: Element = document.get_element_by_id("query-btn").expect("Should be able to get the query button");
+
+    let query_fn: Closure<dyn FnMut()> = Closure::wrap(Box::new(|| {
+        let window: Window = web_sys::window().expect("Should be able to get the window");
+        let document: Document = window.document().expect("Should be able to get the document");
+
+        let query_input: HtmlInputElement = document
+            .get_element_by_id("query-input")
+            .expect("Should be able to get the element")
+            .dyn_into::<HtmlInputElement>()
+            .expect("The element should be recognized as an input element");
+        let query_results: Element = document.get_element_by_id("query-results").expect("Should be able to get the element");
+
+        match AstQuery::parse(&query_input.value()) {
+            Ok(ast_query) => {
+                let result_lines: Vec<String> = run_query(&buttons::get_code_input(), &ast_query).iter()
+                    .map(|result| create_result_text(result, ast_query.is_count))
+                    .collect();
+                query_results.set_inner_html(&result_lines.join("<br>"));
+            },
+            Err(err) => query_results.set_inner_html(&escape_html(&err))
+        }
+    }) as Box<dyn FnMut()>);
+
+    query_btn.add_event_listener_with_callback("click", query_fn.as_ref().unchecked_ref()).expect("Should be able to add the event listener");
+    query_fn.forget();
+}
+
+fn create_result_text(result: &QueryResult, is_count: bool) -> String {
+    let mut result_text: String = format!("Program {}: {} match(es)", result.program_number, result.count);
+    if result.error.is_some() {
+        result_text.push_str(format!(" ({})", escape_html(result.error.as_ref().unwrap())).as_str());
+    }
+    if !is_count {
+        for query_match in result.matches.iter() {
+            result_text.push_str(format!("<br>&nbsp;&nbsp;{} (node {})", escape_html(&query_match.label), query_match.id).as_str());
+        }
+    }
+    return result_text;
+}
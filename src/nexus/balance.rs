@@ -0,0 +1,66 @@
+use crate::nexus::token::{Token, TokenType, Symbols};
+use crate::util::{messages::{Message, MessageCode}, position::Position};
+
+// Function to get the closing symbol of an opening one, which is None for every other token
+fn get_closing_symbol(token_type: &TokenType) -> Option<&'static str> {
+    return match token_type {
+        TokenType::Symbol(Symbols::LBrace) => Some("}"),
+        TokenType::Symbol(Symbols::LParen) => Some(")"),
+        TokenType::Symbol(Symbols::LBracket) => Some("]"),
+        _ => None
+    };
+}
+
+// Function to get the opening symbol of a closing one, which is None for every other token
+fn get_opening_symbol(token_type: &TokenType) -> Option<&'static str> {
+    return match token_type {
+        TokenType::Symbol(Symbols::RBrace) => Some("{"),
+        TokenType::Symbol(Symbols::RParen) => Some("("),
+        TokenType::Symbol(Symbols::RBracket) => Some("["),
+        _ => None
+    };
+}
+
+// Function to find the braces, parentheses, and brackets of a program that are not balanced, in order of their positions
+// This only counts the symbols, so it can point to where the problem started even when the parser's first error is much later
+// Quotes are not counted because the lexer already fails with an unclosed string, and strings cannot have any of these symbols in them
+pub fn find_unbalanced(token_stream: &Vec<Token>) -> Vec<(Position, Message)> {
+    let mut hints: Vec<(Position, Message)> = Vec::new();
+
+    // The symbols that have been opened but not closed yet, innermost last
+    let mut open_tokens: Vec<&Token> = Vec::new();
+
+    for token in token_stream.iter() {
+        if get_closing_symbol(&token.token_type).is_some() {
+            open_tokens.push(token);
+        } else if let Some(opening_symbol) = get_opening_symbol(&token.token_type) {
+            match open_tokens.iter().rposition(|open_token| open_token.text.eq(opening_symbol)) {
+                // Anything opened after the match was never closed, such as the ( in { print(a }
+                Some(match_index) => {
+                    for open_token in open_tokens.drain(match_index..).skip(1) {
+                        hints.push((open_token.position, get_unclosed_message(open_token)));
+                    }
+                },
+                None => hints.push((token.position, Message::new(MessageCode::UnopenedSymbol, vec![
+                    ("position", token.position.to_message_param()),
+                    ("close", token.text.to_owned()),
+                    ("open", String::from(opening_symbol))
+                ])))
+            }
+        }
+    }
+
+    for open_token in open_tokens.into_iter() {
+        hints.push((open_token.position, get_unclosed_message(open_token)));
+    }
+    hints.sort_by_key(|(position, _)| *position);
+    return hints;
+}
+
+fn get_unclosed_message(open_token: &Token) -> Message {
+    return Message::new(MessageCode::UnclosedSymbol, vec![
+        ("position", open_token.position.to_message_param()),
+        ("open", open_token.text.to_owned()),
+        ("close", String::from(get_closing_symbol(&open_token.token_type).unwrap_or_default()))
+    ]);
+}
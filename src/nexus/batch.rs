@@ -0,0 +1,229 @@
+use serde::{Deserialize, Serialize};
+#[cfg(feature = "ui")]
+use wasm_bindgen::prelude::*;
+
+use crate::util::{nexus_log::{self, LogRecord, LogTypes, RecordedLog}, target::TargetConfig};
+#[cfg(feature = "ui")]
+use crate::util::target::Target;
+use crate::nexus::{preprocessor::Preprocessor, lexer::Lexer, token::Token, parser::Parser, syntax_tree::SyntaxTree};
+use crate::nexus::{interpreter, pipeline::{self, CompileOptions, ParsedProgram, ProgramOutput, TargetCodeGenerator}};
+
+// A named source file to compile in a batch, such as one student's submission
+#[derive (Debug, Deserialize)]
+pub struct BatchSource {
+    pub name: String,
+    pub source: String
+}
+
+impl BatchSource {
+    pub fn new(name: &str, source: &str) -> Self {
+        return BatchSource {
+            name: String::from(name),
+            source: String::from(source)
+        };
+    }
+}
+
+// The result of a single program within a source file
+#[derive (Debug, Serialize)]
+#[serde (rename_all = "camelCase")]
+pub struct BatchProgramResult {
+    pub program_number: u32,
+
+    // The stage that failed, or None if code was generated
+    pub failed_stage: Option<String>,
+
+    // The hex image (6502) or assembly (RISC-V) if code generation was successful
+    pub code: Option<String>,
+
+    // What the interpreter printed if the source was interpreted instead of generating code
+    #[serde (skip_serializing_if = "Option::is_none")]
+    pub output: Option<String>,
+
+    // The errors and warnings of this program in the order they were found
+    pub diagnostics: Vec<LogRecord>
+}
+
+// The result of compiling one named source file
+#[derive (Debug, Serialize)]
+#[serde (rename_all = "camelCase")]
+pub struct BatchResult {
+    pub name: String,
+
+    // True if every program in the source generated code
+    pub success: bool,
+    pub num_errors: usize,
+    pub num_warnings: usize,
+    pub programs: Vec<BatchProgramResult>,
+
+    // The errors and warnings in the order they were found
+    pub diagnostics: Vec<LogRecord>
+}
+
+// Function to compile many named sources at once without touching the page
+// The sources are a JSON array of { name, source } and the target is "6502" or "RISC-V"
+#[cfg(feature = "ui")]
+#[wasm_bindgen(js_name = "compileMany")]
+pub fn compile_many(sources_json: &str, target: &str) -> Result<String, JsValue> {
+    let batch_sources: Vec<BatchSource> = serde_json::from_str(sources_json)
+        .map_err(|err| JsValue::from_str(format!("Invalid batch sources: {}", err).as_str()))?;
+
+    let target_config: TargetConfig = match target {
+        "6502" => TargetConfig::new(Target::Target6502),
+        "RISC-V" => TargetConfig::new(Target::TargetRiscV),
+        _ => return Err(JsValue::from_str(format!("Invalid target [ {} ], which must be 6502 or RISC-V", target).as_str()))
+    };
+
+    let results: Vec<BatchResult> = batch_sources.iter()
+        .map(|batch_source| compile_source(batch_source, &target_config))
+        .collect();
+
+    return Ok(serde_json::to_string(&results).expect("Should be able to serialize the batch results"));
+}
+
+// Function to compile a single named source with the logs captured
+pub fn compile_source(batch_source: &BatchSource, target_config: &TargetConfig) -> BatchResult {
+    return compile_source_with_options(batch_source, &CompileOptions::new(target_config.to_owned()));
+}
+
+// Function to compile a single named source with the given options, such as to run every program with the interpreter
+pub fn compile_source_with_options(batch_source: &BatchSource, options: &CompileOptions) -> BatchResult {
+    nexus_log::start_capture();
+
+    let mut programs: Vec<BatchProgramResult> = Vec::new();
+    let mut preprocessor: Preprocessor = Preprocessor::new();
+    preprocessor.set_language_profile(&options.language_profile);
+    let preprocess_res: Result<String, ()> = preprocessor.expand_defines(&batch_source.source);
+    let is_preprocessed: bool = preprocess_res.is_ok();
+
+    if is_preprocessed {
+        // The lexer has to go in order to find where each program ends
+        let mut lexer: Lexer = Lexer::new(&preprocess_res.unwrap());
+        let mut parser: Parser = Parser::new();
+        lexer.set_language_profile(&options.language_profile);
+        lexer.set_column_map(preprocessor.get_column_map());
+        parser.set_language_profile(&options.language_profile);
+        let mut parsed_programs: Vec<ParsedProgram> = Vec::new();
+        // The logs of lexing and parsing each program and the stage that failed (if any)
+        let mut front_end_outputs: Vec<(Vec<RecordedLog>, Option<&'static str>)> = Vec::new();
+        while lexer.has_program_to_lex() {
+            let program_number: u32 = front_end_outputs.len() as u32 + 1;
+            nexus_log::start_recording();
+            let parse_res: Result<ParsedProgram, &'static str> = lex_and_parse(&mut lexer, &mut parser, program_number);
+            let front_end_logs: Vec<RecordedLog> = nexus_log::stop_recording();
+            match parse_res {
+                Ok(parsed_program) => {
+                    parsed_programs.push(parsed_program);
+                    front_end_outputs.push((front_end_logs, None));
+                },
+                Err(stage) => front_end_outputs.push((front_end_logs, Some(stage)))
+            }
+        }
+
+        let mut program_outputs: std::vec::IntoIter<ProgramOutput> = pipeline::compile_programs(&parsed_programs, options).into_iter();
+
+        // The logs are put back in the order of the programs
+        for (i, (front_end_logs, front_end_failed_stage)) in front_end_outputs.iter().enumerate() {
+            // Each program is captured on its own too so its diagnostics can be told apart from the other programs
+            nexus_log::start_capture();
+            nexus_log::replay(front_end_logs);
+
+            let (failed_stage, code, output): (Option<&str>, Option<String>, Option<String>) = match front_end_failed_stage {
+                Some(stage) => (Some(*stage), None, None),
+                None => get_program_result(program_outputs.next().expect("Every parsed program should have an output"))
+            };
+            let program_diagnostics: Vec<LogRecord> = nexus_log::stop_capture();
+            nexus_log::replay(&program_diagnostics.iter().map(|record| RecordedLog::Record(record.to_owned())).collect());
+
+            programs.push(BatchProgramResult {
+                program_number: i as u32 + 1,
+                failed_stage: failed_stage.map(|stage| String::from(stage)),
+                code: code,
+                output: output,
+                diagnostics: program_diagnostics
+            });
+        }
+    }
+
+    let diagnostics: Vec<LogRecord> = nexus_log::stop_capture();
+    return BatchResult {
+        name: batch_source.name.to_owned(),
+        success: is_preprocessed && programs.iter().all(|program| program.failed_stage.is_none()),
+        num_errors: diagnostics.iter().filter(|diagnostic| matches!(diagnostic.log_type, LogTypes::Error)).count(),
+        num_warnings: diagnostics.iter().filter(|diagnostic| matches!(diagnostic.log_type, LogTypes::Warning)).count(),
+        programs: programs,
+        diagnostics: diagnostics
+    };
+}
+
+// Function to get the tokens of every program in a source with the logs captured
+// The errors and warnings are returned instead if the source could not be preprocessed or any program could not be lexed
+pub fn lex_source(source: &str) -> Result<Vec<Vec<Token>>, Vec<LogRecord>> {
+    nexus_log::start_capture();
+
+    let mut programs: Vec<Vec<Token>> = Vec::new();
+    let mut is_lexed: bool = false;
+    let mut preprocessor: Preprocessor = Preprocessor::new();
+    if let Ok(code) = preprocessor.expand_defines(source) {
+        is_lexed = true;
+        let mut lexer: Lexer = Lexer::new(&code);
+        lexer.set_column_map(preprocessor.get_column_map());
+        while lexer.has_program_to_lex() {
+            match lexer.lex_program() {
+                Ok(token_stream) => programs.push(token_stream),
+                Err(_) => is_lexed = false
+            }
+        }
+    }
+
+    let diagnostics: Vec<LogRecord> = nexus_log::stop_capture();
+    return match is_lexed {
+        true => Ok(programs),
+        false => Err(diagnostics)
+    };
+}
+
+// Function to lex and parse the next program, returns the stage that failed if it could not be parsed
+fn lex_and_parse(lexer: &mut Lexer, parser: &mut Parser, program_number: u32) -> Result<ParsedProgram, &'static str> {
+    let lex_res: Result<Vec<Token>, ()> = lexer.lex_program();
+    if lex_res.is_err() {
+        return Err("lex");
+    }
+
+    let token_stream: Vec<Token> = lex_res.unwrap();
+    let parse_res: Result<SyntaxTree, ()> = parser.parse_program(&token_stream);
+    if parse_res.is_err() {
+        return Err("parse");
+    }
+
+    return Ok(ParsedProgram {
+        program_number: program_number,
+        token_stream: token_stream
+    });
+}
+
+// Function to show the logs of the phases after parsing and get the stage that failed (if any), the generated code, and what the interpreter printed
+fn get_program_result(program_output: ProgramOutput) -> (Option<&'static str>, Option<String>, Option<String>) {
+    nexus_log::replay(&program_output.ast_logs);
+    nexus_log::replay(&program_output.analysis_logs);
+    nexus_log::replay(&program_output.code_gen_logs);
+
+    if let Some(program_run) = program_output.program_run {
+        if let Some(message) = &program_run.error {
+            nexus_log::log(
+                LogTypes::Error,
+                interpreter::LOG_SOURCE,
+                format!("Program {} stopped early: {}", program_output.program_number, message)
+            );
+            return (Some("interpreter"), None, Some(program_run.get_output()));
+        }
+        return (None, None, Some(program_run.get_output()));
+    }
+
+    return match program_output.code_gen {
+        None => (Some("semantic analysis"), None, None),
+        Some((_, false)) => (Some("code generation"), None, None),
+        Some((TargetCodeGenerator::Target6502(code_generator), true)) => (None, Some(code_generator.get_image_hex()), None),
+        Some((TargetCodeGenerator::TargetRiscV(mut code_generator), true)) => (None, Some(code_generator.get_assembly()), None)
+    };
+}
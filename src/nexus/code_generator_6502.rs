@@ -1,23 +1,21 @@
 use log::*;
 
-use crate::nexus::{syntax_tree::SyntaxTree, syntax_tree_node::*, symbol_table::*};
-use crate::nexus::token::{TokenType, Keywords};
-use crate::util::nexus_log;
+use crate::nexus::{syntax_tree::SyntaxTree, syntax_tree_node::*, symbol_table::*, semantic_analyzer::SemanticAnalysisResult, optimizer, memory_layout::{MemoryLayout, MemoryRegion}, cost_model::{self, LoopRange}, source_map};
+use crate::nexus::{operand, token::{Token, TokenType, Keywords}};
+use crate::util::{nexus_log, instruction_set::Opcode6502, target::{TargetConfig, MmioConfig}, build_info::BuildInfo};
+#[cfg(feature = "ui")]
+use crate::nexus::cost_model::StatementCost;
+#[cfg(feature = "ui")]
+use crate::editor::output;
+#[cfg(feature = "ui")]
+use crate::util::{instruction_set, html::escape_html};
 use petgraph::graph::{NodeIndex};
 
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
+use indexmap::IndexMap;
 use std::fmt;
+#[cfg(feature = "ui")]
 use web_sys::{Document, Window, Element, DomTokenList};
-use wasm_bindgen::{prelude::Closure, JsCast};
-use wasm_bindgen::prelude::*;
-
-// Have to import the editor js module
-#[wasm_bindgen(module = "/editor.js")]
-extern "C" {
-    // Import the getCodeInput function from js so we can call it from the Rust code
-    #[wasm_bindgen(js_name = "setClipboard")]
-    fn set_clipboard(newText: &str);
-}
 
 enum CodeGenBytes {
     // Representation for final code/data in memory
@@ -51,12 +49,76 @@ impl fmt::Debug for CodeGenBytes {
     }
 }
 
+// The registers that an operand can be loaded into
+#[derive (Debug, Clone, Copy)]
+enum Register {
+    Acc,
+    X,
+    Y
+}
+
+impl Register {
+    // Opcode to load a constant into the register
+    fn load_immediate_opcode(&self) -> Opcode6502 {
+        return match self {
+            Register::Acc => Opcode6502::LdaImmediate,
+            Register::X => Opcode6502::LdxImmediate,
+            Register::Y => Opcode6502::LdyImmediate
+        };
+    }
+
+    // Opcode to load a value from memory into the register
+    fn load_absolute_opcode(&self) -> Opcode6502 {
+        return match self {
+            Register::Acc => Opcode6502::LdaAbsolute,
+            Register::X => Opcode6502::LdxAbsolute,
+            Register::Y => Opcode6502::LdyAbsolute
+        };
+    }
+}
+
+// The memory operand of an absolute instruction
+#[derive (Debug, Clone, Copy)]
+enum Address {
+    // A spot in static memory that is backpatched after the code is generated
+    Static(usize),
+    // A spot in temp memory that is backpatched after the code is generated
+    Temp(usize),
+    // An address that is already known
+    Fixed(u16)
+}
+
+// The operand of a BNE
+#[derive (Debug, Clone, Copy)]
+enum Branch {
+    // Branch over a known number of bytes
+    Offset(u8),
+    // Branch to a spot that is backpatched once the code for it is generated
+    Jump
+}
+
+// The byte at 0xFF is always 0, so comparing X to it sets Z = 0 whenever X is not 0
+const ZERO_ADDRESS: u16 = 0x00FF;
+
+// Booleans are only ever printed, so their strings already end with the new line that every print ends with
+const TRUE_STRING: &str = "true\n";
+const FALSE_STRING: &str = "false\n";
+
+// The values that get their own spot in static memory
+#[derive (Debug, Clone, Copy, PartialEq, Eq, Hash)]
+enum StaticVariable {
+    // A declared variable, so shadowed variables can never share a spot
+    Declaration(DeclId),
+    // A loop-invariant expression that was computed before its loop
+    Hoisted(NodeIndex)
+}
+
 // The struct for the code generator
 #[derive (Debug)]
 pub struct CodeGenerator6502 {
-    // The current max scope we have seen so far, which are encountered in
-    // sequential order
-    max_scope: usize,
+    // The scope the semantic analyzer gave each block, so blocks that are skipped as dead code
+    // do not throw off the scopes of the blocks after them
+    block_scopes: HashMap<NodeIndex, usize>,
     
     // The array for code gen
     code_arr: Vec<CodeGenBytes>,
@@ -68,24 +130,70 @@ pub struct CodeGenerator6502 {
     // The current location of the heap from the back of the array
     heap_pointer: u8,
 
-    // The static table hashmap for <(id, scope), offset>
-    static_table: HashMap<(String, usize), usize>,
+    // The first address of the heap, which is right below the reserved cells
+    heap_top: u8,
+
+    // The lowest address the heap can grow to, or None if it can grow until it meets the static data
+    heap_split: Option<u8>,
+
+    // The output device that prints are stored to, or None to print with system calls
+    mmio: Option<MmioConfig>,
+
+    // The static table for <variable, offset> in the order the variables are declared
+    static_table: IndexMap<StaticVariable, usize>,
 
     // Index for the temoprary data
     temp_index: usize,
 
-    // Hashmap to keep track of the strings being stored on the heap
-    string_history: HashMap<String, u8>,
+    // Map to keep track of the strings being stored on the heap in the order they are stored
+    string_history: IndexMap<String, u8>,
+
+    // The heap address of the string that char variables are copied into to be printed
+    char_buffer: Option<u8>,
+
+    // Whether every string is stored with its length in the byte before it, which is only
+    // needed when the program takes the length of a string variable
+    store_lengths: bool,
+
+    // The heap address of the 0xFF that is added to a string address to get the address of its length
+    minus_one: Option<u8>,
 
     // Vector to keep track of each jump in the code
     jumps: Vec<u8>,
+
+    // Flag for generating smaller code at the cost of more work at compile time
+    optimize_size: bool,
+
+    // How the program was compiled, which is shown next to the image
+    build_info: Option<BuildInfo>,
+
+    // Hashmap of the loop-invariant expressions computed before their loop <expr, static offset>
+    hoisted_values: HashMap<NodeIndex, usize>,
+
+    // The declaration of every identifier node from semantic analysis
+    decl_ids: HashMap<NodeIndex, DeclId>,
+
+    // Assignments that have no effect and do not get any code
+    removed_statements: HashSet<NodeIndex>,
+
+    // The code for each statement in the program block (label, start, end) for the cost estimate
+    statement_ranges: Vec<(String, usize, usize)>,
+
+    // The code for each while loop for the cost estimate
+    loop_ranges: Vec<LoopRange>,
+
+    // The code produced by each statement and block (node, start, end)
+    coverage_ranges: Vec<(NodeIndex, usize, usize)>,
+
+    // Whether the scope of each block is recorded in the debug information, since the image cannot have comments
+    scope_comments: bool
 }
 
 impl CodeGenerator6502 {
     pub fn new() -> Self {
         let mut code_gen: CodeGenerator6502 = CodeGenerator6502 {
             // This is a flag for a new program
-            max_scope: usize::MAX,
+            block_scopes: HashMap::new(),
 
             // We are only able to store 256 bytes in memory
             code_arr: Vec::with_capacity(0x100),
@@ -96,14 +204,39 @@ impl CodeGenerator6502 {
             // Heap starts at 0xFE (0xFF reserved for 0x00)
             heap_pointer: 0xFE,
 
-            static_table: HashMap::new(),
+            heap_top: 0xFE,
+
+            heap_split: None,
+
+            mmio: None,
+
+            static_table: IndexMap::new(),
 
             // Always start with a temp index of 0
             temp_index: 0,
 
-            string_history: HashMap::new(),
+            string_history: IndexMap::new(),
+            char_buffer: None,
+            store_lengths: false,
+            minus_one: None,
+
+            jumps: Vec::new(),
+
+            optimize_size: false,
+
+            build_info: None,
 
-            jumps: Vec::new()
+            hoisted_values: HashMap::new(),
+            decl_ids: HashMap::new(),
+            removed_statements: HashSet::new(),
+
+            statement_ranges: Vec::new(),
+
+            loop_ranges: Vec::new(),
+
+            coverage_ranges: Vec::new(),
+
+            scope_comments: false
         };
 
         // Initialize the entire array to be unused spot in memory
@@ -114,88 +247,184 @@ impl CodeGenerator6502 {
         return code_gen;
     }
 
-    pub fn generate_code(&mut self, ast: &SyntaxTree, symbol_table: &mut SymbolTable, program_number: &u32) {
-        // Make sure the current scope is set to be a flag for none
-        self.max_scope = usize::MAX;
-        
+    // Function to turn the size optimizations on or off
+    pub fn set_optimize_size(&mut self, optimize_size: bool) {
+        self.optimize_size = optimize_size;
+    }
+
+    // Function to turn the scope of each block in the debug information on or off, which is the 6502 version of the scope comments
+    pub fn set_scope_comments(&mut self, scope_comments: bool) {
+        self.scope_comments = scope_comments;
+    }
+
+    // Function to set the build information to show with the image
+    pub fn set_build_info(&mut self, build_info: BuildInfo) {
+        self.build_info = Some(build_info);
+    }
+
+    // Function to set where the heap starts and how far it can grow
+    pub fn set_memory_layout(&mut self, target_config: &TargetConfig) {
+        self.heap_top = (0xFF - target_config.reserved_cells) as u8;
+        self.heap_split = target_config.heap_split.map(|addr| addr as u8);
+        self.mmio = target_config.mmio;
+    }
+
+    // Function to get the number of bytes used for (code, static variables, heap)
+    pub fn get_memory_usage(&self) -> (usize, usize, usize) {
+        // The heap includes the temp data, which sits right below it
+        let memory_layout: MemoryLayout = self.get_memory_layout();
+        return (memory_layout.code_size, memory_layout.static_size, memory_layout.heap_size + memory_layout.temp_size);
+    }
+
+    // Function to get the number of bytes used by the strings on the heap
+    pub fn get_string_heap_size(&self) -> usize {
+        return self.get_memory_layout().heap_size;
+    }
+
+    // Function to generate the executable image and report the size savings, returns true if successful
+    pub fn generate_code(&mut self, semantic_analysis_res: &mut SemanticAnalysisResult) -> bool {
+        // Generate the unoptimized image first so the savings can be reported
+        let mut unoptimized_usage: Option<(usize, usize, usize)> = None;
+        if self.optimize_size {
+            nexus_log::log(
+                nexus_log::LogTypes::Info,
+                nexus_log::LogSource::CODE_GENERATOR,
+                format!("Generating the unoptimized image for the size comparison")
+            );
+
+            let mut unoptimized_generator: CodeGenerator6502 = CodeGenerator6502::new();
+            unoptimized_generator.heap_top = self.heap_top;
+            unoptimized_generator.heap_split = self.heap_split;
+            unoptimized_generator.mmio = self.mmio;
+            if unoptimized_generator.generate_image(semantic_analysis_res) {
+                unoptimized_usage = Some(unoptimized_generator.get_memory_usage());
+            }
+        }
+
+        if !self.generate_image(semantic_analysis_res) {
+            return false;
+        }
+
+        if self.optimize_size {
+            self.log_size_report(unoptimized_usage);
+        }
+        return true;
+    }
+
+    // Function to log the before and after byte counts for the size optimizations
+    fn log_size_report(&self, unoptimized_usage: Option<(usize, usize, usize)>) {
+        let (code_size, static_size, heap_size): (usize, usize, usize) = self.get_memory_usage();
+        let total_size: usize = code_size + static_size + heap_size;
+
+        match unoptimized_usage {
+            Some((old_code_size, old_static_size, old_heap_size)) => {
+                let old_total_size: usize = old_code_size + old_static_size + old_heap_size;
+                nexus_log::log(
+                    nexus_log::LogTypes::Info,
+                    nexus_log::LogSource::CODE_GENERATOR,
+                    format!("Size optimization report: code {} -> {} bytes, static {} -> {} bytes, heap {} -> {} bytes, total {} -> {} of 256 bytes ({} bytes saved)",
+                            old_code_size, code_size, old_static_size, static_size, old_heap_size, heap_size,
+                            old_total_size, total_size, old_total_size as i32 - total_size as i32)
+                );
+            },
+            None => {
+                // The program only fits in memory because of the optimizations
+                nexus_log::log(
+                    nexus_log::LogTypes::Info,
+                    nexus_log::LogSource::CODE_GENERATOR,
+                    format!("Size optimization report: code {} bytes, static {} bytes, heap {} bytes, total {} of 256 bytes (the unoptimized image does not fit in memory)",
+                            code_size, static_size, heap_size, total_size)
+                );
+            }
+        }
+    }
+
+    // Function to generate the executable image without displaying it, returns true if successful
+    pub fn generate_image(&mut self, semantic_analysis_res: &mut SemanticAnalysisResult) -> bool {
+        let ast: &SyntaxTree = &semantic_analysis_res.ast;
+        let symbol_table: &mut SymbolTable = &mut semantic_analysis_res.symbol_table;
+
         // Reset the array and empty it out
         for i in 0..0x100 {
             self.code_arr[i] = CodeGenBytes::Empty;
         }
 
         self.code_pointer = 0x00;
-        self.heap_pointer = 0xFE;
+        self.heap_pointer = self.heap_top;
 
         self.static_table.clear();
         self.temp_index = 0;
         self.string_history.clear();
+        self.char_buffer = None;
+        self.store_lengths = Self::has_variable_length(ast, NodeIndex::new((*ast).root.unwrap()));
+        self.minus_one = None;
         self.jumps.clear();
-
-        // We are going to store the strings false and true to print them
-        // out instead of 0 and 1
-        self.store_string("false");
-        self.store_string("true");
+        self.hoisted_values.clear();
+        self.block_scopes = semantic_analysis_res.block_scopes.clone();
+        self.decl_ids = semantic_analysis_res.decl_ids.clone();
+        self.removed_statements = optimizer::find_no_op_statements(ast);
+        self.statement_ranges.clear();
+        self.loop_ranges.clear();
+        self.coverage_ranges.clear();
 
         // Generate the code for the program
         let program_res: bool = self.code_gen_block(ast, NodeIndex::new((*ast).root.unwrap()), symbol_table);
 
         if program_res {
-            // All programs end with 0x00, which is HALT
-            let final_res: bool = self.add_code(0x00);
+            // All programs end with BRK
+            let final_res: bool = self.emit_brk();
 
             if final_res {
+                if self.optimize_size {
+                    self.thread_branches();
+                }
                 self.backpatch_addresses();
 
-                nexus_log::log(
-                    nexus_log::LogTypes::Info,
-                    nexus_log::LogSources::CodeGenerator,
-                    format!("Code generation completed successfully")
-                );
-
-                nexus_log::log(
-                    nexus_log::LogTypes::Info,
-                    nexus_log::LogSources::Nexus,
-                    format!("Executable image for program {} is below", *program_number)
-                );
+                // Do not show the user an image that would not run correctly
+                let image_problems: Vec<String> = self.verify_image();
+                for problem in image_problems.iter() {
+                    nexus_log::log(
+                        nexus_log::LogTypes::Error,
+                        nexus_log::LogSource::CODE_GENERATOR,
+                        format!("Invalid executable image: {}", problem)
+                    );
+                }
 
-                self.display_code(program_number);
-                return;
+                if image_problems.len() == 0 {
+                    nexus_log::log(
+                        nexus_log::LogTypes::Info,
+                        nexus_log::LogSource::CODE_GENERATOR,
+                        format!("Code generation completed successfully")
+                    );
+                    return true;
+                }
             }
         }
 
         nexus_log::log(
             nexus_log::LogTypes::Error,
-            nexus_log::LogSources::CodeGenerator,
+            nexus_log::LogSource::CODE_GENERATOR,
             format!("Code generation failed")
         );
-        
-        nexus_log::insert_empty_line();
-
-        nexus_log::log(
-            nexus_log::LogTypes::Warning,
-            nexus_log::LogSources::Nexus,
-            format!("Executable image display skipped due to code generation failure")
-        );
+        return false;
     }
 
     fn code_gen_block(&mut self, ast: &SyntaxTree, cur_index: NodeIndex, symbol_table: &mut SymbolTable) -> bool {
-        // If this is the first block, then the first scope is 0
-        if self.max_scope == usize::MAX {
-            self.max_scope = 0;
-        } else {
-            // Otherwise just add 1
-            self.max_scope += 1;
-        }
+        let block_scope: usize = *self.block_scopes.get(&cur_index).unwrap();
 
         nexus_log::log(
             nexus_log::LogTypes::Debug,
-            nexus_log::LogSources::CodeGenerator,
-            format!("Starting code generation for the block for scope {}", self.max_scope)
+            nexus_log::LogSource::CODE_GENERATOR,
+            format!("Starting code generation for the block for scope {}", block_scope)
         );
 
         // Manually set the current scope because we are not able to look down
         // in the symbol table
-        symbol_table.set_cur_scope(self.max_scope);
+        symbol_table.set_cur_scope(block_scope);
+
+        // Only the statements of the program block get their own cost
+        let is_program_block: bool = block_scope == 0;
+        let block_start: usize = self.code_pointer as usize;
 
         // The current node is the block, so we need to loop through each of its children
         let neighbors: Vec<NodeIndex> = (*ast).graph.neighbors(cur_index).collect();
@@ -203,16 +432,28 @@ impl CodeGenerator6502 {
         // Assume a success
         let mut block_res: bool = true;
 
-        for neighbor_index in neighbors.into_iter().rev() {
+        // Statements are stored in reverse order
+        let statements: Vec<NodeIndex> = neighbors.into_iter().rev().collect();
+
+        for i in 0..statements.len() {
+            let neighbor_index: NodeIndex = statements[i];
             let child: &SyntaxTreeNode = (*ast).graph.node_weight(neighbor_index).unwrap();
+            let statement_start: usize = self.code_pointer as usize;
             
             match child {
                 SyntaxTreeNode::NonTerminalAst(non_terminal) => {
                     block_res = match non_terminal {
                         NonTerminalsAst::Block => self.code_gen_block(ast, neighbor_index, symbol_table),
-                        NonTerminalsAst::VarDecl => self.code_gen_var_decl(ast, neighbor_index, symbol_table),
+                        NonTerminalsAst::VarDecl => {
+                            // The default value is not needed if the initializer immediately overwrites it
+                            let skip_default: bool = self.optimize_size && i + 1 < statements.len()
+                                                        && Self::is_overwriting_initializer(ast, neighbor_index, statements[i + 1]);
+                            self.code_gen_var_decl(ast, neighbor_index, symbol_table, skip_default)
+                        },
+                        NonTerminalsAst::Assign if self.removed_statements.contains(&neighbor_index) => true,
                         NonTerminalsAst::Assign => self.code_gen_assignment(ast, neighbor_index, symbol_table),
                         NonTerminalsAst::Print => self.code_gen_print(ast, neighbor_index, symbol_table),
+                        NonTerminalsAst::If | NonTerminalsAst::While if self.removed_statements.contains(&neighbor_index) => true,
                         NonTerminalsAst::If => self.code_gen_if(ast, neighbor_index, symbol_table),
                         NonTerminalsAst::While => self.code_gen_while(ast, neighbor_index, symbol_table),
                         _ => { 
@@ -223,29 +464,60 @@ impl CodeGenerator6502 {
                     if !block_res {
                         return false;
                     }
+
+                    self.coverage_ranges.push((neighbor_index, statement_start, self.code_pointer as usize));
+                    if is_program_block {
+                        self.statement_ranges.push((cost_model::get_statement_label(ast, neighbor_index), statement_start, self.code_pointer as usize));
+                    }
                 }
                 _ => error!("Received {:?} when expecting an AST nonterminal for code gen in a block", child)
             }
         }
 
+        self.coverage_ranges.push((cur_index, block_start, self.code_pointer as usize));
+
         // Exit the current scope
         symbol_table.end_cur_scope();
         return block_res;
     }
 
-    fn has_available_memory(&mut self) -> bool {
-        let num_vars: usize = self.static_table.len();
-        // Check for collision at the double bar (where stack meets heap)
-        //  |  Code  |  Vars  ||  Temp  |  Heap  |
-        return self.code_pointer + (num_vars as u8) <= self.heap_pointer - (self.temp_index as u8);
+    // Function to get the current size of each region of memory
+    fn get_memory_layout(&self) -> MemoryLayout {
+        return MemoryLayout {
+            code_size: self.code_pointer as usize,
+            static_size: self.static_table.len(),
+            temp_size: self.temp_index,
+            heap_size: (self.heap_top - self.heap_pointer) as usize,
+            heap_end: self.heap_top as usize + 1,
+            heap_split: self.heap_split.map(|split| split as usize)
+        };
+    }
+
+    // Function to check if there is room for 1 more byte in the region
+    // Logs which region overflowed if there is not
+    fn has_available_memory(&mut self, region: MemoryRegion) -> bool {
+        let mut memory_layout: MemoryLayout = self.get_memory_layout();
+        memory_layout.grow(region, 1);
+
+        return match memory_layout.find_overflow(region) {
+            Some(overflow_message) => {
+                nexus_log::log(
+                    nexus_log::LogTypes::Error,
+                    nexus_log::LogSource::CODE_GENERATOR,
+                    overflow_message
+                );
+                false
+            },
+            None => true
+        };
     }
 
     // Function to add byte of code to the memory array
     fn add_code(&mut self, code: u8) -> bool {
-        if self.has_available_memory() {
+        if self.has_available_memory(MemoryRegion::Code) {
             nexus_log::log(
                 nexus_log::LogTypes::Debug,
-                nexus_log::LogSources::CodeGenerator,
+                nexus_log::LogSource::CODE_GENERATOR,
                 format!("Adding code 0x{:02X} at memory location 0x{:02X}", code, self.code_pointer)
             );
 
@@ -255,21 +527,16 @@ impl CodeGenerator6502 {
             // No error, so successful addition to the code
             return true;
         } else {
-            nexus_log::log(
-                nexus_log::LogTypes::Error,
-                nexus_log::LogSources::CodeGenerator,
-                String::from("The stack has collided with the heap causing a stack overflow error")
-            );
             return false;
         }
     }
 
     // Function to add byte of code to the memory array for variable addressing
     fn add_var(&mut self, var: usize) -> bool {
-        if self.has_available_memory() {
+        if self.has_available_memory(MemoryRegion::Code) {
             nexus_log::log(
                 nexus_log::LogTypes::Debug,
-                nexus_log::LogSources::CodeGenerator,
+                nexus_log::LogSource::CODE_GENERATOR,
                 format!("Adding variable placeholder {} at memory location 0x{:02X}", var, self.code_pointer)
             );
 
@@ -279,21 +546,16 @@ impl CodeGenerator6502 {
             // All vars are followed by the high order byte
             return self.add_high_order_byte();
         } else {
-            nexus_log::log(
-                nexus_log::LogTypes::Error,
-                nexus_log::LogSources::CodeGenerator,
-                String::from("The stack has collided with the heap causing a stack overflow error")
-            );
             return false;
         }
     }
 
     // Function to add the high order byte for unknown addresses that will be backpatched
     fn add_high_order_byte(&mut self) -> bool {
-        if self.has_available_memory() {
+        if self.has_available_memory(MemoryRegion::Code) {
             nexus_log::log(
                 nexus_log::LogTypes::Debug,
-                nexus_log::LogSources::CodeGenerator,
+                nexus_log::LogSource::CODE_GENERATOR,
                 format!("Adding high order byte placeholder at memory location 0x{:02X}", self.code_pointer)
             );
 
@@ -302,38 +564,28 @@ impl CodeGenerator6502 {
             self.code_pointer += 1;
             return true;
         } else {
-            nexus_log::log(
-                nexus_log::LogTypes::Error,
-                nexus_log::LogSources::CodeGenerator,
-                String::from("The stack has collided with the heap causing a stack overflow error")
-            );
             return false;
         }
     }
 
     // Function to create space for new temp data and return its index
     fn new_temp(&mut self) -> Option<usize> {
-        if self.has_available_memory() {
+        if self.has_available_memory(MemoryRegion::Temp) {
             // Make the room for the single byte
             let temp_addr: usize = self.temp_index.to_owned();
             self.temp_index += 1;
             return Some(temp_addr);
         } else {
-            nexus_log::log(
-                nexus_log::LogTypes::Error,
-                nexus_log::LogSources::CodeGenerator,
-                String::from("The heap has collided with the stack causing a heap overflow error")
-            );
             return None;
         }
     }
 
     // Function to add byte of code to memory array for temporary data
     fn add_temp(&mut self, temp: usize) -> bool {
-        if self.has_available_memory() {
+        if self.has_available_memory(MemoryRegion::Code) {
             nexus_log::log(
                 nexus_log::LogTypes::Debug,
-                nexus_log::LogSources::CodeGenerator,
+                nexus_log::LogSource::CODE_GENERATOR,
                 format!("Adding temp data placeholder {} at memory location 0x{:02X}", temp, self.code_pointer)
             );
 
@@ -343,21 +595,16 @@ impl CodeGenerator6502 {
             // All temps are followed by the high order byte
             return self.add_high_order_byte();
         } else {
-            nexus_log::log(
-                nexus_log::LogTypes::Error,
-                nexus_log::LogSources::CodeGenerator,
-                String::from("The heap has collided with the stack causing a heap overflow error")
-            );
             return false;
         }
     }
 
     // Function to add a byte of data to the heap
     fn add_data(&mut self, data: u8) -> bool {
-        if self.has_available_memory() {
+        if self.has_available_memory(MemoryRegion::Heap) {
             nexus_log::log(
                 nexus_log::LogTypes::Debug,
-                nexus_log::LogSources::CodeGenerator,
+                nexus_log::LogSource::CODE_GENERATOR,
                 format!("Adding data 0x{:02X} at memory location 0x{:02X}", data, self.heap_pointer)
             );
 
@@ -366,11 +613,6 @@ impl CodeGenerator6502 {
             self.heap_pointer -= 1;
             return true;
         } else {
-            nexus_log::log(
-                nexus_log::LogTypes::Error,
-                nexus_log::LogSources::CodeGenerator,
-                String::from("The heap has collided with the stack causing a heap overflow error")
-            );
             return false;
         }
     }
@@ -393,17 +635,23 @@ impl CodeGenerator6502 {
                     break;
                 }
             }
+            let string_addr: u8 = self.heap_pointer + 1;
+
+            // The length goes right before the first character
+            if is_stored && self.store_lengths {
+                is_stored = self.add_data(string.len() as u8);
+            }
            
             if is_stored {
                 nexus_log::log(
                     nexus_log::LogTypes::Debug,
-                    nexus_log::LogSources::CodeGenerator,
-                    format!("Stored string \"{}\" at memory location 0x{:02X}", string, self.heap_pointer + 1)
+                    nexus_log::LogSource::CODE_GENERATOR,
+                    format!("Stored string \"{}\" at memory location 0x{:02X}", string, string_addr)
                 );
 
                 // Store it for future use
-                self.string_history.insert(String::from(string), self.heap_pointer + 1);
-                return Some(self.heap_pointer + 1);
+                self.string_history.insert(String::from(string), string_addr);
+                return Some(string_addr);
             } else {
                 // There is no address to return
                 return None;
@@ -415,10 +663,10 @@ impl CodeGenerator6502 {
     }
 
     fn add_jump(&mut self) -> bool {
-        if self.has_available_memory() {
+        if self.has_available_memory(MemoryRegion::Code) {
             nexus_log::log(
                 nexus_log::LogTypes::Debug,
-                nexus_log::LogSources::CodeGenerator,
+                nexus_log::LogSource::CODE_GENERATOR,
                 format!("Adding jump placeholder {} at memory location 0x{:02X}", self.jumps.len(), self.code_pointer)
             );
 
@@ -428,15 +676,83 @@ impl CodeGenerator6502 {
             self.jumps.push(0x00);
             return true;
         } else {
-            nexus_log::log(
-                nexus_log::LogTypes::Error,
-                nexus_log::LogSources::CodeGenerator,
-                String::from("The stack has collided with the heap causing a stack overflow error")
-            );
             return false;
         }
     }
 
+    // Function to add an instruction that takes a constant
+    fn emit_immediate(&mut self, opcode: Opcode6502, value: u8) -> bool {
+        if !self.add_code(opcode as u8) { return false; }
+        return self.add_code(value);
+    }
+
+    // Function to add an instruction that takes an address in memory
+    fn emit_absolute(&mut self, opcode: Opcode6502, address: Address) -> bool {
+        if !self.add_code(opcode as u8) { return false; }
+        return match address {
+            Address::Static(static_offset) => self.add_var(static_offset),
+            Address::Temp(temp_addr) => self.add_temp(temp_addr),
+            // Addresses are little endian
+            Address::Fixed(addr) => self.add_code((addr & 0xFF) as u8) && self.add_code((addr >> 8) as u8)
+        };
+    }
+
+    fn emit_lda_imm(&mut self, value: u8) -> bool {
+        return self.emit_immediate(Opcode6502::LdaImmediate, value);
+    }
+
+    fn emit_ldx_imm(&mut self, value: u8) -> bool {
+        return self.emit_immediate(Opcode6502::LdxImmediate, value);
+    }
+
+    fn emit_load_imm(&mut self, register: Register, value: u8) -> bool {
+        return self.emit_immediate(register.load_immediate_opcode(), value);
+    }
+
+    fn emit_lda_abs(&mut self, address: Address) -> bool {
+        return self.emit_absolute(Opcode6502::LdaAbsolute, address);
+    }
+
+    fn emit_ldx_abs(&mut self, address: Address) -> bool {
+        return self.emit_absolute(Opcode6502::LdxAbsolute, address);
+    }
+
+    fn emit_ldy_abs(&mut self, address: Address) -> bool {
+        return self.emit_absolute(Opcode6502::LdyAbsolute, address);
+    }
+
+    fn emit_load_abs(&mut self, register: Register, address: Address) -> bool {
+        return self.emit_absolute(register.load_absolute_opcode(), address);
+    }
+
+    fn emit_sta_abs(&mut self, address: Address) -> bool {
+        return self.emit_absolute(Opcode6502::StaAbsolute, address);
+    }
+
+    fn emit_adc_abs(&mut self, address: Address) -> bool {
+        return self.emit_absolute(Opcode6502::AdcAbsolute, address);
+    }
+
+    fn emit_cpx_abs(&mut self, address: Address) -> bool {
+        return self.emit_absolute(Opcode6502::CpxAbsolute, address);
+    }
+
+    fn emit_bne(&mut self, branch: Branch) -> bool {
+        if !self.add_code(Opcode6502::Bne as u8) { return false; }
+        return match branch {
+            Branch::Offset(offset) => self.add_code(offset),
+            Branch::Jump => self.add_jump()
+        };
+    }
+
+    fn emit_sys(&mut self) -> bool {
+        return self.add_code(Opcode6502::Sys as u8);
+    }
+
+    fn emit_brk(&mut self) -> bool {
+        return self.add_code(Opcode6502::Brk as u8);
+    }
+
     // Replaces temp addresses with the actual position in memory
     // Do not have to worry about memory availability because that was taken
     // care of when the placeholders were created
@@ -448,7 +764,7 @@ impl CodeGenerator6502 {
                     let new_addr: u8 = self.code_pointer + *offset as u8;
                     nexus_log::log(
                         nexus_log::LogTypes::Debug,
-                        nexus_log::LogSources::CodeGenerator,
+                        nexus_log::LogSource::CODE_GENERATOR,
                         format!("Backpatching 0x{:02X} for variable placeholder {} at memory location 0x{:02X}", new_addr, offset, i)
                     );
 
@@ -460,7 +776,7 @@ impl CodeGenerator6502 {
 
                     nexus_log::log(
                         nexus_log::LogTypes::Debug,
-                        nexus_log::LogSources::CodeGenerator,
+                        nexus_log::LogSource::CODE_GENERATOR,
                         format!("Backpatching 0x{:02X} for high order byte placeholder at memory location 0x{:02X}", new_high, i + 1)
                     );
 
@@ -472,7 +788,7 @@ impl CodeGenerator6502 {
                     
                     nexus_log::log(
                         nexus_log::LogTypes::Debug,
-                        nexus_log::LogSources::CodeGenerator,
+                        nexus_log::LogSource::CODE_GENERATOR,
                         format!("Backpatching 0x{:02X} for temp data placeholder {} at memory location 0x{:02X}", new_addr, offset, i)
                     );
 
@@ -484,7 +800,7 @@ impl CodeGenerator6502 {
 
                     nexus_log::log(
                         nexus_log::LogTypes::Debug,
-                        nexus_log::LogSources::CodeGenerator,
+                        nexus_log::LogSource::CODE_GENERATOR,
                         format!("Backpatching 0x{:02X} for high order byte placeholder at memory location 0x{:02X}", new_high, i + 1)
                     );
 
@@ -494,7 +810,7 @@ impl CodeGenerator6502 {
                 CodeGenBytes::Jump(jump_index) => {
                     nexus_log::log(
                         nexus_log::LogTypes::Debug,
-                        nexus_log::LogSources::CodeGenerator,
+                        nexus_log::LogSource::CODE_GENERATOR,
                         format!("Backpatching 0x{:02X} for jump placeholder {} at memory location 0x{:02X}", 
                                 self.jumps[*jump_index], *jump_index, i)
                     );
@@ -505,11 +821,143 @@ impl CodeGenerator6502 {
         }
     }
 
+    // Function to send every branch that lands on the unconditional jump at the end of a loop straight to where that jump goes
+    // This happens when an if or a while is the last statement of a loop, and it skips the 3 instructions of the jump every time around the loop
+    fn thread_branches(&mut self) {
+        // A thread can lead to another unconditional jump, so keep going until nothing changes
+        // Every pass follows at least one more jump, so there cannot be more passes than jumps
+        for _ in 0..self.jumps.len() {
+            let mut is_changed: bool = false;
+
+            for i in 0..self.code_arr.len() {
+                let jump_index: usize = match &self.code_arr[i] {
+                    CodeGenBytes::Jump(jump_index) => *jump_index,
+                    _ => continue
+                };
+
+                // Offsets are from the end of the branch and wrap around memory
+                let target: u8 = (i as u8).wrapping_add(1).wrapping_add(self.jumps[jump_index]);
+                let new_target: u8 = match self.get_unconditional_jump_target(target) {
+                    Some(new_target) if new_target != target => new_target,
+                    _ => continue
+                };
+
+                nexus_log::log(
+                    nexus_log::LogTypes::Debug,
+                    nexus_log::LogSource::CODE_GENERATOR,
+                    format!("Threading the branch at memory location 0x{:02X} from 0x{:02X} to 0x{:02X}", i - 1, target, new_target)
+                );
+                self.jumps[jump_index] = new_target.wrapping_sub((i as u8).wrapping_add(1));
+                is_changed = true;
+            }
+
+            if !is_changed {
+                return;
+            }
+        }
+    }
+
+    // Function to get where the code at the address goes if it is the unconditional jump at the end of a loop (LDX #$01, CPX $00FF, BNE)
+    // Nothing after the jump uses X, so skipping the LDX does not change what the program does
+    fn get_unconditional_jump_target(&self, addr: u8) -> Option<u8> {
+        let addr: usize = addr as usize;
+        if addr + 7 > self.code_arr.len() {
+            return None;
+        }
+
+        return match &self.code_arr[addr..addr + 7] {
+            [CodeGenBytes::Code(ldx), CodeGenBytes::Code(0x01), CodeGenBytes::Code(cpx), CodeGenBytes::Code(0xFF), CodeGenBytes::Code(0x00), CodeGenBytes::Code(bne), CodeGenBytes::Jump(jump_index)]
+                if *ldx == Opcode6502::LdxImmediate as u8 && *cpx == Opcode6502::CpxAbsolute as u8 && *bne == Opcode6502::Bne as u8 => {
+                Some((addr as u8).wrapping_add(7).wrapping_add(self.jumps[*jump_index]))
+            },
+            _ => None
+        };
+    }
+
+    // Function to check the image after backpatching and return a description of each problem
+    //  |  Code  |  Vars  ||  Temp  |  Heap  | Reserved
+    fn verify_image(&self) -> Vec<String> {
+        let mut problems: Vec<String> = Vec::new();
+
+        let memory_layout: MemoryLayout = self.get_memory_layout();
+        let code_end: usize = memory_layout.code_size;
+        let heap_start: usize = memory_layout.get_heap_start().unwrap_or(0);
+
+        // The static data is the last region to be placed
+        match memory_layout.find_overflow(MemoryRegion::Static) {
+            Some(overflow_message) => problems.push(overflow_message),
+            None => {}
+        }
+
+        // The last instruction is always the HALT
+        match (code_end, &self.code_arr[code_end.max(1) - 1]) {
+            (1.., CodeGenBytes::Code(0x00)) => {},
+            _ => problems.push(format!("The code does not end with a HALT (0x00)"))
+        }
+
+        for (i, byte) in self.code_arr.iter().enumerate() {
+            let problem: Option<String> = match byte {
+                CodeGenBytes::Var(_) | CodeGenBytes::Temp(_) | CodeGenBytes::Jump(_) | CodeGenBytes::HighOrderByte => {
+                    Some(format!("Placeholder {:?} at memory location 0x{:02X} was never backpatched", byte, i))
+                },
+                CodeGenBytes::Code(_) if i >= code_end => Some(format!("Code byte at memory location 0x{:02X} is past the end of the code", i)),
+                CodeGenBytes::Data(_) if i < heap_start || i > self.heap_top as usize => Some(format!("Heap byte at memory location 0x{:02X} is outside of the heap", i)),
+                CodeGenBytes::Empty if i < code_end => Some(format!("Memory location 0x{:02X} in the code was never filled", i)),
+                CodeGenBytes::Empty if i >= heap_start && i <= self.heap_top as usize => Some(format!("Memory location 0x{:02X} in the heap was never filled", i)),
+                _ => None
+            };
+
+            if problem.is_some() {
+                problems.push(problem.unwrap());
+            }
+        }
+
+        return problems;
+    }
+
+    // Function to check if a statement is the initializer of the declaration before it
+    // and does not read the declared variable, so the default value is never seen
+    fn is_overwriting_initializer(ast: &SyntaxTree, decl_index: NodeIndex, next_index: NodeIndex) -> bool {
+        match (*ast).graph.node_weight(next_index).unwrap() {
+            SyntaxTreeNode::NonTerminalAst(NonTerminalsAst::Assign) => {},
+            _ => return false
+        }
+
+        let decl_children: Vec<NodeIndex> = (*ast).graph.neighbors(decl_index).collect();
+        let assign_children: Vec<NodeIndex> = (*ast).graph.neighbors(next_index).collect();
+
+        match ((*ast).graph.node_weight(decl_children[0]).unwrap(), (*ast).graph.node_weight(assign_children[1]).unwrap()) {
+            // Initializers reuse the token of the declared id, so the positions match
+            (SyntaxTreeNode::Terminal(decl_token), SyntaxTreeNode::Terminal(assign_token)) => {
+                if decl_token.position != assign_token.position {
+                    return false;
+                }
+                return !Self::expr_uses_id(ast, assign_children[0], &decl_token.text);
+            },
+            _ => return false
+        }
+    }
+
+    // Function to check if an expression reads the given identifier
+    fn expr_uses_id(ast: &SyntaxTree, cur_index: NodeIndex, id: &str) -> bool {
+        match (*ast).graph.node_weight(cur_index).unwrap() {
+            SyntaxTreeNode::Terminal(token) => {
+                return match &token.token_type {
+                    TokenType::Identifier(_) => token.text.eq(id),
+                    _ => false
+                };
+            },
+            _ => {
+                return (*ast).graph.neighbors(cur_index).any(|child_index| Self::expr_uses_id(ast, child_index, id));
+            }
+        }
+    }
+
     // Function for creating the code for a variable declaration
-    fn code_gen_var_decl(&mut self, ast: &SyntaxTree, cur_index: NodeIndex, symbol_table: &mut SymbolTable) -> bool {
+    fn code_gen_var_decl(&mut self, ast: &SyntaxTree, cur_index: NodeIndex, symbol_table: &mut SymbolTable, skip_default: bool) -> bool {
         nexus_log::log(
             nexus_log::LogTypes::Debug,
-            nexus_log::LogSources::CodeGenerator,
+            nexus_log::LogSource::CODE_GENERATOR,
             format!("Starting code generation for variable declaration statement in scope {}", symbol_table.cur_scope.unwrap())
         );
 
@@ -520,23 +968,38 @@ impl CodeGenerator6502 {
             SyntaxTreeNode::Terminal(token) => {
                 // Get the offset this variable will be on the stack
                 let static_offset: usize = self.static_table.len();
-                self.static_table.insert((token.text.to_owned(), symbol_table.cur_scope.unwrap()), static_offset);
+                self.static_table.insert(StaticVariable::Declaration(*self.decl_ids.get(&children[0]).unwrap()), static_offset);
 
                 // Get the symbol table entry to get the type of the variable
                 let symbol_table_entry: &SymbolTableEntry = symbol_table.get_symbol_with_context(&token.text, token.position).unwrap();
                 match symbol_table_entry.symbol_type {
-                    // Only integers and booleans are initialized
+                    // The initializer right after will set the value
+                    Type::Int | Type::Boolean | Type::Char | Type::String if skip_default => {
+                        nexus_log::log(
+                            nexus_log::LogTypes::Debug,
+                            nexus_log::LogSource::CODE_GENERATOR,
+                            format!("Skipping the default value for [ {} ] because its initializer immediately overwrites it", token.text)
+                        );
+                    },
+                    // Integers and booleans start as 0
                     Type::Int | Type::Boolean => {
                         // Generate the code for the variable declaration
-                        if !self.add_code(0xA9) { return false; }
-                        if !self.add_code(0x00) { return false; }
-                        if !self.add_code(0x8D) { return false; }
-                        if !self.add_var(static_offset) { return false; }
+                        if !self.emit_lda_imm(0x00) { return false; }
+                        if !self.emit_sta_abs(Address::Static(static_offset)) { return false; }
                     },
-                    // Strings do not get initialized
+                    // Chars start as a space so printing one always shows something before the new line
+                    Type::Char => {
+                        if !self.emit_lda_imm(b' ') { return false; }
+                        if !self.emit_sta_abs(Address::Static(static_offset)) { return false; }
+                    },
+                    // Strings start as the empty string, which is shared with every other empty string on the heap
                     Type::String => {
-                        // Nothing to do here, so may end up initially with dirty data
-                        // from temp values
+                        let empty_string_addr: Option<u8> = self.store_string("");
+                        if empty_string_addr.is_none() {
+                            return false;
+                        }
+                        if !self.emit_lda_imm(empty_string_addr.unwrap()) { return false; }
+                        if !self.emit_sta_abs(Address::Static(static_offset)) { return false; }
                     }
                 }
             },
@@ -550,7 +1013,7 @@ impl CodeGenerator6502 {
     fn code_gen_assignment(&mut self, ast: &SyntaxTree, cur_index: NodeIndex, symbol_table: &mut SymbolTable) -> bool {
         nexus_log::log(
             nexus_log::LogTypes::Debug,
-            nexus_log::LogSources::CodeGenerator,
+            nexus_log::LogSource::CODE_GENERATOR,
             format!("Starting code generation for assignment statement in scope {}", symbol_table.cur_scope.unwrap())
         );
 
@@ -559,49 +1022,9 @@ impl CodeGenerator6502 {
         let id_node: &SyntaxTreeNode = (*ast).graph.node_weight(children[1]).unwrap();
 
         match value_node {
-            SyntaxTreeNode::Terminal(token) => {
-                match &token.token_type {
-                    TokenType::Identifier(_) => {
-                        let value_id_entry: &SymbolTableEntry = symbol_table.get_symbol_with_context(&token.text, token.position).unwrap(); 
-                        let value_static_offset: usize = self.static_table.get(&(token.text.to_owned(), value_id_entry.scope)).unwrap().to_owned();
-                        
-                        if !self.add_code(0xAD) { return false; }
-                        if !self.add_var(value_static_offset) { return false; }
-                    },
-                    TokenType::Digit(val) => {
-                        // Digits just load a constant to the accumulator
-                        if !self.add_code(0xA9) { return false; }
-                        if !self.add_code(*val as u8) { return false; }
-                    },
-                    TokenType::Char(string) => {
-                        // Start by storing the string
-                        let addr: Option<u8> = self.store_string(&string);
-
-                        // Store the starting address of the string in memory
-                        if addr.is_some() {
-                            if !self.add_code (0xA9) { return false; }
-                            if !self.add_code(addr.unwrap()) { return false; }
-                        } else {
-                            return false;
-                        }
-                    },
-                    TokenType::Keyword(keyword) => {
-                        match &keyword {
-                            Keywords::True => {
-                                // True is 0x01
-                                if !self.add_code(0xA9) { return false; }
-                                if !self.add_code(0x01) { return false; }
-                            },
-                            Keywords::False => {
-                                // False is 0x00
-                                if !self.add_code(0xA9) { return false; }
-                                if !self.add_code(0x00) { return false; }
-                            },
-                            _ => error!("Received {:?} when expecting true or false for keyword terminals in assignment", keyword)
-                        }
-                    },
-                    _ => error!("Received {:?} for terminal in assignment when expecting id, digit, char, or keyword", token)
-                }
+            SyntaxTreeNode::Terminal(_) => {
+                // Every type of value is loaded into the accumulator
+                if !self.load_operand(ast, children[0], Register::Acc) { return false; }
             },
             SyntaxTreeNode::NonTerminalAst(non_terminal) => {
                 match non_terminal {
@@ -617,6 +1040,12 @@ impl CodeGenerator6502 {
                         if !self.code_gen_compare(ast, children[0], symbol_table, false) { return false; }
                         if !self.get_z_flag_value() { return false; }
                     },
+                    NonTerminalsAst::Index => {
+                        if !self.code_gen_index(ast, children[0], symbol_table) { return false; }
+                    },
+                    NonTerminalsAst::Len => {
+                        if !self.code_gen_len(ast, children[0], symbol_table) { return false; }
+                    },
                     _ => error!("Received {:?} for nonterminal on right side of assignment for code gen", non_terminal)
                 }
             },
@@ -624,15 +1053,13 @@ impl CodeGenerator6502 {
         }
 
         match id_node {
-            SyntaxTreeNode::Terminal(token) => {
+            SyntaxTreeNode::Terminal(_) => {
                 // Get the static offset for the variable being assigned to
-                let id_entry: &SymbolTableEntry = symbol_table.get_symbol_with_context(&token.text, token.position).unwrap(); 
-                let static_offset = self.static_table.get(&(token.text.to_owned(), id_entry.scope)).unwrap().to_owned();
+                let static_offset: usize = self.get_static_offset(children[1]);
                 
                 // The data that we are storing is already in the accumulator
                 // so just run the code to store the data
-                if !self.add_code(0x8D) { return false; }
-                if !self.add_var(static_offset) { return false; }
+                if !self.emit_sta_abs(Address::Static(static_offset)) { return false; }
             },
             _ => error!("Received {:?} when expecting terminal for assignmentchild in code gen", id_node)
         }
@@ -644,7 +1071,7 @@ impl CodeGenerator6502 {
     fn code_gen_print(&mut self, ast: &SyntaxTree, cur_index: NodeIndex, symbol_table: &mut SymbolTable) -> bool {
         nexus_log::log(
             nexus_log::LogTypes::Debug,
-            nexus_log::LogSources::CodeGenerator,
+            nexus_log::LogSource::CODE_GENERATOR,
             format!("Starting code generation for print statement in scope {}", symbol_table.cur_scope.unwrap())
         );
 
@@ -654,105 +1081,32 @@ impl CodeGenerator6502 {
 
         match child {
             SyntaxTreeNode::Terminal(token) => {
-                match &token.token_type {
-                    TokenType::Identifier(id_name) => {
-                        let print_id: &SymbolTableEntry = symbol_table.get_symbol_with_context(&id_name, token.position).unwrap();
-                        let static_offset: usize = self.static_table.get(&(id_name.to_owned(), print_id.scope)).unwrap().to_owned();
-                        match &print_id.symbol_type {
-                            Type::Int  => {
-                                // Load the integer value into the Y register
-                                if !self.add_code(0xAC) { return false; }
-                                if !self.add_var(static_offset) { return false; }
-
-                                // Set X to 1 for the system call
-                                if !self.add_code(0xA2) { return false; }
-                                if !self.add_code(0x01) { return false; }
-                            },
-                            Type::String => {
-                                // Store the string address in Y
-                                if !self.add_code(0xAC) { return false; }
-                                if !self.add_var(static_offset) { return false; }
-
-                                // X = 2 for this sys call
-                                if !self.add_code(0xA2) { return false; }
-                                if !self.add_code(0x02) { return false; }
-                            },
-                            Type::Boolean => {
-                                // Compare the value of the variable with true
-                                if !self.add_code(0xA2) { return false; }
-                                if !self.add_code(0x01) { return false; }
-                                if !self.add_code(0xEC) { return false; }
-                                if !self.add_var(static_offset) { return false; }
-                                // Skip to the false string if it is false
-                                if !self.add_code(0xD0) { return false; }
-                                if !self.add_code(0x07) { return false; }
-                                
-                                // Load the true string and skip over the false string
-                                if !self.add_code(0xA0) { return false; }
-                                if !self.add_code(*self.string_history.get("true").unwrap()) { return false; }
-                                if !self.add_code(0xEC) { return false; }
-                                if !self.add_code(0xFF) { return false; }
-                                if !self.add_code(0x00) { return false; }
-                                if !self.add_code(0xD0) { return false; }
-                                if !self.add_code(0x02) { return false; }
-                                // Load the false string
-                                if !self.add_code(0xA0) { return false; }
-                                if !self.add_code(*self.string_history.get("false").unwrap()) { return false; }
-
-                                // We are printing a string, so X = 2
-                                if !self.add_code(0xA2) { return false; }
-                                if !self.add_code(0x02) { return false; }
-                            }
-                        }
+                let print_type: Option<Type> = operand::get_operand_type(token, symbol_table);
+                match print_type {
+                    Some(Type::Boolean) => {
+                        if !self.code_gen_print_bool(ast, children[0], symbol_table) { return false; }
                     },
-                    TokenType::Digit(digit) => {
-                        // Sys call 1 for integers needs the number in Y
-                        if !self.add_code(0xA0) { return false; }
-                        if !self.add_code(*digit as u8) { return false; }
-
-                        // And X = 1
-                        if !self.add_code(0xA2) { return false; }
-                        if !self.add_code(0x01) { return false; }
-                    },
-                    TokenType::Char(string) => {
-                        // Store the string in memory and load its address to Y
-                        let addr: Option<u8> = self.store_string(&string);
-                        if addr.is_some() {
-                            if !self.add_code(0xA0) { return false; }
-                            if !self.add_code(addr.unwrap()) { return false; }
-                        } else {
-                            return false;
-                        }
-
-                        // X = 2 for a string sys call
-                        if !self.add_code(0xA2) { return false; }
-                        if !self.add_code(0x02) { return false; }
+                    Some(Type::Char) => {
+                        if !self.code_gen_print_char(ast, children[0], symbol_table) { return false; }
                     },
-                    TokenType::Keyword(keyword) => {
-                        if !self.add_code(0xA0) { return false; }
-                        match keyword {
-                            Keywords::True => {
-                                // Y = true addr for true
-                                if !self.add_code(*self.string_history.get("true").unwrap()) { return false; }
-                            },
-                            Keywords::False => {
-                                // Y = false addr for false
-                                if !self.add_code(*self.string_history.get("false").unwrap()) { return false; }
-                            },
-                            _ => error!("Received {:?} when expecting true or false for print keyword", keyword)
-                        }
-                        // X = 2 for the sys call
-                        if !self.add_code(0xA2) { return false; }
-                        if !self.add_code(0x02) { return false; }
+                    Some(print_type) => {
+                        // Ints and strings are both printed from the same register
+                        if !self.load_operand(ast, children[0], self.get_print_register()) { return false; }
+                        if !self.code_gen_print_call(&print_type) { return false; }
                     },
-                    _ => error!("Received {:?} when expecting id, digit, string, or keyword for print terminal", token)
+                    None => error!("Received {:?} when expecting id, digit, string, or keyword for print terminal", token)
                 }
             },
             SyntaxTreeNode::NonTerminalAst(non_terminal) => {
                 match non_terminal {
-                    NonTerminalsAst::Add => {
-                        // Generate the result of the addition expression
-                        if !self.code_gen_add(ast, children[0], symbol_table, true) { return false; }
+                    NonTerminalsAst::Add | NonTerminalsAst::Len => {
+                        // Generate the result of the int expression
+                        if !self.code_gen_expr_value(ast, children[0], symbol_table) { return false; }
+
+                        // The output device is stored to from the accumulator, which already has the result
+                        if self.mmio.is_some() {
+                            return self.code_gen_print_call(&Type::Int);
+                        }
 
                         let temp_addr_option: Option<usize> = self.new_temp();
                         if temp_addr_option.is_none() {
@@ -760,234 +1114,444 @@ impl CodeGenerator6502 {
                         }
                         let temp_addr: usize = temp_addr_option.unwrap();
 
-                        if !self.add_code(0x8D) { return false; }
-                        if !self.add_temp(temp_addr) { return false; }
+                        if !self.emit_sta_abs(Address::Temp(temp_addr)) { return false; }
                         
                         // Load the result to Y (wish there was TAY)
-                        if !self.add_code(0xAC) { return false; }
-                        if !self.add_temp(temp_addr) { return false; }
+                        if !self.emit_ldy_abs(Address::Temp(temp_addr)) { return false; }
                         
                         // We are done with the temp data
                         self.temp_index -= 1;
 
-                        // X = 1 for the sys call for integers
-                        if !self.add_code(0xA2) { return false; }
-                        if !self.add_code(0x01) { return false; }
+                        if !self.code_gen_print_call(&Type::Int) { return false; }
                     },
-                    NonTerminalsAst::IsEq => {
-                        // If it is true or false is in the Z flag
-                        if !self.code_gen_compare(ast, children[0], symbol_table, true) { return false; }
-
-                        // We are printing a string, so X = 2
-                        if !self.add_code(0xA2) { return false; }
-                        if !self.add_code(0x02) { return false; }
-
-                        // Skip to the false string if it is false
-                        if !self.add_code(0xD0) { return false; }
-                        if !self.add_code(0x07) { return false; }
-                        
-                        // Load the true string and skip over the false string
-                        if !self.add_code(0xA0) { return false; }
-                        if !self.add_code(*self.string_history.get("true").unwrap()) { return false; }
-                        if !self.add_code(0xEC) { return false; }
-                        if !self.add_code(0xFF) { return false; }
-                        if !self.add_code(0x00) { return false; }
-                        if !self.add_code(0xD0) { return false; }
-                        if !self.add_code(0x02) { return false; }
-
-                        // Load the false string
-                        if !self.add_code(0xA0) { return false; }
-                        if !self.add_code(*self.string_history.get("false").unwrap()) { return false; }
+                    NonTerminalsAst::IsEq | NonTerminalsAst::NotEq => {
+                        if !self.code_gen_print_bool(ast, children[0], symbol_table) { return false; }
                     },
-                    NonTerminalsAst::NotEq => {
-                        if !self.code_gen_compare(ast, children[0], symbol_table, false) { return false; }
-                         // We are printing a string, so X = 2
-                        if !self.add_code(0xA2) { return false; }
-                        if !self.add_code(0x02) { return false; }
-
-                        // Skip to the false string if it is false
-                        if !self.add_code(0xD0) { return false; }
-                        if !self.add_code(0x07) { return false; }
-                        
-                        // Load the true string and skip over the false string
-                        if !self.add_code(0xA0) { return false; }
-                        if !self.add_code(*self.string_history.get("true").unwrap()) { return false; }
-                        if !self.add_code(0xEC) { return false; }
-                        if !self.add_code(0xFF) { return false; }
-                        if !self.add_code(0x00) { return false; }
-                        if !self.add_code(0xD0) { return false; }
-                        if !self.add_code(0x02) { return false; }
-
-                        // Load the false string
-                        if !self.add_code(0xA0) { return false; }
-                        if !self.add_code(*self.string_history.get("false").unwrap()) { return false; }
-                   },
-                    _ => error!("Received {:?} when expecting addition or boolean expression for nonterminal print", non_terminal)
+                    NonTerminalsAst::Index => {
+                        if !self.code_gen_print_char(ast, children[0], symbol_table) { return false; }
+                    },
+                    _ => error!("Received {:?} when expecting addition, length, boolean, or index expression for nonterminal print", non_terminal)
                 }
             },
             _ => error!("Received {:?} when expecting terminal or AST nonterminal for print in code gen", child)
         }
 
-        // The x and y registers are all set up, so just add the sys call
-        if !self.add_code(0xFF) { return false; }
         return true;
     }
 
-    // Function to generate code for an addition statement
+    // Function to print a boolean literal, variable, or comparison
+    // Every form gets its value into the Z flag for code_gen_print_call, except literals whose string is already known
+    fn code_gen_print_bool(&mut self, ast: &SyntaxTree, bool_index: NodeIndex, symbol_table: &mut SymbolTable) -> bool {
+        match (*ast).graph.node_weight(bool_index).unwrap() {
+            SyntaxTreeNode::Terminal(token) => {
+                match &token.token_type {
+                    TokenType::Keyword(keyword) => {
+                        // The value is already known, so just load the address of its string into Y
+                        let bool_string_addr: u8 = match self.store_bool_string(keyword.eq(&Keywords::True)) {
+                            Some(addr) => addr,
+                            None => return false
+                        };
+                        if !self.emit_load_imm(self.get_print_register(), bool_string_addr) { return false; }
+                        // The string already ends with the new line
+                        return self.code_gen_print_value(&Type::String);
+                    },
+                    TokenType::Identifier(_) => {
+                        // Compare the value of the variable with true to get it in the Z flag
+                        let static_offset: usize = self.get_static_offset(bool_index);
+                        if !self.emit_ldx_imm(0x01) { return false; }
+                        if !self.emit_cpx_abs(Address::Static(static_offset)) { return false; }
+                    },
+                    _ => error!("Received {:?} when expecting an id or keyword for boolean print", token)
+                }
+            },
+            SyntaxTreeNode::NonTerminalAst(NonTerminalsAst::IsEq) => {
+                if !self.code_gen_compare(ast, bool_index, symbol_table, true) { return false; }
+            },
+            SyntaxTreeNode::NonTerminalAst(NonTerminalsAst::NotEq) => {
+                if !self.code_gen_compare(ast, bool_index, symbol_table, false) { return false; }
+            },
+            node => error!("Received {:?} when expecting a boolean for print", node)
+        }
+
+        return self.code_gen_print_call(&Type::Boolean);
+    }
+
+    // Function to print a char literal or variable
+    // There is no system call for a single character, so chars are printed as a string that already ends with the new line
+    fn code_gen_print_char(&mut self, ast: &SyntaxTree, char_index: NodeIndex, symbol_table: &mut SymbolTable) -> bool {
+        let token: &Token = match (*ast).graph.node_weight(char_index).unwrap() {
+            SyntaxTreeNode::Terminal(token) => token,
+            SyntaxTreeNode::NonTerminalAst(NonTerminalsAst::Index) => {
+                let buffer_addr: u8 = match self.get_char_buffer() {
+                    Some(addr) => addr,
+                    None => return false
+                };
+
+                // Copy the indexed character into the first byte of the buffer
+                if !self.code_gen_index(ast, char_index, symbol_table) { return false; }
+                if !self.emit_sta_abs(Address::Fixed(buffer_addr as u16)) { return false; }
+                if !self.emit_load_imm(self.get_print_register(), buffer_addr) { return false; }
+                return self.code_gen_print_value(&Type::Char);
+            },
+            node => {
+                error!("Received {:?} when expecting a char for print", node);
+                return false;
+            }
+        };
+
+        let string_addr: u8 = match &token.token_type {
+            TokenType::CharLiteral(c) => {
+                // The value is already known, so it can be stored like any other string
+                match self.store_string(&format!("{}\n", c)) {
+                    Some(addr) => addr,
+                    None => return false
+                }
+            },
+            TokenType::Identifier(_) => {
+                let buffer_addr: u8 = match self.get_char_buffer() {
+                    Some(addr) => addr,
+                    None => return false
+                };
+
+                // Copy the value of the variable into the first byte of the buffer
+                let static_offset: usize = self.get_static_offset(char_index);
+                if !self.emit_lda_abs(Address::Static(static_offset)) { return false; }
+                if !self.emit_sta_abs(Address::Fixed(buffer_addr as u16)) { return false; }
+                buffer_addr
+            },
+            _ => {
+                error!("Received {:?} when expecting an id or char literal for char print", token);
+                return false;
+            }
+        };
+
+        if !self.emit_load_imm(self.get_print_register(), string_addr) { return false; }
+        return self.code_gen_print_value(&Type::Char);
+    }
+
+    // Function to get the address of the string that chars are copied into to be printed
+    // The buffer is only stored once the program prints a char variable and is never reused for string literals
+    fn get_char_buffer(&mut self) -> Option<u8> {
+        if self.char_buffer.is_none() {
+            // The first byte gets overwritten before every print, so it is a space until then
+            for data in [0x00, b'\n', b' '] {
+                if !self.add_data(data) {
+                    return None;
+                }
+            }
+            self.char_buffer = Some(self.heap_pointer + 1);
+        }
+        return self.char_buffer;
+    }
+
+    // Function to get the register that values are printed from
+    // The system calls print from Y, but the output device can only be stored to from the accumulator
+    fn get_print_register(&self) -> Register {
+        return match self.mmio {
+            Some(_) => Register::Acc,
+            None => Register::Y
+        };
+    }
+
+    // Function to print a value of the given type and end the print with a new line
+    // Ints and strings have to already be in the print register, and booleans have to be in the Z flag
+    fn code_gen_print_call(&mut self, print_type: &Type) -> bool {
+        if !self.code_gen_print_value(print_type) { return false; }
+        return self.code_gen_print_new_line(print_type);
+    }
+
+    // Function to make the print system call for a value of the given type
+    fn code_gen_print_value(&mut self, print_type: &Type) -> bool {
+        if let Some(mmio) = self.mmio {
+            return self.code_gen_print_mmio(print_type, mmio);
+        }
+
+        match print_type {
+            Type::Int => {
+                // X = 1 for the sys call for integers, which prints Y in decimal without leading zeros
+                if !self.emit_ldx_imm(0x01) { return false; }
+            },
+            // Chars are printed as their string
+            Type::String | Type::Char => {
+                // X = 2 for the sys call for strings
+                if !self.emit_ldx_imm(0x02) { return false; }
+            },
+            Type::Boolean => {
+                // Booleans are printed as the string for their value
+                // X has to be set first because load_bool_string jumps by comparing X with the 0 at $00FF
+                if !self.emit_ldx_imm(0x02) { return false; }
+                if !self.load_bool_string() { return false; }
+            }
+        }
+
+        // The x and y registers are all set up, so just add the sys call
+        if !self.emit_sys() { return false; }
+        return true;
+    }
+
+    // Function to end a print with a new line like every other backend
+    // The new line is a string on the heap that is only stored once the program prints an int or string
+    fn code_gen_print_new_line(&mut self, print_type: &Type) -> bool {
+        // The strings for booleans and chars already end with a new line
+        if let Type::Boolean | Type::Char = print_type {
+            return true;
+        }
+
+        let new_line_addr: u8 = match self.store_string("\n") {
+            Some(addr) => addr,
+            None => return false
+        };
+
+        match self.mmio {
+            Some(mmio) => {
+                if !self.emit_lda_imm(new_line_addr) { return false; }
+                if !self.emit_sta_abs(Address::Fixed(mmio.string_out as u16)) { return false; }
+            },
+            None => {
+                // X = 2 for the sys call for strings
+                if !self.emit_ldx_imm(0x02) { return false; }
+                if !self.emit_load_imm(Register::Y, new_line_addr) { return false; }
+                if !self.emit_sys() { return false; }
+            }
+        }
+        return true;
+    }
+
+    // Function to print a value by storing it to the output device instead of making a system call
+    fn code_gen_print_mmio(&mut self, print_type: &Type, mmio: MmioConfig) -> bool {
+        let port: usize = match print_type {
+            Type::Int => mmio.int_out,
+            Type::String | Type::Char => mmio.string_out,
+            Type::Boolean => {
+                // The unoptimized jump compares X with the 0 at $00FF, so X cannot be 0
+                if !self.optimize_size && !self.emit_ldx_imm(0x02) { return false; }
+                if !self.load_bool_string() { return false; }
+                mmio.string_out
+            }
+        };
+
+        if !self.emit_sta_abs(Address::Fixed(port as u16)) { return false; }
+        return true;
+    }
+
+    // Function to load the value of a terminal into the register
+    // This is the number for ints, the heap address for strings, 1 or 0 for booleans, and the ASCII code for chars
+    fn load_operand(&mut self, ast: &SyntaxTree, operand_index: NodeIndex, register: Register) -> bool {
+        let token: &Token = match (*ast).graph.node_weight(operand_index).unwrap() {
+            SyntaxTreeNode::Terminal(token) => token,
+            operand => {
+                error!("Received {:?} when expecting a terminal operand", operand);
+                return false;
+            }
+        };
+
+        match &token.token_type {
+            TokenType::Identifier(_) => {
+                // Every type of variable takes up a single byte in static memory
+                let static_offset: usize = self.get_static_offset(operand_index);
+                if !self.emit_load_abs(register, Address::Static(static_offset)) { return false; }
+            },
+            TokenType::Digit(num) => {
+                if !self.emit_load_imm(register, *num) { return false; }
+            },
+            TokenType::Char(string) => {
+                // Strings are stored on the heap and their address is the value
+                let addr: Option<u8> = self.store_string(&string);
+                if addr.is_none() {
+                    return false;
+                }
+                if !self.emit_load_imm(register, addr.unwrap()) { return false; }
+            },
+            TokenType::CharLiteral(c) => {
+                if !self.emit_load_imm(register, *c as u8) { return false; }
+            },
+            TokenType::Keyword(keyword) => {
+                // True is 0x01 and false is 0x00
+                let value: u8 = match &keyword {
+                    Keywords::True => 0x01,
+                    Keywords::False => 0x00,
+                    _ => {
+                        error!("Received {:?} when expecting true or false for keyword operand", keyword);
+                        return false;
+                    }
+                };
+                if !self.emit_load_imm(register, value) { return false; }
+            },
+            _ => error!("Received {:?} when expecting an id, digit, string, or keyword operand", token)
+        }
+
+        return true;
+    }
+
+    // Function to get the static offset of the declaration that the id node refers to
+    fn get_static_offset(&self, id_index: NodeIndex) -> usize {
+        let decl_id: DeclId = *self.decl_ids.get(&id_index).unwrap();
+        return self.static_table.get(&StaticVariable::Declaration(decl_id)).unwrap().to_owned();
+    }
+
+    // Function to generate code for an addition statement
     // Result is left in the accumulator
     fn code_gen_add(&mut self, ast: &SyntaxTree, cur_index: NodeIndex, symbol_table: &mut SymbolTable, is_first: bool) -> bool {
         nexus_log::log(
             nexus_log::LogTypes::Debug,
-            nexus_log::LogSources::CodeGenerator,
+            nexus_log::LogSource::CODE_GENERATOR,
             format!("Starting code generation for addition expression in scope {}", symbol_table.cur_scope.unwrap())
         );
 
+        // The digits of the entire expression can be summed at compile time
+        // Lengths are only known at runtime, so expressions with them are added one operand at a time
+        if is_first && self.optimize_size && !Self::has_length_operand(ast, cur_index) {
+            return self.code_gen_folded_add(ast, cur_index);
+        }
+
         // Get the child for addition
         let children: Vec<NodeIndex> = (*ast).graph.neighbors(cur_index).collect();
-        let right_child: &SyntaxTreeNode = (*ast).graph.node_weight(children[0]).unwrap();
-        let left_child: &SyntaxTreeNode = (*ast).graph.node_weight(children[1]).unwrap();
 
-        // Make some space for the temporary data only if first addition
-        // Otherwise, use the current max temp index, which is the working temp location
-        let mut temp_addr: usize = self.temp_index - 1;
-        if is_first {
-            let temp_addr_option: Option<usize> = self.new_temp();
-            if temp_addr_option.is_none() {
-                return false;
-            }
-            temp_addr = temp_addr_option.unwrap();
+        // Additions are left associative, so the left side goes first and ends up in the accumulator
+        if !self.code_gen_add_operand(ast, children[1], symbol_table) { return false; }
+
+        // The left side does not need its temps anymore, so the sum only takes up 1 more
+        let temp_addr_option: Option<usize> = self.new_temp();
+        if temp_addr_option.is_none() {
+            return false;
         }
+        let temp_addr: usize = temp_addr_option.unwrap();
 
-        match right_child {
+        // Save the left side so the right side can be put in the accumulator
+        if !self.emit_sta_abs(Address::Temp(temp_addr)) { return false; }
+
+        if !self.code_gen_add_operand(ast, children[0], symbol_table) { return false; }
+
+        // Perform the addition
+        if !self.emit_adc_abs(Address::Temp(temp_addr)) { return false; }
+
+        // We are done with the memory location, so can move
+        // the pointer back over 1
+        self.temp_index -= 1;
+        return true;
+    }
+
+    // Function to put an operand of an addition in the accumulator
+    fn code_gen_add_operand(&mut self, ast: &SyntaxTree, operand_index: NodeIndex, symbol_table: &mut SymbolTable) -> bool {
+        match (*ast).graph.node_weight(operand_index).unwrap() {
             SyntaxTreeNode::Terminal(token) => {
                 match &token.token_type {
-                    TokenType::Digit(num) => {
-                        // Store right side digit in the accumulator
-                        if !self.add_code(0xA9) { return false; }
-                        if !self.add_code(*num) { return false; }
-                    },
-                    TokenType::Identifier(_) => {
-                        // Get the address needed from memory for the identifier
-                        let value_id_entry: &SymbolTableEntry = symbol_table.get_symbol_with_context(&token.text, token.position).unwrap(); 
-                        let value_static_offset: usize = self.static_table.get(&(token.text.to_owned(), value_id_entry.scope)).unwrap().to_owned();
-                        
-                        // Load the value into the accumulator
-                        if !self.add_code(0xAD) { return false; }
-                        if !self.add_var(value_static_offset) { return false; }
-                    },
-                    _ => error!("Received {:?} when expecting digit or id for right side of addition", token)
+                    TokenType::Digit(_) | TokenType::Identifier(_) => return self.load_operand(ast, operand_index, Register::Acc),
+                    _ => error!("Received {:?} when expecting digit or id for addition", token)
                 }
-
-                // Both digits and ids are in the accumulator, so move them to
-                // the res address for usage in the math operation
-                if !self.add_code(0x8D) { return false; }
-                if !self.add_temp(temp_addr) { return false; }
-                // We are using a new temporary value for temps, so increment the index
             },
-            // Nonterminals are always add, so just call it
-            SyntaxTreeNode::NonTerminalAst(_) => if !self.code_gen_add(ast, children[0], symbol_table, false) { return false; },
-            _ => error!("Received {:?} when expecting terminal or AST nonterminal for right addition value", right_child)
+            SyntaxTreeNode::NonTerminalAst(NonTerminalsAst::Add) => return self.code_gen_add(ast, operand_index, symbol_table, false),
+            SyntaxTreeNode::NonTerminalAst(NonTerminalsAst::Len) => return self.code_gen_len(ast, operand_index, symbol_table),
+            operand => error!("Received {:?} when expecting terminal, addition, or length for addition operand", operand)
         }
+        return true;
+    }
 
-        match left_child {
-            SyntaxTreeNode::Terminal(token) => {
-                match &token.token_type {
-                    TokenType::Digit(num) => {
-                        // Put left digit in acc
-                        if !self.add_code(0xA9) { return false; }
-                        if !self.add_code(*num) { return false; }
-
-                        // Perform the addition
-                        if !self.add_code(0x6D) { return false; }
-                        if !self.add_temp(temp_addr) { return false; }
-
-                        // Only store the result back in memory if we have more addition to do
-                        if !is_first {
-                            // Store it back in the resulting address
-                            if !self.add_code(0x8D) { return false; }
-                            if !self.add_temp(temp_addr) { return false; }
-                        } else {
-                            // We are done with the memory location, so can move
-                            // the pointer back over 1
-                            self.temp_index -= 1;
-                        }
+    // Function to check if any operand of the addition expression is the length of a string
+    fn has_length_operand(ast: &SyntaxTree, cur_index: NodeIndex) -> bool {
+        return operand::get_add_operands(ast, cur_index).into_iter().any(|operand_index| matches!((*ast).graph.node_weight(operand_index).unwrap(), SyntaxTreeNode::NonTerminalAst(NonTerminalsAst::Len)));
+    }
+
+    // Function to generate code for an addition statement with all of the digits folded into 1 constant
+    // Result is left in the accumulator
+    fn code_gen_folded_add(&mut self, ast: &SyntaxTree, cur_index: NodeIndex) -> bool {
+        // The sum wraps around just like it would with ADC
+        let mut digit_sum: u8 = 0;
+        let mut ids: Vec<NodeIndex> = Vec::new();
+        let mut id_names: Vec<String> = Vec::new();
+        for operand_index in operand::get_add_operands(ast, cur_index) {
+            if let SyntaxTreeNode::Terminal(operand_token) = (*ast).graph.node_weight(operand_index).unwrap() {
+                match &operand_token.token_type {
+                    TokenType::Digit(num) => digit_sum = digit_sum.wrapping_add(*num),
+                    TokenType::Identifier(id_name) => {
+                        ids.push(operand_index);
+                        id_names.push(id_name.to_owned());
                     },
-                    _ => error!("Received {:?} when expecting a digit for left side of addition for code gen", token)
+                    _ => error!("Received {:?} when expecting digit or id for addition", operand_token)
                 }
-            },
-            _ => error!("Received {:?} when expecting a terminal for the left side of addition for code gen", left_child)
+            }
         }
 
+        if ids.len() == 0 {
+            // The entire expression is a constant
+            nexus_log::log(
+                nexus_log::LogTypes::Debug,
+                nexus_log::LogSource::CODE_GENERATOR,
+                format!("Folded constant addition expression to {}", digit_sum)
+            );
+
+            if !self.emit_lda_imm(digit_sum) { return false; }
+            return true;
+        }
+
+        nexus_log::log(
+            nexus_log::LogTypes::Debug,
+            nexus_log::LogSource::CODE_GENERATOR,
+            format!("Folded constant digits of addition expression to {} + {}", digit_sum, id_names.join(" + "))
+        );
+
+        let temp_addr_option: Option<usize> = self.new_temp();
+        if temp_addr_option.is_none() {
+            return false;
+        }
+        let temp_addr: usize = temp_addr_option.unwrap();
+
+        // Move the first id to temp memory so the sum can be added to it
+        if !self.load_operand(ast, ids[0], Register::Acc) { return false; }
+        if !self.emit_sta_abs(Address::Temp(temp_addr)) { return false; }
+
+        if !self.emit_lda_imm(digit_sum) { return false; }
+        if !self.emit_adc_abs(Address::Temp(temp_addr)) { return false; }
+
+        // The rest of the ids can be added straight from static memory
+        for id_index in ids.iter().skip(1) {
+            let static_offset: usize = self.get_static_offset(*id_index);
+            if !self.emit_adc_abs(Address::Static(static_offset)) { return false; }
+        }
+
+        // We are done with the temp data
+        self.temp_index -= 1;
         return true;
     }
 
     // Function to generate code for comparisons
     // Result is left in the Z flag and get_z_flag_vale function can be used
     // afterwards to place z flag value into the accumulator
+    // Strings are compared by address, which is the same as comparing their contents
+    // because each string literal is only stored once on the heap
     fn code_gen_compare(&mut self, ast: &SyntaxTree, cur_index: NodeIndex, symbol_table: &mut SymbolTable, is_eq: bool) -> bool {
         nexus_log::log(
             nexus_log::LogTypes::Debug,
-            nexus_log::LogSources::CodeGenerator,
+            nexus_log::LogSource::CODE_GENERATOR,
             format!("Starting code generation for comparison expression (is_eq = {}) in scope {}", is_eq, symbol_table.cur_scope.unwrap())
         );
 
+        // A variable compared with itself has a known result, so the Z flag can be set without loading either side
+        if self.optimize_size {
+            if let Some(value) = optimizer::fold_self_comparison(ast, cur_index) {
+                // The zero address always holds 0, so comparing it with 0 sets the Z flag and comparing it with 1 clears it
+                if !self.emit_ldx_imm(if value { 0x00 } else { 0x01 }) { return false; }
+                return self.emit_cpx_abs(Address::Fixed(ZERO_ADDRESS));
+            }
+        }
+
         // Get the child for comparison
         let children: Vec<NodeIndex> = (*ast).graph.neighbors(cur_index).collect();
         let right_child: &SyntaxTreeNode = (*ast).graph.node_weight(children[0]).unwrap();
         let left_child: &SyntaxTreeNode = (*ast).graph.node_weight(children[1]).unwrap();
 
         match left_child {
-            SyntaxTreeNode::Terminal(token) => {
-                match &token.token_type {
-                    TokenType::Identifier(_) => {
-                        // Get the address needed from memory for the identifier
-                        let value_id_entry: &SymbolTableEntry = symbol_table.get_symbol_with_context(&token.text, token.position).unwrap(); 
-                        let value_static_offset: usize = self.static_table.get(&(token.text.to_owned(), value_id_entry.scope)).unwrap().to_owned();
-                        
-                        // Load the value into the accumulator
-                        if !self.add_code(0xAD) { return false; }
-                        if !self.add_var(value_static_offset) { return false; }
-                    },
-                    TokenType::Digit(num) => {
-                        // Store the digit in memory
-                        if !self.add_code(0xA9) { return false; }
-                        if !self.add_code(*num) { return false; }
-                    },
-                    TokenType::Char(string) => {
-                        let string_addr: Option<u8> = self.store_string(string);
-                        if string_addr.is_some() {
-                            if !self.add_code(0xA9) { return false; }
-                            if !self.add_code(string_addr.unwrap()) { return false; }
-                        } else {
-                            return false;
-                        }
-                    },
-                    TokenType::Keyword(keyword) => {
-                        if !self.add_code(0xA9) { return false; }
-                        match &keyword {
-                            Keywords::True => if !self.add_code(0x01) { return false; },
-                            Keywords::False => if !self.add_code(0x00) { return false; },
-                            _ => error!("Received {:?} when expecting true or false for keywords in boolean expression", keyword)
-                        }
-                    },
-                    _ => error!("Received {:?} when expecting an Id, digit, char, or keyword for left side of boolean expression", token)
-                }
+            SyntaxTreeNode::Terminal(_) => {
+                // Load the left side into the accumulator
+                if !self.load_operand(ast, children[1], Register::Acc) { return false; }
             },
-            SyntaxTreeNode::NonTerminalAst(non_terminal) => {
-                match &non_terminal {
-                    NonTerminalsAst::Add => {
-                        if !self.code_gen_add(ast, children[1], symbol_table, true) { return false; }
-                    },
-                    NonTerminalsAst::IsEq => {
-                        if !self.code_gen_compare(ast, children[1], symbol_table, true) { return false; }
-                        if !self.get_z_flag_value() { return false; }
-                    },
-                    NonTerminalsAst::NotEq => {
-                        if !self.code_gen_compare(ast, children[1], symbol_table, false) { return false; }
-                        if !self.get_z_flag_value() { return false; }
-                    },
-                    _ => error!("Received {:?} for left side of nonterminal boolean expression, when expected Add, IsEq, or NotEq", non_terminal)
-                }
+            SyntaxTreeNode::NonTerminalAst(_) if self.hoisted_values.contains_key(&children[1]) => {
+                // The value was already computed before the loop, so just load it
+                let hoisted_offset: usize = self.hoisted_values.get(&children[1]).unwrap().to_owned();
+                if !self.emit_lda_abs(Address::Static(hoisted_offset)) { return false; }
+            },
+            SyntaxTreeNode::NonTerminalAst(_) => {
+                if !self.code_gen_expr_value(ast, children[1], symbol_table) { return false; }
             },
             _ => error!("Received {:?} when expected terminal or AST nonterminal for left side of comparison in code gen", left_child)
         }
@@ -999,61 +1563,20 @@ impl CodeGenerator6502 {
         }
         let left_temp: usize = left_temp_option.unwrap();
 
-        if !self.add_code(0x8D) { return false; }
-        if !self.add_temp(left_temp) { return false; }
+        if !self.emit_sta_abs(Address::Temp(left_temp)) { return false; }
 
         match right_child {
-            SyntaxTreeNode::Terminal(token) => {
-                match &token.token_type {
-                    TokenType::Identifier(_) => {
-                        // Get the address needed from memory for the identifier
-                        let value_id_entry: &SymbolTableEntry = symbol_table.get_symbol_with_context(&token.text, token.position).unwrap(); 
-                        let value_static_offset: usize = self.static_table.get(&(token.text.to_owned(), value_id_entry.scope)).unwrap().to_owned();
-                        
-                        // Load the value into the X register
-                        if !self.add_code(0xAE) { return false; }
-                        if !self.add_var(value_static_offset) { return false; }
-                    },
-                    TokenType::Digit(num) => {
-                        // Store the digit in X
-                        if !self.add_code(0xA2) { return false; }
-                        if !self.add_code(*num) { return false; }
-                    },
-                    TokenType::Char(string) => {
-                        let string_addr: Option<u8> = self.store_string(string);
-                        if string_addr.is_some() {
-                            if !self.add_code(0xA2) { return false; }
-                            if !self.add_code(string_addr.unwrap()) { return false; }
-                        } else {
-                            return false;
-                        }
-                    },
-                    TokenType::Keyword(keyword) => {
-                        if !self.add_code(0xA2) { return false; }
-                        match &keyword {
-                            Keywords::True => if !self.add_code(0x01) { return false; },
-                            Keywords::False => if !self.add_code(0x00) { return false; },
-                            _ => error!("Received {:?} when expecting true or false for keywords in boolean expression", keyword)
-                        }
-                    },
-                    _ => error!("Received {:?} when expecting an Id, digit, char, or keyword for left side of boolean expression", token)
-                }
+            SyntaxTreeNode::Terminal(_) => {
+                // Load the right side straight into X for the comparison
+                if !self.load_operand(ast, children[0], Register::X) { return false; }
             },
-            SyntaxTreeNode::NonTerminalAst(non_terminal) => {
-                match &non_terminal {
-                    NonTerminalsAst::Add => {
-                        if !self.code_gen_add(ast, children[0], symbol_table, true) { return false; }
-                    },
-                    NonTerminalsAst::IsEq => {
-                        if !self.code_gen_compare(ast, children[0], symbol_table, true) { return false; }
-                        if !self.get_z_flag_value() { return false; }
-                    },
-                    NonTerminalsAst::NotEq => {
-                        if !self.code_gen_compare(ast, children[0], symbol_table, false) { return false; }
-                        if !self.get_z_flag_value() { return false; }
-                    },
-                    _ => error!("Received {:?} for right side of nonterminal boolean expression, when expected Add, IsEq, or NotEq", non_terminal)
-                }
+            SyntaxTreeNode::NonTerminalAst(_) if self.hoisted_values.contains_key(&children[0]) => {
+                // The value was already computed before the loop, so load it straight into X
+                let hoisted_offset: usize = self.hoisted_values.get(&children[0]).unwrap().to_owned();
+                if !self.emit_ldx_abs(Address::Static(hoisted_offset)) { return false; }
+            },
+            SyntaxTreeNode::NonTerminalAst(_) => {
+                if !self.code_gen_expr_value(ast, children[0], symbol_table) { return false; }
 
                 // The nonterminal result is in the ACC, so have to move to X
                 let temp_addr_option: Option<usize> = self.new_temp();
@@ -1062,18 +1585,15 @@ impl CodeGenerator6502 {
                 }
                 let temp_addr: usize = temp_addr_option.unwrap();
 
-                if !self.add_code(0x8D) { return false; }
-                if !self.add_temp(temp_addr) { return false; }
+                if !self.emit_sta_abs(Address::Temp(temp_addr)) { return false; }
 
-                if !self.add_code(0xAE) { return false; }
-                if !self.add_temp(temp_addr) { return false; }
+                if !self.emit_ldx_abs(Address::Temp(temp_addr)) { return false; }
                 self.temp_index -= 1;
             },
-            _ => error!("Received {:?} when expected terminal or AST nonterminal for left side of comparison in code gen", left_child)
+            _ => error!("Received {:?} when expected terminal or AST nonterminal for right side of comparison in code gen", right_child)
         }
 
-        if !self.add_code(0xEC) { return false; }
-        if !self.add_temp(left_temp) { return false; }
+        if !self.emit_cpx_abs(Address::Temp(left_temp)) { return false; }
 
         // We are done with this data
         self.temp_index -= 1;
@@ -1082,18 +1602,229 @@ impl CodeGenerator6502 {
         // This effectively flips the Z flag
         if !is_eq {
             // Start assuming that they were not equal
-            if !self.add_code(0xA2) { return false; }
-            if !self.add_code(0x00) { return false; }
+            if !self.emit_ldx_imm(0x00) { return false; }
             // Take the branch if not equal
-            if !self.add_code(0xD0) { return false; }
-            if !self.add_code(0x02) { return false; }
+            if !self.emit_bne(Branch::Offset(0x02)) { return false; }
             // If equal, set x to 1
-            if !self.add_code(0xA2) { return false; }
-            if !self.add_code(0x01) { return false; }
+            if !self.emit_ldx_imm(0x01) { return false; }
             // Compare with 0 to flip the Z flag
-            if !self.add_code(0xEC) { return false; }
-            if !self.add_code(0xFF) { return false; }
-            if !self.add_code(0x00) { return false; }
+            if !self.emit_cpx_abs(Address::Fixed(ZERO_ADDRESS)) { return false; }
+        }
+
+        return true;
+    }
+
+    // Function to evaluate an addition or nested comparison and leave its value in the accumulator
+    fn code_gen_expr_value(&mut self, ast: &SyntaxTree, cur_index: NodeIndex, symbol_table: &mut SymbolTable) -> bool {
+        match (*ast).graph.node_weight(cur_index).unwrap() {
+            SyntaxTreeNode::NonTerminalAst(non_terminal) => {
+                match &non_terminal {
+                    NonTerminalsAst::Add => {
+                        if !self.code_gen_add(ast, cur_index, symbol_table, true) { return false; }
+                    },
+                    NonTerminalsAst::IsEq => {
+                        if !self.code_gen_compare(ast, cur_index, symbol_table, true) { return false; }
+                        if !self.get_z_flag_value() { return false; }
+                    },
+                    NonTerminalsAst::NotEq => {
+                        if !self.code_gen_compare(ast, cur_index, symbol_table, false) { return false; }
+                        if !self.get_z_flag_value() { return false; }
+                    },
+                    NonTerminalsAst::Index => {
+                        if !self.code_gen_index(ast, cur_index, symbol_table) { return false; }
+                    },
+                    NonTerminalsAst::Len => {
+                        if !self.code_gen_len(ast, cur_index, symbol_table) { return false; }
+                    },
+                    _ => error!("Received {:?} for nonterminal boolean expression operand, when expected Add, IsEq, NotEq, Index, or Len", non_terminal)
+                }
+            },
+            expr_node => error!("Received {:?} when expecting an AST nonterminal for an expression value", expr_node)
+        }
+
+        return true;
+    }
+
+    // Function to generate code for reading a character out of a string
+    // Result is left in the accumulator
+    fn code_gen_index(&mut self, ast: &SyntaxTree, cur_index: NodeIndex, symbol_table: &mut SymbolTable) -> bool {
+        nexus_log::log(
+            nexus_log::LogTypes::Debug,
+            nexus_log::LogSource::CODE_GENERATOR,
+            format!("Starting code generation for index expression in scope {}", symbol_table.cur_scope.unwrap())
+        );
+
+        // The string is the second child and the index is the first
+        let children: Vec<NodeIndex> = (*ast).graph.neighbors(cur_index).collect();
+
+        // Start with the index in the accumulator
+        match (*ast).graph.node_weight(children[0]).unwrap() {
+            SyntaxTreeNode::Terminal(_) => {
+                if !self.load_operand(ast, children[0], Register::Acc) { return false; }
+            },
+            SyntaxTreeNode::NonTerminalAst(_) => {
+                if !self.code_gen_expr_value(ast, children[0], symbol_table) { return false; }
+            },
+            index_node => error!("Received {:?} when expecting a terminal or AST nonterminal for the index", index_node)
+        }
+
+        // Add the heap address of the string to get the address of the character
+        match (*ast).graph.node_weight(children[1]).unwrap() {
+            SyntaxTreeNode::Terminal(token) => {
+                match &token.token_type {
+                    TokenType::Identifier(_) => {
+                        let static_offset: usize = self.get_static_offset(children[1]);
+                        if !self.emit_adc_abs(Address::Static(static_offset)) { return false; }
+                    },
+                    TokenType::Char(_) => {
+                        let temp_addr_option: Option<usize> = self.new_temp();
+                        if temp_addr_option.is_none() {
+                            return false;
+                        }
+                        let temp_addr: usize = temp_addr_option.unwrap();
+
+                        if !self.emit_sta_abs(Address::Temp(temp_addr)) { return false; }
+                        if !self.load_operand(ast, children[1], Register::Acc) { return false; }
+                        if !self.emit_adc_abs(Address::Temp(temp_addr)) { return false; }
+
+                        self.temp_index -= 1;
+                    },
+                    _ => error!("Received {:?} when expecting an id or string for the indexed string", token)
+                }
+            },
+            string_node => error!("Received {:?} when expecting a terminal for the indexed string", string_node)
+        }
+
+        return self.emit_indirect_load();
+    }
+
+    // Function to generate code for the length of a string
+    // Result is left in the accumulator
+    fn code_gen_len(&mut self, ast: &SyntaxTree, cur_index: NodeIndex, symbol_table: &mut SymbolTable) -> bool {
+        nexus_log::log(
+            nexus_log::LogTypes::Debug,
+            nexus_log::LogSource::CODE_GENERATOR,
+            format!("Starting code generation for length expression in scope {}", symbol_table.cur_scope.unwrap())
+        );
+
+        let string_index: NodeIndex = (*ast).graph.neighbors(cur_index).next().unwrap();
+        let token: &Token = match (*ast).graph.node_weight(string_index).unwrap() {
+            SyntaxTreeNode::Terminal(token) => token,
+            string_node => {
+                error!("Received {:?} when expecting a terminal for the length", string_node);
+                return false;
+            }
+        };
+
+        match &token.token_type {
+            // The length of a literal is already known
+            TokenType::Char(string) => return self.emit_lda_imm(string.len() as u8),
+            TokenType::Identifier(_) => {
+                let minus_one_addr: u8 = match self.get_minus_one() {
+                    Some(addr) => addr,
+                    None => return false
+                };
+
+                // Adding 0xFF wraps around to the byte before the string, which is its length
+                let static_offset: usize = self.get_static_offset(string_index);
+                if !self.emit_lda_abs(Address::Static(static_offset)) { return false; }
+                if !self.emit_adc_abs(Address::Fixed(minus_one_addr as u16)) { return false; }
+                return self.emit_indirect_load();
+            },
+            _ => error!("Received {:?} when expecting an id or string for the length", token)
+        }
+        return true;
+    }
+
+    // Function to get the address of the 0xFF on the heap, which is only stored once it is needed
+    fn get_minus_one(&mut self) -> Option<u8> {
+        if self.minus_one.is_none() {
+            if !self.add_data(0xFF) {
+                return None;
+            }
+            self.minus_one = Some(self.heap_pointer + 1);
+        }
+        return self.minus_one;
+    }
+
+    // Function to load the byte at the address in the accumulator into the accumulator
+    // There is no indexed addressing, so the address is written into the operand of the load that follows
+    fn emit_indirect_load(&mut self) -> bool {
+        // The store takes up 3 bytes, so the low order byte of the load's address comes 1 byte after that
+        let operand_addr: u16 = self.code_pointer as u16 + 4;
+        if !self.emit_sta_abs(Address::Fixed(operand_addr)) { return false; }
+        return self.emit_lda_abs(Address::Fixed(0x0000));
+    }
+
+    // Function to check if the program takes the length of a string variable anywhere in the subtree
+    fn has_variable_length(ast: &SyntaxTree, cur_index: NodeIndex) -> bool {
+        let children: Vec<NodeIndex> = (*ast).graph.neighbors(cur_index).collect();
+        return match (*ast).graph.node_weight(cur_index).unwrap() {
+            SyntaxTreeNode::NonTerminalAst(NonTerminalsAst::Len) => matches!((*ast).graph.node_weight(children[0]).unwrap(), SyntaxTreeNode::Terminal(Token { token_type: TokenType::Identifier(_), .. })),
+            SyntaxTreeNode::NonTerminalAst(_) => children.into_iter().any(|child_index| Self::has_variable_length(ast, child_index)),
+            _ => false
+        };
+    }
+
+    // Function to compute the loop-invariant parts of a while condition before the loop starts
+    fn code_gen_hoisted_values(&mut self, ast: &SyntaxTree, cur_index: NodeIndex, symbol_table: &mut SymbolTable) -> bool {
+        // Hoisting trades an extra variable and store for less work per iteration,
+        // which is the opposite of what is wanted when optimizing for size
+        if self.optimize_size {
+            return true;
+        }
+
+        for expr_index in optimizer::find_loop_invariants(ast, cur_index) {
+            if !self.code_gen_expr_value(ast, expr_index, symbol_table) { return false; }
+
+            // Give the value its own spot in static memory so it survives the loop body
+            let static_offset: usize = self.static_table.len();
+            self.static_table.insert(StaticVariable::Hoisted(expr_index), static_offset);
+
+            if !self.emit_sta_abs(Address::Static(static_offset)) { return false; }
+
+            self.hoisted_values.insert(expr_index, static_offset);
+        }
+
+        return true;
+    }
+
+    // Function to get the address of the string that a boolean is printed as
+    // The strings are only stored once the program prints a boolean
+    fn store_bool_string(&mut self, value: bool) -> Option<u8> {
+        return match value {
+            true => self.store_string(TRUE_STRING),
+            false => self.store_string(FALSE_STRING)
+        };
+    }
+
+    // Loads the address of the "true" string into the print register if the Z flag is set and "false" otherwise
+    // This is emitted inline at every boolean print instead of being shared, which is 9 bytes with the LDX and SYS when optimizing for size
+    // The instruction set has no JSR/RTS, so a shared copy would have to return by branching on a stored return selector,
+    // and storing the selector (LDA, STA) and jumping there (CPX, BNE) already takes 10 bytes at every print
+    fn load_bool_string(&mut self) -> bool {
+        let (true_addr, false_addr): (u8, u8) = match (self.store_bool_string(true), self.store_bool_string(false)) {
+            (Some(true_addr), Some(false_addr)) => (true_addr, false_addr),
+            _ => return false
+        };
+
+        if self.optimize_size {
+            // Loads do not change the Z flag, so start with false and
+            // only replace it with true if the branch is not taken
+            if !self.emit_load_imm(self.get_print_register(), false_addr) { return false; }
+            if !self.emit_bne(Branch::Offset(0x02)) { return false; }
+            if !self.emit_load_imm(self.get_print_register(), true_addr) { return false; }
+        } else {
+            // Skip to the false string if it is false
+            if !self.emit_bne(Branch::Offset(0x07)) { return false; }
+            
+            // Load the true string and skip over the false string
+            if !self.emit_load_imm(self.get_print_register(), true_addr) { return false; }
+            if !self.emit_cpx_abs(Address::Fixed(ZERO_ADDRESS)) { return false; }
+            if !self.emit_bne(Branch::Offset(0x02)) { return false; }
+
+            // Load the false string
+            if !self.emit_load_imm(self.get_print_register(), false_addr) { return false; }
         }
 
         return true;
@@ -1102,14 +1833,11 @@ impl CodeGenerator6502 {
     // Stores the value of the Z flag into the accumulator
     fn get_z_flag_value(&mut self) -> bool {
         // Assume Z is set to 0
-        if !self.add_code(0xA9) { return false; }
-        if !self.add_code(0x00) { return false; }
+        if !self.emit_lda_imm(0x00) { return false; }
         // If it is 0, branch
-        if !self.add_code(0xD0) { return false; }
-        if !self.add_code(0x02) { return false; }
+        if !self.emit_bne(Branch::Offset(0x02)) { return false; }
         // Otherwise, set the acc to 1
-        if !self.add_code(0xA9) { return false; }
-        if !self.add_code(0x01) { return false; }
+        if !self.emit_lda_imm(0x01) { return false; }
 
         return true;
     }
@@ -1117,7 +1845,7 @@ impl CodeGenerator6502 {
     fn code_gen_if(&mut self, ast: &SyntaxTree, cur_index: NodeIndex, symbol_table: &mut SymbolTable) -> bool {
         nexus_log::log(
             nexus_log::LogTypes::Debug,
-            nexus_log::LogSources::CodeGenerator,
+            nexus_log::LogSource::CODE_GENERATOR,
             format!("Starting code generation for if statement in scope {}", symbol_table.cur_scope.unwrap())
         );
 
@@ -1141,8 +1869,7 @@ impl CodeGenerator6502 {
                     _ => error!("Received {:?} when expecting IsEq or NotEq for nonterminal if expression", non_terminal)
                 }
                 // Add the branch code
-                if !self.add_code(0xD0) { return false; }
-                if !self.add_jump() { return false; }
+                if !self.emit_bne(Branch::Jump) { return false; }
                 start_addr = self.code_pointer.to_owned();
             },
             SyntaxTreeNode::Terminal(token) => {
@@ -1176,7 +1903,7 @@ impl CodeGenerator6502 {
     fn code_gen_while(&mut self, ast: &SyntaxTree, cur_index: NodeIndex, symbol_table: &mut SymbolTable) -> bool {
          nexus_log::log(
             nexus_log::LogTypes::Debug,
-            nexus_log::LogSources::CodeGenerator,
+            nexus_log::LogSource::CODE_GENERATOR,
             format!("Starting code generation for while statement in scope {}", symbol_table.cur_scope.unwrap())
         );
 
@@ -1184,6 +1911,9 @@ impl CodeGenerator6502 {
         let children: Vec<NodeIndex> = (*ast).graph.neighbors(cur_index).collect();
         let left_child: &SyntaxTreeNode = (*ast).graph.node_weight(children[1]).unwrap();
 
+        // Anything that does not change in the loop only has to be computed once
+        if !self.code_gen_hoisted_values(ast, cur_index, symbol_table) { return false; }
+
         // Save the current address for the loop
         let loop_start_addr: u8 = self.code_pointer.to_owned();
 
@@ -1204,8 +1934,7 @@ impl CodeGenerator6502 {
                     _ => error!("Received {:?} when expecting IsEq or NotEq for nonterminal if expression", non_terminal)
                 }
                 // Add the branch code
-                if !self.add_code(0xD0) { return false; }
-                if !self.add_jump() { return false; }
+                if !self.emit_bne(Branch::Jump) { return false; }
                 body_start_addr = self.code_pointer.to_owned();
             },
             SyntaxTreeNode::Terminal(token) => {
@@ -1229,15 +1958,11 @@ impl CodeGenerator6502 {
         // Get the position in the vector for the unconditional branch
         let unconditional_jump_index: usize = self.jumps.len();
         // Set X to 1
-        if !self.add_code(0xA2) { return false; }
-        if !self.add_code(0x01) { return false; }
+        if !self.emit_ldx_imm(0x01) { return false; }
         // 0xFF is always 0, so comparing it to 1 will result in Z = 0,
         // so the branch will always be taken
-        if !self.add_code(0xEC) { return false; }
-        if !self.add_code(0xFF) { return false; }
-        if !self.add_code(0x00) { return false; }
-        if !self.add_code(0xD0) { return false; }
-        if !self.add_jump() { return false; }
+        if !self.emit_cpx_abs(Address::Fixed(ZERO_ADDRESS)) { return false; }
+        if !self.emit_bne(Branch::Jump) { return false; }
 
         // If there was a comparison to make, there is a start addr for the body
         // to skip over in case evaluate to false
@@ -1253,10 +1978,36 @@ impl CodeGenerator6502 {
         // Set the unconditional branch offset in the jump
         self.jumps[unconditional_jump_index] = unconditional_branch_offset;
 
+        self.loop_ranges.push(LoopRange {
+            start: loop_start_addr as usize,
+            end: self.code_pointer as usize,
+            trip_count: cost_model::get_constant_trip_count(ast, cur_index)
+        });
+
         return true;
     }
 
-    fn display_code(&mut self, program_number: &u32) {
+    // Function to get the image as hex bytes separated by spaces
+    pub fn get_image_hex(&self) -> String {
+        // Get the array of values but only keep the hex digits and spaces
+        let mut code_str: String = format!("{:?}", self.code_arr);
+        code_str.retain(|c| c != ',' && c != '[' && c != ']');
+        return code_str;
+    }
+
+    // Function to get the image as the bytes that are loaded into memory
+    pub fn get_image_bytes(&self) -> Vec<u8> {
+        return self.code_arr.iter().map(|code_byte| match code_byte {
+            CodeGenBytes::Code(code) => *code,
+            CodeGenBytes::Data(data) => *data,
+            // Placeholders are all filled in by backpatching, so anything else is unused memory
+            _ => 0x00
+        }).collect();
+    }
+
+    // Function to show the image in the page, which has to be done on the main thread
+    #[cfg(feature = "ui")]
+    pub fn display_code(&mut self, program_number: &u32) {
         let window: Window = web_sys::window().expect("Should be able to get the window");
         let document: Document = window.document().expect("Should be able to get the document");
 
@@ -1324,35 +2075,113 @@ impl CodeGenerator6502 {
         // The div is a container for the content of the ast info
         display_area_class_list.add_3("container", "text-center", "code-gen-pane").expect("Should be able to add the classes");
 
-        // Get the array of values but only keep the hex digits and spaces
-        let mut code_str: String = format!("{:?}", self.code_arr);
-        code_str.retain(|c| c != ',' && c != '[' && c != ']');
+        let code_str: String = self.get_image_hex();
 
         // This is the element that the code is in
         let code_elem: Element = document.create_element("p").expect("Should be able to create the element");
         code_elem.set_class_name("code-text");
-        code_elem.set_inner_html(&code_str);
+        code_elem.set_inner_html(&self.create_annotated_code(&code_str));
 
         display_area_div.append_child(&code_elem).expect("Should be able to add the child node");
 
-        // This is the button to copy to the clipboard
-        let copy_btn: Element = document.create_element("button").expect("Should be able to create the element");
-        copy_btn.set_inner_html("Copy to Clipboard");
-        copy_btn.set_class_name("copy-btn");
-        display_area_div.append_child(&copy_btn).expect("Should be able to add the child node");
+        // The hex has to stay plain to be loaded, so the build information goes in its own block after it
+        if self.build_info.is_some() {
+            let build_info_elem: Element = document.create_element("p").expect("Should be able to create the element");
+            build_info_elem.set_class_name("build-info");
+            let build_info_lines: Vec<String> = self.build_info.as_ref().unwrap().get_lines().iter().map(|line| escape_html(line)).collect();
+            build_info_elem.set_inner_html(&output::join_html_lines(&build_info_lines));
+            display_area_div.append_child(&build_info_elem).expect("Should be able to add the child node");
+        }
 
-        // Create a function that will be used as the event listener and add it to the copy button
-        let copy_btn_fn: Closure<dyn FnMut()> = Closure::wrap(Box::new(move || {
-            // Call the JS function that handles the clipboard
-            set_clipboard(&code_str);
-        }) as Box<dyn FnMut()>);
-        copy_btn.add_event_listener_with_callback("click", copy_btn_fn.as_ref().unchecked_ref()).expect("Should be able to add the event listener");
-        copy_btn_fn.forget();
+        // Show the cost of each statement below the code
+        cost_model::display_cost_table(&document, &display_area_div, &self.get_statement_costs(), program_number, "cycles");
+
+        // The buttons to copy and download the hex
+        output::add_output_buttons(&document, &display_area_div, code_str, format!("program{}.hex", *program_number));
 
         // Add the div to the pane
         content_area.append_child(&display_area_div).expect("Should be able to add the child node");
     }
 
+    // Function to get the code produced by each statement and block (node, start, end)
+    pub fn get_coverage_ranges(&self) -> &Vec<(NodeIndex, usize, usize)> {
+        return &self.coverage_ranges;
+    }
+
+    // Function to get the code of each block with its scope (scope, block, start, end), which is empty unless the scope comments are on
+    pub fn get_scope_ranges(&self) -> Vec<(usize, NodeIndex, usize, usize)> {
+        if !self.scope_comments {
+            return Vec::new();
+        }
+        return source_map::get_scope_ranges(&self.coverage_ranges, &self.block_scopes);
+    }
+
+    // Function to estimate the instructions and cycles of each statement in the program block
+    #[cfg(feature = "ui")]
+    fn get_statement_costs(&self) -> Vec<StatementCost> {
+        let mut costs: Vec<StatementCost> = self.statement_ranges.iter()
+            .map(|(label, _, _)| StatementCost { label: label.to_owned(), instructions: 0, cycles: Some(0), executed: Some(0) })
+            .collect();
+        // The final break is not part of any statement
+        costs.push(StatementCost { label: String::from("End of program"), instructions: 0, cycles: Some(0), executed: Some(0) });
+
+        // Walk through the instructions so operands are not counted
+        let mut addr: usize = 0;
+        while addr < self.code_pointer as usize {
+            let instruction: Option<&instruction_set::Instruction6502> = match &self.code_arr[addr] {
+                CodeGenBytes::Code(code) => instruction_set::get_instruction_6502(*code),
+                _ => None
+            };
+            if instruction.is_none() {
+                addr += 1;
+                continue;
+            }
+            let instruction_info: &instruction_set::Instruction6502 = instruction.unwrap();
+
+            let cost_index: usize = self.statement_ranges.iter()
+                .position(|(_, start, end)| addr >= *start && addr < *end)
+                .unwrap_or(costs.len() - 1);
+            let cost: &mut StatementCost = &mut costs[cost_index];
+            cost.instructions += 1;
+            cost.cycles = cost.cycles.map(|cycles| cycles + instruction_info.cycles as usize);
+            cost.executed = match (cost.executed, cost_model::get_multiplier(&self.loop_ranges, addr)) {
+                (Some(executed), Some(multiplier)) => Some(executed + instruction_info.cycles as usize * multiplier),
+                _ => None
+            };
+
+            addr += instruction_info.length as usize;
+        }
+
+        return costs;
+    }
+
+    // Function to wrap each opcode in a span that shows the instruction docs on hover
+    #[cfg(feature = "ui")]
+    fn create_annotated_code(&self, code_str: &str) -> String {
+        let bytes: Vec<&str> = code_str.split(' ').collect();
+        let mut annotated_bytes: Vec<String> = Vec::new();
+
+        // Walk through the instructions so operands are not mistaken for opcodes
+        let mut next_instruction: usize = 0;
+        for (i, byte) in bytes.iter().enumerate() {
+            let instruction: Option<&instruction_set::Instruction6502> = match &self.code_arr[i] {
+                CodeGenBytes::Code(code) if i == next_instruction && i < self.code_pointer as usize => instruction_set::get_instruction_6502(*code),
+                _ => None
+            };
+
+            match instruction {
+                Some(instruction_info) => {
+                    next_instruction += instruction_info.length as usize;
+                    annotated_bytes.push(format!("<span class=\"opcode\" title=\"{}\">{}</span>", escape_html(&instruction_set::get_tooltip_6502(instruction_info)), byte));
+                },
+                None => annotated_bytes.push(byte.to_string())
+            }
+        }
+
+        return annotated_bytes.join(" ");
+    }
+
+    #[cfg(feature = "ui")]
     pub fn clear_display() {
         // Get the preliminary objects
         let window: Window = web_sys::window().expect("Should be able to get the window");
@@ -1,34 +1,34 @@
 use log::*;
 
-use crate::nexus::{syntax_tree::SyntaxTree, syntax_tree_node::*, symbol_table::*};
-use crate::nexus::token::{TokenType, Keywords};
-use crate::util::nexus_log;
+use crate::nexus::{syntax_tree::SyntaxTree, syntax_tree_node::*, symbol_table::*, semantic_analyzer::SemanticAnalysisResult, optimizer, riscv_validator, riscv_instruction::{Instruction, Register}, cost_model::{self, LoopRange}, source_map};
+use crate::nexus::{operand, token::{Token, TokenType, Keywords}};
+use crate::util::{nexus_log, build_info::BuildInfo, target::{Target, TargetConfig, SyscallConvention}};
+#[cfg(feature = "ui")]
+use crate::nexus::cost_model::StatementCost;
+#[cfg(feature = "ui")]
+use crate::editor::output;
+#[cfg(feature = "ui")]
+use crate::util::{instruction_set, html::escape_html};
 use petgraph::graph::{NodeIndex};
 
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
+use indexmap::IndexMap;
+#[cfg(feature = "ui")]
 use web_sys::{Document, Window, Element, DomTokenList};
-use wasm_bindgen::{prelude::Closure, JsCast};
-use wasm_bindgen::prelude::*;
 
 use string_builder::Builder;
 
-// Have to import the editor js module
-#[wasm_bindgen(module = "/editor.js")]
-extern "C" {
-    // Import the getCodeInput function from js so we can call it from the Rust code
-    #[wasm_bindgen(js_name = "setClipboard")]
-    fn set_clipboard(newText: &str);
-}
-
 // The struct for the code generator
 #[derive (Debug)]
 pub struct CodeGeneratorRiscV {
-    // The current max scope we have seen so far, which are encountered in
-    // sequential order
-    max_scope: usize,
+    // The scope the semantic analyzer gave each block, which are used to look up the variables in the symbol table
+    block_scopes: HashMap<NodeIndex, usize>,
+
+    // The declaration of every identifier node from semantic analysis, which are used for the variable labels
+    decl_ids: HashMap<NodeIndex, DeclId>,
     
     // The array for the code
-    code_arr: Vec<String>,
+    code_arr: Vec<Instruction>,
 
     // The array for the variables
     static_arr: Vec<String>,
@@ -39,33 +39,90 @@ pub struct CodeGeneratorRiscV {
     // Index for the temoprary data
     temp_index: usize,
 
-    // Hashmap to keep track of the strings being stored on the heap
-    string_history: HashMap<String, usize>,
+    // Map to keep track of the strings being stored on the heap in the order they are stored
+    string_history: IndexMap<String, usize>,
 
     // The number of if statements
     if_count: usize,
 
     // The number of while statements
-    while_count: usize
+    while_count: usize,
+
+    // Hashmap of the loop-invariant expressions computed before their loop <expr, label>
+    hoisted_values: HashMap<NodeIndex, String>,
+
+    // Assignments that have no effect and do not get any code
+    removed_statements: HashSet<NodeIndex>,
+
+    // The code for each statement in the program block (label, start, end) for the cost estimate
+    statement_ranges: Vec<(String, usize, usize)>,
+
+    // The code for each while loop for the cost estimate
+    loop_ranges: Vec<LoopRange>,
+
+    // The code produced by each statement and block (node, start, end)
+    coverage_ranges: Vec<(NodeIndex, usize, usize)>,
+
+    // The number of lines of code before the subroutines
+    program_length: usize,
+
+    // How the program was compiled, which is added to the top of the assembly
+    build_info: Option<BuildInfo>,
+
+    // The system calls the print routines and the end of the program use
+    syscall_convention: SyscallConvention,
+
+    // Whether each block is wrapped in comments with its scope from the symbol table
+    scope_comments: bool
 }
 
 impl CodeGeneratorRiscV {
     pub fn new() -> Self {
         return CodeGeneratorRiscV {
-            max_scope: usize::MAX,
+            block_scopes: HashMap::new(),
+            decl_ids: HashMap::new(),
             code_arr: Vec::new(),
             static_arr: Vec::new(),
             heap_arr: Vec::new(),
             temp_index: 0,
-            string_history: HashMap::new(),
+            string_history: IndexMap::new(),
             if_count: 0,
-            while_count: 0
+            while_count: 0,
+            hoisted_values: HashMap::new(),
+            removed_statements: HashSet::new(),
+            statement_ranges: Vec::new(),
+            loop_ranges: Vec::new(),
+            coverage_ranges: Vec::new(),
+            program_length: 0,
+            build_info: None,
+            syscall_convention: SyscallConvention::Linux,
+            scope_comments: false
         };
     }
 
-    pub fn generate_code(&mut self, ast: &SyntaxTree, symbol_table: &mut SymbolTable, program_number: &u32) {
-        // Make sure the current scope is set to be a flag for none
-        self.max_scope = usize::MAX;
+    // Function to set the build information for the header of the assembly
+    pub fn set_build_info(&mut self, build_info: BuildInfo) {
+        self.build_info = Some(build_info);
+    }
+
+    // Function to set the system calls to print and exit with
+    pub fn set_syscall_convention(&mut self, syscall_convention: SyscallConvention) {
+        self.syscall_convention = syscall_convention;
+    }
+
+    // Function to turn the comments at the start and end of each scope on or off
+    pub fn set_scope_comments(&mut self, scope_comments: bool) {
+        self.scope_comments = scope_comments;
+    }
+
+    // Function to generate the assembly without displaying it, returns true if successful
+    pub fn generate_assembly(&mut self, semantic_analysis_res: &mut SemanticAnalysisResult) -> bool {
+        let ast: &SyntaxTree = &semantic_analysis_res.ast;
+        let symbol_table: &mut SymbolTable = &mut semantic_analysis_res.symbol_table;
+
+        // Use the same scopes as the symbol table so labels always match the declarations
+        self.block_scopes = semantic_analysis_res.block_scopes.clone();
+        self.decl_ids = semantic_analysis_res.decl_ids.clone();
         
         self.code_arr.clear();
         self.static_arr.clear();
@@ -79,6 +136,11 @@ impl CodeGeneratorRiscV {
         self.string_history.clear();
         self.if_count = 0;
         self.while_count = 0;
+        self.hoisted_values.clear();
+        self.removed_statements = optimizer::find_no_op_statements(ast);
+        self.statement_ranges.clear();
+        self.loop_ranges.clear();
+        self.coverage_ranges.clear();
 
         // Store the actual strings "true" and "false"
         self.store_string("false");
@@ -88,278 +150,538 @@ impl CodeGeneratorRiscV {
         self.code_gen_block(ast, NodeIndex::new((*ast).root.unwrap()), symbol_table);
         
         // Add the code to exit the program
-        self.code_arr.push(format!("li  a7, 93"));
-        self.code_arr.push(format!("li  a0, 0"));
-        self.code_arr.push(format!("ecall"));
+        self.add_exit_code();
+        self.program_length = self.code_arr.len();
 
         // Add a function for printing an integer
         self.add_print_int_code();
         self.add_print_string_code();
         self.add_print_boolean_code();
+        self.add_print_char_code();
         self.add_print_new_line_code();
         self.add_compare_eq_code();
         self.add_compare_neq_code();
+
+        // The assembler would reject the program if a label is defined more than once
+        let duplicate_labels: Vec<String> = self.find_duplicate_labels();
+        if duplicate_labels.len() > 0 {
+            for label in duplicate_labels.iter() {
+                nexus_log::log(
+                    nexus_log::LogTypes::Error,
+                    nexus_log::LogSource::CODE_GENERATOR,
+                    format!("Label [ {} ] is defined more than once in the generated assembly", label)
+                );
+            }
+            nexus_log::log(
+                nexus_log::LogTypes::Error,
+                nexus_log::LogSource::CODE_GENERATOR,
+                format!("Code generation failed with {} duplicate label(s)", duplicate_labels.len())
+            );
+            return false;
+        }
+
+        // Make sure a bug in the code generator does not hand the user assembly that will not assemble
+        // The header lines (section, global, _start, and nop) come before the generated code
+        let code_lines: Vec<String> = self.code_arr.iter().map(|line| line.to_string()).collect();
+        let generated_lines: Vec<&String> = code_lines.iter().chain(self.static_arr.iter()).chain(self.heap_arr.iter()).collect();
+        let assembly_problems: Vec<String> = riscv_validator::validate_assembly(&generated_lines, 5);
+        if assembly_problems.len() > 0 {
+            for problem in assembly_problems.iter() {
+                nexus_log::log(
+                    nexus_log::LogTypes::Error,
+                    nexus_log::LogSource::CODE_GENERATOR,
+                    format!("Invalid generated assembly at {}", problem)
+                );
+            }
+            nexus_log::log(
+                nexus_log::LogTypes::Error,
+                nexus_log::LogSource::CODE_GENERATOR,
+                format!("Code generation failed because the assembly has {} problem(s)", assembly_problems.len())
+            );
+            return false;
+        }
        
         nexus_log::log(
             nexus_log::LogTypes::Info,
-            nexus_log::LogSources::CodeGenerator,
+            nexus_log::LogSource::CODE_GENERATOR,
             format!("Code generation completed successfully")
         );
+        return true;
+    }
 
-        nexus_log::log(
-            nexus_log::LogTypes::Info,
-            nexus_log::LogSources::Nexus,
-            format!("Executable image for program {} is below", *program_number)
-        );
-
-        self.display_code(program_number);
+    // Function to get the assembly as plain text with new lines
+    pub fn get_assembly(&mut self) -> String {
+        return self.create_output_string();
     }
 
     fn code_gen_block(&mut self, ast: &SyntaxTree, cur_index: NodeIndex, symbol_table: &mut SymbolTable) {
-        // If this is the first block, then the first scope is 0
-        if self.max_scope == usize::MAX {
-            self.max_scope = 0;
-        } else {
-            // Otherwise just add 1
-            self.max_scope += 1;
-        }
+        let block_scope: usize = *self.block_scopes.get(&cur_index).unwrap();
 
         nexus_log::log(
             nexus_log::LogTypes::Debug,
-            nexus_log::LogSources::CodeGenerator,
-            format!("Starting code generation for the block for scope {}", self.max_scope)
+            nexus_log::LogSource::CODE_GENERATOR,
+            format!("Starting code generation for the block for scope {}", block_scope)
         );
 
         // Manually set the current scope because we are not able to look down
         // in the symbol table
-        symbol_table.set_cur_scope(self.max_scope);
+        symbol_table.set_cur_scope(block_scope);
+
+        // The comments are outside of the block's range, so they are never counted as the code of the block
+        if self.scope_comments {
+            let line: usize = ast.get_node_position(cur_index.index()).map_or(1, |position| position.line);
+            self.code_arr.push(Instruction::Comment(format!("enter scope {} (line {})", block_scope, line)));
+        }
+
+        // Only the statements of the program block get their own cost
+        let is_program_block: bool = block_scope == 0;
+        let block_start: usize = self.code_arr.len();
 
         // The current node is the block, so we need to loop through each of its children
         let neighbors: Vec<NodeIndex> = (*ast).graph.neighbors(cur_index).collect();
 
         for neighbor_index in neighbors.into_iter().rev() {
             let child: &SyntaxTreeNode = (*ast).graph.node_weight(neighbor_index).unwrap();
+            let statement_start: usize = self.code_arr.len();
             
             match child {
                 SyntaxTreeNode::NonTerminalAst(non_terminal) => {
                     match non_terminal {
                         NonTerminalsAst::Block => self.code_gen_block(ast, neighbor_index, symbol_table),
                         NonTerminalsAst::VarDecl => self.code_gen_var_decl(ast, neighbor_index, symbol_table),
+                        NonTerminalsAst::Assign if self.removed_statements.contains(&neighbor_index) => {},
                         NonTerminalsAst::Assign => self.code_gen_assignment(ast, neighbor_index, symbol_table),
                         NonTerminalsAst::Print => self.code_gen_print(ast, neighbor_index, symbol_table),
+                        NonTerminalsAst::If | NonTerminalsAst::While if self.removed_statements.contains(&neighbor_index) => {},
                         NonTerminalsAst::If => self.code_gen_if(ast, neighbor_index, symbol_table),
                         NonTerminalsAst::While => self.code_gen_while(ast, neighbor_index, symbol_table),
                         _ => error!("Received {:?} when expecting an AST nonterminal statement in a block", non_terminal)
                     }
+
+                    self.coverage_ranges.push((neighbor_index, statement_start, self.code_arr.len()));
+                    if is_program_block {
+                        self.statement_ranges.push((cost_model::get_statement_label(ast, neighbor_index), statement_start, self.code_arr.len()));
+                    }
                 }
                 _ => error!("Received {:?} when expecting an AST nonterminal for code gen in a block", child)
             }
         }
 
+        self.coverage_ranges.push((cur_index, block_start, self.code_arr.len()));
+        if self.scope_comments {
+            self.code_arr.push(Instruction::Comment(format!("exit scope {}", block_scope)));
+        }
+
         // Exit the current scope
         symbol_table.end_cur_scope();
     }
 
     fn add_print_int_code(&mut self) {
         // Function is called print_int
-        self.code_arr.push(format!("print_int:"));
+        self.code_arr.push(Instruction::Label(String::from("print_int")));
 
         // Get the byte stored in a0
         // Assume a0 is the number that needs to be printed
-        self.code_arr.push(format!("mv t0, a0"));
-
-        // Sys call 64 is printing
-        self.code_arr.push(format!("li  a7, 64"));
-        // a0 = 1 is sysout
-        self.code_arr.push(format!("li  a0, 1"));
-        // a1 is the address of the string to print
-        self.code_arr.push(format!("la  a1, print_int_char"));
-        // a2 is the length of the string (1 digit at a time)
-        self.code_arr.push(format!("li  a2, 1"));
-
-        // t1 is the index of the string we are on
-        self.code_arr.push(format!("li  t1, 0"));
+        self.code_arr.push(Instruction::Mv { rd: Register::T0, rs1: Register::A0 });
+
+        // Conventions without a write call print the digits as characters instead of from print_int_char
+        let write_call: Option<u32> = self.get_write_call();
+        if let Some(write_call) = write_call {
+            // Sys call 64 is printing on Linux
+            self.code_arr.push(Instruction::Li { rd: Register::A7, imm: write_call as i64 });
+            // a0 = 1 is sysout
+            self.code_arr.push(Instruction::Li { rd: Register::A0, imm: 1 });
+            // a1 is the address of the string to print
+            self.code_arr.push(Instruction::La { rd: Register::A1, label: String::from("print_int_char") });
+            // a2 is the length of the string (1 digit at a time)
+            self.code_arr.push(Instruction::Li { rd: Register::A2, imm: 1 });
+        }
 
         // t2 is what we are dividing by to get the digit
-        // Starts with 100 because a byte is no longer than 3 digits long in base 10
-        self.code_arr.push(format!("li  t2, 100"));
+        // Starts with the place of the highest digit the largest integer on the target can have
+        let max_int: u32 = TargetConfig::new(Target::TargetRiscV).max_int;
+        let mut divisor: u32 = 1;
+        while divisor <= max_int / 10 {
+            divisor *= 10;
+        }
+        self.code_arr.push(Instruction::Li { rd: Register::T2, imm: divisor as i64 });
 
-        // No more than 3 iterations of the loop
-        self.code_arr.push(format!("li  t3, 3"));
+        // 1 is the place of the last digit, which is always printed
+        self.code_arr.push(Instruction::Li { rd: Register::T3, imm: 1 });
 
         // 10 has to be stored for later use
-        self.code_arr.push(format!("li  t4, 10"));
+        self.code_arr.push(Instruction::Li { rd: Register::T4, imm: 10 });
+
+        // Skip the leading zeros by moving down a place while the number is smaller than it
+        self.code_arr.push(Instruction::J { label: String::from("print_int_check") });
+        self.code_arr.push(Instruction::Label(String::from("print_int_skip")));
+        self.code_arr.push(Instruction::Divu { rd: Register::T2, rs1: Register::T2, rs2: Register::T4 });
+        self.code_arr.push(Instruction::Label(String::from("print_int_check")));
+        self.code_arr.push(Instruction::Beq { rs1: Register::T2, rs2: Register::T3, label: String::from("print_int_loop") });
+        self.code_arr.push(Instruction::Blt { rs1: Register::T0, rs2: Register::T2, label: String::from("print_int_skip") });
 
         // Create the label for the loop
-        self.code_arr.push(format!("print_int_loop:"));
+        self.code_arr.push(Instruction::Label(String::from("print_int_loop")));
         
         // Get the top digit
-        self.code_arr.push(format!("divu  t5, t0, t2"));
+        self.code_arr.push(Instruction::Divu { rd: Register::T5, rs1: Register::T0, rs2: Register::T2 });
         // Add 0x30 to convert from digit to ascii (0 is 0x30 - 9 is 0x39)
-        self.code_arr.push(format!("addi  t5, t5, 0x30"));
+        self.code_arr.push(Instruction::Addi { rd: Register::T5, rs1: Register::T5, imm: 0x30 });
 
-        // a1 already has the address of the byte we are storing
-        self.code_arr.push(format!("sb  t5, 0(a1)"));
+        if write_call.is_some() {
+            // a1 already has the address of the byte we are storing
+            self.code_arr.push(Instruction::Sb { rs2: Register::T5, offset: 0, rs1: Register::A1 });
 
-        // Make the sys call to print the digit
-        self.code_arr.push(format!("ecall"));
+            // Make the sys call to print the digit
+            self.code_arr.push(Instruction::Ecall);
+        } else {
+            self.add_put_char_code(Register::T5);
+        }
 
         // Get the remainder
-        self.code_arr.push(format!("remu  t0, t0, t2"));
+        self.code_arr.push(Instruction::Remu { rd: Register::T0, rs1: Register::T0, rs2: Register::T2 });
 
         // Decrease the number we are dividing by
-        self.code_arr.push(format!("divu  t2, t2, t4"));
-
-        // Increment the counter
-        self.code_arr.push(format!("addi  t1, t1, 1"));
+        self.code_arr.push(Instruction::Divu { rd: Register::T2, rs1: Register::T2, rs2: Register::T4 });
 
-        // Branch to top of loop if still more digits to print
-        self.code_arr.push(format!("blt  t1, t3, print_int_loop"));
+        // Branch to top of loop if still more digits to print, which is until the place goes below 1
+        self.code_arr.push(Instruction::Bne { rs1: Register::T2, rs2: Register::Zero, label: String::from("print_int_loop") });
 
         // Return from the function call
-        self.code_arr.push(format!("ret"));
+        self.code_arr.push(Instruction::Ret);
     }
 
     fn add_print_string_code(&mut self) {
         // Create the label for printing the string
-        self.code_arr.push(format!("print_string:"));
+        self.code_arr.push(Instruction::Label(String::from("print_string")));
 
         // Assume a0 has the address of the string to print
-        self.code_arr.push(format!("mv  t0, a0"));
-
-        // Basic setup for the sys call
-        self.code_arr.push(format!("li  a7, 64"));
-        self.code_arr.push(format!("li  a0, 1"));
+        self.code_arr.push(Instruction::Mv { rd: Register::T0, rs1: Register::A0 });
 
         // The halfword is the length of the string
-        self.code_arr.push(format!("lhu  a2, 0(t0)"));
+        self.code_arr.push(Instruction::Lhu { rd: Register::A2, offset: 0, rs1: Register::T0 });
 
         // 2 bytes over is the start of the string
-        self.code_arr.push(format!("addi  a1, t0, 2"));
-        self.code_arr.push(format!("ecall"));
+        self.code_arr.push(Instruction::Addi { rd: Register::A1, rs1: Register::T0, imm: 2 });
+        self.add_write_code("print_string");
 
-        self.code_arr.push(format!("ret"));
+        self.code_arr.push(Instruction::Ret);
     }
 
     fn add_print_boolean_code(&mut self) {
-        self.code_arr.push(format!("print_boolean:"));
+        self.code_arr.push(Instruction::Label(String::from("print_boolean")));
 
         // Assume a0 has the boolean value
-        self.code_arr.push(format!("beq  a0, zero, print_false"));
+        self.code_arr.push(Instruction::Beq { rs1: Register::A0, rs2: Register::Zero, label: String::from("print_false") });
 
         // If the var is true, load true
-        self.code_arr.push(format!("la  a0, string_1"));
-        self.code_arr.push(format!("j  print_bool_call"));
+        self.code_arr.push(Instruction::La { rd: Register::A0, label: String::from("string_1") });
+        self.code_arr.push(Instruction::J { label: String::from("print_bool_call") });
 
-        self.code_arr.push(format!("print_false:"));
+        self.code_arr.push(Instruction::Label(String::from("print_false")));
         // Otherwise load false
-        self.code_arr.push(format!("la  a0, string_0"));
+        self.code_arr.push(Instruction::La { rd: Register::A0, label: String::from("string_0") });
 
-        self.code_arr.push(format!("print_bool_call:"));
+        self.code_arr.push(Instruction::Label(String::from("print_bool_call")));
         
-        self.code_arr.push(format!("addi  sp, sp, -4"));
-        self.code_arr.push(format!("sw  ra, 0(sp)"));
+        self.push_register(Register::Ra);
 
         // Print the string for the respective value of the variable
-        self.code_arr.push(format!("call print_string"));
+        self.code_arr.push(Instruction::Call { label: String::from("print_string") });
 
-        self.code_arr.push(format!("lw  ra, 0(sp)"));
-        self.code_arr.push(format!("addi  sp, sp, 4"));
+        self.pop_register(Register::Ra);
 
-        self.code_arr.push(format!("ret"));
+        self.code_arr.push(Instruction::Ret);
+    }
+
+    fn add_print_char_code(&mut self) {
+        self.code_arr.push(Instruction::Label(String::from("print_char")));
+
+        // Assume a0 has the character, which is written from the same byte that print_int uses for its digits
+        self.code_arr.push(Instruction::La { rd: Register::A1, label: String::from("print_int_char") });
+        self.code_arr.push(Instruction::Sb { rs2: Register::A0, offset: 0, rs1: Register::A1 });
+        self.code_arr.push(Instruction::Li { rd: Register::A2, imm: 1 });
+        self.add_write_code("print_char");
+
+        self.code_arr.push(Instruction::Ret);
     }
 
     fn add_print_new_line_code(&mut self) {
         // Create the label for a new line subroutine
-        self.code_arr.push(format!("print_new_line:"));
+        self.code_arr.push(Instruction::Label(String::from("print_new_line")));
 
         // Print out the new line character
-        self.code_arr.push(format!("li  a7, 64"));
-        self.code_arr.push(format!("li  a0, 1"));
-        self.code_arr.push(format!("la  a1, new_line"));
-        self.code_arr.push(format!("li  a2, 1"));
-        self.code_arr.push(format!("ecall"));
+        self.code_arr.push(Instruction::La { rd: Register::A1, label: String::from("new_line") });
+        self.code_arr.push(Instruction::Li { rd: Register::A2, imm: 1 });
+        self.add_write_code("print_new_line");
+
+        self.code_arr.push(Instruction::Ret);
+    }
+
+    // Function to get the number of the system call that writes a buffer, which only Linux-style conventions have
+    fn get_write_call(&self) -> Option<u32> {
+        return match self.syscall_convention {
+            SyscallConvention::Linux => Some(64),
+            SyscallConvention::Custom { write, .. } => Some(write),
+            _ => None
+        };
+    }
+
+    // Function to add the code to write the a2 bytes that start at a1
+    // Conventions that only print a character at a time loop over the bytes, using the label for the loop
+    fn add_write_code(&mut self, label: &str) {
+        if let Some(write_call) = self.get_write_call() {
+            self.code_arr.push(Instruction::Li { rd: Register::A7, imm: write_call as i64 });
+            self.code_arr.push(Instruction::Li { rd: Register::A0, imm: 1 });
+            self.code_arr.push(Instruction::Ecall);
+            return;
+        }
+
+        // t1 goes through the bytes while a2 counts down how many are left
+        self.code_arr.push(Instruction::Mv { rd: Register::T1, rs1: Register::A1 });
+        self.code_arr.push(Instruction::Label(format!("{}_write_loop", label)));
+        self.code_arr.push(Instruction::Beq { rs1: Register::A2, rs2: Register::Zero, label: format!("{}_write_done", label) });
+        self.code_arr.push(Instruction::Lbu { rd: Register::T5, offset: 0, rs1: Register::T1 });
+        self.add_put_char_code(Register::T5);
+        self.code_arr.push(Instruction::Addi { rd: Register::T1, rs1: Register::T1, imm: 1 });
+        self.code_arr.push(Instruction::Addi { rd: Register::A2, rs1: Register::A2, imm: -1 });
+        self.code_arr.push(Instruction::J { label: format!("{}_write_loop", label) });
+        self.code_arr.push(Instruction::Label(format!("{}_write_done", label)));
+    }
+
+    // Function to add the system call that prints the character in the register
+    fn add_put_char_code(&mut self, char_register: Register) {
+        // (register with the call number, call number, register with the character)
+        let (call_register, call_number, arg_register): (Register, i64, Register) = match self.syscall_convention {
+            SyscallConvention::Sbi => (Register::A7, 1, Register::A0),
+            SyscallConvention::Rars => (Register::A7, 11, Register::A0),
+            SyscallConvention::Venus => (Register::A0, 11, Register::A1),
+            convention => {
+                error!("Received {:?} when expecting a convention that prints a character at a time", convention);
+                return;
+            }
+        };
 
-        self.code_arr.push(format!("ret"));
+        self.code_arr.push(Instruction::Mv { rd: arg_register, rs1: char_register });
+        self.code_arr.push(Instruction::Li { rd: call_register, imm: call_number });
+        self.code_arr.push(Instruction::Ecall);
+    }
+
+    // Function to add the system call that ends the program
+    fn add_exit_code(&mut self) {
+        match self.syscall_convention {
+            SyscallConvention::Linux => {
+                self.code_arr.push(Instruction::Li { rd: Register::A7, imm: 93 });
+                self.code_arr.push(Instruction::Li { rd: Register::A0, imm: 0 });
+            },
+            SyscallConvention::Custom { exit, .. } => {
+                self.code_arr.push(Instruction::Li { rd: Register::A7, imm: exit as i64 });
+                self.code_arr.push(Instruction::Li { rd: Register::A0, imm: 0 });
+            },
+            SyscallConvention::Sbi => self.code_arr.push(Instruction::Li { rd: Register::A7, imm: 8 }),
+            SyscallConvention::Rars => self.code_arr.push(Instruction::Li { rd: Register::A7, imm: 10 }),
+            SyscallConvention::Venus => self.code_arr.push(Instruction::Li { rd: Register::A0, imm: 10 })
+        }
+        self.code_arr.push(Instruction::Ecall);
     }
 
     fn add_compare_eq_code(&mut self) {
         // Create the label for comparing equality between 2 values
-        self.code_arr.push(format!("compare_eq:"));
+        self.code_arr.push(Instruction::Label(String::from("compare_eq")));
 
         // Assume both values are in a0 and a1
-        self.code_arr.push(format!("beq  a0, a1, compare_eq_true"));
+        self.code_arr.push(Instruction::Beq { rs1: Register::A0, rs2: Register::A1, label: String::from("compare_eq_true") });
 
         // Result stored in a0
-        self.code_arr.push(format!("li  a0, 0"));
-        self.code_arr.push(format!("j  compare_eq_ret"));
+        self.code_arr.push(Instruction::Li { rd: Register::A0, imm: 0 });
+        self.code_arr.push(Instruction::J { label: String::from("compare_eq_ret") });
 
         // Create the label for storing the true value
-        self.code_arr.push(format!("compare_eq_true:"));
-        self.code_arr.push(format!("li  a0, 1"));
+        self.code_arr.push(Instruction::Label(String::from("compare_eq_true")));
+        self.code_arr.push(Instruction::Li { rd: Register::A0, imm: 1 });
 
         // Return form the subroutine
-        self.code_arr.push(format!("compare_eq_ret:"));
-        self.code_arr.push(format!("ret"));
+        self.code_arr.push(Instruction::Label(String::from("compare_eq_ret")));
+        self.code_arr.push(Instruction::Ret);
     }
 
     fn add_compare_neq_code(&mut self) {
         // Create the label for comparing equality between 2 values
-        self.code_arr.push(format!("compare_neq:"));
+        self.code_arr.push(Instruction::Label(String::from("compare_neq")));
 
         // Assume both values are in a0 and a1
-        self.code_arr.push(format!("bne  a0, a1, compare_neq_true"));
+        self.code_arr.push(Instruction::Bne { rs1: Register::A0, rs2: Register::A1, label: String::from("compare_neq_true") });
 
         // Result stored in a0
-        self.code_arr.push(format!("li  a0, 0"));
-        self.code_arr.push(format!("j  compare_neq_ret"));
+        self.code_arr.push(Instruction::Li { rd: Register::A0, imm: 0 });
+        self.code_arr.push(Instruction::J { label: String::from("compare_neq_ret") });
 
         // Create the label for storing the true value
-        self.code_arr.push(format!("compare_neq_true:"));
-        self.code_arr.push(format!("li  a0, 1"));
+        self.code_arr.push(Instruction::Label(String::from("compare_neq_true")));
+        self.code_arr.push(Instruction::Li { rd: Register::A0, imm: 1 });
 
         // Return form the subroutine
-        self.code_arr.push(format!("compare_neq_ret:"));
-        self.code_arr.push(format!("ret"));
+        self.code_arr.push(Instruction::Label(String::from("compare_neq_ret")));
+        self.code_arr.push(Instruction::Ret);
     }
 
     fn create_output_string(&mut self) -> String {
         let mut output_builder: Builder = Builder::default();
+
+        // Comment header so shared assembly can be traced back to how it was compiled
+        if self.build_info.is_some() {
+            for line in self.build_info.as_ref().unwrap().get_lines().iter() {
+                output_builder.append(format!("# {}\n", line));
+            }
+        }
         
-        output_builder.append(".section .text<br>");
-        output_builder.append(".global _start<br>");
-        output_builder.append("_start:<br>");
-        output_builder.append("nop<br>");
+        output_builder.append(".section .text\n");
+        output_builder.append(".global _start\n");
+        output_builder.append("_start:\n");
+        output_builder.append("nop\n");
         for code in self.code_arr.iter() {
-            output_builder.append(code.as_str());
-            output_builder.append("<br>");
+            output_builder.append(format!("{}\n", code));
         }
 
         //output_builder.append(".section .data\n");
         for static_data in self.static_arr.iter() {
             output_builder.append(static_data.as_str());
-            output_builder.append("<br>");
+            output_builder.append("\n");
         }
 
         for heap_data in self.heap_arr.iter() {
             output_builder.append(heap_data.as_str());
-            output_builder.append("<br>");
+            output_builder.append("\n");
         }
 
         return output_builder.string().unwrap();
     }
 
+    // Function to get the number of instructions in the assembly, which includes the print routines but not the labels
+    pub fn get_instruction_count(&self) -> usize {
+        return self.code_arr.iter().filter(|line| line.get_mnemonic().is_some()).count();
+    }
+
+    // Function to get the number of bytes used by the strings, which each have a 2 byte length before them
+    pub fn get_string_heap_size(&self) -> usize {
+        return self.string_history.keys().map(|string| string.as_bytes().len() + 2).sum();
+    }
+
+    // Function to get the line of the assembly text (starting at 1) that has the first instruction of the program
+    pub fn get_first_code_line(&self) -> usize {
+        // The header comments are followed by the section, global, _start, and nop lines
+        let num_header_lines: usize = match &self.build_info {
+            Some(build_info) => build_info.get_lines().len(),
+            None => 0
+        };
+        return num_header_lines + 5;
+    }
+
+    // Function to get the code produced by each statement and block (node, start, end)
+    pub fn get_coverage_ranges(&self) -> &Vec<(NodeIndex, usize, usize)> {
+        return &self.coverage_ranges;
+    }
+
+    // Function to get the code of each block with its scope (scope, block, start, end), which is empty unless the scope comments are on
+    pub fn get_scope_ranges(&self) -> Vec<(usize, NodeIndex, usize, usize)> {
+        if !self.scope_comments {
+            return Vec::new();
+        }
+        return source_map::get_scope_ranges(&self.coverage_ranges, &self.block_scopes);
+    }
+
+    // Function to estimate the instructions of each statement in the program block
+    // Calls to the print and compare subroutines are counted as a single instruction
+    #[cfg(feature = "ui")]
+    fn get_statement_costs(&self) -> Vec<StatementCost> {
+        let mut costs: Vec<StatementCost> = self.statement_ranges.iter()
+            .map(|(label, _, _)| StatementCost { label: label.to_owned(), instructions: 0, cycles: None, executed: Some(0) })
+            .collect();
+        // The exit system call is not part of any statement
+        costs.push(StatementCost { label: String::from("End of program"), instructions: 0, cycles: None, executed: Some(0) });
+
+        for line_index in 0..self.program_length {
+            // Labels are not instructions
+            if self.code_arr[line_index].get_mnemonic().is_none() {
+                continue;
+            }
+
+            let cost_index: usize = self.statement_ranges.iter()
+                .position(|(_, start, end)| line_index >= *start && line_index < *end)
+                .unwrap_or(costs.len() - 1);
+            let cost: &mut StatementCost = &mut costs[cost_index];
+            cost.instructions += 1;
+            cost.executed = match (cost.executed, cost_model::get_multiplier(&self.loop_ranges, line_index)) {
+                (Some(executed), Some(multiplier)) => Some(executed + multiplier),
+                _ => None
+            };
+        }
+
+        return costs;
+    }
+
+    // Function to wrap each mnemonic in a span that shows the instruction docs on hover
+    #[cfg(feature = "ui")]
+    fn create_annotated_code(code_str: &str) -> String {
+        // The assembly is plain text, so every line is escaped before it is placed in the page
+        let annotated_lines: Vec<String> = code_str.split('\n')
+            .map(|line| {
+                // Instructions are the mnemonic followed by 2 spaces and the operands
+                let mnemonic: &str = line.split(' ').next().unwrap_or("");
+                match instruction_set::get_instruction_riscv(mnemonic) {
+                    Some(instruction_info) => format!("<span class=\"opcode\" title=\"{}\">{}</span>{}",
+                                                      escape_html(&instruction_set::get_tooltip_riscv(instruction_info)), mnemonic, escape_html(&line[mnemonic.len()..])),
+                    None => escape_html(line)
+                }
+            })
+            .collect();
+
+        return output::join_html_lines(&annotated_lines);
+    }
+
+    // Function to get the label of a variable, which uses the declaration the id node refers to
+    // The name is only there to make the assembly easier to read
+    fn get_id_label(&self, id: &str, id_index: NodeIndex) -> String {
+        // Longer identifiers from the language profile could be the same as another label, like while_end_1, so they get their own prefix
+        if id.len() > 1 {
+            return format!("var_{}_{}", id, self.decl_ids.get(&id_index).unwrap().0);
+        }
+        return format!("{}_{}", id, self.decl_ids.get(&id_index).unwrap().0);
+    }
+
+    // Function to get every label that is defined more than once across the sections
+    fn find_duplicate_labels(&self) -> Vec<String> {
+        let mut defined_labels: HashSet<&str> = HashSet::new();
+        let mut duplicate_labels: Vec<String> = Vec::new();
+
+        // Data label definitions are the first word of the line and end with a colon
+        let data_labels: Vec<&str> = self.static_arr.iter().chain(self.heap_arr.iter())
+            .map(|line| line.split(' ').next().unwrap_or(""))
+            .filter(|first_word| first_word.ends_with(':'))
+            .map(|first_word| &first_word[..first_word.len() - 1])
+            .collect();
+
+        for label in self.code_arr.iter().filter_map(|line| line.get_defined_label()).chain(data_labels) {
+            if !defined_labels.insert(label) && !duplicate_labels.iter().any(|duplicate| duplicate.eq(label)) {
+                duplicate_labels.push(String::from(label));
+            }
+        }
+
+        return duplicate_labels;
+    }
+
     fn store_string(&mut self, string: &str) -> usize {
         let addr: Option<&usize> = self.string_history.get(string);
         if addr.is_none() {
             // Place the string in the heap
             self.heap_arr.push(format!("string_{}:", self.string_history.len()));
-            // We will let strings be no longer than 2^16 - 1
-            self.heap_arr.push(format!(".half {}", string.len()));
-            self.heap_arr.push(format!(".ascii \"{}\"", string));
+            // Semantic analysis makes sure strings are no longer than 2^16 - 1, so the length always fits
+            // The length is the number of bytes that get printed, not the number of characters or the length of the escaped text
+            self.heap_arr.push(format!(".half {}", string.as_bytes().len()));
+            self.heap_arr.push(format!(".ascii \"{}\"", escape_assembly_string(string)));
             nexus_log::log(
                 nexus_log::LogTypes::Debug,
-                nexus_log::LogSources::CodeGenerator,
+                nexus_log::LogSource::CODE_GENERATOR,
                 format!("Stored string \"{}\" at label string_{}", string, self.string_history.len())
             );
 
@@ -378,7 +700,7 @@ impl CodeGeneratorRiscV {
     fn code_gen_var_decl(&mut self, ast: &SyntaxTree, cur_index: NodeIndex, symbol_table: &mut SymbolTable) {
         nexus_log::log(
             nexus_log::LogTypes::Debug,
-            nexus_log::LogSources::CodeGenerator,
+            nexus_log::LogSource::CODE_GENERATOR,
             format!("Starting code generation for variable declaration statement in scope {}", symbol_table.cur_scope.unwrap())
         );
 
@@ -390,20 +712,30 @@ impl CodeGeneratorRiscV {
                 // Get the symbol table entry to get the type of the variable
                 let symbol_table_entry: &SymbolTableEntry = symbol_table.get_symbol_with_context(&token.text, token.position).unwrap();
                 match symbol_table_entry.symbol_type {
-                    // Only integers and booleans are initialized
+                    // Integers and booleans start as 0
                     Type::Int | Type::Boolean => {
-                        self.static_arr.push(format!("{}_{}: .byte 0", token.text, symbol_table_entry.scope));
+                        self.static_arr.push(format!("{}: .byte 0", self.get_id_label(&token.text, children[0])));
                         // Generate the code for the variable initialization to 1
-                        self.code_arr.push(format!("la  t1, {}_{}", token.text, symbol_table_entry.scope));
-                        self.code_arr.push(format!("li  t0, 0"));
-                        self.code_arr.push(format!("sb  t0, 0(t1)"));
+                        self.code_arr.push(Instruction::La { rd: Register::T1, label: self.get_id_label(&token.text, children[0]) });
+                        self.code_arr.push(Instruction::Li { rd: Register::T0, imm: 0 });
+                        self.code_arr.push(Instruction::Sb { rs2: Register::T0, offset: 0, rs1: Register::T1 });
                     },
-                    // Strings do not get initialized
+                    // Chars start as a space like on the 6502
+                    Type::Char => {
+                        self.static_arr.push(format!("{}: .byte 0", self.get_id_label(&token.text, children[0])));
+                        self.code_arr.push(Instruction::La { rd: Register::T1, label: self.get_id_label(&token.text, children[0]) });
+                        self.code_arr.push(Instruction::Li { rd: Register::T0, imm: b' ' as i64 });
+                        self.code_arr.push(Instruction::Sb { rs2: Register::T0, offset: 0, rs1: Register::T1 });
+                    },
+                    // Strings start as the empty string like on the 6502
                     Type::String => {
-                        // Only have to create the static entry here
                         // Since it is a string on the heap, we have to store the address
                         // which is a full word
-                        self.static_arr.push(format!("{}_{}: .word 0", token.text, symbol_table_entry.scope));
+                        self.static_arr.push(format!("{}: .word 0", self.get_id_label(&token.text, children[0])));
+                        let string_index: usize = self.store_string("");
+                        self.code_arr.push(Instruction::La { rd: Register::T1, label: self.get_id_label(&token.text, children[0]) });
+                        self.code_arr.push(Instruction::La { rd: Register::T0, label: format!("string_{}", string_index) });
+                        self.code_arr.push(Instruction::Sw { rs2: Register::T0, offset: 0, rs1: Register::T1 });
                     }
                 }
             },
@@ -415,7 +747,7 @@ impl CodeGeneratorRiscV {
     fn code_gen_assignment(&mut self, ast: &SyntaxTree, cur_index: NodeIndex, symbol_table: &mut SymbolTable) {
         nexus_log::log(
             nexus_log::LogTypes::Debug,
-            nexus_log::LogSources::CodeGenerator,
+            nexus_log::LogSource::CODE_GENERATOR,
             format!("Starting code generation for assignment statement in scope {}", symbol_table.cur_scope.unwrap())
         );
 
@@ -424,66 +756,24 @@ impl CodeGeneratorRiscV {
         let id_node: &SyntaxTreeNode = (*ast).graph.node_weight(children[1]).unwrap();
 
         match value_node {
-            SyntaxTreeNode::Terminal(token) => {
-                match &token.token_type {
-                    TokenType::Identifier(id_name) => {
-                        let value_id_entry: &SymbolTableEntry = symbol_table.get_symbol_with_context(&token.text, token.position).unwrap(); 
-                        
-                        // Load the address of the value variable then load the data
-                        self.code_arr.push(format!("la  t2, {}_{}", id_name, value_id_entry.scope));
-
-                        match value_id_entry.symbol_type {
-                            Type::Int | Type::Boolean => {
-                                // Load only a byte for integers and booleans
-                                self.code_arr.push(format!("lbu t0, 0(t2)"));
-                            },
-                            Type::String => {
-                                // Strings are an entire word
-                                self.code_arr.push(format!("lwu t0, 0(t2)"));
-                            }
-                        }
-                    },
-                    TokenType::Digit(val) => {
-                        // Digits just load a constant to the accumulator
-                        self.code_arr.push(format!("li  t0, {}", val)); 
-                    },
-                    TokenType::Char(string) => {
-                        // Start by storing the string
-                        let string_index: usize = self.store_string(&string);
-
-                        // Store the starting address of the string in memory
-                        self.code_arr.push(format!("la  t0, string_{}", string_index));
-                    },
-                    TokenType::Keyword(keyword) => {
-                        match &keyword {
-                            Keywords::True => {
-                                // True is 1
-                                self.code_arr.push(format!("li  t0, 1"));
-                            },
-                            Keywords::False => {
-                                // False is 0
-                                self.code_arr.push(format!("li  t0, 0")); 
-                            },
-                            _ => error!("Received {:?} when expecting true or false for keyword terminals in assignment", keyword)
-                        }
-                    },
-                    _ => error!("Received {:?} for terminal in assignment when expecting id, digit, char, or keyword", token)
-                }
-            },
+            // Every type of value is loaded into t0
+            SyntaxTreeNode::Terminal(_) => self.load_operand(ast, children[0], symbol_table, Register::T0),
             SyntaxTreeNode::NonTerminalAst(non_terminal) => {
                 match non_terminal {
                     NonTerminalsAst::Add => {
                         // Call add, so the result will be in both the accumulator and in memory
-                        self.code_gen_add(ast, children[0], symbol_table, true);
+                        self.code_gen_add(ast, children[0], symbol_table);
                     },
                     NonTerminalsAst::IsEq => {
                         self.code_gen_compare(ast, children[0], symbol_table, true);
-                        self.code_arr.push(format!("mv  t0, a0"));
+                        self.code_arr.push(Instruction::Mv { rd: Register::T0, rs1: Register::A0 });
                     },
                     NonTerminalsAst::NotEq => {
                         self.code_gen_compare(ast, children[0], symbol_table, false);
-                        self.code_arr.push(format!("mv  t0, a0"));
+                        self.code_arr.push(Instruction::Mv { rd: Register::T0, rs1: Register::A0 });
                     },
+                    NonTerminalsAst::Index => self.code_gen_index(ast, children[0], symbol_table),
+                    NonTerminalsAst::Len => self.code_gen_len(ast, children[0], symbol_table, Register::T0),
                     _ => error!("Received {:?} for nonterminal on right side of assignment for code gen", non_terminal)
                 }
             },
@@ -498,15 +788,15 @@ impl CodeGeneratorRiscV {
                 // The data that we are storing is already in t0, so load the appropriate
                 // address and store the data
 
-                self.code_arr.push(format!("la  t1, {}_{}", token.text, id_entry.scope));
+                self.code_arr.push(Instruction::La { rd: Register::T1, label: self.get_id_label(&token.text, children[1]) });
                 match &id_entry.symbol_type {
-                    Type::Int | Type::Boolean => {
-                        // Int and boolean take up only 1 byte
-                        self.code_arr.push(format!("sb  t0, 0(t1)")); 
+                    Type::Int | Type::Boolean | Type::Char => {
+                        // Int, boolean, and char take up only 1 byte
+                        self.code_arr.push(Instruction::Sb { rs2: Register::T0, offset: 0, rs1: Register::T1 }); 
                     },
                     Type::String => {
                         // Strings take up a full word
-                        self.code_arr.push(format!("sw  t0, 0(t1)"));
+                        self.code_arr.push(Instruction::Sw { rs2: Register::T0, offset: 0, rs1: Register::T1 });
                     }
                 }
             },
@@ -518,7 +808,7 @@ impl CodeGeneratorRiscV {
     fn code_gen_print(&mut self, ast: &SyntaxTree, cur_index: NodeIndex, symbol_table: &mut SymbolTable) {
         nexus_log::log(
             nexus_log::LogTypes::Debug,
-            nexus_log::LogSources::CodeGenerator,
+            nexus_log::LogSource::CODE_GENERATOR,
             format!("Starting code generation for print statement in scope {}", symbol_table.cur_scope.unwrap())
         );
 
@@ -528,155 +818,219 @@ impl CodeGeneratorRiscV {
 
         match child {
             SyntaxTreeNode::Terminal(token) => {
-                match &token.token_type {
-                    TokenType::Identifier(id_name) => {
-                        let print_id: &SymbolTableEntry = symbol_table.get_symbol_with_context(&id_name, token.position).unwrap();
-                        match &print_id.symbol_type {
-                            Type::Int => {
-                                self.code_arr.push(format!("la  t0, {}_{}", id_name, print_id.scope));
-                                self.code_arr.push(format!("lbu  a0, 0(t0)"));
-                                self.code_arr.push(format!("call print_int"));
-                            },
-                            Type::String => {
-                                // Store the string address in Y
-                                self.code_arr.push(format!("lwu  a0, {}_{}", id_name, print_id.scope));
-                                self.code_arr.push(format!("call print_string"));
-                            },
-                            Type::Boolean => {
-                                // Compare the value of the variable with false
-                                self.code_arr.push(format!("lbu  a0, {}_{}", id_name, print_id.scope));
-                                self.code_arr.push(format!("call print_boolean"));
-                            }
-                        }
-                    },
-                    TokenType::Digit(digit) => {
-                        // Place the number in a0 and call the function that
-                        // handles numbers
-                        self.code_arr.push(format!("li  a0, {}", digit));
-                        self.code_arr.push(format!("call print_int"));
-                    },
-                    TokenType::Char(string) => {
-                        // Store the string in memory and get its index
-                        let string_index: usize = self.store_string(&string);
-
-                        // Get the address of the string we want to print
-                        self.code_arr.push(format!("la  a0, string_{}", string_index));
-                        self.code_arr.push(format!("call print_string"));
+                let print_type: Option<Type> = operand::get_operand_type(token, symbol_table);
+                match (&token.token_type, print_type) {
+                    (TokenType::Keyword(keyword), _) => {
+                        // The value is already known, so just print its string
+                        let bool_string_index: usize = match keyword {
+                            Keywords::True => *self.string_history.get("true").unwrap(),
+                            _ => *self.string_history.get("false").unwrap()
+                        };
+                        self.code_arr.push(Instruction::La { rd: Register::A0, label: format!("string_{}", bool_string_index) });
+                        self.code_gen_print_call(&Type::String);
                     },
-                    TokenType::Keyword(keyword) => {
-                        match keyword {
-                            Keywords::True => {
-                                // Load the address for true
-                                self.code_arr.push(format!("la  a0, string_1"));
-                            },
-                            Keywords::False => {
-                                // Load the address for false
-                                self.code_arr.push(format!("la  a0, string_0"));
-                            },
-                            _ => error!("Received {:?} when expecting true or false for print keyword", keyword)
-                        }
-                        // Make the system call
-                        self.code_arr.push(format!("call print_string"));
+                    (_, Some(print_type)) => {
+                        // Every type is printed from a0
+                        self.load_operand(ast, children[0], symbol_table, Register::A0);
+                        self.code_gen_print_call(&print_type);
                     },
-                    _ => error!("Received {:?} when expecting id, digit, string, or keyword for print terminal", token)
+                    (_, None) => error!("Received {:?} when expecting id, digit, string, or keyword for print terminal", token)
                 }
             },
             SyntaxTreeNode::NonTerminalAst(non_terminal) => {
                 match non_terminal {
                     NonTerminalsAst::Add => {
                         // Generate the result of the addition expression
-                        self.code_gen_add(ast, children[0], symbol_table, true);
+                        self.code_gen_add(ast, children[0], symbol_table);
                         
                         // Move the contents in t0 to a0
-                        self.code_arr.push(format!("mv  a0, t0"));
-                        self.code_arr.push(format!("call print_int")); 
+                        self.code_arr.push(Instruction::Mv { rd: Register::A0, rs1: Register::T0 });
+                        self.code_gen_print_call(&Type::Int);
                     },
                     NonTerminalsAst::IsEq => {
                         // The result of the equality comparison is in a0
                         self.code_gen_compare(ast, children[0], symbol_table, true);
-                        self.code_arr.push(format!("call print_boolean"));
+                        self.code_gen_print_call(&Type::Boolean);
                     },
                     NonTerminalsAst::NotEq => {
                         self.code_gen_compare(ast, children[0], symbol_table, false);
-                        self.code_arr.push(format!("call print_boolean"));
+                        self.code_gen_print_call(&Type::Boolean);
                     },
-                    _ => error!("Received {:?} when expecting addition or boolean expression for nonterminal print", non_terminal)
+                    NonTerminalsAst::Index => {
+                        // Move the character from t0 to a0
+                        self.code_gen_index(ast, children[0], symbol_table);
+                        self.code_arr.push(Instruction::Mv { rd: Register::A0, rs1: Register::T0 });
+                        self.code_gen_print_call(&Type::Char);
+                    },
+                    NonTerminalsAst::Len => {
+                        self.code_gen_len(ast, children[0], symbol_table, Register::A0);
+                        self.code_gen_print_call(&Type::Int);
+                    },
+                    _ => error!("Received {:?} when expecting addition, length, boolean, or index expression for nonterminal print", non_terminal)
                 }
             },
             _ => error!("Received {:?} when expecting terminal or AST nonterminal for print in code gen", child)
         }
 
         // Add a new line for cleanliness
-        self.code_arr.push(format!("call print_new_line"));
+        self.code_arr.push(Instruction::Call { label: String::from("print_new_line") });
+    }
+
+    // Function to call the print function for a value of the given type, which has to already be in a0
+    fn code_gen_print_call(&mut self, print_type: &Type) {
+        match print_type {
+            Type::Int => self.code_arr.push(Instruction::Call { label: String::from("print_int") }),
+            Type::String => self.code_arr.push(Instruction::Call { label: String::from("print_string") }),
+            Type::Boolean => self.code_arr.push(Instruction::Call { label: String::from("print_boolean") }),
+            Type::Char => self.code_arr.push(Instruction::Call { label: String::from("print_char") })
+        }
+    }
+
+    // Function to load the value of a terminal into the register
+    // This is the number for ints, the address for strings, 1 or 0 for booleans, and the ASCII code for chars
+    fn load_operand(&mut self, ast: &SyntaxTree, operand_index: NodeIndex, symbol_table: &mut SymbolTable, register: Register) {
+        let token: &Token = match (*ast).graph.node_weight(operand_index).unwrap() {
+            SyntaxTreeNode::Terminal(token) => token,
+            operand => {
+                error!("Received {:?} when expecting a terminal operand", operand);
+                return;
+            }
+        };
+
+        match &token.token_type {
+            TokenType::Identifier(id_name) => {
+                // The address of the variable goes in t2, which never holds a value that is still needed
+                let id_entry: &SymbolTableEntry = symbol_table.get_symbol_with_context(&id_name, token.position).unwrap();
+                self.code_arr.push(Instruction::La { rd: Register::T2, label: self.get_id_label(&id_name, operand_index) });
+                match id_entry.symbol_type {
+                    // Integers, booleans, and chars are a byte and strings are the full address
+                    Type::Int | Type::Boolean | Type::Char => self.code_arr.push(Instruction::Lbu { rd: register, offset: 0, rs1: Register::T2 }),
+                    Type::String => self.code_arr.push(Instruction::Lwu { rd: register, offset: 0, rs1: Register::T2 })
+                }
+            },
+            TokenType::Digit(num) => self.code_arr.push(Instruction::Li { rd: register, imm: *num as i64 }),
+            TokenType::CharLiteral(c) => self.code_arr.push(Instruction::Li { rd: register, imm: *c as i64 }),
+            TokenType::Char(string) => {
+                let string_index: usize = self.store_string(&string);
+                self.code_arr.push(Instruction::La { rd: register, label: format!("string_{}", string_index) });
+            },
+            TokenType::Keyword(keyword) => {
+                match &keyword {
+                    Keywords::True => self.code_arr.push(Instruction::Li { rd: register, imm: 1 }),
+                    Keywords::False => self.code_arr.push(Instruction::Li { rd: register, imm: 0 }),
+                    _ => error!("Received {:?} when expecting true or false for keyword operand", keyword)
+                }
+            },
+            _ => error!("Received {:?} when expecting an id, digit, string, or keyword operand", token)
+        }
     }
 
     // Function to generate code for an addition statement
     // Result is left in t0
-    fn code_gen_add(&mut self, ast: &SyntaxTree, cur_index: NodeIndex, symbol_table: &mut SymbolTable, is_first: bool) {
+    fn code_gen_add(&mut self, ast: &SyntaxTree, cur_index: NodeIndex, symbol_table: &mut SymbolTable) {
         nexus_log::log(
             nexus_log::LogTypes::Debug,
-            nexus_log::LogSources::CodeGenerator,
+            nexus_log::LogSource::CODE_GENERATOR,
             format!("Starting code generation for addition expression in scope {}", symbol_table.cur_scope.unwrap())
         );
 
         // Get the child for addition
         let children: Vec<NodeIndex> = (*ast).graph.neighbors(cur_index).collect();
         let right_child: &SyntaxTreeNode = (*ast).graph.node_weight(children[0]).unwrap();
-        let left_child: &SyntaxTreeNode = (*ast).graph.node_weight(children[1]).unwrap();
+
+        // Additions are left associative, so the left side goes first and ends up in t0
+        self.code_gen_add_operand(ast, children[1], symbol_table, Register::T0);
 
         match right_child {
-            SyntaxTreeNode::Terminal(token) => {
-                match &token.token_type {
-                    TokenType::Digit(num) => {
-                        // Store right side digit in t0
-                        self.code_arr.push(format!("li  t1, {}", num));
-                    },
-                    TokenType::Identifier(id_name) => {
-                        // Get the address needed from memory for the identifier
-                        let value_id_entry: &SymbolTableEntry = symbol_table.get_symbol_with_context(&token.text, token.position).unwrap(); 
-                        
-                        // Load the variable's value into t0
-                        self.code_arr.push(format!("la  t2, {}_{}", id_name, value_id_entry.scope));
-                        self.code_arr.push(format!("lbu  t1, 0(t2)"));
-                    },
-                    _ => error!("Received {:?} when expecting digit or id for right side of addition", token)
-                }
+            // A grouped addition on the right uses t0 and t1, so the left side has to be saved
+            SyntaxTreeNode::NonTerminalAst(NonTerminalsAst::Add) => {
+                self.push_register(Register::T0);
+                self.code_gen_add(ast, children[0], symbol_table);
+                self.code_arr.push(Instruction::Mv { rd: Register::T1, rs1: Register::T0 });
+                self.pop_register(Register::T0);
             },
-            // Nonterminals are always add, so just call it
-            SyntaxTreeNode::NonTerminalAst(_) => self.code_gen_add(ast, children[0], symbol_table, false),
-            _ => error!("Received {:?} when expecting terminal or AST nonterminal for right addition value", right_child)
+            _ => self.code_gen_add_operand(ast, children[0], symbol_table, Register::T1)
         }
 
-        match left_child {
+        self.code_arr.push(Instruction::Add { rd: Register::T0, rs1: Register::T0, rs2: Register::T1 });
+
+        // Integers are a byte like on the 6502, so the sum wraps around instead of growing past the largest integer
+        let max_int: u32 = TargetConfig::new(Target::TargetRiscV).max_int;
+        self.code_arr.push(Instruction::Andi { rd: Register::T0, rs1: Register::T0, imm: max_int as i64 });
+    }
+
+    // Function to put an operand of an addition in the register
+    fn code_gen_add_operand(&mut self, ast: &SyntaxTree, operand_index: NodeIndex, symbol_table: &mut SymbolTable, register: Register) {
+        match (*ast).graph.node_weight(operand_index).unwrap() {
             SyntaxTreeNode::Terminal(token) => {
                 match &token.token_type {
-                    TokenType::Digit(num) => {
-                        // Load the number to t0
-                        self.code_arr.push(format!("li  t0, {}", num));
-                        if is_first {
-                            // If we are in the outermost add, then store the
-                            // result in t0
-                            self.code_arr.push(format!("add  t0, t0, t1"));
-                        } else {
-                            // Otherwise store it in t1 because there are still
-                            // more elements to add that will be loaded into t0
-                            self.code_arr.push(format!("add  t1, t0, t1"));
-                        }
-                    },
-                    _ => error!("Received {:?} when expecting a digit for left side of addition for code gen", token)
+                    TokenType::Digit(_) | TokenType::Identifier(_) => self.load_operand(ast, operand_index, symbol_table, register),
+                    _ => error!("Received {:?} when expecting digit or id for addition", token)
+                }
+            },
+            SyntaxTreeNode::NonTerminalAst(NonTerminalsAst::Add) => {
+                // The sum is always left in t0
+                self.code_gen_add(ast, operand_index, symbol_table);
+                if register != Register::T0 {
+                    self.code_arr.push(Instruction::Mv { rd: register, rs1: Register::T0 });
                 }
             },
-            _ => error!("Received {:?} when expecting a terminal for the left side of addition for code gen", left_child)
+            // The length does not touch t0 unless it goes there, so the left side of the addition is safe
+            SyntaxTreeNode::NonTerminalAst(NonTerminalsAst::Len) => self.code_gen_len(ast, operand_index, symbol_table, register),
+            operand => error!("Received {:?} when expecting terminal, addition, or length for addition operand", operand)
         }
     }
 
+    // Function to generate code for reading a character out of a string
+    // Result is left in t0
+    fn code_gen_index(&mut self, ast: &SyntaxTree, cur_index: NodeIndex, symbol_table: &mut SymbolTable) {
+        nexus_log::log(
+            nexus_log::LogTypes::Debug,
+            nexus_log::LogSource::CODE_GENERATOR,
+            format!("Starting code generation for index expression in scope {}", symbol_table.cur_scope.unwrap())
+        );
+
+        // The string is the second child and the index is the first
+        let children: Vec<NodeIndex> = (*ast).graph.neighbors(cur_index).collect();
+
+        // The sum is always left in t0, so the index goes there too
+        match (*ast).graph.node_weight(children[0]).unwrap() {
+            SyntaxTreeNode::Terminal(_) => self.load_operand(ast, children[0], symbol_table, Register::T0),
+            SyntaxTreeNode::NonTerminalAst(NonTerminalsAst::Add) => self.code_gen_add(ast, children[0], symbol_table),
+            SyntaxTreeNode::NonTerminalAst(NonTerminalsAst::Len) => self.code_gen_len(ast, children[0], symbol_table, Register::T0),
+            index_node => error!("Received {:?} when expecting a terminal, addition, or length for the index", index_node)
+        }
+
+        // The address of the string goes in t1
+        self.load_operand(ast, children[1], symbol_table, Register::T1);
+        self.code_arr.push(Instruction::Add { rd: Register::T0, rs1: Register::T0, rs2: Register::T1 });
+
+        // The characters start 2 bytes over, after the length
+        self.code_arr.push(Instruction::Lbu { rd: Register::T0, offset: 2, rs1: Register::T0 });
+    }
+
+    // Function to generate code for the length of a string
+    // Result is left in the register
+    fn code_gen_len(&mut self, ast: &SyntaxTree, cur_index: NodeIndex, symbol_table: &mut SymbolTable, register: Register) {
+        nexus_log::log(
+            nexus_log::LogTypes::Debug,
+            nexus_log::LogSource::CODE_GENERATOR,
+            format!("Starting code generation for length expression in scope {}", symbol_table.cur_scope.unwrap())
+        );
+
+        // Every string starts with its length as a halfword
+        let string_index: NodeIndex = (*ast).graph.neighbors(cur_index).next().unwrap();
+        self.load_operand(ast, string_index, symbol_table, register);
+        self.code_arr.push(Instruction::Lhu { rd: register, offset: 0, rs1: register });
+    }
+
     // Function to generate code for comparisons
     // Result is left in the Z flag and get_z_flag_vale function can be used
     // afterwards to place z flag value into the accumulator
     fn code_gen_compare(&mut self, ast: &SyntaxTree, cur_index: NodeIndex, symbol_table: &mut SymbolTable, is_eq: bool) {
         nexus_log::log(
             nexus_log::LogTypes::Debug,
-            nexus_log::LogSources::CodeGenerator,
+            nexus_log::LogSource::CODE_GENERATOR,
             format!("Starting code generation for comparison expression (is_eq = {}) in scope {}", is_eq, symbol_table.cur_scope.unwrap())
         );
 
@@ -686,50 +1040,19 @@ impl CodeGeneratorRiscV {
         let left_child: &SyntaxTreeNode = (*ast).graph.node_weight(children[1]).unwrap();
 
         match left_child {
-            SyntaxTreeNode::Terminal(token) => {
-                match &token.token_type {
-                    TokenType::Identifier(id_name) => {
-                        // Get the address needed from memory for the identifier
-                        let value_id_entry: &SymbolTableEntry = symbol_table.get_symbol_with_context(&token.text, token.position).unwrap(); 
-                        
-                        // Get the address of the variable
-                        self.code_arr.push(format!("la  t0, {}_{}", id_name, value_id_entry.scope));
-
-                        // Now store the value of the variable in a0
-                        match value_id_entry.symbol_type {
-                            Type::Int | Type::Boolean => {
-                                self.code_arr.push(format!("lbu  a0, 0(t0)"));
-                            },
-                            Type::String => {
-                                self.code_arr.push(format!("lwu  a0, 0(t0)"));
-                            }
-                        }
-                    },
-                    TokenType::Digit(num) => {
-                        // Store the digit in a0
-                        self.code_arr.push(format!("li  a0, {}", num));
-                    },
-                    TokenType::Char(string) => {
-                        // Store the address of the string in a0
-                        let string_index: usize = self.store_string(string);
-                        self.code_arr.push(format!("la  a0, string_{}", string_index));
-                    },
-                    TokenType::Keyword(keyword) => {
-                        match &keyword {
-                            Keywords::True => self.code_arr.push(format!("li  a0, 1")),
-                            Keywords::False => self.code_arr.push(format!("li  a0, 0")),
-                            _ => error!("Received {:?} when expecting true or false for keywords in boolean expression", keyword)
-                        }
-                    },
-                    _ => error!("Received {:?} when expecting an Id, digit, char, or keyword for left side of boolean expression", token)
-                }
+            // Load the left side into a0
+            SyntaxTreeNode::Terminal(_) => self.load_operand(ast, children[1], symbol_table, Register::A0),
+            SyntaxTreeNode::NonTerminalAst(_) if self.hoisted_values.contains_key(&children[1]) => {
+                // The value was already computed before the loop, so just load it
+                self.code_arr.push(Instruction::La { rd: Register::T0, label: self.hoisted_values.get(&children[1]).unwrap().to_owned() });
+                self.code_arr.push(Instruction::Lbu { rd: Register::A0, offset: 0, rs1: Register::T0 });
             },
             SyntaxTreeNode::NonTerminalAst(non_terminal) => {
                 match &non_terminal {
                     NonTerminalsAst::Add => {
                         // Run the addition and move the result from t0 to a0
-                        self.code_gen_add(ast, children[1], symbol_table, true);
-                        self.code_arr.push(format!("mv  a0, t0"));
+                        self.code_gen_add(ast, children[1], symbol_table);
+                        self.code_arr.push(Instruction::Mv { rd: Register::A0, rs1: Register::T0 });
                     },
                     NonTerminalsAst::IsEq => {
                         self.code_gen_compare(ast, children[1], symbol_table, true);
@@ -737,94 +1060,84 @@ impl CodeGeneratorRiscV {
                     NonTerminalsAst::NotEq => {
                         self.code_gen_compare(ast, children[1], symbol_table, false);
                     },
-                    _ => error!("Received {:?} for left side of nonterminal boolean expression, when expected Add, IsEq, or NotEq", non_terminal)
+                    NonTerminalsAst::Index => {
+                        self.code_gen_index(ast, children[1], symbol_table);
+                        self.code_arr.push(Instruction::Mv { rd: Register::A0, rs1: Register::T0 });
+                    },
+                    NonTerminalsAst::Len => self.code_gen_len(ast, children[1], symbol_table, Register::A0),
+                    _ => error!("Received {:?} for left side of nonterminal boolean expression, when expected Add, IsEq, NotEq, Index, or Len", non_terminal)
                 }
             },
             _ => error!("Received {:?} when expected terminal or AST nonterminal for left side of comparison in code gen", left_child)
         }
 
         match right_child {
-            SyntaxTreeNode::Terminal(token) => {
-                match &token.token_type {
-                    TokenType::Identifier(id_name) => {
-                        // Get the address needed from memory for the identifier
-                        let value_id_entry: &SymbolTableEntry = symbol_table.get_symbol_with_context(&token.text, token.position).unwrap(); 
-
-                        // Get the address of the variable
-                        self.code_arr.push(format!("la  t0, {}_{}", id_name, value_id_entry.scope));
-
-                        // Now store the value of the variable in a1
-                        match value_id_entry.symbol_type {
-                            Type::Int | Type::Boolean => {
-                                self.code_arr.push(format!("lbu  a1, 0(t0)"));
-                            },
-                            Type::String => {
-                                self.code_arr.push(format!("lwu  a1, 0(t0)"));
-                            }
-                        }
-                    },
-                    TokenType::Digit(num) => {
-                        // Store the digit in a1
-                        self.code_arr.push(format!("li  a1, {}", num));
-                    },
-                    TokenType::Char(string) => {
-                        // Store the address of the string in a1
-                        let string_index: usize = self.store_string(string);
-                        self.code_arr.push(format!("la  a1, string_{}", string_index));
-                    },
-                    TokenType::Keyword(keyword) => {
-                        match &keyword {
-                            Keywords::True => self.code_arr.push(format!("li  a1, 1")),
-                            Keywords::False => self.code_arr.push(format!("li  a1, 0")),
-                            _ => error!("Received {:?} when expecting true or false for keywords in boolean expression", keyword)
-                        }
-                    },
-                    _ => error!("Received {:?} when expecting an Id, digit, char, or keyword for left side of boolean expression", token)
-                }
+            // Load the right side into a1, which does not touch the left side in a0
+            SyntaxTreeNode::Terminal(_) => self.load_operand(ast, children[0], symbol_table, Register::A1),
+            SyntaxTreeNode::NonTerminalAst(_) if self.hoisted_values.contains_key(&children[0]) => {
+                // Loading the hoisted value does not touch a0, so the left side is safe
+                self.code_arr.push(Instruction::La { rd: Register::T0, label: self.hoisted_values.get(&children[0]).unwrap().to_owned() });
+                self.code_arr.push(Instruction::Lbu { rd: Register::A1, offset: 0, rs1: Register::T0 });
             },
             SyntaxTreeNode::NonTerminalAst(non_terminal) => {
                 // We have a nonterminal, so store the left side on the stack so there is no
                 // conflict with the right side evaluation
-                self.code_arr.push(format!("addi  sp, sp, -1"));
-                self.code_arr.push(format!("sb  a0, 0(sp)"));
+                self.push_register(Register::A0);
 
                 match &non_terminal {
                     NonTerminalsAst::Add => {
                         // Do the add and move the result from t0 to a1
-                        self.code_gen_add(ast, children[0], symbol_table, true);
-                        self.code_arr.push(format!("mv  a1, t0"));
+                        self.code_gen_add(ast, children[0], symbol_table);
+                        self.code_arr.push(Instruction::Mv { rd: Register::A1, rs1: Register::T0 });
                     },
                     NonTerminalsAst::IsEq => {
                         // Move the result over to a1
                         self.code_gen_compare(ast, children[0], symbol_table, true);
-                        self.code_arr.push(format!("mv  a1, a0"));
+                        self.code_arr.push(Instruction::Mv { rd: Register::A1, rs1: Register::A0 });
                     },
                     NonTerminalsAst::NotEq => {
                         self.code_gen_compare(ast, children[0], symbol_table, false);
-                        self.code_arr.push(format!("mv  a1, a0"));
+                        self.code_arr.push(Instruction::Mv { rd: Register::A1, rs1: Register::A0 });
+                    },
+                    NonTerminalsAst::Index => {
+                        self.code_gen_index(ast, children[0], symbol_table);
+                        self.code_arr.push(Instruction::Mv { rd: Register::A1, rs1: Register::T0 });
                     },
-                    _ => error!("Received {:?} for right side of nonterminal boolean expression, when expected Add, IsEq, or NotEq", non_terminal)
+                    NonTerminalsAst::Len => self.code_gen_len(ast, children[0], symbol_table, Register::A1),
+                    _ => error!("Received {:?} for right side of nonterminal boolean expression, when expected Add, IsEq, NotEq, Index, or Len", non_terminal)
                 }
 
                 // Get the left side back to a0
-                self.code_arr.push(format!("lbu  a0, 0(sp)"));
-                self.code_arr.push(format!("addi  sp, sp, 1"));
+                self.pop_register(Register::A0);
             },
-            _ => error!("Received {:?} when expected terminal or AST nonterminal for left side of comparison in code gen", left_child)
+            _ => error!("Received {:?} when expected terminal or AST nonterminal for right side of comparison in code gen", right_child)
         }
 
         // Perform the appropriate comparison
         if is_eq {
-            self.code_arr.push(format!("call compare_eq"));
+            self.code_arr.push(Instruction::Call { label: String::from("compare_eq") });
         } else {
-            self.code_arr.push(format!("call compare_neq"));
+            self.code_arr.push(Instruction::Call { label: String::from("compare_neq") });
         }
     }
 
+    // Saves the register on the stack, which always moves by a full word so sp stays aligned
+    // no matter how deep the comparisons are nested
+    fn push_register(&mut self, register: Register) {
+        self.code_arr.push(Instruction::Addi { rd: Register::Sp, rs1: Register::Sp, imm: -4 });
+        self.code_arr.push(Instruction::Sw { rs2: register, offset: 0, rs1: Register::Sp });
+    }
+
+    // Restores the register from the last push_register
+    fn pop_register(&mut self, register: Register) {
+        self.code_arr.push(Instruction::Lw { rd: register, offset: 0, rs1: Register::Sp });
+        self.code_arr.push(Instruction::Addi { rd: Register::Sp, rs1: Register::Sp, imm: 4 });
+    }
+
     fn code_gen_if(&mut self, ast: &SyntaxTree, cur_index: NodeIndex, symbol_table: &mut SymbolTable) {
         nexus_log::log(
             nexus_log::LogTypes::Debug,
-            nexus_log::LogSources::CodeGenerator,
+            nexus_log::LogSource::CODE_GENERATOR,
             format!("Starting code generation for if statement in scope {}", symbol_table.cur_scope.unwrap())
         );
 
@@ -844,7 +1157,7 @@ impl CodeGeneratorRiscV {
                     _ => error!("Received {:?} when expecting IsEq or NotEq for nonterminal if expression", non_terminal)
                 }
                 // Add the branch code
-                self.code_arr.push(format!("beq  a0, zero, if_end_{}", if_index)); 
+                self.code_arr.push(Instruction::Beq { rs1: Register::A0, rs2: Register::Zero, label: format!("if_end_{}", if_index) }); 
                 self.if_count += 1;
             },
             SyntaxTreeNode::Terminal(token) => {
@@ -868,14 +1181,14 @@ impl CodeGeneratorRiscV {
         // Only add the label if it is needed
         if if_index != self.if_count {
             // Add the label for the end of the if statement
-            self.code_arr.push(format!("if_end_{}:", if_index));
+            self.code_arr.push(Instruction::Label(format!("if_end_{}", if_index)));
         }
     }
 
     fn code_gen_while(&mut self, ast: &SyntaxTree, cur_index: NodeIndex, symbol_table: &mut SymbolTable) {
          nexus_log::log(
             nexus_log::LogTypes::Debug,
-            nexus_log::LogSources::CodeGenerator,
+            nexus_log::LogSource::CODE_GENERATOR,
             format!("Starting code generation for while statement in scope {}", symbol_table.cur_scope.unwrap())
         );
 
@@ -887,7 +1200,35 @@ impl CodeGeneratorRiscV {
         let while_index: usize = self.while_count.to_owned();
         self.while_count += 1;
 
-        self.code_arr.push(format!("while_start_{}:", while_index));
+        // Anything that does not change in the loop only has to be computed once
+        for expr_index in optimizer::find_loop_invariants(ast, cur_index) {
+            match (*ast).graph.node_weight(expr_index).unwrap() {
+                SyntaxTreeNode::NonTerminalAst(NonTerminalsAst::Add) => {
+                    // Move the sum over to a0 to match the comparisons
+                    self.code_gen_add(ast, expr_index, symbol_table);
+                    self.code_arr.push(Instruction::Mv { rd: Register::A0, rs1: Register::T0 });
+                },
+                SyntaxTreeNode::NonTerminalAst(NonTerminalsAst::IsEq) => self.code_gen_compare(ast, expr_index, symbol_table, true),
+                SyntaxTreeNode::NonTerminalAst(NonTerminalsAst::NotEq) => self.code_gen_compare(ast, expr_index, symbol_table, false),
+                SyntaxTreeNode::NonTerminalAst(NonTerminalsAst::Index) => {
+                    self.code_gen_index(ast, expr_index, symbol_table);
+                    self.code_arr.push(Instruction::Mv { rd: Register::A0, rs1: Register::T0 });
+                },
+                SyntaxTreeNode::NonTerminalAst(NonTerminalsAst::Len) => self.code_gen_len(ast, expr_index, symbol_table, Register::A0),
+                expr_node => error!("Received {:?} when expecting Add, IsEq, NotEq, Index, or Len for a hoisted expression", expr_node)
+            }
+
+            // Store the value in its own spot so it survives the loop body
+            let hoisted_label: String = format!("hoisted_{}", expr_index.index());
+            self.static_arr.push(format!("{}: .byte 0", hoisted_label));
+            self.code_arr.push(Instruction::La { rd: Register::T0, label: hoisted_label.to_owned() });
+            self.code_arr.push(Instruction::Sb { rs2: Register::A0, offset: 0, rs1: Register::T0 });
+
+            self.hoisted_values.insert(expr_index, hoisted_label);
+        }
+
+        let loop_start: usize = self.code_arr.len();
+        self.code_arr.push(Instruction::Label(format!("while_start_{}", while_index)));
 
         match left_child {
             SyntaxTreeNode::NonTerminalAst(non_terminal) => {
@@ -899,7 +1240,7 @@ impl CodeGeneratorRiscV {
                     _ => error!("Received {:?} when expecting IsEq or NotEq for nonterminal if expression", non_terminal)
                 }
                 // Add the branch code
-                self.code_arr.push(format!("beq  a0, zero, while_end_{}", while_index));
+                self.code_arr.push(Instruction::Beq { rs1: Register::A0, rs2: Register::Zero, label: format!("while_end_{}", while_index) });
             },
             SyntaxTreeNode::Terminal(token) => {
                 match &token.token_type {
@@ -920,13 +1261,21 @@ impl CodeGeneratorRiscV {
         self.code_gen_block(ast, children[0], symbol_table);
 
         // Jump back to the condition
-        self.code_arr.push(format!("j  while_start_{}", while_index));
+        self.code_arr.push(Instruction::J { label: format!("while_start_{}", while_index) });
 
         // Label for the end of the while block
-        self.code_arr.push(format!("while_end_{}:", while_index));
+        self.code_arr.push(Instruction::Label(format!("while_end_{}", while_index)));
+
+        self.loop_ranges.push(LoopRange {
+            start: loop_start,
+            end: self.code_arr.len(),
+            trip_count: cost_model::get_constant_trip_count(ast, cur_index)
+        });
     }
 
-    fn display_code(&mut self, program_number: &u32) {
+    // Function to show the assembly in the page, which has to be done on the main thread
+    #[cfg(feature = "ui")]
+    pub fn display_code(&mut self, program_number: &u32) {
         let window: Window = web_sys::window().expect("Should be able to get the window");
         let document: Document = window.document().expect("Should be able to get the document");
 
@@ -1001,28 +1350,34 @@ impl CodeGeneratorRiscV {
         let code_elem: Element = document.create_element("p").expect("Should be able to create the element");
         let code_elem_class_list: DomTokenList = code_elem.class_list();
         code_elem_class_list.add_2("overflow-auto", "code-text").expect("Should be able to add the classes");
-        code_elem.set_inner_html(&code_str);
-
-        // Replace all of the breaks with newlines for the clipboard copy
-        let code_str_clipboard: String = code_str.as_str().replace("<br>", "\n");
+        code_elem.set_inner_html(&Self::create_annotated_code(&code_str));
 
         display_area_div.append_child(&code_elem).expect("Should be able to add the child node");
 
-        // This is the button to copy to the clipboard
-        let copy_btn: Element = document.create_element("button").expect("Should be able to create the element");
-        copy_btn.set_inner_html("Copy to Clipboard");
-        copy_btn.set_class_name("copy-btn");
-        display_area_div.append_child(&copy_btn).expect("Should be able to add the child node");
+        // Show the cost of each statement below the code
+        cost_model::display_cost_table(&document, &display_area_div, &self.get_statement_costs(), program_number, "instructions");
 
-        // Create a function that will be used as the event listener and add it to the copy button
-        let copy_btn_fn: Closure<dyn FnMut()> = Closure::wrap(Box::new(move || {
-            // Call the JS function that handles the clipboard
-            set_clipboard(&code_str_clipboard);
-        }) as Box<dyn FnMut()>);
-        copy_btn.add_event_listener_with_callback("click", copy_btn_fn.as_ref().unchecked_ref()).expect("Should be able to add the event listener");
-        copy_btn_fn.forget();
+        // The clipboard and the download get the plain text of the assembly
+        output::add_output_buttons(&document, &display_area_div, code_str, format!("program{}.s", *program_number));
 
         // Add the div to the pane
         content_area.append_child(&display_area_div).expect("Should be able to add the child node");
     }
 }
+
+// Function to write the string so the assembler reads back the same bytes inside of the quotes of .ascii
+// Quotes and backslashes are escaped, and every byte that is not printable ASCII is written in octal
+pub fn escape_assembly_string(string: &str) -> String {
+    let mut escaped: String = String::new();
+    for byte in string.as_bytes().iter() {
+        match *byte {
+            b'"' => escaped.push_str("\\\""),
+            b'\\' => escaped.push_str("\\\\"),
+            b'\n' => escaped.push_str("\\n"),
+            b'\t' => escaped.push_str("\\t"),
+            0x20..=0x7E => escaped.push(*byte as char),
+            _ => escaped.push_str(&format!("\\{:03o}", byte))
+        }
+    }
+    return escaped;
+}
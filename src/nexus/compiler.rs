@@ -1,230 +1,639 @@
-use crate::util::{nexus_log, target::Target};
-use crate::nexus::{lexer::Lexer, token::Token, parser::Parser, semantic_analyzer::SemanticAnalyzer, syntax_tree::SyntaxTree};
+use crate::util::{nexus_log::{self, RecordedLog}, build_info::BuildInfo, course_stage::CourseStage, language_profile::LanguageProfile, target::{Target, TargetConfig}, position::Position};
+use crate::nexus::{artifacts::{self, GeneratedCode}, coverage::CoverageJson, source_map::SourceMapJson, statistics, semantic_tokens::{self, SemanticToken}, preprocessor::Preprocessor, lexer::Lexer, token::Token, parser::Parser, program_cache, semantic_analyzer::SemanticAnalysisResult, interpreter::{self, ProgramRun}, lint::Linter, syntax_tree::SyntaxTree, progress::{ProgressReporter, ProgressPhase}};
+use crate::nexus::pipeline::{self, CompileOptions, ParsedProgram, ProgramOutput, TargetCodeGenerator};
 use crate::nexus::code_generator_6502::CodeGenerator6502;
-use crate::nexus::code_generator_riscv::CodeGeneratorRiscV;
-use crate::editor::buttons;
+use crate::editor::{buttons, issues, history};
+
+// What lexing and parsing a program produced
+// The logs are recorded so they can be shown with the logs of the rest of the phases once those are done
+struct FrontEndOutput {
+    logs: Vec<RecordedLog>,
+    lex_res: Result<Vec<Token>, ()>,
+
+    // None if the program could not be lexed
+    parse_res: Option<Result<SyntaxTree, ()>>
+}
 
 // Function to compile multiple programs
 pub fn compile(source_code: &str) {
-    let mut lexer: Lexer = Lexer::new(source_code);
+    compile_source(source_code, false);
+}
+
+// Function to analyze multiple programs and run them with the interpreter instead of generating code
+pub fn run(source_code: &str) {
+    compile_source(source_code, true);
+}
+
+// Function to compile only the program that the line (starting at 1) is in, or the last program if the line is after all of them
+// If the programs cannot be split, everything is compiled so the reason is logged like a normal compilation
+pub fn compile_program_at_line(source_code: &str, line_number: usize) {
+    match get_program_at_line(source_code, line_number) {
+        Some(program_code) => compile(&program_code),
+        None => compile(source_code)
+    }
+}
+
+// Function to get the code of the program that the line is in
+// The program is moved down to where it is in the editor so the positions in the messages still match
+fn get_program_at_line(source_code: &str, line_number: usize) -> Option<String> {
+    // Splitting the programs is not part of the compilation, so none of its logs are shown
+    nexus_log::start_capture();
+
+    let mut preprocessor: Preprocessor = Preprocessor::new();
+    let mut program_code: Option<String> = None;
+    if let Ok(preprocessed_code) = preprocessor.expand_defines(source_code) {
+        let mut lexer: Lexer = Lexer::new(&preprocessed_code);
+        while lexer.has_program_to_lex() {
+            let (start, start_position): (usize, Position) = lexer.get_cursor();
+            let _ = lexer.lex_program();
+            let (end, end_position): (usize, Position) = lexer.get_cursor();
+            if end == start {
+                break;
+            }
+
+            program_code = Some(format!("{}{}{}", "\n".repeat(start_position.line - 1), " ".repeat(start_position.col - 1), lexer.get_source(start, end).unwrap_or_default()));
+            if end_position.line >= line_number {
+                break;
+            }
+        }
+    }
+
+    nexus_log::stop_capture();
+    return program_code;
+}
+
+fn compile_source(source_code: &str, is_interpreted: bool) {
+    // The target is fixed for the entire compilation
+    let mut target_config: TargetConfig = TargetConfig::new(buttons::get_current_target());
+
+    let mut preprocessor: Preprocessor = Preprocessor::new();
     let mut parser: Parser = Parser::new();
-    let mut semantic_analyzer: SemanticAnalyzer = SemanticAnalyzer::new();
-    let mut code_generator_6502: CodeGenerator6502 = CodeGenerator6502::new();
-    let mut code_generator_riscv: CodeGeneratorRiscV = CodeGeneratorRiscV::new();
+    let mut progress_reporter: ProgressReporter = ProgressReporter::new();
 
     // Clean up the output area
     SyntaxTree::clear_display();
     CodeGenerator6502::clear_display();
+    statistics::clear_display();
+    issues::clear_display();
+    semantic_tokens::clear_display();
     nexus_log::clear_logs();
+    artifacts::clear_artifacts();
     nexus_log::log(
         nexus_log::LogTypes::Info,
-        nexus_log::LogSources::Nexus,
-        String::from("Nexus compile called")
+        nexus_log::LogSource::NEXUS,
+        String::from(if is_interpreted { "Nexus run called" } else { "Nexus compile called" })
     );
 
-    // Keep track of the number of programs
-    let mut program_number: u32 = 0;
-
-    // Go through each program
-    while lexer.has_program_to_lex() {
-        program_number += 1;
-
-        nexus_log::insert_empty_line();
-
-        // Log the program we are on
-        nexus_log::log(
-            nexus_log::LogTypes::Info,
-            nexus_log::LogSources::Nexus,
-            format!("Compiling program {}", program_number)
-        );
-        nexus_log::insert_empty_line();
-
-        // Log the program we are lexing
-        nexus_log::log(
-            nexus_log::LogTypes::Info,
-            nexus_log::LogSources::Lexer,
-            format!("Lexing program {}", program_number)
-        );
-
-        // Lex the program
-        let lex_res: Result<Vec<Token>, ()> = lexer.lex_program();
-
-        nexus_log::insert_empty_line();
-
-        if lex_res.is_err() {
+    // Only the 6502 has a fixed amount of memory to split between the code and the heap, and an output device in it
+    if target_config.target == Target::Target6502 {
+        let (reserved_cells_input, heap_split_input): (String, String) = buttons::get_memory_layout_input();
+        let (int_out_input, string_out_input): (String, String) = buttons::get_mmio_input();
+        let layout_res: Result<(), String> = target_config.set_memory_layout(&reserved_cells_input, &heap_split_input)
+            .and_then(|_| target_config.set_mmio(&int_out_input, &string_out_input));
+        if let Err(message) = layout_res {
+            nexus_log::log(
+                nexus_log::LogTypes::Error,
+                nexus_log::LogSource::NEXUS,
+                message
+            );
             nexus_log::log(
                 nexus_log::LogTypes::Warning,
-                nexus_log::LogSources::Parser,
-                String::from("Parsing skipped due to lex failure")
+                nexus_log::LogSource::NEXUS,
+                String::from("Compilation skipped due to an invalid memory layout")
             );
+            progress_reporter.finish_compilation();
+            return;
+        }
+    }
 
+    // The system calls only change the RISC-V assembly
+    if target_config.target == Target::TargetRiscV {
+        let (convention_input, write_input, exit_input): (String, String, String) = buttons::get_syscall_input();
+        if let Err(message) = target_config.set_syscall_convention(&convention_input, &write_input, &exit_input) {
             nexus_log::log(
-                nexus_log::LogTypes::Warning,
-                nexus_log::LogSources::Nexus,
-                String::from("CST display skipped due to lex failure")
+                nexus_log::LogTypes::Error,
+                nexus_log::LogSource::NEXUS,
+                message
             );
-            
             nexus_log::log(
                 nexus_log::LogTypes::Warning,
-                nexus_log::LogSources::Nexus,
-                String::from("AST generation and display skipped due to lex failure")
+                nexus_log::LogSource::NEXUS,
+                String::from("Compilation skipped due to invalid system calls")
             );
+            progress_reporter.finish_compilation();
+            return;
+        }
+    }
+
+    if let Err(message) = target_config.set_max_nesting_depth(&buttons::get_max_nesting_depth_input()) {
+        nexus_log::log(
+            nexus_log::LogTypes::Error,
+            nexus_log::LogSource::NEXUS,
+            message
+        );
+        nexus_log::log(
+            nexus_log::LogTypes::Warning,
+            nexus_log::LogSource::NEXUS,
+            String::from("Compilation skipped due to an invalid nesting limit")
+        );
+        progress_reporter.finish_compilation();
+        return;
+    }
+
+    // The limit depends on the heap, so the memory layout has to be set first
+    if let Err(message) = target_config.set_max_string_length(&buttons::get_max_string_length_input()) {
+        nexus_log::log(
+            nexus_log::LogTypes::Error,
+            nexus_log::LogSource::NEXUS,
+            message
+        );
+        nexus_log::log(
+            nexus_log::LogTypes::Warning,
+            nexus_log::LogSource::NEXUS,
+            String::from("Compilation skipped due to an invalid string length limit")
+        );
+        progress_reporter.finish_compilation();
+        return;
+    }
 
+    // The language profile changes what the lexer and parser accept, so it is needed before anything is lexed
+    let language_profile: LanguageProfile = match LanguageProfile::from_inputs(&buttons::get_language_profile_input(), &buttons::get_identifier_charset_input()) {
+        Ok(language_profile) => language_profile,
+        Err(message) => {
             nexus_log::log(
-                nexus_log::LogTypes::Warning,
-                nexus_log::LogSources::SemanticAnalyzer,
-                String::from("Semantic analysis skipped due to lex failure")
+                nexus_log::LogTypes::Error,
+                nexus_log::LogSource::NEXUS,
+                message
             );
-
             nexus_log::log(
                 nexus_log::LogTypes::Warning,
-                nexus_log::LogSources::Nexus,
-                String::from("Symbol table display skipped due to lex failure")
+                nexus_log::LogSource::NEXUS,
+                String::from("Compilation skipped due to an invalid language profile")
             );
-            
+            progress_reporter.finish_compilation();
+            return;
+        }
+    };
+    preprocessor.set_language_profile(&language_profile);
+    parser.set_language_profile(&language_profile);
+
+    // Phases the class has not reached yet are not run at all, no matter what the page shows
+    let course_stage: CourseStage = match CourseStage::from_name(&buttons::get_course_stage_input()) {
+        Ok(course_stage) => course_stage,
+        Err(message) => {
             nexus_log::log(
-                nexus_log::LogTypes::Warning,
-                nexus_log::LogSources::Nexus,
-                String::from("Code generation skipped due to lex failure")
+                nexus_log::LogTypes::Error,
+                nexus_log::LogSource::NEXUS,
+                message
             );
-
             nexus_log::log(
                 nexus_log::LogTypes::Warning,
-                nexus_log::LogSources::Nexus,
-                String::from("Executable image display skipped due to lex failure")
+                nexus_log::LogSource::NEXUS,
+                String::from("Compilation skipped due to an invalid course stage")
             );
-
-            // No need to move on if lex failed, so can go to next program
-            continue;
+            progress_reporter.finish_compilation();
+            return;
         }
-
-        // Log the program we are lexing
+    };
+    if is_interpreted && !course_stage.includes(CourseStage::CodeGeneration) {
+        nexus_log::log(
+            nexus_log::LogTypes::Warning,
+            nexus_log::LogSource::NEXUS,
+            format!("Run skipped because the course stage ends with {}", course_stage.get_phase_name())
+        );
+        progress_reporter.finish_compilation();
+        return;
+    }
+    if course_stage != CourseStage::CodeGeneration {
         nexus_log::log(
             nexus_log::LogTypes::Info,
-            nexus_log::LogSources::Parser,
-            format!("Parsing program {}", program_number)
+            nexus_log::LogSource::NEXUS,
+            format!("Only running the phases through {} for the [ {} ] course stage", course_stage.get_phase_name(), course_stage)
         );
+    }
 
-        let token_stream: Vec<Token> = lex_res.unwrap();
-        let parse_res: Result<SyntaxTree, ()> = parser.parse_program(&token_stream);
+    // Every program gets the same options and build information
+    let compile_options: CompileOptions = CompileOptions {
+        target_config: target_config.to_owned(),
+        optimize_size: buttons::is_optimize_size_checked(),
+        scope_comments: buttons::is_scope_comments_checked(),
+        lints: Linter::new().get_lint_names().into_iter()
+            .map(|lint_name| (lint_name, buttons::is_lint_enabled(lint_name)))
+            .collect(),
+        build_info: Some(BuildInfo::new(&target_config, &language_profile, buttons::is_optimize_size_checked(), source_code)),
+        is_interpreted: is_interpreted,
+        course_stage: course_stage,
+        language_profile: language_profile.to_owned()
+    };
+
+    // Constants have to be expanded before anything can be lexed
+    let preprocess_res: Result<String, ()> = preprocessor.expand_defines(source_code);
+    if preprocess_res.is_err() {
+        nexus_log::log(
+            nexus_log::LogTypes::Warning,
+            nexus_log::LogSource::NEXUS,
+            String::from("Compilation skipped due to preprocessing failure")
+        );
+        progress_reporter.finish_compilation();
+        return;
+    }
+    let preprocessed_code: String = preprocess_res.unwrap();
+
+    // The lexer has to go in order to find where each program ends, and the cache reuses both phases, so they run first
+    program_cache::start_compilation(&language_profile);
+    let mut lexer: Lexer = Lexer::new(&preprocessed_code);
+    lexer.set_language_profile(&language_profile);
+    lexer.set_column_map(preprocessor.get_column_map());
+    let mut front_end_outputs: Vec<FrontEndOutput> = Vec::new();
+    while lexer.has_program_to_lex() {
+        let program_number: u32 = front_end_outputs.len() as u32 + 1;
+        front_end_outputs.push(lex_and_parse(&mut lexer, &mut parser, program_number, course_stage));
+    }
 
-        if parse_res.is_err() {
-            nexus_log::insert_empty_line();
+    // The rest of the phases do not depend on any other program, so they can all be done at once
+    let is_analyzed: bool = course_stage.includes(CourseStage::SemanticAnalysis);
+    let parsed_programs: Vec<ParsedProgram> = front_end_outputs.iter().enumerate()
+        .filter_map(|(i, front_end_output)| match (&front_end_output.lex_res, &front_end_output.parse_res) {
+            (Ok(token_stream), Some(Ok(_))) if is_analyzed => Some(ParsedProgram {
+                program_number: i as u32 + 1,
+                token_stream: token_stream.to_owned()
+            }),
+            _ => None
+        })
+        .collect();
+    let mut program_outputs: std::vec::IntoIter<ProgramOutput> = pipeline::compile_programs(&parsed_programs, &compile_options).into_iter();
+
+    // The results are shown in order on the page, which is only on the main thread
+    // The identifiers of every program are colored together once all of them are analyzed
+    let mut all_semantic_tokens: Vec<SemanticToken> = Vec::new();
+    progress_reporter.start_compilation(front_end_outputs.len() as u32);
+    for (i, front_end_output) in front_end_outputs.into_iter().enumerate() {
+        let program_number: u32 = i as u32 + 1;
+        artifacts::new_program();
+
+        if show_front_end(front_end_output, program_number, &mut progress_reporter) && is_analyzed {
+            let program_output: ProgramOutput = program_outputs.next().expect("Every parsed program should have an output");
+            show_program_output(program_output, is_interpreted, &mut progress_reporter, &mut all_semantic_tokens);
+        }
+    }
 
-            // Do not show CST unless parse is successful
-            nexus_log::log(
-                nexus_log::LogTypes::Warning,
-                nexus_log::LogSources::Nexus,
-                String::from("CST display skipped due to parse failure")
-            );
-            
-            nexus_log::log(
-                nexus_log::LogTypes::Warning,
-                nexus_log::LogSources::Nexus,
-                String::from("AST generation and display skipped due to parse failure")
-            );
+    statistics::display_statistics();
+    issues::display_issues();
+    semantic_tokens::display_semantic_tokens(&all_semantic_tokens);
+    history::record_compilation(source_code, is_interpreted);
+    progress_reporter.finish_compilation();
+}
 
-            nexus_log::log(
-                nexus_log::LogTypes::Warning,
-                nexus_log::LogSources::SemanticAnalyzer,
-                String::from("Semantic analysis skipped due to parse failure")
-            );
+// Function to lex and parse the next program with its logs recorded
+fn lex_and_parse(lexer: &mut Lexer, parser: &mut Parser, program_number: u32, course_stage: CourseStage) -> FrontEndOutput {
+    nexus_log::start_recording();
+    nexus_log::insert_empty_line();
 
-            nexus_log::log(
-                nexus_log::LogTypes::Warning,
-                nexus_log::LogSources::Nexus,
-                String::from("Symbol table display skipped due to parse failure")
-            );
+    // Log the program we are on
+    nexus_log::log(
+        nexus_log::LogTypes::Info,
+        nexus_log::LogSource::NEXUS,
+        format!("Compiling program {}", program_number)
+    );
+    nexus_log::insert_empty_line();
 
-            nexus_log::log(
-                nexus_log::LogTypes::Warning,
-                nexus_log::LogSources::Nexus,
-                String::from("Code generation skipped due to parse failure")
-            );
+    // Log the program we are lexing
+    nexus_log::log(
+        nexus_log::LogTypes::Info,
+        nexus_log::LogSource::LEXER,
+        format!("Lexing program {}", program_number)
+    );
 
-            nexus_log::log(
-                nexus_log::LogTypes::Warning,
-                nexus_log::LogSources::Nexus,
-                String::from("Executable image display skipped due to parse failure")
-            );
+    // Lex the program
+    let lex_res: Result<Vec<Token>, ()> = program_cache::lex_program(lexer);
 
-            continue;
-        }
+    nexus_log::insert_empty_line();
 
+    if lex_res.is_err() {
         nexus_log::log(
-            nexus_log::LogTypes::Info,
-            nexus_log::LogSources::Nexus,
-            format!("CST display for program {} is below", program_number)
+            nexus_log::LogTypes::Warning,
+            nexus_log::LogSource::PARSER,
+            String::from("Parsing skipped due to lex failure")
         );
-        let cst: SyntaxTree = parse_res.unwrap();
-        cst.display(&program_number);
 
+        nexus_log::log(
+            nexus_log::LogTypes::Warning,
+            nexus_log::LogSource::NEXUS,
+            String::from("CST display skipped due to lex failure")
+        );
+        
+        nexus_log::log(
+            nexus_log::LogTypes::Warning,
+            nexus_log::LogSource::NEXUS,
+            String::from("AST generation and display skipped due to lex failure")
+        );
+
+        nexus_log::log(
+            nexus_log::LogTypes::Warning,
+            nexus_log::LogSource::SEMANTIC_ANALYZER,
+            String::from("Semantic analysis skipped due to lex failure")
+        );
+
+        nexus_log::log(
+            nexus_log::LogTypes::Warning,
+            nexus_log::LogSource::NEXUS,
+            String::from("Symbol table display skipped due to lex failure")
+        );
+        
+        nexus_log::log(
+            nexus_log::LogTypes::Warning,
+            nexus_log::LogSource::NEXUS,
+            String::from("Code generation skipped due to lex failure")
+        );
+
+        nexus_log::log(
+            nexus_log::LogTypes::Warning,
+            nexus_log::LogSource::NEXUS,
+            String::from("Executable image display skipped due to lex failure")
+        );
+
+        // No need to move on if lex failed, so can go to next program
+        return FrontEndOutput {
+            logs: nexus_log::stop_recording(),
+            lex_res: lex_res,
+            parse_res: None
+        };
+    }
+
+    // The lexer still has to go through every program to find where the next one starts
+    if !course_stage.includes(CourseStage::Parse) {
+        return FrontEndOutput {
+            logs: nexus_log::stop_recording(),
+            lex_res: lex_res,
+            parse_res: None
+        };
+    }
+
+    // Log the program we are lexing
+    nexus_log::log(
+        nexus_log::LogTypes::Info,
+        nexus_log::LogSource::PARSER,
+        format!("Parsing program {}", program_number)
+    );
+
+    let parse_res: Result<SyntaxTree, ()> = program_cache::parse_program(parser, lex_res.as_ref().unwrap());
+
+    if parse_res.is_err() {
         nexus_log::insert_empty_line();
+
+        // Do not show CST unless parse is successful
+        nexus_log::log(
+            nexus_log::LogTypes::Warning,
+            nexus_log::LogSource::NEXUS,
+            String::from("CST display skipped due to parse failure")
+        );
         
         nexus_log::log(
-            nexus_log::LogTypes::Info,
-            nexus_log::LogSources::Nexus,
-            format!("Generating AST for program {}", program_number)
+            nexus_log::LogTypes::Warning,
+            nexus_log::LogSource::NEXUS,
+            String::from("AST generation and display skipped due to parse failure")
         );
 
-        let ast: SyntaxTree = semantic_analyzer.generate_ast(&token_stream);
-        ast.display(&program_number);
+        nexus_log::log(
+            nexus_log::LogTypes::Warning,
+            nexus_log::LogSource::SEMANTIC_ANALYZER,
+            String::from("Semantic analysis skipped due to parse failure")
+        );
 
         nexus_log::log(
-            nexus_log::LogTypes::Info,
-            nexus_log::LogSources::Nexus,
-            format!("AST display for program {} is below", program_number)
+            nexus_log::LogTypes::Warning,
+            nexus_log::LogSource::NEXUS,
+            String::from("Symbol table display skipped due to parse failure")
         );
 
+        nexus_log::log(
+            nexus_log::LogTypes::Warning,
+            nexus_log::LogSource::NEXUS,
+            String::from("Code generation skipped due to parse failure")
+        );
+
+        nexus_log::log(
+            nexus_log::LogTypes::Warning,
+            nexus_log::LogSource::NEXUS,
+            String::from("Executable image display skipped due to parse failure")
+        );
+    } else {
         nexus_log::log(
             nexus_log::LogTypes::Info,
-            nexus_log::LogSources::SemanticAnalyzer,
-            format!("Beginning semantic analysis on program {}", program_number)
+            nexus_log::LogSource::NEXUS,
+            format!("CST display for program {} is below", program_number)
         );
-        let semantic_analysis_res: bool = semantic_analyzer.analyze_program(&ast);
+    }
 
-        if !semantic_analysis_res {
-            nexus_log::insert_empty_line();
+    return FrontEndOutput {
+        logs: nexus_log::stop_recording(),
+        lex_res: lex_res,
+        parse_res: Some(parse_res)
+    };
+}
 
-            nexus_log::log(
-                nexus_log::LogTypes::Warning,
-                nexus_log::LogSources::Nexus,
-                String::from("Symbol table display skipped due to semantic analysis failure")
-            );
-            
-            nexus_log::log(
-                nexus_log::LogTypes::Warning,
-                nexus_log::LogSources::Nexus,
-                String::from("Code generation skipped due to semantic analysis failure")
-            );
+// Function to show the logs, progress, and CST of lexing and parsing a program, returns true if it was parsed
+fn show_front_end(front_end_output: FrontEndOutput, program_number: u32, progress_reporter: &mut ProgressReporter) -> bool {
+    progress_reporter.start_phase(ProgressPhase::Lex);
+    progress_reporter.finish_phase(ProgressPhase::Lex, front_end_output.lex_res.is_ok());
+    if let Some(parse_res) = &front_end_output.parse_res {
+        progress_reporter.start_phase(ProgressPhase::Parse);
+        progress_reporter.finish_phase(ProgressPhase::Parse, parse_res.is_ok());
+    }
+    nexus_log::replay(&front_end_output.logs);
 
+    if let Ok(token_stream) = &front_end_output.lex_res {
+        artifacts::update_current_program(|program_artifacts| program_artifacts.statistics.token_count = Some(token_stream.len()));
+    }
+
+    let cst: SyntaxTree = match front_end_output.parse_res {
+        Some(Ok(cst)) => cst,
+        _ => return false
+    };
+    cst.display(&program_number);
+    artifacts::update_current_program(|program_artifacts| {
+        program_artifacts.cst_json = Some(cst.to_json());
+        program_artifacts.cst_sexpr = Some(cst.to_sexpr());
+        program_artifacts.statistics.cst_node_count = Some(cst.node_count());
+    });
+
+    nexus_log::insert_empty_line();
+    return true;
+}
+
+// Function to show the logs, progress, and results of the phases after parsing
+fn show_program_output(program_output: ProgramOutput, is_interpreted: bool, progress_reporter: &mut ProgressReporter, all_semantic_tokens: &mut Vec<SemanticToken>) {
+    let program_number: u32 = program_output.program_number;
+    let mut semantic_analysis_res: SemanticAnalysisResult = program_output.semantic_analysis_res;
+
+    // Generating the AST is part of semantic analysis
+    progress_reporter.start_phase(ProgressPhase::SemanticAnalysis);
+    nexus_log::log(
+        nexus_log::LogTypes::Info,
+        nexus_log::LogSource::NEXUS,
+        format!("Generating AST for program {}", program_number)
+    );
+    nexus_log::replay(&program_output.ast_logs);
+    semantic_analysis_res.ast.display(&program_number);
+
+    nexus_log::log(
+        nexus_log::LogTypes::Info,
+        nexus_log::LogSource::NEXUS,
+        format!("AST display for program {} is below", program_number)
+    );
+
+    nexus_log::log(
+        nexus_log::LogTypes::Info,
+        nexus_log::LogSource::SEMANTIC_ANALYZER,
+        format!("Beginning semantic analysis on program {}", program_number)
+    );
+    nexus_log::replay(&program_output.analysis_logs);
+
+    progress_reporter.finish_phase(ProgressPhase::SemanticAnalysis, semantic_analysis_res.is_successful());
+    artifacts::update_current_program(|program_artifacts| {
+        program_artifacts.ast_json = Some(semantic_analysis_res.to_json());
+        program_artifacts.ast_sexpr = Some(semantic_analysis_res.to_sexpr());
+        program_artifacts.statistics.add_semantic_analysis(&semantic_analysis_res);
+    });
+    all_semantic_tokens.append(&mut semantic_tokens::get_semantic_tokens(&semantic_analysis_res));
+
+    if !semantic_analysis_res.is_successful() {
+        nexus_log::insert_empty_line();
+
+        nexus_log::log(
+            nexus_log::LogTypes::Warning,
+            nexus_log::LogSource::NEXUS,
+            String::from("Symbol table display skipped due to semantic analysis failure")
+        );
+        
+        if is_interpreted {
             nexus_log::log(
                 nexus_log::LogTypes::Warning,
-                nexus_log::LogSources::Nexus,
-                String::from("Executable image display skipped due to semantic analysis failure")
+                nexus_log::LogSource::NEXUS,
+                String::from("Run skipped due to semantic analysis failure")
             );
-
-            continue;
+            return;
         }
 
         nexus_log::log(
-            nexus_log::LogTypes::Info,
-            nexus_log::LogSources::Nexus,
-            format!("Symbol table for program {} is below", program_number)
+            nexus_log::LogTypes::Warning,
+            nexus_log::LogSource::NEXUS,
+            String::from("Code generation skipped due to semantic analysis failure")
         );
-        semantic_analyzer.symbol_table.display_symbol_table(&program_number);
 
-        nexus_log::insert_empty_line();
+        nexus_log::log(
+            nexus_log::LogTypes::Warning,
+            nexus_log::LogSource::NEXUS,
+            String::from("Executable image display skipped due to semantic analysis failure")
+        );
+        return;
+    }
+
+    nexus_log::log(
+        nexus_log::LogTypes::Info,
+        nexus_log::LogSource::NEXUS,
+        format!("Symbol table for program {} is below", program_number)
+    );
+    semantic_analysis_res.symbol_table.display_symbol_table(&program_number);
 
+    nexus_log::insert_empty_line();
+
+    // Interpreted programs only show what they printed
+    if let Some(program_run) = program_output.program_run {
+        show_program_run(&program_run, &program_output.code_gen_logs, program_number);
+        return;
+    }
+
+    // The code generator is there whenever analysis was successful, the program was not interpreted, and the course stage has code generation
+    let (code_generator, is_code_gen_successful): (TargetCodeGenerator, bool) = match program_output.code_gen {
+        Some(code_gen) => code_gen,
+        None => return
+    };
+
+    nexus_log::log(
+        nexus_log::LogTypes::Info,
+        nexus_log::LogSource::CODE_GENERATOR,
+        format!("Generating code for program {}", program_number)
+    );
+       
+    progress_reporter.start_phase(ProgressPhase::CodeGeneration);
+    nexus_log::replay(&program_output.code_gen_logs);
+    if is_code_gen_successful {
         nexus_log::log(
             nexus_log::LogTypes::Info,
-            nexus_log::LogSources::CodeGenerator,
-            format!("Generating code for program {}", program_number)
+            nexus_log::LogSource::NEXUS,
+            format!("Executable image for program {} is below", program_number)
         );
-       
-        match buttons::get_current_target() {
-            Target::Target6502 => code_generator_6502.generate_code(&ast, &mut semantic_analyzer.symbol_table, &program_number),
-            Target::TargetRiscV => code_generator_riscv.generate_code(&ast, &mut semantic_analyzer.symbol_table, &program_number)
-        }
+    }
+
+    // The source map uses the same ranges as the coverage, but RISC-V counts lines of the whole assembly text
+    let coverage_res: Option<(CoverageJson, SourceMapJson)> = match (code_generator, is_code_gen_successful) {
+        (TargetCodeGenerator::Target6502(mut code_generator_6502), true) => {
+            code_generator_6502.display_code(&program_number);
+            let coverage: CoverageJson = CoverageJson::new("6502", "byte", &semantic_analysis_res.ast, code_generator_6502.get_coverage_ranges());
+            let source_map: SourceMapJson = SourceMapJson::new("6502", "address", &semantic_analysis_res.ast, &coverage, &code_generator_6502.get_scope_ranges(), 0);
+            artifacts::update_current_program(|program_artifacts| {
+                program_artifacts.generated_code = Some(GeneratedCode::Image(code_generator_6502.get_image_bytes()));
+                program_artifacts.statistics.code_bytes = Some(code_generator_6502.get_memory_usage().0);
+                program_artifacts.statistics.string_heap_bytes = Some(code_generator_6502.get_string_heap_size());
+            });
+            Some((coverage, source_map))
+        },
+        (TargetCodeGenerator::Target6502(_), false) => {
+            nexus_log::insert_empty_line();
+
+            nexus_log::log(
+                nexus_log::LogTypes::Warning,
+                nexus_log::LogSource::NEXUS,
+                format!("Executable image display skipped due to code generation failure")
+            );
+            None
+        },
+        (TargetCodeGenerator::TargetRiscV(mut code_generator_riscv), true) => {
+            code_generator_riscv.display_code(&program_number);
+            let coverage: CoverageJson = CoverageJson::new("RISC-V", "line", &semantic_analysis_res.ast, code_generator_riscv.get_coverage_ranges());
+            let source_map: SourceMapJson = SourceMapJson::new("RISC-V", "line", &semantic_analysis_res.ast, &coverage, &code_generator_riscv.get_scope_ranges(), code_generator_riscv.get_first_code_line());
+            artifacts::update_current_program(|program_artifacts| {
+                program_artifacts.generated_code = Some(GeneratedCode::Assembly(code_generator_riscv.get_assembly()));
+                program_artifacts.statistics.instruction_count = Some(code_generator_riscv.get_instruction_count());
+                program_artifacts.statistics.string_heap_bytes = Some(code_generator_riscv.get_string_heap_size());
+            });
+            Some((coverage, source_map))
+        },
+        (TargetCodeGenerator::TargetRiscV(_), false) => None
+    };
+    progress_reporter.finish_phase(ProgressPhase::CodeGeneration, coverage_res.is_some());
+
+    // Color the AST by which nodes produced code
+    if coverage_res.is_some() {
+        let (coverage, source_map): (CoverageJson, SourceMapJson) = coverage_res.unwrap();
+        semantic_analysis_res.ast.display_coverage(&program_number, &coverage.get_sizes());
+        artifacts::update_current_program(|program_artifacts| {
+            program_artifacts.coverage_json = Some(coverage.to_json());
+            program_artifacts.source_map_json = Some(source_map.to_json());
+        });
+    }
+}
+
+// Function to show the prints of a program that was run with the interpreter
+fn show_program_run(program_run: &ProgramRun, run_logs: &Vec<RecordedLog>, program_number: u32) {
+    nexus_log::log(
+        nexus_log::LogTypes::Info,
+        interpreter::LOG_SOURCE,
+        format!("Running program {} with the interpreter", program_number)
+    );
+    nexus_log::replay(run_logs);
+
+    match &program_run.error {
+        None => nexus_log::log(
+            nexus_log::LogTypes::Info,
+            nexus_log::LogSource::NEXUS,
+            format!("Program {} printed [ {} ]", program_number, program_run.get_output().trim_end())
+        ),
+        Some(message) => nexus_log::log(
+            nexus_log::LogTypes::Error,
+            interpreter::LOG_SOURCE,
+            format!("Program {} stopped early: {}", program_number, message)
+        )
     }
 }
@@ -0,0 +1,263 @@
+use crate::nexus::{syntax_tree::SyntaxTree, syntax_tree_node::*};
+use crate::nexus::token::TokenType;
+#[cfg(feature = "ui")]
+use crate::util::nexus_log;
+
+use petgraph::{graph::NodeIndex, Direction};
+#[cfg(feature = "ui")]
+use web_sys::{Document, Element};
+
+// The estimated cost of a single statement in the program
+#[derive (Debug)]
+pub struct StatementCost {
+    // Description of the statement, such as "Line 3: While"
+    pub label: String,
+
+    // The number of instructions generated for the statement
+    pub instructions: usize,
+
+    // The number of cycles to run each instruction once, if the target has a cycle model
+    pub cycles: Option<usize>,
+
+    // The estimated cost of running the statement, which is None if a loop does not have a constant bound
+    pub executed: Option<usize>
+}
+
+// The code generated for a while loop and how many times it runs
+#[derive (Debug)]
+pub struct LoopRange {
+    // The first position of the loop (condition) in the code
+    pub start: usize,
+
+    // The position right after the jump back to the condition
+    pub end: usize,
+
+    pub trip_count: Option<usize>
+}
+
+// Function to get a description of a statement for the cost table
+pub fn get_statement_label(ast: &SyntaxTree, statement_index: NodeIndex) -> String {
+    let kind: String = format!("{:?}", (*ast).graph.node_weight(statement_index).unwrap());
+    return match get_first_line(ast, statement_index) {
+        Some(line) => format!("Line {}: {}", line, kind),
+        // Empty blocks do not have any tokens
+        None => kind
+    };
+}
+
+// Function to get the earliest line number of any token in the subtree
+fn get_first_line(ast: &SyntaxTree, cur_index: NodeIndex) -> Option<usize> {
+    return match (*ast).graph.node_weight(cur_index).unwrap() {
+        SyntaxTreeNode::Terminal(token) => Some(token.position.line),
+        _ => (*ast).graph.neighbors(cur_index).filter_map(|child_index| get_first_line(ast, child_index)).min()
+    };
+}
+
+// Function to compute the number of iterations of a counting loop like
+// i = 0  while (i != 5) { ... i = 1 + i }, or None if it cannot be known at compile time
+pub fn get_constant_trip_count(ast: &SyntaxTree, while_index: NodeIndex) -> Option<usize> {
+    let children: Vec<NodeIndex> = (*ast).graph.neighbors(while_index).collect();
+
+    // The condition has to compare the counter against a digit with !=
+    match (*ast).graph.node_weight(children[1]).unwrap() {
+        SyntaxTreeNode::NonTerminalAst(NonTerminalsAst::NotEq) => {},
+        _ => return None
+    }
+    let condition_children: Vec<NodeIndex> = (*ast).graph.neighbors(children[1]).collect();
+    let (counter, bound): (String, usize) = match ((*ast).graph.node_weight(condition_children[1]).unwrap(), (*ast).graph.node_weight(condition_children[0]).unwrap()) {
+        (SyntaxTreeNode::Terminal(left), SyntaxTreeNode::Terminal(right)) => {
+            match (&left.token_type, &right.token_type) {
+                (TokenType::Identifier(id), TokenType::Digit(num)) | (TokenType::Digit(num), TokenType::Identifier(id)) => (id.to_owned(), *num as usize),
+                _ => return None
+            }
+        },
+        _ => return None
+    };
+
+    // The body has to increase the counter by a constant exactly once per iteration
+    let step: usize = get_counter_step(ast, children[0], &counter)?;
+
+    // The counter has to be set to a constant right before the loop
+    let initial_value: usize = get_initial_value(ast, while_index, &counter)?;
+
+    // Anything else would wrap around the byte before it stops
+    if bound >= initial_value && (bound - initial_value) % step == 0 {
+        return Some((bound - initial_value) / step);
+    } else {
+        return None;
+    }
+}
+
+// Function to get the amount the counter goes up by in the loop body
+fn get_counter_step(ast: &SyntaxTree, body_index: NodeIndex, counter: &str) -> Option<usize> {
+    let mut step: Option<usize> = None;
+
+    for statement_index in (*ast).graph.neighbors(body_index) {
+        match (*ast).graph.node_weight(statement_index).unwrap() {
+            SyntaxTreeNode::NonTerminalAst(NonTerminalsAst::Assign) if is_assigned_to(ast, statement_index, counter) => {
+                // Only 1 update is allowed
+                if step.is_some() {
+                    return None;
+                }
+
+                let assign_children: Vec<NodeIndex> = (*ast).graph.neighbors(statement_index).collect();
+                let add_children: Vec<NodeIndex> = match (*ast).graph.node_weight(assign_children[0]).unwrap() {
+                    SyntaxTreeNode::NonTerminalAst(NonTerminalsAst::Add) => (*ast).graph.neighbors(assign_children[0]).collect(),
+                    _ => return None
+                };
+
+                // Must look like counter = k + counter
+                match ((*ast).graph.node_weight(add_children[1]).unwrap(), (*ast).graph.node_weight(add_children[0]).unwrap()) {
+                    (SyntaxTreeNode::Terminal(left), SyntaxTreeNode::Terminal(right)) => {
+                        match (&left.token_type, &right.token_type) {
+                            (TokenType::Digit(num), TokenType::Identifier(id)) if id.eq(counter) && *num > 0 => step = Some(*num as usize),
+                            _ => return None
+                        }
+                    },
+                    _ => return None
+                }
+            },
+            // A new declaration would shadow the counter
+            SyntaxTreeNode::NonTerminalAst(NonTerminalsAst::VarDecl) if declares(ast, statement_index, counter) => return None,
+            _ => {
+                // Nested statements cannot change the counter
+                if assigns_anywhere(ast, statement_index, counter) {
+                    return None;
+                }
+            }
+        }
+    }
+
+    return step;
+}
+
+// Function to get the value of the counter right before the loop starts
+fn get_initial_value(ast: &SyntaxTree, while_index: NodeIndex, counter: &str) -> Option<usize> {
+    let parent_index: NodeIndex = (*ast).graph.neighbors_directed(while_index, Direction::Incoming).next()?;
+
+    // Children are stored in reverse order, so this goes backwards from the loop
+    let siblings: Vec<NodeIndex> = (*ast).graph.neighbors(parent_index).collect();
+    let while_position: usize = siblings.iter().position(|sibling| *sibling == while_index)?;
+
+    for sibling_index in siblings.into_iter().skip(while_position + 1) {
+        match (*ast).graph.node_weight(sibling_index).unwrap() {
+            SyntaxTreeNode::NonTerminalAst(NonTerminalsAst::Assign) if is_assigned_to(ast, sibling_index, counter) => {
+                let assign_children: Vec<NodeIndex> = (*ast).graph.neighbors(sibling_index).collect();
+                return match (*ast).graph.node_weight(assign_children[0]).unwrap() {
+                    SyntaxTreeNode::Terminal(token) => {
+                        match &token.token_type {
+                            TokenType::Digit(num) => Some(*num as usize),
+                            _ => None
+                        }
+                    },
+                    _ => None
+                };
+            },
+            // Integers start at 0
+            SyntaxTreeNode::NonTerminalAst(NonTerminalsAst::VarDecl) if declares(ast, sibling_index, counter) => return Some(0),
+            _ => {
+                if assigns_anywhere(ast, sibling_index, counter) {
+                    return None;
+                }
+            }
+        }
+    }
+
+    // The counter comes from an outer block
+    return None;
+}
+
+fn is_assigned_to(ast: &SyntaxTree, assign_index: NodeIndex, id: &str) -> bool {
+    let children: Vec<NodeIndex> = (*ast).graph.neighbors(assign_index).collect();
+    return match (*ast).graph.node_weight(children[1]).unwrap() {
+        SyntaxTreeNode::Terminal(token) => token.text.eq(id),
+        _ => false
+    };
+}
+
+fn declares(ast: &SyntaxTree, decl_index: NodeIndex, id: &str) -> bool {
+    let children: Vec<NodeIndex> = (*ast).graph.neighbors(decl_index).collect();
+    return match (*ast).graph.node_weight(children[0]).unwrap() {
+        SyntaxTreeNode::Terminal(token) => token.text.eq(id),
+        _ => false
+    };
+}
+
+// Function to check if the id is assigned anywhere in the subtree
+fn assigns_anywhere(ast: &SyntaxTree, cur_index: NodeIndex, id: &str) -> bool {
+    return match (*ast).graph.node_weight(cur_index).unwrap() {
+        SyntaxTreeNode::NonTerminalAst(NonTerminalsAst::Assign) => is_assigned_to(ast, cur_index, id),
+        SyntaxTreeNode::NonTerminalAst(_) => (*ast).graph.neighbors(cur_index).any(|child_index| assigns_anywhere(ast, child_index, id)),
+        _ => false
+    };
+}
+
+// Function to get how many times the code at the position runs, or None if it is in a loop without a constant bound
+pub fn get_multiplier(loop_ranges: &Vec<LoopRange>, position: usize) -> Option<usize> {
+    let mut multiplier: usize = 1;
+    for loop_range in loop_ranges.iter() {
+        if position >= loop_range.start && position < loop_range.end {
+            multiplier *= loop_range.trip_count?;
+        }
+    }
+    return Some(multiplier);
+}
+
+// Function to log the totals and add the table of statement costs below the code
+#[cfg(feature = "ui")]
+pub fn display_cost_table(document: &Document, display_area: &Element, costs: &Vec<StatementCost>, program_number: &u32, executed_unit: &str) {
+    let total_instructions: usize = costs.iter().map(|cost| cost.instructions).sum();
+    let total_cycles: Option<usize> = costs.iter().map(|cost| cost.cycles).sum();
+    let total_executed: Option<usize> = costs.iter().map(|cost| cost.executed).sum();
+
+    let mut summary: String = format!("Program {} cost estimate: {} instructions", *program_number, total_instructions);
+    if total_cycles.is_some() {
+        summary.push_str(format!(", {} cycles to run each once", total_cycles.unwrap()).as_str());
+    }
+    match total_executed {
+        Some(executed) => summary.push_str(format!(", about {} {} executed", executed, executed_unit).as_str()),
+        None => summary.push_str(", executed count unknown because a loop does not have a constant bound")
+    }
+    nexus_log::log(
+        nexus_log::LogTypes::Info,
+        nexus_log::LogSource::CODE_GENERATOR,
+        summary
+    );
+
+    let table: Element = document.create_element("table").expect("Should be able to create the element");
+    table.set_class_name("cost-table");
+
+    let mut header: String = String::from("<tr><th>Statement</th><th>Instructions</th>");
+    if total_cycles.is_some() {
+        header.push_str("<th>Cycles</th>");
+    }
+    header.push_str(format!("<th>Executed {}</th></tr>", executed_unit).as_str());
+
+    let mut rows: String = header;
+    for cost in costs.iter() {
+        rows.push_str(create_cost_row(&cost.label, cost.instructions, cost.cycles, cost.executed).as_str());
+    }
+    rows.push_str(create_cost_row("Total", total_instructions, total_cycles, total_executed).as_str());
+    table.set_inner_html(&rows);
+
+    // Let the reader know how the estimate was made
+    let note: Element = document.create_element("p").expect("Should be able to create the element");
+    note.set_class_name("cost-note");
+    note.set_inner_html("Estimates assume every if statement is taken and use the iteration count of loops with constant bounds (? otherwise)");
+
+    display_area.append_child(&table).expect("Should be able to add the child node");
+    display_area.append_child(&note).expect("Should be able to add the child node");
+}
+
+#[cfg(feature = "ui")]
+fn create_cost_row(label: &str, instructions: usize, cycles: Option<usize>, executed: Option<usize>) -> String {
+    let mut row: String = format!("<tr><td>{}</td><td>{}</td>", label, instructions);
+    if cycles.is_some() {
+        row.push_str(format!("<td>{}</td>", cycles.unwrap()).as_str());
+    }
+    match executed {
+        Some(executed_count) => row.push_str(format!("<td>{}</td></tr>", executed_count).as_str()),
+        None => row.push_str("<td>?</td></tr>")
+    }
+    return row;
+}
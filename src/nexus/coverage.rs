@@ -0,0 +1,64 @@
+use std::collections::HashMap;
+
+use crate::nexus::{syntax_tree::SyntaxTree, cost_model};
+
+use petgraph::graph::NodeIndex;
+use serde::Serialize;
+
+// The code produced by a single AST node
+#[derive (Debug, Clone, Serialize)]
+#[serde (rename_all = "camelCase")]
+pub struct NodeCoverage {
+    // The index of the node in the AST, which matches the id in the AST JSON
+    pub id: usize,
+    pub label: String,
+
+    // The range [start, end) of the code produced by the node
+    pub start: usize,
+    pub end: usize,
+    pub size: usize
+}
+
+// The coverage artifact for a program
+#[derive (Debug, Clone, Serialize)]
+#[serde (rename_all = "camelCase")]
+pub struct CoverageJson {
+    pub target: String,
+
+    // Bytes of the image for 6502 and lines of assembly for RISC-V
+    pub unit: String,
+    pub nodes: Vec<NodeCoverage>
+}
+
+impl CoverageJson {
+    // Creates the coverage from the (node, start, end) ranges recorded by a code generator
+    pub fn new(target: &str, unit: &str, ast: &SyntaxTree, ranges: &Vec<(NodeIndex, usize, usize)>) -> Self {
+        let mut nodes: Vec<NodeCoverage> = ranges.iter()
+            .map(|(node_index, start, end)| NodeCoverage {
+                id: node_index.index(),
+                label: cost_model::get_statement_label(ast, *node_index),
+                start: *start,
+                end: *end,
+                size: end - start
+            })
+            .collect();
+
+        // Sort by position so the output reads in program order
+        nodes.sort_by_key(|node| (node.start, node.id));
+
+        return CoverageJson {
+            target: String::from(target),
+            unit: String::from(unit),
+            nodes: nodes
+        };
+    }
+
+    // Function to get the amount of code each node produced
+    pub fn get_sizes(&self) -> HashMap<NodeIndex, usize> {
+        return self.nodes.iter().map(|node| (NodeIndex::new(node.id), node.size)).collect();
+    }
+
+    pub fn to_json(&self) -> String {
+        return serde_json::to_string(self).expect("Should be able to serialize the coverage");
+    }
+}
@@ -0,0 +1,230 @@
+use std::fmt;
+
+use crate::util::{nexus_log, target::{Target, TargetConfig}, program_generator::{ProgramGenerator, ProgramGeneratorConfig}, program_minimizer::{self, Failure}};
+use crate::nexus::{preprocessor::Preprocessor, lexer::Lexer, token::Token, parser::Parser, syntax_tree::SyntaxTree};
+use crate::nexus::semantic_analyzer::{SemanticAnalyzer, SemanticAnalysisResult};
+use crate::nexus::{code_generator_6502::CodeGenerator6502, code_generator_riscv::CodeGeneratorRiscV, emulator_6502::Emulator6502, emulator_riscv::EmulatorRiscV, interpreter::{Interpreter, ProgramRun}};
+
+// The code that is compared with the interpreter
+#[derive (Debug, Clone, Copy, PartialEq)]
+pub enum Backend {
+    Image6502,
+    SizeImage6502,
+    RiscV
+}
+
+impl fmt::Display for Backend {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        return match self {
+            Backend::Image6502 => write!(f, "6502"),
+            Backend::SizeImage6502 => write!(f, "6502 (optimized for size)"),
+            Backend::RiscV => write!(f, "RISC-V")
+        };
+    }
+}
+
+// A backend that printed something different from the interpreter
+#[derive (Debug, Clone, PartialEq)]
+pub struct Divergence {
+    pub backend: Backend,
+    pub expected: String,
+    pub actual: String
+}
+
+impl fmt::Display for Divergence {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        return write!(f, "{} printed [ {} ] instead of [ {} ]", self.backend, self.actual.escape_debug(), self.expected.escape_debug());
+    }
+}
+
+// A divergence that was found in a program of the corpus
+#[derive (Debug)]
+pub struct DifferentialFailure {
+    // Where the program came from, such as the file name and program number or the generator seed
+    pub name: String,
+    pub divergence: Divergence,
+    pub program: String,
+
+    // The smallest program found that still has the same backend diverge
+    pub minimal_program: String
+}
+
+// The result of checking a whole corpus
+#[derive (Debug)]
+pub struct DifferentialReport {
+    pub num_checked: usize,
+
+    // Programs that did not pass semantic analysis or could not be run by the interpreter, so there was nothing to compare
+    pub num_skipped: usize,
+    pub failures: Vec<DifferentialFailure>
+}
+
+impl DifferentialReport {
+    pub fn is_successful(&self) -> bool {
+        return self.failures.len() == 0;
+    }
+}
+
+impl fmt::Display for DifferentialReport {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        for failure in self.failures.iter() {
+            writeln!(f, "FAIL {}: {}", failure.name, failure.divergence)?;
+            writeln!(f, "Minimal program:\n{}", failure.minimal_program)?;
+        }
+        return write!(f, "Checked {} programs ({} skipped), {} diverged", self.num_checked, self.num_skipped, self.failures.len());
+    }
+}
+
+// Function to run the program with the interpreter and compare what every backend prints
+// Programs that do not fit on a target do not have any code to run there
+pub fn compare_outputs(semantic_analysis_res: &mut SemanticAnalysisResult) -> Result<Option<Divergence>, String> {
+    let program_run: ProgramRun = Interpreter::new(&TargetConfig::new(Target::Target6502)).run_program(&semantic_analysis_res.ast);
+    if let Some(message) = program_run.error {
+        return Err(message);
+    }
+
+    // Size optimization can make a program fit that did not, so each image is checked on its own
+    let expected: String = program_run.get_output();
+    for (backend, optimize_size) in [(Backend::Image6502, false), (Backend::SizeImage6502, true)] {
+        if let Some(output) = run_6502(semantic_analysis_res, optimize_size) {
+            if !output.eq(&expected) {
+                return Ok(Some(Divergence { backend: backend, expected: expected, actual: output }));
+            }
+        }
+    }
+
+    if let Some(output) = run_riscv(semantic_analysis_res) {
+        if !output.eq(&expected) {
+            return Ok(Some(Divergence { backend: Backend::RiscV, expected: expected, actual: output }));
+        }
+    }
+    return Ok(None);
+}
+
+// Function to get what the 6502 image prints, or None if there is no image
+fn run_6502(semantic_analysis_res: &mut SemanticAnalysisResult, optimize_size: bool) -> Option<String> {
+    let mut code_generator: CodeGenerator6502 = CodeGenerator6502::new();
+    code_generator.set_optimize_size(optimize_size);
+    if !code_generator.generate_image(semantic_analysis_res) {
+        return None;
+    }
+    return Some(match Emulator6502::from_bytes(&code_generator.get_image_bytes()).and_then(|mut emulator| emulator.run()) {
+        Ok(output) => output,
+        Err(message) => format!("Program failed: {}", message)
+    });
+}
+
+// Function to get what the RISC-V assembly prints, or None if there is no assembly
+fn run_riscv(semantic_analysis_res: &mut SemanticAnalysisResult) -> Option<String> {
+    let mut code_generator: CodeGeneratorRiscV = CodeGeneratorRiscV::new();
+    if !code_generator.generate_assembly(semantic_analysis_res) {
+        return None;
+    }
+    return Some(match EmulatorRiscV::from_assembly(&code_generator.get_assembly()).and_then(|mut emulator| emulator.run()) {
+        Ok(output) => output,
+        Err(message) => format!("Program failed: {}", message)
+    });
+}
+
+// Function to lex, parse, and analyze a single program, which is None if any stage fails
+pub fn analyze_program(program: &str) -> Option<SemanticAnalysisResult> {
+    let mut lexer: Lexer = Lexer::new(program);
+    let token_stream: Vec<Token> = lexer.lex_program().ok()?;
+    // Anything after the first program would not be checked
+    if lexer.has_program_to_lex() {
+        return None;
+    }
+    let mut parser: Parser = Parser::new();
+    parser.parse_program(&token_stream).ok()?;
+
+    let mut semantic_analyzer: SemanticAnalyzer = SemanticAnalyzer::new(TargetConfig::new(Target::Target6502));
+    let ast: SyntaxTree = semantic_analyzer.generate_ast(&token_stream);
+    let semantic_analysis_res: SemanticAnalysisResult = semantic_analyzer.analyze_program(ast);
+    if !semantic_analysis_res.is_successful() {
+        return None;
+    }
+    return Some(semantic_analysis_res);
+}
+
+// Function to check if the program compiles and has the backend diverge from the interpreter
+pub fn has_divergence(program: &str, backend: Backend) -> bool {
+    return match analyze_program(program) {
+        Some(mut semantic_analysis_res) => match compare_outputs(&mut semantic_analysis_res) {
+            Ok(Some(divergence)) => divergence.backend == backend,
+            _ => false
+        },
+        None => false
+    };
+}
+
+// Function to check every program of the sources and the given number of generated programs
+// The sources are (name, source code) pairs, and programs that diverge are shrunk before being reported
+pub fn check_corpus(sources: &Vec<(String, String)>, num_generated: u64) -> DifferentialReport {
+    // Nothing is shown while checking, so the logs are dropped
+    nexus_log::push_sinks(Vec::new());
+
+    let mut programs: Vec<(String, String)> = Vec::new();
+    for (name, source) in sources.iter() {
+        for (program_number, program) in split_programs(source).into_iter().enumerate() {
+            programs.push((format!("{} program {}", name, program_number + 1), program));
+        }
+    }
+
+    // The seeds are fixed so a failure can be reproduced with the generate button's program
+    for seed in 1..=num_generated {
+        let mut program_generator: ProgramGenerator = ProgramGenerator::new(ProgramGeneratorConfig::new(seed));
+        programs.push((format!("generated program with seed {}", seed), program_generator.generate_program()));
+    }
+
+    let mut report: DifferentialReport = DifferentialReport {
+        num_checked: 0,
+        num_skipped: 0,
+        failures: Vec::new()
+    };
+    for (name, program) in programs {
+        report.num_checked += 1;
+        let compare_res: Option<Result<Option<Divergence>, String>> = analyze_program(&program)
+            .map(|mut semantic_analysis_res| compare_outputs(&mut semantic_analysis_res));
+        match compare_res {
+            Some(Ok(Some(divergence))) => {
+                let minimal_program: String = program_minimizer::minimize_failure(&program, Failure::WrongOutput(divergence.backend));
+                report.failures.push(DifferentialFailure {
+                    name: name,
+                    divergence: divergence,
+                    program: program,
+                    minimal_program: minimal_program
+                });
+            },
+            Some(Ok(None)) => {},
+            _ => report.num_skipped += 1
+        }
+    }
+
+    nexus_log::pop_sinks();
+    return report;
+}
+
+// Function to preprocess a source and get the text of each program in it
+fn split_programs(source: &str) -> Vec<String> {
+    let mut preprocessor: Preprocessor = Preprocessor::new();
+    let code: String = match preprocessor.expand_defines(source) {
+        Ok(code) => code,
+        Err(_) => return Vec::new()
+    };
+
+    let mut programs: Vec<String> = Vec::new();
+    let mut lexer: Lexer = Lexer::new(&code);
+    while lexer.has_program_to_lex() {
+        let start: usize = lexer.get_cursor().0;
+        let lex_res: Result<Vec<Token>, ()> = lexer.lex_program();
+        let end: usize = lexer.get_cursor().0;
+        if lex_res.is_ok() {
+            programs.push(String::from(lexer.get_source(start, end).unwrap_or_default().trim()));
+        }
+        // The rest of the source cannot be split after a lex error
+        if lex_res.is_err() || end == start {
+            break;
+        }
+    }
+    return programs;
+}
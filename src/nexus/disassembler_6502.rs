@@ -0,0 +1,45 @@
+use crate::util::instruction_set::{self, Instruction6502};
+
+// Function to turn a 6502 image back into one line of assembly for each instruction
+// The bytes after the code are the static data and the heap, which are written as DB directives with one byte each
+pub fn disassemble(image: &[u8], code_size: usize) -> Result<Vec<String>, String> {
+    let mut lines: Vec<String> = Vec::new();
+
+    let mut address: usize = 0;
+    while address < code_size {
+        let instruction: &Instruction6502 = match instruction_set::get_instruction_6502(image[address]) {
+            Some(instruction) => instruction,
+            None => return Err(format!("Unknown opcode [ {:02X} ] at ${:02X}", image[address], address))
+        };
+
+        let end: usize = address + instruction.length as usize;
+        if end > code_size {
+            return Err(format!("Instruction [ {} ] at ${:02X} goes past the end of the code", instruction.mnemonic, address));
+        }
+
+        lines.push(format_instruction(instruction, &image[address + 1..end]));
+        address = end;
+    }
+
+    for data in image[code_size..].iter() {
+        lines.push(format!("DB ${:02X}", data));
+    }
+
+    return Ok(lines);
+}
+
+// Function to write the instruction with its operand bytes filled into the operand format
+fn format_instruction(instruction: &Instruction6502, operand_bytes: &[u8]) -> String {
+    // Addresses are little endian, so the high order byte comes second
+    let operand: String = match operand_bytes.len() {
+        1 => instruction.operands.replace("nn", &format!("{:02X}", operand_bytes[0])),
+        2 => instruction.operands.replace("nnnn", &format!("{:02X}{:02X}", operand_bytes[1], operand_bytes[0])),
+        _ => String::new()
+    };
+
+    if operand.is_empty() {
+        return String::from(instruction.mnemonic);
+    } else {
+        return format!("{} {}", instruction.mnemonic, operand);
+    }
+}
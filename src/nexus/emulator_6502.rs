@@ -0,0 +1,150 @@
+use crate::util::target::MmioConfig;
+
+// Runs an image from the 6502 code generator so the tests can check what a program prints
+// Only the subset of instructions that the code generator emits is supported
+#[derive (Debug)]
+pub struct Emulator6502 {
+    memory: [u8; 0x100],
+    acc: u8,
+    x_reg: u8,
+    y_reg: u8,
+
+    // Loads do not change the Z flag in this instruction set, only CPX does
+    z_flag: bool,
+    program_counter: usize,
+
+    // The output device that stores print to, or None if the memory is only memory
+    mmio: Option<MmioConfig>,
+
+    // Everything printed by the system calls and the output device
+    output: String
+}
+
+impl Emulator6502 {
+    // Programs stop after this many instructions so an infinite loop cannot freeze the page
+    const MAX_STEPS: usize = 100000;
+
+    // Function to load an image in the format of get_image_bytes
+    pub fn from_bytes(image: &[u8]) -> Result<Self, String> {
+        let mut memory: [u8; 0x100] = [0; 0x100];
+        if image.len() != memory.len() {
+            return Err(format!("Image has {} bytes, but it should have {}", image.len(), memory.len()));
+        }
+        memory.copy_from_slice(image);
+
+        return Ok(Emulator6502 {
+            memory: memory,
+            acc: 0,
+            x_reg: 0,
+            y_reg: 0,
+            z_flag: false,
+            program_counter: 0,
+            mmio: None,
+            output: String::new()
+        });
+    }
+
+    // Function to map the output device over its addresses so storing to them prints
+    pub fn set_mmio(&mut self, mmio: Option<MmioConfig>) {
+        self.mmio = mmio;
+    }
+
+    // Function to run the program until it breaks and get what it printed
+    pub fn run(&mut self) -> Result<String, String> {
+        for _ in 0..Self::MAX_STEPS {
+            let opcode: u8 = self.memory[self.program_counter];
+            match opcode {
+                // LDA constant
+                0xA9 => self.acc = self.read_constant(),
+                // LDA memory
+                0xAD => self.acc = self.memory[self.read_address()?],
+                // STA memory
+                0x8D => {
+                    let address: usize = self.read_address()?;
+                    self.store(address)?;
+                },
+                // ADC memory, where the sum wraps around because there is no carry
+                0x6D => self.acc = self.acc.wrapping_add(self.memory[self.read_address()?]),
+                // LDX constant and memory
+                0xA2 => self.x_reg = self.read_constant(),
+                0xAE => self.x_reg = self.memory[self.read_address()?],
+                // LDY constant and memory
+                0xA0 => self.y_reg = self.read_constant(),
+                0xAC => self.y_reg = self.memory[self.read_address()?],
+                // CPX memory
+                0xEC => self.z_flag = self.x_reg == self.memory[self.read_address()?],
+                // BNE, where the offset wraps around the memory
+                0xD0 => {
+                    let offset: u8 = self.read_constant();
+                    if !self.z_flag {
+                        self.program_counter = (self.program_counter + offset as usize) % self.memory.len();
+                    }
+                },
+                // SYS
+                0xFF => {
+                    self.system_call()?;
+                    self.program_counter += 1;
+                },
+                // BRK
+                0x00 => return Ok(std::mem::take(&mut self.output)),
+                _ => return Err(format!("Invalid opcode {:02X} at ${:02X}", opcode, self.program_counter))
+            }
+        }
+
+        return Err(format!("Program was stopped after {} instructions", Self::MAX_STEPS));
+    }
+
+    // Function to read the operand of a 2 byte instruction and move to the next instruction
+    fn read_constant(&mut self) -> u8 {
+        let constant: u8 = self.memory[(self.program_counter + 1) % self.memory.len()];
+        self.program_counter = (self.program_counter + 2) % self.memory.len();
+        return constant;
+    }
+
+    // Function to read the little endian address of a 3 byte instruction and move to the next instruction
+    fn read_address(&mut self) -> Result<usize, String> {
+        let low_byte: usize = self.memory[(self.program_counter + 1) % self.memory.len()] as usize;
+        let high_byte: usize = self.memory[(self.program_counter + 2) % self.memory.len()] as usize;
+        let address: usize = (high_byte << 8) | low_byte;
+        if address >= self.memory.len() {
+            return Err(format!("Address ${:04X} at ${:02X} is outside of memory", address, self.program_counter));
+        }
+
+        self.program_counter = (self.program_counter + 3) % self.memory.len();
+        return Ok(address);
+    }
+
+    // Function to store the accumulator, where the output device prints it instead of keeping it
+    fn store(&mut self, address: usize) -> Result<(), String> {
+        match self.mmio {
+            Some(mmio) if address == mmio.int_out => self.output.push_str(&self.acc.to_string()),
+            Some(mmio) if address == mmio.string_out => self.print_string(self.acc)?,
+            _ => self.memory[address] = self.acc
+        }
+        return Ok(());
+    }
+
+    fn system_call(&mut self) -> Result<(), String> {
+        match self.x_reg {
+            // Print the integer in Y in decimal without leading zeros, like the operating system does
+            0x01 => self.output.push_str(&self.y_reg.to_string()),
+            // Print the string that starts at the address in Y and ends at 00
+            0x02 => self.print_string(self.y_reg)?,
+            _ => return Err(format!("Invalid system call {:02X} at ${:02X}", self.x_reg, self.program_counter))
+        }
+        return Ok(());
+    }
+
+    // Function to print the string that starts at the given address and ends at 00
+    fn print_string(&mut self, start: u8) -> Result<(), String> {
+        let mut address: usize = start as usize;
+        while self.memory[address] != 0x00 {
+            self.output.push(self.memory[address] as char);
+            address += 1;
+            if address == self.memory.len() {
+                return Err(format!("String at ${:02X} does not end before the end of memory", start));
+            }
+        }
+        return Ok(());
+    }
+}
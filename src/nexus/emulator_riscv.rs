@@ -0,0 +1,304 @@
+use std::collections::HashMap;
+
+use crate::nexus::riscv_validator;
+use crate::util::target::SyscallConvention;
+
+// Runs the assembly from the RISC-V code generator so the tests can check what a program prints
+// Only the instructions, directives, and system calls that the code generator emits are supported
+#[derive (Debug)]
+pub struct EmulatorRiscV {
+    // The mnemonic and operands of each instruction, where the program counter is an index into this
+    instructions: Vec<(String, Vec<String>)>,
+    code_labels: HashMap<String, usize>,
+
+    // The data directives and the stack share the memory, which starts at DATA_START
+    memory: Vec<u8>,
+    data_labels: HashMap<String, u64>,
+
+    registers: [u64; 32],
+    program_counter: usize,
+
+    // The system calls that the program is expected to make
+    syscall_convention: SyscallConvention,
+
+    // Everything written to stdout
+    output: String
+}
+
+impl EmulatorRiscV {
+    // Programs stop after this many instructions so an infinite loop cannot freeze the page
+    const MAX_STEPS: usize = 1000000;
+
+    // Addresses start here so a value of 0 is never a valid address
+    const DATA_START: u64 = 0x10000;
+    const STACK_SIZE: usize = 0x1000;
+
+    // Function to load the assembly in the format of get_assembly
+    pub fn from_assembly(assembly: &str) -> Result<Self, String> {
+        let mut emulator: EmulatorRiscV = EmulatorRiscV {
+            instructions: Vec::new(),
+            code_labels: HashMap::new(),
+            memory: Vec::new(),
+            data_labels: HashMap::new(),
+            registers: [0; 32],
+            program_counter: 0,
+            syscall_convention: SyscallConvention::Linux,
+            output: String::new()
+        };
+
+        // A label belongs to the instruction or directive that comes after it
+        let mut pending_labels: Vec<String> = Vec::new();
+        for line in assembly.lines() {
+            let mut statement: &str = line.trim();
+            if let Some(first_word) = statement.split(' ').next().filter(|word| word.ends_with(':')) {
+                pending_labels.push(String::from(&first_word[..first_word.len() - 1]));
+                statement = statement[first_word.len()..].trim();
+            }
+
+            // Comments like the build information and the scope comments do not change the program
+            if statement.len() == 0 || statement.starts_with('#') || statement.starts_with(".section") || statement.starts_with(".global") {
+                continue;
+            } else if statement.starts_with('.') {
+                for label in pending_labels.drain(..) {
+                    emulator.data_labels.insert(label, Self::DATA_START + emulator.memory.len() as u64);
+                }
+                emulator.add_data(statement)?;
+            } else {
+                for label in pending_labels.drain(..) {
+                    emulator.code_labels.insert(label, emulator.instructions.len());
+                }
+                let (mnemonic, operands) = statement.split_once(' ').unwrap_or((statement, ""));
+                let operands: Vec<String> = operands.split(',')
+                    .map(|operand| String::from(operand.trim()))
+                    .filter(|operand| operand.len() > 0)
+                    .collect();
+                emulator.instructions.push((String::from(mnemonic), operands));
+            }
+        }
+        for label in pending_labels.drain(..) {
+            emulator.code_labels.insert(label, emulator.instructions.len());
+        }
+
+        // The stack goes after the data and grows down from the end of memory, which is 16 byte aligned like in the ABI
+        emulator.memory.resize(emulator.memory.len().next_multiple_of(16) + Self::STACK_SIZE, 0);
+        emulator.registers[2] = Self::DATA_START + emulator.memory.len() as u64;
+        emulator.program_counter = *emulator.code_labels.get("_start").ok_or_else(|| String::from("There is no _start label"))?;
+        return Ok(emulator);
+    }
+
+    // Function to add the bytes of a data directive to memory
+    fn add_data(&mut self, statement: &str) -> Result<(), String> {
+        let (directive, value) = statement.split_once(' ').unwrap_or((statement, ""));
+        let value: &str = value.trim();
+        match directive {
+            ".byte" => self.memory.extend((Self::parse_immediate(value)? as u8).to_le_bytes()),
+            ".half" => self.memory.extend((Self::parse_immediate(value)? as u16).to_le_bytes()),
+            ".word" => self.memory.extend((Self::parse_immediate(value)? as u32).to_le_bytes()),
+            ".ascii" if value.len() >= 2 && value.starts_with('"') && value.ends_with('"') => {
+                self.memory.extend(riscv_validator::decode_ascii_string(&value[1..value.len() - 1])?);
+            },
+            _ => return Err(format!("Unsupported directive [ {} ]", statement))
+        }
+        return Ok(());
+    }
+
+    // Function to run the program until it exits and get what it wrote
+    pub fn run(&mut self) -> Result<String, String> {
+        for _ in 0..Self::MAX_STEPS {
+            let (mnemonic, operands): (String, Vec<String>) = self.instructions
+                .get(self.program_counter)
+                .ok_or_else(|| format!("Program ran past the last instruction without exiting"))?
+                .to_owned();
+            let next_instruction: usize = self.program_counter + 1;
+            self.program_counter = next_instruction;
+
+            match (mnemonic.as_str(), operands.len()) {
+                ("nop", 0) => {},
+                ("li", 2) => self.set_register(&operands[0], Self::parse_immediate(&operands[1])? as u64)?,
+                ("la", 2) => {
+                    let address: u64 = *self.data_labels.get(&operands[1]).ok_or_else(|| format!("There is no data label called {}", operands[1]))?;
+                    self.set_register(&operands[0], address)?;
+                },
+                ("mv", 2) => self.set_register(&operands[0], self.get_register(&operands[1])?)?,
+                ("lbu", 2) | ("lhu", 2) | ("lw", 2) | ("lwu", 2) => {
+                    let num_bytes: usize = match mnemonic.as_str() {
+                        "lbu" => 1,
+                        "lhu" => 2,
+                        _ => 4
+                    };
+                    let address: usize = self.get_memory_index(&operands[1], num_bytes)?;
+                    let mut value_bytes: [u8; 8] = [0; 8];
+                    value_bytes[..num_bytes].copy_from_slice(&self.memory[address..address + num_bytes]);
+                    let mut value: u64 = u64::from_le_bytes(value_bytes);
+                    if mnemonic.eq("lw") {
+                        value = value as u32 as i32 as u64;
+                    }
+                    self.set_register(&operands[0], value)?;
+                },
+                ("sb", 2) | ("sw", 2) => {
+                    let num_bytes: usize = if mnemonic.eq("sb") { 1 } else { 4 };
+                    let value: u64 = self.get_register(&operands[0])?;
+                    let address: usize = self.get_memory_index(&operands[1], num_bytes)?;
+                    self.memory[address..address + num_bytes].copy_from_slice(&value.to_le_bytes()[..num_bytes]);
+                },
+                ("add", 3) => self.set_register(&operands[0], self.get_register(&operands[1])?.wrapping_add(self.get_register(&operands[2])?))?,
+                ("addi", 3) => self.set_register(&operands[0], self.get_register(&operands[1])?.wrapping_add(Self::parse_immediate(&operands[2])? as u64))?,
+                ("andi", 3) => self.set_register(&operands[0], self.get_register(&operands[1])? & Self::parse_immediate(&operands[2])? as u64)?,
+                ("divu", 3) | ("remu", 3) => {
+                    let dividend: u64 = self.get_register(&operands[1])?;
+                    let divisor: u64 = self.get_register(&operands[2])?;
+                    let result: u64 = match (mnemonic.as_str(), divisor) {
+                        // Dividing by 0 does not trap in RISC-V
+                        ("divu", 0) => u64::MAX,
+                        ("remu", 0) => dividend,
+                        ("divu", _) => dividend / divisor,
+                        _ => dividend % divisor
+                    };
+                    self.set_register(&operands[0], result)?;
+                },
+                ("beq", 3) | ("bne", 3) | ("blt", 3) => {
+                    let left: u64 = self.get_register(&operands[0])?;
+                    let right: u64 = self.get_register(&operands[1])?;
+                    let is_taken: bool = match mnemonic.as_str() {
+                        "beq" => left == right,
+                        "bne" => left != right,
+                        _ => (left as i64) < (right as i64)
+                    };
+                    if is_taken {
+                        self.program_counter = self.get_code_label(&operands[2])?;
+                    }
+                },
+                ("j", 1) => self.program_counter = self.get_code_label(&operands[0])?,
+                ("call", 1) => {
+                    self.registers[1] = next_instruction as u64;
+                    self.program_counter = self.get_code_label(&operands[0])?;
+                },
+                ("ret", 0) => self.program_counter = self.registers[1] as usize,
+                ("ecall", 0) => {
+                    if self.system_call()? {
+                        return Ok(std::mem::take(&mut self.output));
+                    }
+                },
+                _ => return Err(format!("Unsupported instruction [ {} {} ]", mnemonic, operands.join(", ")))
+            }
+        }
+
+        return Err(format!("Program was stopped after {} instructions", Self::MAX_STEPS));
+    }
+
+    // Function to set the system calls that the program makes, which is Linux by default
+    pub fn set_syscall_convention(&mut self, syscall_convention: SyscallConvention) {
+        self.syscall_convention = syscall_convention;
+    }
+
+    // Function to run the system call for the convention, which returns if the program exited
+    fn system_call(&mut self) -> Result<bool, String> {
+        // (write, exit) for Linux-style conventions, where a7 has the call
+        let (write_call, exit_call): (u64, u64) = match self.syscall_convention {
+            SyscallConvention::Linux => (64, 93),
+            SyscallConvention::Custom { write, exit } => (write as u64, exit as u64),
+            // The others print a character at a time
+            SyscallConvention::Sbi => return self.put_char_call(17, 1, 8, 10),
+            SyscallConvention::Rars => return self.put_char_call(17, 11, 10, 10),
+            SyscallConvention::Venus => return self.put_char_call(10, 11, 10, 11)
+        };
+
+        match self.registers[17] {
+            // Write a2 bytes starting at a1 to the file in a0
+            system_call if system_call == write_call => {
+                let num_bytes: usize = self.registers[12] as usize;
+                let address: usize = self.get_address_index(self.registers[11], num_bytes)?;
+                let text: String = self.memory[address..address + num_bytes].iter().map(|byte| *byte as char).collect();
+                self.output.push_str(&text);
+                return Ok(false);
+            },
+            // Exit
+            system_call if system_call == exit_call => return Ok(true),
+            system_call => return Err(format!("Unsupported system call {}", system_call))
+        }
+    }
+
+    // Function to run a system call of a convention that prints a character at a time
+    // The registers are numbers, where call_register has the call and char_register has the character
+    fn put_char_call(&mut self, call_register: usize, put_char_call: u64, exit_call: u64, char_register: usize) -> Result<bool, String> {
+        match self.registers[call_register] {
+            system_call if system_call == put_char_call => {
+                self.output.push(self.registers[char_register] as u8 as char);
+                return Ok(false);
+            },
+            system_call if system_call == exit_call => return Ok(true),
+            system_call => return Err(format!("Unsupported system call {}", system_call))
+        }
+    }
+
+    // Function to get the index into memory for an offset(register) operand
+    fn get_memory_index(&self, operand: &String, num_bytes: usize) -> Result<usize, String> {
+        let (offset, register): (&str, &str) = match (operand.find('('), operand.strip_suffix(')')) {
+            (Some(open_index), Some(_)) => (&operand[..open_index], &operand[open_index + 1..operand.len() - 1]),
+            _ => return Err(format!("Invalid memory operand [ {} ]", operand))
+        };
+        let address: u64 = self.get_register(register)?.wrapping_add(Self::parse_immediate(offset)? as u64);
+        return self.get_address_index(address, num_bytes);
+    }
+
+    // Function to make sure all of the bytes starting at the address are in memory
+    fn get_address_index(&self, address: u64, num_bytes: usize) -> Result<usize, String> {
+        if address < Self::DATA_START || address.saturating_add(num_bytes as u64) > Self::DATA_START + self.memory.len() as u64 {
+            return Err(format!("Address 0x{:X} is outside of memory", address));
+        }
+        return Ok((address - Self::DATA_START) as usize);
+    }
+
+    fn get_code_label(&self, label: &String) -> Result<usize, String> {
+        return self.code_labels.get(label).copied().ok_or_else(|| format!("There is no code label called {}", label));
+    }
+
+    fn get_register(&self, name: &str) -> Result<u64, String> {
+        return Ok(self.registers[Self::get_register_number(name)?]);
+    }
+
+    // Writes to zero are ignored like in the hardware
+    fn set_register(&mut self, name: &str, value: u64) -> Result<(), String> {
+        let register_number: usize = Self::get_register_number(name)?;
+        if register_number == 2 && value % 4 != 0 {
+            return Err(format!("Stack pointer 0x{:X} is not word aligned", value));
+        }
+        if register_number != 0 {
+            self.registers[register_number] = value;
+        }
+        return Ok(());
+    }
+
+    // Function to get the x register number for an ABI register name
+    fn get_register_number(name: &str) -> Result<usize, String> {
+        let (prefix, number): (&str, Result<usize, _>) = (&name[..name.len().min(1)], name[name.len().min(1)..].parse::<usize>());
+        let register_number: Option<usize> = match (name, prefix, number) {
+            ("zero", _, _) => Some(0),
+            ("ra", _, _) => Some(1),
+            ("sp", _, _) => Some(2),
+            ("gp", _, _) => Some(3),
+            ("tp", _, _) => Some(4),
+            ("fp", _, _) => Some(8),
+            (_, "x", Ok(num)) if num <= 31 => Some(num),
+            (_, "t", Ok(num)) if num <= 2 => Some(num + 5),
+            (_, "t", Ok(num)) if num <= 6 => Some(num + 25),
+            (_, "s", Ok(num)) if num <= 1 => Some(num + 8),
+            (_, "s", Ok(num)) if num <= 11 => Some(num + 16),
+            (_, "a", Ok(num)) if num <= 7 => Some(num + 10),
+            _ => None
+        };
+        return register_number.ok_or_else(|| format!("Invalid register [ {} ]", name));
+    }
+
+    fn parse_immediate(text: &str) -> Result<i64, String> {
+        let (is_negative, unsigned_text): (bool, &str) = match text.strip_prefix('-') {
+            Some(unsigned_text) => (true, unsigned_text),
+            None => (false, text)
+        };
+        let value: i64 = match unsigned_text.strip_prefix("0x") {
+            Some(hex) => i64::from_str_radix(hex, 16),
+            None => unsigned_text.parse::<i64>()
+        }.map_err(|_| format!("Invalid immediate value [ {} ]", text))?;
+        return Ok(if is_negative { -value } else { value });
+    }
+}
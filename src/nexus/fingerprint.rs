@@ -0,0 +1,203 @@
+use std::collections::BTreeSet;
+
+use petgraph::graph::NodeIndex;
+use serde::Serialize;
+#[cfg(feature = "ui")]
+use wasm_bindgen::prelude::*;
+
+use crate::util::{nexus_log::{self, LogRecord}, target::{Target, TargetConfig}};
+use crate::nexus::{preprocessor::Preprocessor, lexer::Lexer, token::{Token, TokenType}, parser::Parser, syntax_tree::SyntaxTree, syntax_tree_node::SyntaxTreeNode};
+use crate::nexus::{semantic_analyzer::SemanticAnalyzer, batch::BatchSource};
+
+// The number of AST tokens that are hashed together, so shorter runs in common are not counted as copied
+const KGRAM_SIZE: usize = 5;
+
+// The number of k-gram hashes in each window, where the smallest one is kept
+// Any copied run of at least KGRAM_SIZE + WINDOW_SIZE - 1 tokens is guaranteed to share a kept hash
+const WINDOW_SIZE: usize = 4;
+
+// The winnowed hashes of a source's ASTs
+#[derive (Debug, Clone, PartialEq)]
+pub struct Fingerprint {
+    // The number of normalized AST tokens that were hashed
+    pub num_tokens: usize,
+    pub hashes: BTreeSet<u64>
+}
+
+impl Fingerprint {
+    // Function to get how similar two fingerprints are from 0 (nothing in common) to 1 (the same structure)
+    pub fn similarity(&self, other: &Fingerprint) -> f64 {
+        let num_union: usize = self.hashes.union(&other.hashes).count();
+        if num_union == 0 {
+            return 1.0;
+        }
+        return self.hashes.intersection(&other.hashes).count() as f64 / num_union as f64;
+    }
+}
+
+// How similar two submissions in a batch are
+#[derive (Debug, Serialize)]
+#[serde (rename_all = "camelCase")]
+pub struct SimilarityPair {
+    pub first: String,
+    pub second: String,
+    pub similarity: f64
+}
+
+// The similarity of every pair of submissions, most similar first
+#[derive (Debug, Serialize)]
+#[serde (rename_all = "camelCase")]
+pub struct SimilarityReport {
+    pub pairs: Vec<SimilarityPair>,
+
+    // The names of the submissions that could not be parsed, so they have no fingerprint
+    pub unparsed: Vec<String>
+}
+
+// Function to compare every pair of named sources without touching the page
+// The sources are a JSON array of { name, source }, the same as compileMany
+#[cfg(feature = "ui")]
+#[wasm_bindgen(js_name = "compareSubmissions")]
+pub fn compare_submissions_json(sources_json: &str) -> Result<String, JsValue> {
+    let batch_sources: Vec<BatchSource> = serde_json::from_str(sources_json)
+        .map_err(|err| JsValue::from_str(format!("Invalid batch sources: {}", err).as_str()))?;
+
+    return Ok(serde_json::to_string(&compare_submissions(&batch_sources)).expect("Should be able to serialize the similarity report"));
+}
+
+// Function to fingerprint every named source and compare each pair of them
+pub fn compare_submissions(batch_sources: &Vec<BatchSource>) -> SimilarityReport {
+    let mut fingerprints: Vec<(&str, Fingerprint)> = Vec::new();
+    let mut unparsed: Vec<String> = Vec::new();
+    for batch_source in batch_sources.iter() {
+        match fingerprint_source(&batch_source.source) {
+            Ok(fingerprint) => fingerprints.push((&batch_source.name, fingerprint)),
+            Err(_) => unparsed.push(batch_source.name.to_owned())
+        }
+    }
+
+    let mut pairs: Vec<SimilarityPair> = Vec::new();
+    for (i, (first_name, first_fingerprint)) in fingerprints.iter().enumerate() {
+        for (second_name, second_fingerprint) in fingerprints.iter().skip(i + 1) {
+            pairs.push(SimilarityPair {
+                first: String::from(*first_name),
+                second: String::from(*second_name),
+                similarity: first_fingerprint.similarity(second_fingerprint)
+            });
+        }
+    }
+    pairs.sort_by(|a, b| b.similarity.total_cmp(&a.similarity));
+
+    return SimilarityReport {
+        pairs: pairs,
+        unparsed: unparsed
+    };
+}
+
+// Function to get how similar the structure of two sources is from 0 to 1
+// The errors and warnings are returned instead if either source could not be parsed
+pub fn compare_sources(first_source: &str, second_source: &str) -> Result<f64, Vec<LogRecord>> {
+    let first_fingerprint: Fingerprint = fingerprint_source(first_source)?;
+    let second_fingerprint: Fingerprint = fingerprint_source(second_source)?;
+    return Ok(first_fingerprint.similarity(&second_fingerprint));
+}
+
+// Function to get the fingerprint of the ASTs of every program in a source with the logs captured
+// The errors and warnings are returned instead if the source could not be preprocessed or any program could not be parsed
+pub fn fingerprint_source(source: &str) -> Result<Fingerprint, Vec<LogRecord>> {
+    nexus_log::start_capture();
+
+    let mut ast_tokens: Vec<String> = Vec::new();
+    let mut is_parsed: bool = false;
+    let mut preprocessor: Preprocessor = Preprocessor::new();
+    if let Ok(code) = preprocessor.expand_defines(source) {
+        is_parsed = true;
+        let mut lexer: Lexer = Lexer::new(&code);
+        let mut parser: Parser = Parser::new();
+        while lexer.has_program_to_lex() {
+            let token_stream: Vec<Token> = match lexer.lex_program() {
+                Ok(token_stream) => token_stream,
+                Err(_) => {
+                    is_parsed = false;
+                    continue;
+                }
+            };
+            if parser.parse_program(&token_stream).is_err() {
+                is_parsed = false;
+                continue;
+            }
+
+            // The AST does not depend on the target, and semantic errors do not change the structure that was written
+            let mut semantic_analyzer: SemanticAnalyzer = SemanticAnalyzer::new(TargetConfig::new(Target::Target6502));
+            let ast: SyntaxTree = semantic_analyzer.generate_ast(&token_stream);
+            if let Some(root) = ast.root {
+                get_ast_tokens(&ast, NodeIndex::new(root), &mut ast_tokens);
+            }
+        }
+    }
+
+    let diagnostics: Vec<LogRecord> = nexus_log::stop_capture();
+    if !is_parsed {
+        return Err(diagnostics);
+    }
+    return Ok(Fingerprint {
+        num_tokens: ast_tokens.len(),
+        hashes: winnow(&hash_kgrams(&ast_tokens))
+    });
+}
+
+// Function to flatten the AST into tokens in order, where every identifier is the same so renaming variables does not change anything
+fn get_ast_tokens(ast: &SyntaxTree, cur_index: NodeIndex, ast_tokens: &mut Vec<String>) {
+    match ast.graph.node_weight(cur_index).unwrap() {
+        SyntaxTreeNode::Terminal(token) => {
+            ast_tokens.push(match &token.token_type {
+                TokenType::Identifier(_) => String::from("id"),
+                _ => token.text.to_owned()
+            });
+        },
+        non_terminal => {
+            ast_tokens.push(format!("{:?}", non_terminal));
+
+            // Children are stored in reverse order of insertion
+            let neighbors: Vec<NodeIndex> = ast.graph.neighbors(cur_index).collect();
+            for neighbor_index in neighbors.into_iter().rev() {
+                get_ast_tokens(ast, neighbor_index, ast_tokens);
+            }
+
+            // Closing the node keeps statements inside and after a block from looking the same
+            ast_tokens.push(String::from("/"));
+        }
+    }
+}
+
+// Function to hash every run of KGRAM_SIZE tokens, where a shorter program is hashed as a whole
+fn hash_kgrams(ast_tokens: &Vec<String>) -> Vec<u64> {
+    if ast_tokens.len() == 0 {
+        return Vec::new();
+    }
+    return ast_tokens
+        .windows(KGRAM_SIZE.min(ast_tokens.len()))
+        .map(|kgram| hash_tokens(kgram))
+        .collect();
+}
+
+// Function to get the 64-bit FNV-1a hash of the tokens, which is the same on every platform and build
+fn hash_tokens(tokens: &[String]) -> u64 {
+    let mut hash: u64 = 0xcbf29ce484222325;
+    for token in tokens.iter() {
+        // The separator keeps tokens like "ab" "c" and "a" "bc" apart
+        for byte in token.bytes().chain(std::iter::once(0)) {
+            hash ^= byte as u64;
+            hash = hash.wrapping_mul(0x100000001b3);
+        }
+    }
+    return hash;
+}
+
+// Function to keep the smallest hash of each window, which is the same for any copied run no matter what is around it
+fn winnow(hashes: &Vec<u64>) -> BTreeSet<u64> {
+    return hashes
+        .windows(WINDOW_SIZE.min(hashes.len()).max(1))
+        .map(|window| *window.iter().min().expect("Windows should not be empty"))
+        .collect();
+}
@@ -0,0 +1,259 @@
+use serde::{Deserialize, Serialize};
+#[cfg(feature = "ui")]
+use wasm_bindgen::prelude::*;
+
+use crate::util::{nexus_log::{LogRecord, LogTypes}, messages::MessageCode, target::{Target, TargetConfig}, position::Position};
+use crate::nexus::{batch::{self, BatchSource, BatchResult, BatchProgramResult}, pipeline::CompileOptions};
+
+// The version of the rubric format, which is increased whenever a field changes meaning
+const RUBRIC_VERSION: u32 = 1;
+
+// A diagnostic a program is expected to report, such as { "code": "S012", "line": 5 }
+#[derive (Debug, Deserialize)]
+#[serde (rename_all = "camelCase", deny_unknown_fields)]
+pub struct ExpectedDiagnostic {
+    pub code: String,
+
+    // Any line matches if there is no line
+    #[serde (default)]
+    pub line: Option<usize>
+}
+
+// A single check of a rubric, which earns its points only if everything it expects is true
+#[derive (Debug, Deserialize)]
+#[serde (rename_all = "camelCase", deny_unknown_fields)]
+pub struct RubricCheck {
+    pub name: String,
+    #[serde (default = "get_default_points")]
+    pub points: u32,
+    pub program_number: u32,
+
+    // The exact errors of the program in order
+    #[serde (default)]
+    pub errors: Option<Vec<ExpectedDiagnostic>>,
+
+    // The exact warnings of the program in order
+    #[serde (default)]
+    pub warnings: Option<Vec<ExpectedDiagnostic>>,
+
+    // What the program prints when it is run with the interpreter
+    #[serde (default)]
+    pub output: Option<String>
+}
+
+fn get_default_points() -> u32 {
+    return 1;
+}
+
+// The checks an instructor grades every submission with
+#[derive (Debug, Deserialize)]
+#[serde (rename_all = "camelCase", deny_unknown_fields)]
+pub struct Rubric {
+    pub version: u32,
+
+    // The target the diagnostics are found with, which is 6502 or RISC-V
+    #[serde (default = "get_default_target")]
+    pub target: String,
+    pub checks: Vec<RubricCheck>
+}
+
+fn get_default_target() -> String {
+    return String::from("6502");
+}
+
+impl Rubric {
+    // Reads and checks a rubric, which returns why it cannot be used if it is not valid
+    pub fn from_json(rubric_json: &str) -> Result<Self, String> {
+        // The version is checked first so a rubric for a newer version gets a clear error instead of a missing field
+        let rubric_value: serde_json::Value = serde_json::from_str(rubric_json).map_err(|err| format!("Invalid rubric JSON: {}", err))?;
+        match rubric_value.get("version").and_then(|version| version.as_u64()) {
+            Some(version) if version == RUBRIC_VERSION as u64 => {},
+            Some(version) => return Err(format!("Rubric version {} is not supported, which must be {}", version, RUBRIC_VERSION)),
+            None => return Err(String::from("Invalid rubric, which must have a version number"))
+        }
+
+        let rubric: Rubric = serde_json::from_value(rubric_value).map_err(|err| format!("Invalid rubric: {}", err))?;
+        rubric.get_target_config()?;
+        for check in rubric.checks.iter() {
+            if check.program_number == 0 {
+                return Err(format!("Invalid check [ {} ], where programs are numbered starting at 1", check.name));
+            }
+            if check.errors.is_none() && check.warnings.is_none() && check.output.is_none() {
+                return Err(format!("Invalid check [ {} ], which must expect errors, warnings, or output", check.name));
+            }
+            for expected_diagnostic in check.errors.iter().chain(check.warnings.iter()).flatten() {
+                if MessageCode::from_code(&expected_diagnostic.code).is_none() {
+                    return Err(format!("Invalid check [ {} ], where [ {} ] is not a message code", check.name, expected_diagnostic.code));
+                }
+            }
+        }
+        return Ok(rubric);
+    }
+
+    fn get_target_config(&self) -> Result<TargetConfig, String> {
+        return match self.target.as_str() {
+            "6502" => Ok(TargetConfig::new(Target::Target6502)),
+            "RISC-V" => Ok(TargetConfig::new(Target::TargetRiscV)),
+            _ => Err(format!("Invalid target [ {} ], which must be 6502 or RISC-V", self.target))
+        };
+    }
+
+    // Function to get the total number of points of the rubric
+    pub fn get_max_score(&self) -> u32 {
+        return self.checks.iter().map(|check| check.points).sum();
+    }
+}
+
+// How a submission did on a single check
+#[derive (Debug, Serialize)]
+#[serde (rename_all = "camelCase")]
+pub struct CheckResult {
+    pub name: String,
+    pub points: u32,
+    pub passed: bool,
+
+    // Why the check failed, which is None if it passed
+    pub feedback: Option<String>
+}
+
+// The score of one submission
+#[derive (Debug, Serialize)]
+#[serde (rename_all = "camelCase")]
+pub struct GradeReport {
+    pub name: String,
+    pub score: u32,
+    pub max_score: u32,
+    pub checks: Vec<CheckResult>
+}
+
+// Function to grade many named sources with a rubric without touching the page
+// The sources are a JSON array of { name, source }, the same as compileMany
+#[cfg(feature = "ui")]
+#[wasm_bindgen(js_name = "gradeSubmissions")]
+pub fn grade_submissions_json(rubric_json: &str, sources_json: &str) -> Result<String, JsValue> {
+    let rubric: Rubric = Rubric::from_json(rubric_json).map_err(|message| JsValue::from_str(&message))?;
+    let batch_sources: Vec<BatchSource> = serde_json::from_str(sources_json)
+        .map_err(|err| JsValue::from_str(format!("Invalid batch sources: {}", err).as_str()))?;
+
+    return Ok(serde_json::to_string(&grade_submissions(&rubric, &batch_sources)).expect("Should be able to serialize the grade reports"));
+}
+
+// Function to grade every named source in order
+pub fn grade_submissions(rubric: &Rubric, batch_sources: &Vec<BatchSource>) -> Vec<GradeReport> {
+    return batch_sources.iter().map(|batch_source| grade_source(rubric, batch_source)).collect();
+}
+
+// Function to grade a single named source
+// The diagnostics come from compiling for the rubric's target, and the output comes from running every program with the interpreter
+pub fn grade_source(rubric: &Rubric, batch_source: &BatchSource) -> GradeReport {
+    let target_config: TargetConfig = rubric.get_target_config().expect("The target should have been checked with the rubric");
+    let compiled: BatchResult = batch::compile_source(batch_source, &target_config);
+
+    // Nothing has to be run if no check looks at the output
+    let interpreted: Option<BatchResult> = match rubric.checks.iter().any(|check| check.output.is_some()) {
+        true => {
+            let mut options: CompileOptions = CompileOptions::new(target_config);
+            options.is_interpreted = true;
+            Some(batch::compile_source_with_options(batch_source, &options))
+        },
+        false => None
+    };
+
+    let checks: Vec<CheckResult> = rubric.checks.iter()
+        .map(|check| {
+            let feedback: Vec<String> = get_check_feedback(check, &compiled, interpreted.as_ref());
+            CheckResult {
+                name: check.name.to_owned(),
+                points: check.points,
+                passed: feedback.len() == 0,
+                feedback: if feedback.len() == 0 { None } else { Some(feedback.join("; ")) }
+            }
+        })
+        .collect();
+
+    return GradeReport {
+        name: batch_source.name.to_owned(),
+        score: checks.iter().filter(|check| check.passed).map(|check| check.points).sum(),
+        max_score: rubric.get_max_score(),
+        checks: checks
+    };
+}
+
+// Function to get everything the program does differently than the check expects, which is empty if the check passed
+fn get_check_feedback(check: &RubricCheck, compiled: &BatchResult, interpreted: Option<&BatchResult>) -> Vec<String> {
+    let program: &BatchProgramResult = match compiled.programs.get(check.program_number as usize - 1) {
+        Some(program) => program,
+        None => return vec![format!("There is no program {}", check.program_number)]
+    };
+
+    let mut feedback: Vec<String> = Vec::new();
+    if let Some(expected_errors) = &check.errors {
+        if let Some(message) = compare_diagnostics(expected_errors, &program.diagnostics, true) {
+            feedback.push(message);
+        }
+    }
+    if let Some(expected_warnings) = &check.warnings {
+        if let Some(message) = compare_diagnostics(expected_warnings, &program.diagnostics, false) {
+            feedback.push(message);
+        }
+    }
+
+    if let Some(expected_output) = &check.output {
+        let run_program: &BatchProgramResult = &interpreted.expect("Programs should be run when a check has output").programs[check.program_number as usize - 1];
+        match (&run_program.output, &run_program.failed_stage) {
+            // Every print ends with a new line, so the new lines at the end do not have to be written in the rubric
+            (Some(output), None) if output.trim_end().eq(expected_output.trim_end()) => {},
+            (Some(output), None) => feedback.push(format!(
+                "Expected output [ {} ] but printed [ {} ]",
+                expected_output.trim_end().escape_debug(),
+                output.trim_end().escape_debug()
+            )),
+            // The interpreter logs why the program stopped
+            (_, Some(stage)) if stage.eq("interpreter") => feedback.extend(
+                run_program.diagnostics.iter()
+                    .filter(|record| matches!(record.log_type, LogTypes::Error))
+                    .map(|record| record.message.to_owned())
+            ),
+            (_, Some(stage)) => feedback.push(format!("Program {} could not be run because {} failed", check.program_number, stage)),
+            (None, None) => feedback.push(format!("Program {} could not be run", check.program_number))
+        }
+    }
+    return feedback;
+}
+
+// Function to compare the errors or warnings with the expected ones in order, which returns the feedback if they are different
+fn compare_diagnostics(expected_diagnostics: &Vec<ExpectedDiagnostic>, diagnostics: &Vec<LogRecord>, is_error: bool) -> Option<String> {
+    // Only the diagnostics from the catalog have a code, the rest are summaries like the number of errors
+    let actual_diagnostics: Vec<(MessageCode, Option<Position>)> = diagnostics.iter()
+        .filter(|record| matches!((record.log_type, is_error), (LogTypes::Error, true) | (LogTypes::Warning, false)))
+        .filter_map(|record| record.code.map(|code| (code, record.position)))
+        .collect();
+
+    let is_match: bool = actual_diagnostics.len() == expected_diagnostics.len()
+        && actual_diagnostics.iter().zip(expected_diagnostics.iter()).all(|((code, position), expected)| {
+            code.get_code().eq(expected.code.as_str())
+                && expected.line.map_or(true, |line| position.map_or(false, |actual_position| actual_position.line == line))
+        });
+    if is_match {
+        return None;
+    }
+
+    let expected_text: Vec<String> = expected_diagnostics.iter()
+        .map(|expected| match expected.line {
+            Some(line) => format!("{} at line {}", expected.code, line),
+            None => expected.code.to_owned()
+        })
+        .collect();
+    let actual_text: Vec<String> = actual_diagnostics.iter()
+        .map(|(code, position)| match position {
+            Some(position) => format!("{} at line {}", code, position.line),
+            None => code.to_string()
+        })
+        .collect();
+    let kind: &str = if is_error { "errors" } else { "warnings" };
+    return Some(match (expected_text.len(), actual_text.len()) {
+        (0, _) => format!("Expected no {} but found [ {} ]", kind, actual_text.join(", ")),
+        (_, 0) => format!("Expected {} [ {} ] but found none", kind, expected_text.join(", ")),
+        _ => format!("Expected {} [ {} ] but found [ {} ]", kind, expected_text.join(", "), actual_text.join(", "))
+    });
+}
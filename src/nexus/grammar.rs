@@ -0,0 +1,146 @@
+use std::collections::HashSet;
+
+use indexmap::IndexSet;
+
+use crate::nexus::syntax_tree_node::NonTerminalsCst;
+
+// A symbol on the right side of a production
+#[derive (Debug, Clone, Copy)]
+pub enum GrammarSymbol {
+    NonTerminal(NonTerminalsCst),
+
+    // How the token is shown in error messages, such as "Keyword(Print)"
+    Terminal(&'static str)
+}
+
+use GrammarSymbol::{NonTerminal as N, Terminal as T};
+
+// The grammar of the language that the parser implements, with one entry per alternative
+// An empty right side is epsilon
+pub fn get_productions() -> Vec<(NonTerminalsCst, Vec<GrammarSymbol>)> {
+    return vec![
+        (NonTerminalsCst::Program, vec![N(NonTerminalsCst::Block), T("Symbol(EOP)")]),
+        (NonTerminalsCst::Block, vec![T("Symbol(LBrace)"), N(NonTerminalsCst::StatementList), T("Symbol(RBrace)")]),
+        (NonTerminalsCst::StatementList, vec![N(NonTerminalsCst::Statement), N(NonTerminalsCst::StatementList)]),
+        (NonTerminalsCst::StatementList, vec![]),
+        (NonTerminalsCst::Statement, vec![N(NonTerminalsCst::PrintStatement)]),
+        (NonTerminalsCst::Statement, vec![N(NonTerminalsCst::AssignmentStatement)]),
+        (NonTerminalsCst::Statement, vec![N(NonTerminalsCst::VarDecl)]),
+        (NonTerminalsCst::Statement, vec![N(NonTerminalsCst::WhileStatement)]),
+        (NonTerminalsCst::Statement, vec![N(NonTerminalsCst::IfStatement)]),
+        (NonTerminalsCst::Statement, vec![N(NonTerminalsCst::Block)]),
+        (NonTerminalsCst::PrintStatement, vec![T("Keyword(Print)"), T("Symbol(LParen)"), N(NonTerminalsCst::Expr), T("Symbol(RParen)")]),
+        (NonTerminalsCst::AssignmentStatement, vec![N(NonTerminalsCst::Id), T("Symbol(AssignmentOp)"), N(NonTerminalsCst::Expr)]),
+        (NonTerminalsCst::VarDecl, vec![N(NonTerminalsCst::Type), N(NonTerminalsCst::Id)]),
+        (NonTerminalsCst::VarDecl, vec![N(NonTerminalsCst::Type), N(NonTerminalsCst::Id), T("Symbol(AssignmentOp)"), N(NonTerminalsCst::Expr)]),
+        (NonTerminalsCst::WhileStatement, vec![T("Keyword(While)"), N(NonTerminalsCst::BooleanExpr), N(NonTerminalsCst::Block)]),
+        (NonTerminalsCst::IfStatement, vec![T("Keyword(If)"), N(NonTerminalsCst::BooleanExpr), N(NonTerminalsCst::Block)]),
+        (NonTerminalsCst::Expr, vec![N(NonTerminalsCst::IntExpr)]),
+        (NonTerminalsCst::Expr, vec![N(NonTerminalsCst::StringExpr)]),
+        (NonTerminalsCst::Expr, vec![N(NonTerminalsCst::CharExpr)]),
+        (NonTerminalsCst::Expr, vec![N(NonTerminalsCst::IndexExpr)]),
+        (NonTerminalsCst::Expr, vec![N(NonTerminalsCst::LenExpr)]),
+        (NonTerminalsCst::Expr, vec![N(NonTerminalsCst::BooleanExpr)]),
+        (NonTerminalsCst::Expr, vec![N(NonTerminalsCst::Id)]),
+        (NonTerminalsCst::IntExpr, vec![N(NonTerminalsCst::Digit), N(NonTerminalsCst::IntOp), N(NonTerminalsCst::Expr)]),
+        (NonTerminalsCst::IntExpr, vec![N(NonTerminalsCst::Digit)]),
+        (NonTerminalsCst::IntExpr, vec![T("Symbol(LParen)"), N(NonTerminalsCst::IntExpr), T("Symbol(RParen)"), N(NonTerminalsCst::IntOp), N(NonTerminalsCst::Expr)]),
+        (NonTerminalsCst::IntExpr, vec![T("Symbol(LParen)"), N(NonTerminalsCst::IntExpr), T("Symbol(RParen)")]),
+        (NonTerminalsCst::StringExpr, vec![T("Symbol(Quote)"), N(NonTerminalsCst::CharList), T("Symbol(Quote)")]),
+        (NonTerminalsCst::CharExpr, vec![T("CharLiteral(a-z or space)")]),
+        (NonTerminalsCst::IndexExpr, vec![N(NonTerminalsCst::Id), T("Symbol(LBracket)"), N(NonTerminalsCst::Expr), T("Symbol(RBracket)")]),
+        (NonTerminalsCst::IndexExpr, vec![N(NonTerminalsCst::StringExpr), T("Symbol(LBracket)"), N(NonTerminalsCst::Expr), T("Symbol(RBracket)")]),
+        (NonTerminalsCst::LenExpr, vec![T("Keyword(Len)"), T("Symbol(LParen)"), N(NonTerminalsCst::Expr), T("Symbol(RParen)")]),
+        (NonTerminalsCst::BooleanExpr, vec![T("Symbol(LParen)"), N(NonTerminalsCst::Expr), N(NonTerminalsCst::BoolOp), N(NonTerminalsCst::Expr), T("Symbol(RParen)")]),
+        (NonTerminalsCst::BooleanExpr, vec![N(NonTerminalsCst::BoolVal)]),
+        (NonTerminalsCst::Id, vec![T("Identifier(a-z)")]),
+        (NonTerminalsCst::CharList, vec![N(NonTerminalsCst::Char), N(NonTerminalsCst::CharList)]),
+        (NonTerminalsCst::CharList, vec![N(NonTerminalsCst::Space), N(NonTerminalsCst::CharList)]),
+        (NonTerminalsCst::CharList, vec![]),
+        (NonTerminalsCst::Type, vec![T("Keyword(Int)")]),
+        (NonTerminalsCst::Type, vec![T("Keyword(String)")]),
+        (NonTerminalsCst::Type, vec![T("Keyword(Boolean)")]),
+        (NonTerminalsCst::Type, vec![T("Keyword(Char)")]),
+        (NonTerminalsCst::Char, vec![T("Char(a-z)")]),
+        (NonTerminalsCst::Space, vec![T("Char(Space)")]),
+        (NonTerminalsCst::Digit, vec![T("Digit(0-9)")]),
+        (NonTerminalsCst::BoolOp, vec![T("Symbol(EqOp)")]),
+        (NonTerminalsCst::BoolOp, vec![T("Symbol(NeqOp)")]),
+        (NonTerminalsCst::BoolVal, vec![T("Keyword(False)")]),
+        (NonTerminalsCst::BoolVal, vec![T("Keyword(True)")]),
+        (NonTerminalsCst::IntOp, vec![T("Symbol(AdditionOp)")])
+    ];
+}
+
+// The tokens each nonterminal can start with, computed from the productions
+pub struct FirstSets {
+    productions: Vec<(NonTerminalsCst, Vec<GrammarSymbol>)>,
+
+    // The nonterminals that can be empty
+    nullable: HashSet<NonTerminalsCst>
+}
+
+impl FirstSets {
+    pub fn new() -> Self {
+        let productions: Vec<(NonTerminalsCst, Vec<GrammarSymbol>)> = get_productions();
+        let mut nullable: HashSet<NonTerminalsCst> = HashSet::new();
+
+        // A nonterminal is nullable if every symbol of one of its alternatives is nullable
+        // Keep going until nothing changes
+        let mut changed: bool = true;
+        while changed {
+            changed = false;
+            for (non_terminal, right_side) in productions.iter() {
+                let is_nullable: bool = right_side.iter().all(|symbol| match symbol {
+                    GrammarSymbol::Terminal(_) => false,
+                    GrammarSymbol::NonTerminal(symbol_non_terminal) => nullable.contains(symbol_non_terminal)
+                });
+                if is_nullable {
+                    changed |= nullable.insert(*non_terminal);
+                }
+            }
+        }
+
+        return FirstSets {
+            productions: productions,
+            nullable: nullable
+        };
+    }
+
+    // Function to get the tokens the nonterminal can start with in the order they appear in the grammar
+    pub fn get_first(&self, non_terminal: NonTerminalsCst) -> Vec<&'static str> {
+        let mut first: IndexSet<&'static str> = IndexSet::new();
+        let mut visited: HashSet<NonTerminalsCst> = HashSet::new();
+        visited.insert(non_terminal);
+        self.collect_first(non_terminal, &mut visited, &mut first);
+        return first.into_iter().collect();
+    }
+
+    fn collect_first(&self, non_terminal: NonTerminalsCst, visited: &mut HashSet<NonTerminalsCst>, first: &mut IndexSet<&'static str>) {
+        for (_, right_side) in self.productions.iter().filter(|(left_side, _)| left_side.eq(&non_terminal)) {
+            // Go through the symbols until one cannot be empty
+            for symbol in right_side.iter() {
+                match symbol {
+                    GrammarSymbol::Terminal(token) => {
+                        first.insert(token);
+                        break;
+                    },
+                    GrammarSymbol::NonTerminal(symbol_non_terminal) => {
+                        // Left recursion or repeated nonterminals do not add anything new
+                        if visited.insert(*symbol_non_terminal) {
+                            self.collect_first(*symbol_non_terminal, visited, first);
+                        }
+                        if !self.nullable.contains(symbol_non_terminal) {
+                            break;
+                        }
+                    }
+                }
+            }
+        }
+    }
+
+    // Function to get the list of tokens for an error message, such as "[Keyword(Print), Identifier(a-z)]"
+    pub fn get_expected_text(&self, non_terminal: NonTerminalsCst) -> String {
+        return format!("[{}]", self.get_first(non_terminal).join(", "));
+    }
+}
@@ -0,0 +1,244 @@
+use std::collections::HashMap;
+
+use petgraph::graph::NodeIndex;
+
+use crate::nexus::{syntax_tree::SyntaxTree, syntax_tree_node::*, token::{TokenType, Keywords}};
+use crate::util::{nexus_log, target::TargetConfig};
+
+pub const LOG_SOURCE: nexus_log::LogSource = nexus_log::LogSource::new("Interpreter", "Interpreter");
+
+// A value of a variable or expression while the program runs
+#[derive (Debug, Clone, PartialEq)]
+pub enum Value {
+    Int(u32),
+    String(String),
+    Boolean(bool),
+    Char(char)
+}
+
+impl Value {
+    // Function to get the text that print shows for the value
+    pub fn to_output(&self) -> String {
+        return match self {
+            Value::Int(num) => num.to_string(),
+            Value::String(string) => string.to_owned(),
+            Value::Boolean(bool_val) => bool_val.to_string(),
+            Value::Char(c) => c.to_string()
+        };
+    }
+}
+
+// What a whole program printed, and why it stopped early if it did
+#[derive (Debug)]
+pub struct ProgramRun {
+    // The text of each print in order
+    pub prints: Vec<String>,
+    pub error: Option<String>
+}
+
+impl ProgramRun {
+    // Function to get everything the program printed, where every print ends with a new line like it does on both targets
+    pub fn get_output(&self) -> String {
+        return self.prints.iter().map(|print| format!("{}\n", print)).collect();
+    }
+}
+
+// Runs statements of an analyzed AST directly instead of generating code for them
+// Variables are kept between calls so statements can be run one at a time
+// It is also the reference for what a program should print, which the code generators are tested against
+pub struct Interpreter {
+    // The variables of each open scope, with the program scope first
+    scopes: Vec<HashMap<String, Value>>,
+
+    // Integers wrap around after this value like they do on the target
+    max_int: u32,
+
+    // Everything printed by the statement or program that is running
+    prints: Vec<String>,
+
+    // Whether each print is logged as soon as it happens
+    log_prints: bool
+}
+
+impl Interpreter {
+    // While loops stop after this many iterations so an infinite loop cannot freeze the page
+    const MAX_LOOP_ITERATIONS: usize = 1000;
+
+    pub fn new(target_config: &TargetConfig) -> Self {
+        return Interpreter {
+            scopes: vec![HashMap::new()],
+            max_int: target_config.max_int,
+            prints: Vec::new(),
+            log_prints: false
+        };
+    }
+
+    // Function to forget all of the variables
+    pub fn reset(&mut self) {
+        self.scopes = vec![HashMap::new()];
+    }
+
+    // Function to turn logging each print on or off
+    pub fn set_log_prints(&mut self, log_prints: bool) {
+        self.log_prints = log_prints;
+    }
+
+    // Function to run an entire program from the start without any variables
+    pub fn run_program(&mut self, ast: &SyntaxTree) -> ProgramRun {
+        self.reset();
+        self.prints.clear();
+
+        let program_res: Result<(), String> = self.execute(ast, NodeIndex::new((*ast).root.unwrap()));
+        self.scopes.truncate(1);
+
+        return ProgramRun {
+            prints: std::mem::take(&mut self.prints),
+            error: program_res.err()
+        };
+    }
+
+    // Function to run a statement in the program scope and get what it printed
+    pub fn run_statement(&mut self, ast: &SyntaxTree, statement_index: NodeIndex) -> Result<String, String> {
+        self.prints.clear();
+
+        // Only the program scope should be left if the statement stops early
+        let statement_res: Result<(), String> = self.execute(ast, statement_index);
+        self.scopes.truncate(1);
+
+        return match statement_res {
+            Ok(()) => Ok(std::mem::take(&mut self.prints).concat()),
+            Err(message) => Err(message)
+        };
+    }
+
+    fn execute(&mut self, ast: &SyntaxTree, cur_index: NodeIndex) -> Result<(), String> {
+        // Children are stored in reverse order
+        let children: Vec<NodeIndex> = (*ast).graph.neighbors(cur_index).collect();
+
+        match (*ast).graph.node_weight(cur_index).unwrap() {
+            SyntaxTreeNode::NonTerminalAst(NonTerminalsAst::Block) => {
+                self.scopes.push(HashMap::new());
+                for statement_index in children.into_iter().rev() {
+                    self.execute(ast, statement_index)?;
+                }
+                self.scopes.pop();
+            },
+            SyntaxTreeNode::NonTerminalAst(NonTerminalsAst::VarDecl) => {
+                // Variables start with the same default values as the generated code, where strings start empty on both targets
+                let default_value: Value = match get_token_type(ast, children[1]) {
+                    Some(TokenType::Keyword(Keywords::Int)) => Value::Int(0),
+                    Some(TokenType::Keyword(Keywords::String)) => Value::String(String::new()),
+                    Some(TokenType::Keyword(Keywords::Char)) => Value::Char(' '),
+                    _ => Value::Boolean(false)
+                };
+                let id: String = get_token_text(ast, children[0]);
+                self.scopes.last_mut().unwrap().insert(id, default_value);
+            },
+            SyntaxTreeNode::NonTerminalAst(NonTerminalsAst::Assign) => {
+                let value: Value = self.evaluate(ast, children[0])?;
+                let id: String = get_token_text(ast, children[1]);
+                match self.scopes.iter_mut().rev().find(|scope| scope.contains_key(&id)) {
+                    Some(scope) => { scope.insert(id, value); },
+                    None => return Err(format!("Id [ {} ] is not declared", id))
+                }
+            },
+            SyntaxTreeNode::NonTerminalAst(NonTerminalsAst::Print) => {
+                let output: String = self.evaluate(ast, children[0])?.to_output();
+                if self.log_prints {
+                    nexus_log::log(
+                        nexus_log::LogTypes::Info,
+                        LOG_SOURCE,
+                        format!("Printed [ {} ]", output)
+                    );
+                }
+                self.prints.push(output);
+            },
+            SyntaxTreeNode::NonTerminalAst(NonTerminalsAst::If) => {
+                // Children are [block, condition]
+                if self.evaluate(ast, children[1])?.eq(&Value::Boolean(true)) {
+                    self.execute(ast, children[0])?;
+                }
+            },
+            SyntaxTreeNode::NonTerminalAst(NonTerminalsAst::While) => {
+                let mut num_iterations: usize = 0;
+                while self.evaluate(ast, children[1])?.eq(&Value::Boolean(true)) {
+                    if num_iterations == Self::MAX_LOOP_ITERATIONS {
+                        return Err(format!("While loop was stopped after {} iterations", Self::MAX_LOOP_ITERATIONS));
+                    }
+                    self.execute(ast, children[0])?;
+                    num_iterations += 1;
+                }
+            },
+            other => return Err(format!("Cannot run [ {:?} ] as a statement", other))
+        }
+
+        return Ok(());
+    }
+
+    // Function to get the value of an expression with the current values of the variables
+    pub fn evaluate(&self, ast: &SyntaxTree, cur_index: NodeIndex) -> Result<Value, String> {
+        let children: Vec<NodeIndex> = (*ast).graph.neighbors(cur_index).collect();
+
+        return match (*ast).graph.node_weight(cur_index).unwrap() {
+            SyntaxTreeNode::Terminal(token) => {
+                match &token.token_type {
+                    TokenType::Digit(num) => Ok(Value::Int(*num as u32)),
+                    TokenType::Char(string) => Ok(Value::String(string.to_owned())),
+                    TokenType::CharLiteral(c) => Ok(Value::Char(*c)),
+                    TokenType::Keyword(Keywords::True) => Ok(Value::Boolean(true)),
+                    TokenType::Keyword(Keywords::False) => Ok(Value::Boolean(false)),
+                    TokenType::Identifier(id) => {
+                        match self.scopes.iter().rev().find_map(|scope| scope.get(id)) {
+                            Some(value) => Ok(value.to_owned()),
+                            None => Err(format!("Id [ {} ] is not declared", id))
+                        }
+                    },
+                    _ => Err(format!("Cannot evaluate [ {} ]", token.text))
+                }
+            },
+            SyntaxTreeNode::NonTerminalAst(NonTerminalsAst::Add) => {
+                // Children are [right, left]
+                match (self.evaluate(ast, children[1])?, self.evaluate(ast, children[0])?) {
+                    (Value::Int(left), Value::Int(right)) => Ok(Value::Int((left + right) % (self.max_int + 1))),
+                    _ => Err(String::from("Only integers can be added"))
+                }
+            },
+            SyntaxTreeNode::NonTerminalAst(NonTerminalsAst::Index) => {
+                // Children are [index, string]
+                match (self.evaluate(ast, children[1])?, self.evaluate(ast, children[0])?) {
+                    (Value::String(string), Value::Int(index)) => {
+                        match string.chars().nth(index as usize) {
+                            Some(c) => Ok(Value::Char(c)),
+                            None => Err(format!("Index {} is out of bounds for the string \"{}\" of length {}", index, string, string.len()))
+                        }
+                    },
+                    _ => Err(String::from("Only strings can be indexed by integers"))
+                }
+            },
+            SyntaxTreeNode::NonTerminalAst(NonTerminalsAst::Len) => {
+                match self.evaluate(ast, children[0])? {
+                    Value::String(string) => Ok(Value::Int(string.len() as u32 % (self.max_int + 1))),
+                    _ => Err(String::from("Only strings have a length"))
+                }
+            },
+            // Identical strings are stored once, so comparing the addresses is the same as comparing the contents
+            SyntaxTreeNode::NonTerminalAst(NonTerminalsAst::IsEq) => Ok(Value::Boolean(self.evaluate(ast, children[1])?.eq(&self.evaluate(ast, children[0])?))),
+            SyntaxTreeNode::NonTerminalAst(NonTerminalsAst::NotEq) => Ok(Value::Boolean(self.evaluate(ast, children[1])?.ne(&self.evaluate(ast, children[0])?))),
+            other => Err(format!("Cannot evaluate [ {:?} ]", other))
+        };
+    }
+}
+
+fn get_token_type(ast: &SyntaxTree, node_index: NodeIndex) -> Option<TokenType> {
+    return match (*ast).graph.node_weight(node_index).unwrap() {
+        SyntaxTreeNode::Terminal(token) => Some(token.token_type.to_owned()),
+        _ => None
+    };
+}
+
+fn get_token_text(ast: &SyntaxTree, node_index: NodeIndex) -> String {
+    return match (*ast).graph.node_weight(node_index).unwrap() {
+        SyntaxTreeNode::Terminal(token) => token.text.to_owned(),
+        other => format!("{:?}", other)
+    };
+}
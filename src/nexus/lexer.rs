@@ -1,4 +1,4 @@
-use crate::{nexus::token::{Token, TokenType, Keywords, Symbols}, util::nexus_log};
+use crate::{nexus::{token::{Token, TokenType, Keywords, Symbols}, preprocessor::ColumnMap}, util::{nexus_log, messages::{Message, MessageCode}, language_profile::{LanguageProfile, LanguageFeature}, position::Position}};
 use regex::{Regex, RegexSet, SetMatches};
 
 // Struct to maintain the state of the line numbers when compiling multiple programs
@@ -9,9 +9,14 @@ pub struct Lexer {
     current_position: usize, // The current position in the string
     keywords: RegexSet, // The regex set for keywords
     characters: Regex, // The regex for characters
+    identifiers: Regex, // The regex for identifiers, which depends on the language profile
+    language_profile: LanguageProfile, // The grammar extensions that can be used
+    char_literals: Regex, // The regex for char literals
     symbols: RegexSet, // The regex set for symbols
     digits: Regex, // The regex for digits
-    terminal_chars: RegexSet // The regex set for terminal characters
+    terminal_chars: RegexSet, // The regex set for terminal characters
+    column_map: ColumnMap, // Where constants changed the columns of the source code
+    pub num_errors: i32 // The number of errors in the most recently lexed program
 }
 
 impl Lexer {
@@ -22,6 +27,8 @@ impl Lexer {
             line_number: 1,
             col_number: 1,
             current_position: 0,
+            num_errors: 0,
+            column_map: ColumnMap::default(),
             
             // All of the acceptable keywords
             keywords: RegexSet::new(&[
@@ -31,14 +38,23 @@ impl Lexer {
                 r"^string$",
                 r"^int$",
                 r"^boolean$",
+                r"^char$",
                 r"^true$",
                 r"^false$",
+                r"^len$",
             ]).unwrap(),
 
             // a-z
             characters: Regex::new(r"^[a-z]$").unwrap(),
 
-            // (, ), {, }, ==, =, +, ", !=, or $
+            // a-z unless the language profile allows more characters after the first letter
+            identifiers: LanguageProfile::new().get_identifier_regex(),
+            language_profile: LanguageProfile::new(),
+
+            // 'a' through 'z' or ' '
+            char_literals: Regex::new(r"^'[a-z ]'$").unwrap(),
+
+            // (, ), {, }, ==, =, +, ", !=, $, [, or ]
             symbols: RegexSet::new(&[
                 r"^\($",
                 r"^\)$",
@@ -49,7 +65,9 @@ impl Lexer {
                 r"^!=$",
                 r"^=$",
                 r#"^"$"#,
-                r"^\$$"
+                r"^\$$",
+                r"^\[$",
+                r"^\]$"
             ]).unwrap(),
 
             // 0-9
@@ -66,14 +84,33 @@ impl Lexer {
                 r"^\{$",
                 r"^\}$",
                 r"^\+$",
-                r"^\$$"
+                r"^\$$",
+                r"^\[$",
+                r"^\]$"
             ]).unwrap()
         }
     }
 
+    // Function to lex identifiers and the extension tokens the way the language profile allows
+    pub fn set_language_profile(&mut self, language_profile: &LanguageProfile) {
+        self.identifiers = language_profile.get_identifier_regex();
+        self.language_profile = language_profile.to_owned();
+    }
+
+    // Function to give tokens their columns in the source code instead of the code with the constants expanded
+    pub fn set_column_map(&mut self, column_map: ColumnMap) {
+        self.column_map = column_map;
+    }
+
+    // Function to get the current position in the source code
+    fn get_source_position(&self) -> Position {
+        return self.column_map.get_original_position(self.line_number, self.col_number);
+    }
+
     // Function to lex a program
     pub fn lex_program(&mut self) -> Result<Vec<Token>, ()> {
         let lex_out: Result<(Vec<Token>, i32), (i32, i32)> = self.lex();
+        self.num_errors = 0;
         if lex_out.is_ok() {
             // Grab the token stream and number of warnings
             let (token_stream, num_warnings): (Vec<Token>, i32) = lex_out.unwrap();
@@ -85,7 +122,7 @@ impl Lexer {
             }
             nexus_log::log(
                 nexus_log::LogTypes::Info,
-                nexus_log::LogSources::Lexer,
+                nexus_log::LogSource::LEXER,
                 out_string
             );
 
@@ -94,6 +131,7 @@ impl Lexer {
         } else {
             // Get the number of errors and warnings
             let (num_errors, num_warnings): (i32, i32) = lex_out.unwrap_err();
+            self.num_errors = num_errors;
 
             // Generate the output string
             let mut out_string: String = format!("Lexer failed with {} error", num_errors);
@@ -113,7 +151,7 @@ impl Lexer {
             // Log the output string
             nexus_log::log(
                 nexus_log::LogTypes::Error,
-                nexus_log::LogSources::Lexer,
+                nexus_log::LogSource::LEXER,
                 out_string
             );
 
@@ -150,7 +188,7 @@ impl Lexer {
 
         // Initially not in a comment
         let mut in_comment: bool = false;
-        let mut comment_position: (usize, usize) = (0, 0);
+        let mut comment_position: Position = Position::default();
         let comment_regex: RegexSet = RegexSet::new(&[r"^/\*$", r"^\*/$"]).unwrap();
 
         let mut end_found: bool = false;
@@ -167,11 +205,12 @@ impl Lexer {
                 if !in_comment && comment_matches.matched(0) || in_comment && comment_matches.matched(1) {
                     // Get the updated comment start position
                     if !in_comment {
-                        comment_position = (self.line_number, self.col_number);
+                        comment_position = self.get_source_position();
                     }
 
                     // Flip and skip both characters
                     in_comment = !in_comment;
+                    self.advance_position(self.current_position + 2);
                     self.current_position += 2;
                     best_end += 2;
                     trailer += 2;
@@ -184,12 +223,11 @@ impl Lexer {
                 cur_char = &self.source_code[trailer..trailer + 1];
             }
 
-            let mut terminal_found: bool = false;
-            // Check prevents index out of bounds on the low end
-            if trailer > 0 {
-                // Check to see if we hit a terminal character
-                terminal_found = self.check_terminal(&cur_char, &self.source_code[trailer - 1..trailer], &in_string, &trailer);
-            }
+            // There is no previous character at the very start, such as when a define line was blanked out
+            let prev_char: &str = if trailer > 0 { &self.source_code[trailer - 1..trailer] } else { "" };
+
+            // Check to see if we hit a terminal character
+            let terminal_found: bool = self.check_terminal(&cur_char, prev_char, &in_string, &trailer);
 
             // Check if it is a terminal character or in a comment
             if !in_comment && !cur_char.is_empty() && !terminal_found {
@@ -206,7 +244,8 @@ impl Lexer {
                 // Make sure we have something
                 if best_end - self.current_position > 0 {
                     // Create the new token and add it to the stream
-                    let new_token: Token = Token::new(cur_token_type.to_owned(), self.source_code[self.current_position..best_end].to_string(), self.line_number, self.col_number);
+                    let token_position: Position = self.get_source_position();
+                    let new_token: Token = Token::new(cur_token_type.to_owned(), self.source_code[self.current_position..best_end].to_string(), token_position.line, token_position.col);
                     token_stream.push(new_token);
 
                     let new_token_ref: &Token = &token_stream[token_stream.len() - 1];
@@ -214,23 +253,23 @@ impl Lexer {
                         // Log the keyword information
                         TokenType::Keyword(keyword_type) => nexus_log::log(
                             nexus_log::LogTypes::Debug,
-                            nexus_log::LogSources::Lexer,
-                            format!("Keyword - {:?} [ {} ] found at {:?}", keyword_type, new_token_ref.text, new_token_ref.position)
+                            nexus_log::LogSource::LEXER,
+                            format!("Keyword - {:?} [ {} ] found at {}", keyword_type, new_token_ref.text, new_token_ref.position)
                         ),
 
                         // Log the identifier information
                         TokenType::Identifier(id) => nexus_log::log(
                             nexus_log::LogTypes::Debug, 
-                            nexus_log::LogSources::Lexer,
-                            format!("Identifier [ {} ] found at {:?}", id, new_token_ref.position)
+                            nexus_log::LogSource::LEXER,
+                            format!("Identifier [ {} ] found at {}", id, new_token_ref.position)
                         ),
                         
                         // Log the symbol information
                         TokenType::Symbol(symbol_type) => {
                             nexus_log::log(
                                 nexus_log::LogTypes::Debug,
-                                nexus_log::LogSources::Lexer,
-                                format!("Symbol - {:?} [ {} ] found at {:?}", symbol_type, new_token_ref.text, new_token_ref.position)
+                                nexus_log::LogSource::LEXER,
+                                format!("Symbol - {:?} [ {} ] found at {}", symbol_type, new_token_ref.text, new_token_ref.position)
                             );
 
                             // Mark the end found if needed
@@ -243,8 +282,8 @@ impl Lexer {
                         // Log the digit information
                         TokenType::Digit(num) => nexus_log::log(
                             nexus_log::LogTypes::Debug,
-                            nexus_log::LogSources::Lexer,
-                            format!("Digit [ {} ] found at {:?}", num, new_token_ref.position)
+                            nexus_log::LogSource::LEXER,
+                            format!("Digit [ {} ] found at {}", num, new_token_ref.position)
                         ),
                         
                         // Log the char information
@@ -253,17 +292,24 @@ impl Lexer {
                                 // Make sure space is verbally mentioned in the output and not just a space character
                                 " " => nexus_log::log(
                                     nexus_log::LogTypes::Debug,
-                                    nexus_log::LogSources::Lexer,
-                                    format!("Char [ SPACE ] found at {:?}", new_token_ref.position)
+                                    nexus_log::LogSource::LEXER,
+                                    format!("Char [ SPACE ] found at {}", new_token_ref.position)
                                 ),
                                 _ => nexus_log::log(
                                     nexus_log::LogTypes::Debug,
-                                    nexus_log::LogSources::Lexer,
-                                    format!("Char [ {} ] found at {:?}", char, new_token_ref.position)
+                                    nexus_log::LogSource::LEXER,
+                                    format!("Char [ {} ] found at {}", char, new_token_ref.position)
                                 )
                             }
                         },
 
+                        // Log the char literal information
+                        TokenType::CharLiteral(c) => nexus_log::log(
+                            nexus_log::LogTypes::Debug,
+                            nexus_log::LogSource::LEXER,
+                            format!("Char literal [ {} ] found at {}", if *c == ' ' { String::from("SPACE") } else { c.to_string() }, new_token_ref.position)
+                        ),
+
                         // Unrecognized tokens throw errors
                         TokenType::Unrecognized(token) => {
                             if in_string {
@@ -276,35 +322,68 @@ impl Lexer {
                                         _ => open_quote_pos -= 1,
                                     };
                                 }
-                                match token.as_str() {
+                                let token_text: String = match token.as_str() {
                                     // Make sure the tab gets noticed in the error message
-                                    "\t" => nexus_log::log(
-                                        nexus_log::LogTypes::Error,
-                                        nexus_log::LogSources::Lexer,
-                                        format!("Error at {:?}; Unrecognized token 'TAB' in string starting at {:?}; Strings may only contain lowercase letters (a - z) and spaces", new_token_ref.position, token_stream[open_quote_pos as usize].position)
-                                    ),
-                                    _ => nexus_log::log(
-                                        nexus_log::LogTypes::Error,
-                                        nexus_log::LogSources::Lexer,
-                                        format!("Error at {:?}; Unrecognized token '{}' in string starting at {:?}; Strings may only contain lowercase letters (a - z) and spaces", new_token_ref.position, new_token_ref.text, token_stream[open_quote_pos as usize].position)
-                                    )
-                                }
+                                    "\t" => String::from("TAB"),
+                                    _ => new_token_ref.text.to_owned()
+                                };
+                                nexus_log::log_message(
+                                    nexus_log::LogTypes::Error,
+                                    nexus_log::LogSource::LEXER,
+                                    new_token_ref.position,
+                                    &Message::new(MessageCode::UnrecognizedStringToken, vec![
+                                        ("position", new_token_ref.position.to_message_param()),
+                                        ("token", token_text),
+                                        ("string_position", token_stream[open_quote_pos as usize].position.to_message_param())
+                                    ])
+                                );
                             } else {
-                                nexus_log::log(
+                                // Capital letters are usually a keyword or identifier with the wrong case
+                                let mut params: Vec<(&'static str, String)> = vec![
+                                    ("position", new_token_ref.position.to_message_param()),
+                                    ("token", new_token_ref.text.to_owned())
+                                ];
+                                let code: MessageCode = match self.get_case_hint() {
+                                    Some((hint_code, suggestion)) => {
+                                        params.push(("suggestion", suggestion));
+                                        hint_code
+                                    },
+                                    None => MessageCode::UnrecognizedToken
+                                };
+                                nexus_log::log_message(
                                     nexus_log::LogTypes::Error,
-                                    nexus_log::LogSources::Lexer,
-                                    format!("Error at {:?}; Unrecognized token '{}'", new_token_ref.position, new_token_ref.text)
-                                )
+                                    nexus_log::LogSource::LEXER,
+                                    new_token_ref.position,
+                                    &Message::new(code, params)
+                                );
                             }
                             num_errors += 1;
                         },
                     }
 
+                    // Tokens that only the grammar extensions have are errors if the profile does not allow them
+                    if let Some(feature) = Lexer::get_token_feature(&new_token_ref.token_type) {
+                        if !self.language_profile.allows(feature) {
+                            nexus_log::log_message(
+                                nexus_log::LogTypes::Error,
+                                nexus_log::LogSource::LEXER,
+                                new_token_ref.position,
+                                &Message::new(MessageCode::TokenNotInProfile, vec![
+                                    ("position", new_token_ref.position.to_message_param()),
+                                    ("token", new_token_ref.text.to_owned()),
+                                    ("feature", String::from(feature.get_name())),
+                                    ("profile", self.language_profile.preset.to_string())
+                                ])
+                            );
+                            num_errors += 1;
+                        }
+                    }
+
                     // Go back to an unrecognized empty token
                     cur_token_type = TokenType::Unrecognized(String::from(""));
 
-                    // Update the column number to accommodate the length of the token
-                    self.col_number += best_end - self.current_position;
+                    // Update the line and column numbers to accommodate the text of the token
+                    self.advance_position(best_end);
 
                     // Move the trailer to the best end - 1 (will get incremented at the loop bottom)
                     trailer = best_end - 1;
@@ -318,11 +397,12 @@ impl Lexer {
                     if cur_char.eq("\n") {
                         if in_string {
                             // Get the starting position of the string
-                            let string_start: (usize, usize) = self.get_string_start(&token_stream);
-                            nexus_log::log(
+                            let string_start: Position = self.get_string_start(&token_stream);
+                            nexus_log::log_message(
                                 nexus_log::LogTypes::Error,
-                                nexus_log::LogSources::Lexer,
-                                format!("Unclosed string starting at {:?}", string_start)
+                                nexus_log::LogSource::LEXER,
+                                string_start,
+                                &Message::new(MessageCode::UnclosedString, vec![("position", string_start.to_message_param())])
                             );
                             num_errors += 1;
 
@@ -344,10 +424,11 @@ impl Lexer {
 
         // If comment is still open at end of program, the user should be warned
         if in_comment {
-            nexus_log::log(
+            nexus_log::log_message(
                 nexus_log::LogTypes::Warning,
-                nexus_log::LogSources::Lexer,
-                format!("Unclosed comment starting at {:?}", comment_position)
+                nexus_log::LogSource::LEXER,
+                comment_position,
+                &Message::new(MessageCode::UnclosedComment, vec![("position", comment_position.to_message_param())])
             );
             num_warnings += 1;
         }
@@ -355,11 +436,12 @@ impl Lexer {
         // If string is still open at end of program, an error will be thrown for consistency with the other instance
         if in_string {
             // Get the starting position of the string
-            let string_start: (usize, usize) = self.get_string_start(&token_stream);
-            nexus_log::log(
+            let string_start: Position = self.get_string_start(&token_stream);
+            nexus_log::log_message(
                 nexus_log::LogTypes::Error,
-                nexus_log::LogSources::Lexer,
-                format!("Unclosed string starting at {:?}", string_start)
+                nexus_log::LogSource::LEXER,
+                string_start,
+                &Message::new(MessageCode::UnclosedString, vec![("position", string_start.to_message_param())])
             );
             num_errors += 1;
         }
@@ -370,11 +452,14 @@ impl Lexer {
                 // We are good if we have EOP
                 TokenType::Symbol(Symbols::EOP) => {},
                 // Otherwise log out the warning
+                // The parser inserts the $ here so the program can still be compiled
                 _ => {
-                    nexus_log::log(
+                    let eop_position: Position = token_stream[token_stream.len() - 1].get_span().end;
+                    nexus_log::log_message(
                         nexus_log::LogTypes::Warning,
-                        nexus_log::LogSources::Lexer,
-                        String::from("Program did not end with EOP symbol [ $ ]")
+                        nexus_log::LogSource::LEXER,
+                        eop_position,
+                        &Message::new(MessageCode::MissingEop, vec![("position", eop_position.to_message_param())])
                     );
                     num_warnings += 1;
                 }
@@ -383,7 +468,7 @@ impl Lexer {
             // Empty programs by definition have no tokens and, thus, no EOP token
             nexus_log::log(
                 nexus_log::LogTypes::Warning,
-                nexus_log::LogSources::Lexer,
+                nexus_log::LogSource::LEXER,
                 String::from("Program did not end with EOP symbol [ $ ]")
             );
             num_warnings += 1;
@@ -399,6 +484,29 @@ impl Lexer {
         }
     }
 
+    // Function to move the line and column numbers past the source code from current_position up to end
+    // Each character is counted so any new line in the text starts the next line instead of adding to the column
+    fn advance_position(&mut self, end: usize) {
+        for c in self.source_code[self.current_position..end].chars() {
+            if c == '\n' {
+                self.line_number += 1;
+                self.col_number = 1;
+            } else {
+                self.col_number += 1;
+            }
+        }
+    }
+
+    // Function to get the grammar extension that a token belongs to, which is None for tokens of the course grammar
+    fn get_token_feature(token_type: &TokenType) -> Option<LanguageFeature> {
+        return match token_type {
+            TokenType::Keyword(Keywords::Char) | TokenType::CharLiteral(_) => Some(LanguageFeature::Chars),
+            TokenType::Symbol(Symbols::LBracket) | TokenType::Symbol(Symbols::RBracket) => Some(LanguageFeature::StringIndexing),
+            TokenType::Keyword(Keywords::Len) => Some(LanguageFeature::StringLength),
+            _ => None
+        };
+    }
+
     // Function to upgrade a token based on new information
     fn upgrade_token(&self, substr: &str, best_token_type: &mut TokenType, in_string: &mut bool) -> bool {
         // See if we are in a string
@@ -430,14 +538,20 @@ impl Lexer {
                         3 => *best_token_type = TokenType::Keyword(Keywords::String),
                         4 => *best_token_type = TokenType::Keyword(Keywords::Int),
                         5 => *best_token_type = TokenType::Keyword(Keywords::Boolean),
-                        6 => *best_token_type = TokenType::Keyword(Keywords::True),
-                        7 => *best_token_type = TokenType::Keyword(Keywords::False),
+                        6 => *best_token_type = TokenType::Keyword(Keywords::Char),
+                        7 => *best_token_type = TokenType::Keyword(Keywords::True),
+                        8 => *best_token_type = TokenType::Keyword(Keywords::False),
+                        9 => *best_token_type = TokenType::Keyword(Keywords::Len),
                         // Should never be reached
                         _ => panic!("Invalid regex found for keywords")
                     }
                     return true;
                 }
-            } else if self.characters.is_match(substr) {
+            } else if self.char_literals.is_match(substr) {
+                // Char literals are a single token with their quotes, so they never start a string
+                *best_token_type = TokenType::CharLiteral(substr.chars().nth(1).unwrap());
+                return true;
+            } else if self.identifiers.is_match(substr) {
                 // Otherwise it may be an identifier, digit, symbol, or unrecognized
                 // We have an identifier
                 *best_token_type = TokenType::Identifier(String::from(substr));
@@ -461,6 +575,8 @@ impl Lexer {
                             *in_string = true;
                         },
                         9 => *best_token_type = TokenType::Symbol(Symbols::EOP),
+                        10 => *best_token_type = TokenType::Symbol(Symbols::LBracket),
+                        11 => *best_token_type = TokenType::Symbol(Symbols::RBracket),
                         // Should never be reached
                         _ => panic!("Invalid regex found for symbols")
                     }
@@ -491,7 +607,9 @@ impl Lexer {
         if terminal_match.matched_any() {
             if terminal_match.matched(0) {
                 if current_char.eq(" ") || current_char.eq("\t") {
-                    if !*in_string {
+                    // The space in ' ' is the character of the char literal
+                    let is_char_literal_space: bool = current_char.eq(" ") && prev_char.eq("'") && *trailer == self.current_position + 1;
+                    if !*in_string && !is_char_literal_space {
                         out = true;
                     }
                 } else {
@@ -524,6 +642,58 @@ impl Lexer {
         return out;
     }
 
+    // Function to get the message and the suggested fix when an unrecognized token is the start of a word with capital letters
+    // Only the first letter of the word gets the hint so "PRINT" does not get it 5 times
+    fn get_case_hint(&self) -> Option<(MessageCode, String)> {
+        let source_bytes: &[u8] = self.source_code.as_bytes();
+        if !source_bytes[self.current_position].is_ascii_uppercase() || (self.current_position > 0 && source_bytes[self.current_position - 1].is_ascii_alphabetic()) {
+            return None;
+        }
+
+        // Get the rest of the word and see if it is close to a keyword
+        let word_end: usize = source_bytes[self.current_position..].iter().position(|c| !c.is_ascii_alphabetic()).map(|offset| self.current_position + offset).unwrap_or(source_bytes.len());
+        let word: String = self.source_code[self.current_position..word_end].to_lowercase();
+        let closest_keyword: Option<(&str, usize)> = KEYWORD_NAMES.iter()
+            .map(|keyword| (*keyword, get_edit_distance(&word, keyword)))
+            .min_by_key(|(_, distance)| *distance);
+
+        return match closest_keyword {
+            Some((keyword, distance)) if distance <= 1 && word.len() > 1 => Some((MessageCode::UnrecognizedKeywordCase, String::from(keyword))),
+            _ if word.len() == 1 => Some((MessageCode::UnrecognizedIdCase, word)),
+            _ => None
+        };
+    }
+
+    // Function to move past the whitespace before the next program, so the program starts at its first character
+    pub fn skip_whitespace(&mut self) {
+        let end: usize = self.source_code[self.current_position..].find(|c: char| !c.is_whitespace())
+            .map(|offset| self.current_position + offset)
+            .unwrap_or(self.source_code.len());
+        self.advance_position(end);
+        self.current_position = end;
+    }
+
+    // Function to get where the lexer is as (index in the source code, position), which is where the next program starts
+    pub fn get_cursor(&self) -> (usize, Position) {
+        return (self.current_position, Position::new(self.line_number, self.col_number));
+    }
+
+    // Function to move the lexer past a program without lexing it, which is used when its tokens are already known
+    pub fn set_cursor(&mut self, cursor: (usize, Position)) {
+        self.current_position = cursor.0;
+        self.line_number = cursor.1.line;
+        self.col_number = cursor.1.col;
+    }
+
+    // Function to get the source code between two indices, such as the text of a program that was lexed
+    pub fn get_source(&self, start: usize, end: usize) -> Option<&str> {
+        return self.source_code.get(start..end);
+    }
+
+    pub fn get_source_len(&self) -> usize {
+        return self.source_code.len();
+    }
+
     // Check to see if we can lex another program
     pub fn has_program_to_lex(&self) -> bool {
         // We have a program to lex if there is still content in the string that is not purely whitespace
@@ -531,12 +701,15 @@ impl Lexer {
     }
 
     // Function to make sure there is still content to go through
+    // Comments are skipped the same way the lexer skips them, so a comment after the last $ is not another program
     fn has_content(&self) -> bool {
         // String only has whitespace
         let whitespace_regex: Regex = Regex::new(r"^\s*$").unwrap();
+        let comment_regex: Regex = Regex::new(r"(?s)/\*.*?(\*/|\z)").unwrap();
+        let remaining_code: String = comment_regex.replace_all(&self.source_code[self.current_position..], " ").to_string();
 
         // Determine if it is only whitespace or if there is content
-        if whitespace_regex.is_match(&self.source_code[self.current_position..]) {
+        if whitespace_regex.is_match(&remaining_code) {
             return false;
         } else {
             return true;
@@ -544,7 +717,7 @@ impl Lexer {
     }
 
     // Get the starting position
-    fn get_string_start(&self, token_stream: &Vec<Token>) -> (usize, usize) {
+    fn get_string_start(&self, token_stream: &Vec<Token>) -> Position {
         // Get the index of the open quote token by doing a backwards linear search
         let mut i: i32 = token_stream.len() as i32 - 1;
         while i >= 0 {
@@ -557,10 +730,31 @@ impl Lexer {
 
         if i < 0 {
             // Return this in case the quote is not found (should never happen)
-            return (0, 0)
+            return Position::default()
         } else {
             // Return a copy of the string starting position
             return token_stream[i as usize].position.clone();
         }
     }
 }
+
+// The keywords in the same order as the regex set, used to suggest the right spelling
+const KEYWORD_NAMES: [&str; 10] = ["if", "while", "print", "string", "int", "boolean", "char", "true", "false", "len"];
+
+// Function to compute the Levenshtein distance between 2 words
+fn get_edit_distance(first: &str, second: &str) -> usize {
+    let second_chars: Vec<char> = second.chars().collect();
+
+    // Only the previous row of the table is needed
+    let mut prev_row: Vec<usize> = (0..=second_chars.len()).collect();
+    for (i, first_char) in first.chars().enumerate() {
+        let mut cur_row: Vec<usize> = vec![i + 1];
+        for (j, second_char) in second_chars.iter().enumerate() {
+            let substitution_cost: usize = if first_char == *second_char { 0 } else { 1 };
+            cur_row.push((prev_row[j] + substitution_cost).min(prev_row[j + 1] + 1).min(cur_row[j] + 1));
+        }
+        prev_row = cur_row;
+    }
+
+    return prev_row[second_chars.len()];
+}
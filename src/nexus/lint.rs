@@ -0,0 +1,363 @@
+use std::collections::HashSet;
+
+use petgraph::graph::NodeIndex;
+use indexmap::IndexMap;
+
+use crate::nexus::{syntax_tree::SyntaxTree, syntax_tree_node::*, token::{TokenType, Keywords}, semantic_analyzer::SemanticAnalysisResult, optimizer};
+use crate::util::{nexus_log, diagnostic::LintWarning, messages::{Message, MessageCode}, target::{Target, TargetConfig}, position::Position};
+
+// Lint warnings are logged separately from the rest of semantic analysis
+pub const LOG_SOURCE: nexus_log::LogSource = nexus_log::LogSource::new("Linter", "Linter");
+
+// A check that runs over the annotated AST after semantic analysis was successful
+// Lints only find code that is legal but probably not what was meant, so they never cause compilation to fail
+pub trait Lint {
+    // The name used to turn the lint on and off, such as "empty-block"
+    fn get_name(&self) -> &'static str;
+
+    // Function to check the program and get the position and message of each problem that was found
+    fn check(&self, semantic_analysis_res: &SemanticAnalysisResult, target_config: &TargetConfig) -> Vec<(Position, Message)>;
+}
+
+// Runs every enabled lint and reports the warnings
+pub struct Linter {
+    lints: Vec<Box<dyn Lint>>,
+    disabled_lints: HashSet<&'static str>
+}
+
+impl Linter {
+    // Creates a linter with all of the built-in lints enabled
+    pub fn new() -> Self {
+        return Linter {
+            lints: vec![
+                Box::new(EmptyBlockLint),
+                Box::new(ConstantConditionLint),
+                Box::new(SelfAssignmentLint),
+                Box::new(SelfComparisonLint),
+                Box::new(ShadowingLint),
+                Box::new(StringHeapLint)
+            ],
+            disabled_lints: HashSet::new()
+        };
+    }
+
+    // Function to get the name of every lint in the order they run
+    pub fn get_lint_names(&self) -> Vec<&'static str> {
+        return self.lints.iter().map(|lint| lint.get_name()).collect();
+    }
+
+    pub fn set_enabled(&mut self, lint_name: &str, is_enabled: bool) {
+        let name_res: Option<&'static str> = self.lints.iter().map(|lint| lint.get_name()).find(|name| name.eq(&lint_name));
+        if name_res.is_some() {
+            if is_enabled {
+                self.disabled_lints.remove(name_res.unwrap());
+            } else {
+                self.disabled_lints.insert(name_res.unwrap());
+            }
+        }
+    }
+
+    // Function to run the enabled lints and add their warnings to the result
+    pub fn run(&self, semantic_analysis_res: &mut SemanticAnalysisResult, target_config: &TargetConfig) {
+        for lint in self.lints.iter() {
+            if self.disabled_lints.contains(lint.get_name()) {
+                continue;
+            }
+
+            for (position, message) in lint.check(semantic_analysis_res, target_config) {
+                nexus_log::log_message(nexus_log::LogTypes::Warning, LOG_SOURCE, position, &message);
+                semantic_analysis_res.num_warnings += 1;
+                semantic_analysis_res.lint_warnings.push(LintWarning {
+                    lint: lint.get_name(),
+                    code: message.code,
+                    position: position,
+                    message: message.get_text()
+                });
+            }
+        }
+    }
+}
+
+// Function to go through every node in the AST in source order
+fn visit_nodes(ast: &SyntaxTree, visit_fn: &mut dyn FnMut(NodeIndex, &SyntaxTreeNode, &Vec<NodeIndex>)) {
+    if ast.root.is_some() {
+        visit_nodes_dfs(ast, NodeIndex::new(ast.root.unwrap()), visit_fn);
+    }
+}
+
+fn visit_nodes_dfs(ast: &SyntaxTree, cur_index: NodeIndex, visit_fn: &mut dyn FnMut(NodeIndex, &SyntaxTreeNode, &Vec<NodeIndex>)) {
+    let children: Vec<NodeIndex> = (*ast).graph.neighbors(cur_index).collect();
+    visit_fn(cur_index, (*ast).graph.node_weight(cur_index).unwrap(), &children);
+
+    // Children are stored in reverse order
+    for child_index in children.iter().rev() {
+        visit_nodes_dfs(ast, *child_index, visit_fn);
+    }
+}
+
+// Function to get the position of the first token in the subtree, which is used to report the warning
+fn get_position(ast: &SyntaxTree, cur_index: NodeIndex) -> Option<Position> {
+    return match (*ast).graph.node_weight(cur_index).unwrap() {
+        SyntaxTreeNode::Terminal(token) => Some(token.position),
+        _ => (*ast).graph.neighbors(cur_index).filter_map(|child_index| get_position(ast, child_index)).min()
+    };
+}
+
+// Function to get the value of a literal as text so two literals can be compared
+fn get_literal_value(ast: &SyntaxTree, node_index: NodeIndex) -> Option<String> {
+    return match (*ast).graph.node_weight(node_index).unwrap() {
+        SyntaxTreeNode::Terminal(token) => {
+            match &token.token_type {
+                TokenType::Digit(num) => Some(num.to_string()),
+                TokenType::Char(string) => Some(format!("\"{}\"", string)),
+                TokenType::CharLiteral(_) => Some(token.text.to_owned()),
+                TokenType::Keyword(Keywords::True) => Some(String::from("true")),
+                TokenType::Keyword(Keywords::False) => Some(String::from("false")),
+                _ => None
+            }
+        },
+        _ => None
+    };
+}
+
+// Warns about blocks without any statements
+struct EmptyBlockLint;
+
+impl Lint for EmptyBlockLint {
+    fn get_name(&self) -> &'static str {
+        return "empty-block";
+    }
+
+    fn check(&self, semantic_analysis_res: &SemanticAnalysisResult, _target_config: &TargetConfig) -> Vec<(Position, Message)> {
+        let ast: &SyntaxTree = &semantic_analysis_res.ast;
+        let mut warnings: Vec<(Position, Message)> = Vec::new();
+
+        visit_nodes(ast, &mut |cur_index, node, children| {
+            if let SyntaxTreeNode::NonTerminalAst(NonTerminalsAst::Block) = node {
+                if children.len() == 0 {
+                    // Empty blocks have no tokens, so use the statement the block belongs to
+                    let parent_index: Option<NodeIndex> = (*ast).graph.neighbors_directed(cur_index, petgraph::Direction::Incoming).next();
+                    let position: Position = parent_index.and_then(|parent| get_position(ast, parent)).unwrap_or(Position::new(1, 1));
+                    warnings.push((position, Message::new(MessageCode::EmptyBlockLint, vec![("position", position.to_message_param())])));
+                }
+            }
+        });
+
+        return warnings;
+    }
+}
+
+// Warns about if and while conditions that always have the same value
+struct ConstantConditionLint;
+
+impl Lint for ConstantConditionLint {
+    fn get_name(&self) -> &'static str {
+        return "constant-condition";
+    }
+
+    fn check(&self, semantic_analysis_res: &SemanticAnalysisResult, _target_config: &TargetConfig) -> Vec<(Position, Message)> {
+        let ast: &SyntaxTree = &semantic_analysis_res.ast;
+        let mut warnings: Vec<(Position, Message)> = Vec::new();
+
+        visit_nodes(ast, &mut |_cur_index, node, children| {
+            let statement: &str = match node {
+                SyntaxTreeNode::NonTerminalAst(NonTerminalsAst::If) => "if",
+                SyntaxTreeNode::NonTerminalAst(NonTerminalsAst::While) => "while",
+                _ => return
+            };
+
+            // The condition is the first child
+            let condition_index: NodeIndex = children[1];
+            let value_res: Option<bool> = match (*ast).graph.node_weight(condition_index).unwrap() {
+                SyntaxTreeNode::NonTerminalAst(comparison @ (NonTerminalsAst::IsEq | NonTerminalsAst::NotEq)) => {
+                    let operands: Vec<NodeIndex> = (*ast).graph.neighbors(condition_index).collect();
+                    match (get_literal_value(ast, operands[1]), get_literal_value(ast, operands[0])) {
+                        (Some(left), Some(right)) => {
+                            match comparison {
+                                NonTerminalsAst::IsEq => Some(left.eq(&right)),
+                                _ => Some(left.ne(&right))
+                            }
+                        },
+                        _ => None
+                    }
+                },
+                _ => get_literal_value(ast, condition_index).map(|value| value.eq("true"))
+            };
+
+            if value_res.is_some() {
+                let position: Position = get_position(ast, condition_index).unwrap();
+                warnings.push((position, Message::new(MessageCode::ConstantCondition, vec![
+                    ("position", position.to_message_param()),
+                    ("statement", String::from(statement)),
+                    ("value", value_res.unwrap().to_string())
+                ])));
+            }
+        });
+
+        return warnings;
+    }
+}
+
+// Warns about assigning a variable to itself, including through additions of 0 like a = 0 + a
+// The code generators remove these statements, so the warning also explains the missing code
+struct SelfAssignmentLint;
+
+impl Lint for SelfAssignmentLint {
+    fn get_name(&self) -> &'static str {
+        return "self-assignment";
+    }
+
+    fn check(&self, semantic_analysis_res: &SemanticAnalysisResult, _target_config: &TargetConfig) -> Vec<(Position, Message)> {
+        let ast: &SyntaxTree = &semantic_analysis_res.ast;
+        let mut warnings: Vec<(Position, Message)> = Vec::new();
+
+        visit_nodes(ast, &mut |cur_index, _node, children| {
+            if optimizer::is_no_op_assignment(ast, cur_index) {
+                // Children are [value, id]
+                if let SyntaxTreeNode::Terminal(id_token) = (*ast).graph.node_weight(children[1]).unwrap() {
+                    let code: MessageCode = match optimizer::is_zero_addition(ast, children[0]) {
+                        true => MessageCode::ZeroAddition,
+                        false => MessageCode::SelfAssignment
+                    };
+                    warnings.push((id_token.position, Message::new(code, vec![("position", id_token.position.to_message_param()), ("id", id_token.text.to_owned())])));
+                }
+            }
+        });
+
+        return warnings;
+    }
+}
+
+// Warns about comparing a variable with itself, which always has the same result
+// The 6502 code generator replaces these comparisons with their value when optimizing for size
+struct SelfComparisonLint;
+
+impl Lint for SelfComparisonLint {
+    fn get_name(&self) -> &'static str {
+        return "self-comparison";
+    }
+
+    fn check(&self, semantic_analysis_res: &SemanticAnalysisResult, _target_config: &TargetConfig) -> Vec<(Position, Message)> {
+        let ast: &SyntaxTree = &semantic_analysis_res.ast;
+        let mut warnings: Vec<(Position, Message)> = Vec::new();
+
+        visit_nodes(ast, &mut |cur_index, _node, children| {
+            if let Some(value) = optimizer::get_self_comparison_value(ast, cur_index) {
+                // Children are [right, left]
+                if let SyntaxTreeNode::Terminal(id_token) = (*ast).graph.node_weight(children[1]).unwrap() {
+                    warnings.push((id_token.position, Message::new(MessageCode::SelfComparison, vec![
+                        ("position", id_token.position.to_message_param()),
+                        ("id", id_token.text.to_owned()),
+                        ("value", value.to_string())
+                    ])));
+                }
+            }
+        });
+
+        return warnings;
+    }
+}
+
+// Warns about declarations that hide a variable from an outer scope
+struct ShadowingLint;
+
+impl ShadowingLint {
+    fn check_block(&self, ast: &SyntaxTree, block_index: NodeIndex, outer_ids: &mut Vec<IndexMap<String, Position>>, warnings: &mut Vec<(Position, Message)>) {
+        outer_ids.push(IndexMap::new());
+
+        let statements: Vec<NodeIndex> = (*ast).graph.neighbors(block_index).collect();
+        for statement_index in statements.into_iter().rev() {
+            match (*ast).graph.node_weight(statement_index).unwrap() {
+                SyntaxTreeNode::NonTerminalAst(NonTerminalsAst::VarDecl) => {
+                    let children: Vec<NodeIndex> = (*ast).graph.neighbors(statement_index).collect();
+                    if let SyntaxTreeNode::Terminal(id_token) = (*ast).graph.node_weight(children[0]).unwrap() {
+                        // Only the outer scopes matter since redeclaring in the same scope is an error
+                        let shadowed_res: Option<&Position> = outer_ids[..outer_ids.len() - 1].iter().rev().find_map(|scope_ids| scope_ids.get(&id_token.text));
+                        if shadowed_res.is_some() {
+                            warnings.push((id_token.position, Message::new(MessageCode::Shadowing, vec![
+                                ("position", id_token.position.to_message_param()),
+                                ("id", id_token.text.to_owned()),
+                                ("declared_position", shadowed_res.unwrap().to_message_param())
+                            ])));
+                        }
+                        outer_ids.last_mut().unwrap().insert(id_token.text.to_owned(), id_token.position);
+                    }
+                },
+                SyntaxTreeNode::NonTerminalAst(NonTerminalsAst::Block) => self.check_block(ast, statement_index, outer_ids, warnings),
+                SyntaxTreeNode::NonTerminalAst(NonTerminalsAst::If | NonTerminalsAst::While) => {
+                    // The body is stored first
+                    let body_index: NodeIndex = (*ast).graph.neighbors(statement_index).next().unwrap();
+                    self.check_block(ast, body_index, outer_ids, warnings);
+                },
+                _ => {}
+            }
+        }
+
+        outer_ids.pop();
+    }
+}
+
+impl Lint for ShadowingLint {
+    fn get_name(&self) -> &'static str {
+        return "shadowing";
+    }
+
+    fn check(&self, semantic_analysis_res: &SemanticAnalysisResult, _target_config: &TargetConfig) -> Vec<(Position, Message)> {
+        let ast: &SyntaxTree = &semantic_analysis_res.ast;
+        let mut warnings: Vec<(Position, Message)> = Vec::new();
+        if ast.root.is_some() {
+            self.check_block(ast, NodeIndex::new(ast.root.unwrap()), &mut Vec::new(), &mut warnings);
+        }
+        return warnings;
+    }
+}
+
+// Warns when the string literals take up a large part of the 6502 memory
+struct StringHeapLint;
+
+impl StringHeapLint {
+    // The strings can use up to this fraction of the free memory before warning
+    const MAX_HEAP_FRACTION: usize = 4;
+}
+
+impl Lint for StringHeapLint {
+    fn get_name(&self) -> &'static str {
+        return "string-heap";
+    }
+
+    fn check(&self, semantic_analysis_res: &SemanticAnalysisResult, target_config: &TargetConfig) -> Vec<(Position, Message)> {
+        // The RISC-V assembler lays out memory, so there is no fixed limit
+        if target_config.target != Target::Target6502 {
+            return Vec::new();
+        }
+
+        // Identical strings are only stored once, and each one ends with a 0x00
+        let ast: &SyntaxTree = &semantic_analysis_res.ast;
+        let mut strings: IndexMap<String, Position> = IndexMap::new();
+        visit_nodes(ast, &mut |_cur_index, node, _children| {
+            if let SyntaxTreeNode::Terminal(token) = node {
+                if let TokenType::Char(string) = &token.token_type {
+                    strings.entry(string.to_owned()).or_insert(token.position);
+                }
+            }
+        });
+        let string_bytes: usize = strings.keys().map(|string| string.len() + 1).sum();
+
+        let available_bytes: usize = match target_config.heap_split {
+            Some(split) => 0x100 - target_config.reserved_cells - split,
+            None => 0x100 - target_config.reserved_cells
+        };
+
+        if string_bytes * Self::MAX_HEAP_FRACTION > available_bytes {
+            let position: Position = *strings.values().next().unwrap();
+            return vec![(position, Message::new(MessageCode::StringHeap, vec![
+                ("position", position.to_message_param()),
+                ("num_strings", strings.len().to_string()),
+                ("num_bytes", string_bytes.to_string()),
+                ("available_bytes", available_bytes.to_string())
+            ]))];
+        } else {
+            return Vec::new();
+        }
+    }
+}
@@ -0,0 +1,80 @@
+// The areas of the 6502 memory from the front to the back
+#[derive (Debug, Clone, Copy, PartialEq, strum::Display)]
+pub enum MemoryRegion {
+    #[strum (serialize = "code")]
+    Code,
+    #[strum (serialize = "static data")]
+    Static,
+    #[strum (serialize = "temp data")]
+    Temp,
+    #[strum (serialize = "heap")]
+    Heap
+}
+
+// The size of each region of the 6502 memory
+//  |  Code  |  Static  ||  Temp  |  Heap  | Reserved
+// All math is done with usize so nothing can wrap around like it would with u8 addresses
+#[derive (Debug, Clone)]
+pub struct MemoryLayout {
+    pub code_size: usize,
+    pub static_size: usize,
+    pub temp_size: usize,
+    pub heap_size: usize,
+
+    // One past the last address the heap can use, which is where the reserved cells start
+    pub heap_end: usize,
+
+    // The lowest address the temp data and heap can use, or None if they share the space with the code
+    pub heap_split: Option<usize>
+}
+
+impl MemoryLayout {
+    // The first address after the static data
+    pub fn get_static_end(&self) -> usize {
+        return self.code_size + self.static_size;
+    }
+
+    // The lowest address of the strings on the heap
+    // Can go below 0 if the regions do not fit, so it is None then
+    pub fn get_heap_start(&self) -> Option<usize> {
+        return self.heap_end.checked_sub(self.heap_size);
+    }
+
+    // Function to make the region bigger by the number of bytes
+    pub fn grow(&mut self, region: MemoryRegion, num_bytes: usize) {
+        match region {
+            MemoryRegion::Code => self.code_size += num_bytes,
+            MemoryRegion::Static => self.static_size += num_bytes,
+            MemoryRegion::Temp => self.temp_size += num_bytes,
+            MemoryRegion::Heap => self.heap_size += num_bytes
+        }
+    }
+
+    // Function to check if the regions fit in memory without overlapping
+    // Returns a description of the overflow naming the region that grew, or None if everything fits
+    pub fn find_overflow(&self, grown_region: MemoryRegion) -> Option<String> {
+        let front_size: usize = self.get_static_end();
+        let back_size: usize = self.temp_size + self.heap_size;
+
+        match self.heap_split {
+            None => {
+                if front_size + back_size > self.heap_end {
+                    return Some(format!("The {} overflowed memory: code ({}) + static data ({}) + temp data ({}) + heap ({}) is {} bytes, but only {} bytes are available",
+                                        grown_region, self.code_size, self.static_size, self.temp_size, self.heap_size, front_size + back_size, self.heap_end));
+                }
+            },
+            Some(split) => {
+                if front_size > split {
+                    return Some(format!("The {} overflowed past the heap start at 0x{:02X}: code ({}) + static data ({}) is {} bytes, but only {} bytes are available",
+                                        grown_region, split, self.code_size, self.static_size, front_size, split));
+                }
+                if back_size > self.heap_end.saturating_sub(split) {
+                    return Some(format!("The {} overflowed below the heap start at 0x{:02X}: temp data ({}) + heap ({}) is {} bytes, but only {} bytes are available",
+                                        grown_region, split, self.temp_size, self.heap_size, back_size, self.heap_end.saturating_sub(split)));
+                }
+            }
+        }
+
+        return None;
+    }
+}
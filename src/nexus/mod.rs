@@ -1,10 +1,45 @@
+#[cfg(feature = "ui")]
 pub mod compiler;
+#[cfg(feature = "ui")]
+pub mod artifacts;
+pub mod preprocessor;
 pub mod lexer;
 pub mod token;
+pub mod grammar;
 pub mod parser;
+pub mod balance;
+pub mod program_cache;
+pub mod pipeline;
 pub mod semantic_analyzer;
 pub mod symbol_table;
+pub mod operand;
+pub mod semantic_tokens;
+pub mod lint;
 pub mod syntax_tree;
 pub mod syntax_tree_node;
+pub mod memory_layout;
 pub mod code_generator_6502;
 pub mod code_generator_riscv;
+pub mod riscv_validator;
+pub mod riscv_instruction;
+pub mod interpreter;
+pub mod emulator_6502;
+pub mod disassembler_6502;
+pub mod assembler_6502;
+pub mod emulator_riscv;
+pub mod optimizer;
+pub mod cost_model;
+pub mod coverage;
+pub mod source_map;
+#[cfg(feature = "ui")]
+pub mod statistics;
+#[cfg(feature = "ui")]
+pub mod progress;
+#[cfg(feature = "ui")]
+pub mod phase_api;
+pub mod batch;
+pub mod fingerprint;
+pub mod grading;
+#[cfg(feature = "ui")]
+pub mod ast_query;
+pub mod differential;
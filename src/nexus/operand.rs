@@ -0,0 +1,45 @@
+use log::*;
+use petgraph::graph::NodeIndex;
+
+use crate::nexus::{syntax_tree::SyntaxTree, syntax_tree_node::{SyntaxTreeNode, NonTerminalsAst}, symbol_table::{SymbolTable, SymbolTableEntry, Type}, token::{Token, TokenType, Keywords}};
+
+// Function to get the type of a terminal operand, which both code generators use to pick how it is loaded and printed
+// Ids use the type from their declaration in the symbol table, so the symbol table has to be in the scope of the token
+pub fn get_operand_type(token: &Token, symbol_table: &mut SymbolTable) -> Option<Type> {
+    return match &token.token_type {
+        TokenType::Identifier(id_name) => {
+            let id_entry: Option<&SymbolTableEntry> = symbol_table.get_symbol_with_context(id_name, token.position);
+            id_entry.map(|id_entry| id_entry.symbol_type.to_owned())
+        },
+        TokenType::Digit(_) => Some(Type::Int),
+        TokenType::Char(_) => Some(Type::String),
+        TokenType::CharLiteral(_) => Some(Type::Char),
+        TokenType::Keyword(Keywords::True) | TokenType::Keyword(Keywords::False) => Some(Type::Boolean),
+        _ => {
+            error!("Received {:?} when expecting an id, digit, string, boolean, or char operand", token);
+            None
+        }
+    };
+}
+
+// Function to get the terminal and length operand nodes of an addition expression from left to right
+// Grouped additions can be on either side, so this goes through the whole tree under the addition
+pub fn get_add_operands(ast: &SyntaxTree, node_index: NodeIndex) -> Vec<NodeIndex> {
+    let mut operands: Vec<NodeIndex> = Vec::new();
+    collect_add_operands(ast, node_index, &mut operands);
+    return operands;
+}
+
+fn collect_add_operands(ast: &SyntaxTree, node_index: NodeIndex, operands: &mut Vec<NodeIndex>) {
+    match (*ast).graph.node_weight(node_index).unwrap() {
+        SyntaxTreeNode::NonTerminalAst(NonTerminalsAst::Add) => {
+            // Index 1 is the left side and index 0 is the right side
+            let children: Vec<NodeIndex> = (*ast).graph.neighbors(node_index).collect();
+            collect_add_operands(ast, children[1], operands);
+            collect_add_operands(ast, children[0], operands);
+        },
+        // Lengths are the only other int expression
+        SyntaxTreeNode::Terminal(_) | SyntaxTreeNode::NonTerminalAst(NonTerminalsAst::Len) => operands.push(node_index),
+        node => error!("Received {:?} when expecting an addition, a length, or a terminal operand", node)
+    }
+}
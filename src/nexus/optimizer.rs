@@ -0,0 +1,258 @@
+use std::collections::HashSet;
+
+use crate::nexus::{syntax_tree::SyntaxTree, syntax_tree_node::*};
+use crate::nexus::token::{TokenType, Keywords};
+use crate::util::nexus_log;
+
+use petgraph::graph::NodeIndex;
+
+// The optimizations are logged separately from the rest of code generation
+pub const LOG_SOURCE: nexus_log::LogSource = nexus_log::LogSource::new("Optimizer", "Optimizer");
+
+// Function to find the parts of a while condition that do not change while the loop runs,
+// which can be evaluated once before the loop instead of on every iteration
+pub fn find_loop_invariants(ast: &SyntaxTree, while_index: NodeIndex) -> Vec<NodeIndex> {
+    let children: Vec<NodeIndex> = (*ast).graph.neighbors(while_index).collect();
+
+    // Anything assigned in the body may be different on the next iteration
+    let mut assigned_ids: HashSet<String> = HashSet::new();
+    get_assigned_ids(ast, children[0], &mut assigned_ids);
+
+    let mut invariants: Vec<NodeIndex> = Vec::new();
+    match (*ast).graph.node_weight(children[1]).unwrap() {
+        // The comparison itself sets the branch condition, so only its operands can be hoisted
+        SyntaxTreeNode::NonTerminalAst(NonTerminalsAst::IsEq) | SyntaxTreeNode::NonTerminalAst(NonTerminalsAst::NotEq) => {
+            find_invariant_operands(ast, children[1], &assigned_ids, &mut invariants);
+        },
+        _ => {}
+    }
+
+    for invariant in invariants.iter() {
+        nexus_log::log(
+            nexus_log::LogTypes::Info,
+            LOG_SOURCE,
+            format!("Hoisted loop-invariant expression [ {} ] out of the while loop condition", get_expr_text(ast, *invariant))
+        );
+    }
+
+    return invariants;
+}
+
+// Function to collect every identifier that is assigned a value within the subtree
+fn get_assigned_ids(ast: &SyntaxTree, cur_index: NodeIndex, assigned_ids: &mut HashSet<String>) {
+    match (*ast).graph.node_weight(cur_index).unwrap() {
+        SyntaxTreeNode::NonTerminalAst(NonTerminalsAst::Assign) => {
+            let children: Vec<NodeIndex> = (*ast).graph.neighbors(cur_index).collect();
+            match (*ast).graph.node_weight(children[1]).unwrap() {
+                SyntaxTreeNode::Terminal(token) => { assigned_ids.insert(token.text.to_owned()); },
+                _ => {}
+            }
+        },
+        SyntaxTreeNode::NonTerminalAst(_) => {
+            for child_index in (*ast).graph.neighbors(cur_index) {
+                get_assigned_ids(ast, child_index, assigned_ids);
+            }
+        },
+        _ => {}
+    }
+}
+
+// Function to find the largest invariant expressions that are operands of the comparison
+fn find_invariant_operands(ast: &SyntaxTree, compare_index: NodeIndex, assigned_ids: &HashSet<String>, invariants: &mut Vec<NodeIndex>) {
+    // Left side is the second child, so go in reverse for source order
+    let children: Vec<NodeIndex> = (*ast).graph.neighbors(compare_index).collect();
+    for operand_index in children.into_iter().rev() {
+        match (*ast).graph.node_weight(operand_index).unwrap() {
+            // Terminals are already a single load, so there is nothing to gain
+            SyntaxTreeNode::Terminal(_) => {},
+            SyntaxTreeNode::NonTerminalAst(non_terminal) => {
+                if is_invariant(ast, operand_index, assigned_ids) {
+                    invariants.push(operand_index);
+                } else {
+                    match non_terminal {
+                        // Nested comparisons may still have invariant operands
+                        NonTerminalsAst::IsEq | NonTerminalsAst::NotEq => find_invariant_operands(ast, operand_index, assigned_ids, invariants),
+                        _ => {}
+                    }
+                }
+            },
+            _ => {}
+        }
+    }
+}
+
+// Function to check if none of the identifiers in the expression are assigned in the loop
+fn is_invariant(ast: &SyntaxTree, cur_index: NodeIndex, assigned_ids: &HashSet<String>) -> bool {
+    match (*ast).graph.node_weight(cur_index).unwrap() {
+        SyntaxTreeNode::Terminal(token) => {
+            return match &token.token_type {
+                TokenType::Identifier(_) => !assigned_ids.contains(&token.text),
+                _ => true
+            };
+        },
+        _ => {
+            return (*ast).graph.neighbors(cur_index).all(|child_index| is_invariant(ast, child_index, assigned_ids));
+        }
+    }
+}
+
+// Function to rebuild the source text of an expression for the log
+fn get_expr_text(ast: &SyntaxTree, cur_index: NodeIndex) -> String {
+    let children: Vec<NodeIndex> = (*ast).graph.neighbors(cur_index).collect();
+    return match (*ast).graph.node_weight(cur_index).unwrap() {
+        SyntaxTreeNode::Terminal(token) => {
+            match &token.token_type {
+                TokenType::Char(string) => format!("\"{}\"", string),
+                _ => token.text.to_owned()
+            }
+        },
+        SyntaxTreeNode::NonTerminalAst(NonTerminalsAst::Add) => {
+            // Additions are left associative, so one on the right side was grouped in the source code
+            match (*ast).graph.node_weight(children[0]).unwrap() {
+                SyntaxTreeNode::NonTerminalAst(NonTerminalsAst::Add) => format!("{} + ({})", get_expr_text(ast, children[1]), get_expr_text(ast, children[0])),
+                _ => format!("{} + {}", get_expr_text(ast, children[1]), get_expr_text(ast, children[0]))
+            }
+        },
+        SyntaxTreeNode::NonTerminalAst(NonTerminalsAst::IsEq) => format!("({} == {})", get_expr_text(ast, children[1]), get_expr_text(ast, children[0])),
+        SyntaxTreeNode::NonTerminalAst(NonTerminalsAst::NotEq) => format!("({} != {})", get_expr_text(ast, children[1]), get_expr_text(ast, children[0])),
+        SyntaxTreeNode::NonTerminalAst(NonTerminalsAst::Index) => format!("{}[{}]", get_expr_text(ast, children[1]), get_expr_text(ast, children[0])),
+        SyntaxTreeNode::NonTerminalAst(NonTerminalsAst::Len) => format!("len({})", get_expr_text(ast, children[0])),
+        other => format!("{:?}", other)
+    };
+}
+
+// Function to get the value of a comparison of a variable with itself, like (a == a), which is known without running the code
+pub fn get_self_comparison_value(ast: &SyntaxTree, cur_index: NodeIndex) -> Option<bool> {
+    let value: bool = match (*ast).graph.node_weight(cur_index).unwrap() {
+        SyntaxTreeNode::NonTerminalAst(NonTerminalsAst::IsEq) => true,
+        SyntaxTreeNode::NonTerminalAst(NonTerminalsAst::NotEq) => false,
+        _ => return None
+    };
+
+    let children: Vec<NodeIndex> = (*ast).graph.neighbors(cur_index).collect();
+    return match ((*ast).graph.node_weight(children[1]).unwrap(), (*ast).graph.node_weight(children[0]).unwrap()) {
+        (SyntaxTreeNode::Terminal(left_token), SyntaxTreeNode::Terminal(right_token)) => {
+            match (&left_token.token_type, &right_token.token_type) {
+                (TokenType::Identifier(_), TokenType::Identifier(_)) if left_token.text.eq(&right_token.text) => Some(value),
+                _ => None
+            }
+        },
+        _ => None
+    };
+}
+
+// Function to get the value a comparison can be replaced with, which is only known when a variable is compared with itself
+pub fn fold_self_comparison(ast: &SyntaxTree, cur_index: NodeIndex) -> Option<bool> {
+    let value_res: Option<bool> = get_self_comparison_value(ast, cur_index);
+    if value_res.is_some() {
+        nexus_log::log(
+            nexus_log::LogTypes::Info,
+            LOG_SOURCE,
+            format!("Folded comparison [ {} ] to {} because it compares a variable with itself", get_expr_text(ast, cur_index), value_res.unwrap())
+        );
+    }
+    return value_res;
+}
+
+// Function to find the assignments that do not change the value of the variable, like a = a or a = 0 + a,
+// and the if and while statements with empty bodies that can be removed without generating any code
+pub fn find_no_op_statements(ast: &SyntaxTree) -> HashSet<NodeIndex> {
+    let mut no_op_statements: HashSet<NodeIndex> = HashSet::new();
+    for node_index in (*ast).graph.node_indices() {
+        if is_no_op_assignment(ast, node_index) {
+            nexus_log::log(
+                nexus_log::LogTypes::Info,
+                LOG_SOURCE,
+                format!("Removed assignment [ {} = {} ] because it has no effect", get_expr_text(ast, (*ast).graph.neighbors(node_index).nth(1).unwrap()), get_expr_text(ast, (*ast).graph.neighbors(node_index).next().unwrap()))
+            );
+            no_op_statements.insert(node_index);
+        } else if is_no_op_conditional(ast, node_index) {
+            let statement: &str = match (*ast).graph.node_weight(node_index).unwrap() {
+                SyntaxTreeNode::NonTerminalAst(NonTerminalsAst::While) => "while",
+                _ => "if"
+            };
+            nexus_log::log(
+                nexus_log::LogTypes::Info,
+                LOG_SOURCE,
+                format!("Removed {} statement with condition [ {} ] because its body is empty", statement, get_expr_text(ast, (*ast).graph.neighbors(node_index).nth(1).unwrap()))
+            );
+            no_op_statements.insert(node_index);
+        }
+    }
+    return no_op_statements;
+}
+
+// Function to check if the node is an if or while statement with an empty body that can be removed
+// Conditions do not have side effects, so an empty if does nothing, but an empty while only does nothing
+// when its condition is known to be false because otherwise it never ends
+pub fn is_no_op_conditional(ast: &SyntaxTree, cur_index: NodeIndex) -> bool {
+    let is_while: bool = match (*ast).graph.node_weight(cur_index).unwrap() {
+        SyntaxTreeNode::NonTerminalAst(NonTerminalsAst::If) => false,
+        SyntaxTreeNode::NonTerminalAst(NonTerminalsAst::While) => true,
+        _ => return false
+    };
+
+    // Children are [body, condition]
+    let children: Vec<NodeIndex> = (*ast).graph.neighbors(cur_index).collect();
+    if (*ast).graph.neighbors(children[0]).next().is_some() {
+        return false;
+    }
+    if !is_while {
+        return true;
+    }
+    return match (*ast).graph.node_weight(children[1]).unwrap() {
+        SyntaxTreeNode::Terminal(token) => token.token_type.eq(&TokenType::Keyword(Keywords::False)),
+        _ => get_self_comparison_value(ast, children[1]) == Some(false)
+    };
+}
+
+// Function to check if the node is an assignment of a variable to itself plus any number of zeros
+pub fn is_no_op_assignment(ast: &SyntaxTree, cur_index: NodeIndex) -> bool {
+    match (*ast).graph.node_weight(cur_index).unwrap() {
+        SyntaxTreeNode::NonTerminalAst(NonTerminalsAst::Assign) => {},
+        _ => return false
+    }
+
+    // Children are [value, id]
+    let children: Vec<NodeIndex> = (*ast).graph.neighbors(cur_index).collect();
+    let value_index: NodeIndex = strip_zero_additions(ast, children[0]);
+    return match ((*ast).graph.node_weight(children[1]).unwrap(), (*ast).graph.node_weight(value_index).unwrap()) {
+        (SyntaxTreeNode::Terminal(id_token), SyntaxTreeNode::Terminal(value_token)) => {
+            match &value_token.token_type {
+                TokenType::Identifier(_) => id_token.text.eq(&value_token.text),
+                _ => false
+            }
+        },
+        _ => false
+    };
+}
+
+// Function to skip over additions with 0 on the left, so 0 + 0 + a is the same as a
+pub fn strip_zero_additions(ast: &SyntaxTree, cur_index: NodeIndex) -> NodeIndex {
+    if is_zero_addition(ast, cur_index) {
+        // The left side is the second child
+        let right_index: NodeIndex = (*ast).graph.neighbors(cur_index).next().unwrap();
+        return strip_zero_additions(ast, right_index);
+    } else {
+        return cur_index;
+    }
+}
+
+// Function to check if the node is 0 + expr, where the left side can also be a sum of zeros like (0 + 0) + expr
+pub fn is_zero_addition(ast: &SyntaxTree, cur_index: NodeIndex) -> bool {
+    match (*ast).graph.node_weight(cur_index).unwrap() {
+        SyntaxTreeNode::NonTerminalAst(NonTerminalsAst::Add) => {},
+        _ => return false
+    }
+
+    let children: Vec<NodeIndex> = (*ast).graph.neighbors(cur_index).collect();
+    return is_zero_value(ast, children[1]);
+}
+
+fn is_zero_value(ast: &SyntaxTree, cur_index: NodeIndex) -> bool {
+    return match (*ast).graph.node_weight(cur_index).unwrap() {
+        SyntaxTreeNode::Terminal(token) => token.token_type.eq(&TokenType::Digit(0)),
+        SyntaxTreeNode::NonTerminalAst(NonTerminalsAst::Add) => (*ast).graph.neighbors(cur_index).all(|child_index| is_zero_value(ast, child_index)),
+        _ => false
+    };
+}
@@ -1,11 +1,25 @@
-use crate::{nexus::token::{Token, TokenType, Symbols, Keywords}, util::nexus_log};
+use crate::{nexus::token::{Token, TokenType, Symbols, Keywords}, util::{nexus_log, messages::{Message, MessageCode}, diagnostic::QuickFix, language_profile::{LanguageProfile, LanguageFeature}, position::{Position, Span}}};
 
+use crate::nexus::balance;
 use crate::nexus::syntax_tree::{SyntaxTree, SyntaxTreeTypes};
 use crate::nexus::syntax_tree_node::{SyntaxTreeNode, NonTerminalsCst, SyntaxTreeNodeTypes};
+use crate::nexus::grammar::FirstSets;
+
+use regex::Regex;
 
 pub struct Parser {
     cur_token_index: usize,
-    num_warnings: i32
+    num_warnings: i32,
+    pub num_errors: i32, // The number of errors in the most recently parsed program
+
+    // Used for the lists of valid tokens in the error messages so they always match the grammar
+    first_sets: FirstSets,
+
+    // What identifiers can be and which grammar extensions can be used, which is checked for tokens that did not come from the lexer
+    language_profile: LanguageProfile,
+
+    // The regex for identifiers, which is only built when the language profile changes
+    identifiers: Regex
 }
 
 impl Parser {
@@ -13,48 +27,75 @@ impl Parser {
     pub fn new() -> Self {
         return Parser {
             cur_token_index: 0,
-            num_warnings: 0
+            num_warnings: 0,
+            num_errors: 0,
+            first_sets: FirstSets::new(),
+            language_profile: LanguageProfile::new(),
+            identifiers: LanguageProfile::new().get_identifier_regex()
         };
     }
+
+    // Function to only accept the identifiers that the language profile allows
+    pub fn set_language_profile(&mut self, language_profile: &LanguageProfile) {
+        self.identifiers = language_profile.get_identifier_regex();
+        self.language_profile = language_profile.to_owned();
+    }
+
     // Calls for a program to be parsed
     pub fn parse_program(&mut self, token_stream: &Vec<Token>) -> Result<SyntaxTree, ()> {
         // Log that we are parsing the program
         nexus_log::log(
             nexus_log::LogTypes::Debug,
-            nexus_log::LogSources::Parser,
+            nexus_log::LogSource::PARSER,
             String::from("Parsing Program")
         );
 
+        // Unbalanced symbols are pointed out first, since the parser may not notice until much later in the program
+        for (position, hint) in balance::find_unbalanced(token_stream).iter() {
+            nexus_log::log_message(
+                nexus_log::LogTypes::Info,
+                nexus_log::LogSource::PARSER,
+                *position,
+                hint
+            );
+        }
+
         // Reset the index to be 0 and clear the CST
         self.cur_token_index = 0;
         let mut cst: SyntaxTree = SyntaxTree::new(SyntaxTreeTypes::Cst);
 
         let mut success: bool = true;
         self.num_warnings = 0;
+        self.num_errors = 0;
 
         // Add the program node
         cst.add_node(SyntaxTreeNodeTypes::Root, SyntaxTreeNode::NonTerminalCst(NonTerminalsCst::Program));
 
         // A program consists of a block followed by an EOP marker
         // First will check block and then the token
-        let program_block_res: Result<(), String> = self.parse_block(token_stream, &mut cst);
-        if program_block_res.is_ok() {
-            let eop_res: Result<(), String> = self.match_token(token_stream, TokenType::Symbol(Symbols::EOP), &mut cst);
+        let program_block_res: Result<(), Message> = self.parse_block(token_stream, &mut cst, None);
+        if program_block_res.is_ok() && self.cur_token_index >= token_stream.len() {
+            // The lexer already warned about the missing $, so put it right after the block and keep going
+            let eop_position: Position = token_stream[token_stream.len() - 1].get_span().end;
+            let eop_token: Token = Token::new(TokenType::Symbol(Symbols::EOP), String::from("$"), eop_position.line, eop_position.col);
+            nexus_log::log(
+                nexus_log::LogTypes::Debug,
+                nexus_log::LogSource::PARSER,
+                format!("Inserted missing EOP [ $ ] at {}", eop_token.position)
+            );
+            cst.add_node(SyntaxTreeNodeTypes::Leaf, SyntaxTreeNode::Terminal(eop_token));
+        } else if program_block_res.is_ok() {
+            let eop_res: Result<(), Message> = self.match_token(token_stream, TokenType::Symbol(Symbols::EOP), &mut cst);
             if eop_res.is_err() {
-                success = false;
-                nexus_log::log(
-                    nexus_log::LogTypes::Error,
-                    nexus_log::LogSources::Parser,
-                    eop_res.unwrap_err()
-                );
+                self.report_error(token_stream, eop_res.unwrap_err());
             }
         } else {
+            self.report_error(token_stream, program_block_res.unwrap_err());
+        }
+
+        // Errors that could be recovered from were already reported, but the program still cannot be used
+        if self.num_errors > 0 {
             success = false;
-            nexus_log::log(
-                nexus_log::LogTypes::Error,
-                nexus_log::LogSources::Parser,
-                program_block_res.unwrap_err()
-            );
         }
 
         let mut warnings_str: String = format!("{} warning", self.num_warnings);
@@ -66,15 +107,15 @@ impl Parser {
             // Log that we are parsing the program
             nexus_log::log(
                 nexus_log::LogTypes::Error,
-                nexus_log::LogSources::Parser,
-                format!("Parser failed and had {}", warnings_str)
+                nexus_log::LogSource::PARSER,
+                format!("Parser failed with {} error{} and {}", self.num_errors, if self.num_errors == 1 { "" } else { "s" }, warnings_str)
             );
             // Parse error
             return Err(());
         } else {
             nexus_log::log(
                 nexus_log::LogTypes::Info,
-                nexus_log::LogSources::Parser,
+                nexus_log::LogSource::PARSER,
                 format!("Parser completed successfully with {}", warnings_str)
             );
             // Parsing was successful
@@ -82,42 +123,58 @@ impl Parser {
         }
     }
 
-    fn parse_block(&mut self, token_stream: &Vec<Token>, cst: &mut SyntaxTree) -> Result<(), String> {
+    // The condition is the range of token indices of the if or while condition that the block is the body of
+    fn parse_block(&mut self, token_stream: &Vec<Token>, cst: &mut SyntaxTree, condition: Option<(usize, usize)>) -> Result<(), Message> {
         // Log that we are parsing a block
         nexus_log::log(
             nexus_log::LogTypes::Debug,
-            nexus_log::LogSources::Parser,
+            nexus_log::LogSource::PARSER,
             String::from("Parsing Block")
         );
 
         cst.add_node(SyntaxTreeNodeTypes::Branch, SyntaxTreeNode::NonTerminalCst(NonTerminalsCst::Block));
 
         // Check for left brace
-        let lbrace_err: Result<(), String> = self.match_token(token_stream, TokenType::Symbol(Symbols::LBrace), cst);
+        let lbrace_err: Result<(), Message> = self.match_token(token_stream, TokenType::Symbol(Symbols::LBrace), cst);
         if lbrace_err.is_err() {
             // Return the error message if the left brace does not exist
             return lbrace_err;
         }
 
-        let statement_list_res: Result<(), String> = self.parse_statement_list(token_stream, cst);
+        let statement_list_res: Result<(), Message> = self.parse_statement_list(token_stream, cst);
         if statement_list_res.is_err() {
             return statement_list_res;
         }
 
         // Check for right brace
-        let rbrace_err: Result<(), String> = self.match_token(token_stream, TokenType::Symbol(Symbols::RBrace), cst);
+        let rbrace_err: Result<(), Message> = self.match_token(token_stream, TokenType::Symbol(Symbols::RBrace), cst);
         if rbrace_err.is_err() {
             // Return the error message if the right brace does not exist
-            return rbrace_err;
+            return Err(self.add_missing_brace_fix(token_stream, rbrace_err.unwrap_err()));
         } else {
             // Check 2 tokens prior, which should be a left brace if empty block
             // No need to check for going out of bounds because both left and right brace will already have been consumed
-            match &token_stream[self.cur_token_index - 2].token_type {
-                TokenType::Symbol(Symbols::LBrace) => {
-                    nexus_log::log(
+            match (&token_stream[self.cur_token_index - 2].token_type, condition) {
+                // An empty body means the condition is checked for nothing, so point to the condition instead of the block
+                (TokenType::Symbol(Symbols::LBrace), Some((condition_start, condition_end))) => {
+                    let condition_span: Span = token_stream[condition_start].get_span().merge(&token_stream[condition_end].get_span());
+                    nexus_log::log_message(
+                        nexus_log::LogTypes::Warning,
+                        nexus_log::LogSource::PARSER,
+                        condition_span.start,
+                        &Message::new(MessageCode::EmptyConditionBody, vec![
+                            ("span", condition_span.to_message_param()),
+                            ("statement", token_stream[condition_start - 1].text.to_owned())
+                        ])
+                    );
+                    self.num_warnings += 1;
+                },
+                (TokenType::Symbol(Symbols::LBrace), None) => {
+                    nexus_log::log_message(
                         nexus_log::LogTypes::Warning,
-                        nexus_log::LogSources::Parser,
-                        format!("Empty block found starting at {:?}", token_stream[self.cur_token_index - 2].position)
+                        nexus_log::LogSource::PARSER,
+                        token_stream[self.cur_token_index - 2].position,
+                        &Message::new(MessageCode::EmptyBlock, vec![("position", token_stream[self.cur_token_index - 2].position.to_message_param())])
                     );
                     self.num_warnings += 1;
                 },
@@ -133,7 +190,7 @@ impl Parser {
     }
 
     // Function to ensure the token is correct
-    fn match_token(&mut self, token_stream: &Vec<Token>, expected_token: TokenType, cst: &mut SyntaxTree) -> Result<(), String> {
+    fn match_token(&mut self, token_stream: &Vec<Token>, expected_token: TokenType, cst: &mut SyntaxTree) -> Result<(), Message> {
         // Get the next token
         let cur_token_res: Option<Token> = self.peek_next_token(token_stream);
 
@@ -147,21 +204,29 @@ impl Parser {
                     if cur_token.token_type.ne(&expected_token) {
                         // Return an error message if the expected token does not line up
                         match expected_token {
-                            TokenType::Digit(_) => return Err(format!("Invalid token [ {:?} ] at {:?}; Expected [Digit(0-9)]", cur_token.token_type, cur_token.position)),
-                            _ => return Err(format!("Invalid token [ {:?} ] at {:?}; Expected [{:?}]", cur_token.token_type, cur_token.position, expected_token))
+                            TokenType::Digit(_) => return Err(self.unexpected_token(&cur_token, String::from("[Digit(0-9)]"))),
+                            _ => return Err(self.unexpected_token(&cur_token, format!("[{:?}]", expected_token)))
                         }
                     } else {
                         // Add the node to the CST
                         cst.add_node(SyntaxTreeNodeTypes::Leaf, SyntaxTreeNode::Terminal(cur_token.to_owned()));
                     }
                 },
-                TokenType::Identifier(_) => {
+                TokenType::Identifier(id) => {
                     match expected_token {
+                        // Identifiers from the lexer always fit the profile, but handcrafted tokens may not
+                        TokenType::Identifier(_) if !self.identifiers.is_match(id) => {
+                            return Err(Message::new(MessageCode::InvalidIdentifier, vec![
+                                ("position", cur_token.position.to_message_param()),
+                                ("id", id.to_owned()),
+                                ("allowed", self.language_profile.describe_identifiers())
+                            ]));
+                        },
                         // Add the node to the cst
                         TokenType::Identifier(_) => cst.add_node(SyntaxTreeNodeTypes::Leaf, SyntaxTreeNode::Terminal(cur_token.to_owned())),
                         // Otherwise return an error
-                        TokenType::Digit(_) => return Err(format!("Invalid token [ {:?} ] at {:?}; Expected [Digit(0-9)]", cur_token.token_type, cur_token.position)),
-                        _ => return Err(format!("Invalid token [ {:?} ] at {:?}; Expected [{:?}]", cur_token.token_type, cur_token.position, expected_token)),
+                        TokenType::Digit(_) => return Err(self.unexpected_token(&cur_token, String::from("[Digit(0-9)]"))),
+                        _ => return Err(self.unexpected_token(&cur_token, format!("[{:?}]", expected_token))),
                     }
                 },
                 TokenType::Digit(_) => {
@@ -169,7 +234,7 @@ impl Parser {
                         // Add the new node to the cst
                         TokenType::Digit(_) => cst.add_node(SyntaxTreeNodeTypes::Leaf, SyntaxTreeNode::Terminal(cur_token.to_owned())),
                         // Otherwise return an error
-                        _ => return Err(format!("Invalid token [ {:?} ] at {:?}; Expected [{:?}]", cur_token.token_type, cur_token.position, expected_token))
+                        _ => return Err(self.unexpected_token(&cur_token, format!("[{:?}]", expected_token)))
                     }
                 },
                 TokenType::Char(_) => {
@@ -177,8 +242,17 @@ impl Parser {
                         // Add the node to the cst
                         TokenType::Char(_) => cst.add_node(SyntaxTreeNodeTypes::Leaf, SyntaxTreeNode::Terminal(cur_token.to_owned())),
                         // Otherwise return an error
-                        TokenType::Digit(_) => return Err(format!("Invalid token [ {:?} ] at {:?}; Expected [Digit(0-9)]", cur_token.token_type, cur_token.position)),
-                        _ => return Err(format!("Invalid token [ {:?} ] at {:?}; Expected [{:?}]", cur_token.token_type, cur_token.position, expected_token))
+                        TokenType::Digit(_) => return Err(self.unexpected_token(&cur_token, String::from("[Digit(0-9)]"))),
+                        _ => return Err(self.unexpected_token(&cur_token, format!("[{:?}]", expected_token)))
+                    }
+                },
+                TokenType::CharLiteral(_) => {
+                    match expected_token {
+                        // Add the node to the cst
+                        TokenType::CharLiteral(_) => cst.add_node(SyntaxTreeNodeTypes::Leaf, SyntaxTreeNode::Terminal(cur_token.to_owned())),
+                        // Otherwise return an error
+                        TokenType::Digit(_) => return Err(self.unexpected_token(&cur_token, String::from("[Digit(0-9)]"))),
+                        _ => return Err(self.unexpected_token(&cur_token, format!("[{:?}]", expected_token)))
                     }
                 },
                 TokenType::Keyword(keyword_actual) => {
@@ -187,24 +261,28 @@ impl Parser {
                         TokenType::Keyword(keyword_expected) => {
                             // See if there is a discrepancy is the actual keywords
                             if keyword_actual.ne(&keyword_expected) {
-                                return Err(format!("Invalid token at {:?}; Found {:?}, but expected [{:?}]", cur_token.position, cur_token.token_type, expected_token));
+                                return Err(Message::new(MessageCode::UnexpectedKeyword, vec![
+                                    ("position", cur_token.position.to_message_param()),
+                                    ("found", format!("{:?}", cur_token.token_type)),
+                                    ("expected", format!("[{:?}]", expected_token))
+                                ]));
                             } else {
                                 // Add the node to the cst
                                 cst.add_node(SyntaxTreeNodeTypes::Leaf, SyntaxTreeNode::Terminal(cur_token.to_owned()));
                             }
                         },
-                        TokenType::Digit(_) => return Err(format!("Invalid token [ {:?} ] at {:?}; Expected [Digit(0-9)]", cur_token.token_type, cur_token.position)),
-                        _ => return Err(format!("Invalid token [ {:?} ] at {:?}; Expected [{:?}]", cur_token.token_type, cur_token.position, expected_token))
+                        TokenType::Digit(_) => return Err(self.unexpected_token(&cur_token, String::from("[Digit(0-9)]"))),
+                        _ => return Err(self.unexpected_token(&cur_token, format!("[{:?}]", expected_token)))
                     }
                 },
                 _ => {
                     // This should never be reached
-                    return Err(format!("Unrecognized token [ {:?} ] at {:?}", cur_token.text, cur_token.position))
+                    return Err(Message::new(MessageCode::UnknownToken, vec![("position", cur_token.position.to_message_param()), ("token", format!("{:?}", cur_token.text))]))
                 }
             }
         } else {
             // Error if no more tokens and expected something
-            return Err(format!("Missing token [{:?}] at end of program", expected_token));
+            return Err(Message::new(MessageCode::MissingToken, vec![("expected", format!("[{:?}]", expected_token))]));
         }
 
         // Consume the token if it is ok
@@ -212,7 +290,7 @@ impl Parser {
         return Ok(());
     }
 
-    fn match_token_collection(&mut self, token_stream: &Vec<Token>, expected_tokens: Vec<TokenType>, cst: &mut SyntaxTree) -> Result<(), String> {
+    fn match_token_collection(&mut self, token_stream: &Vec<Token>, expected_tokens: Vec<TokenType>, cst: &mut SyntaxTree) -> Result<(), Message> {
         // Get the next token
         let cur_token_res: Option<Token> = self.peek_next_token(token_stream);
 
@@ -227,32 +305,37 @@ impl Parser {
                 self.cur_token_index += 1;
                 return Ok(());
             } else {
-                return Err(format!("Invalid token [ {:?} ] at {:?}; Expected {:?}", cur_token.token_type, cur_token.position, expected_tokens));
+                return Err(self.unexpected_token(&cur_token, format!("{:?}", expected_tokens)));
             }
         } else {
             // Error if no more tokens and expected something
-            return Err(format!("Missing token {:?} at end of program", expected_tokens));
+            return Err(Message::new(MessageCode::MissingToken, vec![("expected", format!("{:?}", expected_tokens))]));
         }
     }
 
-    fn parse_statement_list(&mut self, token_stream: &Vec<Token>, cst: &mut SyntaxTree) -> Result<(), String> {
+    fn parse_statement_list(&mut self, token_stream: &Vec<Token>, cst: &mut SyntaxTree) -> Result<(), Message> {
         // Make sure that the statement list is not empty
         if !self.peek_and_match_next_token(token_stream, TokenType::Symbol(Symbols::RBrace)) {
             // Log that we are parsing a statement list
             nexus_log::log(
                 nexus_log::LogTypes::Debug,
-                nexus_log::LogSources::Parser,
+                nexus_log::LogSource::PARSER,
                 String::from("Parsing StatementList")
             );
             cst.add_node(SyntaxTreeNodeTypes::Branch, SyntaxTreeNode::NonTerminalCst(NonTerminalsCst::StatementList));
             // Parse the statement
-            let statement_res: Result<(), String> = self.parse_statement(token_stream, cst);
+            let statement_res: Result<(), Message> = self.parse_statement(token_stream, cst);
             if statement_res.is_err() {
                 // There was an error so break here
-                return statement_res;
+                let statement_message: Message = statement_res.unwrap_err();
+                return Err(match statement_message.code {
+                    // The program ended where the next statement should be, so the block was never closed
+                    MessageCode::InvalidStatement | MessageCode::MissingStatement => self.add_missing_brace_fix(token_stream, statement_message),
+                    _ => statement_message
+                });
             } else {
                 // StatementList = Statement StatementList, so call parse on the next statement list
-                let statement_list_res: Result<(), String> = self.parse_statement_list(token_stream, cst);
+                let statement_list_res: Result<(), Message> = self.parse_statement_list(token_stream, cst);
                 if statement_list_res.is_ok() {
                     cst.move_up();
                 }
@@ -262,7 +345,7 @@ impl Parser {
         } else {
             nexus_log::log(
                 nexus_log::LogTypes::Debug,
-                nexus_log::LogSources::Parser,
+                nexus_log::LogSource::PARSER,
                 String::from("Parsing StatementList (epsilon base case)")
             );
             // Do nothing here because we have an epsilon with the statement list
@@ -270,11 +353,11 @@ impl Parser {
         }
     }
 
-    fn parse_statement(&mut self, token_stream: &Vec<Token>, cst: &mut SyntaxTree) -> Result<(), String> {
+    fn parse_statement(&mut self, token_stream: &Vec<Token>, cst: &mut SyntaxTree) -> Result<(), Message> {
         // Log that we are parsing a statement
         nexus_log::log(
             nexus_log::LogTypes::Debug,
-            nexus_log::LogSources::Parser,
+            nexus_log::LogSource::PARSER,
             String::from("Parsing Statement")
         );
 
@@ -287,7 +370,7 @@ impl Parser {
             let next_token: Token = next_token_peek.unwrap();
 
             // Assign a result object to statement_res based on the next token in the stream
-            let statement_res: Result<(), String> = match next_token.token_type {
+            let statement_res: Result<(), Message> = match next_token.token_type {
                 // Print statements
                 TokenType::Keyword(Keywords::Print) => self.parse_print_statement(token_stream, cst),
 
@@ -295,7 +378,7 @@ impl Parser {
                 TokenType::Identifier(_) => self.parse_assignment_statement(token_stream, cst),
 
                 // VarDecl statements
-                TokenType::Keyword(Keywords::Int) | TokenType::Keyword(Keywords::String) | TokenType::Keyword(Keywords::Boolean) => self.parse_var_declaration(token_stream, cst),
+                TokenType::Keyword(Keywords::Int) | TokenType::Keyword(Keywords::String) | TokenType::Keyword(Keywords::Boolean) | TokenType::Keyword(Keywords::Char) => self.parse_var_declaration(token_stream, cst),
 
                 // While statements
                 TokenType::Keyword(Keywords::While) => self.parse_while_statement(token_stream, cst), 
@@ -304,10 +387,14 @@ impl Parser {
                 TokenType::Keyword(Keywords::If) => self.parse_if_statement(token_stream, cst),
 
                 // Block statements
-                TokenType::Symbol(Symbols::LBrace) => self.parse_block(token_stream, cst),
+                TokenType::Symbol(Symbols::LBrace) => self.parse_block(token_stream, cst, None),
 
                 // Invalid statement starter tokens
-                _ => Err(format!("Invalid statement token [ {:?} ] at {:?}; Valid statement beginning tokens are {:?}", next_token.token_type, next_token.position, vec![TokenType::Keyword(Keywords::Print), TokenType::Identifier(String::from("a-z")), TokenType::Keyword(Keywords::Int), TokenType::Keyword(Keywords::String), TokenType::Keyword(Keywords::Boolean), TokenType::Keyword(Keywords::While), TokenType::Keyword(Keywords::If), TokenType::Symbol(Symbols::LBrace)]))
+                _ => Err(Message::new(MessageCode::InvalidStatement, vec![
+                    ("position", next_token.position.to_message_param()),
+                    ("found", format!("{:?}", next_token.token_type)),
+                    ("expected", self.first_sets.get_expected_text(NonTerminalsCst::Statement))
+                ]))
             };
             // We have parsed through the statement and can move up
             if statement_res.is_ok() {
@@ -316,15 +403,15 @@ impl Parser {
             return statement_res;
         } else {
             // Return an error because there is no token for the statement
-            return Err(format!("Missing statement token at end of program; Valid statement beginning tokens are {:?}", vec![TokenType::Keyword(Keywords::Print), TokenType::Identifier(String::from("a-z")), TokenType::Keyword(Keywords::Int), TokenType::Keyword(Keywords::String), TokenType::Keyword(Keywords::Boolean), TokenType::Keyword(Keywords::While), TokenType::Keyword(Keywords::If), TokenType::Symbol(Symbols::LBrace)]));
+            return Err(Message::new(MessageCode::MissingStatement, vec![("expected", self.first_sets.get_expected_text(NonTerminalsCst::Statement))]));
         }
     }
 
-    fn parse_print_statement(&mut self, token_stream: &Vec<Token>, cst: &mut SyntaxTree) -> Result<(), String> {
+    fn parse_print_statement(&mut self, token_stream: &Vec<Token>, cst: &mut SyntaxTree) -> Result<(), Message> {
         // Log that we are parsing a print statement
         nexus_log::log(
             nexus_log::LogTypes::Debug,
-            nexus_log::LogSources::Parser,
+            nexus_log::LogSource::PARSER,
             String::from("Parsing PrintStatement")
         );
 
@@ -332,30 +419,67 @@ impl Parser {
         cst.add_node(SyntaxTreeNodeTypes::Branch, SyntaxTreeNode::NonTerminalCst(NonTerminalsCst::PrintStatement));
 
         // Check for the print keyword
-        let keyword_res: Result<(), String> = self.match_token(token_stream, TokenType::Keyword(Keywords::Print), cst);
+        let print_position: Option<Position> = self.peek_next_token(token_stream).map(|token| token.position);
+        let keyword_res: Result<(), Message> = self.match_token(token_stream, TokenType::Keyword(Keywords::Print), cst);
         if keyword_res.is_err() {
             return keyword_res;
         }
+        let print_position: Position = print_position.unwrap();
 
         // Check for the left paren
-        let lparen_res: Result<(), String> = self.match_token(token_stream, TokenType::Symbol(Symbols::LParen), cst);
+        let lparen_res: Result<(), Message> = self.match_token(token_stream, TokenType::Symbol(Symbols::LParen), cst);
         if lparen_res.is_err() {
-            return lparen_res;
+            return Err(match self.peek_next_token(token_stream) {
+                Some(token) => Message::new(MessageCode::PrintMissingLParen, vec![
+                    ("position", print_position.to_message_param()),
+                    ("found", format!("{:?}", token.token_type)),
+                    ("found_position", token.position.to_message_param())
+                ]),
+                None => Message::new(MessageCode::PrintEndedEarly, vec![("position", print_position.to_message_param())])
+            });
         }
 
-        // First make sure that we have tokens available for an expression
-        if self.peek_next_token(token_stream).is_some() {
+        // The parentheses show where the statement ends, so an empty print can be reported and skipped
+        if self.peek_and_match_next_token(token_stream, TokenType::Symbol(Symbols::RParen)) {
+            self.report_error_at(print_position, Message::new(MessageCode::PrintMissingExpression, vec![("position", print_position.to_message_param())]));
+        } else if self.peek_next_token(token_stream).is_some() {
             // Check to make sure we have a valid expression to print
-            let expr_res: Result<(), String> = self.parse_expression(token_stream, cst);
+            let expr_res: Result<(), Message> = self.parse_expression(token_stream, cst);
             if expr_res.is_err() {
                 return expr_res;
             }
+
+            // Anything else before the right paren is another expression, which can be skipped over
+            let extra_token: Option<Token> = self.peek_next_token(token_stream);
+            let rparen_index: Option<usize> = self.find_closing_paren(token_stream);
+            match (extra_token, rparen_index) {
+                (Some(extra_token), Some(rparen_index)) if rparen_index > self.cur_token_index => {
+                    self.report_error_at(extra_token.position, Message::new(MessageCode::PrintExtraExpression, vec![
+                        ("position", print_position.to_message_param()),
+                        ("found", format!("{:?}", extra_token.token_type)),
+                        ("found_position", extra_token.position.to_message_param())
+                    ]));
+                    self.cur_token_index = rparen_index;
+                },
+                (Some(extra_token), None) => {
+                    return Err(Message::new(MessageCode::PrintMissingRParen, vec![
+                        ("position", print_position.to_message_param()),
+                        ("found", format!("{:?}", extra_token.token_type)),
+                        ("found_position", extra_token.position.to_message_param())
+                    ]).with_fix(QuickFix::new("Insert )", token_stream[self.cur_token_index - 1].get_span().end, ")")));
+                },
+                _ => {}
+            }
         }
 
         // Check for the right paren
-        let rparen_res: Result<(), String> = self.match_token(token_stream, TokenType::Symbol(Symbols::RParen), cst);
+        let rparen_res: Result<(), Message> = self.match_token(token_stream, TokenType::Symbol(Symbols::RParen), cst);
         if rparen_res.is_err() {
-            return rparen_res;
+            // The program ended right after the expression
+            return Err(match self.peek_next_token(token_stream) {
+                Some(_) => rparen_res.unwrap_err(),
+                None => rparen_res.unwrap_err().with_fix(QuickFix::new("Insert )", self.get_end_position(token_stream), ")"))
+            });
         }
 
         // All good so we move up
@@ -363,11 +487,11 @@ impl Parser {
         return Ok(());
     }
 
-    fn parse_assignment_statement(&mut self, token_stream: &Vec<Token>, cst: &mut SyntaxTree) -> Result<(), String> {
+    fn parse_assignment_statement(&mut self, token_stream: &Vec<Token>, cst: &mut SyntaxTree) -> Result<(), Message> {
         // Log that we are parsing a print statement
         nexus_log::log(
             nexus_log::LogTypes::Debug,
-            nexus_log::LogSources::Parser,
+            nexus_log::LogSource::PARSER,
             String::from("Parsing AssignmentStatement")
         );
 
@@ -375,19 +499,19 @@ impl Parser {
         cst.add_node(SyntaxTreeNodeTypes::Branch, SyntaxTreeNode::NonTerminalCst(NonTerminalsCst::AssignmentStatement));
 
         // Assignment statements begin with an identifier
-        let id_res: Result<(), String> = self.parse_identifier(token_stream, cst);
+        let id_res: Result<(), Message> = self.parse_identifier(token_stream, cst);
         if id_res.is_err() {
             return id_res;
         }
 
         // Check for a =
-        let assignment_op_res: Result<(), String> = self.match_token(token_stream, TokenType::Symbol(Symbols::AssignmentOp), cst);
+        let assignment_op_res: Result<(), Message> = self.match_token(token_stream, TokenType::Symbol(Symbols::AssignmentOp), cst);
         if assignment_op_res.is_err() {
             return assignment_op_res;
         }
 
         // The right hand side of the statement is an expression
-        let expr_res: Result<(), String> = self.parse_expression(token_stream, cst);
+        let expr_res: Result<(), Message> = self.parse_expression(token_stream, cst);
         if expr_res.is_err() {
             return expr_res;
         }
@@ -396,11 +520,11 @@ impl Parser {
         return Ok(());
     }
 
-    fn parse_var_declaration(&mut self, token_stream: &Vec<Token>, cst: &mut SyntaxTree) -> Result<(), String>{
+    fn parse_var_declaration(&mut self, token_stream: &Vec<Token>, cst: &mut SyntaxTree) -> Result<(), Message>{
         // Log that we are parsing a variable declaration
         nexus_log::log(
             nexus_log::LogTypes::Debug,
-            nexus_log::LogSources::Parser,
+            nexus_log::LogSource::PARSER,
             String::from("Parsing VarDecl")
         );
 
@@ -408,26 +532,46 @@ impl Parser {
         cst.add_node(SyntaxTreeNodeTypes::Branch, SyntaxTreeNode::NonTerminalCst(NonTerminalsCst::VarDecl));
 
         // Make sure we have a valid type
-        let type_res: Result<(), String> = self.parse_type(token_stream, cst);
+        let type_res: Result<(), Message> = self.parse_type(token_stream, cst);
         if type_res.is_err() {
             return type_res;
         }
 
         // Then make sure there is a valid identifier
-        let id_res: Result<(), String> = self.parse_identifier(token_stream, cst);
+        let id_res: Result<(), Message> = self.parse_identifier(token_stream, cst);
         if id_res.is_err() {
             return id_res;
         }
 
+        // The declaration can optionally be initialized with = Expr
+        if self.peek_and_match_next_token(token_stream, TokenType::Symbol(Symbols::AssignmentOp)) {
+            nexus_log::log(
+                nexus_log::LogTypes::Debug,
+                nexus_log::LogSource::PARSER,
+                String::from("Parsing VarDecl initializer")
+            );
+
+            let assignment_op_res: Result<(), Message> = self.match_token(token_stream, TokenType::Symbol(Symbols::AssignmentOp), cst);
+            if assignment_op_res.is_err() {
+                return assignment_op_res;
+            }
+
+            // The initial value is an expression just like an assignment statement
+            let expr_res: Result<(), Message> = self.parse_expression(token_stream, cst);
+            if expr_res.is_err() {
+                return expr_res;
+            }
+        }
+
         cst.move_up();
         return Ok(());
     }
 
-    fn parse_while_statement(&mut self, token_stream: &Vec<Token>, cst: &mut SyntaxTree) -> Result<(), String> {
+    fn parse_while_statement(&mut self, token_stream: &Vec<Token>, cst: &mut SyntaxTree) -> Result<(), Message> {
         // Log that we are parsing a while statement
         nexus_log::log(
             nexus_log::LogTypes::Debug,
-            nexus_log::LogSources::Parser,
+            nexus_log::LogSource::PARSER,
             String::from("Parsing WhileStatement")
         );
 
@@ -435,19 +579,20 @@ impl Parser {
         cst.add_node(SyntaxTreeNodeTypes::Branch, SyntaxTreeNode::NonTerminalCst(NonTerminalsCst::WhileStatement));
 
         // Make sure we have the while token
-        let while_res: Result<(), String> = self.match_token(token_stream, TokenType::Keyword(Keywords::While), cst);
+        let while_res: Result<(), Message> = self.match_token(token_stream, TokenType::Keyword(Keywords::While), cst);
         if while_res.is_err() {
             return while_res;
         }
 
         // While has a boolean expression
-        let bool_expr_res: Result<(), String> = self.parse_bool_expression(token_stream, cst);
+        let condition_start: usize = self.cur_token_index;
+        let bool_expr_res: Result<(), Message> = self.parse_bool_expression(token_stream, cst);
         if bool_expr_res.is_err() {
             return bool_expr_res;
         }
 
         // The body of the loop is defined by a block
-        let block_res: Result<(), String> = self.parse_block(token_stream, cst);
+        let block_res: Result<(), Message> = self.parse_block(token_stream, cst, Some((condition_start, self.cur_token_index - 1)));
         if block_res.is_err() {
             return block_res;
         }
@@ -456,11 +601,11 @@ impl Parser {
         return Ok(());
     }
 
-    fn parse_if_statement(&mut self, token_stream: &Vec<Token>, cst: &mut SyntaxTree) -> Result<(), String> {
+    fn parse_if_statement(&mut self, token_stream: &Vec<Token>, cst: &mut SyntaxTree) -> Result<(), Message> {
         // Log that we are parsing an if statement
         nexus_log::log(
             nexus_log::LogTypes::Debug,
-            nexus_log::LogSources::Parser,
+            nexus_log::LogSource::PARSER,
             String::from("Parsing IfStatement")
         );
 
@@ -468,19 +613,20 @@ impl Parser {
         cst.add_node(SyntaxTreeNodeTypes::Branch, SyntaxTreeNode::NonTerminalCst(NonTerminalsCst::IfStatement));
 
         // Make sure we have the if token
-        let if_res: Result<(), String> = self.match_token(token_stream, TokenType::Keyword(Keywords::If), cst);
+        let if_res: Result<(), Message> = self.match_token(token_stream, TokenType::Keyword(Keywords::If), cst);
         if if_res.is_err() {
             return if_res;
         }
 
         // If has a boolean expression
-        let bool_expr_res: Result<(), String> = self.parse_bool_expression(token_stream, cst);
+        let condition_start: usize = self.cur_token_index;
+        let bool_expr_res: Result<(), Message> = self.parse_bool_expression(token_stream, cst);
         if bool_expr_res.is_err() {
             return bool_expr_res;
         }
 
         // The body of the if-statement is a block
-        let block_res: Result<(), String> = self.parse_block(token_stream, cst);
+        let block_res: Result<(), Message> = self.parse_block(token_stream, cst, Some((condition_start, self.cur_token_index - 1)));
         if block_res.is_err() {
             return block_res;
         }
@@ -489,11 +635,11 @@ impl Parser {
         return Ok(());
     }
 
-    fn parse_expression(&mut self, token_stream: &Vec<Token>, cst: &mut SyntaxTree) -> Result<(), String> {
+    fn parse_expression(&mut self, token_stream: &Vec<Token>, cst: &mut SyntaxTree) -> Result<(), Message> {
         // Log that we are parsing an expression
         nexus_log::log(
             nexus_log::LogTypes::Debug,
-            nexus_log::LogSources::Parser,
+            nexus_log::LogSource::PARSER,
             String::from("Parsing Expr")
         );
 
@@ -506,20 +652,36 @@ impl Parser {
             let next_token: Token = next_token_peek.unwrap();
 
             // Assign a result object to expression_res based on the next token in the stream
-            let expression_res: Result<(), String> = match next_token.token_type {
+            let expression_res: Result<(), Message> = match next_token.token_type {
                 // IntExpr
                 TokenType::Digit(_) => self.parse_int_expression(token_stream, cst),
 
+                // IndexExpr of a string literal or an id
+                TokenType::Symbol(Symbols::Quote) | TokenType::Identifier(_) if Parser::is_index_expression(token_stream, self.cur_token_index) => self.parse_index_expression(token_stream, cst),
+
                 // StringExpr
                 TokenType::Symbol(Symbols::Quote) => self.parse_string_expression(token_stream, cst),
 
+                // CharExpr
+                TokenType::CharLiteral(_) => self.parse_char_expression(token_stream, cst),
+
+                // LenExpr
+                TokenType::Keyword(Keywords::Len) => self.parse_len_expression(token_stream, cst),
+
+                // IntExpr in parentheses
+                TokenType::Symbol(Symbols::LParen) if Parser::find_grouped_int_expression_end(token_stream, self.cur_token_index).is_some() => self.parse_int_expression(token_stream, cst),
+
                 // BooleanExpr
                 TokenType::Symbol(Symbols::LParen) | TokenType::Keyword(Keywords::False) | TokenType::Keyword(Keywords::True) => self.parse_bool_expression(token_stream, cst),
 
                 // Id
                 TokenType::Identifier(_) => self.parse_identifier(token_stream, cst),
 
-                _ => Err(format!("Invalid expression token [ {:?} ] at {:?}; Valid expression beginning tokens are [Digit(0-9), {:?}, {:?}, {:?}, {:?}, {:?}]", next_token.token_type, next_token.position, TokenType::Symbol(Symbols::Quote), TokenType::Symbol(Symbols::LParen), TokenType::Keyword(Keywords::False), TokenType::Keyword(Keywords::True), TokenType::Identifier(String::from("a-z")))),
+                _ => Err(Message::new(MessageCode::InvalidExpression, vec![
+                    ("position", next_token.position.to_message_param()),
+                    ("found", format!("{:?}", next_token.token_type)),
+                    ("expected", self.first_sets.get_expected_text(NonTerminalsCst::Expr))
+                ])),
             };
     
             if expression_res.is_ok() {
@@ -528,31 +690,34 @@ impl Parser {
             return expression_res;
         } else {
             // There are no more tokens to parse
-            return Err(format!("Missing expression token at end of program; Valid expression beginning tokens are [Digit(0-9), {:?}, {:?}, {:?}, {:?}, {:?}]", TokenType::Symbol(Symbols::Quote), TokenType::Symbol(Symbols::LParen), TokenType::Keyword(Keywords::False), TokenType::Keyword(Keywords::True), TokenType::Identifier(String::from("a-z"))));
+            return Err(Message::new(MessageCode::MissingExpression, vec![("expected", self.first_sets.get_expected_text(NonTerminalsCst::Expr))]));
         }
     }
 
 
-    fn parse_int_expression(&mut self, token_stream: &Vec<Token>, cst: &mut SyntaxTree) -> Result<(), String> {
+    fn parse_int_expression(&mut self, token_stream: &Vec<Token>, cst: &mut SyntaxTree) -> Result<(), Message> {
         // Log that we are parsing an integer expression
         nexus_log::log(
             nexus_log::LogTypes::Debug,
-            nexus_log::LogSources::Parser,
+            nexus_log::LogSource::PARSER,
             String::from("Parsing IntExpr")
         );
 
         // Add the IntExpr node
         cst.add_node(SyntaxTreeNodeTypes::Branch, SyntaxTreeNode::NonTerminalCst(NonTerminalsCst::IntExpr));
 
-        // Parse the first digit and return error if needed
-        let first_digit_res: Result<(), String> = self.parse_digit(token_stream, cst);
-        if first_digit_res.is_err() {
-            return first_digit_res;
+        // Parse the first digit or the grouped expression and return error if needed
+        let first_operand_res: Result<(), Message> = match self.peek_and_match_next_token(token_stream, TokenType::Symbol(Symbols::LParen)) {
+            true => self.grouped_int_expression_helper(token_stream, cst),
+            false => self.parse_digit(token_stream, cst)
+        };
+        if first_operand_res.is_err() {
+            return first_operand_res;
         }
 
         // Check the integer operator
         if self.peek_and_match_next_token(token_stream, TokenType::Symbol(Symbols::AdditionOp)) {     
-            let int_op_res: Result<(), String> = self.parse_int_op(token_stream, cst);
+            let int_op_res: Result<(), Message> = self.parse_int_op(token_stream, cst);
     
             if int_op_res.is_err() {
                 return int_op_res;
@@ -560,7 +725,7 @@ impl Parser {
 
             // Get the second half of the expression if there is an integer operator and return the error if needed
             // Type check does not matter, so can parse 3 + "hello" for now and semantic analysis will catch it
-            let second_half_res: Result<(), String> = self.parse_expression(token_stream, cst);
+            let second_half_res: Result<(), Message> = self.parse_expression(token_stream, cst);
             if second_half_res.is_err() {
                 return second_half_res;
             }
@@ -570,11 +735,31 @@ impl Parser {
         return Ok(());
     }
 
-    fn parse_string_expression(&mut self, token_stream: &Vec<Token>, cst: &mut SyntaxTree) -> Result<(), String> {
+    fn grouped_int_expression_helper(&mut self, token_stream: &Vec<Token>, cst: &mut SyntaxTree) -> Result<(), Message> {
+        let feature_res: Result<(), Message> = self.check_feature(token_stream, LanguageFeature::GroupedExpressions);
+        if feature_res.is_err() {
+            return feature_res;
+        }
+
+        let lparen_res: Result<(), Message> = self.match_token(token_stream, TokenType::Symbol(Symbols::LParen), cst);
+        if lparen_res.is_err() {
+            return lparen_res;
+        }
+
+        // Only an integer expression can be inside of the parentheses
+        let int_expr_res: Result<(), Message> = self.parse_int_expression(token_stream, cst);
+        if int_expr_res.is_err() {
+            return int_expr_res;
+        }
+
+        return self.match_token(token_stream, TokenType::Symbol(Symbols::RParen), cst);
+    }
+
+    fn parse_string_expression(&mut self, token_stream: &Vec<Token>, cst: &mut SyntaxTree) -> Result<(), Message> {
         // Log that we are parsing a string expression
         nexus_log::log(
             nexus_log::LogTypes::Debug,
-            nexus_log::LogSources::Parser,
+            nexus_log::LogSource::PARSER,
             String::from("Parsing StringExpr")
         );
 
@@ -582,19 +767,19 @@ impl Parser {
         cst.add_node(SyntaxTreeNodeTypes::Branch, SyntaxTreeNode::NonTerminalCst(NonTerminalsCst::StringExpr));
 
         // Check for the open quote
-        let open_quote_res: Result<(), String> = self.match_token(token_stream, TokenType::Symbol(Symbols::Quote), cst);
+        let open_quote_res: Result<(), Message> = self.match_token(token_stream, TokenType::Symbol(Symbols::Quote), cst);
         if open_quote_res.is_err() {
             return open_quote_res;
         }
 
         // Parse the string contents
-        let char_list_res: Result<(), String> = self.parse_char_list(token_stream, cst);
+        let char_list_res: Result<(), Message> = self.parse_char_list(token_stream, cst);
         if char_list_res.is_err() {
             return char_list_res;
         }
 
         // Check for the close quote
-        let close_quote_res: Result<(), String> = self.match_token(token_stream, TokenType::Symbol(Symbols::Quote), cst);
+        let close_quote_res: Result<(), Message> = self.match_token(token_stream, TokenType::Symbol(Symbols::Quote), cst);
         if close_quote_res.is_err() {
             return close_quote_res;
         } else {
@@ -602,10 +787,11 @@ impl Parser {
             // No need to check for going out of bounds because both quotes will already have been consumed
             match &token_stream[self.cur_token_index - 2].token_type {
                 TokenType::Symbol(Symbols::Quote) => {
-                    nexus_log::log(
+                    nexus_log::log_message(
                         nexus_log::LogTypes::Warning,
-                        nexus_log::LogSources::Parser,
-                        format!("Empty string found starting at {:?}", token_stream[self.cur_token_index - 2].position)
+                        nexus_log::LogSource::PARSER,
+                        token_stream[self.cur_token_index - 2].position,
+                        &Message::new(MessageCode::EmptyString, vec![("position", token_stream[self.cur_token_index - 2].position.to_message_param())])
                     );
                     self.num_warnings += 1;
                 },
@@ -617,11 +803,117 @@ impl Parser {
         return Ok(());
     }
 
-    fn parse_bool_expression(&mut self, token_stream: &Vec<Token>, cst: &mut SyntaxTree) -> Result<(), String> {
+    fn parse_char_expression(&mut self, token_stream: &Vec<Token>, cst: &mut SyntaxTree) -> Result<(), Message> {
+        // Log that we are parsing a char expression
+        nexus_log::log(
+            nexus_log::LogTypes::Debug,
+            nexus_log::LogSource::PARSER,
+            String::from("Parsing CharExpr")
+        );
+
+        let feature_res: Result<(), Message> = self.check_feature(token_stream, LanguageFeature::Chars);
+        if feature_res.is_err() {
+            return feature_res;
+        }
+
+        // Add the CharExpr node
+        cst.add_node(SyntaxTreeNodeTypes::Branch, SyntaxTreeNode::NonTerminalCst(NonTerminalsCst::CharExpr));
+
+        // The quotes are part of the char literal token
+        let char_literal_res: Result<(), Message> = self.match_token(token_stream, TokenType::CharLiteral('a'), cst);
+        if char_literal_res.is_ok() {
+            cst.move_up();
+        }
+        return char_literal_res;
+    }
+
+    fn parse_index_expression(&mut self, token_stream: &Vec<Token>, cst: &mut SyntaxTree) -> Result<(), Message> {
+        // Log that we are parsing an index expression
+        nexus_log::log(
+            nexus_log::LogTypes::Debug,
+            nexus_log::LogSource::PARSER,
+            String::from("Parsing IndexExpr")
+        );
+
+        let feature_res: Result<(), Message> = self.check_feature(token_stream, LanguageFeature::StringIndexing);
+        if feature_res.is_err() {
+            return feature_res;
+        }
+
+        // Add the IndexExpr node
+        cst.add_node(SyntaxTreeNodeTypes::Branch, SyntaxTreeNode::NonTerminalCst(NonTerminalsCst::IndexExpr));
+
+        // The string being indexed is either a literal or an id
+        let string_res: Result<(), Message> = match self.peek_and_match_next_token(token_stream, TokenType::Symbol(Symbols::Quote)) {
+            true => self.parse_string_expression(token_stream, cst),
+            false => self.parse_identifier(token_stream, cst)
+        };
+        if string_res.is_err() {
+            return string_res;
+        }
+
+        let lbracket_res: Result<(), Message> = self.match_token(token_stream, TokenType::Symbol(Symbols::LBracket), cst);
+        if lbracket_res.is_err() {
+            return lbracket_res;
+        }
+
+        // Type check does not matter, so semantic analysis makes sure the index is an integer
+        let index_res: Result<(), Message> = self.parse_expression(token_stream, cst);
+        if index_res.is_err() {
+            return index_res;
+        }
+
+        let rbracket_res: Result<(), Message> = self.match_token(token_stream, TokenType::Symbol(Symbols::RBracket), cst);
+        if rbracket_res.is_ok() {
+            cst.move_up();
+        }
+        return rbracket_res;
+    }
+
+    fn parse_len_expression(&mut self, token_stream: &Vec<Token>, cst: &mut SyntaxTree) -> Result<(), Message> {
+        // Log that we are parsing a length expression
+        nexus_log::log(
+            nexus_log::LogTypes::Debug,
+            nexus_log::LogSource::PARSER,
+            String::from("Parsing LenExpr")
+        );
+
+        let feature_res: Result<(), Message> = self.check_feature(token_stream, LanguageFeature::StringLength);
+        if feature_res.is_err() {
+            return feature_res;
+        }
+
+        // Add the LenExpr node
+        cst.add_node(SyntaxTreeNodeTypes::Branch, SyntaxTreeNode::NonTerminalCst(NonTerminalsCst::LenExpr));
+
+        let len_res: Result<(), Message> = self.match_token(token_stream, TokenType::Keyword(Keywords::Len), cst);
+        if len_res.is_err() {
+            return len_res;
+        }
+
+        let lparen_res: Result<(), Message> = self.match_token(token_stream, TokenType::Symbol(Symbols::LParen), cst);
+        if lparen_res.is_err() {
+            return lparen_res;
+        }
+
+        // Type check does not matter, so semantic analysis makes sure the argument is a string
+        let string_res: Result<(), Message> = self.parse_expression(token_stream, cst);
+        if string_res.is_err() {
+            return string_res;
+        }
+
+        let rparen_res: Result<(), Message> = self.match_token(token_stream, TokenType::Symbol(Symbols::RParen), cst);
+        if rparen_res.is_ok() {
+            cst.move_up();
+        }
+        return rparen_res;
+    }
+
+    fn parse_bool_expression(&mut self, token_stream: &Vec<Token>, cst: &mut SyntaxTree) -> Result<(), Message> {
         // Log that we are parsing a boolean expression
         nexus_log::log(
             nexus_log::LogTypes::Debug,
-            nexus_log::LogSources::Parser,
+            nexus_log::LogSource::PARSER,
             String::from("Parsing BooleanExpr")
         );
 
@@ -632,7 +924,7 @@ impl Parser {
         if next_token_peek.is_some() {
             let next_token: Token = next_token_peek.unwrap();
 
-            let bool_expr_res: Result<(), String> = match next_token.token_type {
+            let bool_expr_res: Result<(), Message> = match next_token.token_type {
                 // Long boolean expressions start with LParen
                 TokenType::Symbol(Symbols::LParen) => self.long_bool_expression_helper(token_stream, cst),
     
@@ -640,7 +932,11 @@ impl Parser {
                 TokenType::Keyword(Keywords::False) | TokenType::Keyword(Keywords::True) => self.parse_bool_val(token_stream, cst),
     
                 // Invalid boolean expression
-                _ => Err(format!("Invalid boolean expression token [ {:?} ] at {:?}; Valid boolean expression beginning tokens are {:?}", next_token.token_type, next_token.position, vec![TokenType::Symbol(Symbols::LParen), TokenType::Keyword(Keywords::False), TokenType::Keyword(Keywords::True)]))
+                _ => Err(Message::new(MessageCode::InvalidBooleanExpr, vec![
+                    ("position", next_token.position.to_message_param()),
+                    ("found", format!("{:?}", next_token.token_type)),
+                    ("expected", self.first_sets.get_expected_text(NonTerminalsCst::BooleanExpr))
+                ]))
             };
     
             if bool_expr_res.is_ok() {
@@ -649,45 +945,45 @@ impl Parser {
             return bool_expr_res;
         } else {
             // There are no more tokens to parse
-            return Err(format!("Missing boolean expression token at end of program; Valid boolean expression beginning tokens are {:?}", vec![TokenType::Symbol(Symbols::LParen), TokenType::Keyword(Keywords::False), TokenType::Keyword(Keywords::True)]));
+            return Err(Message::new(MessageCode::MissingBooleanExpr, vec![("expected", self.first_sets.get_expected_text(NonTerminalsCst::BooleanExpr))]));
         }
     }
 
-    fn long_bool_expression_helper(&mut self, token_stream: &Vec<Token>, cst: &mut SyntaxTree) -> Result<(), String> {
-        let lparen_res: Result<(), String> = self.match_token(token_stream, TokenType::Symbol(Symbols::LParen), cst);
+    fn long_bool_expression_helper(&mut self, token_stream: &Vec<Token>, cst: &mut SyntaxTree) -> Result<(), Message> {
+        let lparen_res: Result<(), Message> = self.match_token(token_stream, TokenType::Symbol(Symbols::LParen), cst);
         if lparen_res.is_err() {
             return lparen_res;
         }
 
         // Then move on to the left side of the expression
-        let expr1_res: Result<(), String> = self.parse_expression(token_stream, cst);
+        let expr1_res: Result<(), Message> = self.parse_expression(token_stream, cst);
         if expr1_res.is_err() {
             return expr1_res;
         }
 
         // Next check for a boolean operator
-        let bool_op_res: Result<(), String> = self.parse_bool_op(token_stream, cst);
+        let bool_op_res: Result<(), Message> = self.parse_bool_op(token_stream, cst);
         if bool_op_res.is_err() {
             return bool_op_res;
         }
 
         // Next check for the other side of the expression
-        let expr2_res: Result<(), String> = self.parse_expression(token_stream, cst);
+        let expr2_res: Result<(), Message> = self.parse_expression(token_stream, cst);
         if expr2_res.is_err() {
             return expr2_res;
         }
 
         // Lastly close it with a paren
-        let rparen_res: Result<(), String> = self.match_token(token_stream, TokenType::Symbol(Symbols::RParen), cst);
+        let rparen_res: Result<(), Message> = self.match_token(token_stream, TokenType::Symbol(Symbols::RParen), cst);
         // Return the result regardless of error or ok
         return rparen_res;
     }
 
-    fn parse_identifier(&mut self, token_stream: &Vec<Token>, cst: &mut SyntaxTree) -> Result<(), String> {
+    fn parse_identifier(&mut self, token_stream: &Vec<Token>, cst: &mut SyntaxTree) -> Result<(), Message> {
         // Log that we are parsing an identifier
         nexus_log::log(
             nexus_log::LogTypes::Debug,
-            nexus_log::LogSources::Parser,
+            nexus_log::LogSource::PARSER,
             String::from("Parsing Id")
         );
 
@@ -695,7 +991,7 @@ impl Parser {
         cst.add_node(SyntaxTreeNodeTypes::Branch, SyntaxTreeNode::NonTerminalCst(NonTerminalsCst::Id));
 
         // Match the id
-        let id_res: Result<(), String> = self.match_token(token_stream, TokenType::Identifier(String::from("a-z")), cst);
+        let id_res: Result<(), Message> = self.match_token(token_stream, TokenType::Identifier(String::from("a-z")), cst);
 
         if id_res.is_ok() {
             cst.move_up();
@@ -703,14 +999,14 @@ impl Parser {
         return id_res;
     }
 
-    fn parse_char_list(&mut self, token_stream: &Vec<Token>, cst: &mut SyntaxTree) -> Result<(), String> {
+    fn parse_char_list(&mut self, token_stream: &Vec<Token>, cst: &mut SyntaxTree) -> Result<(), Message> {
         // Recursion base case
         // We have reached the end of the character list
         if self.peek_and_match_next_token(token_stream, TokenType::Symbol(Symbols::Quote)) {
             // Log that we are parsing a CharList
             nexus_log::log(
                 nexus_log::LogTypes::Debug,
-                nexus_log::LogSources::Parser,
+                nexus_log::LogSource::PARSER,
                 String::from("Parsing CharList (epsilon base case)")
             );
             // Do nothing here because we have reached the end of the string (epsilon case)
@@ -719,19 +1015,19 @@ impl Parser {
             // Log that we are parsing a CharList
             nexus_log::log(
                 nexus_log::LogTypes::Debug,
-                nexus_log::LogSources::Parser,
+                nexus_log::LogSource::PARSER,
                 String::from("Parsing CharList")
             );
     
             // Add the CharList node
             cst.add_node(SyntaxTreeNodeTypes::Branch, SyntaxTreeNode::NonTerminalCst(NonTerminalsCst::CharList));
-            let char_res: Result<(), String> = self.parse_char(token_stream, cst);
+            let char_res: Result<(), Message> = self.parse_char(token_stream, cst);
             if char_res.is_err() {
                 // Break from error
                 return char_res;
             } else {
                 // Otherwise continue for the rest of the string
-                let char_list_res: Result<(), String> = self.parse_char_list(token_stream, cst);
+                let char_list_res: Result<(), Message> = self.parse_char_list(token_stream, cst);
                 if char_list_res.is_ok() {
                     cst.move_up();
                 }
@@ -740,18 +1036,26 @@ impl Parser {
         }
     }
 
-    fn parse_type(&mut self, token_stream: &Vec<Token>, cst: &mut SyntaxTree) -> Result<(), String> {
+    fn parse_type(&mut self, token_stream: &Vec<Token>, cst: &mut SyntaxTree) -> Result<(), Message> {
         // Log that we are parsing a type
         nexus_log::log(
             nexus_log::LogTypes::Debug,
-            nexus_log::LogSources::Parser,
+            nexus_log::LogSource::PARSER,
             String::from("Parsing type")
         );
 
+        // The char type is part of the char extension
+        if self.peek_and_match_next_token(token_stream, TokenType::Keyword(Keywords::Char)) {
+            let feature_res: Result<(), Message> = self.check_feature(token_stream, LanguageFeature::Chars);
+            if feature_res.is_err() {
+                return feature_res;
+            }
+        }
+
         cst.add_node(SyntaxTreeNodeTypes::Branch, SyntaxTreeNode::NonTerminalCst(NonTerminalsCst::Type));
 
         // Try to consume the int token
-        let type_res: Result<(), String> = self.match_token_collection(token_stream, vec![TokenType::Keyword(Keywords::Int), TokenType::Keyword(Keywords::String), TokenType::Keyword(Keywords::Boolean)], cst);
+        let type_res: Result<(), Message> = self.match_token_collection(token_stream, vec![TokenType::Keyword(Keywords::Int), TokenType::Keyword(Keywords::String), TokenType::Keyword(Keywords::Boolean), TokenType::Keyword(Keywords::Char)], cst);
         
         if type_res.is_ok() {
             cst.move_up();
@@ -760,11 +1064,11 @@ impl Parser {
         return type_res;
     }
 
-    fn parse_digit(&mut self, token_stream: &Vec<Token>, cst: &mut SyntaxTree) -> Result<(), String> {
+    fn parse_digit(&mut self, token_stream: &Vec<Token>, cst: &mut SyntaxTree) -> Result<(), Message> {
         // Log what we are doing
         nexus_log::log(
             nexus_log::LogTypes::Debug,
-            nexus_log::LogSources::Parser,
+            nexus_log::LogSource::PARSER,
             String::from("Parsing digit")
         );
 
@@ -772,7 +1076,7 @@ impl Parser {
         cst.add_node(SyntaxTreeNodeTypes::Branch, SyntaxTreeNode::NonTerminalCst(NonTerminalsCst::Digit));
 
         // Match the token with a digit
-        let digit_res: Result<(), String> = self.match_token(token_stream, TokenType::Digit(0), cst);
+        let digit_res: Result<(), Message> = self.match_token(token_stream, TokenType::Digit(0), cst);
         if digit_res.is_err() {
             return digit_res;
         } else {
@@ -781,7 +1085,7 @@ impl Parser {
         }
     }
 
-    fn parse_char(&mut self, token_stream: &Vec<Token>, cst: &mut SyntaxTree) -> Result<(), String> {
+    fn parse_char(&mut self, token_stream: &Vec<Token>, cst: &mut SyntaxTree) -> Result<(), Message> {
         // Check for the next character's content to have the correct output (space vs char)
         let cur_token: Option<Token> = self.peek_next_token(token_stream);
         if cur_token.is_some() {
@@ -789,7 +1093,7 @@ impl Parser {
                 " " => {
                     nexus_log::log(
                         nexus_log::LogTypes::Debug,
-                        nexus_log::LogSources::Parser,
+                        nexus_log::LogSource::PARSER,
                         String::from("Parsing space")
                     );
 
@@ -800,7 +1104,7 @@ impl Parser {
                     // Log that we are parsing a Char
                     nexus_log::log(
                         nexus_log::LogTypes::Debug,
-                        nexus_log::LogSources::Parser,
+                        nexus_log::LogSource::PARSER,
                         String::from("Parsing char")
                     );
                     cst.add_node(SyntaxTreeNodeTypes::Branch, SyntaxTreeNode::NonTerminalCst(NonTerminalsCst::Char));
@@ -809,7 +1113,7 @@ impl Parser {
         }
 
         // Make sure we have a character token here
-        let char_res: Result<(), String> = self.match_token(token_stream, TokenType::Char(String::from("a-z or space")), cst);
+        let char_res: Result<(), Message> = self.match_token(token_stream, TokenType::Char(String::from("a-z or space")), cst);
 
         if char_res.is_ok() {
             cst.move_up();
@@ -818,31 +1122,39 @@ impl Parser {
         return char_res;
     }
 
-    fn parse_bool_op(&mut self, token_stream: &Vec<Token>, cst: &mut SyntaxTree) -> Result<(), String> {
+    fn parse_bool_op(&mut self, token_stream: &Vec<Token>, cst: &mut SyntaxTree) -> Result<(), Message> {
         // Log that we are parsing a boolean operator
         nexus_log::log(
             nexus_log::LogTypes::Debug,
-            nexus_log::LogSources::Parser,
+            nexus_log::LogSource::PARSER,
             String::from("Parsing boolop")
         );
 
         cst.add_node(SyntaxTreeNodeTypes::Branch, SyntaxTreeNode::NonTerminalCst(NonTerminalsCst::BoolOp));
 
         // Try to consume the token
-        let bool_op_res: Result<(), String> = self.match_token_collection(token_stream, vec![TokenType::Symbol(Symbols::EqOp), TokenType::Symbol(Symbols::NeqOp)], cst);
+        let bool_op_res: Result<(), Message> = self.match_token_collection(token_stream, vec![TokenType::Symbol(Symbols::EqOp), TokenType::Symbol(Symbols::NeqOp)], cst);
 
         if bool_op_res.is_ok() {
             cst.move_up();
+            return bool_op_res;
         }
-        
-        return bool_op_res;
+
+        // = is usually meant to be ==, so it gets its own error and the fix of adding another = after it
+        return match self.peek_next_token(token_stream) {
+            Some(token) if token.token_type.eq(&TokenType::Symbol(Symbols::AssignmentOp)) => Err(
+                Message::new(MessageCode::AssignmentInCondition, vec![("position", token.position.to_message_param())])
+                    .with_fix(QuickFix::new("Replace = with ==", token.position, "="))
+            ),
+            _ => bool_op_res
+        };
     }
 
-    fn parse_bool_val(&mut self, token_stream: &Vec<Token>, cst: &mut SyntaxTree) -> Result<(), String> {
+    fn parse_bool_val(&mut self, token_stream: &Vec<Token>, cst: &mut SyntaxTree) -> Result<(), Message> {
         // Log that we are parsing a boolean operator
         nexus_log::log(
             nexus_log::LogTypes::Debug,
-            nexus_log::LogSources::Parser,
+            nexus_log::LogSource::PARSER,
             String::from("Parsing boolval")
         );
 
@@ -850,7 +1162,7 @@ impl Parser {
         cst.add_node(SyntaxTreeNodeTypes::Branch, SyntaxTreeNode::NonTerminalCst(NonTerminalsCst::BoolVal));
 
         // Attempt to consume the token
-        let bool_val_res: Result<(), String> = self.match_token_collection(token_stream, vec![TokenType::Keyword(Keywords::False), TokenType::Keyword(Keywords::True)], cst);
+        let bool_val_res: Result<(), Message> = self.match_token_collection(token_stream, vec![TokenType::Keyword(Keywords::False), TokenType::Keyword(Keywords::True)], cst);
 
         if bool_val_res.is_ok() {
             // Move up if appropriate to do so
@@ -860,18 +1172,18 @@ impl Parser {
         return bool_val_res;
     }
 
-    fn parse_int_op(&mut self, token_stream: &Vec<Token>, cst: &mut SyntaxTree) -> Result<(), String> {
+    fn parse_int_op(&mut self, token_stream: &Vec<Token>, cst: &mut SyntaxTree) -> Result<(), Message> {
         // Log that we are parsing an integer operator
         nexus_log::log(
             nexus_log::LogTypes::Debug,
-            nexus_log::LogSources::Parser,
+            nexus_log::LogSource::PARSER,
             String::from("Parsing intop")
         );
 
         cst.add_node(SyntaxTreeNodeTypes::Branch, SyntaxTreeNode::NonTerminalCst(NonTerminalsCst::IntOp));
 
         // Match the token or get the error
-        let res: Result<(), String> = self.match_token(token_stream, TokenType::Symbol(Symbols::AdditionOp), cst);
+        let res: Result<(), Message> = self.match_token(token_stream, TokenType::Symbol(Symbols::AdditionOp), cst);
 
         // Move up
         if res.is_ok() {
@@ -881,6 +1193,119 @@ impl Parser {
         return res;
     }
 
+    // Function to find the right paren that closes the parentheses the parser is currently in
+    // The search stops at braces and the end of the program because the statement cannot go past them
+    fn find_closing_paren(&self, token_stream: &Vec<Token>) -> Option<usize> {
+        let mut depth: usize = 0;
+        for (token_index, token) in token_stream.iter().enumerate().skip(self.cur_token_index) {
+            match &token.token_type {
+                TokenType::Symbol(Symbols::LParen) => depth += 1,
+                TokenType::Symbol(Symbols::RParen) if depth == 0 => return Some(token_index),
+                TokenType::Symbol(Symbols::RParen) => depth -= 1,
+                TokenType::Symbol(Symbols::LBrace) | TokenType::Symbol(Symbols::RBrace) | TokenType::Symbol(Symbols::EOP) => return None,
+                _ => {}
+            }
+        }
+        return None;
+    }
+
+    // Function to check if the string literal or id that starts at start_index is followed by [ to index into it
+    pub fn is_index_expression(token_stream: &Vec<Token>, start_index: usize) -> bool {
+        let mut bracket_index: usize = start_index + 1;
+        if let Some(TokenType::Symbol(Symbols::Quote)) = token_stream.get(start_index).map(|token| &token.token_type) {
+            // Skip over the characters of the string and its close quote
+            while let Some(TokenType::Char(_)) = token_stream.get(bracket_index).map(|token| &token.token_type) {
+                bracket_index += 1;
+            }
+            bracket_index += 1;
+        }
+        return matches!(token_stream.get(bracket_index).map(|token| &token.token_type), Some(TokenType::Symbol(Symbols::LBracket)));
+    }
+
+    // Function to get the index of the close paren if the open paren at lparen_index groups an integer expression
+    // Otherwise it is a boolean expression, which is the case when it has a boolean operator that is not in any inner parentheses
+    pub fn find_grouped_int_expression_end(token_stream: &Vec<Token>, lparen_index: usize) -> Option<usize> {
+        // An integer expression starts with a digit or another group
+        match token_stream.get(lparen_index + 1).map(|token| &token.token_type) {
+            Some(TokenType::Digit(_)) | Some(TokenType::Symbol(Symbols::LParen)) => {},
+            _ => return None
+        }
+
+        let mut depth: usize = 0;
+        for (token_index, token) in token_stream.iter().enumerate().skip(lparen_index + 1) {
+            match &token.token_type {
+                TokenType::Symbol(Symbols::LParen) => depth += 1,
+                TokenType::Symbol(Symbols::RParen) if depth == 0 => return Some(token_index),
+                TokenType::Symbol(Symbols::RParen) => depth -= 1,
+                TokenType::Symbol(Symbols::EqOp) | TokenType::Symbol(Symbols::NeqOp) if depth == 0 => return None,
+                TokenType::Symbol(Symbols::LBrace) | TokenType::Symbol(Symbols::RBrace) | TokenType::Symbol(Symbols::EOP) => return None,
+                _ => {}
+            }
+        }
+        return None;
+    }
+
+    // Function to add the fix of inserting a right brace if the block runs into the end of the program, which is right before the $
+    fn add_missing_brace_fix(&mut self, token_stream: &Vec<Token>, message: Message) -> Message {
+        return match self.peek_next_token(token_stream) {
+            Some(token) if token.token_type.eq(&TokenType::Symbol(Symbols::EOP)) => message.with_fix(QuickFix::new("Insert }", token.position, "}")),
+            None => message.with_fix(QuickFix::new("Insert }", self.get_end_position(token_stream), "}")),
+            _ => message
+        };
+    }
+
+    // Function to get the position right after the last token, which is where the program ended
+    fn get_end_position(&self, token_stream: &Vec<Token>) -> Position {
+        return token_stream.last().map(|token| token.get_span().end).unwrap_or(Position::new(1, 1));
+    }
+
+    // Function to log an error that stopped the parse and keep count of it
+    // Tokens are not consumed when they do not match, so the next token is the one the error is about
+    fn report_error(&mut self, token_stream: &Vec<Token>, message: Message) {
+        let position: Position = match self.peek_next_token(token_stream) {
+            Some(token) => token.position,
+            // The program ended early, so the error is right after the last token
+            None => self.get_end_position(token_stream)
+        };
+        self.report_error_at(position, message);
+    }
+
+    // Function to log an error at the position and keep count of it
+    fn report_error_at(&mut self, position: Position, message: Message) {
+        nexus_log::log_message(
+            nexus_log::LogTypes::Error,
+            nexus_log::LogSource::PARSER,
+            position,
+            &message
+        );
+        self.num_errors += 1;
+    }
+
+    // Function to get the error for a grammar extension that starts at the next token if the language profile does not allow it
+    fn check_feature(&mut self, token_stream: &Vec<Token>, feature: LanguageFeature) -> Result<(), Message> {
+        if self.language_profile.allows(feature) {
+            return Ok(());
+        }
+        let position: Position = match self.peek_next_token(token_stream) {
+            Some(token) => token.position,
+            None => self.get_end_position(token_stream)
+        };
+        return Err(Message::new(MessageCode::GrammarNotInProfile, vec![
+            ("position", position.to_message_param()),
+            ("feature", String::from(feature.get_name())),
+            ("profile", self.language_profile.preset.to_string())
+        ]));
+    }
+
+    // Function to get the error for a token that is not one of the expected tokens
+    fn unexpected_token(&self, cur_token: &Token, expected: String) -> Message {
+        return Message::new(MessageCode::UnexpectedToken, vec![
+            ("position", cur_token.position.to_message_param()),
+            ("found", format!("{:?}", cur_token.token_type)),
+            ("expected", expected)
+        ]);
+    }
+
     fn peek_next_token(&mut self, token_stream: &Vec<Token>) -> Option<Token> {
         // Make sure we are in-bounds
         if self.cur_token_index < token_stream.len() {
@@ -0,0 +1,280 @@
+use serde::Serialize;
+use wasm_bindgen::prelude::*;
+
+use crate::util::{nexus_log::{self, LogRecord}, target::{Target, TargetConfig}, position::Position};
+use crate::nexus::{token::{Token, TokenJson, TokenType, Keywords}, parser::Parser, semantic_analyzer::{SemanticAnalyzer, SemanticAnalysisResult}};
+use crate::nexus::syntax_tree::{SyntaxTree, SyntaxTreeTypes, SyntaxTreeJson, SyntaxTreeJsonNode};
+use crate::nexus::syntax_tree_node::{SyntaxTreeNode, SyntaxTreeNodeTypes, NonTerminalsAst};
+use crate::nexus::symbol_table::SymbolTableJsonEntry;
+use crate::nexus::code_generator_6502::CodeGenerator6502;
+use crate::nexus::code_generator_riscv::CodeGeneratorRiscV;
+
+// The result of running a single phase
+#[derive (Debug, Serialize)]
+#[serde (rename_all = "camelCase")]
+pub struct PhaseResult {
+    pub success: bool,
+
+    // The CST, the analyzed AST, or the generated code, depending on the phase
+    #[serde (skip_serializing_if = "Option::is_none")]
+    pub output: Option<serde_json::Value>,
+
+    // The errors and warnings of the phase
+    pub diagnostics: Vec<LogRecord>
+}
+
+// Function to parse a JSON array of tokens, such as [{ "kind": "Symbol", "text": "{", "position": [1, 1] }, ...]
+// The output is the CST in the same format as getCstJson
+#[wasm_bindgen(js_name = "parseTokens")]
+pub fn parse_tokens(tokens_json: &str) -> Result<String, JsValue> {
+    let token_jsons: Vec<TokenJson> = serde_json::from_str(tokens_json)
+        .map_err(|err| JsValue::from_str(format!("Invalid tokens: {}", err).as_str()))?;
+    if token_jsons.len() == 0 {
+        return Err(JsValue::from_str("There are no tokens to parse"));
+    }
+
+    let mut token_stream: Vec<Token> = Vec::new();
+    for token_json in token_jsons.iter() {
+        token_stream.push(Token::from_json(token_json).map_err(|message| JsValue::from_str(&message))?);
+    }
+
+    nexus_log::start_capture();
+    let mut parser: Parser = Parser::new();
+    let parse_res: Result<SyntaxTree, ()> = parser.parse_program(&token_stream);
+    let diagnostics: Vec<LogRecord> = nexus_log::stop_capture();
+
+    let phase_res: PhaseResult = PhaseResult {
+        success: parse_res.is_ok(),
+        output: parse_res.ok().map(|cst| serde_json::from_str(&cst.to_json()).unwrap()),
+        diagnostics: diagnostics
+    };
+    return Ok(serde_json::to_string(&phase_res).expect("Should be able to serialize the phase result"));
+}
+
+// Function to run semantic analysis on an AST in the same format as getAstJson, where only the root is needed
+// The target is "6502" (the default) or "RISC-V" because it changes the largest integer
+// The output is the analyzed AST with its symbol table in the same format as getAstJson
+#[wasm_bindgen(js_name = "analyzeAst")]
+pub fn analyze_ast(ast_json: &str, target: Option<String>) -> Result<String, JsValue> {
+    let target_config: TargetConfig = get_target_config(target.as_deref().unwrap_or("6502"))?;
+    let ast: SyntaxTree = ast_from_json(ast_json)?;
+
+    nexus_log::start_capture();
+    let mut semantic_analyzer: SemanticAnalyzer = SemanticAnalyzer::new(target_config);
+    let semantic_analysis_res: SemanticAnalysisResult = semantic_analyzer.analyze_program(ast);
+    let diagnostics: Vec<LogRecord> = nexus_log::stop_capture();
+
+    let phase_res: PhaseResult = PhaseResult {
+        success: semantic_analysis_res.is_successful(),
+        output: Some(serde_json::from_str(&semantic_analysis_res.to_json()).unwrap()),
+        diagnostics: diagnostics
+    };
+    return Ok(serde_json::to_string(&phase_res).expect("Should be able to serialize the phase result"));
+}
+
+// Function to generate code for an AST and its symbol table, which is the symbolTable of getAstJson
+// The AST is analyzed again to get the types and scopes, and the symbol table has to match the one it builds
+// The output is the hex image (6502) or assembly (RISC-V)
+#[wasm_bindgen(js_name = "generateFromAst")]
+pub fn generate_from_ast(ast_json: &str, symbols_json: &str, target: &str) -> Result<String, JsValue> {
+    let target_config: TargetConfig = get_target_config(target)?;
+    let ast: SyntaxTree = ast_from_json(ast_json)?;
+    let symbols: Vec<SymbolTableJsonEntry> = serde_json::from_str(symbols_json)
+        .map_err(|err| JsValue::from_str(format!("Invalid symbol table: {}", err).as_str()))?;
+
+    nexus_log::start_capture();
+    let mut semantic_analyzer: SemanticAnalyzer = SemanticAnalyzer::new(target_config.to_owned());
+    let mut semantic_analysis_res: SemanticAnalysisResult = semantic_analyzer.analyze_program(ast);
+    if !semantic_analysis_res.is_successful() {
+        let phase_res: PhaseResult = PhaseResult {
+            success: false,
+            output: None,
+            diagnostics: nexus_log::stop_capture()
+        };
+        return Ok(serde_json::to_string(&phase_res).expect("Should be able to serialize the phase result"));
+    }
+
+    // Code generation allocates the variables from the symbol table, so a different one would not match the AST
+    let symbols_res: Result<(), String> = check_symbols(&symbols, &semantic_analysis_res.symbol_table.get_json_entries());
+    if symbols_res.is_err() {
+        nexus_log::stop_capture();
+        return Err(JsValue::from_str(&symbols_res.unwrap_err()));
+    }
+
+    let code: Option<String> = match target_config.target {
+        Target::Target6502 => {
+            let mut code_generator: CodeGenerator6502 = CodeGenerator6502::new();
+            match code_generator.generate_image(&mut semantic_analysis_res) {
+                true => Some(code_generator.get_image_hex()),
+                false => None
+            }
+        },
+        Target::TargetRiscV => {
+            let mut code_generator: CodeGeneratorRiscV = CodeGeneratorRiscV::new();
+            match code_generator.generate_assembly(&mut semantic_analysis_res) {
+                true => Some(code_generator.get_assembly()),
+                false => None
+            }
+        }
+    };
+
+    let phase_res: PhaseResult = PhaseResult {
+        success: code.is_some(),
+        output: code.map(|code_text| serde_json::Value::String(code_text)),
+        diagnostics: nexus_log::stop_capture()
+    };
+    return Ok(serde_json::to_string(&phase_res).expect("Should be able to serialize the phase result"));
+}
+
+fn get_target_config(target: &str) -> Result<TargetConfig, JsValue> {
+    return match target {
+        "6502" => Ok(TargetConfig::new(Target::Target6502)),
+        "RISC-V" => Ok(TargetConfig::new(Target::TargetRiscV)),
+        _ => Err(JsValue::from_str(format!("Invalid target [ {} ], which must be 6502 or RISC-V", target).as_str()))
+    };
+}
+
+// Function to make sure every given symbol has the same id, type, and scope as the symbol table from analysis
+fn check_symbols(given_symbols: &Vec<SymbolTableJsonEntry>, derived_symbols: &Vec<SymbolTableJsonEntry>) -> Result<(), String> {
+    let is_same = |given: &SymbolTableJsonEntry, derived: &SymbolTableJsonEntry| {
+        given.id.eq(&derived.id) && given.symbol_type.eq(&derived.symbol_type) && given.scope == derived.scope
+    };
+
+    for given in given_symbols.iter() {
+        if !derived_symbols.iter().any(|derived| is_same(given, derived)) {
+            return Err(format!("Symbol [ {} ] of type [ {} ] in scope {} is not declared in the AST", given.id, given.symbol_type, given.scope));
+        }
+    }
+    for derived in derived_symbols.iter() {
+        if !given_symbols.iter().any(|given| is_same(given, derived)) {
+            return Err(format!("Symbol [ {} ] of type [ {} ] in scope {} is missing from the symbol table", derived.id, derived.symbol_type, derived.scope));
+        }
+    }
+    return Ok(());
+}
+
+// Function to build an AST from its JSON, making sure it has the shape the parser would have made
+pub fn ast_from_json(ast_json: &str) -> Result<SyntaxTree, JsValue> {
+    let tree_json: SyntaxTreeJson = serde_json::from_str(ast_json)
+        .map_err(|err| JsValue::from_str(format!("Invalid AST: {}", err).as_str()))?;
+
+    let mut ast: SyntaxTree = SyntaxTree::new(SyntaxTreeTypes::Ast);
+    match tree_json.root {
+        Some(root) => {
+            if get_ast_non_terminal(&root).is_none() || !root.label.eq("Block") {
+                return Err(JsValue::from_str(format!("Invalid AST root [ {} ]; The root has to be a Block", root.label).as_str()));
+            }
+            add_ast_node(&mut ast, &root).map_err(|message| JsValue::from_str(&message))?;
+        },
+        None => return Err(JsValue::from_str("The AST does not have a root"))
+    }
+    return Ok(ast);
+}
+
+// Function to add the node and its children to the AST after checking its children
+fn add_ast_node(ast: &mut SyntaxTree, node: &SyntaxTreeJsonNode) -> Result<(), String> {
+    match get_ast_non_terminal(node) {
+        Some(non_terminal) => {
+            check_children(&non_terminal, node)?;
+            ast.add_node(SyntaxTreeNodeTypes::Branch, SyntaxTreeNode::NonTerminalAst(non_terminal));
+            for child in node.children.iter() {
+                add_ast_node(ast, child)?;
+            }
+            ast.move_up();
+        },
+        None => {
+            let token: Token = get_ast_token(node)?;
+            if node.children.len() > 0 {
+                return Err(format!("Invalid AST terminal [ {} ] at {}; Terminals cannot have children", token.text, token.position));
+            }
+            ast.add_node(SyntaxTreeNodeTypes::Leaf, SyntaxTreeNode::Terminal(token));
+        }
+    }
+    return Ok(());
+}
+
+// Function to make sure the children are the ones the grammar allows, in source order
+fn check_children(non_terminal: &NonTerminalsAst, node: &SyntaxTreeJsonNode) -> Result<(), String> {
+    let children: &Vec<SyntaxTreeJsonNode> = &node.children;
+    let (expected_children, is_valid): (&str, bool) = match non_terminal {
+        NonTerminalsAst::Block => ("only statements", children.iter().all(|child| is_statement(child))),
+        NonTerminalsAst::VarDecl => ("[type, id]", children.len() == 2 && is_type_keyword(&children[0]) && is_token_kind(&children[1], "Identifier")),
+        NonTerminalsAst::Assign => ("[id, expression]", children.len() == 2 && is_token_kind(&children[0], "Identifier") && is_expression(&children[1])),
+        NonTerminalsAst::Print => ("[expression]", children.len() == 1 && is_expression(&children[0])),
+        NonTerminalsAst::While | NonTerminalsAst::If => ("[boolean expression, Block]", children.len() == 2 && is_boolean_expression(&children[0]) && children[1].label.eq("Block") && get_ast_non_terminal(&children[1]).is_some()),
+        NonTerminalsAst::Add => ("[digit or Add, expression]", children.len() == 2 && (is_token_kind(&children[0], "Digit") || matches!(get_ast_non_terminal(&children[0]), Some(NonTerminalsAst::Add))) && is_expression(&children[1])),
+        NonTerminalsAst::IsEq | NonTerminalsAst::NotEq => ("[expression, expression]", children.len() == 2 && children.iter().all(|child| is_expression(child))),
+        NonTerminalsAst::Index => ("[string or id, expression]", children.len() == 2 && (is_token_kind(&children[0], "Char") || is_token_kind(&children[0], "Identifier")) && is_expression(&children[1])),
+        NonTerminalsAst::Len => ("[expression]", children.len() == 1 && is_expression(&children[0]))
+    };
+
+    if !is_valid {
+        let child_labels: Vec<&str> = children.iter().map(|child| child.label.as_str()).collect();
+        return Err(format!("Invalid children [{}] of AST node [ {} ] with id {}; Expected {}", child_labels.join(", "), node.label, node.id, expected_children));
+    }
+    return Ok(());
+}
+
+fn get_ast_non_terminal(node: &SyntaxTreeJsonNode) -> Option<NonTerminalsAst> {
+    if !node.kind.eq("nonterminal") {
+        return None;
+    }
+    return match node.label.as_str() {
+        "Block" => Some(NonTerminalsAst::Block),
+        "VarDecl" => Some(NonTerminalsAst::VarDecl),
+        "Assign" => Some(NonTerminalsAst::Assign),
+        "Print" => Some(NonTerminalsAst::Print),
+        "While" => Some(NonTerminalsAst::While),
+        "If" => Some(NonTerminalsAst::If),
+        "Add" => Some(NonTerminalsAst::Add),
+        "IsEq" => Some(NonTerminalsAst::IsEq),
+        "NotEq" => Some(NonTerminalsAst::NotEq),
+        "Index" => Some(NonTerminalsAst::Index),
+        "Len" => Some(NonTerminalsAst::Len),
+        _ => None
+    };
+}
+
+// Function to create the token of a terminal, which needs its kind and position
+fn get_ast_token(node: &SyntaxTreeJsonNode) -> Result<Token, String> {
+    if !node.kind.eq("terminal") {
+        return Err(format!("Invalid AST node [ {} ] of kind [ {} ]; Nonterminals are Block, VarDecl, Assign, Print, While, If, Add, IsEq, NotEq, Index, and Len", node.label, node.kind));
+    }
+    if node.token_kind.is_none() || node.position.is_none() {
+        return Err(format!("Invalid AST terminal [ {} ] with id {}; Terminals need a tokenKind and a position", node.label, node.id));
+    }
+    let token_type: TokenType = TokenType::from_kind(node.token_kind.as_ref().unwrap(), &node.label)?;
+    let position: Position = node.position.unwrap();
+    return Ok(Token::new(token_type, node.label.to_owned(), position.line, position.col));
+}
+
+fn is_token_kind(node: &SyntaxTreeJsonNode, token_kind: &str) -> bool {
+    return node.kind.eq("terminal") && node.token_kind.as_deref() == Some(token_kind);
+}
+
+fn is_type_keyword(node: &SyntaxTreeJsonNode) -> bool {
+    return is_token_kind(node, "Keyword") && ["int", "string", "boolean", "char"].contains(&node.label.as_str());
+}
+
+fn is_statement(node: &SyntaxTreeJsonNode) -> bool {
+    return match get_ast_non_terminal(node) {
+        Some(NonTerminalsAst::Add) | Some(NonTerminalsAst::IsEq) | Some(NonTerminalsAst::NotEq) | Some(NonTerminalsAst::Index) | Some(NonTerminalsAst::Len) | None => false,
+        Some(_) => true
+    };
+}
+
+fn is_boolean_expression(node: &SyntaxTreeJsonNode) -> bool {
+    return match get_ast_non_terminal(node) {
+        Some(NonTerminalsAst::IsEq) | Some(NonTerminalsAst::NotEq) => true,
+        Some(_) => false,
+        None => is_token_kind(node, "Keyword") && matches!(TokenType::from_kind("Keyword", &node.label), Ok(TokenType::Keyword(Keywords::True)) | Ok(TokenType::Keyword(Keywords::False)))
+    };
+}
+
+fn is_expression(node: &SyntaxTreeJsonNode) -> bool {
+    return match get_ast_non_terminal(node) {
+        Some(NonTerminalsAst::Add) | Some(NonTerminalsAst::Index) | Some(NonTerminalsAst::Len) => true,
+        Some(_) => is_boolean_expression(node),
+        None => is_boolean_expression(node) || is_token_kind(node, "Digit") || is_token_kind(node, "Char") || is_token_kind(node, "CharLiteral") || is_token_kind(node, "Identifier")
+    };
+}
@@ -0,0 +1,181 @@
+#[cfg(not(target_arch = "wasm32"))]
+use std::thread;
+
+use crate::util::{nexus_log::{self, RecordedLog}, build_info::BuildInfo, course_stage::CourseStage, language_profile::LanguageProfile, target::{Target, TargetConfig}};
+use crate::nexus::{token::Token, semantic_analyzer::{SemanticAnalyzer, SemanticAnalysisResult}, lint::Linter, syntax_tree::SyntaxTree, interpreter::{Interpreter, ProgramRun}};
+use crate::nexus::code_generator_6502::CodeGenerator6502;
+use crate::nexus::code_generator_riscv::CodeGeneratorRiscV;
+
+// The options that every program is compiled with after it is parsed
+#[derive (Debug, Clone)]
+pub struct CompileOptions {
+    pub target_config: TargetConfig,
+    pub optimize_size: bool,
+
+    // Whether the RISC-V assembly has a comment where each scope starts and ends, and the 6502 debug information has the code of each scope
+    pub scope_comments: bool,
+
+    // Whether each lint is on, where a lint that is not here is on
+    pub lints: Vec<(&'static str, bool)>,
+
+    pub build_info: Option<BuildInfo>,
+
+    // Whether the programs are run with the interpreter instead of generating code for them
+    pub is_interpreted: bool,
+
+    // The last phase that is run, where programs are only analyzed if the class has not reached code generation
+    pub course_stage: CourseStage,
+
+    // What identifiers can be, which the driver gives to its lexer and parser before the programs get here
+    pub language_profile: LanguageProfile
+}
+
+impl CompileOptions {
+    pub fn new(target_config: TargetConfig) -> Self {
+        return CompileOptions {
+            target_config: target_config,
+            optimize_size: false,
+            scope_comments: false,
+            lints: Vec::new(),
+            build_info: None,
+            is_interpreted: false,
+            course_stage: CourseStage::default(),
+            language_profile: LanguageProfile::new()
+        };
+    }
+}
+
+// A program that was lexed and parsed, which is all the rest of the phases need
+#[derive (Debug)]
+pub struct ParsedProgram {
+    pub program_number: u32,
+    pub token_stream: Vec<Token>
+}
+
+// The code generator of the target, which is kept so the driver can show the code and read from it
+pub enum TargetCodeGenerator {
+    Target6502(CodeGenerator6502),
+    TargetRiscV(CodeGeneratorRiscV)
+}
+
+// Everything a program produced after it was parsed
+// The logs of each step are recorded so the driver can show them in order with its own logs
+pub struct ProgramOutput {
+    pub program_number: u32,
+    pub ast_logs: Vec<RecordedLog>,
+    pub semantic_analysis_res: SemanticAnalysisResult,
+
+    // The logs of semantic analysis and the lints
+    pub analysis_logs: Vec<RecordedLog>,
+
+    // The code generator and whether it was successful, which is None if semantic analysis failed, the program was interpreted,
+    // or the course stage ends before code generation
+    pub code_gen: Option<(TargetCodeGenerator, bool)>,
+
+    // What the interpreter printed, which is only there if the program was interpreted
+    pub program_run: Option<ProgramRun>,
+
+    // The logs of code generation or of the interpreter
+    pub code_gen_logs: Vec<RecordedLog>
+}
+
+// Function to compile the parsed programs, giving back their outputs in the same order as the programs
+// Natively the programs are split between threads, but wasm only has the one thread so they are compiled one at a time
+#[cfg(not(target_arch = "wasm32"))]
+pub fn compile_programs(programs: &Vec<ParsedProgram>, options: &CompileOptions) -> Vec<ProgramOutput> {
+    let num_threads: usize = thread::available_parallelism().map(|num| num.get()).unwrap_or(1).min(programs.len());
+    if num_threads <= 1 {
+        return programs.iter().map(|program| compile_program(program, options)).collect();
+    }
+
+    // Each thread gets the next group of programs, so joining the threads in order keeps the programs in order
+    let chunk_size: usize = programs.len().div_ceil(num_threads);
+    return thread::scope(|scope| {
+        let handles: Vec<thread::ScopedJoinHandle<Vec<ProgramOutput>>> = programs.chunks(chunk_size)
+            .map(|chunk| scope.spawn(move || chunk.iter().map(|program| compile_program(program, options)).collect()))
+            .collect();
+        handles.into_iter()
+            .flat_map(|handle| handle.join().expect("Should be able to join the compiler thread"))
+            .collect()
+    });
+}
+
+#[cfg(target_arch = "wasm32")]
+pub fn compile_programs(programs: &Vec<ParsedProgram>, options: &CompileOptions) -> Vec<ProgramOutput> {
+    return programs.iter().map(|program| compile_program(program, options)).collect();
+}
+
+// Function to generate the AST, analyze it, and generate the code of a parsed program
+// Every phase is made just for this program, so no program depends on another and any thread can compile it
+pub fn compile_program(program: &ParsedProgram, options: &CompileOptions) -> ProgramOutput {
+    let mut semantic_analyzer: SemanticAnalyzer = SemanticAnalyzer::new(options.target_config.to_owned());
+
+    nexus_log::start_recording();
+    let ast: SyntaxTree = semantic_analyzer.generate_ast(&program.token_stream);
+    let ast_logs: Vec<RecordedLog> = nexus_log::stop_recording();
+
+    nexus_log::start_recording();
+    let mut semantic_analysis_res: SemanticAnalysisResult = semantic_analyzer.analyze_program(ast);
+
+    // The lints need the derived types, so they only run if analysis was successful
+    if semantic_analysis_res.is_successful() {
+        let mut linter: Linter = Linter::new();
+        for (lint_name, is_enabled) in options.lints.iter() {
+            linter.set_enabled(lint_name, *is_enabled);
+        }
+        linter.run(&mut semantic_analysis_res, &options.target_config);
+    }
+    let analysis_logs: Vec<RecordedLog> = nexus_log::stop_recording();
+
+    let mut code_gen: Option<(TargetCodeGenerator, bool)> = None;
+    let mut program_run: Option<ProgramRun> = None;
+    let mut code_gen_logs: Vec<RecordedLog> = Vec::new();
+    if semantic_analysis_res.is_successful() && options.course_stage.includes(CourseStage::CodeGeneration) {
+        nexus_log::start_recording();
+        if options.is_interpreted {
+            // The interpreter runs the AST directly, so there is no code to generate
+            let mut interpreter: Interpreter = Interpreter::new(&options.target_config);
+            interpreter.set_log_prints(true);
+            program_run = Some(interpreter.run_program(&semantic_analysis_res.ast));
+        } else {
+            code_gen = Some(generate_code(&mut semantic_analysis_res, options));
+        }
+        code_gen_logs = nexus_log::stop_recording();
+    }
+
+    return ProgramOutput {
+        program_number: program.program_number,
+        ast_logs: ast_logs,
+        semantic_analysis_res: semantic_analysis_res,
+        analysis_logs: analysis_logs,
+        code_gen: code_gen,
+        program_run: program_run,
+        code_gen_logs: code_gen_logs
+    };
+}
+
+fn generate_code(semantic_analysis_res: &mut SemanticAnalysisResult, options: &CompileOptions) -> (TargetCodeGenerator, bool) {
+    return match options.target_config.target {
+        Target::Target6502 => {
+            let mut code_generator: CodeGenerator6502 = CodeGenerator6502::new();
+            code_generator.set_optimize_size(options.optimize_size);
+            code_generator.set_memory_layout(&options.target_config);
+            code_generator.set_scope_comments(options.scope_comments);
+            if let Some(build_info) = &options.build_info {
+                code_generator.set_build_info(build_info.to_owned());
+            }
+            let is_successful: bool = code_generator.generate_code(semantic_analysis_res);
+            (TargetCodeGenerator::Target6502(code_generator), is_successful)
+        },
+        Target::TargetRiscV => {
+            let mut code_generator: CodeGeneratorRiscV = CodeGeneratorRiscV::new();
+            code_generator.set_syscall_convention(options.target_config.syscall_convention);
+            code_generator.set_scope_comments(options.scope_comments);
+            if let Some(build_info) = &options.build_info {
+                code_generator.set_build_info(build_info.to_owned());
+            }
+            let is_successful: bool = code_generator.generate_assembly(semantic_analysis_res);
+            (TargetCodeGenerator::TargetRiscV(code_generator), is_successful)
+        }
+    };
+}
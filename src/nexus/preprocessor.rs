@@ -0,0 +1,240 @@
+use std::collections::HashMap;
+
+use crate::util::{nexus_log, messages::{Message, MessageCode}, language_profile::{LanguageProfile, LanguageFeature}, position::Position};
+use regex::Regex;
+
+// A constant that was replaced with a value of a different length, where the columns start at 1
+#[derive (Debug, Clone)]
+struct Substitution {
+    expanded_col: usize,
+    expanded_len: usize,
+    original_col: usize,
+    original_len: usize
+}
+
+// Maps the columns of the expanded code back to the source code, which are different after a constant is replaced with a longer or shorter value
+#[derive (Debug, Clone, Default)]
+pub struct ColumnMap {
+    // The substitutions on each line from left to right <line, substitutions>
+    substitutions: HashMap<usize, Vec<Substitution>>
+}
+
+impl ColumnMap {
+    // Function to get the position in the source code of the position in the expanded code
+    // Anything inside of a value is at the name of the constant
+    pub fn get_original_position(&self, line: usize, col: usize) -> Position {
+        let mut original_col: usize = col;
+        if let Some(substitutions) = self.substitutions.get(&line) {
+            for substitution in substitutions.iter() {
+                if col < substitution.expanded_col {
+                    break;
+                } else if col < substitution.expanded_col + substitution.expanded_len {
+                    return Position::new(line, substitution.original_col + (col - substitution.expanded_col).min(substitution.original_len - 1));
+                }
+                original_col = col + substitution.original_col + substitution.original_len - substitution.expanded_col - substitution.expanded_len;
+            }
+        }
+        return Position::new(line, original_col);
+    }
+}
+
+// Expands the constants made with `define NAME value` before the source code is lexed
+// Constants are not part of the grammar, so they do not take up any variable storage
+pub struct Preprocessor {
+    // The constants that have been defined so far <name, value>
+    defines: HashMap<String, String>,
+
+    // The regex for a valid constant name
+    name_regex: Regex,
+
+    // The regex for a valid constant value (digit, boolean, or string)
+    value_regex: Regex,
+
+    // Whether constants can be defined at all, since they are not part of the course grammar
+    language_profile: LanguageProfile,
+
+    // Where the columns changed in the last expansion
+    column_map: ColumnMap,
+
+    // The number of errors found while expanding
+    pub num_errors: i32
+}
+
+impl Preprocessor {
+    pub fn new() -> Self {
+        return Preprocessor {
+            defines: HashMap::new(),
+            // Names are uppercase so they can never be confused with ids or keywords
+            name_regex: Regex::new(r"^[A-Z][A-Z_]*$").unwrap(),
+            value_regex: Regex::new(r#"^([0-9]|true|false|"[a-z ]*")$"#).unwrap(),
+            language_profile: LanguageProfile::new(),
+            column_map: ColumnMap::default(),
+            num_errors: 0
+        };
+    }
+
+    // Function to only allow constants if the language profile has them
+    pub fn set_language_profile(&mut self, language_profile: &LanguageProfile) {
+        self.language_profile = language_profile.to_owned();
+    }
+
+    // Function to get where the columns changed in the last expansion, which the lexer uses to give tokens their place in the source code
+    pub fn get_column_map(&self) -> ColumnMap {
+        return self.column_map.to_owned();
+    }
+
+    // Function to expand all of the constants in the source code
+    // Directives are replaced with whitespace so line numbers stay the same
+    pub fn expand_defines(&mut self, source_code: &str) -> Result<String, ()> {
+        self.defines.clear();
+        self.column_map = ColumnMap::default();
+        self.num_errors = 0;
+
+        let mut output: String = String::new();
+
+        // Comments can span multiple lines, so the state is carried over
+        let mut in_comment: bool = false;
+
+        for (line_index, line) in source_code.split('\n').enumerate() {
+            if line_index > 0 {
+                output.push('\n');
+            }
+
+            let trimmed_line: &str = line.trim_start();
+            let is_directive: bool = !in_comment
+                && trimmed_line.starts_with("define")
+                && trimmed_line["define".len()..].starts_with(|c: char| c.is_whitespace());
+
+            if is_directive {
+                self.parse_directive(trimmed_line, Position::new(line_index + 1, line.len() - trimmed_line.len() + 1));
+                output.push_str(" ".repeat(line.len()).as_str());
+            } else {
+                let (substituted_line, substitutions): (String, Vec<Substitution>) = self.substitute_line(line, &mut in_comment);
+                output.push_str(substituted_line.as_str());
+                if substitutions.len() > 0 {
+                    self.column_map.substitutions.insert(line_index + 1, substitutions);
+                }
+            }
+        }
+
+        if self.num_errors == 0 {
+            return Ok(output);
+        } else {
+            nexus_log::log(
+                nexus_log::LogTypes::Error,
+                nexus_log::LogSource::LEXER,
+                format!("Preprocessing failed with {} error(s)", self.num_errors)
+            );
+            return Err(());
+        }
+    }
+
+    // Function to replace the constants from the last expansion in a single line of code, such as an expression
+    pub fn expand_constants(&mut self, code: &str) -> String {
+        let mut in_comment: bool = false;
+        return self.substitute_line(code, &mut in_comment).0;
+    }
+
+    // Function to record the constant from a define directive, which starts at the position
+    fn parse_directive(&mut self, directive: &str, position: Position) {
+        // Split into define, the name, and the rest of the line as the value
+        let mut parts = directive["define".len()..].trim().splitn(2, char::is_whitespace);
+        let name: &str = parts.next().unwrap_or("");
+        let value: &str = parts.next().unwrap_or("").trim();
+
+        let error_res: Option<Message> = if !self.language_profile.allows(LanguageFeature::Constants) {
+            Some(Message::new(MessageCode::ConstantNotInProfile, vec![
+                ("position", position.to_message_param()),
+                ("name", String::from(name)),
+                ("feature", String::from(LanguageFeature::Constants.get_name())),
+                ("profile", self.language_profile.preset.to_string())
+            ]))
+        } else if !self.name_regex.is_match(name) {
+            Some(Message::new(MessageCode::InvalidConstantName, vec![("position", position.to_message_param()), ("name", String::from(name))]))
+        } else if !self.value_regex.is_match(value) {
+            Some(Message::new(MessageCode::InvalidConstantValue, vec![("position", position.to_message_param()), ("value", String::from(value)), ("name", String::from(name))]))
+        } else if self.defines.contains_key(name) {
+            Some(Message::new(MessageCode::ConstantRedefinition, vec![("position", position.to_message_param()), ("name", String::from(name))]))
+        } else {
+            None
+        };
+
+        match error_res {
+            Some(message) => {
+                self.num_errors += 1;
+                nexus_log::log_message(nexus_log::LogTypes::Error, nexus_log::LogSource::LEXER, position, &message);
+            },
+            None => {
+                nexus_log::log(
+                    nexus_log::LogTypes::Debug,
+                    nexus_log::LogSource::LEXER,
+                    format!("Defined constant [ {} ] with value [ {} ] at {}", name, value, position)
+                );
+                self.defines.insert(String::from(name), String::from(value));
+            }
+        }
+    }
+
+    // Function to replace every defined constant in the line that is not in a comment or string
+    // The values that are a different length than their name are also returned so their columns can be mapped back
+    fn substitute_line(&mut self, line: &str, in_comment: &mut bool) -> (String, Vec<Substitution>) {
+        let mut output: String = String::new();
+        let mut substitutions: Vec<Substitution> = Vec::new();
+        let chars: Vec<char> = line.chars().collect();
+
+        // Strings cannot go past the end of the line
+        let mut in_string: bool = false;
+        let mut i: usize = 0;
+        while i < chars.len() {
+            if *in_comment {
+                if chars[i] == '*' && i + 1 < chars.len() && chars[i + 1] == '/' {
+                    *in_comment = false;
+                    output.push_str("*/");
+                    i += 2;
+                } else {
+                    output.push(chars[i]);
+                    i += 1;
+                }
+            } else if in_string {
+                in_string = chars[i] != '"';
+                output.push(chars[i]);
+                i += 1;
+            } else if chars[i] == '/' && i + 1 < chars.len() && chars[i + 1] == '*' {
+                *in_comment = true;
+                output.push_str("/*");
+                i += 2;
+            } else if chars[i] == '"' {
+                in_string = true;
+                output.push(chars[i]);
+                i += 1;
+            } else if chars[i].is_ascii_alphanumeric() || chars[i] == '_' {
+                // Read the whole word so names are only matched in full
+                let word_start: usize = i;
+                while i < chars.len() && (chars[i].is_ascii_alphanumeric() || chars[i] == '_') {
+                    i += 1;
+                }
+                let word: String = chars[word_start..i].iter().collect();
+
+                match self.defines.get(&word) {
+                    Some(value) => {
+                        if value.len() != word.len() {
+                            substitutions.push(Substitution {
+                                expanded_col: output.chars().count() + 1,
+                                expanded_len: value.chars().count(),
+                                original_col: word_start + 1,
+                                original_len: word.len()
+                            });
+                        }
+                        output.push_str(value);
+                    },
+                    None => output.push_str(&word)
+                }
+            } else {
+                output.push(chars[i]);
+                i += 1;
+            }
+        }
+
+        return (output, substitutions);
+    }
+}
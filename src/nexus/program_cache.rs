@@ -0,0 +1,174 @@
+use std::cell::RefCell;
+use std::collections::HashMap;
+use std::collections::hash_map::DefaultHasher;
+use std::hash::{Hash, Hasher};
+
+use crate::nexus::{lexer::Lexer, parser::Parser, syntax_tree::SyntaxTree, token::Token};
+use crate::util::{nexus_log::{self, RecordedLog}, language_profile::LanguageProfile, position::Position};
+
+// A program from the previous compilation with everything the lexer and parser made for it
+#[derive (Debug, Clone)]
+struct CachedProgram {
+    // The length and hash of the source code of the program
+    source_len: usize,
+    source_hash: u64,
+
+    // A program that ran into the end of the code without a $ would be longer if anything was added after it
+    ends_at_eof: bool,
+
+    // The (line, col) where the next program starts
+    end_position: Position,
+
+    lex_res: Result<Vec<Token>, ()>,
+    lex_records: Vec<RecordedLog>,
+
+    // None until the program is parsed
+    parse_res: Option<Result<SyntaxTree, ()>>,
+    parse_records: Vec<RecordedLog>
+}
+
+// The programs are kept by the (line, col) of their first character, which the positions of their tokens depend on
+// Editing a program without adding or removing lines lets every program after it be reused
+#[derive (Debug, Default)]
+struct ProgramCache {
+    // The programs of the previous compilation that have not been used yet
+    previous_programs: HashMap<Position, CachedProgram>,
+
+    // The programs of the current compilation, which are the previous programs for the next compilation
+    cur_programs: HashMap<Position, CachedProgram>,
+
+    // The start of the program being compiled and whether it was reused
+    cur_start: Position,
+    is_cur_reused: bool,
+
+    // The profile the programs were lexed and parsed with, since the same code can have different tokens in another profile
+    language_profile: LanguageProfile
+}
+
+// The cache has to last between compilations, so it lives in thread local storage like the artifacts
+thread_local! {
+    static PROGRAM_CACHE: RefCell<ProgramCache> = RefCell::new(ProgramCache::default());
+}
+
+// Function to make the programs of the last compilation the ones that can be reused
+// Nothing can be reused if the language profile is not the same as the last compilation's
+pub fn start_compilation(language_profile: &LanguageProfile) {
+    PROGRAM_CACHE.with(|program_cache| {
+        let mut cache = program_cache.borrow_mut();
+        cache.previous_programs = std::mem::take(&mut cache.cur_programs);
+        if cache.language_profile.ne(language_profile) {
+            cache.previous_programs.clear();
+            cache.language_profile = language_profile.to_owned();
+        }
+    });
+}
+
+// Function to forget every program, such as when the messages they logged are no longer in the right language
+pub fn clear_cache() {
+    PROGRAM_CACHE.with(|program_cache| *program_cache.borrow_mut() = ProgramCache::default());
+}
+
+// Function to lex the next program, which reuses the tokens and logs of the previous compilation if the program has not changed
+pub fn lex_program(lexer: &mut Lexer) -> Result<Vec<Token>, ()> {
+    // Whitespace between programs is skipped so a program is found by where its code starts, not where the last one ended
+    lexer.skip_whitespace();
+    let (start, start_position): (usize, Position) = lexer.get_cursor();
+    let cached_program: Option<CachedProgram> = PROGRAM_CACHE.with(|program_cache| {
+        program_cache.borrow_mut().previous_programs.remove(&start_position)
+            .filter(|program| is_unchanged(lexer, start, program))
+    });
+    let is_reused: bool = cached_program.is_some();
+
+    let program: CachedProgram = match cached_program {
+        Some(program) => {
+            nexus_log::log(
+                nexus_log::LogTypes::Debug,
+                nexus_log::LogSource::NEXUS,
+                format!("Program starting at {} is unchanged, so it was not lexed or parsed again", start_position)
+            );
+            nexus_log::replay(&program.lex_records);
+            lexer.set_cursor((start + program.source_len, program.end_position));
+            program
+        },
+        None => {
+            // The logs are kept so they can be shown again when the program is reused
+            nexus_log::start_recording();
+            let lex_res: Result<Vec<Token>, ()> = lexer.lex_program();
+            let lex_records: Vec<RecordedLog> = nexus_log::stop_recording();
+            nexus_log::replay(&lex_records);
+
+            let end_cursor: (usize, Position) = lexer.get_cursor();
+            CachedProgram {
+                source_len: end_cursor.0 - start,
+                source_hash: hash_source(lexer.get_source(start, end_cursor.0).unwrap_or("")),
+                ends_at_eof: end_cursor.0 >= lexer.get_source_len(),
+                end_position: end_cursor.1,
+                lex_res: lex_res,
+                lex_records: lex_records,
+                parse_res: None,
+                parse_records: Vec::new()
+            }
+        }
+    };
+
+    let lex_res: Result<Vec<Token>, ()> = program.lex_res.to_owned();
+    PROGRAM_CACHE.with(|program_cache| {
+        let mut cache = program_cache.borrow_mut();
+        cache.cur_start = start_position;
+        cache.is_cur_reused = is_reused;
+        cache.cur_programs.insert(start_position, program);
+    });
+    return lex_res;
+}
+
+// Function to parse the program that was just lexed, which reuses the CST and logs of the previous compilation if the program has not changed
+pub fn parse_program(parser: &mut Parser, token_stream: &Vec<Token>) -> Result<SyntaxTree, ()> {
+    let cached_parse: Option<(Result<SyntaxTree, ()>, Vec<RecordedLog>)> = PROGRAM_CACHE.with(|program_cache| {
+        let cache = program_cache.borrow();
+        cache.cur_programs.get(&cache.cur_start)
+            .and_then(|program| program.parse_res.to_owned().map(|parse_res| (parse_res, program.parse_records.to_owned())))
+    });
+    if let Some((parse_res, parse_records)) = cached_parse {
+        nexus_log::replay(&parse_records);
+        return parse_res;
+    }
+
+    nexus_log::start_recording();
+    let parse_res: Result<SyntaxTree, ()> = parser.parse_program(token_stream);
+    let parse_records: Vec<RecordedLog> = nexus_log::stop_recording();
+    nexus_log::replay(&parse_records);
+
+    PROGRAM_CACHE.with(|program_cache| {
+        let mut cache = program_cache.borrow_mut();
+        let cur_start: Position = cache.cur_start;
+        if let Some(program) = cache.cur_programs.get_mut(&cur_start) {
+            program.parse_res = Some(parse_res.to_owned());
+            program.parse_records = parse_records;
+        }
+    });
+    return parse_res;
+}
+
+// Function to check if the program that was just lexed came from the cache
+pub fn is_reused() -> bool {
+    return PROGRAM_CACHE.with(|program_cache| program_cache.borrow().is_cur_reused);
+}
+
+// Function to check if the program at the position has the same source code as before
+fn is_unchanged(lexer: &Lexer, start: usize, program: &CachedProgram) -> bool {
+    let end: usize = start + program.source_len;
+    if program.ends_at_eof && end != lexer.get_source_len() {
+        return false;
+    }
+
+    return match lexer.get_source(start, end) {
+        Some(source) => hash_source(source) == program.source_hash,
+        None => false
+    };
+}
+
+fn hash_source(source: &str) -> u64 {
+    let mut hasher: DefaultHasher = DefaultHasher::new();
+    source.hash(&mut hasher);
+    return hasher.finish();
+}
@@ -0,0 +1,158 @@
+use std::cell::RefCell;
+
+use serde::Serialize;
+use wasm_bindgen::prelude::*;
+
+use crate::util::nexus_log;
+
+// The phases each program goes through, in order
+#[derive (Debug, Clone, Copy, PartialEq, Serialize)]
+pub enum ProgressPhase {
+    Lex,
+    Parse,
+    SemanticAnalysis,
+    CodeGeneration
+}
+
+impl ProgressPhase {
+    // The number of phases in a program that compiles successfully
+    const NUM_PHASES: u32 = 4;
+
+    // Function to get how many phases come before this one
+    fn get_index(&self) -> u32 {
+        return match self {
+            ProgressPhase::Lex => 0,
+            ProgressPhase::Parse => 1,
+            ProgressPhase::SemanticAnalysis => 2,
+            ProgressPhase::CodeGeneration => 3
+        };
+    }
+}
+
+// The kinds of events sent while compiling
+#[derive (Debug, Clone, Copy, PartialEq, Serialize)]
+#[serde (rename_all = "camelCase")]
+pub enum ProgressKind {
+    CompilationStarted,
+    PhaseStarted,
+    PhaseFinished,
+    CompilationFinished
+}
+
+// An event that is sent to the progress callback, such as { "kind": "phaseFinished", "programNumber": 2, "numPrograms": 3, "phase": "Parse", "success": true, "percent": 50.0 }
+#[derive (Debug, Clone, Serialize)]
+#[serde (rename_all = "camelCase")]
+pub struct ProgressEvent {
+    pub kind: ProgressKind,
+
+    // The program the phase is for, starting at 1
+    #[serde (skip_serializing_if = "Option::is_none")]
+    pub program_number: Option<u32>,
+
+    pub num_programs: u32,
+
+    #[serde (skip_serializing_if = "Option::is_none")]
+    pub phase: Option<ProgressPhase>,
+
+    // Whether the phase was successful, only for phaseFinished
+    #[serde (skip_serializing_if = "Option::is_none")]
+    pub success: Option<bool>,
+
+    // How much of the whole compilation is done, from 0 to 100
+    pub percent: f64
+}
+
+// The function from JavaScript that gets the events, if one was given
+thread_local! {
+    static PROGRESS_CALLBACK: RefCell<Option<js_sys::Function>> = RefCell::new(None);
+}
+
+// Function for JavaScript to get an object for each progress event, or stop getting them by passing undefined
+#[wasm_bindgen(js_name = "setProgressCallback")]
+pub fn set_progress_callback(callback: Option<js_sys::Function>) {
+    PROGRESS_CALLBACK.with(|progress_callback| *progress_callback.borrow_mut() = callback);
+}
+
+// Keeps track of where the compilation is so each event can have the percent done
+pub struct ProgressReporter {
+    num_programs: u32,
+    program_number: u32
+}
+
+impl ProgressReporter {
+    pub fn new() -> Self {
+        return ProgressReporter {
+            num_programs: 0,
+            program_number: 0
+        };
+    }
+
+    // Function to start reporting on a compilation with the given number of programs
+    pub fn start_compilation(&mut self, num_programs: u32) {
+        self.num_programs = num_programs;
+        self.program_number = 0;
+        self.send_event(ProgressKind::CompilationStarted, None, None, 0.0);
+    }
+
+    // Function to send the event for a phase starting, which also moves to the next program when lexing
+    pub fn start_phase(&mut self, phase: ProgressPhase) {
+        if phase == ProgressPhase::Lex {
+            self.program_number += 1;
+        }
+        self.send_event(ProgressKind::PhaseStarted, Some(phase), None, self.get_percent(phase.get_index()));
+    }
+
+    // Function to send the event for a phase ending
+    pub fn finish_phase(&mut self, phase: ProgressPhase, success: bool) {
+        // A failed phase skips the rest of the program, so it counts as the end of the program
+        let phases_done: u32 = match success {
+            true => phase.get_index() + 1,
+            false => ProgressPhase::NUM_PHASES
+        };
+        self.send_event(ProgressKind::PhaseFinished, Some(phase), Some(success), self.get_percent(phases_done));
+    }
+
+    pub fn finish_compilation(&mut self) {
+        self.send_event(ProgressKind::CompilationFinished, None, None, 100.0);
+    }
+
+    // Function to get the percent of the compilation that is done, where every program has the same number of phases
+    fn get_percent(&self, phases_done: u32) -> f64 {
+        if self.num_programs == 0 {
+            return 100.0;
+        }
+        let total_phases: u32 = self.num_programs * ProgressPhase::NUM_PHASES;
+        let completed_phases: u32 = (self.program_number - 1) * ProgressPhase::NUM_PHASES + phases_done;
+        return (completed_phases as f64 * 100.0 / total_phases as f64).min(100.0);
+    }
+
+    fn send_event(&self, kind: ProgressKind, phase: Option<ProgressPhase>, success: Option<bool>, percent: f64) {
+        let event: ProgressEvent = ProgressEvent {
+            kind: kind,
+            program_number: match phase {
+                Some(_) => Some(self.program_number),
+                None => None
+            },
+            num_programs: self.num_programs,
+            phase: phase,
+            success: success,
+            percent: percent
+        };
+
+        // The callback is cloned so it can safely set a new callback while it runs
+        let progress_callback: Option<js_sys::Function> = PROGRESS_CALLBACK.with(|progress_callback| progress_callback.borrow().to_owned());
+        if progress_callback.is_none() {
+            return;
+        }
+
+        let event_json: String = serde_json::to_string(&event).expect("Should be able to serialize the event");
+        let event_value: JsValue = js_sys::JSON::parse(&event_json).expect("Should be able to parse the event");
+        if progress_callback.unwrap().call1(&JsValue::NULL, &event_value).is_err() {
+            nexus_log::log(
+                nexus_log::LogTypes::Warning,
+                nexus_log::LogSource::NEXUS,
+                String::from("The progress callback threw an error")
+            );
+        }
+    }
+}
@@ -0,0 +1,157 @@
+use std::fmt;
+
+// The registers that the RISC-V code generator uses
+#[derive (Debug, Clone, Copy, PartialEq)]
+pub enum Register {
+    Zero,
+    Ra,
+    Sp,
+    T0,
+    T1,
+    T2,
+    T3,
+    T4,
+    T5,
+    A0,
+    A1,
+    A2,
+    A7
+}
+
+impl Register {
+    // Function to get the ABI name of the register, which is how it is written in the assembly
+    pub fn get_name(&self) -> &'static str {
+        return match self {
+            Register::Zero => "zero",
+            Register::Ra => "ra",
+            Register::Sp => "sp",
+            Register::T0 => "t0",
+            Register::T1 => "t1",
+            Register::T2 => "t2",
+            Register::T3 => "t3",
+            Register::T4 => "t4",
+            Register::T5 => "t5",
+            Register::A0 => "a0",
+            Register::A1 => "a1",
+            Register::A2 => "a2",
+            Register::A7 => "a7"
+        };
+    }
+}
+
+impl fmt::Display for Register {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        return write!(f, "{}", self.get_name());
+    }
+}
+
+// A line in the text section of the assembly, which is every instruction in INSTRUCTIONS_RISCV, label definitions, and comments
+#[derive (Debug, Clone, PartialEq)]
+pub enum Instruction {
+    Label(String),
+    Comment(String),
+    Li { rd: Register, imm: i64 },
+    La { rd: Register, label: String },
+    Lbu { rd: Register, offset: i32, rs1: Register },
+    Lhu { rd: Register, offset: i32, rs1: Register },
+    Lw { rd: Register, offset: i32, rs1: Register },
+    Lwu { rd: Register, offset: i32, rs1: Register },
+    Sb { rs2: Register, offset: i32, rs1: Register },
+    Sw { rs2: Register, offset: i32, rs1: Register },
+    Add { rd: Register, rs1: Register, rs2: Register },
+    Addi { rd: Register, rs1: Register, imm: i64 },
+    Andi { rd: Register, rs1: Register, imm: i64 },
+    Divu { rd: Register, rs1: Register, rs2: Register },
+    Remu { rd: Register, rs1: Register, rs2: Register },
+    Mv { rd: Register, rs1: Register },
+    Beq { rs1: Register, rs2: Register, label: String },
+    Bne { rs1: Register, rs2: Register, label: String },
+    Blt { rs1: Register, rs2: Register, label: String },
+    J { label: String },
+    Call { label: String },
+    Ret,
+    Ecall
+}
+
+impl Instruction {
+    // Function to get the mnemonic of the instruction, which is None for labels and comments
+    pub fn get_mnemonic(&self) -> Option<&'static str> {
+        return match self {
+            Instruction::Label(_) | Instruction::Comment(_) => None,
+            Instruction::Li { .. } => Some("li"),
+            Instruction::La { .. } => Some("la"),
+            Instruction::Lbu { .. } => Some("lbu"),
+            Instruction::Lhu { .. } => Some("lhu"),
+            Instruction::Lw { .. } => Some("lw"),
+            Instruction::Lwu { .. } => Some("lwu"),
+            Instruction::Sb { .. } => Some("sb"),
+            Instruction::Sw { .. } => Some("sw"),
+            Instruction::Add { .. } => Some("add"),
+            Instruction::Addi { .. } => Some("addi"),
+            Instruction::Andi { .. } => Some("andi"),
+            Instruction::Divu { .. } => Some("divu"),
+            Instruction::Remu { .. } => Some("remu"),
+            Instruction::Mv { .. } => Some("mv"),
+            Instruction::Beq { .. } => Some("beq"),
+            Instruction::Bne { .. } => Some("bne"),
+            Instruction::Blt { .. } => Some("blt"),
+            Instruction::J { .. } => Some("j"),
+            Instruction::Call { .. } => Some("call"),
+            Instruction::Ret => Some("ret"),
+            Instruction::Ecall => Some("ecall")
+        };
+    }
+
+    // Function to get the label the line defines
+    pub fn get_defined_label(&self) -> Option<&str> {
+        return match self {
+            Instruction::Label(label) => Some(label),
+            _ => None
+        };
+    }
+
+    // Function to get the operands in the order of the operands in the instruction set
+    fn get_operands(&self) -> Vec<String> {
+        return match self {
+            Instruction::Label(_) | Instruction::Comment(_) | Instruction::Ret | Instruction::Ecall => Vec::new(),
+            Instruction::Li { rd, imm } => vec![rd.to_string(), imm.to_string()],
+            Instruction::La { rd, label } => vec![rd.to_string(), label.to_owned()],
+            Instruction::Lbu { rd, offset, rs1 }
+            | Instruction::Lhu { rd, offset, rs1 }
+            | Instruction::Lw { rd, offset, rs1 }
+            | Instruction::Lwu { rd, offset, rs1 } => vec![rd.to_string(), format!("{}({})", offset, rs1)],
+            Instruction::Sb { rs2, offset, rs1 }
+            | Instruction::Sw { rs2, offset, rs1 } => vec![rs2.to_string(), format!("{}({})", offset, rs1)],
+            Instruction::Add { rd, rs1, rs2 }
+            | Instruction::Divu { rd, rs1, rs2 }
+            | Instruction::Remu { rd, rs1, rs2 } => vec![rd.to_string(), rs1.to_string(), rs2.to_string()],
+            Instruction::Addi { rd, rs1, imm }
+            | Instruction::Andi { rd, rs1, imm } => vec![rd.to_string(), rs1.to_string(), imm.to_string()],
+            Instruction::Mv { rd, rs1 } => vec![rd.to_string(), rs1.to_string()],
+            Instruction::Beq { rs1, rs2, label }
+            | Instruction::Bne { rs1, rs2, label }
+            | Instruction::Blt { rs1, rs2, label } => vec![rs1.to_string(), rs2.to_string(), label.to_owned()],
+            Instruction::J { label } | Instruction::Call { label } => vec![label.to_owned()]
+        };
+    }
+}
+
+// Every line of the text section is written here, which is the mnemonic followed by 2 spaces and the operands
+impl fmt::Display for Instruction {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if let Instruction::Label(label) = self {
+            return write!(f, "{}:", label);
+        }
+        if let Instruction::Comment(comment) = self {
+            return write!(f, "# {}", comment);
+        }
+
+        let mnemonic: &str = self.get_mnemonic().unwrap();
+        let operands: Vec<String> = self.get_operands();
+        if operands.len() == 0 {
+            return write!(f, "{}", mnemonic);
+        } else {
+            return write!(f, "{}  {}", mnemonic, operands.join(", "));
+        }
+    }
+}
@@ -0,0 +1,214 @@
+use std::collections::HashSet;
+
+use crate::util::instruction_set::{self, InstructionRiscV};
+
+// The data directives the code generator uses and the number of operands for each
+const DIRECTIVES: [(&str, usize); 4] = [(".byte", 1), (".half", 1), (".word", 1), (".ascii", 1)];
+
+// Function to check that every line of the generated assembly would be accepted by the assembler
+// Returns a description of each problem that was found, which is empty if the assembly is valid
+// The first line number is the line after the section header and _start label
+pub fn validate_assembly(lines: &Vec<&String>, first_line_number: usize) -> Vec<String> {
+    let mut problems: Vec<String> = Vec::new();
+
+    // Labels can be used before they are defined, so find all of them first
+    let mut defined_labels: HashSet<&str> = HashSet::new();
+    defined_labels.insert("_start");
+    for line in lines.iter() {
+        let (label, _) = split_label(line.trim());
+        if label.is_some() {
+            defined_labels.insert(label.unwrap());
+        }
+    }
+
+    // Strings are stored as a .half with the length followed by the .ascii with the text
+    let mut previous_statement: &str = "";
+
+    for (line_index, line) in lines.iter().enumerate() {
+        let (label, statement) = split_label(line.trim());
+
+        let problem: Option<String> = if label.is_some() && !is_valid_label(label.unwrap()) {
+            Some(format!("Invalid label name [ {} ]", label.unwrap()))
+        } else if statement.len() == 0 || statement.starts_with('#') {
+            None
+        } else if statement.starts_with('.') {
+            validate_directive(statement).or_else(|| validate_string_length(previous_statement, statement))
+        } else {
+            validate_instruction(statement, &defined_labels)
+        };
+        previous_statement = statement;
+
+        if problem.is_some() {
+            problems.push(format!("Line {} [ {} ]: {}", first_line_number + line_index, line.trim(), problem.unwrap()));
+        }
+    }
+
+    return problems;
+}
+
+// Function to split the line into its label (if any) and the rest of the line
+fn split_label(line: &str) -> (Option<&str>, &str) {
+    let first_word: &str = line.split(' ').next().unwrap_or("");
+    if first_word.ends_with(':') {
+        return (Some(&first_word[..first_word.len() - 1]), line[first_word.len()..].trim());
+    } else {
+        return (None, line);
+    }
+}
+
+fn validate_directive(statement: &str) -> Option<String> {
+    let directive: &str = statement.split(' ').next().unwrap_or("");
+    let operands: &str = statement[directive.len()..].trim();
+
+    return match DIRECTIVES.iter().find(|(name, _)| name.eq(&directive)) {
+        Some(_) if operands.len() == 0 => Some(format!("Directive {} is missing its value", directive)),
+        Some((".ascii", _)) if !(operands.len() >= 2 && operands.starts_with('"') && operands.ends_with('"')) => {
+            Some(format!("Directive .ascii expects a string in quotes"))
+        },
+        Some((".ascii", _)) => decode_ascii_string(&operands[1..operands.len() - 1]).err(),
+        Some(_) if !is_valid_immediate(operands) => Some(format!("Directive {} expects a number", directive)),
+        Some(_) => None,
+        None => Some(format!("Unknown directive {}", directive))
+    };
+}
+
+fn validate_instruction(statement: &str, defined_labels: &HashSet<&str>) -> Option<String> {
+    let mnemonic: &str = statement.split(' ').next().unwrap_or("");
+    let instruction: &InstructionRiscV = match instruction_set::get_instruction_riscv(mnemonic) {
+        Some(instruction) => instruction,
+        None => return Some(format!("Unknown instruction {}", mnemonic))
+    };
+
+    let operands: Vec<&str> = split_operands(statement[mnemonic.len()..].trim());
+    let expected_operands: Vec<&str> = split_operands(instruction.operands);
+    if operands.len() != expected_operands.len() {
+        return Some(format!("{} expects {} operand(s) but has {}", mnemonic, expected_operands.len(), operands.len()));
+    }
+
+    for (operand, expected_operand) in operands.iter().zip(expected_operands.iter()) {
+        let is_valid: bool = match *expected_operand {
+            "imm" => is_valid_immediate(operand),
+            "label" => {
+                if !defined_labels.contains(operand) {
+                    return Some(format!("Label [ {} ] is never defined", operand));
+                }
+                true
+            },
+            // Loads can also take a label, which the assembler expands
+            "offset(rs1)" => is_valid_offset(operand) || (mnemonic.starts_with('l') && defined_labels.contains(operand)),
+            _ => is_valid_register(operand)
+        };
+
+        if !is_valid {
+            return Some(format!("Invalid operand [ {} ] where {} expects {}", operand, mnemonic, expected_operand));
+        }
+    }
+
+    return None;
+}
+
+// Function to get the bytes the assembler stores for the text inside of the quotes of .ascii
+pub fn decode_ascii_string(text: &str) -> Result<Vec<u8>, String> {
+    let mut bytes: Vec<u8> = Vec::new();
+    let text_bytes: &[u8] = text.as_bytes();
+    let mut index: usize = 0;
+    while index < text_bytes.len() {
+        match text_bytes[index] {
+            b'"' => return Err(String::from("Directive .ascii has a quote that is not escaped")),
+            b'\\' => {
+                // Escapes are a single character or up to 3 octal digits
+                let octal_len: usize = text_bytes[index + 1..].iter().take(3).take_while(|c| (b'0'..=b'7').contains(c)).count();
+                if octal_len > 0 {
+                    let octal_text: &str = &text[index + 1..index + 1 + octal_len];
+                    match u8::from_str_radix(octal_text, 8) {
+                        Ok(byte) => bytes.push(byte),
+                        Err(_) => return Err(format!("Directive .ascii has an octal escape [ \\{} ] that is larger than a byte", octal_text))
+                    }
+                    index += 1 + octal_len;
+                    continue;
+                }
+                let escaped_byte: Option<u8> = match text_bytes.get(index + 1) {
+                    Some(b'"') => Some(b'"'),
+                    Some(b'\\') => Some(b'\\'),
+                    Some(b'n') => Some(b'\n'),
+                    Some(b't') => Some(b'\t'),
+                    _ => None
+                };
+                if escaped_byte.is_none() {
+                    return Err(String::from("Directive .ascii has an invalid escape sequence"));
+                }
+                bytes.push(escaped_byte.unwrap());
+                index += 2;
+            },
+            byte => {
+                bytes.push(byte);
+                index += 1;
+            }
+        }
+    }
+    return Ok(bytes);
+}
+
+// Function to make sure the length before a string is the number of bytes in it
+fn validate_string_length(previous_statement: &str, statement: &str) -> Option<String> {
+    let length_text: Option<&str> = previous_statement.strip_prefix(".half ");
+    let string_text: Option<&str> = statement.strip_prefix(".ascii ");
+    if length_text.is_none() || string_text.is_none() {
+        return None;
+    }
+
+    let string_text: &str = string_text.unwrap().trim();
+    let num_bytes: usize = decode_ascii_string(&string_text[1..string_text.len() - 1]).unwrap().len();
+    return match length_text.unwrap().trim().parse::<usize>() {
+        Ok(length) if length == num_bytes && length <= u16::MAX as usize => None,
+        Ok(length) if length == num_bytes => Some(format!("String of {} bytes is longer than the limit of {} bytes", num_bytes, u16::MAX)),
+        _ => Some(format!("String has {} bytes but its length is {}", num_bytes, length_text.unwrap().trim()))
+    };
+}
+
+fn split_operands(operands: &str) -> Vec<&str> {
+    if operands.len() == 0 {
+        return Vec::new();
+    } else {
+        return operands.split(',').map(|operand| operand.trim()).collect();
+    }
+}
+
+fn is_valid_register(operand: &str) -> bool {
+    return match operand {
+        "zero" | "ra" | "sp" | "gp" | "tp" | "fp" => true,
+        _ => {
+            // The register number has to be in range for each group
+            let (prefix, number): (&str, &str) = operand.split_at(operand.len().min(1));
+            match (prefix, number.parse::<usize>()) {
+                ("x", Ok(num)) => num <= 31,
+                ("t", Ok(num)) => num <= 6,
+                ("s", Ok(num)) => num <= 11,
+                ("a", Ok(num)) => num <= 7,
+                _ => false
+            }
+        }
+    };
+}
+
+fn is_valid_immediate(operand: &str) -> bool {
+    let unsigned_operand: &str = operand.strip_prefix('-').unwrap_or(operand);
+    return match unsigned_operand.strip_prefix("0x") {
+        Some(hex) => i64::from_str_radix(hex, 16).is_ok(),
+        None => unsigned_operand.parse::<i64>().is_ok()
+    };
+}
+
+// Function to check for the offset(register) form
+fn is_valid_offset(operand: &str) -> bool {
+    return match (operand.find('('), operand.ends_with(')')) {
+        (Some(open_index), true) => is_valid_immediate(&operand[..open_index]) && is_valid_register(&operand[open_index + 1..operand.len() - 1]),
+        _ => false
+    };
+}
+
+fn is_valid_label(label: &str) -> bool {
+    return label.len() > 0
+        && !label.starts_with(|c: char| c.is_ascii_digit())
+        && label.chars().all(|c| c.is_ascii_alphanumeric() || c == '_');
+}
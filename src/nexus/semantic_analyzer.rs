@@ -1,29 +1,123 @@
 use log::*;
 use crate::{nexus::token::{Token, TokenType, Symbols, Keywords}, util::nexus_log};
+use crate::util::{target::TargetConfig, diagnostic::{Diagnostic, ErrorClass, LintWarning}, messages::{Message, MessageCode}, position::{Position, Span}};
 
-use crate::nexus::syntax_tree::{SyntaxTree, SyntaxTreeTypes};
+use crate::nexus::syntax_tree::{SyntaxTree, SyntaxTreeTypes, SyntaxTreeJson};
 use crate::nexus::syntax_tree_node::{SyntaxTreeNode, NonTerminalsAst, SyntaxTreeNodeTypes};
-use crate::nexus::symbol_table::{SymbolTable, Type, SymbolTableEntry, SymbolTableEntryField};
+use crate::nexus::symbol_table::{SymbolTable, Type, SymbolTableEntry, SymbolTableEntryField, SymbolTableJsonEntry, DeclId};
+use crate::nexus::semantic_tokens::{IdentifierReference, ReferenceKind};
+use crate::nexus::{parser::Parser, operand};
 
-use petgraph::graph::NodeIndex;
+use petgraph::{graph::NodeIndex, Direction};
 
 use string_builder::Builder;
+use serde::Serialize;
+
+use std::collections::HashMap;
+
+// The output of semantic analysis that gets passed on to code generation
+#[derive (Debug)]
+pub struct SemanticAnalysisResult {
+    // The AST that was analyzed
+    pub ast: SyntaxTree,
+
+    // The symbol table that was built while analyzing the AST
+    pub symbol_table: SymbolTable,
+
+    // The derived type of every expression node in the AST
+    pub node_types: HashMap<NodeIndex, Type>,
+
+    // The scope in the symbol table that each block node created
+    pub block_scopes: HashMap<NodeIndex, usize>,
+
+    // How many blocks each block node is inside of, where the outer block of the program is 1
+    pub block_depths: HashMap<NodeIndex, usize>,
+
+    // The reference index, which has how each identifier node is used and the declaration it refers to
+    pub identifier_refs: HashMap<NodeIndex, IdentifierReference>,
+
+    // The declaration id of every identifier node that refers to a declared variable, which the code generators key their storage by
+    pub decl_ids: HashMap<NodeIndex, DeclId>,
+
+    pub num_errors: i32,
+    pub num_warnings: i32,
+
+    // Every error that was found during analysis
+    pub errors: Vec<Diagnostic>,
+
+    // The warnings from the lints, which run after analysis
+    pub lint_warnings: Vec<LintWarning>
+}
+
+impl SemanticAnalysisResult {
+    // Semantic analysis is successful if there are no errors
+    pub fn is_successful(&self) -> bool {
+        return self.num_errors == 0;
+    }
+
+    // Function to get the annotated AST and the analysis summary as JSON
+    pub fn to_json(&self) -> String {
+        let result_json: SemanticAnalysisJson = SemanticAnalysisJson {
+            tree: self.ast.create_json(Some(&self.node_types)),
+            num_errors: self.num_errors,
+            num_warnings: self.num_warnings,
+            errors: &self.errors,
+            lint_warnings: &self.lint_warnings,
+            symbol_table: self.symbol_table.get_json_entries()
+        };
+        return serde_json::to_string(&result_json).expect("Should be able to serialize the semantic analysis result");
+    }
+
+    // Function to get the AST as an s-expression with the derived types
+    pub fn to_sexpr(&self) -> String {
+        return self.ast.create_sexpr(Some(&self.node_types));
+    }
+}
+
+// The JSON export of the semantic analysis result
+#[derive (Debug, Serialize)]
+#[serde (rename_all = "camelCase")]
+struct SemanticAnalysisJson<'a> {
+    #[serde (flatten)]
+    tree: SyntaxTreeJson,
+    num_errors: i32,
+    num_warnings: i32,
+    errors: &'a Vec<Diagnostic>,
+    lint_warnings: &'a Vec<LintWarning>,
+    symbol_table: Vec<SymbolTableJsonEntry>
+}
 
 pub struct SemanticAnalyzer {
     cur_token_index: usize,
     num_errors: i32,
     num_warnings: i32,
-    pub symbol_table: SymbolTable
+    symbol_table: SymbolTable,
+    node_types: HashMap<NodeIndex, Type>,
+    block_scopes: HashMap<NodeIndex, usize>,
+    block_depths: HashMap<NodeIndex, usize>,
+    cur_block_depth: usize,
+    identifier_refs: HashMap<NodeIndex, IdentifierReference>,
+    decl_ids: HashMap<NodeIndex, DeclId>,
+    errors: Vec<Diagnostic>,
+    target_config: TargetConfig
 }
 
 impl SemanticAnalyzer {
     // Constructor for the parser
-    pub fn new() -> Self {
+    pub fn new(target_config: TargetConfig) -> Self {
         return SemanticAnalyzer {
             cur_token_index: 0,
             num_errors: 0,
             num_warnings: 0,
-            symbol_table: SymbolTable::new()
+            symbol_table: SymbolTable::new(),
+            node_types: HashMap::new(),
+            block_scopes: HashMap::new(),
+            block_depths: HashMap::new(),
+            cur_block_depth: 0,
+            identifier_refs: HashMap::new(),
+            decl_ids: HashMap::new(),
+            errors: Vec::new(),
+            target_config: target_config
         };
     }
 
@@ -43,6 +137,8 @@ impl SemanticAnalyzer {
 
     fn parse_ast_block(&mut self, token_stream: &Vec<Token>, ast: &mut SyntaxTree) {
         ast.add_node(SyntaxTreeNodeTypes::Branch, SyntaxTreeNode::NonTerminalAst(NonTerminalsAst::Block));
+        // Nesting warnings are reported at the left brace
+        ast.set_current_position(token_stream[self.cur_token_index].position.to_owned());
 
         // Advance a token for the left brace
         self.cur_token_index += 1;
@@ -79,7 +175,7 @@ impl SemanticAnalyzer {
             TokenType::Identifier(_) => self.parse_ast_assignment_statement(token_stream, ast),
 
             // VarDecl statements
-            TokenType::Keyword(Keywords::Int) | TokenType::Keyword(Keywords::String) | TokenType::Keyword(Keywords::Boolean) => self.parse_ast_var_declaration(token_stream, ast),
+            TokenType::Keyword(Keywords::Int) | TokenType::Keyword(Keywords::String) | TokenType::Keyword(Keywords::Boolean) | TokenType::Keyword(Keywords::Char) => self.parse_ast_var_declaration(token_stream, ast),
 
             // While statements
             TokenType::Keyword(Keywords::While) => self.parse_ast_while_statement(token_stream, ast), 
@@ -91,7 +187,7 @@ impl SemanticAnalyzer {
             TokenType::Symbol(Symbols::LBrace) => self.parse_ast_block(token_stream, ast),
 
             // Invalid statement starter tokens
-            _ => error!("Invalid statement token [ {:?} ] at {:?}; Valid statement beginning tokens are {:?}", next_token.token_type, next_token.position, vec![TokenType::Keyword(Keywords::Print), TokenType::Identifier(String::from("a-z")), TokenType::Keyword(Keywords::Int), TokenType::Keyword(Keywords::String), TokenType::Keyword(Keywords::Boolean), TokenType::Keyword(Keywords::While), TokenType::Keyword(Keywords::If), TokenType::Symbol(Symbols::LBrace)])
+            _ => error!("Invalid statement token [ {:?} ] at {}; Valid statement beginning tokens are {:?}", next_token.token_type, next_token.position, vec![TokenType::Keyword(Keywords::Print), TokenType::Identifier(String::from("a-z")), TokenType::Keyword(Keywords::Int), TokenType::Keyword(Keywords::String), TokenType::Keyword(Keywords::Boolean), TokenType::Keyword(Keywords::Char), TokenType::Keyword(Keywords::While), TokenType::Keyword(Keywords::If), TokenType::Symbol(Symbols::LBrace)])
         }
     }
 
@@ -141,9 +237,27 @@ impl SemanticAnalyzer {
         self.cur_token_index += 1;
 
         // Then make sure there is a valid identifier
+        let id_token_index: usize = self.cur_token_index;
         self.parse_ast_identifier(token_stream, ast);
 
         ast.move_up();
+
+        // Initializers are desugared into an assignment right after the declaration,
+        // so analysis and code generation can reuse the assignment path
+        if token_stream[self.cur_token_index].token_type.eq(&TokenType::Symbol(Symbols::AssignmentOp)) {
+            ast.add_node(SyntaxTreeNodeTypes::Branch, SyntaxTreeNode::NonTerminalAst(NonTerminalsAst::Assign));
+
+            // The identifier being assigned is the one that was just declared
+            ast.add_node(SyntaxTreeNodeTypes::Leaf, SyntaxTreeNode::Terminal(token_stream[id_token_index].to_owned()));
+
+            // Increment the index for the = sign that parse checked
+            self.cur_token_index += 1;
+
+            // The right hand side of the initializer is an expression
+            self.parse_ast_expression(token_stream, ast);
+
+            ast.move_up();
+        }
     }
 
     fn parse_ast_while_statement(&mut self, token_stream: &Vec<Token>, ast: &mut SyntaxTree) {
@@ -183,9 +297,21 @@ impl SemanticAnalyzer {
             // IntExpr
             TokenType::Digit(_) => self.parse_ast_int_expression(token_stream, ast),
 
+            // IndexExpr of a string literal or an id
+            TokenType::Symbol(Symbols::Quote) | TokenType::Identifier(_) if Parser::is_index_expression(token_stream, self.cur_token_index) => self.parse_ast_index_expression(token_stream, ast),
+
             // StringExpr
             TokenType::Symbol(Symbols::Quote) => self.parse_ast_string_expression(token_stream, ast),
 
+            // CharExpr
+            TokenType::CharLiteral(_) => self.parse_ast_char_expression(token_stream, ast),
+
+            // LenExpr
+            TokenType::Keyword(Keywords::Len) => self.parse_ast_len_expression(token_stream, ast),
+
+            // IntExpr in parentheses
+            TokenType::Symbol(Symbols::LParen) if Parser::find_grouped_int_expression_end(token_stream, self.cur_token_index).is_some() => self.parse_ast_int_expression(token_stream, ast),
+
             // BooleanExpr
             TokenType::Symbol(Symbols::LParen) | TokenType::Keyword(Keywords::False) | TokenType::Keyword(Keywords::True) => self.parse_ast_bool_expression(token_stream, ast),
 
@@ -193,21 +319,66 @@ impl SemanticAnalyzer {
             TokenType::Identifier(_) => self.parse_ast_identifier(token_stream, ast),
 
             // Parse already ensured correctness, but have to include this case
-            _ => error!("Invalid expression token [ {:?} ] at {:?}; Valid expression beginning tokens are [Digit(0-9), {:?}, {:?}, {:?}, {:?}, {:?}]", next_token.token_type, next_token.position, TokenType::Symbol(Symbols::Quote), TokenType::Symbol(Symbols::LParen), TokenType::Keyword(Keywords::False), TokenType::Keyword(Keywords::True), TokenType::Identifier(String::from("a-z"))),
+            _ => error!("Invalid expression token [ {:?} ] at {}; Valid expression beginning tokens are [Digit(0-9), {:?}, CharLiteral(a-z or space), {:?}, {:?}, {:?}, {:?}, {:?}]", next_token.token_type, next_token.position, TokenType::Symbol(Symbols::Quote), TokenType::Keyword(Keywords::Len), TokenType::Symbol(Symbols::LParen), TokenType::Keyword(Keywords::False), TokenType::Keyword(Keywords::True), TokenType::Identifier(String::from("a-z"))),
         }
     }
 
     fn parse_ast_int_expression(&mut self, token_stream: &Vec<Token>, ast: &mut SyntaxTree) {
-        match &token_stream[self.cur_token_index + 1].token_type {
-            TokenType::Symbol(Symbols::AdditionOp) => {
-                // Add the addition nonterminal
-                ast.add_node(SyntaxTreeNodeTypes::Branch, SyntaxTreeNode::NonTerminalAst(NonTerminalsAst::Add));
-                // Add the first digit
-                ast.add_node(SyntaxTreeNodeTypes::Leaf, SyntaxTreeNode::Terminal(token_stream[self.cur_token_index].to_owned()));
-                self.cur_token_index += 2;
-                
+        // Additions are left associative, so 1 + 2 + a is (1 + 2) + a
+        // The grammar is right recursive, so count the operands of the chain first to know how many additions to nest
+        let mut num_operands: usize = 1;
+        let mut operand_index: usize = self.cur_token_index;
+        loop {
+            // Only a digit or a group can have more of the chain after it
+            let operand_end: usize = match &token_stream[operand_index].token_type {
+                TokenType::Digit(_) => operand_index,
+                TokenType::Symbol(Symbols::LParen) => {
+                    match Parser::find_grouped_int_expression_end(token_stream, operand_index) {
+                        Some(rparen_index) => rparen_index,
+                        None => break
+                    }
+                },
+                _ => break
+            };
+
+            if token_stream[operand_end + 1].token_type.eq(&TokenType::Symbol(Symbols::AdditionOp)) {
+                num_operands += 1;
+                operand_index = operand_end + 2;
+            } else {
+                break;
+            }
+        }
+
+        for _ in 1..num_operands {
+            ast.add_node(SyntaxTreeNodeTypes::Branch, SyntaxTreeNode::NonTerminalAst(NonTerminalsAst::Add));
+        }
+
+        for operand_number in 0..num_operands {
+            if operand_number == num_operands - 1 && num_operands > 1 {
+                // The last operand can be any expression, and semantic analysis makes sure it is an int
                 self.parse_ast_expression(token_stream, ast);
+            } else {
+                self.parse_ast_int_operand(token_stream, ast);
+            }
+
+            if operand_number > 0 {
+                // The addition of this operand is done, so go back up to the next one
                 ast.move_up();
+            }
+            if operand_number < num_operands - 1 {
+                // Skip over the addition operator
+                self.cur_token_index += 1;
+            }
+        }
+    }
+
+    fn parse_ast_int_operand(&mut self, token_stream: &Vec<Token>, ast: &mut SyntaxTree) {
+        match &token_stream[self.cur_token_index].token_type {
+            TokenType::Symbol(Symbols::LParen) => {
+                // The parentheses only group the expression, so they do not get a node of their own
+                self.cur_token_index += 1;
+                self.parse_ast_int_expression(token_stream, ast);
+                self.cur_token_index += 1;
             },
             _ => {
                 // It is just the digit, so we can just add the digit (current token) to the ast
@@ -215,11 +386,11 @@ impl SemanticAnalyzer {
                 self.cur_token_index += 1;
             }
         }
-      }
+    }
 
     fn parse_ast_string_expression(&mut self, token_stream: &Vec<Token>, ast: &mut SyntaxTree) {
         // Get the posititon of the string because we will make a new token for the whole thing
-        let string_pos: (usize, usize) = token_stream[self.cur_token_index].position.to_owned();
+        let string_pos: Position = token_stream[self.cur_token_index].position.to_owned();
 
         // Increment the index for the first quote
         self.cur_token_index += 1;
@@ -239,10 +410,41 @@ impl SemanticAnalyzer {
 
         // Crate a new token and add it to the AST
         let new_string: String = str_builder.string().unwrap();
-        let new_token: Token = Token::new(TokenType::Char(new_string.to_owned()), new_string.to_owned(), string_pos.0, string_pos.1);  
+        let new_token: Token = Token::new(TokenType::Char(new_string.to_owned()), new_string.to_owned(), string_pos.line, string_pos.col);  
         ast.add_node(SyntaxTreeNodeTypes::Leaf, SyntaxTreeNode::Terminal(new_token));
     }
 
+    fn parse_ast_index_expression(&mut self, token_stream: &Vec<Token>, ast: &mut SyntaxTree) {
+        // Add the Index node, which has the string and then the index
+        ast.add_node(SyntaxTreeNodeTypes::Branch, SyntaxTreeNode::NonTerminalAst(NonTerminalsAst::Index));
+
+        match &token_stream[self.cur_token_index].token_type {
+            TokenType::Symbol(Symbols::Quote) => self.parse_ast_string_expression(token_stream, ast),
+            _ => self.parse_ast_identifier(token_stream, ast)
+        }
+
+        // Skip over the brackets around the index
+        self.cur_token_index += 1;
+        self.parse_ast_expression(token_stream, ast);
+        self.cur_token_index += 1;
+
+        ast.move_up();
+    }
+
+    fn parse_ast_len_expression(&mut self, token_stream: &Vec<Token>, ast: &mut SyntaxTree) {
+        // Add the Len node, which only has the string as a child
+        ast.add_node(SyntaxTreeNodeTypes::Branch, SyntaxTreeNode::NonTerminalAst(NonTerminalsAst::Len));
+
+        // Skip over the len keyword and the left paren
+        self.cur_token_index += 2;
+        self.parse_ast_expression(token_stream, ast);
+
+        // Increment the token index by 1 for the right paren
+        self.cur_token_index += 1;
+
+        ast.move_up();
+    }
+
     fn parse_ast_bool_expression(&mut self, token_stream: &Vec<Token>, ast: &mut SyntaxTree) {
         match &token_stream[self.cur_token_index].token_type {
             // Long boolean expressions start with LParen
@@ -256,7 +458,7 @@ impl SemanticAnalyzer {
             },
 
             // Invalid boolean expression, but parse should have already handled this
-            _ => error!("Invalid boolean expression token [ {:?} ] at {:?}; Valid boolean expression beginning tokens are {:?}", token_stream[self.cur_token_index].token_type, token_stream[self.cur_token_index].position, vec![TokenType::Symbol(Symbols::LParen), TokenType::Keyword(Keywords::False), TokenType::Keyword(Keywords::True)])
+            _ => error!("Invalid boolean expression token [ {:?} ] at {}; Valid boolean expression beginning tokens are {:?}", token_stream[self.cur_token_index].token_type, token_stream[self.cur_token_index].position, vec![TokenType::Symbol(Symbols::LParen), TokenType::Keyword(Keywords::False), TokenType::Keyword(Keywords::True)])
         }
     }
 
@@ -315,6 +517,12 @@ impl SemanticAnalyzer {
         ast.move_up();
     }
 
+    fn parse_ast_char_expression(&mut self, token_stream: &Vec<Token>, ast: &mut SyntaxTree) {
+        // The char literal is a single token, so it goes straight into the AST
+        ast.add_node(SyntaxTreeNodeTypes::Leaf, SyntaxTreeNode::Terminal(token_stream[self.cur_token_index].to_owned()));
+        self.cur_token_index += 1;
+    }
+
     fn parse_ast_identifier(&mut self, token_stream: &Vec<Token>, ast: &mut SyntaxTree) {
         // Add the Id node
         ast.add_node(SyntaxTreeNodeTypes::Leaf, SyntaxTreeNode::Terminal(token_stream[self.cur_token_index].to_owned()));
@@ -323,12 +531,19 @@ impl SemanticAnalyzer {
         self.cur_token_index += 1;
     }
 
-    pub fn analyze_program(&mut self, ast: &SyntaxTree) -> bool {
+    pub fn analyze_program(&mut self, ast: SyntaxTree) -> SemanticAnalysisResult {
         self.num_errors = 0;
         self.num_warnings = 0;
         self.symbol_table.reset();
-        if (*ast).root.is_some() {
-            self.analyze_dfs(ast, (*ast).root.unwrap());
+        self.node_types.clear();
+        self.block_scopes.clear();
+        self.block_depths.clear();
+        self.cur_block_depth = 0;
+        self.identifier_refs.clear();
+        self.decl_ids.clear();
+        self.errors.clear();
+        if ast.root.is_some() {
+            self.analyze_dfs(&ast, ast.root.unwrap());
 
             self.num_warnings += self.symbol_table.mass_warnings();
 
@@ -353,20 +568,52 @@ impl SemanticAnalyzer {
             if self.num_errors == 0 {
                 nexus_log::log(
                     nexus_log::LogTypes::Info,
-                    nexus_log::LogSources::SemanticAnalyzer,
+                    nexus_log::LogSource::SEMANTIC_ANALYZER,
                     output_string
                 );
-                return true;
             } else {
                 nexus_log::log(
                     nexus_log::LogTypes::Error,
-                    nexus_log::LogSources::SemanticAnalyzer,
+                    nexus_log::LogSource::SEMANTIC_ANALYZER,
                     output_string
                 );
-                return false;
             }
+        } else {
+            // An empty AST cannot be analyzed
+            self.num_errors += 1;
         }
-        return false;
+
+        // Hand off everything that was built so the analyzer is ready for the next program
+        return SemanticAnalysisResult {
+            ast: ast,
+            symbol_table: std::mem::replace(&mut self.symbol_table, SymbolTable::new()),
+            node_types: std::mem::take(&mut self.node_types),
+            block_scopes: std::mem::take(&mut self.block_scopes),
+            block_depths: std::mem::take(&mut self.block_depths),
+            identifier_refs: std::mem::take(&mut self.identifier_refs),
+            decl_ids: std::mem::take(&mut self.decl_ids),
+            num_errors: self.num_errors,
+            num_warnings: self.num_warnings,
+            errors: std::mem::take(&mut self.errors),
+            lint_warnings: Vec::new()
+        };
+    }
+
+    // Function to log an error and keep track of it for the result
+    fn report_error(&mut self, class: ErrorClass, position: Position, message: Message) {
+        nexus_log::log_message(
+            nexus_log::LogTypes::Error,
+            nexus_log::LogSource::SEMANTIC_ANALYZER,
+            position,
+            &message
+        );
+        self.errors.push(Diagnostic {
+            class: class,
+            code: message.code,
+            position: position,
+            message: message.get_text()
+        });
+        self.num_errors += 1;
     }
 
     fn analyze_dfs(&mut self, ast: &SyntaxTree, cur_index: usize) {
@@ -379,11 +626,14 @@ impl SemanticAnalyzer {
                     NonTerminalsAst::Block => {
                         // Create a new scope for the block
                         self.symbol_table.new_scope();
+                        self.block_scopes.insert(NodeIndex::new(cur_index), self.symbol_table.cur_scope.unwrap());
                         nexus_log::log(
                             nexus_log::LogTypes::Debug,
-                            nexus_log::LogSources::SemanticAnalyzer,
+                            nexus_log::LogSource::SEMANTIC_ANALYZER,
                             format!("Entering new scope {}", self.symbol_table.cur_scope.unwrap())
                         );
+                        self.cur_block_depth += 1;
+                        self.analyze_block_depth(ast, NodeIndex::new(cur_index));
 
                         // Everything inside is a statement, so analyze each node
                         for neighbor_index in neighbors.into_iter().rev() {
@@ -392,11 +642,12 @@ impl SemanticAnalyzer {
 
                         nexus_log::log(
                             nexus_log::LogTypes::Debug,
-                            nexus_log::LogSources::SemanticAnalyzer,
+                            nexus_log::LogSource::SEMANTIC_ANALYZER,
                             format!("Exiting scope {}", self.symbol_table.cur_scope.unwrap())
                         );
                         // This is the end of the current scope
                         self.symbol_table.end_cur_scope();
+                        self.cur_block_depth -= 1;
                     },
                     NonTerminalsAst::VarDecl => self.analyze_var_decl(ast, &neighbors),
                     NonTerminalsAst::Assign => self.analyze_assignment(ast, &neighbors),
@@ -424,10 +675,10 @@ impl SemanticAnalyzer {
     }
 
     // Function to derive the type of a node and returns the left-most token position
-    fn derive_type(&mut self, ast: &SyntaxTree, node_index: NodeIndex) -> Option<(Type, (usize, usize))> {
+    fn derive_type(&mut self, ast: &SyntaxTree, node_index: NodeIndex) -> Option<(Type, Position)> {
         let ast_node: &SyntaxTreeNode = (*ast).graph.node_weight(node_index).unwrap();
 
-        let mut output: Option<(Type, (usize, usize))> = None;
+        let mut output: Option<(Type, Position)> = None;
 
         match ast_node {
             SyntaxTreeNode::Terminal(token) => {
@@ -435,7 +686,12 @@ impl SemanticAnalyzer {
                     // Digits are integer types
                     TokenType::Digit(_) => output = Some((Type::Int, token.position.to_owned())),
                     // The AST combined CharLists into a single Char token, so this is a string
-                    TokenType::Char(_) => output = Some((Type::String, token.position.to_owned())),
+                    TokenType::Char(string) => {
+                        self.check_string_length(string, token.position);
+                        output = Some((Type::String, token.position.to_owned()));
+                    },
+                    // Char literals are a single character
+                    TokenType::CharLiteral(_) => output = Some((Type::Char, token.position.to_owned())),
                     TokenType::Identifier(id_name) => {
                         // Get the identifier from the symbol table
                         let symbol_table_entry: Option<&SymbolTableEntry> = self.get_identifier(&token);
@@ -443,25 +699,35 @@ impl SemanticAnalyzer {
                             // Make clones of a these fields to prevent the rust borrow checker
                             // from going crazy
                             let symbol_table_entry_type: Type = symbol_table_entry.unwrap().symbol_type.to_owned();
-                            let symbol_table_entry_position: (usize, usize) = symbol_table_entry.unwrap().position.to_owned();
+                            let symbol_table_entry_position: Position = symbol_table_entry.unwrap().span.start;
                             let symbol_table_entry_is_initialized: bool = symbol_table_entry.unwrap().is_initialized.to_owned();
                             let symbol_table_entry_is_used: bool = symbol_table_entry.unwrap().is_used.to_owned();
                             let symbol_table_entry_scope: usize = symbol_table_entry.unwrap().scope.to_owned();
+                            let symbol_table_entry_decl_id: DeclId = symbol_table_entry.unwrap().decl_id.to_owned();
+                            self.decl_ids.insert(node_index, symbol_table_entry_decl_id);
+                            self.identifier_refs.insert(node_index, IdentifierReference {
+                                kind: ReferenceKind::Read { is_initialized: symbol_table_entry_is_initialized },
+                                declaration: Some((symbol_table_entry_scope, symbol_table_entry_position))
+                            });
 
                             nexus_log::log(
                                 nexus_log::LogTypes::Debug,
-                                nexus_log::LogSources::SemanticAnalyzer,
-                                format!("Id [ {} ] declared in scope {} at position {:?} is valid and has been used at {:?} in scope {}",
+                                nexus_log::LogSource::SEMANTIC_ANALYZER,
+                                format!("Id [ {} ] declared in scope {} at {} is valid and has been used at {} in scope {}",
                                         id_name, symbol_table_entry_scope, symbol_table_entry_position, token.position, self.symbol_table.cur_scope.unwrap())
                             );
 
                             if !symbol_table_entry_is_initialized {
                                 // Throw a warning for using an uninitialized variable
-                                nexus_log::log(
+                                nexus_log::log_message(
                                     nexus_log::LogTypes::Warning,
-                                    nexus_log::LogSources::SemanticAnalyzer,
-                                    format!("Warning at {:?}; Use of uninitialized variable [ {} ] that was declared at {:?}",
-                                            token.position, id_name, symbol_table_entry_position)
+                                    nexus_log::LogSource::SEMANTIC_ANALYZER,
+                                    token.position,
+                                    &Message::new(MessageCode::UninitializedUse, vec![
+                                        ("position", token.position.to_message_param()),
+                                        ("id", id_name.to_owned()),
+                                        ("declared_position", symbol_table_entry_position.to_message_param())
+                                    ])
                                 );
                                 self.num_warnings += 1;
                             }
@@ -473,6 +739,8 @@ impl SemanticAnalyzer {
 
                             // Return the type and position of the identifier being used
                             output = Some((symbol_table_entry_type, token.position.to_owned()));
+                        } else {
+                            self.identifier_refs.insert(node_index, IdentifierReference { kind: ReferenceKind::Read { is_initialized: false }, declaration: None });
                         }
                     },
                     TokenType::Keyword(keyword) => {
@@ -482,7 +750,7 @@ impl SemanticAnalyzer {
                             _ => error!("Cannot derive type of keyword {:?}, only true and false", keyword)
                         }
                     },
-                    _ => error!("Cannot derive type of terminal {:?}, only Digit, Char, Identifier, and Keyword", token)
+                    _ => error!("Cannot derive type of terminal {:?}, only Digit, Char, CharLiteral, Identifier, and Keyword", token)
                 }
             },
             SyntaxTreeNode::NonTerminalAst(non_terminal) => {
@@ -490,15 +758,31 @@ impl SemanticAnalyzer {
                 let non_term_neighbors: Vec<NodeIndex> = (*ast).graph.neighbors(node_index).collect();
                 match &non_terminal {
                     // Analyze the addition statement
-                    NonTerminalsAst::Add => output = self.analyze_add(ast, &non_term_neighbors),
+                    NonTerminalsAst::Add => {
+                        output = self.analyze_add(ast, &non_term_neighbors);
+
+                        // Only the outermost addition is checked so the whole expression is reported once
+                        if output.is_some() && !self.is_nested_add(ast, node_index) {
+                            self.check_int_overflow(ast, node_index);
+                        }
+                    },
                     // Analyze the boolean expression
                     NonTerminalsAst::IsEq | NonTerminalsAst::NotEq => output = self.analyze_eq_neq(ast, &non_term_neighbors),
-                    _ => error!("Cannot derive type of nonterminal {:?}, only Add, IsEq, and NotEq", non_terminal)
+                    // Analyze the index expression
+                    NonTerminalsAst::Index => output = self.analyze_index(ast, &non_term_neighbors),
+                    // Analyze the length expression
+                    NonTerminalsAst::Len => output = self.analyze_len(ast, &non_term_neighbors),
+                    _ => error!("Cannot derive type of nonterminal {:?}, only Add, IsEq, NotEq, Index, and Len", non_terminal)
                 }
             },
             SyntaxTreeNode::NonTerminalCst(_) => error!("Found a CST node in the AST")
         }
 
+        // Annotate the node with its type for later stages
+        if output.is_some() {
+            self.node_types.insert(node_index, output.as_ref().unwrap().0.to_owned());
+        }
+
         return output;
     }
 
@@ -506,17 +790,17 @@ impl SemanticAnalyzer {
         // Index 0 should be the id token
         let id_node: &SyntaxTreeNode = (*ast).graph.node_weight(neighbors[0]).unwrap();
         let mut new_id: Option<String> = None;
-        let mut new_id_pos: (usize, usize) = (0, 0);
+        let mut new_id_span: Span = Span::default();
 
         match id_node {
             SyntaxTreeNode::Terminal(id_token) => {
                 match &id_token.token_type {
                     TokenType::Identifier(id_name) => {
                         new_id = Some(id_name.to_owned());
-                        new_id_pos = id_token.position.to_owned();
+                        new_id_span = id_token.get_span();
                     },
                     // Should also never be reached, this is an internal error
-                    _ => error!("Received {:?} at {:?}; Expected an identifier", id_token.token_type, id_token.position)
+                    _ => error!("Received {:?} at {}; Expected an identifier", id_token.token_type, id_token.position)
                 }
             },
             // Nonterminal should never be reached
@@ -538,13 +822,14 @@ impl SemanticAnalyzer {
                             Keywords::String => new_type = Some(Type::String),
                             Keywords::Int => new_type = Some(Type::Int),
                             Keywords::Boolean => new_type = Some(Type::Boolean),
+                            Keywords::Char => new_type = Some(Type::Char),
 
                             // Should never be reached once again, but have to add
-                            _ => error!("Received {:?} at {:?}; Expected String, Int, or Boolean", id_token.token_type, id_token.position)
+                            _ => error!("Received {:?} at {}; Expected String, Int, Boolean, or Char", id_token.token_type, id_token.position)
                         }
                     },
                     // Should also never be reached, this is an internal error
-                    _ => error!("Received {:?} at {:?}; Expected a keyword", id_token.token_type, id_token.position)
+                    _ => error!("Received {:?} at {}; Expected a keyword", id_token.token_type, id_token.position)
                 }
             },
             // Nonterminal should never be reached
@@ -557,21 +842,30 @@ impl SemanticAnalyzer {
         if new_id.is_some() && new_type.is_some() {
             let cur_scope = self.symbol_table.cur_scope.unwrap().to_owned();
             // Attempt to add the new id to the symbol table
-            let new_id_res: bool = self.symbol_table.new_identifier(new_id.as_ref().unwrap().to_owned(), new_type.as_ref().unwrap().to_owned(), new_id_pos);
-            
+            let new_decl_id: Option<DeclId> = self.symbol_table.new_identifier(new_id.as_ref().unwrap().to_owned(), new_type.as_ref().unwrap().to_owned(), new_id_span);
+            let new_id_res: bool = new_decl_id.is_some();
+            if new_id_res {
+                self.decl_ids.insert(neighbors[0], new_decl_id.unwrap());
+            }
+
+            // A redeclaration still gets its own position so it is not linked to the first declaration
+            self.identifier_refs.insert(neighbors[0], IdentifierReference {
+                kind: ReferenceKind::Declaration,
+                declaration: if new_id_res { Some((cur_scope, new_id_span.start)) } else { None }
+            });
+
             // Throw an error if the id wasn't added to the symbol table
             if new_id_res == false {
-                nexus_log::log(
-                    nexus_log::LogTypes::Error,
-                    nexus_log::LogSources::SemanticAnalyzer,
-                    format!("Error at {:?}; Id [ {} ] has already been declared within the current scope", new_id_pos, new_id.unwrap())
+                self.report_error(
+                    ErrorClass::Redeclaration,
+                    new_id_span.start,
+                    Message::new(MessageCode::Redeclaration, vec![("position", new_id_span.start.to_message_param()), ("id", new_id.unwrap().to_owned())])
                 );
-                self.num_errors += 1;
             } else {
                 nexus_log::log(
                     nexus_log::LogTypes::Debug,
-                    nexus_log::LogSources::SemanticAnalyzer,
-                    format!("Id [ {} ] of type {:?} has been declared at {:?} in scope {}", new_id.unwrap(), new_type.unwrap(), new_id_pos, cur_scope)
+                    nexus_log::LogSource::SEMANTIC_ANALYZER,
+                    format!("Id [ {} ] of type {:?} has been declared at {} in scope {}", new_id.unwrap(), new_type.unwrap(), new_id_span.start, cur_scope)
                 );
             }
         }
@@ -580,7 +874,9 @@ impl SemanticAnalyzer {
     fn analyze_assignment(&mut self, ast: &SyntaxTree, neighbors: &Vec<NodeIndex>) {
         // Index 1 should be the id token
         let id_node: &SyntaxTreeNode = (*ast).graph.node_weight(neighbors[1]).unwrap();
-        let mut id_info: Option<(Type, String, bool, bool, (usize, usize), (usize, usize))> = None;
+        let mut id_info: Option<(Type, String, bool, bool, Position, Position)> = None;
+        let mut declaration: Option<(usize, Position)> = None;
+        let mut decl_id: Option<DeclId> = None;
 
         match id_node {
             // We assume this is an identifier because of the grammar and the AST
@@ -590,15 +886,17 @@ impl SemanticAnalyzer {
                 // Get the id result
                 let id_res: Option<&SymbolTableEntry> = self.get_identifier(&id_token);
                 if id_res.is_some() {
+                    declaration = Some((id_res.unwrap().scope.to_owned(), id_res.unwrap().span.start));
+                    decl_id = Some(id_res.unwrap().decl_id.to_owned());
                     // Collect copies of a bunch of information to prevent rust borrow errors
                     id_info = Some((id_res.unwrap().symbol_type.to_owned(), id_token.text.to_owned(),
                                     id_res.unwrap().is_initialized.to_owned(), id_res.unwrap().is_used.to_owned(),
-                                    id_res.unwrap().position.to_owned(), id_token.position.to_owned()));
+                                    id_res.unwrap().span.start, id_token.position.to_owned()));
 
                     nexus_log::log(
                         nexus_log::LogTypes::Debug,
-                        nexus_log::LogSources::SemanticAnalyzer,
-                        format!("Id [ {} ] declared in scope {} at position {:?} is valid at {:?} in scope {}",
+                        nexus_log::LogSource::SEMANTIC_ANALYZER,
+                        format!("Id [ {} ] declared in scope {} at {} is valid at {} in scope {}",
                                 id_token.text, id_res.unwrap().scope, id_info.as_ref().unwrap().4, id_token.position, cur_scope)
                     );
 
@@ -608,23 +906,30 @@ impl SemanticAnalyzer {
             SyntaxTreeNode::NonTerminalAst(_) => error!("Received a nonterminal when expecting a terminal to Assign"),
             SyntaxTreeNode::NonTerminalCst(_) => error!("Found a CST node in the AST")
         }
+        self.identifier_refs.insert(neighbors[1], IdentifierReference { kind: ReferenceKind::Write, declaration: declaration });
+        if decl_id.is_some() {
+            self.decl_ids.insert(neighbors[1], decl_id.unwrap());
+        }
 
         // Index 0 is the value being assigned
         let right_entry = self.derive_type(ast, neighbors[0]);
 
         // If both sides check out, then we can compare types
         if id_info.is_some() && right_entry.is_some() {
-            let id_info_real: (Type, String, bool, bool, (usize, usize), (usize, usize)) = id_info.unwrap();
-            let right_entry_real: (Type, (usize, usize)) = right_entry.unwrap();
+            let id_info_real: (Type, String, bool, bool, Position, Position) = id_info.unwrap();
+            let right_entry_real: (Type, Position) = right_entry.unwrap();
             
             // Compare the types and throw and error if they do not line up
             if id_info_real.0.ne(&right_entry_real.0) {
-                nexus_log::log(
-                    nexus_log::LogTypes::Error,
-                    nexus_log::LogSources::SemanticAnalyzer,
-                    format!("Mismatched types at {:?}; Expected {:?} for the assignment type, but received {:?}", right_entry_real.1, id_info_real.0, right_entry_real.0)
+                self.report_error(
+                    ErrorClass::TypeMismatch,
+                    right_entry_real.1,
+                    Message::new(MessageCode::AssignmentTypeMismatch, vec![
+                        ("position", right_entry_real.1.to_message_param()),
+                        ("expected", format!("{:?}", id_info_real.0)),
+                        ("received", format!("{:?}", right_entry_real.0))
+                    ])
                 );
-                self.num_errors += 1;
             } else {
                 // The variable has now been assigned a value, so make sure it is
                 // updated in the symbol table if it has not been done so already
@@ -633,27 +938,31 @@ impl SemanticAnalyzer {
                
                     nexus_log::log(
                         nexus_log::LogTypes::Debug,
-                        nexus_log::LogSources::SemanticAnalyzer,
-                        format!("Id [ {} ] declared at {:?} of type {:?} has been initialized with a value of type {:?} at position {:?}",
+                        nexus_log::LogSource::SEMANTIC_ANALYZER,
+                        format!("Id [ {} ] declared at {} of type {:?} has been initialized with a value of type {:?} at {}",
                                 id_info_real.1, id_info_real.4, id_info_real.0, right_entry_real.0, id_info_real.5)
                     );
 
                     // Throw a warning for the variable being initialized here because
                     // it was already used
                     if id_info_real.3 == true {
-                        nexus_log::log(
+                        nexus_log::log_message(
                             nexus_log::LogTypes::Warning,
-                            nexus_log::LogSources::SemanticAnalyzer,
-                            format!("Warning at {:?}; Id [ {} ] declared at {:?} is being initialized after already being used",
-                                    id_info_real.5, id_info_real.1, id_info_real.4)
+                            nexus_log::LogSource::SEMANTIC_ANALYZER,
+                            id_info_real.5,
+                            &Message::new(MessageCode::InitializedAfterUse, vec![
+                                ("position", id_info_real.5.to_message_param()),
+                                ("id", id_info_real.1.to_owned()),
+                                ("declared_position", id_info_real.4.to_message_param())
+                            ])
                         );
                         self.num_warnings += 1;
                     }
                 } else {
                     nexus_log::log(
                         nexus_log::LogTypes::Debug,
-                        nexus_log::LogSources::SemanticAnalyzer,
-                        format!("Id [ {} ] declared at {:?} of type {:?} has been assigned a value of type {:?} at position {:?}",
+                        nexus_log::LogSource::SEMANTIC_ANALYZER,
+                        format!("Id [ {} ] declared at {} of type {:?} has been assigned a value of type {:?} at {}",
                                 id_info_real.1, id_info_real.4, id_info_real.0, right_entry_real.0, id_info_real.5)
                     );
                 }
@@ -663,62 +972,51 @@ impl SemanticAnalyzer {
 
     // Gets a symbol table entry for an identifier, or None if it does not exist
     fn get_identifier(&mut self, id_token: &Token) -> Option<&SymbolTableEntry> {
-        let symbol_table_entry: Option<&SymbolTableEntry> = self.symbol_table.get_symbol(&id_token.text);
-
-        if symbol_table_entry.is_none() {
+        if self.symbol_table.get_symbol(&id_token.text).is_none() {
             // Throw an error from the undeclared identifier
-            nexus_log::log(
-                nexus_log::LogTypes::Error,
-                nexus_log::LogSources::SemanticAnalyzer,
-                format!("Error at {:?}; Id [ {} ] has not been declared", id_token.position, id_token.text)
+            self.report_error(
+                ErrorClass::UndeclaredId,
+                id_token.position,
+                Message::new(MessageCode::UndeclaredId, vec![("position", id_token.position.to_message_param()), ("id", id_token.text.to_owned())])
             );
-            self.num_errors += 1;
+            return None;
         }
-        return symbol_table_entry;
+        return self.symbol_table.get_symbol(&id_token.text);
     }
 
     // Function that analyzes an add statement
-    fn analyze_add(&mut self, ast: &SyntaxTree, neighbors: &Vec<NodeIndex>) -> Option<(Type, (usize, usize))> {
-        // Index 1 will always be a digit, so that is by default an Int
-        // Only have to check index 0 of neighbors, which can be a nonterminal
-    
+    fn analyze_add(&mut self, ast: &SyntaxTree, neighbors: &Vec<NodeIndex>) -> Option<(Type, Position)> {
+        // Index 1 is the left side, which is a digit or a grouped addition, so it is always an Int
+        // Its type is still derived so a grouped addition gets analyzed, and it gives the position of the expression
+        let left_res: Option<(Type, Position)> = self.derive_type(ast, neighbors[1]);
+
         // Get the type of the right hand side, which can be any expression
-        let right_res: Option<(Type, (usize, usize))> = self.derive_type(ast, neighbors[0]);
+        let right_res: Option<(Type, Position)> = self.derive_type(ast, neighbors[0]);
 
-        if right_res.is_some() {
-            let right_res_real: (Type, (usize, usize)) = right_res.unwrap();
+        if left_res.is_some() && right_res.is_some() {
+            let left_position: Position = left_res.unwrap().1;
+            let right_res_real: (Type, Position) = right_res.unwrap();
 
             // Since the left is already an int, we have to make sure the right is an int too
             if right_res_real.0.ne(&Type::Int) {
-                nexus_log::log(
-                    nexus_log::LogTypes::Error,
-                    nexus_log::LogSources::SemanticAnalyzer,
-                    format!("Error at {:?}; Expected {:?} for the addition expression, but received {:?}", right_res_real.1, Type::Int, right_res_real.0)
+                self.report_error(
+                    ErrorClass::TypeMismatch,
+                    right_res_real.1,
+                    Message::new(MessageCode::AdditionTypeMismatch, vec![
+                        ("position", right_res_real.1.to_message_param()),
+                        ("expected", format!("{:?}", Type::Int)),
+                        ("received", format!("{:?}", right_res_real.0))
+                    ])
                 );
-                self.num_errors += 1;
                 return None;
             } else {
                 nexus_log::log(
                     nexus_log::LogTypes::Debug,
-                    nexus_log::LogSources::SemanticAnalyzer,
-                    format!("Correctly received expression of type {:?} for right side of addition operator at position {:?}",
+                    nexus_log::LogSource::SEMANTIC_ANALYZER,
+                    format!("Correctly received expression of type {:?} for right side of addition operator at {}",
                             right_res_real.0, right_res_real.1)
                 );
 
-                // Get the left side node of the addition for its position
-                let left_node: &SyntaxTreeNode = (*ast).graph.node_weight(neighbors[1]).unwrap();
-                let mut left_position: (usize, usize) = (0, 0);
-
-                match &left_node {
-                    SyntaxTreeNode::Terminal(token) => {
-                        // Grab the position of the token
-                        // Parse already made sure it is a digit
-                        left_position = token.position.to_owned();
-                    },
-                    SyntaxTreeNode::NonTerminalAst(non_terminal) => error!("Received [ {:?} ] as a value for addition; Expected a terminal", non_terminal),
-                    SyntaxTreeNode::NonTerminalCst(_) => error!("Found CST node in the AST")
-                }
-
                 return Some((right_res_real.0, left_position));
             }
         } else {
@@ -726,35 +1024,256 @@ impl SemanticAnalyzer {
         }
     }
 
-    pub fn analyze_eq_neq(&mut self, ast: &SyntaxTree, neighbors: &Vec<NodeIndex>) -> Option<(Type, (usize, usize))>{
+    fn analyze_index(&mut self, ast: &SyntaxTree, neighbors: &Vec<NodeIndex>) -> Option<(Type, Position)> {
+        // Index 1 is the string and index 0 is the index into it
+        let string_res: Option<(Type, Position)> = self.derive_type(ast, neighbors[1]);
+        let index_res: Option<(Type, Position)> = self.derive_type(ast, neighbors[0]);
+
+        if string_res.is_none() || index_res.is_none() {
+            return None;
+        }
+        let (string_type, string_position): (Type, Position) = string_res.unwrap();
+        let (index_type, index_position): (Type, Position) = index_res.unwrap();
+
+        // Only strings can be indexed, and only with an integer
+        let mut is_valid: bool = true;
+        for (part, expected, received, position) in [("string", Type::String, string_type, string_position), ("index", Type::Int, index_type, index_position)] {
+            if received.ne(&expected) {
+                self.report_error(
+                    ErrorClass::TypeMismatch,
+                    position,
+                    Message::new(MessageCode::IndexTypeMismatch, vec![
+                        ("position", position.to_message_param()),
+                        ("expected", format!("{:?}", expected)),
+                        ("part", String::from(part)),
+                        ("received", format!("{:?}", received))
+                    ])
+                );
+                is_valid = false;
+            }
+        }
+        if !is_valid {
+            return None;
+        }
+
+        // A constant index into a string literal can be checked before the program runs
+        if let (SyntaxTreeNode::Terminal(Token { token_type: TokenType::Char(string), .. }), Some(index)) = ((*ast).graph.node_weight(neighbors[1]).unwrap(), self.get_constant_int(ast, neighbors[0])) {
+            if index as usize >= string.len() {
+                self.report_error(
+                    ErrorClass::IndexOutOfBounds,
+                    index_position,
+                    Message::new(MessageCode::IndexOutOfBounds, vec![
+                        ("position", index_position.to_message_param()),
+                        ("index", index.to_string()),
+                        ("string", string.to_owned()),
+                        ("length", string.len().to_string())
+                    ])
+                );
+                return None;
+            }
+        }
+
+        return Some((Type::Char, string_position));
+    }
+
+    fn analyze_len(&mut self, ast: &SyntaxTree, neighbors: &Vec<NodeIndex>) -> Option<(Type, Position)> {
+        // The only child is the string
+        let string_res: Option<(Type, Position)> = self.derive_type(ast, neighbors[0]);
+        if string_res.is_none() {
+            return None;
+        }
+        let (string_type, string_position): (Type, Position) = string_res.unwrap();
+
+        if string_type.ne(&Type::String) {
+            self.report_error(
+                ErrorClass::TypeMismatch,
+                string_position,
+                Message::new(MessageCode::LenTypeMismatch, vec![
+                    ("position", string_position.to_message_param()),
+                    ("received", format!("{:?}", string_type))
+                ])
+            );
+            return None;
+        }
+
+        return Some((Type::Int, string_position));
+    }
+
+    // Function to get the value of an integer expression that only has digits, which wraps around like it does when the program runs
+    fn get_constant_int(&self, ast: &SyntaxTree, node_index: NodeIndex) -> Option<u32> {
+        let mut constant_sum: u32 = 0;
+        for operand_index in operand::get_add_operands(ast, node_index) {
+            match (*ast).graph.node_weight(operand_index).unwrap() {
+                SyntaxTreeNode::Terminal(Token { token_type: TokenType::Digit(num), .. }) => constant_sum += *num as u32,
+                _ => return None
+            }
+        }
+        return Some(constant_sum % (self.target_config.max_int + 1));
+    }
+
+    // Function to determine if an addition node is an operand of another addition
+    fn is_nested_add(&self, ast: &SyntaxTree, node_index: NodeIndex) -> bool {
+        let parent: Option<NodeIndex> = (*ast).graph.neighbors_directed(node_index, Direction::Incoming).next();
+        if parent.is_some() {
+            match (*ast).graph.node_weight(parent.unwrap()).unwrap() {
+                SyntaxTreeNode::NonTerminalAst(NonTerminalsAst::Add) => return true,
+                _ => return false
+            }
+        } else {
+            return false;
+        }
+    }
+
+    // Function to warn if the constant operands of an addition expression exceed the integer width of the target
+    fn check_int_overflow(&mut self, ast: &SyntaxTree, node_index: NodeIndex) {
+        // Grouped additions can be on either side, so get every operand of the expression in order
+        let operand_indices: Vec<NodeIndex> = operand::get_add_operands(ast, node_index);
+        let operands: Vec<Token> = operand_indices.iter()
+            .filter_map(|operand_index| match (*ast).graph.node_weight(*operand_index).unwrap() {
+                SyntaxTreeNode::Terminal(token) => Some(token.to_owned()),
+                _ => None
+            })
+            .collect();
+        if operands.len() == 0 {
+            return;
+        }
+
+        let mut constant_sum: u32 = 0;
+        // Lengths are not known at compile time either
+        let mut is_fully_constant: bool = operands.len() == operand_indices.len();
+        for operand in operands.iter() {
+            match &operand.token_type {
+                TokenType::Digit(num) => constant_sum += *num as u32,
+                // Identifiers are not known at compile time
+                _ => is_fully_constant = false
+            }
+        }
+        let operands_span: Span = operands[0].get_span().merge(&operands[operands.len() - 1].get_span());
+
+        if constant_sum > self.target_config.max_int {
+            let code: MessageCode = match is_fully_constant {
+                true => MessageCode::ConstantOverflow,
+                false => MessageCode::OperandsOverflow
+            };
+
+            nexus_log::log_message(
+                nexus_log::LogTypes::Warning,
+                nexus_log::LogSource::SEMANTIC_ANALYZER,
+                operands_span.start,
+                &Message::new(code, vec![
+                    ("span", operands_span.to_message_param()),
+                    ("sum", constant_sum.to_string()),
+                    ("max", self.target_config.max_int.to_string()),
+                    ("target", self.target_config.target.to_string())
+                ])
+            );
+            self.num_warnings += 1;
+        }
+    }
+
+    // Function to report a string literal that is longer than the target can store or the class allows
+    // Checking here means the code generators never have to run out of heap or cut off the length partway through
+    fn check_string_length(&mut self, string: &str, position: Position) {
+        let max_length: usize = self.target_config.get_max_string_length();
+        if string.len() > max_length {
+            self.report_error(
+                ErrorClass::StringTooLong,
+                position,
+                Message::new(MessageCode::StringTooLong, vec![
+                    ("position", position.to_message_param()),
+                    ("string", string.to_owned()),
+                    ("length", string.len().to_string()),
+                    ("max_length", max_length.to_string()),
+                    ("target", self.target_config.target.to_string())
+                ])
+            );
+        }
+    }
+
+    // Function to record how deep the block is and warn if it is past the nesting limit
+    fn analyze_block_depth(&mut self, ast: &SyntaxTree, block_index: NodeIndex) {
+        self.block_depths.insert(block_index, self.cur_block_depth);
+
+        // Only the block that first goes past the limit gets the warning, not every block inside of it
+        if self.cur_block_depth == self.target_config.max_nesting_depth + 1 {
+            let position: Position = ast.get_node_position(block_index.index()).unwrap_or(Position::new(1, 1));
+            nexus_log::log_message(
+                nexus_log::LogTypes::Warning,
+                nexus_log::LogSource::SEMANTIC_ANALYZER,
+                position,
+                &Message::new(MessageCode::DeepNesting, vec![
+                    ("position", position.to_message_param()),
+                    ("depth", self.cur_block_depth.to_string()),
+                    ("max_depth", self.target_config.max_nesting_depth.to_string())
+                ])
+            );
+            self.num_warnings += 1;
+        }
+    }
+
+    // Function to check if the node is a single identifier
+    fn is_identifier(&self, ast: &SyntaxTree, node_index: NodeIndex) -> bool {
+        return match (*ast).graph.node_weight(node_index).unwrap() {
+            SyntaxTreeNode::Terminal(token) => {
+                match &token.token_type {
+                    TokenType::Identifier(_) => true,
+                    _ => false
+                }
+            },
+            _ => false
+        };
+    }
+
+    pub fn analyze_eq_neq(&mut self, ast: &SyntaxTree, neighbors: &Vec<NodeIndex>) -> Option<(Type, Position)>{
         // Get the type for the left side of the boolean operator
-        let left_entry: Option<(Type, (usize, usize))> = self.derive_type(ast, neighbors[1]);
+        let left_entry: Option<(Type, Position)> = self.derive_type(ast, neighbors[1]);
 
         // Get the type for the right side of the boolean operator
-        let right_entry: Option<(Type, (usize, usize))> = self.derive_type(ast, neighbors[0]);
+        let right_entry: Option<(Type, Position)> = self.derive_type(ast, neighbors[0]);
 
         if left_entry.is_some() && right_entry.is_some() {
             // Unwrap both entries
-            let left_entry_real: (Type, (usize, usize)) = left_entry.unwrap();
-            let right_entry_real: (Type, (usize, usize)) = right_entry.unwrap();
+            let left_entry_real: (Type, Position) = left_entry.unwrap();
+            let right_entry_real: (Type, Position) = right_entry.unwrap();
 
             if left_entry_real.0.ne(&right_entry_real.0) {
                 // Throw an error if the types do not match
-                nexus_log::log(
-                    nexus_log::LogTypes::Error,
-                    nexus_log::LogSources::SemanticAnalyzer,
-                    format!("Error at {:?}; Mismatched types for boolean expression; Received {:?} on the left side and {:?} on the right side",
-                            left_entry_real.1, left_entry_real.0, right_entry_real.0)
+                self.report_error(
+                    ErrorClass::TypeMismatch,
+                    left_entry_real.1,
+                    Message::new(MessageCode::BooleanTypeMismatch, vec![
+                        ("position", left_entry_real.1.to_message_param()),
+                        ("left", format!("{:?}", left_entry_real.0)),
+                        ("right", format!("{:?}", right_entry_real.0))
+                    ])
                 );
-                self.num_errors += 1;
                 return None;
             } else {
                 nexus_log::log(
                     nexus_log::LogTypes::Debug,
-                    nexus_log::LogSources::SemanticAnalyzer,
-                    format!("Comparing expressions of type {:?} (position {:?}) and type {:?} (position {:?})",
+                    nexus_log::LogSource::SEMANTIC_ANALYZER,
+                    format!("Comparing expressions of type {:?} ({}) and type {:?} ({})",
                             left_entry_real.0, left_entry_real.1, right_entry_real.0, right_entry_real.1)
                 );
+
+                // Strings are compared by their address, so let the user know when a variable is involved
+                if left_entry_real.0.eq(&Type::String) && (self.is_identifier(ast, neighbors[1]) || self.is_identifier(ast, neighbors[0])) {
+                    let comparison_span: Span = match (ast.get_node_span(neighbors[1]), ast.get_node_span(neighbors[0])) {
+                        (Some(left_span), Some(right_span)) => left_span.merge(&right_span),
+                        _ => Span::new(left_entry_real.1, right_entry_real.1)
+                    };
+                    nexus_log::log_message(
+                        nexus_log::LogTypes::Warning,
+                        nexus_log::LogSource::SEMANTIC_ANALYZER,
+                        comparison_span.start,
+                        &Message::new(MessageCode::StringComparison, vec![
+                            ("span", comparison_span.to_message_param()),
+                            ("target", self.target_config.target.to_string())
+                        ])
+                    );
+                    self.num_warnings += 1;
+                }
+
                 // Otherwise, we have a boolean result from the expression
                 return Some((Type::Boolean, left_entry_real.1));
             }
@@ -0,0 +1,174 @@
+use petgraph::graph::NodeIndex;
+use serde::Serialize;
+#[cfg(feature = "ui")]
+use wasm_bindgen::prelude::*;
+
+use crate::nexus::{preprocessor::Preprocessor, lexer::Lexer, token::Token, parser::Parser, semantic_analyzer::{SemanticAnalyzer, SemanticAnalysisResult}, syntax_tree::SyntaxTree, syntax_tree_node::SyntaxTreeNode, symbol_table::SymbolTableJsonEntry};
+use crate::util::{nexus_log, target::{Target, TargetConfig}, position::Position};
+
+// Have to import the editor js module
+#[cfg(feature = "ui")]
+#[wasm_bindgen(module = "/editor.js")]
+extern "C" {
+    #[wasm_bindgen(js_name = "highlightSemanticTokens")]
+    fn highlight_semantic_tokens(tokens_json: &str);
+}
+
+// How an identifier in the AST is used, which is found while it is being analyzed
+#[derive (Debug, Clone, Copy)]
+pub enum ReferenceKind {
+    Declaration,
+    // The value is read, and is_initialized is if it had been assigned a value at that point
+    Read { is_initialized: bool },
+    // The identifier is the left side of an assignment
+    Write
+}
+
+// An entry of the reference index, which links an identifier in the AST to its declaration
+#[derive (Debug, Clone)]
+pub struct IdentifierReference {
+    pub kind: ReferenceKind,
+
+    // The (scope, position) of the declaration, or None if the identifier was never declared
+    pub declaration: Option<(usize, Position)>
+}
+
+// The classes of identifiers that the editor colors differently
+#[derive (Debug, Clone, Copy, PartialEq, Serialize)]
+#[serde (rename_all = "camelCase")]
+pub enum SemanticTokenKind {
+    Declaration,
+    UnusedDeclaration,
+    InitializedUse,
+    UninitializedUse,
+    Assignment,
+    Undeclared
+}
+
+// An identifier in the source code and its class
+#[derive (Debug, Clone, Serialize)]
+#[serde (rename_all = "camelCase")]
+pub struct SemanticToken {
+    pub line: usize,
+    pub column: usize,
+    pub length: usize,
+    pub id: String,
+    pub kind: SemanticTokenKind,
+
+    // The position of the declaration the identifier refers to
+    pub declaration: Option<Position>
+}
+
+// The semantic tokens of a single program
+#[derive (Debug, Serialize)]
+#[serde (rename_all = "camelCase")]
+pub struct ProgramSemanticTokens {
+    pub program_number: u32,
+    pub tokens: Vec<SemanticToken>,
+
+    // The phase that failed before the program could be analyzed
+    pub error: Option<String>
+}
+
+// Function to classify every identifier in the analyzed program, in order of position
+// Declarations can only be known to be unused after the whole program is analyzed, so this runs afterwards
+pub fn get_semantic_tokens(semantic_analysis_res: &SemanticAnalysisResult) -> Vec<SemanticToken> {
+    let symbol_entries: Vec<SymbolTableJsonEntry> = semantic_analysis_res.symbol_table.get_json_entries();
+
+    let mut semantic_tokens: Vec<SemanticToken> = semantic_analysis_res.identifier_refs.iter()
+        .filter_map(|(node_index, reference)| {
+            let token: &Token = get_token(&semantic_analysis_res.ast, *node_index)?;
+            let kind: SemanticTokenKind = match (reference.kind, reference.declaration) {
+                (ReferenceKind::Declaration, Some((scope, position))) => {
+                    // A redeclaration is not in the symbol table, so it cannot be unused
+                    let is_unused: bool = symbol_entries.iter()
+                        .any(|entry| entry.scope == scope && entry.position == position && !entry.is_used);
+                    if is_unused { SemanticTokenKind::UnusedDeclaration } else { SemanticTokenKind::Declaration }
+                },
+                (ReferenceKind::Declaration, None) => SemanticTokenKind::Declaration,
+                (_, None) => SemanticTokenKind::Undeclared,
+                (ReferenceKind::Read { is_initialized: true }, _) => SemanticTokenKind::InitializedUse,
+                (ReferenceKind::Read { is_initialized: false }, _) => SemanticTokenKind::UninitializedUse,
+                (ReferenceKind::Write, _) => SemanticTokenKind::Assignment
+            };
+
+            return Some(SemanticToken {
+                line: token.position.line,
+                column: token.position.col,
+                length: token.text.len(),
+                id: token.text.to_owned(),
+                kind: kind,
+                declaration: reference.declaration.map(|(_, position)| position)
+            });
+        })
+        .collect();
+
+    semantic_tokens.sort_by_key(|semantic_token| (semantic_token.line, semantic_token.column));
+    return semantic_tokens;
+}
+
+fn get_token(ast: &SyntaxTree, node_index: NodeIndex) -> Option<&Token> {
+    return match (*ast).graph.node_weight(node_index) {
+        Some(SyntaxTreeNode::Terminal(token)) => Some(token),
+        _ => None
+    };
+}
+
+// Function to color the identifiers in the editor, which replaces the colors from the last compilation
+#[cfg(feature = "ui")]
+pub fn display_semantic_tokens(semantic_tokens: &Vec<SemanticToken>) {
+    highlight_semantic_tokens(&serde_json::to_string(semantic_tokens).expect("Should be able to serialize the semantic tokens"));
+}
+
+#[cfg(feature = "ui")]
+pub fn clear_display() {
+    highlight_semantic_tokens("[]");
+}
+
+// Function to get the semantic tokens of every program in the source code as JSON without showing any logs
+// Programs that do not lex or parse do not have any tokens, but the ones with semantic errors still do
+#[cfg_attr(feature = "ui", wasm_bindgen(js_name = "getSemanticTokens"))]
+pub fn get_semantic_tokens_json(source_code: &str) -> String {
+    return serde_json::to_string(&get_program_semantic_tokens(source_code)).expect("Should be able to serialize the semantic tokens");
+}
+
+pub fn get_program_semantic_tokens(source_code: &str) -> Vec<ProgramSemanticTokens> {
+    nexus_log::start_capture();
+
+    let mut results: Vec<ProgramSemanticTokens> = Vec::new();
+    let mut preprocessor: Preprocessor = Preprocessor::new();
+    let preprocess_res: Result<String, ()> = preprocessor.expand_defines(source_code);
+
+    if preprocess_res.is_ok() {
+        let mut lexer: Lexer = Lexer::new(&preprocess_res.unwrap());
+        lexer.set_column_map(preprocessor.get_column_map());
+        let mut parser: Parser = Parser::new();
+        let mut semantic_analyzer: SemanticAnalyzer = SemanticAnalyzer::new(TargetConfig::new(Target::Target6502));
+
+        let mut program_number: u32 = 0;
+        while lexer.has_program_to_lex() {
+            program_number += 1;
+            let mut result: ProgramSemanticTokens = ProgramSemanticTokens {
+                program_number: program_number,
+                tokens: Vec::new(),
+                error: None
+            };
+
+            match lexer.lex_program() {
+                Ok(token_stream) => {
+                    if parser.parse_program(&token_stream).is_ok() {
+                        let ast: SyntaxTree = semantic_analyzer.generate_ast(&token_stream);
+                        result.tokens = get_semantic_tokens(&semantic_analyzer.analyze_program(ast));
+                    } else {
+                        result.error = Some(String::from("Parse failed"));
+                    }
+                },
+                Err(_) => result.error = Some(String::from("Lex failed"))
+            }
+            results.push(result);
+        }
+    }
+
+    nexus_log::stop_capture();
+    return results;
+}
@@ -0,0 +1,147 @@
+use std::collections::{HashMap, HashSet};
+
+use crate::nexus::{coverage::CoverageJson, syntax_tree::SyntaxTree, syntax_tree_node::*};
+use crate::util::position::Position;
+
+use petgraph::graph::NodeIndex;
+use serde::Serialize;
+
+// A range of generated code that came from a single statement
+#[derive (Debug, Clone, Serialize)]
+#[serde (rename_all = "camelCase")]
+pub struct SourceMapping {
+    // The range [start, end) of the generated code
+    pub generated_start: usize,
+    pub generated_end: usize,
+
+    // The position in the source code of the first token of the statement
+    pub line: usize,
+    pub column: usize,
+
+    // The statement in the AST, which matches the id in the AST JSON
+    pub node_id: usize,
+    pub kind: String
+}
+
+// The code of a block and the scope the symbol table gave it
+#[derive (Debug, Clone, Serialize)]
+#[serde (rename_all = "camelCase")]
+pub struct ScopeExtent {
+    pub scope: usize,
+
+    // The range [start, end) of the generated code, which includes every block inside of it
+    pub generated_start: usize,
+    pub generated_end: usize,
+
+    // The position of the left brace of the block
+    pub line: usize,
+    pub column: usize
+}
+
+// The source map artifact for a program, which maps the generated code back to the source code
+#[derive (Debug, Clone, Serialize)]
+#[serde (rename_all = "camelCase")]
+pub struct SourceMapJson {
+    pub version: u32,
+    pub target: String,
+
+    // Addresses of the image for 6502 and lines of the assembly text (starting at 1) for RISC-V
+    pub unit: String,
+
+    // The mappings in order of the generated code, which do not overlap
+    pub mappings: Vec<SourceMapping>,
+
+    // The blocks in order of the generated code, which are only there with the scope comments option
+    #[serde (skip_serializing_if = "Vec::is_empty")]
+    pub scopes: Vec<ScopeExtent>
+}
+
+impl SourceMapJson {
+    // Creates the source map from the coverage of the program
+    // The offset is added to every generated position, such as the lines of the header before the RISC-V instructions
+    // The scope ranges are (scope, block, start, end) from the code generator
+    pub fn new(target: &str, unit: &str, ast: &SyntaxTree, coverage: &CoverageJson, scope_ranges: &Vec<(usize, NodeIndex, usize, usize)>, generated_offset: usize) -> Self {
+        // Blocks only hold other statements, so the code always belongs to a statement inside of them
+        let statements: Vec<(usize, usize, NodeIndex, Position)> = coverage.nodes.iter()
+            .map(|node| (node.start, node.end, NodeIndex::new(node.id)))
+            .filter(|(start, end, node_index)| start < end && !matches!((*ast).graph.node_weight(*node_index).unwrap(), SyntaxTreeNode::NonTerminalAst(NonTerminalsAst::Block)))
+            .filter_map(|(start, end, node_index)| get_first_position(ast, node_index).map(|position| (start, end, node_index, position)))
+            .collect();
+
+        let code_end: usize = statements.iter().map(|(_, end, _, _)| *end).max().unwrap_or(0);
+
+        // Each part of the code belongs to the smallest statement around it, which is the most nested one
+        let mut mappings: Vec<SourceMapping> = Vec::new();
+        for generated_index in 0..code_end {
+            let innermost: Option<&(usize, usize, NodeIndex, Position)> = statements.iter()
+                .filter(|(start, end, _, _)| *start <= generated_index && generated_index < *end)
+                .min_by_key(|(start, end, _, _)| end - start);
+            if innermost.is_none() {
+                continue;
+            }
+
+            let (_, _, node_index, position) = innermost.unwrap();
+            let generated_position: usize = generated_index + generated_offset;
+
+            // Extend the last mapping if it is the same statement and right before this one
+            match mappings.last_mut() {
+                Some(last_mapping) if last_mapping.node_id == node_index.index() && last_mapping.generated_end == generated_position => {
+                    last_mapping.generated_end += 1;
+                },
+                _ => mappings.push(SourceMapping {
+                    generated_start: generated_position,
+                    generated_end: generated_position + 1,
+                    line: position.line,
+                    column: position.col,
+                    node_id: node_index.index(),
+                    kind: format!("{:?}", (*ast).graph.node_weight(*node_index).unwrap())
+                })
+            }
+        }
+
+        // Outer blocks start first, so they come before the blocks inside of them
+        let mut scopes: Vec<ScopeExtent> = scope_ranges.iter()
+            .map(|(scope, block_index, start, end)| {
+                let position: Position = ast.get_node_position(block_index.index()).unwrap_or(Position::new(1, 1));
+                ScopeExtent {
+                    scope: *scope,
+                    generated_start: start + generated_offset,
+                    generated_end: end + generated_offset,
+                    line: position.line,
+                    column: position.col
+                }
+            })
+            .collect();
+        scopes.sort_by_key(|scope_extent| (scope_extent.generated_start, scope_extent.scope));
+
+        return SourceMapJson {
+            version: 1,
+            target: String::from(target),
+            unit: String::from(unit),
+            mappings: mappings,
+            scopes: scopes
+        };
+    }
+
+    pub fn to_json(&self) -> String {
+        return serde_json::to_string(self).expect("Should be able to serialize the source map");
+    }
+}
+
+// Function to get the earliest position of any token in the subtree
+fn get_first_position(ast: &SyntaxTree, cur_index: NodeIndex) -> Option<Position> {
+    return match (*ast).graph.node_weight(cur_index).unwrap() {
+        SyntaxTreeNode::Terminal(token) => Some(token.position),
+        _ => (*ast).graph.neighbors(cur_index).filter_map(|child_index| get_first_position(ast, child_index)).min()
+    };
+}
+
+// Function to get the code of each block with its scope (scope, block, start, end) from the coverage ranges of either backend
+pub fn get_scope_ranges(coverage_ranges: &Vec<(NodeIndex, usize, usize)>, block_scopes: &HashMap<NodeIndex, usize>) -> Vec<(usize, NodeIndex, usize, usize)> {
+    // Blocks inside of another block are also recorded as one of its statements, so each block is only taken once
+    let mut seen_blocks: HashSet<NodeIndex> = HashSet::new();
+    return coverage_ranges.iter()
+        .filter(|(node_index, _, _)| seen_blocks.insert(*node_index))
+        .filter_map(|(node_index, start, end)| block_scopes.get(node_index).map(|scope| (*scope, *node_index, *start, *end)))
+        .collect();
+}
@@ -0,0 +1,83 @@
+use serde::{Serialize, Deserialize};
+use web_sys::{Window, Document, Element};
+
+use crate::nexus::{artifacts, semantic_analyzer::SemanticAnalysisResult};
+
+// Counts that describe a program, where each one is None if compilation did not get far enough to know it
+#[derive (Debug, Default, Clone, Serialize, Deserialize)]
+#[serde (rename_all = "camelCase", default)]
+pub struct ProgramStatistics {
+    pub program_number: u32,
+    pub token_count: Option<usize>,
+    pub cst_node_count: Option<usize>,
+    pub ast_node_count: Option<usize>,
+    pub scope_count: Option<usize>,
+    pub identifier_count: Option<usize>,
+
+    // The most blocks inside of each other, where the outer block of the program is 1
+    pub max_nesting_depth: Option<usize>,
+
+    // Bytes of code in the 6502 image, or instructions in the RISC-V assembly
+    pub code_bytes: Option<usize>,
+    pub instruction_count: Option<usize>,
+
+    // Bytes used by the strings, including the length of each one for RISC-V
+    pub string_heap_bytes: Option<usize>
+}
+
+impl ProgramStatistics {
+    pub fn new(program_number: u32) -> Self {
+        return ProgramStatistics {
+            program_number: program_number,
+            ..Default::default()
+        };
+    }
+
+    // Fills in the counts from the AST and symbol table, which are known even if analysis finds errors
+    pub fn add_semantic_analysis(&mut self, semantic_analysis_res: &SemanticAnalysisResult) {
+        self.ast_node_count = Some(semantic_analysis_res.ast.node_count());
+        self.scope_count = Some(semantic_analysis_res.block_scopes.len());
+        self.identifier_count = Some(semantic_analysis_res.symbol_table.get_json_entries().len());
+        self.max_nesting_depth = Some(semantic_analysis_res.block_depths.values().copied().max().unwrap_or(0));
+    }
+
+    pub fn to_json(&self) -> String {
+        return serde_json::to_string(self).expect("Should be able to serialize the program statistics");
+    }
+}
+
+// Function to show the statistics of every program from the most recent compilation
+pub fn display_statistics() {
+    let window: Window = web_sys::window().expect("Should be able to get the window");
+    let document: Document = window.document().expect("Should be able to get the document");
+    let statistics_table: Element = document.get_element_by_id("statistics-table").expect("Should be able to find the element");
+
+    let mut rows: String = String::from("<tr><th>Program</th><th>Tokens</th><th>CST nodes</th><th>AST nodes</th><th>Scopes</th><th>Identifiers</th>\
+                                        <th>Max nesting</th><th>Code bytes (6502)</th><th>Instructions (RISC-V)</th><th>String heap bytes</th></tr>");
+    for program_number in 1..=artifacts::get_program_count() {
+        let statistics: ProgramStatistics = artifacts::get_program_artifacts(program_number).unwrap().statistics;
+        let cells: Vec<String> = vec![
+            statistics.token_count,
+            statistics.cst_node_count,
+            statistics.ast_node_count,
+            statistics.scope_count,
+            statistics.identifier_count,
+            statistics.max_nesting_depth,
+            statistics.code_bytes,
+            statistics.instruction_count,
+            statistics.string_heap_bytes
+        ].iter()
+            // Counts that are not known are shown as a dash
+            .map(|count| format!("<td>{}</td>", count.map_or(String::from("-"), |count| count.to_string())))
+            .collect();
+        rows.push_str(format!("<tr><td>{}</td>{}</tr>", program_number, cells.join("")).as_str());
+    }
+
+    statistics_table.set_inner_html(&rows);
+}
+
+pub fn clear_display() {
+    let window: Window = web_sys::window().expect("Should be able to get the window");
+    let document: Document = window.document().expect("Should be able to get the document");
+    document.get_element_by_id("statistics-table").expect("Should be able to find the element").set_inner_html("");
+}
@@ -1,9 +1,11 @@
-use std::collections::HashMap;
+use indexmap::IndexMap;
 
 use petgraph::graph::{NodeIndex, Graph};
+use serde::{Deserialize, Serialize};
 
-use crate::util::nexus_log;
+use crate::util::{nexus_log, messages::{Message, MessageCode}, position::{Position, Span}};
 
+#[cfg(feature = "ui")]
 use web_sys::{Window, Document, Element, DomTokenList};
 
 // Enum for determining the type of a variable in a symbol table
@@ -11,9 +13,14 @@ use web_sys::{Window, Document, Element, DomTokenList};
 pub enum Type {
     Int,
     String,
-    Boolean
+    Boolean,
+    Char
 }
 
+// Unique for every declaration in a program, so it identifies a variable without its name and scope
+#[derive (Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct DeclId(pub usize);
+
 // Enum for the symbol table entry fields to keep track of to prevent code duplication
 #[derive (Debug)]
 pub enum SymbolTableEntryField {
@@ -26,19 +33,42 @@ pub enum SymbolTableEntryField {
 #[derive (Debug)]
 pub struct SymbolTableEntry {
     pub symbol_type: Type,
-    pub position: (usize, usize),
+    // The code of the id where it is declared
+    pub span: Span,
+    pub scope: usize,
+    pub is_initialized: bool,
+    pub is_used: bool,
+
+    pub decl_id: DeclId
+}
+
+// A single entry of the symbol table in the JSON export
+// Only the id, type, and scope are needed when a symbol table is given to the phase APIs
+#[derive (Debug, Clone, Serialize, Deserialize)]
+#[serde (rename_all = "camelCase")]
+pub struct SymbolTableJsonEntry {
+    pub id: String,
+    pub symbol_type: String,
     pub scope: usize,
+    #[serde (default)]
+    pub position: Position,
+    #[serde (default)]
     pub is_initialized: bool,
+    #[serde (default)]
     pub is_used: bool
 }
 
 #[derive (Debug)]
 pub struct SymbolTable {
     // The graph for the symbol table
-    graph: Graph<HashMap<String, SymbolTableEntry>, ()>,
+    // Each scope keeps its ids in declaration order so the display and warnings are the same every run
+    graph: Graph<IndexMap<String, SymbolTableEntry>, ()>,
 
     // The index of the node of the current scope
-    pub cur_scope: Option<usize>
+    pub cur_scope: Option<usize>,
+
+    // The number of declarations so far, which is the next declaration id
+    num_declarations: usize
 }
 
 impl SymbolTable {
@@ -46,14 +76,15 @@ impl SymbolTable {
     pub fn new() -> Self {
         return SymbolTable {
             graph: Graph::new(),
-            cur_scope: None
+            cur_scope: None,
+            num_declarations: 0
         };
     }
 
     // Function to create a new scope and set it as the current scope
     pub fn new_scope(&mut self) {
         // Add a new node to the graph with the new hashmap
-        let new_node: NodeIndex = self.graph.add_node(HashMap::new());
+        let new_node: NodeIndex = self.graph.add_node(IndexMap::new());
        
         // Check to see if we already have a scope
         if self.cur_scope.is_some() {
@@ -87,24 +118,26 @@ impl SymbolTable {
         }
     }
 
-    // Adds an identifier to the current scope and returns if it was successful
-    pub fn new_identifier(&mut self, id: String, id_type: Type, id_position: (usize, usize)) -> bool {
+    // Adds an identifier to the current scope and returns its declaration id if it was successful
+    pub fn new_identifier(&mut self, id: String, id_type: Type, id_span: Span) -> Option<DeclId> {
         // Get the current scope's hash table
-        let scope_table: &mut HashMap<String, SymbolTableEntry> = self.graph.node_weight_mut(NodeIndex::new(self.cur_scope.unwrap())).unwrap();
+        let scope_table: &mut IndexMap<String, SymbolTableEntry> = self.graph.node_weight_mut(NodeIndex::new(self.cur_scope.unwrap())).unwrap();
         if (*scope_table).contains_key(&id) {
-            // The id already exists so return false
-            return false;
+            // The id already exists so it cannot be declared again
+            return None;
         } else {
             // Add the id and its respective information to the hash table
             let new_entry = SymbolTableEntry {
                 symbol_type: id_type,
-                position: id_position,
+                span: id_span,
                 scope: self.cur_scope.unwrap(),
                 is_initialized: false,
-                is_used: false
+                is_used: false,
+                decl_id: DeclId(self.num_declarations)
             };
             (*scope_table).insert(id, new_entry);
-            return true;
+            self.num_declarations += 1;
+            return Some(DeclId(self.num_declarations - 1));
         }
     }
 
@@ -117,7 +150,7 @@ impl SymbolTable {
         // This loop has checks at the end, but work has to be done first
         loop {
             // Get the hashmap for the scope
-            let scope_table: &HashMap<String, SymbolTableEntry> = self.graph.node_weight(NodeIndex::new(cur_scope_check)).unwrap();
+            let scope_table: &IndexMap<String, SymbolTableEntry> = self.graph.node_weight(NodeIndex::new(cur_scope_check)).unwrap();
             if (*scope_table).contains_key(id) {
                 // If the variable exists, then return the entry
                 return (*scope_table).get(id);
@@ -140,18 +173,18 @@ impl SymbolTable {
     // Returns a reference to the appropriate symbol table entry
     // based on the current scope and position in the code
     // for code generation after the symbol table is already fully populated
-    pub fn get_symbol_with_context(&mut self, id: &str, cur_position: (usize, usize)) -> Option<&SymbolTableEntry> {
+    pub fn get_symbol_with_context(&mut self, id: &str, cur_position: Position) -> Option<&SymbolTableEntry> {
         // Start with the current scope
         let mut cur_scope_check: usize = self.cur_scope.unwrap();
       
         // This loop has checks at the end, but work has to be done first
         loop {
             // Get the hashmap for the scope
-            let scope_table: &HashMap<String, SymbolTableEntry> = self.graph.node_weight(NodeIndex::new(cur_scope_check)).unwrap();
+            let scope_table: &IndexMap<String, SymbolTableEntry> = self.graph.node_weight(NodeIndex::new(cur_scope_check)).unwrap();
 
             // We have to make sure that the entry being received was declared before the current position
             let entry: Option<&SymbolTableEntry> = (*scope_table).get(id);
-            if entry.is_some() && self.is_in_context(entry.unwrap().position, cur_position) {
+            if entry.is_some() && self.is_in_context(entry.unwrap().span.start, cur_position) {
                 return entry;
             } else {
                 if cur_scope_check == 0 {
@@ -169,17 +202,9 @@ impl SymbolTable {
         }
     }
 
-    fn is_in_context(&self, symbol_position: (usize, usize), cur_position: (usize, usize), ) -> bool {
-        if symbol_position.0 < cur_position.0 {
-            // Symbol declared first, so it is valid
-            return true;
-        } else if symbol_position.0 > cur_position.0 {
-            // Symbol declared second, so is not valid
-            return false;
-        } else {
-            // Position in the row makes the difference if in the same row
-            return symbol_position.1 <= cur_position.1;
-        }
+    fn is_in_context(&self, symbol_position: Position, cur_position: Position, ) -> bool {
+        // Positions are ordered by line and then by column, so the symbol is valid if it was declared first
+        return symbol_position <= cur_position;
     }
 
     // Function to set a variable to be initialized
@@ -189,7 +214,7 @@ impl SymbolTable {
 
         loop {
             // Get the hashmap for the current scope being checked
-            let scope_table: &mut HashMap<String, SymbolTableEntry> = self.graph.node_weight_mut(NodeIndex::new(cur_scope_use)).unwrap();
+            let scope_table: &mut IndexMap<String, SymbolTableEntry> = self.graph.node_weight_mut(NodeIndex::new(cur_scope_use)).unwrap();
             if (*scope_table).contains_key(id) {
                 // Get the entry and update the initialized field
                 let id_entry: &mut SymbolTableEntry = (*scope_table).get_mut(id).unwrap();
@@ -224,28 +249,31 @@ impl SymbolTable {
                 if !entry.is_initialized {
                     if entry.is_used {
                         // Throw warning for declared and used but not initialized
-                        nexus_log::log(
+                        nexus_log::log_message(
                             nexus_log::LogTypes::Warning,
-                            nexus_log::LogSources::SemanticAnalyzer,
-                            format!("Warning at {:?}; Id [ {} ] is declared and used, but never initialized", entry.position, id_name)
+                            nexus_log::LogSource::SEMANTIC_ANALYZER,
+                            entry.span.start,
+                            &Message::new(MessageCode::UsedButNeverInitialized, vec![("position", entry.span.start.to_message_param()), ("id", id_name.to_owned())])
                         );
                         warning_count += 1;
                     } else {
                         // Throw warning for declared but never initialized or used
-                        nexus_log::log(
+                        nexus_log::log_message(
                             nexus_log::LogTypes::Warning,
-                            nexus_log::LogSources::SemanticAnalyzer,
-                            format!("Warning at {:?}; Id [ {} ] is declared, but never initialized or used", entry.position, id_name)
+                            nexus_log::LogSource::SEMANTIC_ANALYZER,
+                            entry.span.start,
+                            &Message::new(MessageCode::NeverInitializedOrUsed, vec![("position", entry.span.start.to_message_param()), ("id", id_name.to_owned())])
                         );
                         warning_count += 1;
                     }
                 } else {
                     if !entry.is_used {
                         // Throw warning for declared and initialized but never used
-                        nexus_log::log(
+                        nexus_log::log_message(
                             nexus_log::LogTypes::Warning,
-                            nexus_log::LogSources::SemanticAnalyzer,
-                            format!("Warning at {:?}; Id [ {} ] is declared and initialized, but never used", entry.position, id_name)
+                            nexus_log::LogSource::SEMANTIC_ANALYZER,
+                            entry.span.start,
+                            &Message::new(MessageCode::NeverUsed, vec![("position", entry.span.start.to_message_param()), ("id", id_name.to_owned())])
                         );
                         warning_count += 1;
                     }
@@ -255,11 +283,13 @@ impl SymbolTable {
         return warning_count;
     }
 
+    #[cfg(feature = "ui")]
     pub fn display_symbol_table(&mut self, program_number: &u32) {
         self.initialize_symbol_table(program_number);
         self.populate_symbol_table(program_number);
     }
 
+    #[cfg(feature = "ui")]
     fn initialize_symbol_table(&mut self, program_number: &u32) {
         // Get the preliminary objects
         let window: Window = web_sys::window().expect("Should be able to get the window");
@@ -322,6 +352,7 @@ impl SymbolTable {
     }
 
     // Function to populate the symbol table on the webpage
+    #[cfg(feature = "ui")]
     fn populate_symbol_table(&mut self, program_number: &u32) {
          // Get the preliminary objects
         let window: Window = web_sys::window().expect("Should be able to get the window");
@@ -350,7 +381,7 @@ impl SymbolTable {
                 row_elem.append_child(&scope_elem).expect("Should be able to append child node");
 
                 let position_elem: Element = document.create_element("td").expect("Should be able to create position element");
-                position_elem.set_inner_html(format!("{:?}", entry.position).as_str());
+                position_elem.set_inner_html(format!("{:?}", entry.span.start).as_str());
                 row_elem.append_child(&position_elem).expect("Should be able to append child node");
 
                 let init_elem: Element = document.create_element("td").expect("Should be able to create init element");
@@ -366,9 +397,28 @@ impl SymbolTable {
         }
     }
 
+    // Function to get every entry of every scope in the same order as the display
+    pub fn get_json_entries(&self) -> Vec<SymbolTableJsonEntry> {
+        let mut entries: Vec<SymbolTableJsonEntry> = Vec::new();
+        for scope_table in self.graph.node_weights() {
+            for (id_name, entry) in scope_table.iter() {
+                entries.push(SymbolTableJsonEntry {
+                    id: id_name.to_owned(),
+                    symbol_type: format!("{:?}", entry.symbol_type),
+                    scope: entry.scope,
+                    position: entry.span.start,
+                    is_initialized: entry.is_initialized,
+                    is_used: entry.is_used
+                });
+            }
+        }
+        return entries;
+    }
+
     // Function to reset the symbol table for the new analysis
     pub fn reset(&mut self) {
         self.graph.clear();
         self.cur_scope = None;
+        self.num_declarations = 0;
     }
 }
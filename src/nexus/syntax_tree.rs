@@ -1,17 +1,23 @@
-use std::{collections::HashMap};
+use std::{collections::{HashMap, HashSet}};
 
 use log::*;
 use petgraph::{graph::{NodeIndex, Graph}, dot::{Dot, Config}};
+use serde::{Deserialize, Serialize};
 
+#[cfg(feature = "ui")]
 use wasm_bindgen::{prelude::*, JsCast};
-use web_sys::{Window, Document, HtmlTextAreaElement, Element, DomTokenList};
+#[cfg(feature = "ui")]
+use web_sys::{Window, Document, HtmlTextAreaElement, HtmlInputElement, Element, DomTokenList};
 
-use crate::nexus::syntax_tree_node::{SyntaxTreeNode, SyntaxTreeNodeTypes};
+use crate::nexus::syntax_tree_node::{SyntaxTreeNode, SyntaxTreeNodeTypes, NonTerminalsCst};
+use crate::nexus::symbol_table::Type;
+use crate::util::position::{Position, Span};
 
 use string_builder::Builder;
 
 // Code from https://github.com/rustwasm/wasm-bindgen/blob/main/examples/import_js/crate/src/lib.rs
 // Have to import the treeRenderer js module
+#[cfg(feature = "ui")]
 #[wasm_bindgen(module = "/treeRenderer.js")]
 extern "C" {
     // Import the createSyntaxTree function from js so we can call it from the Rust code
@@ -19,15 +25,64 @@ extern "C" {
     fn create_rendering(dotSrc: &str, svgId: &str);
 }
 
+// Trees with more nodes than this are simplified before they are drawn because rendering them can freeze the page
+pub const MAX_RENDERED_NODES: usize = 400;
+
+// The deepest level of a simplified tree that gets drawn
+pub const MAX_RENDERED_DEPTH: usize = 25;
+
 // Enum for differentiating a CST vs an AST
-#[derive (Debug, strum::Display, PartialEq)]
+#[derive (Debug, Clone, strum::Display, PartialEq)]
 #[strum (serialize_all = "lowercase")]
 pub enum SyntaxTreeTypes {
     Cst,
     Ast
 }
 
-#[derive (Debug)]
+// A single node of a syntax tree in the JSON export
+#[derive (Debug, Serialize, Deserialize)]
+#[serde (rename_all = "camelCase")]
+pub struct SyntaxTreeJsonNode {
+    // The index of the node in the graph
+    pub id: usize,
+
+    // The text that is displayed for the node
+    pub label: String,
+
+    // Either terminal or nonterminal
+    pub kind: String,
+
+    // The position of the token for terminals
+    #[serde (default, skip_serializing_if = "Option::is_none")]
+    pub position: Option<Position>,
+
+    // The kind of token for terminals, such as "Identifier", so a string and an id with the same text can be told apart
+    #[serde (default, skip_serializing_if = "Option::is_none")]
+    pub token_kind: Option<String>,
+
+    // The derived type of the node if the tree has been analyzed
+    #[serde (default, skip_serializing_if = "Option::is_none")]
+    pub node_type: Option<String>,
+
+    #[serde (default)]
+    pub children: Vec<SyntaxTreeJsonNode>
+}
+
+// The JSON export of an entire syntax tree
+// Only the root is needed when a tree is given to the phase APIs
+#[derive (Debug, Serialize, Deserialize)]
+#[serde (rename_all = "camelCase")]
+pub struct SyntaxTreeJson {
+    #[serde (default)]
+    pub tree_type: String,
+    #[serde (default)]
+    pub node_count: usize,
+    #[serde (default)]
+    pub epsilon_node_count: usize,
+    pub root: Option<SyntaxTreeJsonNode>
+}
+
+#[derive (Debug, Clone)]
 pub struct SyntaxTree {
     // A graph with a string as the node content and no edge weights
     pub graph: Graph<SyntaxTreeNode, ()>,
@@ -41,6 +96,9 @@ pub struct SyntaxTree {
     // A hashmap to keep track of parents
     parents: HashMap<usize, Option<usize>>,
 
+    // The source position of nodes that do not have a token, such as the left brace of an AST block
+    node_positions: HashMap<usize, Position>,
+
     // The type of the syntax tree
     tree_type: SyntaxTreeTypes
 }
@@ -53,6 +111,7 @@ impl SyntaxTree {
             root: None,
             current: None,
             parents: HashMap::new(),
+            node_positions: HashMap::new(),
             tree_type: syntax_tree_type
         };
     }
@@ -96,6 +155,27 @@ impl SyntaxTree {
         }
     }
 
+    // Function to record the source position of the node that was just added as a branch
+    pub fn set_current_position(&mut self, position: Position) {
+        if self.current.is_some() {
+            self.node_positions.insert(self.current.unwrap(), position);
+        }
+    }
+
+    pub fn get_node_position(&self, node_index: usize) -> Option<Position> {
+        return self.node_positions.get(&node_index).copied();
+    }
+
+    // Function to get the code covered by every token in the subtree of the node
+    pub fn get_node_span(&self, node_index: NodeIndex) -> Option<Span> {
+        return match self.graph.node_weight(node_index)? {
+            SyntaxTreeNode::Terminal(token) => Some(token.get_span()),
+            _ => self.graph.neighbors(node_index)
+                .filter_map(|child_index| self.get_node_span(child_index))
+                .reduce(|span, child_span| span.merge(&child_span))
+        };
+    }
+
     // Function to move back up
     pub fn move_up(&mut self) {
         // Get the current parent
@@ -110,6 +190,7 @@ impl SyntaxTree {
         }
     }
 
+    #[cfg(feature = "ui")]
     pub fn display(&self, program_number: &u32) {
         let svg_id: String = self.create_display_area(program_number);
 
@@ -124,12 +205,327 @@ impl SyntaxTree {
 
         text_area_tree.set_value(&tree_string);
 
+        // Show how big the tree is
+        let epsilon_nodes: HashSet<NodeIndex> = self.get_epsilon_nodes();
+        let node_count_elem: Element = document.get_element_by_id(format!("program{}-{}-node-count", *program_number, self.tree_type).as_str())
+                                                    .expect("Should be able to get the node count element");
+        node_count_elem.set_inner_html(format!("{} nodes ({} in epsilon-only branches)", self.node_count(), epsilon_nodes.len()).as_str());
+
+        // Every version of the image is created up front so the toggles can swap between them
+        // The order is [full, hidden epsilon, simplified full, simplified hidden epsilon]
+        let is_simplified: bool = self.needs_simplified_dot();
+        let mut dots: Vec<String> = vec![self.to_dot(false), self.to_dot(true)];
+        if is_simplified {
+            dots.push(self.to_simplified_dot(false));
+            dots.push(self.to_simplified_dot(true));
+            node_count_elem.set_inner_html(format!("{} nodes ({} in epsilon-only branches), simplified because it has more than {} nodes",
+                                                    self.node_count(), epsilon_nodes.len(), MAX_RENDERED_NODES).as_str());
+        }
+
+        let epsilon_toggle: HtmlInputElement = document.get_element_by_id(format!("program{}-{}-epsilon-toggle", *program_number, self.tree_type).as_str())
+                                                    .expect("Should be able to get the epsilon toggle")
+                                                    .dyn_into::<HtmlInputElement>()
+                                                    .expect("Should be able to convert to input");
+        let full_toggle: HtmlInputElement = document.get_element_by_id(format!("program{}-{}-full-toggle", *program_number, self.tree_type).as_str())
+                                                    .expect("Should be able to get the full tree toggle")
+                                                    .dyn_into::<HtmlInputElement>()
+                                                    .expect("Should be able to convert to input");
+
+        // The full tree toggle only matters when the tree is simplified
+        if !is_simplified {
+            full_toggle.parent_element().expect("Should have the toggle div").set_attribute("hidden", "").expect("Should be able to add the attribute");
+        }
+
+        for toggle in [&epsilon_toggle, &full_toggle] {
+            let toggle_svg_id: String = svg_id.to_owned();
+            let toggle_dots: Vec<String> = dots.to_owned();
+            let epsilon_input: HtmlInputElement = epsilon_toggle.to_owned();
+            let full_input: HtmlInputElement = full_toggle.to_owned();
+            let toggle_fn: Closure<dyn FnMut()> = Closure::wrap(Box::new(move || {
+                // Redraw the image with or without the epsilon branches and the simplifications
+                let mut dot_index: usize = if epsilon_input.checked() { 1 } else { 0 };
+                if toggle_dots.len() > 2 && !full_input.checked() {
+                    dot_index += 2;
+                }
+                create_rendering(&toggle_dots[dot_index], &toggle_svg_id);
+            }) as Box<dyn FnMut()>);
+
+            toggle.add_event_listener_with_callback("change", toggle_fn.as_ref().unchecked_ref()).expect("Should be able to add the event listener");
+            toggle_fn.forget();
+        }
+
+        // Draw the image to the webpage, which is the simplified one if the tree is too big
+        self.create_image(&dots[dots.len() - 2], svg_id);
+    }
+
+    // Function to get the number of nodes in the tree
+    pub fn node_count(&self) -> usize {
+        return self.graph.node_count();
+    }
+
+    // Function to get all nonterminals that do not have any terminals below them
+    pub fn get_epsilon_nodes(&self) -> HashSet<NodeIndex> {
+        let mut epsilon_nodes: HashSet<NodeIndex> = HashSet::new();
+        if self.root.is_some() {
+            self.find_epsilon_nodes_dfs(NodeIndex::new(self.root.unwrap()), &mut epsilon_nodes);
+        }
+        return epsilon_nodes;
+    }
+
+    // Returns true if the subtree at the node contains a terminal
+    fn find_epsilon_nodes_dfs(&self, cur_index: NodeIndex, epsilon_nodes: &mut HashSet<NodeIndex>) -> bool {
+        match self.graph.node_weight(cur_index).unwrap() {
+            SyntaxTreeNode::Terminal(_) => return true,
+            _ => {
+                // Every child has to be visited so all epsilon nodes get recorded
+                let mut has_terminal: bool = false;
+                let neighbors: Vec<NodeIndex> = self.graph.neighbors(cur_index).collect();
+                for neighbor_index in neighbors.into_iter() {
+                    if self.find_epsilon_nodes_dfs(neighbor_index, epsilon_nodes) {
+                        has_terminal = true;
+                    }
+                }
+
+                if !has_terminal {
+                    epsilon_nodes.insert(cur_index);
+                }
+                return has_terminal;
+            }
+        }
+    }
+
+    // Function to get the DOT representation of the tree
+    pub fn to_dot(&self, hide_epsilon: bool) -> String {
+        if hide_epsilon {
+            // Remove every node that is part of an epsilon-only branch
+            let epsilon_nodes: HashSet<NodeIndex> = self.get_epsilon_nodes();
+            let filtered_graph: Graph<&SyntaxTreeNode, ()> = self.graph.filter_map(
+                |node_index, node| if epsilon_nodes.contains(&node_index) { None } else { Some(node) },
+                |_, edge| Some(*edge)
+            );
+            return format!("{:?}", Dot::with_config(&filtered_graph, &[Config::EdgeNoLabel]));
+        } else {
+            return format!("{:?}", Dot::with_config(&self.graph, &[Config::EdgeNoLabel]));
+        }
+    }
+
+    // Function to check if the tree is too big to be drawn in full by default
+    pub fn needs_simplified_dot(&self) -> bool {
+        return self.node_count() > MAX_RENDERED_NODES;
+    }
+
+    // Function to get the DOT representation of the tree with each CharList chain collapsed into one node
+    // with its string and the branches below MAX_RENDERED_DEPTH replaced by a node with how many were left out
+    pub fn to_simplified_dot(&self, hide_epsilon: bool) -> String {
+        let mut epsilon_nodes: HashSet<NodeIndex> = HashSet::new();
+        if hide_epsilon {
+            epsilon_nodes = self.get_epsilon_nodes();
+        }
+
+        let mut simplified_graph: Graph<String, &str> = Graph::new();
+        if self.root.is_some() && !epsilon_nodes.contains(&NodeIndex::new(self.root.unwrap())) {
+            self.simplify_dfs(NodeIndex::new(self.root.unwrap()), 0, &epsilon_nodes, &mut simplified_graph);
+        }
+
+        // The labels are already text, so they are written with Display to avoid extra quotes
+        // Edges do not have labels, but their weight still has to be displayable
+        return format!("{}", Dot::with_config(&simplified_graph, &[Config::EdgeNoLabel]));
+    }
+
+    fn simplify_dfs(&self, cur_index: NodeIndex, depth: usize, epsilon_nodes: &HashSet<NodeIndex>, simplified_graph: &mut Graph<String, &str>) -> NodeIndex {
+        let node: &SyntaxTreeNode = self.graph.node_weight(cur_index).unwrap();
+
+        // Strings in the CST are a chain with a CharList for every character, so the whole chain becomes one node
+        if matches!(node, SyntaxTreeNode::NonTerminalCst(NonTerminalsCst::CharList)) {
+            let mut string_text: String = String::new();
+            self.get_terminal_text(cur_index, &mut string_text);
+            return simplified_graph.add_node(format!("CharList \"{}\"", string_text));
+        }
+
+        let new_index: NodeIndex = simplified_graph.add_node(format!("{:?}", node));
+
+        // Children are stored in reverse order of insertion
+        let children: Vec<NodeIndex> = self.graph.neighbors(cur_index)
+            .filter(|child_index| !epsilon_nodes.contains(child_index))
+            .collect::<Vec<NodeIndex>>()
+            .into_iter()
+            .rev()
+            .collect();
+
+        if depth == MAX_RENDERED_DEPTH && children.len() > 0 {
+            let num_hidden: usize = children.iter().map(|child_index| self.count_subtree_nodes(*child_index, epsilon_nodes)).sum();
+            let hidden_index: NodeIndex = simplified_graph.add_node(format!("... {} more nodes", num_hidden));
+            simplified_graph.add_edge(new_index, hidden_index, "");
+        } else {
+            for child_index in children.into_iter() {
+                let new_child_index: NodeIndex = self.simplify_dfs(child_index, depth + 1, epsilon_nodes, simplified_graph);
+                simplified_graph.add_edge(new_index, new_child_index, "");
+            }
+        }
+
+        return new_index;
+    }
+
+    // Function to add the text of every terminal under the node from left to right
+    fn get_terminal_text(&self, cur_index: NodeIndex, text: &mut String) {
+        match self.graph.node_weight(cur_index).unwrap() {
+            SyntaxTreeNode::Terminal(token) => text.push_str(&token.text),
+            _ => {
+                let neighbors: Vec<NodeIndex> = self.graph.neighbors(cur_index).collect();
+                for neighbor_index in neighbors.into_iter().rev() {
+                    self.get_terminal_text(neighbor_index, text);
+                }
+            }
+        }
+    }
+
+    // Function to get the number of nodes in the subtree that are not in an epsilon-only branch
+    fn count_subtree_nodes(&self, cur_index: NodeIndex, epsilon_nodes: &HashSet<NodeIndex>) -> usize {
+        return 1 + self.graph.neighbors(cur_index)
+            .filter(|child_index| !epsilon_nodes.contains(child_index))
+            .map(|child_index| self.count_subtree_nodes(child_index, epsilon_nodes))
+            .sum::<usize>();
+    }
+
+    // Function to get the DOT representation of the tree where statements are colored by
+    // whether they produced any code
+    pub fn to_coverage_dot(&self, code_sizes: &HashMap<NodeIndex, usize>) -> String {
+        let get_node_attributes = |_, (node_index, _): (NodeIndex, &SyntaxTreeNode)| {
+            return match code_sizes.get(&node_index) {
+                Some(0) => String::from("style=filled fillcolor=\"#F05454\""),
+                Some(_) => String::from("style=filled fillcolor=\"#70AE6E\""),
+                None => String::new()
+            };
+        };
+        return format!("{:?}", Dot::with_attr_getters(&self.graph, &[Config::EdgeNoLabel], &|_, _| String::new(), &get_node_attributes));
+    }
+
+    // Function to redraw the tree colored by the code each node produced
+    #[cfg(feature = "ui")]
+    pub fn display_coverage(&self, program_number: &u32, code_sizes: &HashMap<NodeIndex, usize>) {
+        let window: Window = web_sys::window().expect("Should be able to get the window");
+        let document: Document = window.document().expect("Should be able to get the document");
+
+        let node_count_elem: Element = document.get_element_by_id(format!("program{}-{}-node-count", *program_number, self.tree_type).as_str())
+                                                    .expect("Should be able to get the node count element");
+        let num_empty: usize = code_sizes.values().filter(|size| **size == 0).count();
+        node_count_elem.set_inner_html(format!("{} nodes ({} statements with code in green, {} without code in red)",
+                                                self.node_count(), code_sizes.len() - num_empty, num_empty).as_str());
+
+        create_rendering(self.to_coverage_dot(code_sizes).as_str(), format!("program{}-{}-svg-div", *program_number, self.tree_type).as_str());
+    }
+
+    // Function to get the JSON representation of the tree
+    pub fn to_json(&self) -> String {
+        return serde_json::to_string(&self.create_json(None)).expect("Should be able to serialize the syntax tree");
+    }
+
+    // Function to create the serializable tree with optional type annotations
+    pub fn create_json(&self, node_types: Option<&HashMap<NodeIndex, Type>>) -> SyntaxTreeJson {
+        let mut root_node: Option<SyntaxTreeJsonNode> = None;
+        if self.root.is_some() {
+            root_node = Some(self.create_json_dfs(NodeIndex::new(self.root.unwrap()), node_types));
+        }
+
+        return SyntaxTreeJson {
+            tree_type: self.tree_type.to_string(),
+            node_count: self.node_count(),
+            epsilon_node_count: self.get_epsilon_nodes().len(),
+            root: root_node
+        };
+    }
+
+    fn create_json_dfs(&self, cur_index: NodeIndex, node_types: Option<&HashMap<NodeIndex, Type>>) -> SyntaxTreeJsonNode {
+        let mut node_type: Option<String> = None;
+        if node_types.is_some() {
+            let derived_type: Option<&Type> = node_types.unwrap().get(&cur_index);
+            if derived_type.is_some() {
+                node_type = Some(format!("{:?}", derived_type.unwrap()));
+            }
+        }
 
+        // Children are stored in reverse order of insertion
+        let mut children: Vec<SyntaxTreeJsonNode> = Vec::new();
+        let neighbors: Vec<NodeIndex> = self.graph.neighbors(cur_index).collect();
+        for neighbor_index in neighbors.into_iter().rev() {
+            children.push(self.create_json_dfs(neighbor_index, node_types));
+        }
 
-        // Draw the image to the webpage
-        self.create_image(svg_id);
+        let node: &SyntaxTreeNode = self.graph.node_weight(cur_index).unwrap();
+        return match node {
+            SyntaxTreeNode::Terminal(token) => SyntaxTreeJsonNode {
+                id: cur_index.index(),
+                label: token.text.to_owned(),
+                kind: String::from("terminal"),
+                position: Some(token.position.to_owned()),
+                token_kind: Some(String::from(token.token_type.get_kind_name())),
+                node_type: node_type,
+                children: children
+            },
+            _ => SyntaxTreeJsonNode {
+                id: cur_index.index(),
+                label: format!("{:?}", node),
+                kind: String::from("nonterminal"),
+                position: None,
+                token_kind: None,
+                node_type: node_type,
+                children: children
+            }
+        };
+    }
+
+    // Function to get the tree as an s-expression with one node per line, which is easy to diff
+    pub fn to_sexpr(&self) -> String {
+        return self.create_sexpr(None);
+    }
+
+    // Function to create the s-expression with optional type annotations after a colon
+    pub fn create_sexpr(&self, node_types: Option<&HashMap<NodeIndex, Type>>) -> String {
+        let mut sexpr_builder: Builder = Builder::default();
+        if self.root.is_some() {
+            self.create_sexpr_dfs(&mut sexpr_builder, NodeIndex::new(self.root.unwrap()), 0, node_types);
+        }
+        return sexpr_builder.string().unwrap();
     }
 
+    fn create_sexpr_dfs(&self, builder: &mut Builder, cur_index: NodeIndex, level: usize, node_types: Option<&HashMap<NodeIndex, Type>>) {
+        // Children are indented by 2 spaces per level
+        builder.append("  ".repeat(level));
+
+        let type_text: String = match node_types.and_then(|types| types.get(&cur_index)) {
+            Some(derived_type) => format!(" :{:?}", derived_type),
+            None => String::new()
+        };
+
+        match self.graph.node_weight(cur_index).unwrap() {
+            SyntaxTreeNode::Terminal(token) => {
+                // Quote the text if it would be confused with the structure of the tree
+                if token.text.len() == 0 || token.text.contains(|c: char| c.is_whitespace() || c == '(' || c == ')' || c == '"') {
+                    builder.append(format!("{:?}{}", token.text, type_text));
+                } else {
+                    builder.append(format!("{}{}", token.text, type_text));
+                }
+            },
+            non_terminal => {
+                builder.append(format!("({:?}{}", non_terminal, type_text));
+
+                // Children are stored in reverse order of insertion
+                let neighbors: Vec<NodeIndex> = self.graph.neighbors(cur_index).collect();
+                for neighbor_index in neighbors.into_iter().rev() {
+                    builder.append("\n");
+                    self.create_sexpr_dfs(builder, neighbor_index, level + 1, node_types);
+                }
+                builder.append(")");
+            }
+        }
+
+        // The whole tree ends with a new line
+        if level == 0 {
+            builder.append("\n");
+        }
+    }
+
+    #[cfg(feature = "ui")]
     fn create_text(&self) -> String {
         let mut tree_builder: Builder = Builder::default();
 
@@ -138,6 +534,7 @@ impl SyntaxTree {
         return tree_builder.string().unwrap();
     }
 
+    #[cfg(feature = "ui")]
     fn create_text_dfs(&self, builder: &mut Builder, cur_id: usize, level: usize) {
         // Set the level
         for _i in 0..level {
@@ -161,14 +558,13 @@ impl SyntaxTree {
     }
 
     // Function that creates 
-    fn create_image(&self, svg_id: String) {
-        // Convert the graph into a dot format
-        let graph_dot: Dot<&Graph<SyntaxTreeNode, ()>> = Dot::with_config(&self.graph, &[Config::EdgeNoLabel]);
-        
+    #[cfg(feature = "ui")]
+    fn create_image(&self, dot: &str, svg_id: String) {
         // Call the JS to create the graph on the webpage using d3.js
-        create_rendering(format!("{:?}", graph_dot).as_str(), &svg_id);
+        create_rendering(dot, &svg_id);
     }
 
+    #[cfg(feature = "ui")]
     fn create_display_area(&self, program_number: &u32) -> String {
         // Get the preliminary objects
         let window: Window = web_sys::window().expect("Should be able to get the window");
@@ -242,6 +638,53 @@ impl SyntaxTree {
         // The div is a container for the content of the ast info
         display_area_class_list.add_2("container", format!("{}-pane", self.tree_type).as_str()).expect("Should be able to add the classes");
 
+        // Header row with the tree information and display options
+        let header_div: Element = document.create_element("div").expect("Should be able to create the div");
+        let header_classes: DomTokenList = header_div.class_list();
+        header_classes.add_3("row", "justify-content-between", format!("{}-header", self.tree_type).as_str()).expect("Should be able to add the classes");
+
+        let node_count_elem: Element = document.create_element("span").expect("Should be able to create the span");
+        node_count_elem.class_list().add_1("col-auto").expect("Should be able to add the class");
+        node_count_elem.set_id(format!("program{}-{}-node-count", *program_number, self.tree_type).as_str());
+        header_div.append_child(&node_count_elem).expect("Should be able to add child node");
+
+        // Checkbox to hide the branches that did not derive any tokens
+        let toggle_div: Element = document.create_element("div").expect("Should be able to create the div");
+        toggle_div.class_list().add_2("col-auto", "form-check").expect("Should be able to add the classes");
+
+        let epsilon_toggle: Element = document.create_element("input").expect("Should be able to create the input");
+        epsilon_toggle.class_list().add_1("form-check-input").expect("Should be able to add the class");
+        epsilon_toggle.set_attribute("type", "checkbox").expect("Should be able to add the attribute");
+        epsilon_toggle.set_id(format!("program{}-{}-epsilon-toggle", *program_number, self.tree_type).as_str());
+        toggle_div.append_child(&epsilon_toggle).expect("Should be able to add child node");
+
+        let toggle_label: Element = document.create_element("label").expect("Should be able to create the label");
+        toggle_label.class_list().add_1("form-check-label").expect("Should be able to add the class");
+        toggle_label.set_attribute("for", format!("program{}-{}-epsilon-toggle", *program_number, self.tree_type).as_str()).expect("Should be able to add the attribute");
+        toggle_label.set_inner_html("Hide epsilon-only branches");
+        toggle_div.append_child(&toggle_label).expect("Should be able to add child node");
+
+        header_div.append_child(&toggle_div).expect("Should be able to add child node");
+
+        // Checkbox to draw big trees without simplifying them
+        let full_toggle_div: Element = document.create_element("div").expect("Should be able to create the div");
+        full_toggle_div.class_list().add_2("col-auto", "form-check").expect("Should be able to add the classes");
+
+        let full_toggle: Element = document.create_element("input").expect("Should be able to create the input");
+        full_toggle.class_list().add_1("form-check-input").expect("Should be able to add the class");
+        full_toggle.set_attribute("type", "checkbox").expect("Should be able to add the attribute");
+        full_toggle.set_id(format!("program{}-{}-full-toggle", *program_number, self.tree_type).as_str());
+        full_toggle_div.append_child(&full_toggle).expect("Should be able to add child node");
+
+        let full_label: Element = document.create_element("label").expect("Should be able to create the label");
+        full_label.class_list().add_1("form-check-label").expect("Should be able to add the class");
+        full_label.set_attribute("for", format!("program{}-{}-full-toggle", *program_number, self.tree_type).as_str()).expect("Should be able to add the attribute");
+        full_label.set_inner_html("Render full tree anyway");
+        full_toggle_div.append_child(&full_label).expect("Should be able to add child node");
+
+        header_div.append_child(&full_toggle_div).expect("Should be able to add child node");
+        display_area_div.append_child(&header_div).expect("Should be able to append child");
+
         // Single row container
         let row_div: Element = document.create_element("div").expect("Should be able to create the div");
         let row_classes: DomTokenList = row_div.class_list();
@@ -278,6 +721,7 @@ impl SyntaxTree {
         return svg_div_elem.id();
     }
 
+    #[cfg(feature = "ui")]
     pub fn clear_display() {
         // Get the preliminary objects
         let window: Window = web_sys::window().expect("Should be able to get the window");
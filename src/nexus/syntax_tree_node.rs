@@ -2,6 +2,7 @@ use std::fmt;
 
 use crate::nexus::token::Token;
 
+#[derive (Clone)]
 pub enum SyntaxTreeNode {
     Terminal(Token),
     NonTerminalCst(NonTerminalsCst),
@@ -23,7 +24,7 @@ impl fmt::Debug for SyntaxTreeNode {
 }
 
 // Valid nonterminals for a CST
-#[derive (Debug, strum::Display)]
+#[derive (Debug, Clone, Copy, PartialEq, Eq, Hash, strum::Display)]
 #[strum (serialize_all = "PascalCase")]
 pub enum NonTerminalsCst {
     Program,
@@ -38,6 +39,9 @@ pub enum NonTerminalsCst {
     Expr,
     IntExpr,
     StringExpr,
+    CharExpr,
+    IndexExpr,
+    LenExpr,
     BooleanExpr,
     Id,
     CharList,
@@ -51,7 +55,7 @@ pub enum NonTerminalsCst {
 }
 
 // Valid nonterminals for an AST
-#[derive (Debug, strum::Display)]
+#[derive (Debug, Clone, strum::Display)]
 #[strum (serialize_all = "PascalCase")]
 pub enum NonTerminalsAst {
     Block,
@@ -62,7 +66,9 @@ pub enum NonTerminalsAst {
     If,
     Add,
     IsEq,
-    NotEq
+    NotEq,
+    Index,
+    Len
 }
 
 // The type of a node relative to the tree
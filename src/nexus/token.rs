@@ -1,3 +1,7 @@
+use serde::Deserialize;
+
+use crate::util::{language_profile, position::{Position, Span}};
+
 // Defines a token
 #[derive (Debug, Clone)]
 pub struct Token {
@@ -6,7 +10,7 @@ pub struct Token {
     // The content of the token
     pub text: String,
     // The position in the source code the token is located
-    pub position: (usize, usize)
+    pub position: Position
 }
 
 impl Token {
@@ -15,9 +19,21 @@ impl Token {
         return Token {
             token_type: token_type_in,
             text: token_text,
-            position: (line_number, col_number)
+            position: Position::new(line_number, col_number)
         }
     }
+
+    // Function to create a token from its JSON form, making sure the text is valid for the kind of token
+    pub fn from_json(token_json: &TokenJson) -> Result<Self, String> {
+        let token_type: TokenType = TokenType::from_kind(&token_json.kind, &token_json.text)?;
+        return Ok(Token::new(token_type, token_json.text.to_owned(), token_json.position.line, token_json.position.col));
+    }
+
+    // Function to get the code the token covers, which is always on a single line
+    // The end is right after the token, which is where a missing token is assumed to be
+    pub fn get_span(&self) -> Span {
+        return Span::new(self.position, self.position.offset_cols(self.text.len()));
+    }
 }
 
 // Defines the token types and what they hold
@@ -28,9 +44,81 @@ pub enum TokenType {
     Symbol(Symbols),
     Digit(u8),
     Char(String),
+    CharLiteral(char),
     Unrecognized(String)
 }
 
+impl TokenType {
+    // Function to get the name of the kind of token, such as "Keyword"
+    pub fn get_kind_name(&self) -> &'static str {
+        return match self {
+            TokenType::Keyword(_) => "Keyword",
+            TokenType::Identifier(_) => "Identifier",
+            TokenType::Symbol(_) => "Symbol",
+            TokenType::Digit(_) => "Digit",
+            TokenType::Char(_) => "Char",
+            TokenType::CharLiteral(_) => "CharLiteral",
+            TokenType::Unrecognized(_) => "Unrecognized"
+        };
+    }
+
+    // Function to get the token type from the name of its kind and its text, such as Keyword and "print"
+    // Chars can be any number of letters and spaces because the AST combines the characters of a string
+    pub fn from_kind(kind: &str, text: &str) -> Result<TokenType, String> {
+        let token_type: Option<TokenType> = match kind {
+            "Keyword" => match text {
+                "if" => Some(TokenType::Keyword(Keywords::If)),
+                "while" => Some(TokenType::Keyword(Keywords::While)),
+                "print" => Some(TokenType::Keyword(Keywords::Print)),
+                "string" => Some(TokenType::Keyword(Keywords::String)),
+                "int" => Some(TokenType::Keyword(Keywords::Int)),
+                "boolean" => Some(TokenType::Keyword(Keywords::Boolean)),
+                "char" => Some(TokenType::Keyword(Keywords::Char)),
+                "true" => Some(TokenType::Keyword(Keywords::True)),
+                "false" => Some(TokenType::Keyword(Keywords::False)),
+                "len" => Some(TokenType::Keyword(Keywords::Len)),
+                _ => None
+            },
+            // The parser checks the identifier against its language profile, so only keywords and other text are refused here
+            "Identifier" if language_profile::is_possible_identifier(text) && TokenType::from_kind("Keyword", text).is_err() => Some(TokenType::Identifier(String::from(text))),
+            "Symbol" => match text {
+                "(" => Some(TokenType::Symbol(Symbols::LParen)),
+                ")" => Some(TokenType::Symbol(Symbols::RParen)),
+                "{" => Some(TokenType::Symbol(Symbols::LBrace)),
+                "}" => Some(TokenType::Symbol(Symbols::RBrace)),
+                "+" => Some(TokenType::Symbol(Symbols::AdditionOp)),
+                "==" => Some(TokenType::Symbol(Symbols::EqOp)),
+                "!=" => Some(TokenType::Symbol(Symbols::NeqOp)),
+                "=" => Some(TokenType::Symbol(Symbols::AssignmentOp)),
+                "\"" => Some(TokenType::Symbol(Symbols::Quote)),
+                "$" => Some(TokenType::Symbol(Symbols::EOP)),
+                "[" => Some(TokenType::Symbol(Symbols::LBracket)),
+                "]" => Some(TokenType::Symbol(Symbols::RBracket)),
+                _ => None
+            },
+            "Digit" if text.len() == 1 && text.chars().all(|c| c.is_ascii_digit()) => Some(TokenType::Digit(text.parse::<u8>().unwrap())),
+            "Char" if text.chars().all(|c| c.is_ascii_lowercase() || c == ' ') => Some(TokenType::Char(String::from(text))),
+            "CharLiteral" => get_char_literal(text).map(|c| TokenType::CharLiteral(c)),
+            _ => None
+        };
+
+        return match token_type {
+            Some(valid_type) => Ok(valid_type),
+            None => Err(format!("Invalid token [ {} ] of kind [ {} ]; Valid kinds are Keyword, Identifier, Symbol, Digit, Char, and CharLiteral", text, kind))
+        };
+    }
+}
+
+// Function to get the character of a char literal's text, such as 'a'
+// Char literals hold a single lowercase letter or a space like the characters of a string
+pub fn get_char_literal(text: &str) -> Option<char> {
+    let chars: Vec<char> = text.chars().collect();
+    if chars.len() == 3 && chars[0] == '\'' && chars[2] == '\'' && (chars[1].is_ascii_lowercase() || chars[1] == ' ') {
+        return Some(chars[1]);
+    }
+    return None;
+}
+
 // Defines the keywords
 #[derive (Debug, Clone, PartialEq)]
 pub enum Keywords {
@@ -40,8 +128,10 @@ pub enum Keywords {
     String,
     Int,
     Boolean,
+    Char,
     True,
-    False
+    False,
+    Len
 }
 
 // Defines the possible symbols
@@ -56,5 +146,15 @@ pub enum Symbols {
     NeqOp, // !=
     AssignmentOp, // =
     Quote, // "
-    EOP // $
+    EOP, // $
+    LBracket, // [
+    RBracket // ]
+}
+
+// A token in the JSON given to the phase APIs, such as { "kind": "Keyword", "text": "print", "position": [1, 2] }
+#[derive (Debug, Deserialize)]
+pub struct TokenJson {
+    pub kind: String,
+    pub text: String,
+    pub position: Position
 }
@@ -0,0 +1,82 @@
+use crate::util::{language_profile::{LanguageProfile, LanguagePreset}, target::{Target, TargetConfig, SyscallConvention}};
+
+// Information about how a program was compiled so generated code can be traced back to it
+#[derive (Debug, Clone)]
+pub struct BuildInfo {
+    // The version of the compiler from the crate
+    pub version: &'static str,
+
+    pub target: Target,
+
+    // Descriptions of the options that affect the generated code, like "optimize size: on"
+    pub options: Vec<String>,
+
+    // FNV-1a hash of the source code that was compiled
+    pub source_hash: u32
+}
+
+impl BuildInfo {
+    pub fn new(target_config: &TargetConfig, language_profile: &LanguageProfile, optimize_size: bool, source_code: &str) -> Self {
+        let mut options: Vec<String> = Vec::new();
+
+        // The extended grammar with single letter identifiers is the default, so the profile is only listed when it changes the language
+        if language_profile.preset != LanguagePreset::default() {
+            options.push(format!("language profile: {}", language_profile.preset));
+        }
+        if language_profile.identifier_charset.is_extended() {
+            options.push(format!("identifier characters: {}", language_profile.identifier_charset.to_input()));
+        }
+
+        // These options only change the 6502 image
+        if target_config.target == Target::Target6502 {
+            options.push(format!("optimize size: {}", if optimize_size { "on" } else { "off" }));
+            options.push(format!("reserved cells: {}", target_config.reserved_cells));
+            match target_config.heap_split {
+                Some(addr) => options.push(format!("heap start: 0x{:02X}", addr)),
+                None => options.push(String::from("heap start: shared"))
+            }
+            if let Some(mmio) = target_config.mmio {
+                options.push(format!("output device: int 0x{:02X}, string 0x{:02X}", mmio.int_out, mmio.string_out));
+            }
+        }
+
+        // Linux is the default, so it is the only convention that is not listed
+        if target_config.target == Target::TargetRiscV {
+            match target_config.syscall_convention {
+                SyscallConvention::Linux => {},
+                SyscallConvention::Custom { write, exit } => options.push(format!("system calls: write {}, exit {}", write, exit)),
+                syscall_convention => options.push(format!("system calls: {}", syscall_convention))
+            }
+        }
+
+        return BuildInfo {
+            version: env!("CARGO_PKG_VERSION"),
+            target: target_config.target,
+            options: options,
+            source_hash: Self::hash_source(source_code)
+        };
+    }
+
+    // Function to hash the source code with FNV-1a, which is the same on every platform and run
+    fn hash_source(source_code: &str) -> u32 {
+        let mut hash: u32 = 0x811C9DC5;
+        for byte in source_code.bytes() {
+            hash ^= byte as u32;
+            hash = hash.wrapping_mul(0x01000193);
+        }
+        return hash;
+    }
+
+    // Function to get each line of the build information without any comment markers
+    pub fn get_lines(&self) -> Vec<String> {
+        let mut lines: Vec<String> = vec![
+            format!("Generated by Nexus v{}", self.version),
+            format!("Target: {}", self.target)
+        ];
+        if self.options.len() > 0 {
+            lines.push(format!("Options: {}", self.options.join(", ")));
+        }
+        lines.push(format!("Source hash: {:08X}", self.source_hash));
+        return lines;
+    }
+}
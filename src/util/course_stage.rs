@@ -0,0 +1,51 @@
+// How far into the course the class is, which is the last phase the compiler runs and shows
+// The stages are in order, so a stage includes every phase before it
+#[derive (Debug, Clone, Copy, PartialEq, PartialOrd, strum::Display)]
+pub enum CourseStage {
+    #[strum (serialize = "lex")]
+    Lex,
+    #[strum (serialize = "parse")]
+    Parse,
+    #[strum (serialize = "semantic")]
+    SemanticAnalysis,
+    #[strum (serialize = "codegen")]
+    CodeGeneration
+}
+
+impl CourseStage {
+    pub const ALL: [CourseStage; 4] = [CourseStage::Lex, CourseStage::Parse, CourseStage::SemanticAnalysis, CourseStage::CodeGeneration];
+
+    // Function to get the stage from the name it is saved and linked with
+    pub fn from_name(name: &str) -> Result<Self, String> {
+        return match CourseStage::ALL.iter().find(|stage| stage.to_string().eq(name.trim())) {
+            Some(stage) => Ok(*stage),
+            None => Err(format!(
+                "Invalid course stage [ {} ], which must be one of {}",
+                name.trim(),
+                CourseStage::ALL.iter().map(|stage| stage.to_string()).collect::<Vec<String>>().join(", ")
+            ))
+        };
+    }
+
+    // Function to get the name of the last phase of the stage for the logs
+    pub fn get_phase_name(&self) -> &'static str {
+        return match self {
+            CourseStage::Lex => "lexing",
+            CourseStage::Parse => "parsing",
+            CourseStage::SemanticAnalysis => "semantic analysis",
+            CourseStage::CodeGeneration => "code generation"
+        };
+    }
+
+    // Function to check if the phase of the other stage is run in this stage
+    pub fn includes(&self, other: CourseStage) -> bool {
+        return other <= *self;
+    }
+}
+
+impl Default for CourseStage {
+    // Everything is shown unless an instructor picks an earlier stage
+    fn default() -> Self {
+        return CourseStage::CodeGeneration;
+    }
+}
@@ -0,0 +1,82 @@
+use serde::Serialize;
+
+use crate::util::{messages::MessageCode, nexus_log::{LogTypes, LogSource}, position::Position};
+
+// The category of an error so tools can check for a specific problem without reading the message
+#[derive (Debug, Clone, Copy, PartialEq, strum::Display, Serialize)]
+pub enum ErrorClass {
+    InvalidToken,
+    SyntaxError,
+    UndeclaredId,
+    Redeclaration,
+    TypeMismatch,
+    IndexOutOfBounds,
+    StringTooLong
+}
+
+// A single error that was reported by the compiler
+#[derive (Debug, Clone, Serialize)]
+pub struct Diagnostic {
+    pub class: ErrorClass,
+    // The code of the message in the catalog, which does not change with the language of the message
+    pub code: MessageCode,
+    pub position: Position,
+    pub message: String
+}
+
+// A single warning that was reported by a lint
+#[derive (Debug, Clone, Serialize)]
+pub struct LintWarning {
+    // The name of the lint that found the problem
+    pub lint: &'static str,
+    // The code of the message in the catalog, which does not change with the language of the message
+    pub code: MessageCode,
+    pub position: Position,
+    pub message: String
+}
+
+// A change to the source code that fixes a problem, which inserts the text at the position (line, col) starting at 1
+#[derive (Debug, Clone, PartialEq, Serialize)]
+#[serde (rename_all = "camelCase")]
+pub struct QuickFix {
+    // What the fix does, such as "Insert }"
+    pub title: String,
+    pub position: Position,
+    pub insert: String
+}
+
+impl QuickFix {
+    pub fn new(title: &str, position: Position, insert: &str) -> Self {
+        return QuickFix {
+            title: String::from(title),
+            position: position,
+            insert: String::from(insert)
+        };
+    }
+
+    // Function to get the source code with the fix applied, which is None if the position is not in the code
+    pub fn apply(&self, source_code: &str) -> Option<String> {
+        let mut lines: Vec<String> = source_code.split('\n').map(|source_line| String::from(source_line)).collect();
+        let fixed_line: &mut String = lines.get_mut(self.position.line.checked_sub(1)?)?;
+
+        // The column can be right after the end of the line, such as to close a block at the end of the code
+        let insert_index: usize = match fixed_line.char_indices().nth(self.position.col.checked_sub(1)?) {
+            Some((index, _)) => index,
+            None if self.position.col - 1 == fixed_line.chars().count() => fixed_line.len(),
+            None => return None
+        };
+        fixed_line.insert_str(insert_index, &self.insert);
+        return Some(lines.join("\n"));
+    }
+}
+
+// A single error or warning of a program that is shown in the issues pane
+#[derive (Debug, Clone)]
+pub struct Issue {
+    pub log_type: LogTypes,
+    pub source: LogSource,
+    pub code: Option<MessageCode>,
+    pub position: Position,
+    pub message: String,
+    pub fix: Option<QuickFix>
+}
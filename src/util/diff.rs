@@ -0,0 +1,61 @@
+// A piece of the old or new text in a diff
+#[derive (Debug, Clone, PartialEq)]
+pub enum DiffItem {
+    // In both the old and new text
+    Same(String),
+    // Only in the old text
+    Removed(String),
+    // Only in the new text
+    Added(String)
+}
+
+// Function to get the smallest set of changes that turn the old items into the new items
+// Items can be anything that is compared as a whole, such as the lines of assembly or the bytes of an image
+pub fn diff_items(old_items: &Vec<&str>, new_items: &Vec<&str>) -> Vec<DiffItem> {
+    // Longest common subsequence table
+    let mut lcs: Vec<Vec<usize>> = vec![vec![0; new_items.len() + 1]; old_items.len() + 1];
+    for i in (0..old_items.len()).rev() {
+        for j in (0..new_items.len()).rev() {
+            if old_items[i].eq(new_items[j]) {
+                lcs[i][j] = lcs[i + 1][j + 1] + 1;
+            } else {
+                lcs[i][j] = lcs[i + 1][j].max(lcs[i][j + 1]);
+            }
+        }
+    }
+
+    // Walk the table to build the diff
+    let mut diff: Vec<DiffItem> = Vec::new();
+    let mut i: usize = 0;
+    let mut j: usize = 0;
+    while i < old_items.len() && j < new_items.len() {
+        if old_items[i].eq(new_items[j]) {
+            diff.push(DiffItem::Same(String::from(old_items[i])));
+            i += 1;
+            j += 1;
+        } else if lcs[i + 1][j] >= lcs[i][j + 1] {
+            diff.push(DiffItem::Removed(String::from(old_items[i])));
+            i += 1;
+        } else {
+            diff.push(DiffItem::Added(String::from(new_items[j])));
+            j += 1;
+        }
+    }
+    while i < old_items.len() {
+        diff.push(DiffItem::Removed(String::from(old_items[i])));
+        i += 1;
+    }
+    while j < new_items.len() {
+        diff.push(DiffItem::Added(String::from(new_items[j])));
+        j += 1;
+    }
+
+    return diff;
+}
+
+// Function to get the number of (added, removed) items in a diff
+pub fn count_changes(diff: &Vec<DiffItem>) -> (usize, usize) {
+    let num_added: usize = diff.iter().filter(|item| matches!(item, DiffItem::Added(_))).count();
+    let num_removed: usize = diff.iter().filter(|item| matches!(item, DiffItem::Removed(_))).count();
+    return (num_added, num_removed);
+}
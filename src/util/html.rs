@@ -0,0 +1,25 @@
+// Function to escape text so it is shown as is when placed in an element with set_inner_html
+// Quotes are also escaped so the text is safe to use inside of attributes like title
+pub fn escape_html(text: &str) -> String {
+    let mut escaped: String = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            '\'' => escaped.push_str("&#39;"),
+            _ => escaped.push(c)
+        }
+    }
+    return escaped;
+}
+
+// Function to undo escape_html, which is used to make sure escaped text reads back the same
+pub fn unescape_html(text: &str) -> String {
+    return text.replace("&lt;", "<")
+        .replace("&gt;", ">")
+        .replace("&quot;", "\"")
+        .replace("&#39;", "'")
+        .replace("&amp;", "&");
+}
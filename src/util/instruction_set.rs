@@ -0,0 +1,110 @@
+// The opcodes in the 6502 subset, named by mnemonic and addressing mode
+#[derive (Debug, Clone, Copy, PartialEq)]
+pub enum Opcode6502 {
+    LdaImmediate = 0xA9,
+    LdaAbsolute = 0xAD,
+    StaAbsolute = 0x8D,
+    AdcAbsolute = 0x6D,
+    LdxImmediate = 0xA2,
+    LdxAbsolute = 0xAE,
+    LdyImmediate = 0xA0,
+    LdyAbsolute = 0xAC,
+    CpxAbsolute = 0xEC,
+    Bne = 0xD0,
+    Sys = 0xFF,
+    Brk = 0x00
+}
+
+// Metadata for a 6502 instruction used by the code generator
+#[derive (Debug)]
+pub struct Instruction6502 {
+    pub opcode: Opcode6502,
+
+    // The number of bytes including the opcode
+    pub length: u8,
+
+    // The number of cycles the instruction takes, not counting extra cycles for a taken branch
+    pub cycles: u8,
+    pub mnemonic: &'static str,
+    pub operands: &'static str,
+    pub description: &'static str
+}
+
+// Metadata for a RISC-V instruction used by the code generator
+#[derive (Debug)]
+pub struct InstructionRiscV {
+    pub mnemonic: &'static str,
+    pub operands: &'static str,
+    pub description: &'static str
+}
+
+// Every instruction in the 6502 subset that the code generator emits
+pub const INSTRUCTIONS_6502: [Instruction6502; 12] = [
+    Instruction6502 { opcode: Opcode6502::LdaImmediate, length: 2, cycles: 2, mnemonic: "LDA", operands: "#$nn", description: "Load the accumulator with a constant" },
+    Instruction6502 { opcode: Opcode6502::LdaAbsolute, length: 3, cycles: 4, mnemonic: "LDA", operands: "$nnnn", description: "Load the accumulator from memory" },
+    Instruction6502 { opcode: Opcode6502::StaAbsolute, length: 3, cycles: 4, mnemonic: "STA", operands: "$nnnn", description: "Store the accumulator in memory" },
+    Instruction6502 { opcode: Opcode6502::AdcAbsolute, length: 3, cycles: 4, mnemonic: "ADC", operands: "$nnnn", description: "Add the value in memory to the accumulator and keep the result in the accumulator" },
+    Instruction6502 { opcode: Opcode6502::LdxImmediate, length: 2, cycles: 2, mnemonic: "LDX", operands: "#$nn", description: "Load the X register with a constant" },
+    Instruction6502 { opcode: Opcode6502::LdxAbsolute, length: 3, cycles: 4, mnemonic: "LDX", operands: "$nnnn", description: "Load the X register from memory" },
+    Instruction6502 { opcode: Opcode6502::LdyImmediate, length: 2, cycles: 2, mnemonic: "LDY", operands: "#$nn", description: "Load the Y register with a constant" },
+    Instruction6502 { opcode: Opcode6502::LdyAbsolute, length: 3, cycles: 4, mnemonic: "LDY", operands: "$nnnn", description: "Load the Y register from memory" },
+    Instruction6502 { opcode: Opcode6502::CpxAbsolute, length: 3, cycles: 4, mnemonic: "CPX", operands: "$nnnn", description: "Compare the value in memory with the X register and set the Z flag if they are equal" },
+    Instruction6502 { opcode: Opcode6502::Bne, length: 2, cycles: 2, mnemonic: "BNE", operands: "$nn", description: "Branch forward or backward by the offset if the Z flag is not set" },
+    // SYS is not a real 6502 instruction, so its cost is estimated to be the same as a JSR
+    Instruction6502 { opcode: Opcode6502::Sys, length: 1, cycles: 6, mnemonic: "SYS", operands: "", description: "System call to print the integer in Y if X is 1 or the string at the address in Y if X is 2" },
+    Instruction6502 { opcode: Opcode6502::Brk, length: 1, cycles: 7, mnemonic: "BRK", operands: "", description: "Break, which ends the program" }
+];
+
+// Every instruction that the RISC-V code generator emits
+pub const INSTRUCTIONS_RISCV: [InstructionRiscV; 21] = [
+    InstructionRiscV { mnemonic: "li", operands: "rd, imm", description: "Load the immediate value into rd" },
+    InstructionRiscV { mnemonic: "la", operands: "rd, label", description: "Load the address of the label into rd" },
+    InstructionRiscV { mnemonic: "lbu", operands: "rd, offset(rs1)", description: "Load the unsigned byte at rs1 + offset into rd" },
+    InstructionRiscV { mnemonic: "lhu", operands: "rd, offset(rs1)", description: "Load the unsigned half word at rs1 + offset into rd" },
+    InstructionRiscV { mnemonic: "lw", operands: "rd, offset(rs1)", description: "Load the word at rs1 + offset into rd" },
+    InstructionRiscV { mnemonic: "lwu", operands: "rd, offset(rs1)", description: "Load the unsigned word at rs1 + offset into rd" },
+    InstructionRiscV { mnemonic: "sb", operands: "rs2, offset(rs1)", description: "Store the low byte of rs2 at rs1 + offset" },
+    InstructionRiscV { mnemonic: "sw", operands: "rs2, offset(rs1)", description: "Store the word in rs2 at rs1 + offset" },
+    InstructionRiscV { mnemonic: "add", operands: "rd, rs1, rs2", description: "Add rs1 and rs2 and store the sum in rd" },
+    InstructionRiscV { mnemonic: "addi", operands: "rd, rs1, imm", description: "Add the immediate value to rs1 and store the sum in rd" },
+    InstructionRiscV { mnemonic: "andi", operands: "rd, rs1, imm", description: "Bitwise and rs1 with the immediate value and store the result in rd" },
+    InstructionRiscV { mnemonic: "divu", operands: "rd, rs1, rs2", description: "Divide rs1 by rs2 as unsigned values and store the quotient in rd" },
+    InstructionRiscV { mnemonic: "remu", operands: "rd, rs1, rs2", description: "Divide rs1 by rs2 as unsigned values and store the remainder in rd" },
+    InstructionRiscV { mnemonic: "mv", operands: "rd, rs1", description: "Copy the value in rs1 to rd" },
+    InstructionRiscV { mnemonic: "beq", operands: "rs1, rs2, label", description: "Branch to the label if rs1 and rs2 are equal" },
+    InstructionRiscV { mnemonic: "bne", operands: "rs1, rs2, label", description: "Branch to the label if rs1 and rs2 are not equal" },
+    InstructionRiscV { mnemonic: "blt", operands: "rs1, rs2, label", description: "Branch to the label if rs1 is less than rs2" },
+    InstructionRiscV { mnemonic: "j", operands: "label", description: "Jump to the label" },
+    InstructionRiscV { mnemonic: "call", operands: "label", description: "Call the subroutine at the label and save the return address in ra" },
+    InstructionRiscV { mnemonic: "ret", operands: "", description: "Return from the subroutine to the address in ra" },
+    InstructionRiscV { mnemonic: "ecall", operands: "", description: "System call selected by a7 (64 is write and 93 is exit)" }
+];
+
+// Function to get the metadata for a 6502 opcode
+pub fn get_instruction_6502(opcode: u8) -> Option<&'static Instruction6502> {
+    return INSTRUCTIONS_6502.iter().find(|instruction| instruction.opcode as u8 == opcode);
+}
+
+// Function to get the metadata for a RISC-V mnemonic
+pub fn get_instruction_riscv(mnemonic: &str) -> Option<&'static InstructionRiscV> {
+    return INSTRUCTIONS_RISCV.iter().find(|instruction| instruction.mnemonic.eq(mnemonic));
+}
+
+// Function to get the text of the hover tooltip for a 6502 instruction
+pub fn get_tooltip_6502(instruction: &Instruction6502) -> String {
+    return get_tooltip(instruction.mnemonic, instruction.operands, instruction.description);
+}
+
+// Function to get the text of the hover tooltip for a RISC-V instruction
+pub fn get_tooltip_riscv(instruction: &InstructionRiscV) -> String {
+    return get_tooltip(instruction.mnemonic, instruction.operands, instruction.description);
+}
+
+fn get_tooltip(mnemonic: &str, operands: &str, description: &str) -> String {
+    // Instructions without operands should not have the extra space
+    if operands.len() == 0 {
+        return format!("{} - {}", mnemonic, description);
+    } else {
+        return format!("{} {} - {}", mnemonic, operands, description);
+    }
+}
@@ -0,0 +1,200 @@
+use regex::Regex;
+
+// The characters that can come after the first letter of an identifier, which is always a-z
+// The course grammar only has single letter identifiers, so nothing can follow the first letter by default
+#[derive (Debug, Clone, Copy, PartialEq, Default)]
+pub struct IdentifierCharset {
+    pub letters: bool,
+    pub digits: bool,
+    pub underscores: bool
+}
+
+impl IdentifierCharset {
+    // Function to read the characters from the user's input, such as "a-z0-9_", where an empty input is single letters
+    pub fn from_input(charset_input: &str) -> Result<Self, String> {
+        let mut identifier_charset: IdentifierCharset = IdentifierCharset::default();
+        let mut remaining_input: &str = charset_input.trim();
+        while remaining_input.len() > 0 {
+            if let Some(rest) = remaining_input.strip_prefix("a-z") {
+                identifier_charset.letters = true;
+                remaining_input = rest;
+            } else if let Some(rest) = remaining_input.strip_prefix("0-9") {
+                identifier_charset.digits = true;
+                remaining_input = rest;
+            } else if let Some(rest) = remaining_input.strip_prefix("_") {
+                identifier_charset.underscores = true;
+                remaining_input = rest;
+            } else {
+                return Err(format!("Invalid identifier characters [ {} ], which can only be a-z, 0-9, and _ after the first letter", charset_input.trim()));
+            }
+        }
+        return Ok(identifier_charset);
+    }
+
+    // Function to get the characters in the same form as the input, which is empty for single letters
+    pub fn to_input(&self) -> String {
+        let mut charset_input: String = String::new();
+        if self.letters {
+            charset_input.push_str("a-z");
+        }
+        if self.digits {
+            charset_input.push_str("0-9");
+        }
+        if self.underscores {
+            charset_input.push_str("_");
+        }
+        return charset_input;
+    }
+
+    // Function to check if identifiers can be longer than a single letter
+    pub fn is_extended(&self) -> bool {
+        return self.letters || self.digits || self.underscores;
+    }
+}
+
+// The additions to the course grammar, which each profile either allows or reports where they are used
+#[derive (Debug, Clone, Copy, PartialEq)]
+pub enum LanguageFeature {
+    Constants,
+    GroupedExpressions,
+    Chars,
+    StringIndexing,
+    StringLength,
+    LongIdentifiers
+}
+
+impl LanguageFeature {
+    pub const ALL: [LanguageFeature; 6] = [
+        LanguageFeature::Constants,
+        LanguageFeature::GroupedExpressions,
+        LanguageFeature::Chars,
+        LanguageFeature::StringIndexing,
+        LanguageFeature::StringLength,
+        LanguageFeature::LongIdentifiers
+    ];
+
+    // Function to get the name of the extension for the error messages
+    pub fn get_name(&self) -> &'static str {
+        return match self {
+            LanguageFeature::Constants => "constant",
+            LanguageFeature::GroupedExpressions => "grouped integer expression",
+            LanguageFeature::Chars => "char",
+            LanguageFeature::StringIndexing => "string indexing",
+            LanguageFeature::StringLength => "string length",
+            LanguageFeature::LongIdentifiers => "long identifier"
+        };
+    }
+}
+
+// The grammars a class can pick from
+#[derive (Debug, Clone, Copy, PartialEq, strum::Display)]
+pub enum LanguagePreset {
+    // Only the grammar from the course, so projects can be checked against it exactly
+    #[strum (serialize = "course")]
+    Course,
+    // Every extension Nexus adds to the grammar
+    #[strum (serialize = "extended")]
+    Extended
+}
+
+impl LanguagePreset {
+    pub const ALL: [LanguagePreset; 2] = [LanguagePreset::Course, LanguagePreset::Extended];
+
+    // Function to get the preset from the name it is saved with
+    pub fn from_name(name: &str) -> Result<Self, String> {
+        return match LanguagePreset::ALL.iter().find(|preset| preset.to_string().eq(name.trim())) {
+            Some(preset) => Ok(*preset),
+            None => Err(format!(
+                "Invalid language profile [ {} ], which must be one of {}",
+                name.trim(),
+                LanguagePreset::ALL.iter().map(|preset| preset.to_string()).collect::<Vec<String>>().join(", ")
+            ))
+        };
+    }
+
+    // Function to get the extensions that can be used with the preset
+    pub fn get_features(&self) -> &'static [LanguageFeature] {
+        return match self {
+            // The course grammar does not have any of the extensions
+            LanguagePreset::Course => &[],
+            LanguagePreset::Extended => &LanguageFeature::ALL
+        };
+    }
+
+    // Function to check if the extension can be used with the preset
+    pub fn allows(&self, feature: LanguageFeature) -> bool {
+        return self.get_features().contains(&feature);
+    }
+}
+
+impl Default for LanguagePreset {
+    // The extensions have always been on, so existing programs keep compiling
+    fn default() -> Self {
+        return LanguagePreset::Extended;
+    }
+}
+
+// The parts of the language that can be changed from the course grammar
+#[derive (Debug, Clone, PartialEq, Default)]
+pub struct LanguageProfile {
+    pub preset: LanguagePreset,
+    pub identifier_charset: IdentifierCharset
+}
+
+impl LanguageProfile {
+    pub fn new() -> Self {
+        return LanguageProfile::default();
+    }
+
+    // Function to get the profile from the user's inputs for the preset and the identifier characters
+    pub fn from_inputs(preset_input: &str, charset_input: &str) -> Result<Self, String> {
+        let mut language_profile: LanguageProfile = LanguageProfile::new();
+        language_profile.preset = LanguagePreset::from_name(preset_input)?;
+        language_profile.set_identifier_charset(charset_input)?;
+        return Ok(language_profile);
+    }
+
+    // Function to set the characters identifiers can have from the user's input
+    pub fn set_identifier_charset(&mut self, charset_input: &str) -> Result<(), String> {
+        let identifier_charset: IdentifierCharset = IdentifierCharset::from_input(charset_input)?;
+        if identifier_charset.is_extended() && !self.allows(LanguageFeature::LongIdentifiers) {
+            return Err(format!("Identifier characters cannot be changed in the {} language profile, which only has single letter identifiers", self.preset));
+        }
+        self.identifier_charset = identifier_charset;
+        return Ok(());
+    }
+
+    // Function to check if the extension can be used in this profile
+    pub fn allows(&self, feature: LanguageFeature) -> bool {
+        return self.preset.allows(feature);
+    }
+
+    // Function to check if identifiers can be more than one letter, which also needs the characters after the first letter
+    fn has_long_identifiers(&self) -> bool {
+        return self.identifier_charset.is_extended() && self.allows(LanguageFeature::LongIdentifiers);
+    }
+
+    // Function to get the regex the lexer and parser match identifiers with
+    // Building a regex is slow, so they keep it until the profile changes instead of building it for every token
+    pub fn get_identifier_regex(&self) -> Regex {
+        if !self.has_long_identifiers() {
+            return Regex::new(r"^[a-z]$").unwrap();
+        }
+        return Regex::new(&format!("^[a-z][{}]*$", self.identifier_charset.to_input())).unwrap();
+    }
+
+    // Function to describe what identifiers can be for the error messages
+    pub fn describe_identifiers(&self) -> String {
+        return match self.has_long_identifiers() {
+            true => format!("a letter (a - z) followed by any number of [ {} ]", self.identifier_charset.to_input()),
+            false => String::from("a single letter (a - z)")
+        };
+    }
+}
+
+// Function to check if the text could be an identifier in any profile, such as for tokens that are given directly to the parser
+pub fn is_possible_identifier(text: &str) -> bool {
+    let mut widest_profile: LanguageProfile = LanguageProfile::new();
+    widest_profile.identifier_charset = IdentifierCharset { letters: true, digits: true, underscores: true };
+    return widest_profile.get_identifier_regex().is_match(text);
+}
@@ -0,0 +1,392 @@
+use std::collections::{BTreeMap, HashMap};
+use std::sync::RwLock;
+use std::fmt;
+
+use serde::{Serialize, Serializer};
+use strum::IntoEnumIterator;
+#[cfg(feature = "ui")]
+use wasm_bindgen::prelude::*;
+
+use crate::nexus::program_cache;
+use crate::util::diagnostic::{ErrorClass, QuickFix};
+
+// The code of every error, warning, and hint with a position, which stays the same when the message is translated
+// Codes start with D for the define directives of the preprocessor, L for the lexer, P for the parser, S for semantic analysis, and W for the lints
+#[derive (Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, strum::EnumIter)]
+pub enum MessageCode {
+    ConstantNotInProfile,
+    InvalidConstantName,
+    InvalidConstantValue,
+    ConstantRedefinition,
+
+    UnrecognizedToken,
+    UnrecognizedKeywordCase,
+    UnrecognizedIdCase,
+    UnrecognizedStringToken,
+    UnclosedString,
+    UnclosedComment,
+    MissingEop,
+    TokenNotInProfile,
+
+    UnexpectedToken,
+    UnexpectedKeyword,
+    UnknownToken,
+    MissingToken,
+    InvalidStatement,
+    MissingStatement,
+    InvalidExpression,
+    MissingExpression,
+    InvalidBooleanExpr,
+    MissingBooleanExpr,
+    PrintMissingLParen,
+    PrintEndedEarly,
+    PrintMissingExpression,
+    PrintExtraExpression,
+    PrintMissingRParen,
+    EmptyBlock,
+    EmptyString,
+    EmptyConditionBody,
+    InvalidIdentifier,
+    GrammarNotInProfile,
+    UnclosedSymbol,
+    UnopenedSymbol,
+    AssignmentInCondition,
+
+    Redeclaration,
+    UndeclaredId,
+    AssignmentTypeMismatch,
+    AdditionTypeMismatch,
+    BooleanTypeMismatch,
+    UninitializedUse,
+    InitializedAfterUse,
+    ConstantOverflow,
+    OperandsOverflow,
+    StringComparison,
+    UsedButNeverInitialized,
+    NeverInitializedOrUsed,
+    NeverUsed,
+    DeepNesting,
+    IndexTypeMismatch,
+    IndexOutOfBounds,
+    LenTypeMismatch,
+    StringTooLong,
+
+    EmptyBlockLint,
+    ConstantCondition,
+    SelfAssignment,
+    ZeroAddition,
+    SelfComparison,
+    Shadowing,
+    StringHeap
+}
+
+impl MessageCode {
+    pub fn get_code(&self) -> &'static str {
+        return match self {
+            MessageCode::ConstantNotInProfile => "D001",
+            MessageCode::InvalidConstantName => "D002",
+            MessageCode::InvalidConstantValue => "D003",
+            MessageCode::ConstantRedefinition => "D004",
+
+            MessageCode::UnrecognizedToken => "L001",
+            MessageCode::UnrecognizedKeywordCase => "L002",
+            MessageCode::UnrecognizedIdCase => "L003",
+            MessageCode::UnrecognizedStringToken => "L004",
+            MessageCode::UnclosedString => "L005",
+            MessageCode::UnclosedComment => "L006",
+            MessageCode::MissingEop => "L007",
+            MessageCode::TokenNotInProfile => "L008",
+
+            MessageCode::UnexpectedToken => "P001",
+            MessageCode::UnexpectedKeyword => "P002",
+            MessageCode::UnknownToken => "P003",
+            MessageCode::MissingToken => "P004",
+            MessageCode::InvalidStatement => "P005",
+            MessageCode::MissingStatement => "P006",
+            MessageCode::InvalidExpression => "P007",
+            MessageCode::MissingExpression => "P008",
+            MessageCode::InvalidBooleanExpr => "P009",
+            MessageCode::MissingBooleanExpr => "P010",
+            MessageCode::PrintMissingLParen => "P011",
+            MessageCode::PrintEndedEarly => "P012",
+            MessageCode::PrintMissingExpression => "P013",
+            MessageCode::PrintExtraExpression => "P014",
+            MessageCode::PrintMissingRParen => "P015",
+            MessageCode::EmptyBlock => "P016",
+            MessageCode::EmptyString => "P017",
+            MessageCode::EmptyConditionBody => "P018",
+            MessageCode::InvalidIdentifier => "P019",
+            MessageCode::GrammarNotInProfile => "P020",
+            MessageCode::UnclosedSymbol => "P021",
+            MessageCode::UnopenedSymbol => "P022",
+            MessageCode::AssignmentInCondition => "P023",
+
+            MessageCode::Redeclaration => "S001",
+            MessageCode::UndeclaredId => "S002",
+            MessageCode::AssignmentTypeMismatch => "S003",
+            MessageCode::AdditionTypeMismatch => "S004",
+            MessageCode::BooleanTypeMismatch => "S005",
+            MessageCode::UninitializedUse => "S006",
+            MessageCode::InitializedAfterUse => "S007",
+            MessageCode::ConstantOverflow => "S008",
+            MessageCode::OperandsOverflow => "S009",
+            MessageCode::StringComparison => "S010",
+            MessageCode::UsedButNeverInitialized => "S011",
+            MessageCode::NeverInitializedOrUsed => "S012",
+            MessageCode::NeverUsed => "S013",
+            MessageCode::DeepNesting => "S014",
+            MessageCode::IndexTypeMismatch => "S015",
+            MessageCode::IndexOutOfBounds => "S016",
+            MessageCode::LenTypeMismatch => "S017",
+            MessageCode::StringTooLong => "S018",
+
+            MessageCode::EmptyBlockLint => "W001",
+            MessageCode::ConstantCondition => "W002",
+            MessageCode::SelfAssignment => "W003",
+            MessageCode::ZeroAddition => "W004",
+            MessageCode::SelfComparison => "W005",
+            MessageCode::Shadowing => "W006",
+            MessageCode::StringHeap => "W007"
+        };
+    }
+
+    pub fn from_code(code: &str) -> Option<MessageCode> {
+        return MessageCode::iter().find(|message_code| message_code.get_code().eq(code));
+    }
+
+    // The class of the error the message reports, which is None for warnings and hints
+    pub fn get_error_class(&self) -> Option<ErrorClass> {
+        return match self {
+            MessageCode::ConstantNotInProfile
+            | MessageCode::InvalidConstantName
+            | MessageCode::InvalidConstantValue => Some(ErrorClass::SyntaxError),
+            MessageCode::ConstantRedefinition => Some(ErrorClass::Redeclaration),
+
+            MessageCode::UnrecognizedToken
+            | MessageCode::UnrecognizedKeywordCase
+            | MessageCode::UnrecognizedIdCase
+            | MessageCode::UnrecognizedStringToken
+            | MessageCode::TokenNotInProfile
+            | MessageCode::UnknownToken => Some(ErrorClass::InvalidToken),
+
+            MessageCode::UnclosedString
+            | MessageCode::UnclosedComment
+            | MessageCode::UnexpectedToken
+            | MessageCode::UnexpectedKeyword
+            | MessageCode::MissingToken
+            | MessageCode::InvalidStatement
+            | MessageCode::MissingStatement
+            | MessageCode::InvalidExpression
+            | MessageCode::MissingExpression
+            | MessageCode::InvalidBooleanExpr
+            | MessageCode::MissingBooleanExpr
+            | MessageCode::PrintMissingLParen
+            | MessageCode::PrintEndedEarly
+            | MessageCode::PrintMissingExpression
+            | MessageCode::PrintExtraExpression
+            | MessageCode::PrintMissingRParen
+            | MessageCode::InvalidIdentifier
+            | MessageCode::GrammarNotInProfile
+            | MessageCode::AssignmentInCondition => Some(ErrorClass::SyntaxError),
+
+            MessageCode::Redeclaration => Some(ErrorClass::Redeclaration),
+            MessageCode::UndeclaredId => Some(ErrorClass::UndeclaredId),
+            MessageCode::AssignmentTypeMismatch
+            | MessageCode::AdditionTypeMismatch
+            | MessageCode::BooleanTypeMismatch
+            | MessageCode::IndexTypeMismatch
+            | MessageCode::LenTypeMismatch => Some(ErrorClass::TypeMismatch),
+            MessageCode::IndexOutOfBounds => Some(ErrorClass::IndexOutOfBounds),
+            MessageCode::StringTooLong => Some(ErrorClass::StringTooLong),
+
+            MessageCode::MissingEop
+            | MessageCode::EmptyBlock
+            | MessageCode::EmptyString
+            | MessageCode::EmptyConditionBody
+            | MessageCode::UnclosedSymbol
+            | MessageCode::UnopenedSymbol
+            | MessageCode::UninitializedUse
+            | MessageCode::InitializedAfterUse
+            | MessageCode::ConstantOverflow
+            | MessageCode::OperandsOverflow
+            | MessageCode::StringComparison
+            | MessageCode::UsedButNeverInitialized
+            | MessageCode::NeverInitializedOrUsed
+            | MessageCode::NeverUsed
+            | MessageCode::DeepNesting
+            | MessageCode::EmptyBlockLint
+            | MessageCode::ConstantCondition
+            | MessageCode::SelfAssignment
+            | MessageCode::ZeroAddition
+            | MessageCode::SelfComparison
+            | MessageCode::Shadowing
+            | MessageCode::StringHeap => None
+        };
+    }
+
+    // The English message, where each {name} is replaced with the parameter of that name
+    pub fn get_default_template(&self) -> &'static str {
+        return match self {
+            MessageCode::ConstantNotInProfile => "Error at {position}; Constant [ {name} ] cannot be defined because the {feature} extension is not allowed in the {profile} language profile",
+            MessageCode::InvalidConstantName => "Error at {position}; Invalid constant name [ {name} ], which must be uppercase letters and underscores",
+            MessageCode::InvalidConstantValue => "Error at {position}; Invalid value [ {value} ] for constant [ {name} ], which must be a digit, boolean, or string",
+            MessageCode::ConstantRedefinition => "Error at {position}; Constant [ {name} ] has already been defined",
+
+            MessageCode::UnrecognizedToken => "Error at {position}; Unrecognized token '{token}'",
+            MessageCode::UnrecognizedKeywordCase => "Error at {position}; Unrecognized token '{token}'; Did you mean '{suggestion}'? Keywords are lowercase",
+            MessageCode::UnrecognizedIdCase => "Error at {position}; Unrecognized token '{token}'; Did you mean '{suggestion}'? Identifiers are lowercase letters (a - z)",
+            MessageCode::UnrecognizedStringToken => "Error at {position}; Unrecognized token '{token}' in string starting at {string_position}; Strings may only contain lowercase letters (a - z) and spaces",
+            MessageCode::UnclosedString => "Unclosed string starting at {position}",
+            MessageCode::UnclosedComment => "Unclosed comment starting at {position}",
+            MessageCode::MissingEop => "Program did not end with EOP symbol [ $ ], so it was assumed at {position}",
+            MessageCode::TokenNotInProfile => "Error at {position}; Token [ {token} ] is part of the {feature} extension, which is not allowed in the {profile} language profile",
+
+            MessageCode::UnexpectedToken => "Invalid token [ {found} ] at {position}; Expected {expected}",
+            MessageCode::UnexpectedKeyword => "Invalid token at {position}; Found {found}, but expected {expected}",
+            MessageCode::UnknownToken => "Unrecognized token [ {token} ] at {position}",
+            MessageCode::MissingToken => "Missing token {expected} at end of program",
+            MessageCode::InvalidStatement => "Invalid statement token [ {found} ] at {position}; Valid statement beginning tokens are {expected}",
+            MessageCode::MissingStatement => "Missing statement token at end of program; Valid statement beginning tokens are {expected}",
+            MessageCode::InvalidExpression => "Invalid expression token [ {found} ] at {position}; Valid expression beginning tokens are {expected}",
+            MessageCode::MissingExpression => "Missing expression token at end of program; Valid expression beginning tokens are {expected}",
+            MessageCode::InvalidBooleanExpr => "Invalid boolean expression token [ {found} ] at {position}; Valid boolean expression beginning tokens are {expected}",
+            MessageCode::MissingBooleanExpr => "Missing boolean expression token at end of program; Valid boolean expression beginning tokens are {expected}",
+            MessageCode::PrintMissingLParen => "Invalid print statement at {position}; print requires exactly one expression in parentheses, but found [ {found} ] at {found_position}",
+            MessageCode::PrintEndedEarly => "Invalid print statement at {position}; print requires exactly one expression in parentheses, but the program ended",
+            MessageCode::PrintMissingExpression => "Invalid print statement at {position}; print requires exactly one expression, but none was found",
+            MessageCode::PrintExtraExpression => "Invalid print statement at {position}; print requires exactly one expression, but found [ {found} ] at {found_position} after it",
+            MessageCode::PrintMissingRParen => "Invalid print statement at {position}; print is missing [Symbol(RParen)] after its expression, but found [ {found} ] at {found_position}",
+            MessageCode::EmptyBlock => "Empty block found starting at {position}",
+            MessageCode::EmptyString => "Empty string found starting at {position}",
+            MessageCode::EmptyConditionBody => "Warning at {span}; The condition of the {statement} statement has no effect because its body is empty",
+            MessageCode::InvalidIdentifier => "Error at {position}; Identifier [ {id} ] is not allowed in this language profile; Identifiers are {allowed}",
+            MessageCode::GrammarNotInProfile => "Error at {position}; The {feature} extension is not allowed in the {profile} language profile",
+            MessageCode::UnclosedSymbol => "Hint at {position}; [ {open} ] is never closed with [ {close} ]",
+            MessageCode::UnopenedSymbol => "Hint at {position}; [ {close} ] does not close any [ {open} ]",
+            MessageCode::AssignmentInCondition => "Error at {position}; Found the assignment [ = ] in a boolean expression, which compares with [ == ] or [ != ]; Did you mean [ == ]?",
+
+            MessageCode::Redeclaration => "Error at {position}; Id [ {id} ] has already been declared within the current scope",
+            MessageCode::UndeclaredId => "Error at {position}; Id [ {id} ] has not been declared",
+            MessageCode::AssignmentTypeMismatch => "Mismatched types at {position}; Expected {expected} for the assignment type, but received {received}",
+            MessageCode::AdditionTypeMismatch => "Error at {position}; Expected {expected} for the addition expression, but received {received}",
+            MessageCode::BooleanTypeMismatch => "Error at {position}; Mismatched types for boolean expression; Received {left} on the left side and {right} on the right side",
+            MessageCode::UninitializedUse => "Warning at {position}; Use of uninitialized variable [ {id} ] that was declared at {declared_position}",
+            MessageCode::InitializedAfterUse => "Warning at {position}; Id [ {id} ] declared at {declared_position} is being initialized after already being used",
+            MessageCode::ConstantOverflow => "Warning at {span}; Constant expression evaluates to {sum}, which exceeds the maximum integer value of {max} on the {target} target and will overflow",
+            MessageCode::OperandsOverflow => "Warning at {span}; Constant operands of the expression sum to {sum}, which exceeds the maximum integer value of {max} on the {target} target and will overflow",
+            MessageCode::StringComparison => "Warning at {span}; String comparison compares addresses on the {target} target, not the contents of the strings",
+            MessageCode::UsedButNeverInitialized => "Warning at {position}; Id [ {id} ] is declared and used, but never initialized",
+            MessageCode::NeverInitializedOrUsed => "Warning at {position}; Id [ {id} ] is declared, but never initialized or used",
+            MessageCode::NeverUsed => "Warning at {position}; Id [ {id} ] is declared and initialized, but never used",
+            MessageCode::DeepNesting => "Warning at {position}; Block is nested {depth} levels deep, which is more than the limit of {max_depth}",
+            MessageCode::IndexTypeMismatch => "Error at {position}; Expected {expected} for the {part} of the index expression, but received {received}",
+            MessageCode::IndexOutOfBounds => "Error at {position}; Index {index} is out of bounds for the string \"{string}\" of length {length}",
+            MessageCode::LenTypeMismatch => "Error at {position}; Expected String for the argument of len, but received {received}",
+            MessageCode::StringTooLong => "Error at {position}; The string \"{string}\" has {length} characters, but strings can have at most {max_length} characters on the {target}",
+
+            MessageCode::EmptyBlockLint => "Warning at {position}; Empty block has no effect [empty-block]",
+            MessageCode::ConstantCondition => "Warning at {position}; The {statement} condition is always {value} [constant-condition]",
+            MessageCode::SelfAssignment => "Warning at {position}; Assignment of [ {id} ] to itself has no effect and will be removed [self-assignment]",
+            MessageCode::ZeroAddition => "Warning at {position}; Assignment of [ {id} ] plus 0 has no effect and will be removed [self-assignment]",
+            MessageCode::SelfComparison => "Warning at {position}; Comparison of [ {id} ] with itself is always {value} [self-comparison]",
+            MessageCode::Shadowing => "Warning at {position}; Id [ {id} ] shadows the declaration at {declared_position} [shadowing]",
+            MessageCode::StringHeap => "Warning at {position}; {num_strings} unique string(s) use {num_bytes} of the {available_bytes} bytes available for the heap, which leaves little room for the code [string-heap]"
+        };
+    }
+}
+
+impl fmt::Display for MessageCode {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        return write!(f, "{}", self.get_code());
+    }
+}
+
+impl Serialize for MessageCode {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        return serializer.serialize_str(self.get_code());
+    }
+}
+
+// A message from the catalog with the values of its parameters, which is turned into text when it is logged
+#[derive (Debug, Clone, PartialEq)]
+pub struct Message {
+    pub code: MessageCode,
+    pub params: Vec<(&'static str, String)>,
+
+    // The change that fixes the problem, if the fix is certain enough to be applied without asking
+    pub fix: Option<QuickFix>
+}
+
+impl Message {
+    pub fn new(code: MessageCode, params: Vec<(&'static str, String)>) -> Self {
+        return Message {
+            code: code,
+            params: params,
+            fix: None
+        };
+    }
+
+    // Function to add a quick fix to the message
+    pub fn with_fix(mut self, fix: QuickFix) -> Self {
+        self.fix = Some(fix);
+        return self;
+    }
+
+    // Function to get the text of the message in the language of the current catalog
+    pub fn get_text(&self) -> String {
+        let mut text: String = get_template(self.code);
+        for (name, value) in self.params.iter() {
+            text = text.replace(format!("{{{}}}", name).as_str(), value);
+        }
+        return text;
+    }
+}
+
+// Translated templates that replace the English ones, where codes that are not here use the default
+// Every thread shares them so programs compiled in parallel are in the same language
+static TRANSLATIONS: RwLock<BTreeMap<MessageCode, String>> = RwLock::new(BTreeMap::new());
+
+fn get_template(code: MessageCode) -> String {
+    return match TRANSLATIONS.read().expect("Should be able to read the translations").get(&code) {
+        Some(template) => template.to_owned(),
+        None => String::from(code.get_default_template())
+    };
+}
+
+// Function to replace the message templates with a JSON object of code to template
+// The whole catalog is checked before any of it is used, so an invalid catalog leaves the current one in place
+pub fn set_translations(catalog_json: &str) -> Result<(), String> {
+    let catalog: HashMap<String, String> = serde_json::from_str(catalog_json).map_err(|err| format!("Invalid message catalog: {}", err))?;
+
+    let mut new_translations: BTreeMap<MessageCode, String> = BTreeMap::new();
+    for (code, template) in catalog.into_iter() {
+        match MessageCode::from_code(&code) {
+            Some(message_code) => {
+                new_translations.insert(message_code, template);
+            },
+            None => return Err(format!("Invalid message catalog: Unknown message code {}", code))
+        }
+    }
+
+    *TRANSLATIONS.write().expect("Should be able to change the translations") = new_translations;
+    // The logs kept for unchanged programs are in the old language
+    program_cache::clear_cache();
+    return Ok(());
+}
+
+// Function to set the translated message templates, or go back to English with an empty object
+#[cfg(feature = "ui")]
+#[wasm_bindgen(js_name = "setMessageCatalog")]
+pub fn set_message_catalog(catalog_json: &str) -> Result<(), JsValue> {
+    return set_translations(catalog_json).map_err(|err| JsValue::from_str(&err));
+}
+
+// Function to get every code and its English template as a JSON object, which is the starting point for a translation
+#[cfg_attr(feature = "ui", wasm_bindgen(js_name = "getDefaultMessageCatalog"))]
+pub fn get_default_message_catalog() -> String {
+    // The codes sort in the order of the phases
+    let catalog: BTreeMap<&str, &str> = MessageCode::iter()
+        .map(|message_code| (message_code.get_code(), message_code.get_default_template()))
+        .collect();
+    return serde_json::to_string_pretty(&catalog).expect("Should be able to serialize the message catalog");
+}
@@ -1,3 +1,15 @@
 pub mod nexus_log;
 pub mod test;
 pub mod target;
+pub mod position;
+pub mod program_generator;
+pub mod program_mutator;
+pub mod program_minimizer;
+pub mod diagnostic;
+pub mod messages;
+pub mod instruction_set;
+pub mod build_info;
+pub mod html;
+pub mod diff;
+pub mod course_stage;
+pub mod language_profile;
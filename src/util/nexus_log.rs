@@ -1,9 +1,22 @@
-use web_sys::{Document, Window, Element, DomTokenList};
+use std::cell::RefCell;
+
+use std::fmt;
+
+use serde::{Serialize, Serializer};
+#[cfg(feature = "ui")]
+use wasm_bindgen::prelude::*;
+#[cfg(feature = "ui")]
+use web_sys::{Document, Window, Element};
+
+#[cfg(feature = "ui")]
+use crate::util::html::escape_html;
+use crate::util::{messages::{Message, MessageCode}, diagnostic::QuickFix, position::Position};
 
 // Defines the type of logs
 // https://stackoverflow.com/questions/69015213/how-can-i-display-an-enum-in-lowercase
-#[derive (Debug, strum::Display)]
+#[derive (Debug, Clone, Copy, strum::Display, Serialize)]
 #[strum (serialize_all = "UPPERCASE")]
+#[serde (rename_all = "UPPERCASE")]
 pub enum LogTypes {
     Info,
     Warning,
@@ -12,68 +25,445 @@ pub enum LogTypes {
 }
 
 // Defines where the logs can come from
-#[derive (Debug, strum::Display)]
-#[strum (serialize_all = "UPPERCASE")]
-pub enum LogSources {
-    Nexus,
-    Lexer,
-    Parser,
-    SemanticAnalyzer,
-    CodeGenerator
+// The sources of the compiler are here, and other parts of Nexus can make their own with LogSource::new
+// and register them so they get a verbose toggle on the page
+#[derive (Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct LogSource {
+    // Shown in uppercase at the start of each log, such as "LEXER"
+    name: &'static str,
+
+    // Shown above the verbose toggle, such as "Lexer"
+    label: &'static str
 }
 
-// Function that logs a message with the given type and source
-pub fn log(log_type: LogTypes, src: LogSources, msg: String) {
-    // Get the log area
-    let log_area: Element = get_log_area();
-
-    // Create the new element to place in the logs
-    let new_log: Element = get_document().create_element("p").expect("Should be able to create the element");
-    new_log.set_inner_html(format!("[{} - {}]: {}", log_type, src, msg).as_str());
-
-    // Set the new value
-    log_area.append_child(&new_log).expect("Should be able to add the child");
-
-    // Special cases and such
-    match log_type {
-        LogTypes::Debug => {
-            // Only log if in verbose mode
-            if !is_verbose_mode(&src) {
-                log_area.remove_child(&new_log).expect("Should be able to remove the child");
+impl LogSource {
+    pub const NEXUS: LogSource = LogSource::new("Nexus", "Nexus");
+    pub const LEXER: LogSource = LogSource::new("Lexer", "Lexer");
+    pub const PARSER: LogSource = LogSource::new("Parser", "Parser");
+    pub const SEMANTIC_ANALYZER: LogSource = LogSource::new("SemanticAnalyzer", "Semantic Analyzer");
+    pub const CODE_GENERATOR: LogSource = LogSource::new("CodeGenerator", "Code Generator");
+
+    pub const fn new(name: &'static str, label: &'static str) -> Self {
+        return LogSource {
+            name: name,
+            label: label
+        };
+    }
+
+    pub fn get_label(&self) -> &'static str {
+        return self.label;
+    }
+
+    // Function to get the id of the verbose toggle, such as "codegenerator-log-mode"
+    pub fn get_mode_id(&self) -> String {
+        return format!("{}-log-mode", self.name.to_lowercase());
+    }
+}
+
+impl fmt::Display for LogSource {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        return write!(f, "{}", self.name.to_uppercase());
+    }
+}
+
+impl Serialize for LogSource {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        return serializer.serialize_str(&self.to_string());
+    }
+}
+
+// A single log that is sent to the sinks
+#[derive (Debug, Clone, Serialize)]
+#[serde (rename_all = "camelCase")]
+pub struct LogRecord {
+    pub log_type: LogTypes,
+    pub source: LogSource,
+    pub message: String,
+
+    // Where in the source code the log is about, which is only set by log_at
+    #[serde (skip_serializing_if = "Option::is_none")]
+    pub position: Option<Position>,
+
+    // The code of the message in the catalog, which is only set by log_message
+    #[serde (skip_serializing_if = "Option::is_none")]
+    pub code: Option<MessageCode>,
+
+    // The change that fixes the problem, which is only set for some messages from the catalog
+    #[serde (skip_serializing_if = "Option::is_none")]
+    pub fix: Option<QuickFix>
+}
+
+// Somewhere that logs are written to
+pub trait LogSink {
+    // Used to find the sink again to remove it
+    fn get_name(&self) -> &'static str;
+
+    fn write(&mut self, record: &LogRecord);
+
+    fn insert_empty_line(&mut self) {}
+
+    fn clear(&mut self) {}
+
+    // Function to get the logs the sink kept, which is only used by sinks that collect the logs
+    fn get_records(&self) -> Vec<LogRecord> {
+        return Vec::new();
+    }
+
+    // Function to get the logs and empty lines the sink kept, which is only used by the recorder
+    fn get_recorded_logs(&self) -> Vec<RecordedLog> {
+        return Vec::new();
+    }
+}
+
+// Writes the logs to the log area of the page
+#[cfg(feature = "ui")]
+pub struct DomSink;
+
+#[cfg(feature = "ui")]
+impl LogSink for DomSink {
+    fn get_name(&self) -> &'static str {
+        return "dom";
+    }
+
+    fn write(&mut self, record: &LogRecord) {
+        // Only log debug messages if in verbose mode
+        if matches!(record.log_type, LogTypes::Debug) && !is_verbose_mode(&record.source) {
+            return;
+        }
+
+        // Create the new element to place in the logs
+        let new_log: Element = get_document().create_element("p").expect("Should be able to create the element");
+        // Messages can have source text in them, so they are escaped to be shown as is
+        new_log.set_inner_html(format!("[{} - {}]: {}", record.log_type, record.source, escape_html(&record.message)).as_str());
+
+        // Errors and warnings have special classes
+        match record.log_type {
+            LogTypes::Error => new_log.set_class_name("error"),
+            LogTypes::Warning => new_log.set_class_name("warning"),
+            _ => {}
+        }
+
+        get_log_area().append_child(&new_log).expect("Should be able to add the child");
+    }
+
+    fn insert_empty_line(&mut self) {
+        // The new line is just a br tag
+        let new_line: Element = get_document().create_element("br").expect("Should be able to create the br element");
+        get_log_area().append_child(&new_line).expect("Should be able to add the child");
+    }
+
+    fn clear(&mut self) {
+        // Remove all children by wiping the inner html
+        get_log_area().set_inner_html("");
+    }
+}
+
+// Writes the logs to the browser's console
+pub struct ConsoleSink;
+
+impl LogSink for ConsoleSink {
+    fn get_name(&self) -> &'static str {
+        return "console";
+    }
+
+    fn write(&mut self, record: &LogRecord) {
+        let text: String = format!("[{}]: {}", record.source, record.message);
+        match record.log_type {
+            LogTypes::Info => log::info!("{}", text),
+            LogTypes::Warning => log::warn!("{}", text),
+            LogTypes::Error => log::error!("{}", text),
+            LogTypes::Debug => log::debug!("{}", text)
+        }
+    }
+}
+
+// Keeps every log so the whole compilation can be read back as JSON
+pub struct JsonTraceSink {
+    records: Vec<LogRecord>
+}
+
+impl JsonTraceSink {
+    pub fn new() -> Self {
+        return JsonTraceSink { records: Vec::new() };
+    }
+}
+
+impl LogSink for JsonTraceSink {
+    fn get_name(&self) -> &'static str {
+        return "json-trace";
+    }
+
+    fn write(&mut self, record: &LogRecord) {
+        self.records.push(record.to_owned());
+    }
+
+    fn clear(&mut self) {
+        self.records.clear();
+    }
+
+    fn get_records(&self) -> Vec<LogRecord> {
+        return self.records.to_owned();
+    }
+}
+
+// Keeps only the errors and warnings, which are the useful logs when compiling without the page
+pub struct CollectorSink {
+    records: Vec<LogRecord>
+}
+
+impl CollectorSink {
+    pub fn new() -> Self {
+        return CollectorSink { records: Vec::new() };
+    }
+}
+
+impl LogSink for CollectorSink {
+    fn get_name(&self) -> &'static str {
+        return "collector";
+    }
+
+    fn write(&mut self, record: &LogRecord) {
+        match record.log_type {
+            LogTypes::Error | LogTypes::Warning => self.records.push(record.to_owned()),
+            _ => {}
+        }
+    }
+
+    fn get_records(&self) -> Vec<LogRecord> {
+        return self.records.to_owned();
+    }
+}
+
+// A log or empty line that was recorded so it can be shown later, such as by another thread or another compilation
+#[derive (Debug, Clone)]
+pub enum RecordedLog {
+    Record(LogRecord),
+    EmptyLine
+}
+
+// Keeps every log and empty line in order instead of showing them
+pub struct RecorderSink {
+    logs: Vec<RecordedLog>
+}
+
+impl RecorderSink {
+    pub fn new() -> Self {
+        return RecorderSink { logs: Vec::new() };
+    }
+}
+
+impl LogSink for RecorderSink {
+    fn get_name(&self) -> &'static str {
+        return "recorder";
+    }
+
+    fn write(&mut self, record: &LogRecord) {
+        self.logs.push(RecordedLog::Record(record.to_owned()));
+    }
+
+    fn insert_empty_line(&mut self) {
+        self.logs.push(RecordedLog::EmptyLine);
+    }
+
+    fn get_recorded_logs(&self) -> Vec<RecordedLog> {
+        return self.logs.to_owned();
+    }
+}
+
+thread_local! {
+    // The sets of sinks, where only the last set gets the logs
+    // Other sets are kept so they can be restored after the logs are captured
+    static SINK_STACK: RefCell<Vec<Vec<Box<dyn LogSink>>>> = RefCell::new(vec![get_page_sinks()]);
+
+    // Every source that has a verbose toggle and whether it is in verbose mode
+    static REGISTERED_SOURCES: RefCell<Vec<(LogSource, bool)>> = RefCell::new(vec![
+        (LogSource::NEXUS, true),
+        (LogSource::LEXER, true),
+        (LogSource::PARSER, true),
+        (LogSource::SEMANTIC_ANALYZER, true),
+        (LogSource::CODE_GENERATOR, true)
+    ]);
+}
+
+// Function to get the sinks that are used when the logs are not captured
+// Without the page, the logs are only kept for the JSON trace
+#[cfg(feature = "ui")]
+fn get_page_sinks() -> Vec<Box<dyn LogSink>> {
+    return vec![Box::new(DomSink), Box::new(JsonTraceSink::new())];
+}
+
+#[cfg(not(feature = "ui"))]
+fn get_page_sinks() -> Vec<Box<dyn LogSink>> {
+    return vec![Box::new(JsonTraceSink::new())];
+}
+
+// Function to give a source a verbose toggle on the page, which has to be done before the buttons are set up
+pub fn register_source(source: LogSource) {
+    REGISTERED_SOURCES.with(|sources| {
+        let mut sources_ref = sources.borrow_mut();
+        if !sources_ref.iter().any(|(registered_source, _)| registered_source.eq(&source)) {
+            sources_ref.push((source, true));
+        }
+    });
+}
+
+// Function to get every registered source in the order they were registered
+pub fn get_registered_sources() -> Vec<LogSource> {
+    return REGISTERED_SOURCES.with(|sources| sources.borrow().iter().map(|(source, _)| *source).collect());
+}
+
+// Function to show or hide the debug logs of a source
+pub fn set_verbose_mode(source: &LogSource, is_verbose: bool) {
+    REGISTERED_SOURCES.with(|sources| {
+        for (registered_source, verbose) in sources.borrow_mut().iter_mut() {
+            if (*registered_source).eq(source) {
+                *verbose = is_verbose;
             }
-        },
-        LogTypes::Error => {
-            // Errors have special classes
-            new_log.set_class_name("error");
-        },
-        LogTypes::Warning => {
-            // Set the warning class
-            new_log.set_class_name("warning");
-        },
-        _ => {
-            // Nothing else to do here
         }
+    });
+}
+
+// Function to add a sink to the sinks getting the logs
+pub fn add_sink(sink: Box<dyn LogSink>) {
+    SINK_STACK.with(|sink_stack| sink_stack.borrow_mut().last_mut().unwrap().push(sink));
+}
+
+// Function to remove the sinks with the given name from the sinks getting the logs
+pub fn remove_sink(name: &str) {
+    SINK_STACK.with(|sink_stack| sink_stack.borrow_mut().last_mut().unwrap().retain(|sink| sink.get_name().ne(name)));
+}
+
+// Function to send the logs only to the given sinks until pop_sinks is called
+pub fn push_sinks(sinks: Vec<Box<dyn LogSink>>) {
+    SINK_STACK.with(|sink_stack| sink_stack.borrow_mut().push(sinks));
+}
+
+// Function to go back to the sinks before the last push_sinks and get the sinks that were removed
+pub fn pop_sinks() -> Vec<Box<dyn LogSink>> {
+    return SINK_STACK.with(|sink_stack| {
+        let mut sink_stack_ref = sink_stack.borrow_mut();
+        // The page sinks are always kept
+        if sink_stack_ref.len() > 1 {
+            sink_stack_ref.pop().unwrap()
+        } else {
+            Vec::new()
+        }
+    });
+}
+
+// Function to get the logs kept by the sinks getting the logs
+pub fn get_records() -> Vec<LogRecord> {
+    return SINK_STACK.with(|sink_stack| sink_stack.borrow().last().unwrap().iter().flat_map(|sink| sink.get_records()).collect());
+}
+
+// Function to get every log of the most recent compilation as JSON, including the debug logs
+#[cfg_attr(feature = "ui", wasm_bindgen(js_name = "getLogTraceJson"))]
+pub fn get_log_trace_json() -> String {
+    return serde_json::to_string(&get_records()).expect("Should be able to serialize the logs");
+}
+
+// Function to also send the logs to the browser's console
+#[cfg_attr(feature = "ui", wasm_bindgen(js_name = "setConsoleLogging"))]
+pub fn set_console_logging(is_enabled: bool) {
+    remove_sink("console");
+    if is_enabled {
+        add_sink(Box::new(ConsoleSink));
     }
 }
 
-pub fn insert_empty_line() {
-    // Get the log area
-    let log_area: Element = get_log_area();
+// Function to start keeping the errors and warnings instead of showing any logs
+pub fn start_capture() {
+    push_sinks(vec![Box::new(CollectorSink::new())]);
+}
+
+// Function to go back to showing logs and get the logs that were captured
+pub fn stop_capture() -> Vec<LogRecord> {
+    return pop_sinks().iter().flat_map(|sink| sink.get_records()).collect();
+}
+
+// Function to start keeping every log and empty line instead of showing them
+// Each thread has its own sinks, so this is also how logs get back from programs compiled on other threads
+pub fn start_recording() {
+    push_sinks(vec![Box::new(RecorderSink::new())]);
+}
+
+// Function to go back to showing logs and get the logs that were recorded
+pub fn stop_recording() -> Vec<RecordedLog> {
+    return pop_sinks().iter().flat_map(|sink| sink.get_recorded_logs()).collect();
+}
+
+// Function to show recorded logs in the same order they were recorded
+pub fn replay(logs: &Vec<RecordedLog>) {
+    for log in logs.iter() {
+        match log {
+            RecordedLog::Record(record) => write_record(record.to_owned()),
+            RecordedLog::EmptyLine => insert_empty_line()
+        }
+    }
+}
+
+// Function that logs a message with the given type and source
+pub fn log(log_type: LogTypes, src: LogSource, msg: String) {
+    write_record(LogRecord {
+        log_type: log_type,
+        source: src,
+        message: msg,
+        position: None,
+        code: None,
+        fix: None
+    });
+}
+
+// Function to log a problem at a position in the source code, which is how errors and warnings get into the issues pane
+pub fn log_at(log_type: LogTypes, src: LogSource, position: Position, msg: String) {
+    write_record(LogRecord {
+        log_type: log_type,
+        source: src,
+        message: msg,
+        position: Some(position),
+        code: None,
+        fix: None
+    });
+}
+
+// Function to log a message from the catalog at a position, so the text is in the language of the catalog
+pub fn log_message(log_type: LogTypes, src: LogSource, position: Position, message: &Message) {
+    write_record(LogRecord {
+        log_type: log_type,
+        source: src,
+        message: message.get_text(),
+        position: Some(position),
+        code: Some(message.code),
+        fix: message.fix.to_owned()
+    });
+}
 
-    // The new line is just a br tag
-    let new_line: Element = get_document().create_element("br").expect("Should be able to create the br element");
-    log_area.append_child(&new_line).expect("Should be able to add the child");
+fn write_record(record: LogRecord) {
+    SINK_STACK.with(|sink_stack| {
+        for sink in sink_stack.borrow_mut().last_mut().unwrap().iter_mut() {
+            sink.write(&record);
+        }
+    });
+}
+
+pub fn insert_empty_line() {
+    SINK_STACK.with(|sink_stack| {
+        for sink in sink_stack.borrow_mut().last_mut().unwrap().iter_mut() {
+            sink.insert_empty_line();
+        }
+    });
 }
 
 // Function to clean the logs
 pub fn clear_logs() {
-    // Get the log area
-    let log_area: Element = get_log_area();
-
-    // Remove all children by wiping the inner html
-    log_area.set_inner_html("");
+    SINK_STACK.with(|sink_stack| {
+        for sink in sink_stack.borrow_mut().last_mut().unwrap().iter_mut() {
+            sink.clear();
+        }
+    });
 }
 
+#[cfg(feature = "ui")]
 fn get_log_area() -> Element {
     let document: Document = get_document();
 
@@ -85,6 +475,7 @@ fn get_log_area() -> Element {
     return log_area;
 }
 
+#[cfg(feature = "ui")]
 fn get_document() -> Document {
     // Grab the window and document elements for DOM manipulation
     let window: Window = web_sys::window().expect("The window object should exist.");
@@ -93,27 +484,13 @@ fn get_document() -> Document {
     return document;
 }
 
-fn is_verbose_mode(src: &LogSources) -> bool {
-    // Grab the window and document elements for DOM manipulation
-    let window: Window = web_sys::window().expect("The window object should exist.");
-    let document: Document = window.document().expect("The document object should exist");
-
-    // Assume we are in verbose mode
-    let mut out: bool = true;
-
-    // Get the target button element
-    let target: Element = match src {
-        LogSources::Nexus => document.get_element_by_id("nexus-log-mode").expect("Should be able to find the nexus-log-mode element"),
-        LogSources::Lexer => document.get_element_by_id("lexer-log-mode").expect("Should be able to find the lexer-log-mode element"),
-        LogSources::Parser => document.get_element_by_id("parser-log-mode").expect("Should be able to find the parser-log-mode element"),
-        LogSources::SemanticAnalyzer => document.get_element_by_id("semantic-log-mode").expect("Should be able to find the semantic-log-mode element"),
-        LogSources::CodeGenerator => document.get_element_by_id("codegen-log-mode").expect("Should be able to find the codegen-log-mode element"),
-    };
-
-    // Check to see if it is in simple mode
-    let class_list: DomTokenList = target.class_list();
-    if class_list.contains("simple") {
-        out = false;
-    }
-    return out;
+#[cfg(feature = "ui")]
+fn is_verbose_mode(src: &LogSource) -> bool {
+    // Sources without a toggle always show their debug logs
+    return REGISTERED_SOURCES.with(|sources| {
+        match sources.borrow().iter().find(|(source, _)| source.eq(src)) {
+            Some((_, is_verbose)) => *is_verbose,
+            None => true
+        }
+    });
 }
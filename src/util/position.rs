@@ -0,0 +1,101 @@
+use std::fmt;
+
+use serde::{Deserialize, Serialize};
+
+// A place in the source code, where the line and column both start at 1
+// Positions are compared line first, so they sort in the order they appear in the code
+// The JSON form is [line, col], which is what every JSON API has always used
+#[derive (Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Default, Serialize, Deserialize)]
+#[serde (from = "(usize, usize)", into = "(usize, usize)")]
+pub struct Position {
+    pub line: usize,
+    pub col: usize
+}
+
+impl Position {
+    pub fn new(line: usize, col: usize) -> Self {
+        return Position {
+            line: line,
+            col: col
+        };
+    }
+
+    // Function to write the position as a parameter of a message in the catalog
+    // This is the same (line, col) as the debug form, so a translated message does not have English in it
+    pub fn to_message_param(&self) -> String {
+        return format!("({}, {})", self.line, self.col);
+    }
+
+    // Function to get the position the given number of columns to the right on the same line
+    pub fn offset_cols(&self, num_cols: usize) -> Self {
+        return Position::new(self.line, self.col + num_cols);
+    }
+}
+
+impl From<(usize, usize)> for Position {
+    fn from(position: (usize, usize)) -> Self {
+        return Position::new(position.0, position.1);
+    }
+}
+
+impl From<Position> for (usize, usize) {
+    fn from(position: Position) -> Self {
+        return (position.line, position.col);
+    }
+}
+
+impl fmt::Display for Position {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        return write!(f, "line {}, col {}", self.line, self.col);
+    }
+}
+
+// The debug form is the same short (line, col) as the JSON, which keeps dumps of tokens and trees readable
+impl fmt::Debug for Position {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        return write!(f, "({}, {})", self.line, self.col);
+    }
+}
+
+// The code from the start position up to but not including the end position, such as a token or an expression
+#[derive (Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Default, Serialize, Deserialize)]
+pub struct Span {
+    pub start: Position,
+    pub end: Position
+}
+
+impl Span {
+    pub fn new(start: Position, end: Position) -> Self {
+        return Span {
+            start: start.min(end),
+            end: start.max(end)
+        };
+    }
+
+    // Function to get the span that covers both spans and everything between them
+    pub fn merge(&self, other: &Span) -> Self {
+        return Span {
+            start: self.start.min(other.start),
+            end: self.end.max(other.end)
+        };
+    }
+
+    // Function to check if the position is inside of the span
+    pub fn contains(&self, position: Position) -> bool {
+        return self.start <= position && position < self.end;
+    }
+
+    // Function to write the span as a parameter of a message in the catalog, such as (4, 7)-(4, 12)
+    pub fn to_message_param(&self) -> String {
+        return format!("{}-{}", self.start.to_message_param(), self.end.to_message_param());
+    }
+}
+
+impl fmt::Display for Span {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        if self.start.line == self.end.line {
+            return write!(f, "{} to col {}", self.start, self.end.col);
+        }
+        return write!(f, "{} to {}", self.start, self.end);
+    }
+}
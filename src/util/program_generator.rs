@@ -0,0 +1,322 @@
+use std::collections::HashMap;
+
+use crate::nexus::symbol_table::Type;
+
+// Settings for the random program generator
+#[derive (Debug, Clone)]
+pub struct ProgramGeneratorConfig {
+    // The deepest that blocks and expressions are allowed to nest
+    pub max_depth: usize,
+
+    // The most statements that can be generated in a single block
+    pub max_statements: usize,
+
+    // The seed for the random number generator so programs can be reproduced
+    pub seed: u64
+}
+
+impl ProgramGeneratorConfig {
+    // Creates the default configuration with the given seed
+    pub fn new(seed: u64) -> Self {
+        return ProgramGeneratorConfig {
+            max_depth: 3,
+            max_statements: 5,
+            seed: seed
+        };
+    }
+}
+
+// Generates random programs that are valid for every phase of the compiler
+pub struct ProgramGenerator {
+    config: ProgramGeneratorConfig,
+
+    // The state of the xorshift random number generator
+    rng_state: u64,
+
+    // Stack of scopes, where each scope maps the declared identifiers to their types
+    scopes: Vec<HashMap<char, Type>>,
+
+    // Loop counters that cannot be reassigned or else the loop may never end
+    loop_counters: Vec<char>,
+
+    // The current indentation level of the output
+    indent_level: usize
+}
+
+impl ProgramGenerator {
+    // Constructor for the program generator
+    pub fn new(config: ProgramGeneratorConfig) -> Self {
+        // Xorshift cannot have a state of 0
+        let initial_state: u64 = match config.seed {
+            0 => 0x2545F4914F6CDD1D,
+            _ => config.seed
+        };
+
+        return ProgramGenerator {
+            config: config,
+            rng_state: initial_state,
+            scopes: Vec::new(),
+            loop_counters: Vec::new(),
+            indent_level: 0
+        };
+    }
+
+    // Function to generate a complete program
+    pub fn generate_program(&mut self) -> String {
+        self.scopes.clear();
+        self.loop_counters.clear();
+        self.indent_level = 0;
+
+        let mut program: String = String::from("/* Generated with seed ");
+        program.push_str(format!("{} */\n", self.config.seed).as_str());
+        program.push_str(self.generate_block(0).as_str());
+        program.push_str("$");
+        return program;
+    }
+
+    // Xorshift64 to get the next random number
+    fn next_random(&mut self) -> u64 {
+        self.rng_state ^= self.rng_state << 13;
+        self.rng_state ^= self.rng_state >> 7;
+        self.rng_state ^= self.rng_state << 17;
+        return self.rng_state;
+    }
+
+    // Function to get a random number in the range [0, upper)
+    fn random_below(&mut self, upper: usize) -> usize {
+        return (self.next_random() % upper as u64) as usize;
+    }
+
+    fn indent(&self) -> String {
+        return "\t".repeat(self.indent_level);
+    }
+
+    fn generate_block(&mut self, depth: usize) -> String {
+        let mut block: String = String::from("{\n");
+        self.scopes.push(HashMap::new());
+        self.indent_level += 1;
+
+        // Always have at least 1 statement so the block does something
+        let num_statements: usize = self.random_below(self.config.max_statements) + 1;
+        for _ in 0..num_statements {
+            block.push_str(self.generate_statement(depth).as_str());
+        }
+
+        self.indent_level -= 1;
+        self.scopes.pop();
+        block.push_str(self.indent().as_str());
+        block.push_str("}");
+        return block;
+    }
+
+    fn generate_statement(&mut self, depth: usize) -> String {
+        let mut statement: String = self.indent();
+
+        // Nested statements are only allowed if the depth has not been reached
+        let num_options: usize = match depth < self.config.max_depth {
+            true => 6,
+            false => 3
+        };
+
+        match self.random_below(num_options) {
+            0 => statement.push_str(self.generate_var_decl(depth).as_str()),
+            1 => statement.push_str(self.generate_print(depth).as_str()),
+            2 => {
+                // Can only assign if there is something to assign to
+                let assign_res: Option<String> = self.generate_assignment(depth);
+                if assign_res.is_some() {
+                    statement.push_str(assign_res.unwrap().as_str());
+                } else {
+                    statement.push_str(self.generate_var_decl(depth).as_str());
+                }
+            },
+            3 => statement.push_str(self.generate_if(depth).as_str()),
+            4 => statement.push_str(self.generate_while(depth).as_str()),
+            _ => statement.push_str(self.generate_block(depth + 1).as_str())
+        }
+
+        statement.push_str("\n");
+        return statement;
+    }
+
+    fn generate_var_decl(&mut self, depth: usize) -> String {
+        // Pick a name that can be declared in the current scope
+        let available_ids: Vec<char> = self.get_declarable_ids();
+        if available_ids.len() == 0 {
+            return self.generate_print(depth);
+        }
+        let new_id: char = available_ids[self.random_below(available_ids.len())];
+        let new_type: Type = self.random_type();
+
+        // Some variables are printed before they are assigned so the default values of every type are compared too
+        if self.random_below(4) == 0 {
+            self.scopes.last_mut().unwrap().insert(new_id, new_type.to_owned());
+            return format!("{} {}\n{}print({})", Self::type_keyword(&new_type), new_id, self.indent(), new_id);
+        }
+
+        // The initializer is generated before the id is declared, so it cannot refer to itself
+        let initial_value: String = self.generate_expr(&new_type, depth);
+        self.scopes.last_mut().unwrap().insert(new_id, new_type.to_owned());
+
+        return format!("{} {} = {}", Self::type_keyword(&new_type), new_id, initial_value);
+    }
+
+    fn generate_print(&mut self, depth: usize) -> String {
+        let print_type: Type = self.random_type();
+        return format!("print({})", self.generate_expr(&print_type, depth));
+    }
+
+    fn generate_assignment(&mut self, depth: usize) -> Option<String> {
+        let assignable_ids: Vec<(char, Type)> = self.get_visible_ids()
+            .into_iter()
+            .filter(|(id, _)| !self.loop_counters.contains(id))
+            .collect();
+        if assignable_ids.len() == 0 {
+            return None;
+        }
+
+        let (id, id_type): (char, Type) = assignable_ids[self.random_below(assignable_ids.len())].to_owned();
+        return Some(format!("{} = {}", id, self.generate_expr(&id_type, depth)));
+    }
+
+    fn generate_if(&mut self, depth: usize) -> String {
+        let condition: String = self.generate_expr(&Type::Boolean, depth);
+        return format!("if {} {}", Self::wrap_condition(condition), self.generate_block(depth + 1));
+    }
+
+    fn generate_while(&mut self, depth: usize) -> String {
+        // Loops count up to a bound so every generated program terminates
+        let available_ids: Vec<char> = self.get_declarable_ids();
+        if available_ids.len() == 0 {
+            return self.generate_if(depth);
+        }
+        let counter: char = available_ids[self.random_below(available_ids.len())];
+        let bound: usize = self.random_below(4) + 1;
+        self.scopes.last_mut().unwrap().insert(counter, Type::Int);
+
+        self.loop_counters.push(counter);
+        let mut body: String = self.generate_block(depth + 1);
+        self.loop_counters.pop();
+
+        // Increment the counter at the end of the body
+        body.truncate(body.len() - 1);
+        body.push_str(format!("\t{} = 1 + {}\n{}}}", counter, counter, self.indent()).as_str());
+
+        return format!("int {} = 0\n{}while ({} != {}) {}", counter, self.indent(), counter, bound, body);
+    }
+
+    fn generate_expr(&mut self, expr_type: &Type, depth: usize) -> String {
+        // Identifiers of the same type are also valid expressions
+        let matching_ids: Vec<char> = self.get_visible_ids()
+            .into_iter()
+            .filter(|(_, id_type)| id_type.eq(expr_type))
+            .map(|(id, _)| id)
+            .collect();
+        if matching_ids.len() > 0 && self.random_below(3) == 0 {
+            return matching_ids[self.random_below(matching_ids.len())].to_string();
+        }
+
+        match expr_type {
+            Type::Int => {
+                // Addition is right recursive, so the right side is another expression
+                if depth < self.config.max_depth && self.random_below(2) == 0 {
+                    return format!("{} + {}", self.random_below(10), self.generate_expr(&Type::Int, depth + 1));
+                } else {
+                    return self.random_below(10).to_string();
+                }
+            },
+            Type::String => {
+                let length: usize = self.random_below(6);
+                let mut string_expr: String = String::from("\"");
+                for _ in 0..length {
+                    // Spaces are valid characters in strings
+                    let char_index: usize = self.random_below(27);
+                    if char_index == 26 {
+                        string_expr.push(' ');
+                    } else {
+                        string_expr.push((b'a' + char_index as u8) as char);
+                    }
+                }
+                string_expr.push('"');
+                return string_expr;
+            },
+            Type::Char => {
+                // Chars are a letter or a space like the characters of a string
+                let char_index: usize = self.random_below(27);
+                if char_index == 26 {
+                    return String::from("' '");
+                } else {
+                    return format!("'{}'", (b'a' + char_index as u8) as char);
+                }
+            },
+            Type::Boolean => {
+                if depth < self.config.max_depth && self.random_below(2) == 0 {
+                    // Comparisons can be between any 2 expressions of the same type
+                    let compare_type: Type = self.random_type();
+                    let left: String = self.generate_expr(&compare_type, depth + 1);
+                    let right: String = self.generate_expr(&compare_type, depth + 1);
+                    let bool_op: &str = match self.random_below(2) {
+                        0 => "==",
+                        _ => "!="
+                    };
+                    return format!("({} {} {})", left, bool_op, right);
+                } else {
+                    return match self.random_below(2) {
+                        0 => String::from("true"),
+                        _ => String::from("false")
+                    };
+                }
+            }
+        }
+    }
+
+    // Function to get the identifiers that can be declared in the current scope
+    fn get_declarable_ids(&self) -> Vec<char> {
+        // Loop counters cannot be shadowed or else the increment at the end of the loop would use the wrong variable
+        return ('a'..='z')
+            .filter(|id| !self.scopes.last().unwrap().contains_key(id) && !self.loop_counters.contains(id))
+            .collect();
+    }
+
+    // Function to get the innermost declaration of every identifier in scope
+    fn get_visible_ids(&self) -> Vec<(char, Type)> {
+        let mut visible_ids: HashMap<char, Type> = HashMap::new();
+        for scope in self.scopes.iter() {
+            for (id, id_type) in scope.iter() {
+                visible_ids.insert(*id, id_type.to_owned());
+            }
+        }
+
+        // Sort to keep the output deterministic for a given seed
+        let mut ids_vec: Vec<(char, Type)> = visible_ids.into_iter().collect();
+        ids_vec.sort_by_key(|(id, _)| *id);
+        return ids_vec;
+    }
+
+    fn random_type(&mut self) -> Type {
+        return match self.random_below(4) {
+            0 => Type::Int,
+            1 => Type::String,
+            2 => Type::Char,
+            _ => Type::Boolean
+        };
+    }
+
+    fn type_keyword(id_type: &Type) -> &'static str {
+        return match id_type {
+            Type::Int => "int",
+            Type::String => "string",
+            Type::Boolean => "boolean",
+            Type::Char => "char"
+        };
+    }
+
+    // If and while conditions need parentheses unless they already are a comparison
+    fn wrap_condition(condition: String) -> String {
+        if condition.starts_with('(') || condition.eq("true") || condition.eq("false") {
+            return condition;
+        } else {
+            return format!("({} == true)", condition);
+        }
+    }
+}
@@ -0,0 +1,257 @@
+use std::panic::{self, AssertUnwindSafe};
+
+use petgraph::graph::NodeIndex;
+
+use crate::nexus::{lexer::Lexer, token::Token, parser::Parser, syntax_tree::SyntaxTree, syntax_tree_node::{SyntaxTreeNode, NonTerminalsCst}};
+use crate::nexus::{code_generator_6502::CodeGenerator6502, code_generator_riscv::CodeGeneratorRiscV, differential::{self, Backend}};
+use crate::util::{nexus_log, target::Target};
+
+// The failures that a program can be minimized for
+#[derive (Debug, Clone, Copy, PartialEq)]
+pub enum Failure {
+    // One of the phases panics
+    Crash,
+
+    // The backend prints something different from the interpreter
+    WrongOutput(Backend),
+
+    // The program passes semantic analysis but no code can be generated for the target
+    CodeGenError(Target)
+}
+
+impl Failure {
+    // Function to check if the program still has the failure
+    pub fn is_failing(&self, program: &str) -> bool {
+        // The logs of compiling the program are not shown
+        nexus_log::push_sinks(Vec::new());
+        let is_failing: bool = match self {
+            Failure::Crash => has_crash(program),
+            Failure::WrongOutput(backend) => differential::has_divergence(program, *backend),
+            Failure::CodeGenError(target) => match differential::analyze_program(program) {
+                Some(mut semantic_analysis_res) => match target {
+                    Target::Target6502 => !CodeGenerator6502::new().generate_image(&mut semantic_analysis_res),
+                    Target::TargetRiscV => !CodeGeneratorRiscV::new().generate_assembly(&mut semantic_analysis_res)
+                },
+                None => false
+            }
+        };
+        nexus_log::pop_sinks();
+        return is_failing;
+    }
+}
+
+// Function to check if any phase panics while compiling and running the program
+fn has_crash(program: &str) -> bool {
+    // The default hook would print every panic that is found while minimizing
+    let default_hook = panic::take_hook();
+    panic::set_hook(Box::new(|_| {}));
+    let run_res = panic::catch_unwind(AssertUnwindSafe(|| {
+        if let Some(mut semantic_analysis_res) = differential::analyze_program(program) {
+            let _ = differential::compare_outputs(&mut semantic_analysis_res);
+        }
+    }));
+    panic::set_hook(default_hook);
+    return run_res.is_err();
+}
+
+// Function to minimize the program for one of the known failures
+pub fn minimize_failure(program: &str, failure: Failure) -> String {
+    return minimize(program, |candidate| failure.is_failing(candidate));
+}
+
+// Function to find a smaller program that still fails
+// Statements are removed, blocks are unwrapped, and expressions are replaced by their parts for as long as the program fails,
+// and then lines are removed with delta debugging for what the parse tree cannot reach, such as comments and programs that do not parse
+pub fn minimize<F: FnMut(&str) -> bool>(program: &str, mut is_failing: F) -> String {
+    // Nothing is shown while minimizing, so the logs are dropped
+    nexus_log::push_sinks(Vec::new());
+
+    let mut program: String = String::from(program.trim());
+    loop {
+        let program_len: usize = program.len();
+        while let Some(candidate) = get_candidates(&program).into_iter().find(|candidate| is_failing(candidate)) {
+            program = candidate;
+        }
+        program = remove_chunks(&program, &mut is_failing);
+        program = remove_blocks(&program, &mut is_failing);
+        if program.len() == program_len {
+            break;
+        }
+    }
+
+    nexus_log::pop_sinks();
+    return program;
+}
+
+// Function to remove chunks of lines that get smaller until single lines are tried
+fn remove_chunks<F: FnMut(&str) -> bool>(program: &str, is_failing: &mut F) -> String {
+    let mut lines: Vec<&str> = program.lines().collect();
+    let mut num_chunks: usize = 2;
+    while lines.len() >= 2 {
+        let chunk_size: usize = lines.len().div_ceil(num_chunks);
+        let mut is_reduced: bool = false;
+
+        let mut start: usize = 0;
+        while start < lines.len() {
+            let end: usize = (start + chunk_size).min(lines.len());
+            let candidate: Vec<&str> = [&lines[..start], &lines[end..]].concat();
+            if is_failing(&candidate.join("\n")) {
+                lines = candidate;
+                num_chunks = (num_chunks - 1).max(2);
+                is_reduced = true;
+                break;
+            }
+            start = end;
+        }
+
+        if !is_reduced {
+            if num_chunks >= lines.len() {
+                break;
+            }
+            num_chunks = (num_chunks * 2).min(lines.len());
+        }
+    }
+    return lines.join("\n");
+}
+
+// Function to remove each block with the line that opens it, or to keep only what is inside the block
+fn remove_blocks<F: FnMut(&str) -> bool>(program: &str, is_failing: &mut F) -> String {
+    let mut lines: Vec<&str> = program.lines().collect();
+    let mut i: usize = 0;
+    while i < lines.len() {
+        if let Some(close) = find_block_end(&lines, i) {
+            let without_block: Vec<&str> = [&lines[..i], &lines[close + 1..]].concat();
+            let unwrapped: Vec<&str> = [&lines[..i], &lines[i + 1..close], &lines[close + 1..]].concat();
+            if is_failing(&without_block.join("\n")) {
+                lines = without_block;
+                continue;
+            } else if is_failing(&unwrapped.join("\n")) {
+                lines = unwrapped;
+                continue;
+            }
+        }
+        i += 1;
+    }
+    return lines.join("\n");
+}
+
+// Function to get the line that closes the block opened at the end of the given line
+fn find_block_end(lines: &[&str], start: usize) -> Option<usize> {
+    if !lines[start].trim_end().ends_with('{') {
+        return None;
+    }
+
+    let mut depth: i32 = 0;
+    for (i, line) in lines.iter().enumerate().skip(start) {
+        for c in line.chars() {
+            match c {
+                '{' => depth += 1,
+                '}' => depth -= 1,
+                _ => {}
+            }
+        }
+        if depth <= 0 {
+            return Some(i);
+        }
+    }
+    return None;
+}
+
+// Function to get every smaller program that can be made from the parse tree, with the biggest changes first
+fn get_candidates(program: &str) -> Vec<String> {
+    let cst: SyntaxTree = match parse_cst(program) {
+        Some(cst) => cst,
+        None => return Vec::new()
+    };
+    let line_starts: Vec<usize> = get_line_starts(program);
+
+    // Each edit replaces a span of the program with new text
+    let mut statement_edits: Vec<((usize, usize), String)> = Vec::new();
+    let mut expression_edits: Vec<((usize, usize), String)> = Vec::new();
+    for node in cst.graph.node_indices() {
+        let span: (usize, usize) = match get_span(&cst, node, &line_starts) {
+            Some(span) => span,
+            None => continue
+        };
+
+        match &cst.graph[node] {
+            SyntaxTreeNode::NonTerminalCst(NonTerminalsCst::Statement) => {
+                statement_edits.push((span, String::new()));
+
+                // Loops, if statements, and blocks can be replaced by what is inside of them
+                for inner_block in find_children(&cst, node, NonTerminalsCst::Block).into_iter()
+                    .chain(find_children(&cst, node, NonTerminalsCst::WhileStatement).into_iter().flat_map(|child| find_children(&cst, child, NonTerminalsCst::Block)))
+                    .chain(find_children(&cst, node, NonTerminalsCst::IfStatement).into_iter().flat_map(|child| find_children(&cst, child, NonTerminalsCst::Block))) {
+                    if let Some(block_span) = get_span(&cst, inner_block, &line_starts) {
+                        statement_edits.push((span, String::from(&program[block_span.0 + 1..block_span.1 - 1])));
+                    }
+                }
+            },
+            SyntaxTreeNode::NonTerminalCst(NonTerminalsCst::IntExpr) | SyntaxTreeNode::NonTerminalCst(NonTerminalsCst::BooleanExpr) => {
+                // Expressions can be replaced by the expressions and digits they are made of
+                for part in cst.graph.neighbors(node) {
+                    let is_part: bool = matches!(&cst.graph[part], SyntaxTreeNode::NonTerminalCst(NonTerminalsCst::Expr) | SyntaxTreeNode::NonTerminalCst(NonTerminalsCst::Digit));
+                    if let (true, Some(part_span)) = (is_part, get_span(&cst, part, &line_starts)) {
+                        expression_edits.push((span, String::from(&program[part_span.0..part_span.1])));
+                    }
+                }
+                if matches!(&cst.graph[node], SyntaxTreeNode::NonTerminalCst(NonTerminalsCst::BooleanExpr)) {
+                    expression_edits.push((span, String::from("true")));
+                    expression_edits.push((span, String::from("false")));
+                }
+            },
+            SyntaxTreeNode::NonTerminalCst(NonTerminalsCst::StringExpr) => expression_edits.push((span, String::from("\"\""))),
+            _ => {}
+        }
+    }
+
+    // Bigger statements are tried first so whole sections of the program go away at once
+    statement_edits.sort_by_key(|(span, _)| span.0 as isize - span.1 as isize);
+    return statement_edits.into_iter().chain(expression_edits)
+        .filter(|(span, text)| text.len() < span.1 - span.0)
+        .map(|(span, text)| format!("{}{}{}", &program[..span.0], text, &program[span.1..]))
+        .collect();
+}
+
+// Function to lex and parse the program to get its CST, or None if it does not parse
+fn parse_cst(program: &str) -> Option<SyntaxTree> {
+    let mut lexer: Lexer = Lexer::new(program);
+    let token_stream: Vec<Token> = lexer.lex_program().ok()?;
+    let mut parser: Parser = Parser::new();
+    return parser.parse_program(&token_stream).ok();
+}
+
+// Function to get the index in the program where each line starts
+fn get_line_starts(program: &str) -> Vec<usize> {
+    let mut line_starts: Vec<usize> = vec![0];
+    line_starts.extend(program.match_indices('\n').map(|(index, _)| index + 1));
+    return line_starts;
+}
+
+// Function to get the children of the node with the given nonterminal
+fn find_children(cst: &SyntaxTree, node: NodeIndex, non_terminal: NonTerminalsCst) -> Vec<NodeIndex> {
+    return cst.graph.neighbors(node)
+        .filter(|child| matches!(&cst.graph[*child], SyntaxTreeNode::NonTerminalCst(child_non_terminal) if *child_non_terminal == non_terminal))
+        .collect();
+}
+
+// Function to get the indices in the program covered by the tokens under the node
+fn get_span(cst: &SyntaxTree, node: NodeIndex, line_starts: &[usize]) -> Option<(usize, usize)> {
+    let mut span: Option<(usize, usize)> = None;
+    let mut stack: Vec<NodeIndex> = vec![node];
+    while let Some(cur_node) = stack.pop() {
+        match &cst.graph[cur_node] {
+            SyntaxTreeNode::Terminal(token) => {
+                // Positions start at (1, 1)
+                let start: usize = line_starts.get(token.position.line - 1)? + token.position.col - 1;
+                let end: usize = start + token.text.len();
+                span = match span {
+                    Some((span_start, span_end)) => Some((span_start.min(start), span_end.max(end))),
+                    None => Some((start, end))
+                };
+            },
+            _ => stack.extend(cst.graph.neighbors(cur_node))
+        }
+    }
+    return span;
+}
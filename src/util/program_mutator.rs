@@ -0,0 +1,240 @@
+use std::collections::HashSet;
+
+use crate::nexus::{lexer::Lexer, token::Token, parser::Parser, semantic_analyzer::SemanticAnalyzer, syntax_tree::SyntaxTree};
+use crate::util::{diagnostic::ErrorClass, nexus_log::{self, LogRecord}, target::{Target, TargetConfig}};
+
+// The kinds of defects that can be introduced into a valid program
+#[derive (Debug, Clone, Copy, PartialEq, strum::Display)]
+pub enum DefectClass {
+    MissingBrace,
+    BadToken,
+    TypeMismatch,
+    UndeclaredId
+}
+
+impl DefectClass {
+    // Every defect class, used to pick one at random
+    pub const ALL: [DefectClass; 4] = [DefectClass::MissingBrace, DefectClass::BadToken, DefectClass::TypeMismatch, DefectClass::UndeclaredId];
+
+    // The error the compiler is expected to report for the defect
+    pub fn expected_error_class(&self) -> ErrorClass {
+        return match self {
+            DefectClass::MissingBrace => ErrorClass::SyntaxError,
+            DefectClass::BadToken => ErrorClass::InvalidToken,
+            DefectClass::TypeMismatch => ErrorClass::TypeMismatch,
+            DefectClass::UndeclaredId => ErrorClass::UndeclaredId
+        };
+    }
+}
+
+// A program that has exactly one known defect
+#[derive (Debug, Clone)]
+pub struct MutatedProgram {
+    pub source_code: String,
+    pub defect_class: DefectClass,
+
+    // Human readable explanation of what was changed
+    pub description: String
+}
+
+// Introduces a single classified defect into a valid program
+pub struct ProgramMutator {
+    // The state of the xorshift random number generator
+    rng_state: u64
+}
+
+impl ProgramMutator {
+    // Constructor for the mutator
+    pub fn new(seed: u64) -> Self {
+        // Xorshift cannot have a state of 0
+        let initial_state: u64 = match seed {
+            0 => 0x2545F4914F6CDD1D,
+            _ => seed
+        };
+
+        return ProgramMutator {
+            rng_state: initial_state
+        };
+    }
+
+    // Xorshift64 to get the next random number
+    fn next_random(&mut self) -> u64 {
+        self.rng_state ^= self.rng_state << 13;
+        self.rng_state ^= self.rng_state >> 7;
+        self.rng_state ^= self.rng_state << 17;
+        return self.rng_state;
+    }
+
+    // Function to get a random number in the range [0, upper)
+    fn random_below(&mut self, upper: usize) -> usize {
+        return (self.next_random() % upper as u64) as usize;
+    }
+
+    // Function to introduce a random defect into the program
+    pub fn mutate(&mut self, source_code: &str) -> Option<MutatedProgram> {
+        let defect_class: DefectClass = DefectClass::ALL[self.random_below(DefectClass::ALL.len())];
+        return self.mutate_with(source_code, defect_class);
+    }
+
+    // Function to introduce a specific defect into the program, or None if the program cannot have that defect
+    pub fn mutate_with(&mut self, source_code: &str, defect_class: DefectClass) -> Option<MutatedProgram> {
+        return match defect_class {
+            DefectClass::MissingBrace => self.remove_brace(source_code),
+            DefectClass::BadToken => {
+                // The symbols that are not part of the grammar
+                let bad_tokens: [&str; 4] = ["@", "#", "%", "&"];
+                let bad_token: &str = bad_tokens[self.random_below(bad_tokens.len())];
+                self.insert_statement(source_code, bad_token, DefectClass::BadToken)
+            },
+            DefectClass::TypeMismatch => {
+                let unused_id: Option<char> = Self::get_unused_id(source_code);
+                if unused_id.is_none() {
+                    return None;
+                }
+
+                // Declare a new variable with a value of the wrong type
+                let mismatches: [&str; 3] = ["int {} = \"oops\"", "string {} = 5", "boolean {} = 3 + 4"];
+                let mismatch: String = mismatches[self.random_below(mismatches.len())].replace("{}", unused_id.unwrap().to_string().as_str());
+                self.insert_statement(source_code, &mismatch, DefectClass::TypeMismatch)
+            },
+            DefectClass::UndeclaredId => {
+                // An id that is not declared anywhere cannot be in scope
+                let unused_id: Option<char> = Self::get_unused_id(source_code);
+                if unused_id.is_none() {
+                    return None;
+                }
+                self.insert_statement(source_code, format!("print({})", unused_id.unwrap()).as_str(), DefectClass::UndeclaredId)
+            }
+        };
+    }
+
+    // Removes a random brace so the program no longer parses
+    fn remove_brace(&mut self, source_code: &str) -> Option<MutatedProgram> {
+        let brace_indices: Vec<usize> = Self::get_code_char_indices(source_code)
+            .into_iter()
+            .filter(|index| source_code.as_bytes()[*index] == b'{' || source_code.as_bytes()[*index] == b'}')
+            .collect();
+        if brace_indices.len() == 0 {
+            return None;
+        }
+
+        let remove_index: usize = brace_indices[self.random_below(brace_indices.len())];
+        let mut new_source: String = String::from(source_code);
+        let removed: char = new_source.remove(remove_index);
+
+        return Some(MutatedProgram {
+            source_code: new_source,
+            defect_class: DefectClass::MissingBrace,
+            description: format!("Removed [ {} ] at index {}", removed, remove_index)
+        });
+    }
+
+    // Inserts a new line of code at the start of a random block
+    fn insert_statement(&mut self, source_code: &str, statement: &str, defect_class: DefectClass) -> Option<MutatedProgram> {
+        // The start of every block is a safe place for a new statement
+        let block_starts: Vec<usize> = Self::get_code_char_indices(source_code)
+            .into_iter()
+            .filter(|index| source_code.as_bytes()[*index] == b'{')
+            .collect();
+        if block_starts.len() == 0 {
+            return None;
+        }
+
+        let insert_index: usize = block_starts[self.random_below(block_starts.len())] + 1;
+        let mut new_source: String = String::from(source_code);
+        new_source.insert_str(insert_index, format!(" {} ", statement).as_str());
+
+        return Some(MutatedProgram {
+            source_code: new_source,
+            defect_class: defect_class,
+            description: format!("Inserted [ {} ] at index {}", statement, insert_index)
+        });
+    }
+
+    // Gets the indices of every character that is not in a comment or string
+    fn get_code_char_indices(source_code: &str) -> Vec<usize> {
+        let mut indices: Vec<usize> = Vec::new();
+        let bytes: &[u8] = source_code.as_bytes();
+
+        let mut in_comment: bool = false;
+        let mut in_string: bool = false;
+        let mut i: usize = 0;
+        while i < bytes.len() {
+            if in_comment {
+                if bytes[i] == b'*' && i + 1 < bytes.len() && bytes[i + 1] == b'/' {
+                    in_comment = false;
+                    i += 1;
+                }
+            } else if in_string {
+                if bytes[i] == b'"' {
+                    in_string = false;
+                }
+            } else if bytes[i] == b'/' && i + 1 < bytes.len() && bytes[i + 1] == b'*' {
+                in_comment = true;
+                i += 1;
+            } else if bytes[i] == b'"' {
+                in_string = true;
+            } else {
+                indices.push(i);
+            }
+            i += 1;
+        }
+
+        return indices;
+    }
+
+    // Finds an identifier that is not declared anywhere in the program
+    fn get_unused_id(source_code: &str) -> Option<char> {
+        let code_chars: Vec<usize> = Self::get_code_char_indices(source_code);
+        let used_ids: HashSet<char> = code_chars.into_iter()
+            .map(|index| source_code.as_bytes()[index] as char)
+            .filter(|c| c.is_ascii_lowercase())
+            .collect();
+
+        // Any letter that does not appear in the code cannot be declared
+        return ('a'..='z').find(|id| !used_ids.contains(id));
+    }
+}
+
+// Function to run the mutated program through the compiler and make sure exactly one error of the expected class is reported
+// The classes come from the message codes, so a defect caught by the lexer or parser is checked the same way as one caught by semantic analysis
+pub fn check_mutated_program(mutated_program: &MutatedProgram) -> Result<(), String> {
+    nexus_log::start_capture();
+    compile_mutated_program(&mutated_program.source_code);
+    let records: Vec<LogRecord> = nexus_log::stop_capture();
+
+    // The lexer and parser end with an error that sums up the failure, which does not have a code
+    let errors: Vec<(&LogRecord, ErrorClass)> = records.iter()
+        .filter(|record| matches!(record.log_type, nexus_log::LogTypes::Error))
+        .filter_map(|record| record.code.and_then(|code| code.get_error_class()).map(|error_class| (record, error_class)))
+        .collect();
+    let expected_class: ErrorClass = mutated_program.defect_class.expected_error_class();
+
+    return match errors.as_slice() {
+        [] => Err(format!("No error was reported instead of a {} error", expected_class)),
+        [(_, error_class)] if error_class.eq(&expected_class) => Ok(()),
+        [(record, error_class)] => Err(format!("A {} error ({}) was reported instead of a {} error", error_class, record.code.unwrap(), expected_class)),
+        _ => {
+            let codes: Vec<String> = errors.iter().map(|(record, _)| record.code.unwrap().to_string()).collect();
+            Err(format!("{} errors were reported [ {} ] instead of 1 {} error", errors.len(), codes.join(", "), expected_class))
+        }
+    };
+}
+
+// Function to run the program through each phase until one of them fails
+fn compile_mutated_program(source_code: &str) {
+    let mut lexer: Lexer = Lexer::new(source_code);
+    let token_stream: Vec<Token> = match lexer.lex_program() {
+        Ok(token_stream) => token_stream,
+        Err(_) => return
+    };
+
+    let mut parser: Parser = Parser::new();
+    if parser.parse_program(&token_stream).is_err() {
+        return;
+    }
+
+    let mut semantic_analyzer: SemanticAnalyzer = SemanticAnalyzer::new(TargetConfig::new(Target::Target6502));
+    let ast: SyntaxTree = semantic_analyzer.generate_ast(&token_stream);
+    semantic_analyzer.analyze_program(ast);
+}
@@ -1,6 +1,227 @@
 // Enum for determining the target for the compiler
-#[derive (Debug)]
+#[derive (Debug, Clone, Copy, PartialEq, strum::Display)]
 pub enum Target {
+    #[strum (serialize = "6502")]
     Target6502,
+    #[strum (serialize = "RISC-V")]
     TargetRiscV
 }
+
+// The addresses of the 6502 output device, which prints whatever is stored to them
+// Homebrew simulators usually map a console like this instead of having system calls
+#[derive (Debug, Clone, Copy, PartialEq)]
+pub struct MmioConfig {
+    // Storing a byte here prints it in decimal without leading zeros
+    pub int_out: usize,
+
+    // Storing an address here prints the string that starts there and ends at 00
+    pub string_out: usize
+}
+
+// The system calls that RISC-V programs print and exit with, which depend on what runs the program
+#[derive (Debug, Clone, Copy, PartialEq, strum::Display)]
+pub enum SyscallConvention {
+    // write (64) and exit (93), like Linux and the GNU toolchain's simulator
+    #[strum (serialize = "linux")]
+    Linux,
+    // console_putchar (1) and shutdown (8) of the legacy SBI extensions for bare-metal programs
+    #[strum (serialize = "sbi")]
+    Sbi,
+    // print character (11) and exit (10) in a7, like RARS
+    #[strum (serialize = "rars")]
+    Rars,
+    // print character (11) and exit (10) in a0 with the character in a1, like venus
+    #[strum (serialize = "venus")]
+    Venus,
+    // A write and an exit with the Linux arguments, but at other numbers
+    #[strum (serialize = "custom")]
+    Custom { write: u32, exit: u32 }
+}
+
+// Target-dependent settings and limits used throughout the compiler
+#[derive (Debug, Clone)]
+pub struct TargetConfig {
+    // The target the program is being compiled for
+    pub target: Target,
+
+    // The largest value an integer can hold on the target
+    pub max_int: u32,
+
+    // The number of bytes at the end of memory that the heap cannot use
+    // 0xFF is always reserved because it is compared against as a constant 0
+    pub reserved_cells: usize,
+
+    // The first address of the heap, or None if the code and the heap share the free space
+    pub heap_split: Option<usize>,
+
+    // The most blocks that can be inside of each other before semantic analysis warns about it
+    // Every block is a new scope and the 6502 branches have to jump over each one
+    pub max_nesting_depth: usize,
+
+    // The longest string literal the class allows, or None to only be limited by the target
+    pub max_string_length: Option<usize>,
+
+    // The output device that prints are stored to, or None to print with system calls
+    // The device lives in the reserved cells so the program never uses its addresses
+    pub mmio: Option<MmioConfig>,
+
+    // The system calls that RISC-V programs use to print and exit
+    pub syscall_convention: SyscallConvention
+}
+
+impl TargetConfig {
+    // Creates the default configuration for the given target
+    pub fn new(target: Target) -> Self {
+        return match target {
+            // Integers are a single byte in memory
+            Target::Target6502 => TargetConfig {
+                target: target,
+                max_int: 0xFF,
+                reserved_cells: 1,
+                heap_split: None,
+                max_nesting_depth: 8,
+                max_string_length: None,
+                mmio: None,
+                syscall_convention: SyscallConvention::Linux
+            },
+            // Integers are stored with sb/lbu, so they are also a single byte
+            // The assembler lays out memory, so there is nothing to reserve
+            Target::TargetRiscV => TargetConfig {
+                target: target,
+                max_int: 0xFF,
+                reserved_cells: 0,
+                heap_split: None,
+                max_nesting_depth: 8,
+                max_string_length: None,
+                mmio: None,
+                syscall_convention: SyscallConvention::Linux
+            }
+        };
+    }
+
+    // Function to set the 6502 memory split from the user's input
+    // The heap split is a hex address and can be left blank to share the free space
+    pub fn set_memory_layout(&mut self, reserved_cells_input: &str, heap_split_input: &str) -> Result<(), String> {
+        let reserved_cells: usize = match reserved_cells_input.trim().parse::<usize>() {
+            Ok(num) if num >= 1 && num <= 0x80 => num,
+            _ => return Err(format!("Invalid number of reserved cells [ {} ], which must be between 1 and 128", reserved_cells_input.trim()))
+        };
+
+        // The heap has to have room for the true and false strings
+        let heap_top: usize = 0xFF - reserved_cells;
+        let heap_split_text: &str = heap_split_input.trim().trim_start_matches("0x").trim_start_matches("0X");
+        let heap_split: Option<usize> = match heap_split_text.len() {
+            0 => None,
+            _ => match usize::from_str_radix(heap_split_text, 16) {
+                Ok(addr) if addr > 0 && addr + 10 <= heap_top => Some(addr),
+                _ => return Err(format!("Invalid heap start [ {} ], which must be a hex address between 0x01 and 0x{:02X}", heap_split_input.trim(), heap_top - 10))
+            }
+        };
+
+        self.reserved_cells = reserved_cells;
+        self.heap_split = heap_split;
+        return Ok(());
+    }
+
+    // Function to set the nesting depth that gets warned about from the user's input
+    pub fn set_max_nesting_depth(&mut self, max_nesting_depth_input: &str) -> Result<(), String> {
+        self.max_nesting_depth = match max_nesting_depth_input.trim().parse::<usize>() {
+            Ok(num) if num >= 1 && num <= 64 => num,
+            _ => return Err(format!("Invalid nesting limit [ {} ], which must be between 1 and 64", max_nesting_depth_input.trim()))
+        };
+        return Ok(());
+    }
+
+    // Function to get the longest string the target can store, no matter what the class allows
+    pub fn get_string_length_limit(&self) -> usize {
+        return match self.target {
+            // The whole heap minus the 00 at the end of the string and the length byte before it
+            Target::Target6502 => (0xFF - self.reserved_cells + 1) - self.heap_split.unwrap_or(0) - 2,
+            // The length is stored with .half
+            Target::TargetRiscV => u16::MAX as usize
+        };
+    }
+
+    // Function to get the longest string literal a program can have
+    pub fn get_max_string_length(&self) -> usize {
+        return match self.max_string_length {
+            Some(max_string_length) => max_string_length.min(self.get_string_length_limit()),
+            None => self.get_string_length_limit()
+        };
+    }
+
+    // Function to set the longest string literal from the user's input, which has to come after the memory layout
+    // The input can be left blank to allow anything the target can store
+    pub fn set_max_string_length(&mut self, max_string_length_input: &str) -> Result<(), String> {
+        if max_string_length_input.trim().len() == 0 {
+            self.max_string_length = None;
+            return Ok(());
+        }
+        let limit: usize = self.get_string_length_limit();
+        self.max_string_length = match max_string_length_input.trim().parse::<usize>() {
+            Ok(num) if num >= 1 && num <= limit => Some(num),
+            _ => return Err(format!("Invalid string length limit [ {} ], which must be between 1 and {} on the {}", max_string_length_input.trim(), limit, self.target))
+        };
+        return Ok(());
+    }
+
+    // Function to set the 6502 output device from the user's input, which has to come after the memory layout
+    // Both addresses are hex and can be left blank to print with system calls
+    pub fn set_mmio(&mut self, int_out_input: &str, string_out_input: &str) -> Result<(), String> {
+        let int_out_text: &str = int_out_input.trim().trim_start_matches("0x").trim_start_matches("0X");
+        let string_out_text: &str = string_out_input.trim().trim_start_matches("0x").trim_start_matches("0X");
+        if int_out_text.len() == 0 && string_out_text.len() == 0 {
+            self.mmio = None;
+            return Ok(());
+        }
+
+        // 0xFF is the constant 0, so the device needs 2 more reserved cells below it
+        if self.reserved_cells < 3 {
+            return Err(format!("Memory-mapped output needs at least 3 reserved cells, but there are only {}", self.reserved_cells));
+        }
+        let first_reserved: usize = 0x100 - self.reserved_cells;
+        let mut addresses: Vec<usize> = Vec::new();
+        for (input, text) in [(int_out_input, int_out_text), (string_out_input, string_out_text)] {
+            match usize::from_str_radix(text, 16) {
+                Ok(addr) if addr >= first_reserved && addr < 0xFF => addresses.push(addr),
+                _ => return Err(format!("Invalid output address [ {} ], which must be a hex address between 0x{:02X} and 0xFE", input.trim(), first_reserved))
+            }
+        }
+        if addresses[0] == addresses[1] {
+            return Err(format!("The integer and string output addresses cannot both be 0x{:02X}", addresses[0]));
+        }
+
+        self.mmio = Some(MmioConfig {
+            int_out: addresses[0],
+            string_out: addresses[1]
+        });
+        return Ok(());
+    }
+
+    // Function to set the RISC-V system calls from the user's input
+    // The numbers are only used by the custom convention
+    pub fn set_syscall_convention(&mut self, convention_input: &str, write_input: &str, exit_input: &str) -> Result<(), String> {
+        self.syscall_convention = match convention_input.trim() {
+            "linux" => SyscallConvention::Linux,
+            "sbi" => SyscallConvention::Sbi,
+            "rars" => SyscallConvention::Rars,
+            "venus" => SyscallConvention::Venus,
+            "custom" => {
+                // The numbers have to fit in the immediate of a single addi so li stays one instruction
+                let mut numbers: Vec<u32> = Vec::new();
+                for input in [write_input, exit_input] {
+                    match input.trim().parse::<u32>() {
+                        Ok(num) if num <= 2047 => numbers.push(num),
+                        _ => return Err(format!("Invalid system call number [ {} ], which must be between 0 and 2047", input.trim()))
+                    }
+                }
+                if numbers[0] == numbers[1] {
+                    return Err(format!("The write and exit system calls cannot both be {}", numbers[0]));
+                }
+                SyscallConvention::Custom { write: numbers[0], exit: numbers[1] }
+            },
+            _ => return Err(format!("Invalid system call convention [ {} ], which must be linux, sbi, rars, venus, or custom", convention_input.trim()))
+        };
+        return Ok(());
+    }
+}
@@ -1,3 +1,5 @@
+use crate::util::{diagnostic::ErrorClass, program_minimizer::Failure, program_mutator::DefectClass, target::Target};
+
 // Basic struct for a test
 #[derive (Debug)]
 pub struct Test {
@@ -13,5 +15,101 @@ pub enum TestType {
     Lex,
     Parse,
     SemanticAnalysis,
-    CodeGen
+    CodeGen,
+    Editor
+}
+
+// What a test case expects the compiler to do with its code
+#[derive (Debug)]
+pub enum TestExpectation {
+    // Expanding the constants should fail with the given number of errors
+    PreprocessErrors(i32),
+    // The lexer should produce exactly these tokens
+    Tokens(Vec<&'static str>),
+    // The lexer should produce exactly these tokens, given as "text (line, col)"
+    TokenPositions(Vec<&'static str>),
+    // The lexer should fail with the given number of errors
+    LexErrors(i32),
+    // The source should be split into the given number of programs
+    ProgramCount(u32),
+    // After the code is compiled and edited to this, each program should be reused or lexed again like this, given as "Program n reused" or "Program n lexed"
+    // Reused programs also have to match lexing and parsing them again
+    CachedPrograms(&'static str, Vec<&'static str>),
+    ParseSucceeds,
+    ParseFails,
+    // The parser should fail with the given number of errors
+    ParseErrors(i32),
+    // The unbalanced symbols of the first program should be pointed out like this before it is parsed, given as "code (line, col)"
+    BalanceHints(Vec<&'static str>),
+    // Semantic analysis should report exactly these errors in order
+    SemanticErrors(Vec<ErrorClass>),
+    CodeGenSucceeds,
+    CodeGenFails,
+    // The 6502 image should print exactly this when it runs, both with and without size optimization, where every print ends with a new line
+    ProgramOutput(&'static str),
+    // The 6502 image should print exactly this to the output device at 0xFD (integers) and 0xFE (strings) without any system calls
+    MmioProgramOutput(&'static str),
+    // The 6502 code (without static variables and the heap) should take up exactly this many bytes without and with size optimization
+    CodeSize(usize, usize),
+    // With size optimization, the 6502 code (without static variables and the heap) should disassemble to exactly these instructions
+    OptimizedCode(Vec<&'static str>),
+    // The RISC-V assembly should print exactly this when it runs with every system call convention, where every print ends with a new line
+    RiscVProgramOutput(&'static str),
+    // The 6502 images of the code and of this many generated programs should be the same after being disassembled and assembled again
+    DisassemblyRoundTrip(u64),
+    // The interpreter should print the same as the 6502 image and the RISC-V assembly for the code and this many generated programs
+    InterpreterMatches(u64),
+    // With scope comments on, the RISC-V assembly should have exactly these comments and the 6502 source map these scopes, given as "scope n at line l"
+    // The assembly with the comments should still run
+    ScopeComments(Vec<&'static str>),
+    // Giving the code this defect should be reported as exactly one error of the class of the defect
+    Mutation(DefectClass),
+    // The code should have the failure and be minimized to exactly this program
+    MinimizedProgram(Failure, &'static str),
+    // The code is a string literal that should be written in the RISC-V assembly like this and read back the same
+    // Source code cannot have these characters in strings yet, so the literal is given directly
+    AssemblyString(&'static str),
+    // The code should be shown in the page as this escaped text and read back the same
+    HtmlText(&'static str),
+    // Toggling the comments of the lines (start, end) starting at 1 should give this code
+    ToggleComment(usize, usize, &'static str),
+    // The code is a workspace file that should be valid (and read back the same once exported) or fail with this error
+    Workspace(Result<(), &'static str>),
+    // The code is saved compiler options that should be valid (and read back the same once saved) or fail with this error
+    SavedOptions(Result<(), &'static str>),
+    // The code is the JSON options for an embedded compiler that should be valid or fail with this error
+    EmbedOptions(Result<(), &'static str>),
+    // The code is the query of the page's link, which should load this source, this gist link, nothing (an empty string), or fail with this error
+    LinkedSource(Result<&'static str, &'static str>),
+    // The code should be the same after being shared with the options in the JSON (the same as compilerOptions in a workspace),
+    // or the code is the fragment of a shared link that should fail with this error
+    SharedLink(&'static str, Result<(), &'static str>),
+    // The positions in the code, given as line:col and separated by spaces, should sort and be written like this
+    SortedPositions(Vec<&'static str>),
+    // The identifiers of every program should be classified like this in order, given as "id (line, col) kind"
+    SemanticTokens(Vec<&'static str>),
+    // Compiling every program should report exactly these message codes in order
+    MessageCodes(Vec<&'static str>),
+    // Compiling every program for the target with the string length limit input should report exactly these message codes in order, or the error of the limit
+    StringLengthCodes(Target, &'static str, Vec<&'static str>),
+    // With the translations in this JSON catalog, compiling every program should report exactly these messages in order
+    TranslatedMessages(&'static str, Vec<&'static str>),
+    // The structure of the code should be this similar to the other source, as a whole percent
+    Similarity(&'static str, u32),
+    // Grading the code with this JSON rubric should give this score and feedback for each failed check, or fail with this rubric error
+    Grade(&'static str, &'static str),
+    // Applying the first quick fix of compiling the code should give this code
+    QuickFix(&'static str),
+    // With this language profile and identifier characters, such as "a-z0-9_", the interpreter should print exactly this for every program or fail with this error
+    // Programs that fail are given as "Program n failed at stage with [ codes ]"
+    ProfileOutput(&'static str, &'static str, &'static str)
+}
+
+// A test with an expected result that can be run in the browser
+#[derive (Debug)]
+pub struct TestCase {
+    pub test_type: TestType,
+    pub test_name: String,
+    pub test_code: String,
+    pub expectation: TestExpectation
 }